@@ -14,21 +14,19 @@ use std::path::Path;
 #[exclude = "README.md"]
 pub struct AgentAssets;
 
+/// Largest markdown file the server accepts for an `include!`.
+pub const MAX_MARKDOWN_BYTES: usize = 16 * 1024;
+
 /// Source for agent files - either embedded defaults or a custom directory.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum AgentSource {
     /// Use embedded default files.
+    #[default]
     Embedded,
     /// Use files from a custom directory path.
     Custom(String),
 }
 
-impl Default for AgentSource {
-    fn default() -> Self {
-        Self::Embedded
-    }
-}
-
 /// Validation result for a file.
 #[derive(Debug, Clone)]
 pub enum FileStatus {
@@ -40,6 +38,15 @@ pub enum FileStatus {
     RequiredMissing,
 }
 
+/// A problem in an agent file, found before compiling.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileIssue {
+    pub file: &'static str,
+    pub message: String,
+    /// The server would reject the file, so compiling is pointless
+    pub blocking: bool,
+}
+
 /// Validation result for an agent source.
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
@@ -47,12 +54,25 @@ pub struct ValidationResult {
     pub soul_md: FileStatus,
     pub skill_md: FileStatus,
     pub heartbeat_md: FileStatus,
+    /// Lint findings for the markdown files that are present
+    pub issues: Vec<FileIssue>,
 }
 
 impl ValidationResult {
-    /// Check if the source is valid (all required files present).
+    /// Check if the source is valid (required files present, nothing over a server limit).
     pub fn is_valid(&self) -> bool {
-        !matches!(self.ship_file, FileStatus::RequiredMissing)
+        self.problem().is_none()
+    }
+
+    /// Why the source can't be used, if it can't.
+    pub fn problem(&self) -> Option<String> {
+        if matches!(self.ship_file, FileStatus::RequiredMissing) {
+            return Some("moltbook_agent.ship is required".to_string());
+        }
+        self.issues
+            .iter()
+            .find(|i| i.blocking)
+            .map(|i| format!("{}: {}", i.file, i.message))
     }
 }
 
@@ -92,12 +112,100 @@ impl AgentSource {
             }
         };
 
+        let issues = ["SOUL.md", "SKILL.md", "HEARTBEAT.md"]
+            .into_iter()
+            .filter_map(|name| self.read_file(name).map(|content| lint_markdown(name, &content)))
+            .flatten()
+            .collect();
+
         ValidationResult {
             ship_file: check_file("moltbook_agent.ship", true),
             soul_md: check_file("SOUL.md", false),
             skill_md: check_file("SKILL.md", false),
             heartbeat_md: check_file("HEARTBEAT.md", false),
+            issues,
         }
     }
 
 }
+
+/// Check an included markdown file for what the server rejects (size) and
+/// what makes a poor agent: no content, headings with nothing under them, and
+/// `{{placeholders}}` left over from a template.
+pub fn lint_markdown(file: &'static str, content: &str) -> Vec<FileIssue> {
+    let issue = |message: String, blocking: bool| FileIssue { file, message, blocking };
+    let mut issues = Vec::new();
+
+    if content.len() > MAX_MARKDOWN_BYTES {
+        issues.push(issue(
+            format!("{} KB, over the {} KB limit", content.len().div_ceil(1024), MAX_MARKDOWN_BYTES / 1024),
+            true,
+        ));
+    }
+    if content.trim().is_empty() {
+        issues.push(issue("file is empty".to_string(), false));
+        return issues;
+    }
+
+    // Headings outside code blocks, as (line index, level, title)
+    let mut in_code = false;
+    let mut headings = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if !in_code && line.starts_with('#') {
+            let level = line.chars().take_while(|c| *c == '#').count();
+            headings.push((i, level, line[level..].trim()));
+        }
+    }
+    for (n, (start, level, title)) in headings.iter().enumerate() {
+        // A section runs to the next heading at the same level or above
+        let end = headings[n + 1..]
+            .iter()
+            .find(|(_, l, _)| l <= level)
+            .map_or(lines.len(), |(i, _, _)| *i);
+        if lines[start + 1..end].iter().all(|l| l.trim().is_empty()) {
+            issues.push(issue(format!("section \"{}\" is empty", title), false));
+        }
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let mut rest = *line;
+        while let Some(open) = rest.find("{{") {
+            let Some(close) = rest[open..].find("}}") else {
+                break;
+            };
+            let placeholder = &rest[open..open + close + 2];
+            issues.push(issue(format!("line {}: unfilled placeholder {}", i + 1, placeholder), false));
+            rest = &rest[open + close + 2..];
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_files_are_clean() {
+        let validation = AgentSource::Embedded.validate();
+        assert!(validation.is_valid());
+        assert_eq!(validation.issues, vec![]);
+    }
+
+    #[test]
+    fn test_lint_markdown() {
+        let content = "# Soul\n\nYou are {{agent name}}.\n\n## Personality\n\n## Rules\n### Posting\nBe kind.\n```\n# not a heading\n```\n";
+        let messages: Vec<String> = lint_markdown("SOUL.md", content).into_iter().map(|i| i.message).collect();
+        assert_eq!(messages, vec![
+            "section \"Personality\" is empty".to_string(),
+            "line 3: unfilled placeholder {{agent name}}".to_string(),
+        ]);
+
+        let issues = lint_markdown("SKILL.md", &"x".repeat(MAX_MARKDOWN_BYTES + 1));
+        assert!(issues[0].blocking);
+        assert!(lint_markdown("HEARTBEAT.md", " \n").iter().any(|i| i.message == "file is empty"));
+    }
+}
@@ -0,0 +1,144 @@
+//! Engagement figures of the agent's Moltbook posts: how often it posts,
+//! how well the posts do, and in which submolts they do best.
+//!
+//! Totals and posts per day come from the gateway's post stats when it has
+//! them; the submolt breakdown is always worked out from the loaded posts.
+
+use crate::client::{MoltbookPost, PostStats};
+use std::collections::BTreeMap;
+
+/// Days of posting history asked for and charted.
+pub const DAYS: u32 = 14;
+
+/// Submolts charted, best first.
+const MAX_SUBMOLTS: usize = 5;
+
+/// How the agent's posts did in one submolt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmoltStats {
+    pub name: String,
+    pub posts: u32,
+    pub average_upvotes: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analytics {
+    /// All posts when the stats are known, else the loaded ones
+    pub posts: u32,
+    pub average_upvotes: Option<f64>,
+    /// Average comments per post, when the stats are known
+    pub average_comments: Option<f64>,
+    /// Posts per day, oldest first
+    pub per_day: Vec<u64>,
+    /// First and last day of `per_day`
+    pub span: Option<(String, String)>,
+    /// Best average upvotes first
+    pub submolts: Vec<SubmoltStats>,
+    /// Loaded posts the submolt figures come from
+    pub sampled: usize,
+    /// The figures come from the gateway's stats, not only the loaded posts
+    pub from_stats: bool,
+}
+
+impl Analytics {
+    pub fn new(posts: &[MoltbookPost], stats: Option<&PostStats>) -> Self {
+        let average = |sum: u64, count: u32| (count > 0).then(|| sum as f64 / count as f64);
+
+        let (total, average_upvotes, average_comments, days) = match stats {
+            Some(stats) => {
+                let days: Vec<(String, u64)> = stats.daily.iter().map(|d| (d.date.clone(), d.posts as u64)).collect();
+                (
+                    stats.total_posts,
+                    average(stats.total_upvotes, stats.total_posts),
+                    average(stats.total_comments, stats.total_posts),
+                    days,
+                )
+            }
+            None => {
+                // Only days with a loaded post: the best a page of posts can tell
+                let mut by_day: BTreeMap<String, u64> = BTreeMap::new();
+                for post in posts {
+                    *by_day.entry(post.created_at.chars().take(10).collect()).or_default() += 1;
+                }
+                let skip = by_day.len().saturating_sub(DAYS as usize);
+                let upvotes = posts.iter().map(|p| p.upvotes as u64).sum();
+                (posts.len() as u32, average(upvotes, posts.len() as u32), None, by_day.into_iter().skip(skip).collect())
+            }
+        };
+
+        let mut by_submolt: BTreeMap<&str, (u32, u64)> = BTreeMap::new();
+        for post in posts {
+            let name = post.submolt.as_ref().map(|s| s.name.as_str()).unwrap_or("general");
+            let entry = by_submolt.entry(name).or_default();
+            entry.0 += 1;
+            entry.1 += post.upvotes as u64;
+        }
+        let mut submolts: Vec<SubmoltStats> = by_submolt
+            .into_iter()
+            .map(|(name, (count, upvotes))| SubmoltStats {
+                name: name.to_string(),
+                posts: count,
+                average_upvotes: upvotes as f64 / count as f64,
+            })
+            .collect();
+        submolts.sort_by(|a, b| b.average_upvotes.total_cmp(&a.average_upvotes).then(b.posts.cmp(&a.posts)));
+        submolts.truncate(MAX_SUBMOLTS);
+
+        Self {
+            posts: total,
+            average_upvotes,
+            average_comments,
+            span: days.first().zip(days.last()).map(|(first, last)| (first.0.clone(), last.0.clone())),
+            per_day: days.into_iter().map(|(_, count)| count).collect(),
+            submolts,
+            sampled: posts.len(),
+            from_stats: stats.is_some(),
+        }
+    }
+
+    /// Posts per day over the charted days.
+    pub fn posts_per_day(&self) -> Option<f64> {
+        (!self.per_day.is_empty()).then(|| self.per_day.iter().sum::<u64>() as f64 / self.per_day.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(submolt: &str, created_at: &str, upvotes: u32) -> MoltbookPost {
+        serde_json::from_value(serde_json::json!({
+            "id": format!("{}-{}", submolt, created_at),
+            "submolt": { "name": submolt },
+            "created_at": created_at,
+            "upvotes": upvotes,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_analytics() {
+        let posts = vec![
+            post("crabs", "2026-01-03T10:00:00Z", 9),
+            post("general", "2026-01-03T08:00:00Z", 2),
+            post("general", "2026-01-01T12:00:00Z", 4),
+        ];
+        let loaded = Analytics::new(&posts, None);
+        assert_eq!((loaded.posts, loaded.average_upvotes), (3, Some(5.0)));
+        assert_eq!(loaded.per_day, [1, 2]);
+        assert_eq!(loaded.span, Some(("2026-01-01".to_string(), "2026-01-03".to_string())));
+        assert_eq!(loaded.submolts.iter().map(|s| (s.name.as_str(), s.posts)).collect::<Vec<_>>(), [("crabs", 1), ("general", 2)]);
+        assert!(!loaded.from_stats);
+
+        let stats: PostStats = serde_json::from_value(serde_json::json!({
+            "total_posts": 40, "total_upvotes": 100, "total_comments": 20,
+            "daily": [{ "date": "2026-01-01", "posts": 3 }, { "date": "2026-01-02", "posts": 0 }, { "date": "2026-01-03", "posts": 3 }],
+        }))
+        .unwrap();
+        let full = Analytics::new(&posts, Some(&stats));
+        assert_eq!((full.posts, full.average_upvotes, full.average_comments), (40, Some(2.5), Some(0.5)));
+        assert_eq!(full.per_day, [3, 0, 3]);
+        assert_eq!(full.posts_per_day(), Some(2.0));
+        assert_eq!(full.sampled, 3);
+    }
+}
@@ -2,19 +2,63 @@
 
 use crate::{
     auth,
-    client::ApiClient,
-    config::AppConfig,
+    balance_stream::BalanceStream,
+    banner::{self, Banner, Encoded},
+    client::{ApiClient, BalanceResponse, FaucetResponse},
+    config::{AppConfig, KeyPreset, DEFAULT_SERVER_URL},
+    funds::Funds,
+    health::FailureAlert,
+    heartbeat::Heartbeat,
+    keymap::{self, VimKey},
+    notifications::{Level, Notifications},
+    schedule,
     screens::{
-        create::CreateScreen, home::HomeScreen, prompt::PromptScreen, view::ViewScreen, Screen,
+        backup::{BackupDialog, BackupKey},
+        compose::{ComposeMessage, ComposeScreen, ComposeStep},
+        create::{CreateDraft, CreateMessage, CreateScreen, CreateStep},
+        error_modal::ErrorModal,
+        feed::{FeedMessage, FeedScreen},
+        home::HomeScreen,
+        inbox::{InboxMessage, InboxScreen},
+        input::TextInput,
+        inspect::{InspectMessage, InspectScreen},
+        leaderboard::{LeaderboardMessage, LeaderboardScreen},
+        lock::LockScreen,
+        onboarding::OnboardingScreen,
+        palette::{self, Command, CommandPalette},
+        prompt::{PromptMessage, PromptScreen, PromptStep},
+        runs::RunsScreen,
+        search::SearchScreen,
+        settings::SettingsScreen,
+        submolts::{SubmoltsMessage, SubmoltsScreen},
+        view::{ViewMessage, ViewScreen},
+        Screen,
     },
+    text::truncate_width,
+    theme::Theme,
     wallet::WalletConfig,
+    watcher::AgentDirWatcher,
+    webhook::{self, WebhookEvent},
 };
 use anyhow::Result;
-use crossterm::event::KeyCode;
-use ratatui::{layout::Rect, Frame};
-use ratatui_image::{picker::Picker, protocol::StatefulProtocol};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    Frame,
+};
+use ratatui_image::picker::Picker;
+use std::path::PathBuf;
 use tokio::sync::mpsc;
 
+/// How long quitting waits for deployments, runs and transfers to finish.
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Activity events fetched to find the latest heartbeat and its run.
+const HEARTBEAT_ACTIVITY_LIMIT: u32 = 20;
+
+/// Runs fetched to tell whether the scheduled ones keep failing.
+const RUN_HISTORY_LIMIT: u32 = 20;
+
 /// Messages for async operations.
 #[derive(Debug, Clone)]
 pub enum AppMessage {
@@ -22,74 +66,188 @@ pub enum AppMessage {
     AuthCompleted(String),
     /// Auth failed
     AuthFailed(String),
+    /// Auth callback listener is up (address it bound, URL the browser returns to)
+    AuthListening { bound: String, url: String },
     /// Wallet funded
     WalletFunded,
     /// Wallet funding failed
     WalletFundFailed(String),
     /// Balance updated
-    BalanceUpdated(String),
-    /// Moltbook registered (from direct TUI call to Moltbook API)
-    MoltbookRegistered { api_key: String, claim_url: String, verification_code: String },
-    /// Moltbook registration failed (any error)
-    RegistrationFailed(String),
-    /// Agent name already taken - need to choose different name
-    NameTaken(String),
-    /// Existing API key validated - got agent info
-    ApiKeyValidated { api_key: String, name: String, description: String, is_claimed: bool },
-    /// API key validation failed
-    ApiKeyInvalid(String),
-    /// Ready to store agent with existing API key (skip registration)
-    ApiKeyReadyToStore { api_key: String, name: String },
-    /// Moltbook claimed - agent stored on server
-    MoltbookClaimed { agent_id: String },
-    /// Compilation done
-    CompileDone { compiled_hex: String },
-    /// Compilation failed
-    CompileFailed(String),
-    /// Deployment done
-    DeployDone { agent_address: String },
-    /// Deployment failed
-    DeployFailed(String),
-    /// Prompt submitted, now streaming
-    PromptSubmitted { run_id: u64 },
-    /// Structured chain event from agent run
-    ChainEvent(crate::client::ChainEventData),
-    /// Status message (non-structured feedback)
-    PromptStatus(String),
-    /// Agent run completed
-    RunCompleted { result: String },
-    /// Prompt failed
-    PromptFailed(String),
-    /// Agent info fetched
-    AgentInfoFetched { info: crate::client::AgentInfo },
-    /// Agent posts fetched
-    PostsFetched { posts: Vec<crate::client::MoltbookPost> },
-    /// Fetch failed
-    FetchFailed(String),
+    BalanceUpdated(BalanceResponse),
+    /// Test tokens were asked for from a screen without the wallet at hand
+    RequestTestTokens,
+    /// The faucet sent tokens; they show up once the transaction is in a block
+    TestTokensSent(FaucetResponse),
+    TestTokensFailed(String),
+    /// Gateway answered the chain head poll with this block number
+    ChainHead(u64),
+    /// Gateway could not be reached by the chain head poll
+    ConnectionLost(String),
+    /// Heartbeat schedule of an agent, from its chain info and activity
+    HeartbeatFetched { agent_address: String, heartbeat: Option<Heartbeat> },
+    /// Run history of an agent checked for scheduled runs failing in a row
+    RunFailuresFetched { agent_address: String, alert: Option<FailureAlert> },
+    /// Price feed answered with the token's USD price
+    PriceUpdated(f64),
+    /// Balance stream connected (true) or dropped, so polling takes over (false)
+    BalanceStreamLive(bool),
+    /// A newer release is published (its version)
+    UpdateAvailable(String),
+    /// Banner image decoded in the background (None when it can't be)
+    BannerLoaded(Option<Banner>),
+    /// Banner frames encoded for an area size
+    BannerEncoded(Encoded),
     /// User's agent data restored from server
     AgentDataRestored { name: String, chain_address: String },
-    /// Agent source selected (embedded or custom dir)
-    AgentSourceSelected { custom_dir: Option<String> },
+    /// A scheduled prompt came due and was submitted as this run
+    ScheduledPromptSubmitted { prompt: String, run_id: u64 },
+    /// A scheduled prompt came due but could not be submitted
+    ScheduledPromptFailed { prompt: String, error: String },
+    /// An encrypted backup was written here, or could not be
+    BackupExported(Result<PathBuf, String>),
     /// Error occurred
     Error(String),
+    /// Results for the create wizard
+    Create(CreateMessage),
+    /// Results for the prompt screen
+    Prompt(PromptMessage),
+    /// Results for the agent page and its panels
+    View(ViewMessage),
+    /// Results for the post composer
+    Compose(ComposeMessage),
+    /// Results for the feed
+    Feed(FeedMessage),
+    /// Results for the submolt list
+    Submolts(SubmoltsMessage),
+    /// Results for the inbox
+    Inbox(InboxMessage),
+    /// Results for the leaderboard
+    Leaderboard(LeaderboardMessage),
+    /// Storage read for the on-chain state inspector
+    Inspect(InspectMessage),
+}
+
+macro_rules! screen_messages {
+    ($($variant:ident($message:ty)),* $(,)?) => {
+        $(impl From<$message> for AppMessage {
+            fn from(msg: $message) -> Self {
+                AppMessage::$variant(msg)
+            }
+        })*
+    };
+}
+
+screen_messages!(
+    Create(CreateMessage),
+    Prompt(PromptMessage),
+    View(ViewMessage),
+    Compose(ComposeMessage),
+    Feed(FeedMessage),
+    Submolts(SubmoltsMessage),
+    Inbox(InboxMessage),
+    Leaderboard(LeaderboardMessage),
+    Inspect(InspectMessage),
+);
+
+/// What a screen's message handler may use besides its own state.
+pub struct MessageContext<'a> {
+    pub config: &'a mut AppConfig,
+    pub client: &'a ApiClient,
+    pub wallet: Option<&'a WalletConfig>,
+    pub notifications: &'a mut Notifications,
+    /// Balances as last known, for checks before signing
+    pub funds: Funds,
+    pub tx: mpsc::Sender<AppMessage>,
+    /// The handling screen is the one on screen
+    pub showing: bool,
+    /// The terminal has focus
+    pub focused: bool,
+    /// Set by the handler to refetch the wallet balance afterwards
+    pub refresh_balance: bool,
+}
+
+impl MessageContext<'_> {
+    /// Toast a background result, or only record it when its screen is showing it.
+    pub fn notify(&mut self, level: Level, message: impl Into<String>) {
+        if self.showing {
+            self.notifications.record(level, message);
+        } else {
+            self.notifications.push(level, message);
+        }
+    }
+
+    /// Raise a desktop notification too when the user isn't looking: the
+    /// terminal lost focus, or the result is for a screen in the background.
+    pub fn desktop_notify(&self, summary: &str, body: &str) {
+        if self.config.desktop_notifications && !(self.focused && self.showing) {
+            crate::desktop::notify(summary, body);
+        }
+    }
+
+    pub fn save_config(&self) {
+        if let Err(e) = self.config.save() {
+            tracing::warn!("Failed to save config: {:#}", e);
+        }
+    }
 }
 
 /// Application screen state.
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppScreen {
     Home,
+    Onboarding,  // First-run walkthrough
     EmailInput,  // Email entry for magic link
     Auth,        // Waiting for auth callback
     Create,
     Prompt,
     View,
+    Compose,
+    Feed,
+    Submolts,
+    Inbox,
+    Leaderboard,
+    Runs,
+    Inspect,
+    Search,
+    Settings,
 }
 
 /// Action returned from screen handlers.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScreenAction {
     None,
+    /// Back to the menu, forgetting the way here
     GoHome,
+    /// Open a screen on top of this one; Esc there comes back
+    Push(AppScreen),
+    /// Return to the screen below
+    Pop,
+    /// Open the prompt screen with this text pre-filled
+    PromptAgent(String),
+    /// Open the prompt screen for a dry run of the local agent files
+    SimulateAgent,
+    /// Settings were validated and written to config.toml
+    SettingsSaved,
+    /// The screen changed the config; write config.toml
+    ConfigChanged,
+}
+
+/// Test tokens asked for on a test network, until the wallet balance grows.
+#[derive(Debug, Clone)]
+pub struct FaucetRequest {
+    /// Wallet balance in planck when the request was made
+    pub before: u128,
+    /// Faucet transfer, once the faucet has answered
+    pub tx_hash: Option<String>,
+}
+
+/// Gateway reachability as last seen by the chain head poll.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Connection {
+    /// No poll has finished yet
+    Unknown,
+    Online,
+    Offline(String),
 }
 
 /// Main application state.
@@ -99,76 +257,262 @@ pub struct App {
     pub client: ApiClient,
     pub agent_dir: String,
     pub screen: AppScreen,
+    /// Screens below the current one, returned to one at a time by Esc
+    pub back_stack: Vec<AppScreen>,
     pub quit: bool,
 
     // Screen states
+    #[allow(dead_code)]
     pub home: HomeScreen,
     pub create: CreateScreen,
     pub prompt: PromptScreen,
     pub view: ViewScreen,
+    pub compose: ComposeScreen,
+    pub feed: FeedScreen,
+    pub submolts: SubmoltsScreen,
+    pub inbox: InboxScreen,
+    pub leaderboard: LeaderboardScreen,
+    pub runs: RunsScreen,
+    pub inspect: InspectScreen,
+    pub search: SearchScreen,
+    pub settings: SettingsScreen,
+    pub onboarding: OnboardingScreen,
 
     // Transient state
     pub status_message: Option<String>,
     pub error_message: Option<String>,
     
     // Email input for magic link auth
-    pub email_input: String,
+    pub email_input: TextInput,
+
+    // Auth callback listener (bound address, callback URL) while waiting for login
+    pub auth_callback: Option<(String, String)>,
     
     // Wallet balance (formatted string)
-    pub wallet_balance: Option<String>,
+    pub wallet_balance: Option<BalanceResponse>,
+
+    // Home banner image (lobster art or user-supplied, possibly animated)
+    pub banner: Option<Banner>,
+
+    // Graphics capabilities for the banner
+    banner_picker: Option<Picker>,
+
+    // Image path and animation the banner was decoded with (None when disabled)
+    banner_source: Option<(Option<String>, bool)>,
+
+    // Banner image decoding in the background
+    pub banner_loading: bool,
+
+    // Color palette resolved from config
+    pub theme: Theme,
+
+    // Draw with ASCII stand-ins for Unicode glyphs
+    pub ascii: bool,
+
+    // Polling intervals changed in settings; the main loop rebuilds its timers
+    pub intervals_changed: bool,
+
+    // Terminal has focus (terminals that don't report focus always count as focused)
+    pub focused: bool,
+    // The current low-balance warning was already raised on the desktop
+    low_balance_notified: bool,
+    // The agent account's low balance was already sent to webhooks
+    low_agent_hooked: bool,
+
+    // Key binding help overlay is shown on top of the current screen
+    pub help_open: bool,
+
+    // First `g` of a vim `gg`, waiting for the second
+    vim_pending_g: bool,
+
+    // Animation frame counter, advanced by the main loop while something animates
+    pub tick: u64,
+
+    // Gateway reachability and latest block, from the chain head poll
+    pub connection: Connection,
+    pub block_number: Option<u64>,
 
-    // Image state for lobster banner
-    pub lobster_image: Option<StatefulProtocol>,
+    // Heartbeat schedule of the agent it was fetched for, shown on the home screen
+    pub heartbeat: Option<(String, Heartbeat)>,
+    // Scheduled runs of that agent failing in a row, alerted on the home screen
+    pub run_failures: Option<(String, FailureAlert)>,
+
+    // Wallet balance arrives from the gateway's stream; polling is paused
+    pub balance_pushed: bool,
+    balance_stream: Option<BalanceStream>,
+
+    // Token price in USD from the configured price feed
+    pub usd_price: Option<f64>,
+
+    // Faucet request whose tokens haven't reached the wallet yet
+    pub faucet: Option<FaucetRequest>,
+
+    // Newer release found by the startup update check
+    pub update_available: Option<String>,
+
+    // Results of background work, shown as toasts and kept for the history panel
+    pub notifications: Notifications,
+    pub history_open: bool,
+
+    // Quit was pressed while operations were running; waiting for confirmation
+    pub quit_confirm: bool,
+
+    // Full text of an error, opened with `!`
+    pub error_modal: Option<ErrorModal>,
+    // Passphrase dialog of "Export encrypted backup"
+    pub backup_dialog: Option<BackupDialog>,
+    // Idle lock, covering everything until unlocked
+    pub lock: Option<LockScreen>,
+    // Last key press, for the idle lock
+    pub last_input: std::time::Instant,
+
+    // Command palette, opened with Ctrl+P
+    pub palette: Option<CommandPalette>,
+
+    // Live reload of the custom agent directory on the create screen
+    agent_watcher: Option<AgentDirWatcher>,
 }
 
 impl App {
-    pub async fn new(server_url: String, agent_dir: String) -> Result<Self> {
+    pub async fn new(server_url: Option<String>, agent_dir: String, mock: bool) -> Result<Self> {
         // Load or create config
-        let mut config = AppConfig::load().unwrap_or_default();
-        config.server_url = server_url.clone();
+        let mut config = AppConfig::load()?;
+        // --server wins, then the saved URL, then the local development server
+        let server_url = server_url
+            .or_else(|| Some(config.server_url.clone()).filter(|url| !url.is_empty()))
+            .unwrap_or_else(|| DEFAULT_SERVER_URL.to_string());
+        config.switch_server(server_url.clone());
 
         // Create API client
-        let mut client = ApiClient::new(server_url);
+        let client = if mock { ApiClient::mock() } else { ApiClient::new(server_url) };
+        let mut client = client.with_moltbook_url(config.moltbook_api_url());
         if let Some(token) = &config.auth_token {
             client.set_auth_token(token.clone());
         }
 
         // Only load wallet if user is authenticated (wallet is created after first auth)
         let wallet = if config.auth_token.is_some() {
-            WalletConfig::load()?
+            WalletConfig::load(&config)?
         } else {
             None
         };
 
-        // Try to load the lobster image
-        let lobster_image = Self::load_lobster_image(&agent_dir);
+        let mut app = Self::with_config(config, client, wallet, agent_dir);
+        // Without a PIN the lock needs this run's passphrase, which a config
+        // without encrypt_secrets never asks for
+        if app.config.idle_lock_timeout().is_some() && !crate::secrets::can_unlock(app.config.lock_pin.as_deref()) {
+            app.notifications.push(Level::Error, "Idle lock is off: set a lock PIN in Settings to turn it on");
+        }
+
+        // The banner image is decoded on the first home render; only the
+        // terminal query happens now, before the event loop reads stdin
+        // (terminals without Unicode get the ASCII lobster)
+        if app.config.any_banner_enabled() && !app.ascii {
+            app.banner_picker = Some(Self::query_picker());
+        }
+
+        Ok(app)
+    }
+
+    /// App in its startup state for `config`, without touching the terminal:
+    /// the banner image, which needs a graphics query, is left unset.
+    pub fn with_config(config: AppConfig, client: ApiClient, wallet: Option<WalletConfig>, agent_dir: String) -> Self {
+        let ascii = crate::glyphs::use_ascii(config.glyphs);
+        let theme = Theme::from_config(&config);
 
         // Extract custom_agent_dir before moving config
         let custom_agent_dir = config.custom_agent_dir.clone();
+        // A fresh config gets the walkthrough instead of the bare menu
+        let screen = if config.onboarded { AppScreen::Home } else { AppScreen::Onboarding };
 
-        Ok(Self {
+        Self {
             config,
             wallet,
             client,
             agent_dir,
-            screen: AppScreen::Home,
+            screen,
+            back_stack: Vec::new(),
             quit: false,
             home: HomeScreen::new(),
             create: CreateScreen::new_with_config(custom_agent_dir),
             prompt: PromptScreen::new(),
             view: ViewScreen::new(),
+            compose: ComposeScreen::new(),
+            feed: FeedScreen::new(),
+            submolts: SubmoltsScreen::new(),
+            inbox: InboxScreen::new(),
+            leaderboard: LeaderboardScreen::new(),
+            runs: RunsScreen::new(),
+            inspect: InspectScreen::new(),
+            search: SearchScreen::new(),
+            settings: SettingsScreen::new(),
+            onboarding: OnboardingScreen::new(),
             status_message: None,
             error_message: None,
-            email_input: String::new(),
+            email_input: TextInput::new(),
+            auth_callback: None,
             wallet_balance: None,
-            lobster_image,
-        })
+            banner: None,
+            banner_picker: None,
+            banner_source: None,
+            banner_loading: false,
+            theme,
+            ascii,
+            intervals_changed: false,
+            focused: true,
+            low_balance_notified: false,
+            low_agent_hooked: false,
+            help_open: false,
+            vim_pending_g: false,
+            tick: 0,
+            connection: Connection::Unknown,
+            block_number: None,
+            heartbeat: None,
+            run_failures: None,
+            balance_pushed: false,
+            balance_stream: None,
+            usd_price: None,
+            faucet: None,
+            update_available: None,
+            notifications: Notifications::default(),
+            history_open: false,
+            quit_confirm: false,
+            error_modal: None,
+            backup_dialog: None,
+            lock: None,
+            last_input: std::time::Instant::now(),
+            palette: None,
+            agent_watcher: None,
+        }
     }
     
+    /// Take up the active network profile's login, wallet and agent, which
+    /// change when settings or the palette switch networks.
+    fn load_profile(&mut self) {
+        match &self.config.auth_token {
+            Some(token) => self.client.set_auth_token(token.clone()),
+            None => self.client.clear_auth_token(),
+        }
+        let wallet = if self.config.is_authenticated() {
+            WalletConfig::load(&self.config).unwrap_or_else(|e| {
+                self.notifications.push(Level::Error, format!("Could not load the wallet: {:#}", e));
+                None
+            })
+        } else {
+            None
+        };
+        if wallet.as_ref().map(|w| &w.public_key) != self.wallet.as_ref().map(|w| &w.public_key) {
+            self.wallet_balance = None;
+            self.low_balance_notified = false;
+            self.faucet = None;
+        }
+        self.wallet = wallet;
+    }
+
     /// Ensure wallet exists (create if needed). Called after successful authentication.
     pub fn ensure_wallet(&mut self) -> Result<()> {
         if self.wallet.is_none() {
-            let wallet = WalletConfig::load_or_generate()?;
+            let wallet = WalletConfig::load_or_generate(&self.config)?;
             self.wallet = Some(wallet);
         }
         Ok(())
@@ -215,14 +559,49 @@ impl App {
         self.config.is_authenticated() && self.config.agent_address.is_some()
     }
 
-    /// Initialize the app after creation - validates persisted session and fetches balance.
+    /// Heartbeat schedule of the current agent, once fetched.
+    pub fn heartbeat(&self) -> Option<&Heartbeat> {
+        let address = self.agent_address()?;
+        self.heartbeat.as_ref().filter(|(a, _)| a == address).map(|(_, h)| h)
+    }
+
+    /// Scheduled runs of the current agent failing in a row, if they do.
+    pub fn failure_alert(&self) -> Option<&FailureAlert> {
+        let address = self.agent_address()?;
+        self.run_failures.as_ref().filter(|(a, _)| a == address).map(|(_, f)| f)
+    }
+
+    /// Initialize the app after creation - polls the chain head, validates persisted session and fetches balance.
     /// This should be called once after App::new() with the message sender.
     pub fn init_session(&self, tx: mpsc::Sender<AppMessage>) {
+        self.refresh_chain_head(tx.clone());
+        self.refresh_heartbeat(tx.clone());
+        self.refresh_run_failures(tx.clone());
+        self.refresh_price(tx.clone());
+        if self.config.check_updates {
+            let tx = tx.clone();
+            crate::tasks::spawn(async move {
+                // Best effort: offline or rate limited just means no notice
+                match crate::update::newer_version().await {
+                    Ok(Some(version)) => {
+                        let _ = tx.send(AppMessage::UpdateAvailable(version)).await;
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::info!("Update check failed: {:#}", e),
+                }
+            });
+        }
+        self.validate_session(tx);
+    }
+
+    /// Check the saved token against the gateway and fetch the balance, or
+    /// clear the login when the gateway rejects it.
+    fn validate_session(&self, tx: mpsc::Sender<AppMessage>) {
         if self.config.auth_token.is_some() {
             // We have a persisted token - validate it and fetch balance
             let client = self.client.clone();
-            let wallet_address = self.wallet.as_ref().map(|w| w.public_key.clone());
-            tokio::spawn(async move {
+            let wallet_address = self.wallet_address().map(str::to_string);
+            crate::tasks::spawn(async move {
                 // Try to get user info to validate the token
                 match client.get_me().await {
                     Ok(_) => {
@@ -230,16 +609,18 @@ impl App {
                         if let Some(addr) = wallet_address {
                             match client.get_balance(&addr).await {
                                 Ok(resp) => {
-                                    let _ = tx.send(AppMessage::BalanceUpdated(resp.balance_formatted)).await;
+                                    let _ = tx.send(AppMessage::BalanceUpdated(resp)).await;
                                 }
-                                Err(_) => {
+                                Err(e) => {
                                     // Balance fetch failed but session is valid
+                                    tracing::warn!("Balance fetch failed: {:#}", e);
                                 }
                             }
                         }
                     }
-                    Err(_) => {
+                    Err(e) => {
                         // Token is invalid/expired - notify to clear it
+                        tracing::info!("Saved session rejected: {:#}", e);
                         let _ = tx.send(AppMessage::AuthFailed("Session expired. Please login again.".to_string())).await;
                     }
                 }
@@ -247,57 +628,119 @@ impl App {
         }
     }
 
-    fn load_lobster_image(agent_dir: &str) -> Option<StatefulProtocol> {
+    fn query_picker() -> Picker {
         // Query terminal for graphics capabilities and font size
         // This automatically detects: Kitty, iTerm2, Sixel, or falls back to halfblocks
         // Note: Must be called AFTER entering alternate screen but BEFORE event loop
-        let picker = match Picker::from_query_stdio() {
+        match Picker::from_query_stdio() {
             Ok(p) => p,
-            Err(_) => {
+            Err(e) => {
+                tracing::debug!("Terminal graphics query failed: {}", e);
                 // Fallback: use halfblocks with estimated font size
                 // This works on ALL terminals but doesn't support transparency
                 Picker::from_fontsize((8, 16))
             }
-        };
-        
-        // Try multiple possible paths for the image
-        let possible_paths = [
-            format!("{}/pol.png", agent_dir),
-            "pol.png".to_string(),
-            "app/pol.png".to_string(),
-        ];
-        
-        for path in &possible_paths {
-            if let Ok(reader) = image::ImageReader::open(path) {
-                if let Ok(dyn_img) = reader.decode() {
-                    return Some(picker.new_resize_protocol(dyn_img));
+        }
+    }
+
+    /// Decode the banner the first time home is drawn and again when a
+    /// network switch changes it, and encode it for a size it was drawn at
+    /// without one, both off the UI thread (called from main loop after each
+    /// draw).
+    pub fn prepare_banner(&mut self, tx: mpsc::Sender<AppMessage>) {
+        if self.screen != AppScreen::Home || self.config.banner_hidden {
+            return;
+        }
+        let wanted = self.config.banner_enabled().then(|| (self.config.banner_path(), self.config.banner_animated()));
+        if self.banner_source != wanted && !self.banner_loading {
+            self.banner = None;
+            self.banner_source = wanted.clone();
+            let Some(((path, animate), picker)) = wanted.zip(self.banner_picker) else { return };
+            self.banner_loading = true;
+            crate::tasks::spawn(async move {
+                let banner = tokio::task::spawn_blocking(move || Banner::load(&picker, path.as_deref(), animate))
+                    .await
+                    .unwrap_or_default();
+                let _ = tx.send(AppMessage::BannerLoaded(banner)).await;
+            });
+        } else if let Some(job) = self.banner.as_mut().and_then(|b| b.encode_job()) {
+            crate::tasks::spawn(async move {
+                if let Ok(encoded) = tokio::task::spawn_blocking(job).await {
+                    let _ = tx.send(AppMessage::BannerEncoded(encoded)).await;
                 }
-            }
+            });
+        }
+    }
+
+    /// Banner bytes served to the auth callback pages (None when disabled).
+    fn auth_banner(&self) -> Option<(Vec<u8>, &'static str)> {
+        if self.config.banner_enabled() {
+            banner::read_bytes(self.config.banner_path().as_deref())
+        } else {
+            None
         }
-        
-        None
     }
 
     pub fn render(&mut self, frame: &mut Frame) {
-        let area = frame.area();
+        if self.lock.is_some() {
+            crate::screens::lock::render_lock(frame, frame.area(), self);
+            return;
+        }
+        let warning = self.funds().warning();
+        let [area, warning_area, status_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(warning.is_some() as u16), Constraint::Length(1)])
+                .areas(frame.area());
+        crate::screens::status_bar::render_status_bar(frame, status_area, self);
+        if let Some(warning) = warning {
+            crate::screens::status_bar::render_warning(frame, warning_area, &warning, &self.theme);
+        }
 
         match self.screen {
             AppScreen::Home => {
                 // Use the special render function that handles the image
                 crate::screens::home::render_home_with_image(frame, area, self);
             }
+            AppScreen::Onboarding => self.onboarding.render(frame, area, self),
             AppScreen::EmailInput => self.render_email_input(frame, area),
             AppScreen::Auth => self.render_auth(frame, area),
             AppScreen::Create => self.create.render(frame, area, self),
             AppScreen::Prompt => self.prompt.render(frame, area, self),
             AppScreen::View => self.view.render(frame, area, self),
+            AppScreen::Compose => self.compose.render(frame, area, self),
+            AppScreen::Feed => self.feed.render(frame, area, self),
+            AppScreen::Submolts => self.submolts.render(frame, area, self),
+            AppScreen::Inbox => self.inbox.render(frame, area, self),
+            AppScreen::Leaderboard => self.leaderboard.render(frame, area, self),
+            AppScreen::Runs => self.runs.render(frame, area, self),
+            AppScreen::Inspect => self.inspect.render(frame, area, self),
+            AppScreen::Search => self.search.render(frame, area, self),
+            AppScreen::Settings => self.settings.render(frame, area, self),
+        }
+
+        crate::screens::notifications::render_toasts(frame, area, self);
+        if self.history_open {
+            crate::screens::notifications::render_history(frame, area, self);
+        }
+        if self.help_open {
+            crate::screens::help::render_help_overlay(frame, area, self);
+        }
+        crate::screens::error_modal::render_error_modal(frame, area, self);
+        crate::screens::backup::render_backup_dialog(frame, area, self);
+        palette::render_palette(frame, area, self);
+        if self.quit_confirm {
+            crate::screens::quit_confirm::render_quit_confirm(frame, area, self);
+        }
+
+        if self.ascii {
+            crate::glyphs::asciify(frame.buffer_mut());
         }
     }
 
     fn render_email_input(&self, frame: &mut Frame, area: Rect) {
+        let theme = &self.theme;
         use ratatui::{
             layout::{Alignment, Constraint, Direction, Layout},
-            style::{Color, Modifier, Style},
+            style::{Modifier, Style},
             text::{Line, Span},
             widgets::{Block, Borders, Paragraph},
         };
@@ -316,29 +759,28 @@ impl App {
 
         // Title
         let title = Paragraph::new(Line::from(vec![
-            Span::styled(" LOGIN ", Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
-            Span::styled("│ ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Email Magic Link", Style::default().fg(Color::LightRed)),
+            Span::styled(" LOGIN ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled("Email Magic Link", Style::default().fg(theme.accent)),
         ]))
         .alignment(Alignment::Center)
         .block(Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::DarkGray)));
+            .border_style(Style::default().fg(theme.muted)));
         frame.render_widget(title, chunks[0]);
 
         // Instructions
         let instructions = Paragraph::new("Enter your email address to receive a magic link:")
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(theme.text));
         frame.render_widget(instructions, chunks[1]);
 
         // Email input
-        let cursor = if self.email_input.is_empty() { "│" } else { "" };
-        let input = Paragraph::new(format!("{}{}", self.email_input, cursor))
-            .style(Style::default().fg(Color::Cyan))
+        let width = chunks[2].width.saturating_sub(2) as usize;
+        let input = Paragraph::new(self.email_input.line(true, width, Style::default().fg(theme.info)))
             .block(Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(Span::styled(" Email ", Style::default().fg(Color::White))));
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(" Email ", Style::default().fg(theme.text))));
         frame.render_widget(input, chunks[2]);
 
         // Help text
@@ -346,30 +788,31 @@ impl App {
             Line::from(""),
             Line::from(Span::styled(
                 "A magic link will be sent to your email.",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )),
             Line::from(Span::styled(
                 "Click the link to complete authentication.",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )),
         ]);
         frame.render_widget(help, chunks[3]);
 
         // Footer
         let footer = Paragraph::new(Line::from(vec![
-            Span::styled("[Enter] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Send", Style::default().fg(Color::DarkGray)),
-            Span::styled("  [Esc] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Cancel", Style::default().fg(Color::DarkGray)),
+            Span::styled("[Enter] ", Style::default().fg(theme.muted)),
+            Span::styled("Send", Style::default().fg(theme.muted)),
+            Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+            Span::styled("Cancel", Style::default().fg(theme.muted)),
         ]))
         .alignment(Alignment::Center);
         frame.render_widget(footer, chunks[4]);
     }
 
     fn render_auth(&self, frame: &mut Frame, area: Rect) {
+        let theme = &self.theme;
         use ratatui::{
             layout::{Alignment, Constraint, Direction, Layout},
-            style::{Color, Modifier, Style},
+            style::{Modifier, Style},
             text::{Line, Span},
             widgets::{Block, Borders, Paragraph},
         };
@@ -385,114 +828,498 @@ impl App {
             .split(area);
 
         let title = Paragraph::new(Line::from(vec![
-            Span::styled(" LOGIN ", Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
-            Span::styled("│ ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Waiting for authentication...", Style::default().fg(Color::Yellow)),
+            Span::styled(" LOGIN ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled("Waiting for authentication...", Style::default().fg(theme.warning)),
         ]))
         .alignment(Alignment::Center)
         .block(Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::DarkGray)));
+            .border_style(Style::default().fg(theme.muted)));
         frame.render_widget(title, chunks[0]);
 
-        let message = Paragraph::new(vec![
+        let mut lines = vec![
             Line::from(""),
-            Line::from(Span::styled("⏳ Check your email for the magic link", Style::default().fg(Color::Yellow))),
+            Line::from(Span::styled(
+                format!("{} Check your email for the magic link", self.spinner()),
+                Style::default().fg(theme.warning),
+            )),
             Line::from(""),
-            Line::from(Span::styled("Click the link in your email to authenticate.", Style::default().fg(Color::White))),
-            Line::from(Span::styled("This screen will update automatically when complete.", Style::default().fg(Color::DarkGray))),
-        ])
+            Line::from(Span::styled("Click the link in your email to authenticate.", Style::default().fg(theme.text))),
+            Line::from(Span::styled("This screen will update automatically when complete.", Style::default().fg(theme.muted))),
+        ];
+        // Remote sessions need to forward the callback port to the browser's machine
+        if let Some((bound, url)) = &self.auth_callback {
+            let port = bound.rsplit(':').next().unwrap_or_default();
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("Callback: ", Style::default().fg(theme.muted)),
+                Span::styled(url.as_str(), Style::default().fg(theme.info)),
+                Span::styled(format!(" (listening on {})", bound), Style::default().fg(theme.muted)),
+            ]));
+            lines.push(Line::from(Span::styled(
+                format!("On a remote machine? Forward it first: ssh -L {0}:localhost:{0} <host>", port),
+                Style::default().fg(theme.muted),
+            )));
+        }
+
+        let message = Paragraph::new(lines)
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).title("Login"));
         frame.render_widget(message, chunks[1]);
     }
 
-    pub async fn handle_key(&mut self, key: KeyCode, tx: mpsc::Sender<AppMessage>) -> Result<()> {
+    pub async fn handle_key(&mut self, event: KeyEvent, tx: mpsc::Sender<AppMessage>) -> Result<()> {
+        let key = event.code;
+        // Background work goes on while locked; only the keys are held back
+        if let Some(lock) = &mut self.lock {
+            if lock.handle_key(event, self.config.lock_pin.as_deref()) {
+                self.lock = None;
+            }
+            return Ok(());
+        }
+        // Only an explicit yes quits; anything else keeps the app running
+        if self.quit_confirm {
+            self.quit_confirm = false;
+            self.quit = matches!(key, KeyCode::Char('y') | KeyCode::Char('Y'));
+            return Ok(());
+        }
+        if let Some(modal) = &mut self.error_modal {
+            if modal.handle_key(key) {
+                self.error_modal = None;
+            }
+            return Ok(());
+        }
+        if let Some(dialog) = &mut self.backup_dialog {
+            match dialog.handle_key(event) {
+                BackupKey::None => {}
+                BackupKey::Close => self.backup_dialog = None,
+                BackupKey::Export(passphrase) => Self::export_backup(passphrase, tx),
+            }
+            return Ok(());
+        }
+        if self.palette.is_some() {
+            let matches = self.palette.as_ref().map(|p| p.matches(palette::available_commands(self))).unwrap_or_default();
+            let Some(palette) = self.palette.as_mut() else {
+                return Ok(());
+            };
+            let (command, keep_open) = palette.handle_key(event, &matches);
+            if !keep_open {
+                self.palette = None;
+            }
+            if let Some(command) = command {
+                self.run_command(command, tx).await?;
+            }
+            return Ok(());
+        }
+        // Any key dismisses an overlay without reaching the screen
+        if self.help_open || self.history_open {
+            self.help_open = false;
+            self.history_open = false;
+            return Ok(());
+        }
+        if key == KeyCode::F(1) || (key == KeyCode::Char('?') && !self.is_typing()) {
+            self.help_open = true;
+            return Ok(());
+        }
+        if key == KeyCode::Char('N') && !self.is_typing() {
+            self.notifications.dismiss_toasts();
+            self.history_open = true;
+            return Ok(());
+        }
+        if key == KeyCode::Char('!') && !self.is_typing() {
+            self.open_error_modal();
+            return Ok(());
+        }
+
+        let key = if self.config.keybindings.preset == KeyPreset::Vim && !self.is_typing() {
+            match keymap::vim_key(key, self.has_list(), &mut self.vim_pending_g) {
+                VimKey::Key(key) => key,
+                VimKey::Pending => return Ok(()),
+                VimKey::Palette => {
+                    self.open_palette();
+                    return Ok(());
+                }
+                // The search screen's own `/` edits the query
+                VimKey::Search if self.screen == AppScreen::Search => KeyCode::Char('/'),
+                VimKey::Search => return self.run_command(Command::SearchRuns, tx).await,
+            }
+        } else {
+            key
+        };
+        // What the screens see: the key after the preset, with the modifiers held
+        let event = KeyEvent::new(key, event.modifiers);
+
         // Clear error message on any key
         self.error_message = None;
 
         match self.screen {
             AppScreen::Home => self.handle_home_key(key, tx).await,
-            AppScreen::EmailInput => self.handle_email_input_key(key, tx).await,
+            AppScreen::Onboarding => {
+                let action = self.onboarding.handle_key(key, &mut self.config, self.wallet.is_some())?;
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::EmailInput => self.handle_email_input_key(event, tx).await,
             AppScreen::Auth => self.handle_auth_key(key),
             AppScreen::Create => {
-                let action = self.create.handle_key(key, &self.client, &self.agent_dir, tx).await?;
+                let funds = self.funds();
+                let action = self.create.handle_key(event, &self.client, &self.agent_dir, &funds, self.wallet.as_ref(), tx).await?;
                 self.handle_screen_action(action);
                 Ok(())
             }
             AppScreen::Prompt => {
-                let action = self.prompt.handle_key(key, &self.config, &self.client, self.wallet.as_ref(), tx).await?;
+                let funds = self.funds();
+                let action = self.prompt.handle_key(event, &mut self.config, &self.client, self.wallet.as_ref(), &funds, tx).await?;
                 self.handle_screen_action(action);
                 Ok(())
             }
             AppScreen::View => {
-                let agent_addr = self.agent_address().map(|s| s.to_string());
-                let action = self.view.handle_key(key, &self.client, agent_addr.as_deref(), tx)?;
+                let action = self.view.handle_key(event, &self.config, &self.client, &self.funds(), self.wallet.as_ref(), tx.clone())?;
+                if action == ScreenAction::Push(AppScreen::Inspect) {
+                    // The inspector reads the agent's storage as it opens
+                    return self.run_command(Command::InspectAgent, tx).await;
+                }
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::Compose => {
+                let api_key = self.config.moltbook_api_key.clone();
+                let action = self.compose.handle_key(event, &self.client, api_key.as_deref(), tx)?;
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::Feed => {
+                let api_key = self.config.moltbook_api_key.clone();
+                let action = self.feed.handle_key(key, &self.client, api_key.as_deref(), tx)?;
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::Submolts => {
+                let api_key = self.config.moltbook_api_key.clone();
+                let action = self.submolts.handle_key(key, &self.client, api_key.as_deref(), tx)?;
                 self.handle_screen_action(action);
                 Ok(())
             }
+            AppScreen::Inbox => {
+                let api_key = self.config.moltbook_api_key.clone();
+                let action = self.inbox.handle_key(key, &self.client, api_key.as_deref(), tx)?;
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::Leaderboard => {
+                let agent_address = self.agent_address().map(str::to_string);
+                let action = self.leaderboard.handle_key(key, &self.client, agent_address.as_deref(), tx)?;
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::Runs => {
+                let action = self.runs.handle_key(key)?;
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::Inspect => {
+                let action = self.inspect.handle_key(key, &self.client, tx)?;
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::Search => {
+                let action = self.search.handle_key(event)?;
+                self.handle_screen_action(action);
+                Ok(())
+            }
+            AppScreen::Settings => {
+                let (server, agent) = (self.config.server_url.clone(), self.config.agent_address.clone());
+                let action = self.settings.handle_key(event, &mut self.config)?;
+                self.handle_screen_action(action);
+                if self.config.server_url != server {
+                    self.server_switched(agent, tx);
+                }
+                Ok(())
+            }
         }
     }
 
     fn handle_screen_action(&mut self, action: ScreenAction) {
         match action {
             ScreenAction::None => {}
-            ScreenAction::GoHome => {
-                self.screen = AppScreen::Home;
+            ScreenAction::GoHome => self.go_home(),
+            ScreenAction::Push(screen) => {
+                // Screens opened from elsewhere start fresh
+                match screen {
+                    AppScreen::EmailInput => self.email_input.clear(),
+                    AppScreen::Create => {
+                        self.create.reset();
+                        if let Some(draft) = CreateDraft::take(self.config.encrypt_secrets) {
+                            self.notifications.push(Level::Info, format!("Resuming {} where you left off", draft.agent_name));
+                            self.create.resume(draft);
+                        }
+                    }
+                    _ => {}
+                }
+                self.push_screen(screen);
+            }
+            ScreenAction::Pop => self.pop_screen(),
+            ScreenAction::PromptAgent(text) => {
+                self.prompt.reset();
+                self.prompt.input_buffer.set_text(text);
+                self.push_screen(AppScreen::Prompt);
+            }
+            ScreenAction::SimulateAgent => {
+                self.prompt.reset();
+                self.prompt.simulate = true;
+                self.push_screen(AppScreen::Prompt);
             }
+            ScreenAction::SettingsSaved => self.apply_settings(),
+            ScreenAction::ConfigChanged => self.save_config_logged(),
+        }
+    }
+
+    /// Show `screen`, keeping the current one to come back to.
+    fn push_screen(&mut self, screen: AppScreen) {
+        if screen == self.screen {
+            return;
+        }
+        tracing::debug!(from = ?self.screen, to = ?screen, "push screen");
+        // Already open further down: unwind to it rather than growing the stack
+        if let Some(depth) = self.back_stack.iter().position(|s| *s == screen) {
+            self.back_stack.truncate(depth);
+        } else {
+            self.back_stack.push(self.screen.clone());
         }
+        self.screen = screen;
+    }
+
+    /// Go back one screen, or home when there is nothing below.
+    fn pop_screen(&mut self) {
+        self.screen = self.back_stack.pop().unwrap_or_else(|| self.home_screen());
+        tracing::debug!(to = ?self.screen, "pop screen");
+    }
+
+    fn go_home(&mut self) {
+        self.back_stack.clear();
+        self.screen = self.home_screen();
+        tracing::debug!(to = ?self.screen, "go home");
+    }
+
+    /// Save config.toml where a failure should not interrupt the flow; it is logged instead.
+    fn save_config_logged(&self) {
+        if let Err(e) = self.config.save() {
+            tracing::warn!("Failed to save config: {:#}", e);
+        }
+    }
+
+    /// Where "back to home" leads: the walkthrough until it is finished or skipped.
+    fn home_screen(&self) -> AppScreen {
+        if self.config.onboarded {
+            AppScreen::Home
+        } else {
+            AppScreen::Onboarding
+        }
+    }
+
+    /// Catch up with a server switched to from Settings or the palette:
+    /// check the session against the new gateway and reload Home.
+    fn server_switched(&mut self, agent_before: Option<String>, tx: mpsc::Sender<AppMessage>) {
+        // Agent screens would go on showing the other network's agent
+        if self.config.agent_address != agent_before {
+            self.go_home();
+        }
+        self.connection = Connection::Unknown;
+        self.block_number = None;
+        self.refresh_chain_head(tx.clone());
+        self.refresh_heartbeat(tx.clone());
+        self.refresh_run_failures(tx.clone());
+        self.validate_session(tx);
+    }
+
+    /// Pick up settings that were just saved without a restart.
+    fn apply_settings(&mut self) {
+        if !self.client.is_mock() && self.client.base_url() != self.config.server_url {
+            self.client = ApiClient::new(self.config.server_url.clone());
+            self.faucet = None;
+        }
+        if !self.client.is_mock() && self.client.moltbook_url() != self.config.moltbook_api_url().trim_end_matches('/') {
+            self.client = self.client.clone().with_moltbook_url(self.config.moltbook_api_url());
+        }
+        self.load_profile();
+        self.create = CreateScreen::new_with_config(self.config.custom_agent_dir.clone());
+        self.theme = Theme::from_config(&self.config);
+        self.ascii = crate::glyphs::use_ascii(self.config.glyphs);
+        // The price of a feed that was switched off or replaced is stale
+        self.usd_price = None;
+        self.intervals_changed = true;
     }
 
     async fn handle_home_key(&mut self, key: KeyCode, tx: mpsc::Sender<AppMessage>) -> Result<()> {
-        match key {
-            KeyCode::Char('1') => {
-                if !self.config.is_authenticated() {
-                    // Navigate to email input screen
-                    self.email_input.clear();
-                    self.screen = AppScreen::EmailInput;
-                } else {
-                    self.screen = AppScreen::Create;
-                    self.create.reset();
-                }
+        let authenticated = self.config.is_authenticated();
+        let command = match key {
+            KeyCode::Char('1') if !authenticated => Command::Login,
+            KeyCode::Char('1') => Command::CreateAgent,
+            KeyCode::Char('2') if !authenticated => {
+                // Twitter login - not yet implemented
+                self.status_message = Some("Twitter login coming soon! Use email login for now.".to_string());
+                return Ok(());
             }
-            KeyCode::Char('2') => {
-                if !self.config.is_authenticated() {
-                    // Twitter login - not yet implemented
-                    self.status_message = Some("Twitter login coming soon! Use email login for now.".to_string());
-                } else if self.config.has_agent() {
-                    self.screen = AppScreen::Prompt;
-                    self.prompt.reset();
-                }
+            KeyCode::Char('2') if self.config.has_agent() => Command::PromptAgent,
+            KeyCode::Char('3') if self.has_agent() => Command::ViewAgent,
+            KeyCode::Char('f') | KeyCode::Char('F') if self.failure_alert().is_some() => Command::OpenFailingRun,
+            KeyCode::Char('5') if self.has_agent() => Command::NewPost,
+            KeyCode::Char('6') if self.has_agent() => Command::Feed,
+            KeyCode::Char('7') if self.has_agent() => Command::Submolts,
+            KeyCode::Char('0') if self.has_agent() => Command::Inbox,
+            KeyCode::Char('l') | KeyCode::Char('L') if authenticated => Command::Leaderboard,
+            KeyCode::Char('d') | KeyCode::Char('D') => Command::CompareRuns,
+            KeyCode::Char('/') => Command::SearchRuns,
+            KeyCode::Char('8') => Command::Settings,
+            KeyCode::Char('9') if self.wallet_address().is_some() => Command::RequestTestTokens,
+            KeyCode::Char('4') if authenticated => Command::Logout,
+            KeyCode::Char('b') | KeyCode::Char('B') => {
+                self.config.banner_hidden = !self.config.banner_hidden;
+                self.save_config_logged();
+                return Ok(());
             }
-            KeyCode::Char('3') if self.config.is_authenticated() && self.config.has_agent() => {
-                self.screen = AppScreen::View;
+            _ => return Ok(()),
+        };
+        self.run_command(command, tx).await
+    }
+
+    /// Run an app-wide action from the home menu or the command palette.
+    pub async fn run_command(&mut self, command: Command, tx: mpsc::Sender<AppMessage>) -> Result<()> {
+        match command {
+            Command::Home => self.go_home(),
+            Command::Login => self.handle_screen_action(ScreenAction::Push(AppScreen::EmailInput)),
+            Command::CreateAgent => self.handle_screen_action(ScreenAction::Push(AppScreen::Create)),
+            Command::PromptAgent => {
+                self.push_screen(AppScreen::Prompt);
+                self.prompt.reset();
+            }
+            Command::SimulateAgent => self.handle_screen_action(ScreenAction::SimulateAgent),
+            Command::OpenFailingRun => self.open_failing_run(tx),
+            Command::ViewAgent => {
+                self.push_screen(AppScreen::View);
                 self.view.reset();
                 // Start fetching data immediately (only if authenticated)
                 if let Some(addr) = self.agent_address() {
                     self.view.start_fetch(self.client.clone(), addr.to_string(), tx.clone());
                 }
             }
-            KeyCode::Char('4') if self.config.is_authenticated() => {
+            Command::NewPost | Command::Feed | Command::Submolts | Command::Inbox if self.config.moltbook_api_key.is_none() => {
+                self.error_message = Some("No Moltbook API key stored for this agent".to_string());
+            }
+            Command::NewPost => {
+                self.push_screen(AppScreen::Compose);
+                self.compose.reset();
+            }
+            Command::Feed => {
+                self.push_screen(AppScreen::Feed);
+                self.feed.reset();
+                self.feed.refresh(&self.client, self.config.moltbook_api_key.as_deref(), tx.clone());
+            }
+            Command::Submolts => {
+                self.push_screen(AppScreen::Submolts);
+                self.submolts.reset();
+                self.submolts.refresh(&self.client, self.config.moltbook_api_key.as_deref(), tx.clone());
+            }
+            Command::Inbox => {
+                self.push_screen(AppScreen::Inbox);
+                self.inbox.reset();
+                self.inbox.refresh(&self.client, self.config.moltbook_api_key.as_deref(), tx.clone());
+            }
+            Command::Leaderboard => {
+                self.push_screen(AppScreen::Leaderboard);
+                self.leaderboard.reset();
+                let agent_address = self.agent_address().map(str::to_string);
+                self.leaderboard.refresh(&self.client, agent_address.as_deref(), tx.clone());
+            }
+            Command::SearchRuns => {
+                self.push_screen(AppScreen::Search);
+                self.search.reset();
+            }
+            Command::CompareRuns => {
+                self.push_screen(AppScreen::Runs);
+                let agent_address = self.agent_address().map(str::to_string);
+                self.runs.reset(agent_address.as_deref());
+            }
+            Command::InspectAgent => {
+                if let Some(agent_address) = self.agent_address().map(str::to_string) {
+                    self.push_screen(AppScreen::Inspect);
+                    self.inspect.reset();
+                    self.inspect.refresh(&self.client, &agent_address, tx.clone());
+                }
+            }
+            Command::Settings => {
+                self.settings.reset(&self.config);
+                self.push_screen(AppScreen::Settings);
+            }
+            Command::Logout => {
                 self.config.logout();
                 self.config.save()?;
                 self.client.clear_auth_token();
+                self.go_home();
             }
-            _ => {}
+            Command::RequestTestTokens => self.request_test_tokens(tx),
+            Command::CopyWalletAddress => {
+                if let Some(address) = self.wallet_address().map(str::to_string) {
+                    self.copy_to_clipboard("Wallet address", &address);
+                }
+            }
+            Command::CopyAgentAddress => {
+                if let Some(address) = self.agent_address().map(str::to_string) {
+                    self.copy_to_clipboard("Agent address", &address);
+                }
+            }
+            Command::OpenExplorer => {
+                let link = self.agent_address().and_then(|addr| self.config.explorer_link(addr));
+                if let Some(url) = link {
+                    if let Err(e) = open::that(&url) {
+                        self.notifications.push(Level::Error, format!("Could not open browser: {}", e));
+                    }
+                }
+            }
+            Command::Notifications => {
+                self.notifications.dismiss_toasts();
+                self.history_open = true;
+            }
+            Command::ErrorDetails => self.open_error_modal(),
+            Command::ExportBackup => self.backup_dialog = Some(BackupDialog::new()),
+            Command::Help => self.help_open = true,
+            Command::SwitchNetwork(name) => {
+                if let Some(network) = self.config.network_profiles().into_iter().find(|n| n.name == name) {
+                    let agent = self.config.agent_address.clone();
+                    self.config.switch_server(network.server_url);
+                    self.config.save()?;
+                    self.apply_settings();
+                    self.server_switched(agent, tx);
+                    self.notifications.push(Level::Info, format!("Switched to {}", name));
+                }
+            }
+            Command::Quit => self.request_quit(),
         }
         Ok(())
     }
 
-    async fn handle_email_input_key(&mut self, key: KeyCode, tx: mpsc::Sender<AppMessage>) -> Result<()> {
+    fn copy_to_clipboard(&mut self, what: &str, text: &str) {
+        match crate::clipboard::copy(text) {
+            Ok(()) => self.notifications.push(Level::Success, format!("{} copied", what)),
+            Err(e) => self.notifications.push(Level::Error, format!("Copy failed: {}", e)),
+        }
+    }
+
+    /// Open the command palette over the current screen.
+    pub fn open_palette(&mut self) {
+        self.help_open = false;
+        self.history_open = false;
+        self.palette = Some(CommandPalette::new());
+    }
+
+    async fn handle_email_input_key(&mut self, event: KeyEvent, tx: mpsc::Sender<AppMessage>) -> Result<()> {
+        let key = event.code;
         match key {
-            KeyCode::Char(c) => {
-                self.email_input.push(c);
-            }
-            KeyCode::Backspace => {
-                self.email_input.pop();
-            }
+            _ if self.email_input.handle_key(event) => {}
             KeyCode::Enter if !self.email_input.is_empty() => {
                 // Validate email format (basic check)
-                if self.email_input.contains('@') && self.email_input.contains('.') {
+                if self.email_input.text().contains('@') && self.email_input.text().contains('.') {
                     self.start_email_auth(tx).await?;
                 } else {
                     self.error_message = Some("Please enter a valid email address".to_string());
@@ -500,7 +1327,7 @@ impl App {
             }
             KeyCode::Esc => {
                 self.email_input.clear();
-                self.screen = AppScreen::Home;
+                self.pop_screen();
             }
             _ => {}
         }
@@ -509,7 +1336,7 @@ impl App {
 
     fn handle_auth_key(&mut self, key: KeyCode) -> Result<()> {
         if key == KeyCode::Esc {
-            self.screen = AppScreen::Home;
+            self.pop_screen();
         }
         Ok(())
     }
@@ -518,19 +1345,8 @@ impl App {
         self.screen = AppScreen::Auth;
         self.status_message = Some("Sending magic link...".to_string());
 
-        let server_url = self.config.server_url.clone();
-        let email = self.email_input.clone();
-        
-        tokio::spawn(async move {
-            match auth::run_oauth_flow(&server_url, auth::AuthMethod::Email(email)).await {
-                Ok(token) => {
-                    let _ = tx.send(AppMessage::AuthCompleted(token)).await;
-                }
-                Err(e) => {
-                    let _ = tx.send(AppMessage::AuthFailed(e.to_string())).await;
-                }
-            }
-        });
+        let email = self.email_input.text().to_string();
+        self.spawn_auth_flow(auth::AuthMethod::Email(email), tx);
 
         Ok(())
     }
@@ -539,34 +1355,85 @@ impl App {
     async fn start_twitter_auth(&mut self, tx: mpsc::Sender<AppMessage>) -> Result<()> {
         self.screen = AppScreen::Auth;
         self.status_message = Some("Opening browser for Twitter login...".to_string());
+        self.spawn_auth_flow(auth::AuthMethod::Twitter, tx);
 
+        Ok(())
+    }
+
+    /// Bind the callback listener and run the auth flow in the background.
+    fn spawn_auth_flow(&mut self, method: auth::AuthMethod, tx: mpsc::Sender<AppMessage>) {
+        self.auth_callback = None;
         let server_url = self.config.server_url.clone();
-        tokio::spawn(async move {
-            match auth::run_oauth_flow(&server_url, auth::AuthMethod::Twitter).await {
+        let listener_config = self.config.auth_callback.clone();
+        let pages = self.config.auth_pages.clone();
+        let banner = self.auth_banner();
+        let mock = self.client.is_mock();
+
+        crate::tasks::spawn(async move {
+            if mock {
+                // No gateway to sign in with; any token will do
+                let _ = tx.send(AppMessage::AuthCompleted("mock-token".to_string())).await;
+                return;
+            }
+            let result = async {
+                let listener = listener_config.bind().await?;
+                let addr = listener.local_addr()?;
+                let _ = tx
+                    .send(AppMessage::AuthListening {
+                        bound: addr.to_string(),
+                        url: auth::callback_url(addr.port()),
+                    })
+                    .await;
+                auth::run_oauth_flow(&server_url, method, listener, pages, banner).await
+            }
+            .await;
+
+            match result {
                 Ok(token) => {
                     let _ = tx.send(AppMessage::AuthCompleted(token)).await;
                 }
                 Err(e) => {
-                    let _ = tx.send(AppMessage::AuthFailed(e.to_string())).await;
+                    let _ = tx.send(AppMessage::AuthFailed(format!("{:#}", e))).await;
                 }
             }
         });
-
-        Ok(())
     }
 
     pub async fn handle_message(&mut self, msg: AppMessage, tx: mpsc::Sender<AppMessage>) -> Result<()> {
+        // Hand a screen's message to its handler, then carry out what it asks of the app
+        macro_rules! route {
+            ($app:ident, $tx:ident, $screen:ident, $field:ident, $msg:ident) => {{
+                let funds = $app.funds();
+                let mut ctx = MessageContext {
+                    config: &mut $app.config,
+                    client: &$app.client,
+                    wallet: $app.wallet.as_ref(),
+                    notifications: &mut $app.notifications,
+                    funds,
+                    tx: $tx.clone(),
+                    showing: $app.screen == AppScreen::$screen,
+                    focused: $app.focused,
+                    refresh_balance: false,
+                };
+                let action = $app.$field.handle_message($msg, &mut ctx)?;
+                if ctx.refresh_balance {
+                    $app.fetch_balance($tx.clone());
+                }
+                $app.handle_screen_action(action);
+            }};
+        }
+
         match msg {
             AppMessage::AuthCompleted(token) => {
                 self.config.auth_token = Some(token.clone());
                 self.config.save()?;
                 self.client.set_auth_token(token);
-                self.screen = AppScreen::Home;
-                self.status_message = Some("Logged in! Setting up wallet...".to_string());
+                self.go_home();
+                self.notifications.push(Level::Info, "Logged in! Setting up wallet...");
                 
                 // Create wallet if it doesn't exist (first-time auth)
                 if let Err(e) = self.ensure_wallet() {
-                    self.error_message = Some(format!("Failed to create wallet: {}", e));
+                    self.notifications.push(Level::Error, format!("Failed to create wallet: {}", e));
                     return Ok(());
                 }
                 
@@ -574,7 +1441,7 @@ impl App {
                 let client = self.client.clone();
                 let wallet_address = self.wallet.as_ref().map(|w| w.public_key.clone()).unwrap_or_default();
                 let tx_clone = tx.clone();
-                tokio::spawn(async move {
+                crate::tasks::spawn(async move {
                     match client.get_me().await {
                         Ok(me) if !me.has_wallet => {
                             // Wallet not funded yet, fund it
@@ -583,7 +1450,7 @@ impl App {
                                     let _ = tx_clone.send(AppMessage::WalletFunded).await;
                                 }
                                 Err(e) => {
-                                    let _ = tx_clone.send(AppMessage::WalletFundFailed(e.to_string())).await;
+                                    let _ = tx_clone.send(AppMessage::WalletFundFailed(format!("{:#}", e))).await;
                                 }
                             }
                         }
@@ -592,153 +1459,234 @@ impl App {
                             let _ = tx_clone.send(AppMessage::WalletFunded).await;
                         }
                         Err(e) => {
-                            let _ = tx_clone.send(AppMessage::Error(format!("Failed to check wallet: {}", e))).await;
+                            let _ = tx_clone.send(AppMessage::Error(format!("Failed to check wallet: {:#}", e))).await;
                         }
                     }
                 });
             }
+            AppMessage::AuthListening { bound, url } => {
+                self.auth_callback = Some((bound, url));
+            }
             AppMessage::AuthFailed(e) => {
-                self.screen = AppScreen::Home;
-                self.error_message = Some(format!("Auth failed: {}", e));
+                self.go_home();
+                self.notifications.push(Level::Error, format!("Auth failed: {}", e));
                 // Clear invalid token
                 self.config.auth_token = None;
                 self.client.clear_auth_token();
-                let _ = self.config.save();
+                self.save_config_logged();
             }
             AppMessage::WalletFunded => {
-                self.status_message = Some("Logged in! Wallet ready.".to_string());
+                self.notifications.push(Level::Success, "Wallet ready");
                 // Fetch balance
                 self.fetch_balance(tx.clone());
                 // Also fetch user's agents to restore any existing agent data
                 self.fetch_user_agents(tx.clone());
             }
             AppMessage::WalletFundFailed(e) => {
-                self.error_message = Some(format!("Wallet funding failed: {}. You may need more tokens to deploy.", e));
+                self.notifications.push(
+                    Level::Error,
+                    format!("Wallet funding failed: {}. You may need more tokens to deploy.", e),
+                );
                 // Still try to fetch balance
                 self.fetch_balance(tx.clone());
             }
-            AppMessage::BalanceUpdated(balance) => {
-                self.wallet_balance = Some(balance);
+            AppMessage::UpdateAvailable(version) => {
+                self.update_available = Some(version);
             }
-            AppMessage::MoltbookRegistered { api_key, claim_url, verification_code } => {
-                self.create.handle_moltbook_registered(api_key, claim_url, verification_code);
-            }
-            AppMessage::RegistrationFailed(msg) => {
-                // Go back to agent info form with error
-                self.create.handle_registration_failed(&msg);
+            AppMessage::BannerLoaded(banner) => {
+                if banner.is_none() {
+                    tracing::warn!("Cannot decode the banner image; showing the ASCII lobster");
+                }
+                self.banner = banner;
+                self.banner_loading = false;
             }
-            AppMessage::NameTaken(msg) => {
-                // Go back to name input with name-specific error
-                self.create.handle_name_taken(&msg);
+            AppMessage::BannerEncoded(encoded) => {
+                if let Some(banner) = &mut self.banner {
+                    banner.insert(encoded);
+                }
             }
-            AppMessage::ApiKeyValidated { api_key, name, description, is_claimed } => {
-                self.create.handle_api_key_validated(api_key, name, description, is_claimed);
+            AppMessage::ChainHead(block_number) => {
+                if matches!(self.connection, Connection::Offline(_)) {
+                    self.notifications.push(Level::Success, "Reconnected to the server");
+                }
+                self.connection = Connection::Online;
+                self.block_number = Some(block_number);
+                self.submit_scheduled(tx.clone());
             }
-            AppMessage::ApiKeyInvalid(msg) => {
-                self.create.handle_api_key_invalid(&msg);
+            AppMessage::HeartbeatFetched { agent_address, heartbeat } => {
+                self.heartbeat = heartbeat.map(|h| (agent_address, h));
             }
-            AppMessage::ApiKeyReadyToStore { api_key, name } => {
-                // Store existing agent on our server
-                let client = self.client.clone();
-                tokio::spawn(async move {
-                    match client.store_agent(&name, &api_key).await {
-                        Ok(resp) => {
-                            let _ = tx.send(AppMessage::MoltbookClaimed { 
-                                agent_id: resp.agent_id 
-                            }).await;
-                        }
-                        Err(e) => {
-                            let _ = tx.send(AppMessage::RegistrationFailed(
-                                format!("Failed to store agent: {}", e)
-                            )).await;
-                        }
-                    }
-                });
-            }
-            AppMessage::MoltbookClaimed { agent_id } => {
-                self.create.handle_moltbook_claimed(agent_id);
-            }
-            AppMessage::CompileDone { compiled_hex } => {
-                self.create.handle_compile_done(compiled_hex);
-                // Start deployment immediately after compilation
-                if let Some(wallet) = &self.wallet {
-                    self.create.start_deployment(
-                        self.client.clone(),
-                        wallet.clone(),
-                        tx.clone(),
+            AppMessage::RunFailuresFetched { agent_address, alert } => {
+                // Raised once per failing run, not on every poll
+                let known = self.failure_alert().map(|a| a.run_id);
+                if let Some(alert) = alert.as_ref().filter(|a| Some(a.run_id) != known) {
+                    let body = format!(
+                        "{} scheduled runs failed in a row. Last: {}",
+                        alert.count,
+                        alert.last_error.as_deref().unwrap_or("no reason given")
                     );
-                } else {
-                    self.error_message = Some("No wallet available for deployment".to_string());
+                    self.notifications.push(Level::Error, body.clone());
+                    if self.config.desktop_notifications && !self.focused {
+                        crate::desktop::notify("Scheduled runs failing", &body);
+                    }
                 }
+                self.run_failures = alert.map(|a| (agent_address, a));
             }
-            AppMessage::CompileFailed(e) => {
-                self.error_message = Some(format!("Compilation failed: {}", e));
-                self.create.handle_compile_failed(&e);
-            }
-            AppMessage::DeployDone { agent_address } => {
-                self.config.agent_address = Some(agent_address.clone());
-                self.config.agent_name = Some(self.create.agent_name.clone());
-                self.config.save()?;
-                
-                // Update the server with the chain address
-                if let Some(agent_id) = self.create.agent_id.clone() {
-                    let client = self.client.clone();
-                    let addr = agent_address.clone();
-                    tokio::spawn(async move {
-                        // Best-effort update - deployment already succeeded
-                        let _ = client.update_agent_address(&agent_id, &addr).await;
-                    });
+            AppMessage::ConnectionLost(e) => {
+                if self.connection == Connection::Online {
+                    self.notifications.push(Level::Error, format!("Lost connection to the server: {}", e));
                 }
-                
-                self.create.handle_deploy_done(agent_address);
+                self.connection = Connection::Offline(e);
             }
-            AppMessage::DeployFailed(e) => {
-                self.error_message = Some(format!("Deployment failed: {}", e));
-                self.create.handle_deploy_failed(&e);
-            }
-            AppMessage::PromptSubmitted { run_id } => {
-                self.prompt.handle_prompt_submitted(run_id);
-            }
-            AppMessage::ChainEvent(event) => {
-                self.prompt.handle_chain_event(event);
+            AppMessage::BalanceUpdated(balance) if self.wallet_balance.as_ref() == Some(&balance) => {}
+            AppMessage::BalanceUpdated(balance) => {
+                let planck = balance.balance.parse::<u128>().unwrap_or(0);
+                if self.faucet.as_ref().is_some_and(|f| f.tx_hash.is_some() && planck > f.before) {
+                    self.faucet = None;
+                    self.create.waiting_for_tokens = false;
+                    let symbol = self.config.network().token_symbol;
+                    self.notifications.push(
+                        Level::Success,
+                        format!("Test tokens arrived: wallet balance now {} {}", balance.balance_formatted, symbol),
+                    );
+                } else if self.wallet_balance.is_some() {
+                    // The first fetch is not news; later changes are (funding, fees, top-ups)
+                    let symbol = self.config.network().token_symbol;
+                    self.notifications.push(Level::Info, format!("Wallet balance now {} {}", balance.balance_formatted, symbol));
+                }
+                self.wallet_balance = Some(balance);
             }
-            AppMessage::PromptStatus(msg) => {
-                self.prompt.handle_status_message(msg);
+            AppMessage::RequestTestTokens => self.request_test_tokens(tx.clone()),
+            AppMessage::TestTokensSent(resp) => {
+                let symbol = self.config.network().token_symbol;
+                self.notifications.push(
+                    Level::Info,
+                    format!("Faucet sent {} {} (tx {}), waiting for the balance to update", resp.amount, symbol, resp.tx_hash),
+                );
+                if let Some(faucet) = &mut self.faucet {
+                    faucet.tx_hash = Some(resp.tx_hash);
+                }
+                self.fetch_balance(tx.clone());
             }
-            AppMessage::RunCompleted { result } => {
-                self.prompt.handle_run_completed(result);
+            AppMessage::TestTokensFailed(e) => {
+                self.faucet = None;
+                self.create.waiting_for_tokens = false;
+                self.notifications.push(Level::Error, format!("Faucet request failed: {}", e));
             }
-            AppMessage::PromptFailed(e) => {
-                self.prompt.handle_prompt_failed(e);
+            AppMessage::PriceUpdated(price) => {
+                self.usd_price = Some(price);
             }
-            AppMessage::AgentInfoFetched { info } => {
-                self.view.handle_agent_info(info);
+            AppMessage::BalanceStreamLive(live) => {
+                self.balance_pushed = live;
             }
-            AppMessage::PostsFetched { posts } => {
-                self.view.handle_posts(posts);
+            AppMessage::ScheduledPromptSubmitted { prompt, run_id } => {
+                let body = format!("Run #{}: {}", run_id, truncate_width(&prompt, 60));
+                self.notifications.push(Level::Success, format!("Scheduled prompt submitted. {}", body));
+                if self.config.desktop_notifications && !self.focused {
+                    crate::desktop::notify("Scheduled prompt submitted", &body);
+                }
             }
-            AppMessage::FetchFailed(e) => {
-                self.view.handle_fetch_error(e);
+            AppMessage::ScheduledPromptFailed { prompt, error } => {
+                let body = format!("{}: {}", truncate_width(&prompt, 60), error);
+                self.notifications.push(Level::Error, format!("Scheduled prompt failed. {}", body));
+                if self.config.desktop_notifications && !self.focused {
+                    crate::desktop::notify("Scheduled prompt failed", &body);
+                }
+                webhook::send(&self.config, WebhookEvent::RunFailed, &body);
             }
             AppMessage::AgentDataRestored { name, chain_address } => {
                 // Restore agent data from server (happens on login)
+                if self.config.agent_address.as_deref() != Some(chain_address.as_str()) {
+                    // A stored API key belongs to a different agent
+                    self.config.moltbook_api_key = None;
+                }
                 self.config.agent_name = Some(name);
                 self.config.agent_address = Some(chain_address);
-                let _ = self.config.save();
+                self.save_config_logged();
             }
-            AppMessage::AgentSourceSelected { custom_dir } => {
-                // Save the agent source selection to config
-                self.config.custom_agent_dir = custom_dir;
-                let _ = self.config.save();
+            AppMessage::BackupExported(result) => {
+                self.backup_dialog = None;
+                match result {
+                    Ok(path) => self.notifications.push(Level::Success, format!("Backup written to {}", path.display())),
+                    Err(e) => self.notifications.push(Level::Error, format!("Backup failed: {}", e)),
+                }
             }
             AppMessage::Error(e) => {
-                self.error_message = Some(e);
+                self.notifications.push(Level::Error, e);
             }
+            AppMessage::Create(msg) => route!(self, tx, Create, create, msg),
+            AppMessage::Prompt(msg) => route!(self, tx, Prompt, prompt, msg),
+            AppMessage::View(msg) => route!(self, tx, View, view, msg),
+            AppMessage::Compose(msg) => route!(self, tx, Compose, compose, msg),
+            AppMessage::Feed(msg) => route!(self, tx, Feed, feed, msg),
+            AppMessage::Submolts(msg) => route!(self, tx, Submolts, submolts, msg),
+            AppMessage::Inbox(msg) => route!(self, tx, Inbox, inbox, msg),
+            AppMessage::Leaderboard(msg) => route!(self, tx, Leaderboard, leaderboard, msg),
+            AppMessage::Inspect(msg) => route!(self, tx, Inspect, inspect, msg),
         }
+        self.check_low_balance();
         Ok(())
     }
 
+    /// Raise a low-balance warning on the desktop once, when it appears while
+    /// the terminal is in the background; in view, the banner is enough.
+    /// Webhooks hear once about the agent account running low, in view or not.
+    fn check_low_balance(&mut self) {
+        let funds = self.funds();
+        match funds.check_agent() {
+            Err(e) if !self.low_agent_hooked => {
+                self.low_agent_hooked = true;
+                webhook::send(&self.config, WebhookEvent::LowBalance, &e);
+            }
+            Err(_) => {}
+            Ok(()) => self.low_agent_hooked = false,
+        }
+        match funds.warning() {
+            Some(warning) if !self.low_balance_notified => {
+                self.low_balance_notified = true;
+                if self.config.desktop_notifications && !self.focused {
+                    crate::desktop::notify("Balance running low", &warning);
+                }
+            }
+            Some(_) => {}
+            None => self.low_balance_notified = false,
+        }
+    }
+
     /// Fetch wallet balance in background.
+    /// Ask the test network's faucet for tokens; the wizard waits for them
+    /// to arrive before it deploys.
+    fn request_test_tokens(&mut self, tx: mpsc::Sender<AppMessage>) {
+        let network = self.config.network();
+        let Some(address) = self.wallet_address().map(str::to_string) else {
+            self.notifications.push(Level::Error, "Log in first: the faucet sends tokens to your wallet");
+            return;
+        };
+        if !network.kind.has_faucet() {
+            self.notifications.push(Level::Error, format!("{} has no faucet; only test networks do", network.name));
+            return;
+        }
+        if self.faucet.is_some() {
+            self.notifications.push(Level::Info, "Still waiting for the last faucet request");
+            return;
+        }
+
+        let before = self.funds().wallet.unwrap_or(0);
+        self.faucet = Some(FaucetRequest { before, tx_hash: None });
+        self.create.waiting_for_tokens = true;
+        let client = self.client.clone();
+        crate::tasks::spawn(async move {
+            match client.request_test_tokens(&address).await {
+                Ok(resp) => {
+                    let _ = tx.send(AppMessage::TestTokensSent(resp)).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(AppMessage::TestTokensFailed(format!("{:#}", e))).await;
+                }
+            }
+        });
+    }
+
     fn fetch_balance(&self, tx: mpsc::Sender<AppMessage>) {
         let Some(wallet) = &self.wallet else {
             return; // No wallet yet
@@ -747,13 +1695,14 @@ impl App {
         let client = self.client.clone();
         let address = wallet.public_key.clone();
         
-        tokio::spawn(async move {
+        crate::tasks::spawn(async move {
             match client.get_balance(&address).await {
                 Ok(resp) => {
-                    let _ = tx.send(AppMessage::BalanceUpdated(resp.balance_formatted)).await;
+                    let _ = tx.send(AppMessage::BalanceUpdated(resp)).await;
                 }
-                Err(_) => {
-                    // Silently ignore balance fetch errors
+                Err(e) => {
+                    // Not worth a toast every refresh; the status bar keeps the last balance
+                    tracing::warn!("Balance fetch failed: {:#}", e);
                 }
             }
         });
@@ -763,7 +1712,7 @@ impl App {
     fn fetch_user_agents(&self, tx: mpsc::Sender<AppMessage>) {
         let client = self.client.clone();
         
-        tokio::spawn(async move {
+        crate::tasks::spawn(async move {
             match client.list_agents().await {
                 Ok(agents) => {
                     // Find the first agent with a chain_address (deployed agent)
@@ -776,8 +1725,9 @@ impl App {
                         }
                     }
                 }
-                Err(_) => {
-                    // Silently ignore - user might not have any agents yet
+                Err(e) => {
+                    // User might not have any agents yet
+                    tracing::warn!("Listing agents failed: {:#}", e);
                 }
             }
         });
@@ -790,13 +1740,14 @@ impl App {
         }
         
         let client = self.client.clone();
-        tokio::spawn(async move {
+        crate::tasks::spawn(async move {
             match client.get_me().await {
                 Ok(_) => {
                     // Session is still valid
                 }
-                Err(_) => {
+                Err(e) => {
                     // Session expired or invalid - trigger logout
+                    tracing::info!("Session check failed: {:#}", e);
                     let _ = tx.send(AppMessage::AuthFailed("Session expired. Please login again.".to_string())).await;
                 }
             }
@@ -808,11 +1759,402 @@ impl App {
         self.fetch_balance(tx);
     }
 
+    /// Fetch the token price, if a price feed is configured (called from main loop).
+    pub fn refresh_price(&self, tx: mpsc::Sender<AppMessage>) {
+        let Some(url) = self.config.price_feed_url.clone() else {
+            return;
+        };
+        crate::tasks::spawn(async move {
+            // A stale price is kept on failure; it is only ever shown as approximate
+            match crate::price::fetch_usd_price(&url).await {
+                Ok(price) => {
+                    let _ = tx.send(AppMessage::PriceUpdated(price)).await;
+                }
+                Err(e) => tracing::warn!("Price feed failed: {:#}", e),
+            }
+        });
+    }
+
+    /// Balances known right now, for the checks made before signing.
+    pub fn funds(&self) -> Funds {
+        let planck = |b: &BalanceResponse| b.balance.parse::<u128>().ok();
+        let wallet = self.wallet_balance.as_ref().and_then(planck);
+        let agent = self.view.agent_balance.as_ref().filter(|_| self.has_agent()).and_then(planck);
+        let network = self.config.network();
+        Funds::new(wallet, agent, self.config.min_balance_planck(&network), &network).with_limits(&self.config.spending_limits)
+    }
+
+    /// Approximate USD value of a token amount, when a price is known.
+    pub fn usd_value(&self, amount: &str) -> Option<String> {
+        crate::price::usd_value(amount, self.usd_price?)
+    }
+
+    /// Poll the latest block, which doubles as the connectivity check (called from main loop).
+    pub fn refresh_chain_head(&self, tx: mpsc::Sender<AppMessage>) {
+        let client = self.client.clone();
+        crate::tasks::spawn(async move {
+            let msg = match client.get_chain_head().await {
+                Ok(head) => AppMessage::ChainHead(head.block_number),
+                Err(e) => AppMessage::ConnectionLost(format!("{:#}", e)),
+            };
+            let _ = tx.send(msg).await;
+        });
+    }
+
+    /// Fetch the agent's schedule and recent activity for the heartbeat
+    /// line on the home screen (called with the chain head poll, only
+    /// while home is showing).
+    pub fn refresh_heartbeat(&self, tx: mpsc::Sender<AppMessage>) {
+        let Some(agent_address) = self.agent_address().map(str::to_string).filter(|_| self.screen == AppScreen::Home) else {
+            return;
+        };
+        let client = self.client.clone();
+        crate::tasks::spawn(async move {
+            let (info, activity) =
+                tokio::join!(client.get_agent(&agent_address), client.get_activity(&agent_address, HEARTBEAT_ACTIVITY_LIMIT));
+            // Best effort: the line just stays as it was
+            let (Ok(info), Ok(activity)) = (info, activity) else {
+                return;
+            };
+            let heartbeat = info.chain_info.and_then(|chain| Heartbeat::from_activity(&chain, &activity.events));
+            let _ = tx.send(AppMessage::HeartbeatFetched { agent_address, heartbeat }).await;
+        });
+    }
+
+    /// Check the agent's run history for scheduled runs failing in a row
+    /// (called with the chain head poll, only while home is showing).
+    pub fn refresh_run_failures(&self, tx: mpsc::Sender<AppMessage>) {
+        let Some(agent_address) = self.agent_address().map(str::to_string).filter(|_| self.screen == AppScreen::Home) else {
+            return;
+        };
+        let client = self.client.clone();
+        crate::tasks::spawn(async move {
+            // Best effort: the alert just stays as it was
+            let Ok(history) = client.get_runs(&agent_address, RUN_HISTORY_LIMIT).await else {
+                return;
+            };
+            let alert = FailureAlert::from_runs(&history.runs);
+            let _ = tx.send(AppMessage::RunFailuresFetched { agent_address, alert }).await;
+        });
+    }
+
+    /// Open the latest failing scheduled run's event log.
+    pub fn open_failing_run(&mut self, tx: mpsc::Sender<AppMessage>) {
+        let Some(run_id) = self.failure_alert().map(|a| a.run_id) else {
+            self.notifications.push(Level::Info, "No failing scheduled runs");
+            return;
+        };
+        self.prompt.open_run(&self.client, run_id, self.config.stream_stall_timeout(), tx.clone());
+        if let Some(addr) = self.agent_address().map(str::to_string).filter(|_| self.prompt.show_posts) {
+            self.prompt.refresh_posts(&self.client, &addr, tx);
+        }
+        self.push_screen(AppScreen::Prompt);
+    }
+
+    /// Submit the scheduled prompts that are due at the latest block (called
+    /// on every chain head), each signed now with the wallet's current nonce.
+    fn submit_scheduled(&self, tx: mpsc::Sender<AppMessage>) {
+        let Some(wallet) = self.wallet.clone().filter(|_| self.config.is_authenticated()) else {
+            return;
+        };
+        for prompt in schedule::take_due(&self.config.network().name, self.block_number, schedule::now()) {
+            let (client, wallet, tx) = (self.client.clone(), wallet.clone(), tx.clone());
+            crate::tasks::spawn_operation("a scheduled prompt", async move {
+                let msg = match schedule::submit(&client, &wallet, &prompt).await {
+                    Ok((run_id, _)) => AppMessage::ScheduledPromptSubmitted { prompt: prompt.prompt, run_id },
+                    Err(e) => AppMessage::ScheduledPromptFailed { prompt: prompt.prompt, error: format!("{:#}", e) },
+                };
+                let _ = tx.send(msg).await;
+            });
+        }
+    }
+
+    /// Keep the prompt screen's cost preview in step with the prompt being
+    /// typed, one estimate at a time (called from main loop).
+    pub fn preview_prompt_cost(&mut self, tx: mpsc::Sender<AppMessage>) {
+        if self.screen != AppScreen::Prompt {
+            return;
+        }
+        let (Some(wallet), Some(agent_address)) = (self.wallet.clone(), self.config.agent_address.clone()) else {
+            return;
+        };
+        if let Some(input) = self.prompt.cost_request() {
+            PromptScreen::start_cost_estimate(self.client.clone(), wallet, agent_address, input, tx);
+        }
+    }
+
+    /// Watch the custom agent directory while the create wizard is open (called from main loop).
+    pub fn watch_agent_dir(&mut self, tx: mpsc::Sender<AppMessage>) {
+        let wanted = (self.screen == AppScreen::Create && !self.create.use_embedded)
+            .then(|| self.create.custom_dir_input.text().to_string())
+            .filter(|dir| std::path::Path::new(dir).is_dir());
+        if self.agent_watcher.as_ref().map(|w| w.dir.as_str()) == wanted.as_deref() {
+            return;
+        }
+        self.agent_watcher = wanted.and_then(|dir| AgentDirWatcher::start(&dir, tx).ok());
+    }
+
+    /// Keep a balance stream open for the wallet while logged in (called from main loop).
+    pub fn subscribe_balance(&mut self, tx: mpsc::Sender<AppMessage>) {
+        let wanted = self
+            .wallet_address()
+            .filter(|_| self.config.is_authenticated())
+            .map(|address| (self.client.base_url().to_string(), address.to_string()));
+        if self.balance_stream.as_ref().map(|s| &s.key) == wanted.as_ref() {
+            return;
+        }
+        self.balance_pushed = false;
+        self.balance_stream = wanted.map(|(_, address)| BalanceStream::start(self.client.clone(), address, tx));
+    }
+
+    /// Periodic reload of the view screen, or of the prompt screen's posts
+    /// pane, while it is open (called from main loop).
+    pub fn refresh_view(&mut self, tx: mpsc::Sender<AppMessage>) {
+        let Some(addr) = self.agent_address().map(str::to_string) else {
+            return;
+        };
+        match self.screen {
+            AppScreen::View => self.view.auto_refresh(&self.client, &addr, tx),
+            AppScreen::Prompt if self.prompt.show_posts && self.prompt.step != PromptStep::EnterPrompt => {
+                self.prompt.refresh_posts(&self.client, &addr, tx)
+            }
+            _ => {}
+        }
+    }
+
+    /// Check the Twitter verification while the create wizard waits for it
+    /// (called from main loop every `claim_poll_secs`).
+    pub fn poll_claim(&mut self, tx: mpsc::Sender<AppMessage>) {
+        if self.screen == AppScreen::Create && self.create.step == CreateStep::WaitingClaim {
+            self.create.check_claim(self.client.clone(), tx, true);
+        }
+    }
+
+    /// Current frame of the loading spinner.
+    pub fn spinner(&self) -> &'static str {
+        crate::screens::spinner(self.tick)
+    }
+
+    /// Whether anything on screen (banner animation, toasts, spinners) needs periodic redraws.
+    pub fn is_animating(&self) -> bool {
+        self.notifications.has_toasts()
+            // Every spinner belongs to a background task
+            || crate::tasks::in_flight() > 0
+            || (self.screen == AppScreen::Home && !self.config.banner_hidden && self.banner.as_ref().is_some_and(|b| b.is_animated()))
+    }
+
+    /// Whether the screen shows relative times ("2m ago") that go stale while idle.
+    pub fn shows_elapsed_time(&self) -> bool {
+        self.history_open || self.screen == AppScreen::View
+    }
+
     pub fn can_quit(&self) -> bool {
-        self.screen == AppScreen::Home
+        matches!(self.screen, AppScreen::Home | AppScreen::Onboarding)
+            && !self.help_open
+            && !self.history_open
+            && !self.quit_confirm
+            && self.error_modal.is_none()
+            && self.backup_dialog.is_none()
+            && self.palette.is_none()
+            && self.lock.is_none()
+    }
+
+    /// Lock the screen once `idle_lock_minutes` passed without a key press;
+    /// true when it just locked. Never locks without a way to unlock.
+    pub fn lock_if_idle(&mut self) -> bool {
+        let Some(timeout) = self.config.idle_lock_timeout() else {
+            return false;
+        };
+        if self.lock.is_some() || self.last_input.elapsed() < timeout || !crate::secrets::can_unlock(self.config.lock_pin.as_deref()) {
+            return false;
+        }
+        tracing::info!("locked after {} idle minutes", timeout.as_secs() / 60);
+        self.palette = None;
+        self.lock = Some(LockScreen::new());
+        true
+    }
+
+    /// Write the encrypted backup off the UI thread; the key derivation
+    /// takes about a second.
+    fn export_backup(passphrase: String, tx: mpsc::Sender<AppMessage>) {
+        crate::tasks::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || crate::backup::export(&passphrase))
+                .await
+                .unwrap_or_else(|e| Err(anyhow::anyhow!("{}", e)))
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(AppMessage::BackupExported(result)).await;
+        });
+    }
+
+    /// Quit, or ask first when a deployment, run or transfer is still running.
+    pub fn request_quit(&mut self) {
+        if crate::tasks::operations().is_empty() {
+            self.quit = true;
+        } else {
+            self.quit_confirm = true;
+        }
+    }
+
+    /// Error shown on the current screen, if any.
+    pub fn current_error(&self) -> Option<&str> {
+        let view = &self.view;
+        let screen_error = match self.screen {
+            AppScreen::Create => self
+                .create
+                .error
+                .as_ref()
+                .or(self.create.name_error.as_ref())
+                .or(self.create.api_key_error.as_ref())
+                .or(self.create.balance_error.as_ref()),
+            AppScreen::Prompt => self.prompt.error.as_ref(),
+            AppScreen::View => view
+                .retire
+                .as_ref()
+                .and_then(|f| f.error.as_ref())
+                .or(view.transfer.as_ref().and_then(|f| f.error.as_ref()))
+                .or(view.profile.as_ref().and_then(|f| f.error.as_ref()))
+                .or(view.error.as_ref())
+                .or(view.activity_error.as_ref()),
+            AppScreen::Compose => self.compose.error.as_ref(),
+            AppScreen::Feed => self.feed.error.as_ref(),
+            AppScreen::Submolts => self.submolts.error.as_ref(),
+            AppScreen::Inbox => self.inbox.error.as_ref(),
+            AppScreen::Leaderboard => self.leaderboard.error.as_ref(),
+            AppScreen::Runs => self.runs.error.as_ref(),
+            AppScreen::Inspect => self.inspect.error.as_ref(),
+            AppScreen::Search => self.search.error.as_ref(),
+            AppScreen::Settings => self.settings.error.as_ref(),
+            AppScreen::Home | AppScreen::Onboarding | AppScreen::EmailInput | AppScreen::Auth => None,
+        };
+        screen_error.or(self.error_message.as_ref()).map(String::as_str)
+    }
+
+    /// Show the current screen's error in full, or else the latest error notification.
+    fn open_error_modal(&mut self) {
+        let modal = match self.current_error() {
+            Some(error) => Some(ErrorModal::new(error.to_string(), crate::keymap::active_bindings(self).0)),
+            None => self
+                .notifications
+                .history()
+                .find(|n| n.level == Level::Error)
+                .map(|n| ErrorModal::new(n.message.clone(), "Notifications")),
+        };
+        match modal {
+            Some(modal) => self.error_modal = Some(modal),
+            None => self.notifications.push(Level::Info, "No errors to show"),
+        }
+    }
+
+    /// Whether printable keys currently go into a text field.
+    fn is_typing(&self) -> bool {
+        match self.screen {
+            AppScreen::EmailInput => true,
+            AppScreen::Create => match self.create.step {
+                CreateStep::SelectAgentSource => !self.create.use_embedded,
+                CreateStep::EnterAgentInfo => true,
+                CreateStep::ReviewDeploy => self.create.amount_confirm.is_some(),
+                _ => false,
+            },
+            AppScreen::Prompt => self.prompt.step == PromptStep::EnterPrompt || self.prompt.follow_up_input.is_some(),
+            AppScreen::Compose => self.compose.step == ComposeStep::Editing,
+            AppScreen::View => {
+                self.view.comment_input.is_some()
+                    || self.view.amount_confirm.is_some()
+                    || self.view.profile.is_some()
+                    || self.view.retire.is_some()
+                    || self.view.transfer.is_some()
+            }
+            AppScreen::Settings => self.settings.editing.is_some() || self.settings.capturing_key,
+            AppScreen::Home | AppScreen::Onboarding | AppScreen::Auth | AppScreen::Feed | AppScreen::Submolts | AppScreen::Inbox | AppScreen::Leaderboard | AppScreen::Runs | AppScreen::Inspect => false,
+            AppScreen::Search => self.search.editing,
+        }
+    }
+
+    /// Whether the screen is a list or a scrolling view, where the vim
+    /// preset's motions apply. The menus keep their letter shortcuts.
+    fn has_list(&self) -> bool {
+        !matches!(self.screen, AppScreen::Home | AppScreen::Onboarding | AppScreen::Auth)
     }
 
     pub fn should_quit(&self) -> bool {
         self.quit
     }
+
+    /// Wind down after the event loop: close live connections, let running
+    /// operations finish (handling their results, so a deploy that lands is
+    /// still saved) for up to `SHUTDOWN_GRACE`, abort the rest and save state.
+    pub async fn shutdown(&mut self, tx: mpsc::Sender<AppMessage>, rx: &mut mpsc::Receiver<AppMessage>) {
+        self.balance_stream = None;
+        self.agent_watcher = None;
+
+        let deadline = tokio::time::Instant::now() + SHUTDOWN_GRACE;
+        let operations = crate::tasks::operations();
+        if !operations.is_empty() {
+            eprintln!("Waiting for {} to finish...", operations.join(", "));
+        }
+        while !crate::tasks::operations().is_empty() && tokio::time::Instant::now() < deadline {
+            tokio::select! {
+                Some(msg) = rx.recv() => {
+                    if let Err(e) = self.handle_message(msg, tx.clone()).await {
+                        tracing::warn!("Message during shutdown failed: {:#}", e);
+                    }
+                }
+                _ = tokio::time::sleep(std::time::Duration::from_millis(50)) => {}
+            }
+        }
+
+        let abandoned = crate::tasks::operations();
+        let aborted = crate::tasks::abort_all();
+        tracing::info!(aborted, ?abandoned, "background tasks stopped");
+        if !abandoned.is_empty() {
+            eprintln!("Stopped waiting for {}; check the agent page for its outcome.", abandoned.join(", "));
+        }
+        if let (Some(run_id), None) = (self.prompt.run_id, &self.prompt.final_output) {
+            // The run carries on on-chain; only the live view is lost
+            tracing::info!(run_id, "left while the agent run was in progress");
+        }
+
+        if let Some(draft) = self.create.draft() {
+            match draft.save(self.config.encrypt_secrets) {
+                Ok(()) => tracing::info!(agent = %draft.agent_name, "saved the create wizard draft"),
+                Err(e) => tracing::warn!("Could not save the create wizard draft: {:#}", e),
+            }
+        }
+        if let Err(e) = self.save_unsaved_config() {
+            tracing::warn!("Could not save config on exit: {:#}", e);
+        }
+    }
+
+    /// Write the config if it has changes not on disk yet. A `--server`
+    /// override is for this run only, and an unreadable file is left alone.
+    fn save_unsaved_config(&self) -> Result<()> {
+        let saved = match AppConfig::load() {
+            Ok(saved) => saved,
+            Err(e) => {
+                tracing::warn!("Not saving over an unreadable config: {:#}", e);
+                return Ok(());
+            }
+        };
+        let mut config = self.config.clone();
+        config.switch_server(saved.server_url.clone());
+        if serde_json::to_value(&config)? != serde_json::to_value(&saved)? {
+            tracing::info!("saving config changes on exit");
+            config.save()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app, assert_snapshot, config, screen_text};
+
+    #[test]
+    fn test_email_input() {
+        let mut app = app(config());
+        app.screen = AppScreen::EmailInput;
+        app.email_input.set_text("lobster@example.com");
+        assert_snapshot("email_input", &screen_text(&mut app));
+    }
 }
@@ -0,0 +1,189 @@
+//! Compiled programs kept for reuse.
+//!
+//! Every successful compile is saved as `artifacts/<input hash>.json` in the
+//! data directory, keyed by a hash of the agent files, the schedule and the
+//! Moltbook agent id it was compiled for. The wizard skips the compiler when
+//! nothing changed, and `lobster deploy --artifact <file>` deploys a saved
+//! program without compiling at all.
+
+use crate::client::ApiClient;
+use crate::config::{AppConfig, DEFAULT_SERVER_URL};
+use crate::funds::{format_tokens, parse_tokens, Funds};
+use crate::versions::{self, DeployedVersion};
+use crate::wallet::WalletConfig;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// A compiled program and what it was compiled from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Artifact {
+    pub input_hash: String,
+    pub compiled_hex: String,
+    /// Compiler warnings, shown again when the artifact is reused
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    pub agent_name: String,
+    /// Moltbook agent id on the server, linked to the address once deployed
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub schedule_blocks: Option<u32>,
+    /// "embedded", the custom agent directory or git URL
+    pub source: String,
+    /// Unix seconds
+    pub compiled_at: u64,
+}
+
+pub fn artifacts_dir() -> PathBuf {
+    crate::config::data_dir().join("artifacts")
+}
+
+/// blake2-256 over everything that goes into a compile. Each part is length
+/// prefixed, so moving text between files changes the hash.
+pub fn input_hash(agent_id: &str, files: &[&str], schedule_blocks: Option<u32>) -> String {
+    let mut input = Vec::new();
+    for part in std::iter::once(agent_id).chain(files.iter().copied()) {
+        input.extend((part.len() as u64).to_le_bytes());
+        input.extend(part.as_bytes());
+    }
+    input.extend(schedule_blocks.map_or([0xff; 4], u32::to_le_bytes));
+    hex::encode(sp_core::hashing::blake2_256(&input))
+}
+
+impl Artifact {
+    fn path(dir: &Path, input_hash: &str) -> PathBuf {
+        dir.join(format!("{}.json", input_hash))
+    }
+
+    /// The artifact compiled from the same input, if one was saved in `dir`.
+    pub fn cached(dir: &Path, input_hash: &str) -> Option<Self> {
+        Self::load(&Self::path(dir, input_hash))
+            .inspect_err(|e| tracing::debug!("No reusable artifact: {:#}", e))
+            .ok()
+            .filter(|a| a.input_hash == input_hash)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+        serde_json::from_str(&contents).with_context(|| format!("{} is not a compiled artifact", path.display()))
+    }
+
+    /// Write the artifact into `dir`, normally `artifacts_dir()`.
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+        let path = Self::path(dir, &self.input_hash);
+        std::fs::write(&path, serde_json::to_string_pretty(self)?).with_context(|| format!("Cannot write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// `lobster deploy --artifact <file>`: deploy a saved program with the
+/// logged-in wallet, funding the agent account with `deposit` tokens, and
+/// make the new agent the current one.
+pub async fn deploy(server_url: Option<String>, mock: bool, path: &Path, deposit: &str) -> Result<()> {
+    let artifact = Artifact::load(path)?;
+    let mut config = AppConfig::load()?;
+    let server_url = server_url
+        .or_else(|| Some(config.server_url.clone()).filter(|url| !url.is_empty()))
+        .unwrap_or_else(|| DEFAULT_SERVER_URL.to_string());
+    config.switch_server(server_url);
+    let mut client = if mock { ApiClient::mock() } else { ApiClient::new(config.server_url.clone()) };
+    match &config.auth_token {
+        Some(token) if config.is_authenticated() => client.set_auth_token(token.clone()),
+        _ => anyhow::bail!("Not logged in; log in with lobster first"),
+    }
+    let wallet = WalletConfig::load(&config)?.context("No wallet yet; log in with lobster first")?;
+    let network = config.network();
+    let value = parse_tokens(deposit, network.decimals)
+        .filter(|&planck| planck > 0)
+        .with_context(|| format!("Not a positive token amount: {}", deposit))?;
+    // An unknown balance is left to the chain to reject, as in the wizard
+    let wallet_balance = match client.get_balance(&wallet.public_key).await {
+        Ok(balance) => balance.balance.parse::<u128>().ok(),
+        Err(e) => {
+            tracing::warn!("Balance fetch failed: {:#}", e);
+            None
+        }
+    };
+    let funds = Funds::new(wallet_balance, None, config.min_balance_planck(&network), &network)
+        .with_limits(&config.spending_limits);
+    if let Err(problem) = funds.check_wallet(value) {
+        bail!(problem);
+    }
+    if let Some(reason) = funds.over_limit(value) {
+        confirm_over_limit(&reason, value, network.decimals)?;
+    }
+
+    // A fixed salt would collide with earlier deployments of the same program
+    let mut salt = [0u8; 32];
+    getrandom::getrandom(&mut salt).context("No randomness for the deployment salt")?;
+    let build = client
+        .build_deploy(&artifact.compiled_hex, &format!("0x{}", hex::encode(salt)), &wallet.public_key, value)
+        .await
+        .context("Build failed")?;
+    let submitted = crate::extrinsic::sign_and_submit(&client, &build, &wallet.keypair()?).await.context("Submit failed")?;
+    crate::spending::record(&network.name, value);
+    let address = crate::extrinsic::parse_agent_registered_event(&submitted.events)
+        .context("Could not find AgentRegistered event")?;
+
+    if config.agent_name.as_deref() != Some(artifact.agent_name.as_str()) {
+        // A stored API key belongs to a different agent
+        config.moltbook_api_key = None;
+    }
+    config.agent_address = Some(address.clone());
+    config.agent_name = Some(artifact.agent_name.clone());
+    config.save()?;
+    if let Some(agent_id) = &artifact.agent_id {
+        if let Err(e) = client.update_agent_address(agent_id, &address).await {
+            tracing::warn!(agent_id, "Failed to record the agent address: {:#}", e);
+        }
+    }
+    let version = client.get_agent(&address).await.ok().and_then(|i| i.chain_info).map(|c| c.version);
+    let deployed = DeployedVersion {
+        agent_address: address.clone(),
+        version,
+        code_hash: versions::code_hash(&artifact.compiled_hex),
+        block_number: submitted.block_number,
+        block_hash: submitted.block_hash.clone(),
+        deployed_at: crate::schedule::now(),
+        source: artifact.source.clone(),
+        snapshot: None,
+    };
+    if let Err(e) = versions::record(deployed) {
+        tracing::warn!("Cannot record the deployed version: {:#}", e);
+    }
+
+    println!("Deployed {} at {}", artifact.agent_name, address);
+    println!("Block #{} ({})", submitted.block_number, submitted.block_hash);
+    Ok(())
+}
+
+/// Have a deposit over a spending limit typed out again on the terminal.
+fn confirm_over_limit(reason: &str, value: u128, decimals: u8) -> Result<()> {
+    if !std::io::stdin().is_terminal() {
+        bail!("{}; run it from a terminal to confirm the amount", reason);
+    }
+    eprint!("{}. Type {} to send it: ", reason, format_tokens(value, decimals));
+    let mut typed = String::new();
+    std::io::stdin().read_line(&mut typed).context("Cannot read the amount")?;
+    if parse_tokens(&typed, decimals) != Some(value) {
+        bail!("The amount was not confirmed; nothing was sent");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        let hash = input_hash("agent-1", &["ship", "soul", "", ""], Some(600));
+        assert_eq!(hash, input_hash("agent-1", &["ship", "soul", "", ""], Some(600)));
+        assert_ne!(hash, input_hash("agent-1", &["ship", "soul", "", ""], None));
+        assert_ne!(hash, input_hash("agent-1", &["shi", "psoul", "", ""], Some(600)));
+        assert_ne!(hash, input_hash("agent-2", &["ship", "soul", "", ""], Some(600)));
+    }
+}
@@ -0,0 +1,93 @@
+//! Local text files attached to a prompt.
+//!
+//! The file is read once when attached and sent as part of the prompt text,
+//! below what the user typed. Large files are refused outright and long text
+//! is cut, since the whole prompt goes into an extrinsic and the agent's context.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Files larger than this are not read at all.
+pub const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Characters of the file kept in the prompt; the rest is cut.
+pub const MAX_CHARS: usize = 16_000;
+
+/// A file's text, ready to go out with a prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    /// File name as shown to the agent, without the directories
+    pub name: String,
+    /// Text sent, at most `MAX_CHARS` characters
+    pub text: String,
+    /// Characters in the whole file
+    pub total_chars: usize,
+}
+
+impl Attachment {
+    /// Read a UTF-8 text file; `~/` is the home directory.
+    pub fn load(path: &str) -> Result<Self> {
+        let path = expand_home(path.trim());
+        let size = std::fs::metadata(&path).with_context(|| format!("Cannot read {}", path.display()))?.len();
+        if size > MAX_FILE_BYTES {
+            bail!("{} is {} KiB; files up to {} KiB can be attached", path.display(), size / 1024, MAX_FILE_BYTES / 1024);
+        }
+        let bytes = std::fs::read(&path).with_context(|| format!("Cannot read {}", path.display()))?;
+        let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+        Self::from_bytes(name, &bytes)
+    }
+
+    fn from_bytes(name: String, bytes: &[u8]) -> Result<Self> {
+        let Ok(text) = std::str::from_utf8(bytes) else {
+            bail!("{} is not a text file", name);
+        };
+        if text.contains('\0') {
+            bail!("{} is not a text file", name);
+        }
+        let total_chars = text.chars().count();
+        let text = text.chars().take(MAX_CHARS).collect();
+        Ok(Self { name, text, total_chars })
+    }
+
+    pub fn truncated(&self) -> bool {
+        self.total_chars > MAX_CHARS
+    }
+
+    /// `prompt` with the file below it, fenced and named so the agent can
+    /// tell the two apart, and a note when the file was cut.
+    pub fn append_to(&self, prompt: &str) -> String {
+        let mut out = format!("{}\n\n--- Attached file: {} ---\n{}", prompt.trim_end(), self.name, self.text.trim_end());
+        if self.truncated() {
+            out.push_str(&format!("\n[Truncated: first {} of {} characters]", MAX_CHARS, self.total_chars));
+        }
+        out.push_str("\n--- End of file ---");
+        out
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => Path::new(path).to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attachment_truncation() {
+        let long = "a".repeat(MAX_CHARS + 5);
+        let attachment = Attachment::from_bytes("notes.txt".to_string(), long.as_bytes()).unwrap();
+        assert!(attachment.truncated());
+        assert_eq!(attachment.text.len(), MAX_CHARS);
+        let prompt = attachment.append_to("Summarize this");
+        assert!(prompt.starts_with("Summarize this\n\n--- Attached file: notes.txt ---\n"));
+        assert!(prompt.ends_with(&format!("[Truncated: first {} of {} characters]\n--- End of file ---", MAX_CHARS, MAX_CHARS + 5)));
+
+        let short = Attachment::from_bytes("a.md".to_string(), "# Title\n".as_bytes()).unwrap();
+        assert_eq!(short.append_to("Post it"), "Post it\n\n--- Attached file: a.md ---\n# Title\n--- End of file ---");
+        assert!(Attachment::from_bytes("a.png".to_string(), &[0x89, 0x50, 0xff, 0x00]).is_err());
+    }
+}
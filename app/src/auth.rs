@@ -1,6 +1,7 @@
 //! Authentication flows for OAuth and magic link.
 
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpListener;
@@ -12,10 +13,75 @@ pub enum AuthMethod {
     Email(String),
 }
 
-/// Run the OAuth flow by opening a browser and waiting for callback.
-pub async fn run_oauth_flow(server_url: &str, method: AuthMethod) -> Result<String> {
-    // Bind to random available port
-    let listener = TcpListener::bind("127.0.0.1:0").await?;
+/// Banner image bytes and their content type, served at `/lobster.png`.
+pub type BannerImage = (Vec<u8>, &'static str);
+
+/// Where the local auth callback listener binds.
+///
+/// Defaults to any free port on loopback. A fixed port (or small range) is
+/// useful behind firewalls or when forwarding the port from a remote machine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CallbackListener {
+    /// Address to bind, e.g. `0.0.0.0` to accept forwarded connections
+    pub bind_address: String,
+    /// First port to try; 0 lets the OS pick a free one
+    pub port: u16,
+    /// Last port to try when earlier ones are taken (defaults to `port`)
+    pub port_end: Option<u16>,
+}
+
+impl Default for CallbackListener {
+    fn default() -> Self {
+        Self {
+            bind_address: "127.0.0.1".to_string(),
+            port: 0,
+            port_end: None,
+        }
+    }
+}
+
+impl CallbackListener {
+    /// Inclusive range of ports to try, in order.
+    fn ports(&self) -> std::ops::RangeInclusive<u16> {
+        self.port..=self.port_end.unwrap_or(self.port).max(self.port)
+    }
+
+    /// Bind the first free port in the configured range.
+    pub async fn bind(&self) -> Result<TcpListener> {
+        let mut last_error = None;
+        for port in self.ports() {
+            match TcpListener::bind((self.bind_address.as_str(), port)).await {
+                Ok(listener) => return Ok(listener),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        let range = match self.port_end {
+            Some(end) if end > self.port => format!("{}-{}", self.port, end),
+            _ => self.port.to_string(),
+        };
+        Err(anyhow::anyhow!(
+            "Could not bind auth callback on {} port {}: {}",
+            self.bind_address,
+            range,
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        ))
+    }
+}
+
+/// URL the browser is redirected to for a listener bound on `port`.
+pub fn callback_url(port: u16) -> String {
+    format!("http://localhost:{}/", port)
+}
+
+/// Run the OAuth flow by opening a browser and waiting for callback on `listener`.
+pub async fn run_oauth_flow(
+    server_url: &str,
+    method: AuthMethod,
+    listener: TcpListener,
+    pages: AuthPages,
+    banner: Option<BannerImage>,
+) -> Result<String> {
     let port = listener.local_addr()?.port();
 
     // Get OAuth URL from server based on method
@@ -23,7 +89,7 @@ pub async fn run_oauth_flow(server_url: &str, method: AuthMethod) -> Result<Stri
     match method {
         AuthMethod::Twitter => {
             let auth_url: String = client
-                .get(&format!("{}/auth/url?redirect_port={}&provider=twitter", server_url, port))
+                .get(format!("{}/auth/url?redirect_port={}&provider=twitter", server_url, port))
                 .send()
                 .await?
                 .json()
@@ -35,7 +101,7 @@ pub async fn run_oauth_flow(server_url: &str, method: AuthMethod) -> Result<Stri
         AuthMethod::Email(ref email) => {
             // For email, request magic link to be sent
             let resp = client
-                .post(&format!("{}/auth/magic-link", server_url))
+                .post(format!("{}/auth/magic-link", server_url))
                 .json(&serde_json::json!({
                     "email": email,
                     "redirect_port": port
@@ -53,7 +119,7 @@ pub async fn run_oauth_flow(server_url: &str, method: AuthMethod) -> Result<Stri
     };
 
     // Wait for callback with timeout (10 minutes for email)
-    wait_for_callback_with_fragment(listener).await
+    wait_for_callback_with_fragment(listener, &pages, banner).await
 }
 
 /// Wait for callback and handle URL fragment extraction.
@@ -63,10 +129,15 @@ pub async fn run_oauth_flow(server_url: &str, method: AuthMethod) -> Result<Stri
 /// 1. Serve an HTML page with JavaScript that reads the fragment
 /// 2. JavaScript redirects to us with the token in the query string
 /// 3. We read the token from the query string
-async fn wait_for_callback_with_fragment(listener: TcpListener) -> Result<String> {
-    // Load the lobster image once
-    let lobster_image = load_lobster_image();
-    
+///
+/// If the fragment has no token, the page redirects to `/error` instead and
+/// the flow fails with the provider's reason.
+async fn wait_for_callback_with_fragment(
+    listener: TcpListener,
+    pages: &AuthPages,
+    banner: Option<BannerImage>,
+) -> Result<String> {
+
     let token = tokio::time::timeout(Duration::from_secs(600), async {
         loop {
             let (mut socket, _) = listener.accept().await?;
@@ -78,15 +149,26 @@ async fn wait_for_callback_with_fragment(listener: TcpListener) -> Result<String
 
             // Check what's being requested
             if first_line.contains("GET /lobster.png") {
-                // Serve the lobster image
-                send_lobster_image(&mut socket, &lobster_image).await?;
+                // Serve the banner image
+                send_banner_image(&mut socket, banner.as_ref()).await?;
             } else if let Some(token) = try_parse_token_from_query(&request) {
                 // Got the token! Send success page.
-                send_success_response(&mut socket).await?;
+                send_page(&mut socket, "200 OK", &pages.render(Page::Success)).await?;
                 return Ok::<_, anyhow::Error>(token);
+            } else if first_line.starts_with("GET /error") || first_line.contains("error=") {
+                // No token in the fragment, or the provider reported an error
+                let reason = query_param(&request, "reason")
+                    .or_else(|| query_param(&request, "error_description"))
+                    .or_else(|| query_param(&request, "error"))
+                    .filter(|r| !r.is_empty());
+                send_page(&mut socket, "400 Bad Request", &pages.render(Page::Error(reason.as_deref()))).await?;
+                anyhow::bail!(
+                    "Authentication failed: {}",
+                    reason.as_deref().unwrap_or("no access token received")
+                );
             } else {
                 // Initial callback - serve the fragment extractor page
-                send_fragment_extractor(&mut socket).await?;
+                send_page(&mut socket, "200 OK", &pages.render(Page::FragmentExtractor)).await?;
                 // Continue waiting for the redirect with the token
             }
         }
@@ -96,37 +178,19 @@ async fn wait_for_callback_with_fragment(listener: TcpListener) -> Result<String
     Ok(token)
 }
 
-/// Load the lobster image from app folder
-fn load_lobster_image() -> Vec<u8> {
-    // Try to load from various possible paths
-    let paths = [
-        "pol.png",
-        "app/pol.png",
-    ];
-    
-    for path in paths {
-        if let Ok(data) = std::fs::read(path) {
-            return data;
-        }
-    }
-    
-    // Return empty if not found - we'll use emoji fallback
-    Vec::new()
-}
-
-/// Serve the lobster PNG image
-async fn send_lobster_image(socket: &mut tokio::net::TcpStream, image_data: &[u8]) -> Result<()> {
-    if image_data.is_empty() {
-        // 404 if no image
-        let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
-        socket.write_all(response.as_bytes()).await?;
-    } else {
+/// Serve the banner image (404 when missing so the page shows the emoji fallback)
+async fn send_banner_image(socket: &mut tokio::net::TcpStream, banner: Option<&BannerImage>) -> Result<()> {
+    if let Some((image_data, content_type)) = banner {
         let response = format!(
-            "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nCache-Control: max-age=3600\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: max-age=3600\r\nConnection: close\r\n\r\n",
+            content_type,
             image_data.len()
         );
         socket.write_all(response.as_bytes()).await?;
         socket.write_all(image_data).await?;
+    } else {
+        let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        socket.write_all(response.as_bytes()).await?;
     }
     socket.flush().await?;
     Ok(())
@@ -134,40 +198,102 @@ async fn send_lobster_image(socket: &mut tokio::net::TcpStream, image_data: &[u8
 
 /// Try to parse token from query string (not fragment)
 fn try_parse_token_from_query(request: &str) -> Option<String> {
-    let first_line = request.lines().next()?;
-    
     // Look for: GET /callback?access_token=xxx or GET /token?access_token=xxx
-    if !first_line.contains("access_token=") {
-        return None;
-    }
-    
+    query_param(request, "access_token")
+}
+
+/// Look up a query parameter in the request line.
+fn query_param(request: &str, name: &str) -> Option<String> {
+    let first_line = request.lines().next()?;
+
     let path_start = first_line.find('/')?;
     let path_end = first_line.rfind(" HTTP")?;
     let path = &first_line[path_start..path_end];
 
     // Extract query string (after ?)
     let query = path.split('?').nth(1)?;
-    
+
     // Parse query params
+    let prefix = format!("{}=", name);
     for param in query.split('&') {
-        if let Some(value) = param.strip_prefix("access_token=") {
-            return urlencoding::decode(value).ok().map(|s| s.into_owned());
+        if let Some(value) = param.strip_prefix(prefix.as_str()) {
+            return urlencoding::decode(&value.replace('+', " ")).ok().map(|s| s.into_owned());
         }
     }
-    
+
     None
 }
 
-/// Send HTML page that extracts the URL fragment and redirects with query params
-async fn send_fragment_extractor(socket: &mut tokio::net::TcpStream) -> Result<()> {
-    // This page runs JavaScript to:
-    // 1. Read the URL fragment (which contains the token)
-    // 2. Redirect to the same server with the token in the query string
-    let html = r#"<!DOCTYPE html>
-<html>
+/// Colour scheme of the browser pages served during login.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageTheme {
+    /// Follow the browser's `prefers-color-scheme`
+    #[default]
+    Auto,
+    Light,
+    Dark,
+}
+
+/// Branding and text of the browser pages served during login.
+///
+/// Every field can be overridden from `auth_pages` in config, which is how
+/// the pages are translated or rebranded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthPages {
+    /// Value of the `<html lang>` attribute
+    pub lang: String,
+    pub brand: String,
+    /// CSS hex colour, e.g. `#e74c3c`
+    pub accent_color: String,
+    pub theme: PageTheme,
+    pub authenticating_title: String,
+    pub authenticating_message: String,
+    pub success_title: String,
+    pub success_message: String,
+    pub error_title: String,
+    pub missing_token_message: String,
+    pub noscript_message: String,
+}
+
+impl Default for AuthPages {
+    fn default() -> Self {
+        Self {
+            lang: "en".to_string(),
+            brand: "Proof of Lobster".to_string(),
+            accent_color: DEFAULT_ACCENT.to_string(),
+            theme: PageTheme::Auto,
+            authenticating_title: "Completing authentication...".to_string(),
+            authenticating_message: "Please wait...".to_string(),
+            success_title: "Authentication Successful!".to_string(),
+            success_message: "You can close this tab and return to the terminal.".to_string(),
+            error_title: "Authentication Error".to_string(),
+            missing_token_message: "No access token was received. Return to the terminal and try again.".to_string(),
+            noscript_message: "JavaScript is required to finish signing in.".to_string(),
+        }
+    }
+}
+
+const DEFAULT_ACCENT: &str = "#e74c3c";
+
+/// Which callback page to render.
+enum Page<'a> {
+    /// Reads the URL fragment and redirects with the token in the query
+    FragmentExtractor,
+    Success,
+    /// Login failed; carries the provider's reason when it sent one
+    Error(Option<&'a str>),
+}
+
+const PAGE_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="{{lang}}">
 <head>
-    <title>Authenticating...</title>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>{{title}} · {{brand}}</title>
     <style>
+        :root { --accent: {{accent}}; }
         body {
             font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
             display: flex;
@@ -175,116 +301,143 @@ async fn send_fragment_extractor(socket: &mut tokio::net::TcpStream) -> Result<(
             align-items: center;
             height: 100vh;
             margin: 0;
-            background: linear-gradient(135deg, #e74c3c 0%, #c0392b 100%);
+            background: linear-gradient(135deg, var(--accent) 0%, color-mix(in srgb, var(--accent) 75%, black) 100%);
             color: white;
         }
         .container {
             text-align: center;
             padding: 2rem;
         }
-        .lobster { 
-            width: 150px; 
-            height: 150px; 
+        .lobster {
+            width: 150px;
+            height: 150px;
             margin-bottom: 1rem;
             image-rendering: pixelated;
         }
-        h1 { font-size: 1.5rem; margin-bottom: 1rem; }
-        p { font-size: 1rem; opacity: 0.9; }
+        .fallback { font-size: 6rem; margin-bottom: 1rem; display: none; }
+        h1 { font-size: 1.75rem; margin-bottom: 1rem; }
+        p { font-size: 1.1rem; opacity: 0.9; }
         .error { color: #ffcccc; }
+        .brand { font-size: 0.85rem; opacity: 0.6; margin-top: 2rem; }
+{{theme_css}}
     </style>
 </head>
 <body>
     <div class="container">
-        <img src="/lobster.png" alt="🦞" class="lobster" onerror="this.style.display='none';this.nextElementSibling.style.display='block'">
-        <div style="font-size: 6rem; margin-bottom: 1rem; display: none;">🦞</div>
-        <h1 id="status">Completing authentication...</h1>
-        <p id="message">Please wait...</p>
+        <img src="/lobster.png" alt="" class="lobster" onerror="this.style.display='none';this.nextElementSibling.style.display='block'">
+        <div class="fallback">🦞</div>
+        <h1>{{heading}}</h1>
+        <p class="{{message_class}}">{{message}}</p>
+        {{extra}}
+        <div class="brand">{{brand}}</div>
     </div>
-    <script>
+</body>
+</html>"#;
+
+const DARK_CSS: &str = r#"        body { background: #16161a; color: #e8e8e8; }
+        h1 { color: var(--accent); }
+        .error { color: #ff8a80; }"#;
+
+/// Reads the token from the URL fragment and hands it back to us in the
+/// query string; anything else goes to the error page.
+const FRAGMENT_SCRIPT: &str = r#"<script>
         (function() {
-            // Get the fragment (everything after #)
-            var hash = window.location.hash.substring(1);
-            
-            if (!hash) {
-                document.getElementById('status').textContent = 'Authentication Error';
-                document.getElementById('message').textContent = 'No authentication data received. Please try again.';
-                document.getElementById('message').className = 'error';
-                return;
-            }
-            
-            // Parse the fragment to get access_token
-            var params = new URLSearchParams(hash);
+            var params = new URLSearchParams(window.location.hash.substring(1));
             var accessToken = params.get('access_token');
-            
-            if (!accessToken) {
-                document.getElementById('status').textContent = 'Authentication Error';
-                document.getElementById('message').textContent = 'No access token found. Please try again.';
-                document.getElementById('message').className = 'error';
+            if (accessToken) {
+                window.location.replace('/token?access_token=' + encodeURIComponent(accessToken));
                 return;
             }
-            
-            // Redirect to the same server with token in query string
-            // This allows the server to read it
-            window.location.href = '/token?access_token=' + encodeURIComponent(accessToken);
+            var reason = params.get('error_description') || params.get('error') || '';
+            window.location.replace('/error?reason=' + encodeURIComponent(reason));
         })();
-    </script>
-</body>
-</html>"#;
+    </script>"#;
 
-    let response = format!(
-        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
-        html.len(),
-        html
-    );
+impl AuthPages {
+    /// Render one of the callback pages as a full HTML document.
+    fn render(&self, page: Page) -> String {
+        let (title, heading, message, message_class, extra) = match page {
+            Page::FragmentExtractor => (
+                &self.authenticating_title,
+                &self.authenticating_title,
+                html_escape(&self.authenticating_message),
+                "",
+                format!(
+                    "<noscript><p class=\"error\">{}</p></noscript>\n        {}",
+                    html_escape(&self.noscript_message),
+                    FRAGMENT_SCRIPT
+                ),
+            ),
+            Page::Success => (
+                &self.success_title,
+                &self.success_title,
+                html_escape(&self.success_message),
+                "",
+                String::new(),
+            ),
+            Page::Error(reason) => {
+                let message = match reason {
+                    Some(reason) => format!(
+                        "{}<br><small>{}</small>",
+                        html_escape(&self.missing_token_message),
+                        html_escape(reason)
+                    ),
+                    None => html_escape(&self.missing_token_message),
+                };
+                (&self.error_title, &self.error_title, message, "error", String::new())
+            }
+        };
 
-    socket.write_all(response.as_bytes()).await?;
-    socket.flush().await?;
-    Ok(())
+        let theme_css = match self.theme {
+            PageTheme::Light => String::new(),
+            PageTheme::Dark => DARK_CSS.to_string(),
+            PageTheme::Auto => format!("        @media (prefers-color-scheme: dark) {{\n{}\n        }}", DARK_CSS),
+        };
+
+        PAGE_TEMPLATE
+            .replace("{{lang}}", &html_escape(&self.lang))
+            .replace("{{accent}}", sanitize_color(&self.accent_color))
+            .replace("{{theme_css}}", &theme_css)
+            .replace("{{title}}", &html_escape(title))
+            .replace("{{brand}}", &html_escape(&self.brand))
+            .replace("{{heading}}", &html_escape(heading))
+            .replace("{{message_class}}", message_class)
+            .replace("{{message}}", &message)
+            .replace("{{extra}}", &extra)
+    }
 }
 
-/// Send success HTML response to browser
-async fn send_success_response(socket: &mut tokio::net::TcpStream) -> Result<()> {
-    let html = r#"<!DOCTYPE html>
-<html>
-<head>
-    <title>Authentication Successful</title>
-    <style>
-        body {
-            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
-            display: flex;
-            justify-content: center;
-            align-items: center;
-            height: 100vh;
-            margin: 0;
-            background: linear-gradient(135deg, #e74c3c 0%, #c0392b 100%);
-            color: white;
-        }
-        .container {
-            text-align: center;
-            padding: 2rem;
+/// Escape text for interpolation into HTML.
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
         }
-        .lobster { 
-            width: 150px; 
-            height: 150px; 
-            margin-bottom: 1rem;
-            image-rendering: pixelated;
-        }
-        h1 { font-size: 2rem; margin-bottom: 1rem; }
-        p { font-size: 1.1rem; opacity: 0.9; }
-    </style>
-</head>
-<body>
-    <div class="container">
-        <img src="/lobster.png" alt="🦞" class="lobster" onerror="this.style.display='none';this.nextElementSibling.style.display='block'">
-        <div style="font-size: 6rem; margin-bottom: 1rem; display: none;">🦞</div>
-        <h1>Authentication Successful!</h1>
-        <p>You can close this tab and return to the terminal.</p>
-    </div>
-</body>
-</html>"#;
+    }
+    out
+}
+
+/// Only allow `#rgb`/`#rrggbb` colours into the stylesheet.
+fn sanitize_color(color: &str) -> &str {
+    let hex = color.strip_prefix('#').unwrap_or("");
+    if matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        color
+    } else {
+        DEFAULT_ACCENT
+    }
+}
 
+/// Write an HTML page to the browser.
+async fn send_page(socket: &mut tokio::net::TcpStream, status: &str, html: &str) -> Result<()> {
     let response = format!(
-        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
         html.len(),
         html
     );
@@ -309,4 +462,57 @@ mod tests {
         let request = "GET /callback HTTP/1.1\r\nHost: localhost\r\n\r\n";
         assert!(try_parse_token_from_query(request).is_none());
     }
+
+    #[tokio::test]
+    async fn test_callback_listener_skips_taken_port() {
+        let taken = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = taken.local_addr().unwrap().port();
+        let config = CallbackListener {
+            port,
+            port_end: Some(port),
+            ..CallbackListener::default()
+        };
+        assert!(config.bind().await.is_err());
+
+        let config = CallbackListener {
+            port,
+            port_end: Some(port.saturating_add(1)),
+            ..CallbackListener::default()
+        };
+        // The next port may itself be busy on a shared machine
+        if let Ok(listener) = config.bind().await {
+            assert_eq!(listener.local_addr().unwrap().port(), port + 1);
+        }
+    }
+
+    #[test]
+    fn test_parse_error_reason() {
+        let request = "GET /error?reason=access%20denied+by+user HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        assert_eq!(query_param(request, "reason").unwrap(), "access denied by user");
+    }
+
+    #[test]
+    fn test_error_page_escapes_reason() {
+        let html = AuthPages::default().render(Page::Error(Some("<script>")));
+        assert!(html.contains("Authentication Error"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn test_page_branding_and_theme() {
+        let pages = AuthPages {
+            lang: "fr".to_string(),
+            accent_color: "red;}</style>".to_string(),
+            theme: PageTheme::Dark,
+            success_title: "Connexion réussie !".to_string(),
+            ..AuthPages::default()
+        };
+        let html = pages.render(Page::Success);
+        assert!(html.contains(r#"<html lang="fr">"#));
+        assert!(html.contains("Connexion réussie !"));
+        assert!(html.contains(&format!("--accent: {};", DEFAULT_ACCENT)));
+        assert!(html.contains("#16161a"));
+        assert!(!html.contains("prefers-color-scheme"));
+    }
 }
@@ -0,0 +1,254 @@
+//! Encrypted backups for moving to a new machine.
+//!
+//! A backup packs the wallets, `config.toml` with its agent bindings, the
+//! create draft, scheduled prompts, deployed versions and the run logs that
+//! hold the conversation history into one JSON bundle, sealed with a
+//! passphrase in the age format, so `age -d` opens it as well.
+//! `lobster restore <file>` unpacks it into this machine's directories.
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
+use std::path::{Component, Path, PathBuf};
+
+/// Layout of the bundle inside the encryption.
+const BUNDLE_VERSION: u32 = 1;
+
+/// Entries of the data directory that are backed up, files or directories.
+/// Artifacts, git checkouts and reports can be made again and are left out.
+const DATA_ENTRIES: [&str; 4] = ["runs", "scheduled.json", "versions.json", "versions"];
+
+/// Where `lobster-backup-<time>.age` files go.
+pub fn backups_dir() -> PathBuf {
+    crate::config::data_dir().join("backups")
+}
+
+/// Directory a backed-up file belongs under.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Root {
+    Config,
+    Data,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    root: Root,
+    /// Relative to the root, `/`-separated
+    path: String,
+    /// Base64 of the file
+    contents: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Bundle {
+    lobster_backup: u32,
+    /// Unix seconds
+    created_at: u64,
+    files: Vec<Entry>,
+}
+
+/// Files of the config directory that are backed up: the config, wallets of
+/// every network profile and the create draft. Logs and crash reports stay.
+fn is_config_file(name: &str) -> bool {
+    name == "config.toml" || name == "create_draft.json" || (name.starts_with("wallet") && name.ends_with(".json"))
+}
+
+fn entry(root: Root, path: String, file: &Path) -> Result<Entry> {
+    let bytes = std::fs::read(file).with_context(|| format!("Cannot read {}", file.display()))?;
+    Ok(Entry { root, path, contents: STANDARD.encode(bytes) })
+}
+
+/// Every file under `dir`, as `prefix/...` paths, in name order.
+fn collect_dir(dir: &Path, prefix: &str, files: &mut Vec<Entry>) -> Result<()> {
+    let mut children: Vec<_> = std::fs::read_dir(dir)
+        .with_context(|| format!("Cannot read {}", dir.display()))?
+        .filter_map(|e| e.ok())
+        .collect();
+    children.sort_by_key(|e| e.file_name());
+    for child in children {
+        let path = format!("{}/{}", prefix, child.file_name().to_string_lossy());
+        if child.path().is_dir() {
+            collect_dir(&child.path(), &path, files)?;
+        } else {
+            files.push(entry(Root::Data, path, &child.path())?);
+        }
+    }
+    Ok(())
+}
+
+fn collect(config_dir: &Path, data_dir: &Path) -> Result<Vec<Entry>> {
+    let mut files = Vec::new();
+    if let Ok(dir) = std::fs::read_dir(config_dir) {
+        let mut names: Vec<String> = dir
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_file())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .filter(|name| is_config_file(name))
+            .collect();
+        names.sort();
+        for name in names {
+            files.push(entry(Root::Config, name.clone(), &config_dir.join(&name))?);
+        }
+    }
+    for name in DATA_ENTRIES {
+        let path = data_dir.join(name);
+        if path.is_dir() {
+            collect_dir(&path, name, &mut files)?;
+        } else if path.is_file() {
+            files.push(entry(Root::Data, name.to_string(), &path)?);
+        }
+    }
+    if !files.iter().any(|f| f.root == Root::Config) {
+        bail!("There is nothing to back up in {}", config_dir.display());
+    }
+    Ok(files)
+}
+
+fn seal(passphrase: &str, bundle: &Bundle) -> Result<Vec<u8>> {
+    #[allow(unused_mut)]
+    let mut recipient = age::scrypt::Recipient::new(passphrase.to_string().into());
+    // The default targets a second of work, too slow for every test run
+    #[cfg(test)]
+    recipient.set_work_factor(10);
+    age::encrypt(&recipient, &serde_json::to_vec(bundle)?).context("Cannot encrypt the backup")
+}
+
+fn open(passphrase: &str, sealed: &[u8]) -> Result<Bundle> {
+    let identity = age::scrypt::Identity::new(passphrase.to_string().into());
+    let plain = age::decrypt(&identity, sealed).map_err(|e| match e {
+        age::DecryptError::DecryptionFailed | age::DecryptError::NoMatchingKeys => {
+            anyhow::anyhow!("The backup cannot be decrypted; is the passphrase right?")
+        }
+        e => anyhow::anyhow!("Not a lobster backup: {}", e),
+    })?;
+    let bundle: Bundle = serde_json::from_slice(&plain).context("Not a lobster backup")?;
+    if bundle.lobster_backup > BUNDLE_VERSION {
+        bail!("The backup was written by a newer lobster (this one reads up to version {}); update lobster", BUNDLE_VERSION);
+    }
+    Ok(bundle)
+}
+
+/// `path` under `root`, refusing anything that would land outside it.
+fn target(root: &Path, path: &str) -> Result<PathBuf> {
+    let relative = Path::new(path);
+    if path.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+        bail!("The backup holds an unsafe path: {}", path);
+    }
+    Ok(root.join(relative))
+}
+
+/// Write the bundle's files out, those of the config directory (tokens,
+/// mnemonics) readable by the owner only. Without `force`, files already
+/// here with other contents are named and nothing is written.
+fn unpack(bundle: &Bundle, config_dir: &Path, data_dir: &Path, force: bool) -> Result<usize> {
+    let mut files = Vec::new();
+    for file in &bundle.files {
+        let root = match file.root {
+            Root::Config => config_dir,
+            Root::Data => data_dir,
+        };
+        let contents = STANDARD.decode(&file.contents).with_context(|| format!("{} is damaged in the backup", file.path))?;
+        files.push((target(root, &file.path)?, contents, file.root));
+    }
+    if !force {
+        let clashes: Vec<String> = files
+            .iter()
+            .filter(|(path, contents, _)| std::fs::read(path).is_ok_and(|current| &current != contents))
+            .map(|(path, _, _)| path.display().to_string())
+            .collect();
+        if !clashes.is_empty() {
+            bail!("These files already exist with other contents: {}\nPass --force to replace them", clashes.join(", "));
+        }
+    }
+    for (path, contents, root) in &files {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Cannot create {}", parent.display()))?;
+        }
+        match root {
+            Root::Config => crate::secrets::write_private(path, contents)?,
+            Root::Data => std::fs::write(path, contents).with_context(|| format!("Cannot write {}", path.display()))?,
+        }
+    }
+    Ok(files.len())
+}
+
+/// Back up this machine to a new file in `backups_dir`, sealed with
+/// `passphrase`. Takes about a second, for the key derivation.
+pub fn export(passphrase: &str) -> Result<PathBuf> {
+    let created_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let files = collect(&crate::config::config_dir(), &crate::config::data_dir())?;
+    let sealed = seal(passphrase, &Bundle { lobster_backup: BUNDLE_VERSION, created_at, files })?;
+    let dir = backups_dir();
+    std::fs::create_dir_all(&dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+    let path = dir.join(format!("lobster-backup-{}.age", created_at));
+    crate::secrets::write_private(&path, &sealed)?;
+    Ok(path)
+}
+
+/// `lobster restore <file>`. The passphrase comes from `LOBSTER_PASSPHRASE`
+/// or is asked for on the terminal.
+pub fn restore(file: &Path, force: bool) -> Result<()> {
+    let sealed = std::fs::read(file).with_context(|| format!("Cannot read {}", file.display()))?;
+    let passphrase = match std::env::var("LOBSTER_PASSPHRASE") {
+        Ok(passphrase) => passphrase,
+        Err(_) if std::io::stdin().is_terminal() => {
+            rpassword::prompt_password("Passphrase of the backup: ").context("Cannot read the passphrase")?
+        }
+        Err(_) => bail!("Set LOBSTER_PASSPHRASE to restore without a terminal"),
+    };
+    let bundle = open(&passphrase, &sealed)?;
+    let config_dir = crate::config::config_dir();
+    let count = unpack(&bundle, &config_dir, &crate::config::data_dir(), force)?;
+    println!("Restored {} files from {} into {}", count, file.display(), config_dir.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_round_trip() {
+        let base = std::env::temp_dir().join(format!("lobster-backup-{}", std::process::id()));
+        let (config_dir, data_dir) = (base.join("config"), base.join("data"));
+        std::fs::create_dir_all(data_dir.join("runs")).unwrap();
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(config_dir.join("config.toml"), "agent_name = \"pinchy\"\n").unwrap();
+        std::fs::write(config_dir.join("wallet-testnet.json"), "{}").unwrap();
+        std::fs::write(config_dir.join("crash-1.txt"), "left behind").unwrap();
+        std::fs::write(data_dir.join("runs").join("5Agent-7.ndjson"), "{\"event\":1}\n").unwrap();
+
+        let files = collect(&config_dir, &data_dir).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["config.toml", "wallet-testnet.json", "runs/5Agent-7.ndjson"]);
+
+        let sealed = seal("correct horse", &Bundle { lobster_backup: BUNDLE_VERSION, created_at: 0, files }).unwrap();
+        assert!(!String::from_utf8_lossy(&sealed).contains("pinchy"));
+        assert!(open("wrong horse", &sealed).unwrap_err().to_string().contains("passphrase"));
+        let bundle = open("correct horse", &sealed).unwrap();
+
+        // A new machine gets every file; one that differs needs --force
+        let (new_config, new_data) = (base.join("new-config"), base.join("new-data"));
+        assert_eq!(unpack(&bundle, &new_config, &new_data, false).unwrap(), 3);
+        assert_eq!(std::fs::read_to_string(new_data.join("runs").join("5Agent-7.ndjson")).unwrap(), "{\"event\":1}\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(new_config.join("wallet-testnet.json")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_eq!(unpack(&bundle, &new_config, &new_data, false).unwrap(), 3);
+        std::fs::write(new_config.join("wallet-testnet.json"), "{\"other\":true}").unwrap();
+        assert!(unpack(&bundle, &new_config, &new_data, false).unwrap_err().to_string().contains("--force"));
+        unpack(&bundle, &new_config, &new_data, true).unwrap();
+        assert_eq!(std::fs::read_to_string(new_config.join("wallet-testnet.json")).unwrap(), "{}");
+
+        assert!(target(&base, "../escape").is_err());
+        assert!(target(&base, "/etc/passwd").is_err());
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+//! Wallet balance pushed by the gateway instead of polled.
+//!
+//! The gateway streams the balance over SSE whenever a block changes it. While
+//! the stream is up the balance poll is skipped; when it drops, polling takes
+//! over until a reconnect succeeds. Gateways without the endpoint (404) are
+//! polled for the rest of the session.
+
+use crate::{
+    app::AppMessage,
+    client::{ApiClient, BalanceResponse},
+};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// First reconnect delay; doubles up to `MAX_RETRY`.
+const FIRST_RETRY: Duration = Duration::from_secs(2);
+const MAX_RETRY: Duration = Duration::from_secs(60);
+
+/// Live subscription for one wallet on one gateway; dropping it disconnects.
+pub struct BalanceStream {
+    /// Gateway and wallet address this subscription is for
+    pub key: (String, String),
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl BalanceStream {
+    /// Subscribe to `address` and keep reconnecting until dropped. Sends
+    /// `BalanceUpdated` for changes and `BalanceStreamLive` on (dis)connects.
+    pub fn start(client: ApiClient, address: String, tx: mpsc::Sender<AppMessage>) -> Self {
+        let key = (client.base_url().to_string(), address.clone());
+        // Not counted as a background task: it runs for the whole session
+        let task = tokio::spawn(async move {
+            let mut retry = FIRST_RETRY;
+            let mut last: Option<BalanceResponse> = None;
+            loop {
+                match client.balance_events(&address).await {
+                    Ok(Some(resp)) => {
+                        let _ = tx.send(AppMessage::BalanceStreamLive(true)).await;
+                        retry = FIRST_RETRY;
+                        consume(resp, &mut last, &tx).await;
+                        tracing::info!("Balance stream closed, polling until it reconnects");
+                    }
+                    Ok(None) => {
+                        tracing::info!("Gateway has no balance stream, polling instead");
+                        let _ = tx.send(AppMessage::BalanceStreamLive(false)).await;
+                        return;
+                    }
+                    Err(e) => tracing::warn!("Balance stream failed: {:#}", e),
+                }
+                let _ = tx.send(AppMessage::BalanceStreamLive(false)).await;
+                tokio::time::sleep(retry).await;
+                retry = (retry * 2).min(MAX_RETRY);
+            }
+        });
+        Self { key, task }
+    }
+}
+
+impl Drop for BalanceStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Forward balance events until the stream ends, skipping repeats of `last`.
+async fn consume(resp: reqwest::Response, last: &mut Option<BalanceResponse>, tx: &mpsc::Sender<AppMessage>) {
+    use eventsource_stream::Eventsource;
+    use futures::StreamExt;
+
+    let mut stream = resp.bytes_stream().eventsource();
+    while let Some(event) = stream.next().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::warn!("Balance stream error: {}", e);
+                return;
+            }
+        };
+        match serde_json::from_str::<BalanceResponse>(&event.data) {
+            Ok(balance) if last.as_ref() != Some(&balance) => {
+                *last = Some(balance.clone());
+                let _ = tx.send(AppMessage::BalanceUpdated(balance)).await;
+            }
+            Ok(_) => {}
+            Err(e) => tracing::debug!(event = %event.event, "Ignoring balance event: {}", e),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Banner image loading for the home screen and auth callback pages.
+//!
+//! The banner defaults to the lobster art bundled into the binary but can be
+//! replaced with a user-supplied image (PNG, JPEG or animated GIF) or
+//! disabled in config.
+//!
+//! Decoding and the resize + encode for the terminal's graphics protocol
+//! are slow, so both run off the UI thread (see `App::prepare_banner`): the
+//! home screen draws without the image until its frames are encoded for the
+//! area it has. Encodings are kept per area size, so resizing the terminal
+//! back and forth doesn't redo them.
+
+use image::{codecs::gif::GifDecoder, AnimationDecoder, DynamicImage};
+use ratatui::layout::Rect;
+use ratatui_image::{picker::Picker, protocol::StatefulProtocol, Resize};
+use rust_embed::RustEmbed;
+use std::collections::VecDeque;
+use std::io::Cursor;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Shortest frame delay we honour; GIFs commonly declare 0ms frames.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(50);
+
+/// Area sizes whose encoded frames are kept, newest last.
+const CACHED_SIZES: usize = 4;
+
+/// Default lobster art embedded in the binary, so it works from any CWD.
+#[derive(RustEmbed)]
+#[folder = "."]
+#[include = "pol.png"]
+struct ArtAssets;
+
+/// Read the banner image bytes and their content type.
+///
+/// A configured custom path wins; otherwise the embedded lobster art is used.
+pub fn read_bytes(custom: Option<&str>) -> Option<(Vec<u8>, &'static str)> {
+    match custom {
+        Some(path) => {
+            let data = std::fs::read(path).ok()?;
+            Some((data, content_type(Path::new(path))))
+        }
+        None => ArtAssets::get("pol.png").map(|f| (f.data.into_owned(), "image/png")),
+    }
+}
+
+/// HTTP content type for an image path, based on its extension.
+fn content_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("gif") => "image/gif",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        _ => "image/png",
+    }
+}
+
+/// Banner frames resized and encoded for one area size.
+#[derive(Clone)]
+pub struct Encoded {
+    size: (u16, u16),
+    frames: Vec<StatefulProtocol>,
+}
+
+impl std::fmt::Debug for Encoded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Encoded({}x{}, {} frames)", self.size.0, self.size.1, self.frames.len())
+    }
+}
+
+/// Resize and encode `frames` to fit an area of `size`.
+fn encode(mut frames: Vec<StatefulProtocol>, size: (u16, u16)) -> Encoded {
+    let area = Rect::new(0, 0, size.0, size.1);
+    for frame in &mut frames {
+        if let Some(rect) = frame.needs_resize(&Resize::Fit(None), area) {
+            let background = frame.background_color();
+            frame.resize_encode(&Resize::Fit(None), background, rect);
+        }
+    }
+    Encoded { size, frames }
+}
+
+/// A decoded banner, possibly animated.
+#[derive(Clone)]
+pub struct Banner {
+    /// Frames at the image's own size, each with its delay
+    frames: Vec<(StatefulProtocol, Duration)>,
+    /// Encodings of recently drawn sizes, most recent last
+    encoded: VecDeque<Encoded>,
+    /// Frames being drawn: a fresh copy from `encoded`, so a graphics
+    /// protocol that sends the image once sends it again after a resize
+    active: Option<Encoded>,
+    /// Size drawn without encoded frames, to encode next
+    wanted: Option<(u16, u16)>,
+    /// Size being encoded in the background
+    encoding: Option<(u16, u16)>,
+    current: usize,
+    last_switch: Instant,
+}
+
+impl std::fmt::Debug for Banner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Banner({} frames)", self.frames.len())
+    }
+}
+
+impl Banner {
+    /// Load the custom banner, or the embedded lobster art.
+    ///
+    /// Animated GIFs keep every frame when `animate` is set; otherwise only
+    /// the first frame is used.
+    pub fn load(picker: &Picker, custom: Option<&str>, animate: bool) -> Option<Self> {
+        let (data, content_type) = read_bytes(custom)?;
+        let frames = if content_type == "image/gif" && animate {
+            decode_gif_frames(&data)?
+        } else {
+            vec![(image::load_from_memory(&data).ok()?, Duration::ZERO)]
+        };
+        if frames.is_empty() {
+            return None;
+        }
+
+        let frames = frames
+            .into_iter()
+            .map(|(img, delay)| (picker.new_resize_protocol(img), delay))
+            .collect();
+        Some(Self {
+            frames,
+            encoded: VecDeque::new(),
+            active: None,
+            wanted: None,
+            encoding: None,
+            current: 0,
+            last_switch: Instant::now(),
+        })
+    }
+
+    /// Whether the banner has more than one frame.
+    pub fn is_animated(&self) -> bool {
+        self.frames.len() > 1
+    }
+
+    /// The frame to draw in `area` now, advancing the animation when its
+    /// delay elapsed. None until the frames are encoded for the area's size;
+    /// `encode_job` then has the work to do.
+    pub fn frame(&mut self, area: Rect) -> Option<&mut StatefulProtocol> {
+        let size = (area.width, area.height);
+        if self.active.as_ref().map(|a| a.size) != Some(size) {
+            let Some(i) = self.encoded.iter().position(|e| e.size == size) else {
+                self.wanted = Some(size);
+                return None;
+            };
+            let encoded = self.encoded.remove(i)?;
+            self.active = Some(encoded.clone());
+            self.encoded.push_back(encoded);
+        }
+        if self.is_animated() {
+            let delay = self.frames[self.current].1.max(MIN_FRAME_DELAY);
+            if self.last_switch.elapsed() >= delay {
+                self.current = (self.current + 1) % self.frames.len();
+                self.last_switch = Instant::now();
+            }
+        }
+        self.active.as_mut().map(|a| &mut a.frames[self.current])
+    }
+
+    /// Encoding for the size last drawn without one, unless it is already
+    /// under way. Slow; run it off the UI thread and hand back the result
+    /// with `insert`.
+    pub fn encode_job(&mut self) -> Option<impl FnOnce() -> Encoded + Send + 'static> {
+        let size = self.wanted.take().filter(|&size| self.encoding != Some(size))?;
+        self.encoding = Some(size);
+        let frames: Vec<StatefulProtocol> = self.frames.iter().map(|(frame, _)| frame.clone()).collect();
+        Some(move || encode(frames, size))
+    }
+
+    /// Keep frames encoded by an `encode_job`, dropping the least recently
+    /// drawn size past `CACHED_SIZES`.
+    pub fn insert(&mut self, encoded: Encoded) {
+        if self.encoding == Some(encoded.size) {
+            self.encoding = None;
+        }
+        self.encoded.retain(|e| e.size != encoded.size);
+        self.encoded.push_back(encoded);
+        if self.encoded.len() > CACHED_SIZES {
+            self.encoded.pop_front();
+        }
+    }
+}
+
+fn decode_gif_frames(data: &[u8]) -> Option<Vec<(DynamicImage, Duration)>> {
+    let decoder = GifDecoder::new(Cursor::new(data)).ok()?;
+    let frames = decoder.into_frames().collect_frames().ok()?;
+    Some(
+        frames
+            .into_iter()
+            .map(|frame| {
+                let delay = Duration::from(frame.delay());
+                (DynamicImage::ImageRgba8(frame.into_buffer()), delay)
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_art_is_bundled() {
+        let (data, content_type) = read_bytes(None).expect("pol.png should be embedded");
+        assert!(!data.is_empty());
+        assert_eq!(content_type, "image/png");
+        assert!(image::load_from_memory(&data).is_ok());
+    }
+
+    #[test]
+    fn test_frames_encoded_per_size() {
+        let mut banner = Banner::load(&Picker::from_fontsize((8, 16)), None, false).unwrap();
+        let area = Rect::new(0, 0, 32, 16);
+        assert!(banner.frame(area).is_none());
+        let job = banner.encode_job().unwrap();
+        // Drawn again while it encodes: no second job for the same size
+        assert!(banner.frame(area).is_none());
+        assert!(banner.encode_job().is_none());
+        banner.insert(job());
+        assert!(banner.frame(area).is_some());
+
+        for width in 20..20 + CACHED_SIZES as u16 {
+            assert!(banner.frame(Rect::new(0, 0, width, 10)).is_none());
+            let job = banner.encode_job().unwrap();
+            banner.insert(job());
+        }
+        // The first size was drawn longest ago, so it was dropped
+        assert_eq!(banner.encoded.len(), CACHED_SIZES);
+        assert!(banner.frame(Rect::new(0, 0, 20, 10)).is_some());
+        assert!(banner.frame(area).is_none());
+    }
+
+    #[test]
+    fn test_content_type_from_extension() {
+        assert_eq!(content_type(Path::new("/tmp/art.GIF")), "image/gif");
+        assert_eq!(content_type(Path::new("art.jpeg")), "image/jpeg");
+    }
+}
@@ -1,11 +1,27 @@
 //! HTTP client for moltbook-server API.
 
+use crate::moltbook::{
+    AgentMeResponse, AgentProfile, Comment, CreatedPost, FeedPage, InboxItem, MoltbookClient, MoltbookResult,
+    RegisterResponse, Submolt,
+};
 use anyhow::Result;
+use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
 use thiserror::Error;
 
+/// Body of a failed response, logged with its URL and status.
+pub async fn error_body(resp: reqwest::Response) -> String {
+    let url = resp.url().clone();
+    let status = resp.status();
+    let body = crate::secrets::redact_json(&resp.text().await.unwrap_or_default());
+    tracing::warn!(%url, %status, %body, "request failed");
+    body
+}
+
 /// API error types.
 #[derive(Debug, Error)]
+#[allow(dead_code)]
 pub enum ApiError {
     #[error("Agent name already taken: {0}")]
     NameTaken(String),
@@ -17,15 +33,23 @@ pub enum ApiError {
     Request(#[from] reqwest::Error),
 }
 
-/// API client for moltbook-server.
+/// API client for moltbook-server: a cheap handle on the backend in use.
 #[derive(Clone)]
 pub struct ApiClient {
+    api: Arc<dyn Api>,
+}
+
+/// The gateway over HTTP, and Moltbook for what the app does as the agent.
+#[derive(Clone)]
+pub struct HttpApi {
     base_url: String,
     http: reqwest::Client,
     auth_token: Option<String>,
+    moltbook: MoltbookClient,
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct AuthMeResponse {
     pub user_id: String,
     pub has_wallet: bool,
@@ -33,24 +57,39 @@ pub struct AuthMeResponse {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct FundResponse {
     pub funded: bool,
     pub tx_hash: String,
     pub amount: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct FaucetResponse {
+    pub tx_hash: String,
+    /// Tokens sent, formatted like `balance_formatted`
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[allow(dead_code)]
 pub struct BalanceResponse {
     pub balance: String,
     pub balance_formatted: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainHeadResponse {
+    pub block_number: u64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct StoreAgentResponse {
     pub agent_id: String,
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct MoltbookStatusResponse {
     pub status: String,
     pub claimed: bool,
@@ -61,15 +100,24 @@ pub struct CompileResponse {
     pub success: bool,
     pub compiled_hex: Option<String>,
     pub errors: Vec<String>,
+    /// Warnings and optimization notes; the code compiled regardless
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct SubmitResponse {
     pub block_hash: String,
     pub block_number: u32,
     pub events: Vec<ChainEvent>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeeEstimateResponse {
+    /// Planck, as a decimal string
+    pub partial_fee: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ChainEvent {
     pub pallet: String,
@@ -78,20 +126,26 @@ pub struct ChainEvent {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct AgentInfo {
     pub chain_info: Option<ChainAgentInfo>,
     pub moltbook_info: Option<MoltbookAgentInfo>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct ChainAgentInfo {
     pub owner: String,
     pub name: String,
     pub active: bool,
     pub version: u32,
+    /// Blocks between scheduled (heartbeat) runs, when the agent has a schedule
+    #[serde(default)]
+    pub schedule_blocks: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct MoltbookAgentInfo {
     pub name: String,
     pub description: Option<String>,
@@ -100,6 +154,7 @@ pub struct MoltbookAgentInfo {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct MoltbookPost {
     pub id: String,
     #[serde(default)]
@@ -120,6 +175,7 @@ pub struct MoltbookPost {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct SubmoltInfo {
     pub name: String,
     #[serde(default)]
@@ -127,6 +183,7 @@ pub struct SubmoltInfo {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct AuthorInfo {
     pub name: String,
 }
@@ -134,6 +191,184 @@ pub struct AuthorInfo {
 #[derive(Debug, Deserialize)]
 pub struct PostsResponse {
     pub posts: Vec<MoltbookPost>,
+    /// Whether more posts exist past this page (absent on older servers).
+    #[serde(default)]
+    pub has_more: Option<bool>,
+}
+
+/// A run queued on-chain for an agent that hasn't started yet.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PendingRun {
+    pub run_id: u64,
+    /// Block the run was queued in
+    pub queued_block: u64,
+    /// Queued by the schedule rather than a prompt
+    #[serde(default)]
+    pub scheduled: bool,
+}
+
+/// The agent pallet's storage for one agent, decoded by the gateway.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgentState {
+    pub owner: String,
+    /// Hash of the stored program; None when no code is stored
+    #[serde(default)]
+    pub code_hash: Option<String>,
+    pub version: u32,
+    pub active: bool,
+    #[serde(default)]
+    pub schedule_blocks: Option<u32>,
+    /// Block the scheduler will queue the next heartbeat run in
+    #[serde(default)]
+    pub next_run_block: Option<u64>,
+    #[serde(default)]
+    pub pending_runs: Vec<PendingRun>,
+    /// Free balance of the agent account, in planck
+    pub balance: String,
+    /// Block the storage was read at
+    pub block_number: u64,
+}
+
+/// Posts an agent published on one day.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DailyPosts {
+    /// `YYYY-MM-DD`, UTC
+    pub date: String,
+    #[serde(default)]
+    pub posts: u32,
+}
+
+/// Posting totals of an agent over all its posts, not just a loaded page.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostStats {
+    pub total_posts: u32,
+    #[serde(default)]
+    pub total_upvotes: u64,
+    #[serde(default)]
+    pub total_comments: u64,
+    /// Oldest first, days without posts included
+    #[serde(default)]
+    pub daily: Vec<DailyPosts>,
+}
+
+/// Kind of on-chain event in an agent's activity log.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    RunStarted,
+    RunCompleted,
+    RunFailed,
+    HeartbeatScheduled,
+    /// Event types added server-side after this client was built
+    #[serde(other)]
+    Other,
+}
+
+/// One on-chain event for an agent.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActivityEvent {
+    pub kind: ActivityKind,
+    pub block_number: u64,
+    /// RFC 3339 block timestamp, when the server knows it
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    #[serde(default)]
+    pub run_id: Option<u64>,
+    /// Short human-readable detail (error message, schedule, ...)
+    #[serde(default)]
+    pub detail: Option<String>,
+    /// Planck the run was charged, on finished runs when the server knows it
+    #[serde(default)]
+    pub cost: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityResponse {
+    pub events: Vec<ActivityEvent>,
+}
+
+/// Outcome of a run in the agent's run history.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    Completed,
+    Failed,
+    #[serde(other)]
+    Other,
+}
+
+/// One run of an agent, prompted or scheduled.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunRecord {
+    pub run_id: u64,
+    pub status: RunStatus,
+    /// Started by the agent's schedule rather than a prompt
+    #[serde(default)]
+    pub scheduled: bool,
+    /// Why a failed run failed
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunsResponse {
+    pub runs: Vec<RunRecord>,
+}
+
+/// Rankings the server keeps across all agents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeaderboardKind {
+    /// Most upvoted on Moltbook, all time
+    Upvotes,
+    /// Most runs over the last seven days
+    ActiveWeek,
+}
+
+impl LeaderboardKind {
+    pub const ALL: [LeaderboardKind; 2] = [LeaderboardKind::Upvotes, LeaderboardKind::ActiveWeek];
+
+    /// Name of the board in the API.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LeaderboardKind::Upvotes => "upvotes",
+            LeaderboardKind::ActiveWeek => "active_week",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LeaderboardKind::Upvotes => "Most upvoted",
+            LeaderboardKind::ActiveWeek => "Most active this week",
+        }
+    }
+
+    /// What the score counts.
+    pub fn unit(self) -> &'static str {
+        match self {
+            LeaderboardKind::Upvotes => "upvotes",
+            LeaderboardKind::ActiveWeek => "runs",
+        }
+    }
+}
+
+/// An agent's place on a leaderboard.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub name: String,
+    #[serde(default)]
+    pub chain_address: Option<String>,
+    pub score: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LeaderboardResponse {
+    /// Top of the board, best first
+    pub entries: Vec<LeaderboardEntry>,
+    /// The requested agent's entry, also when it is below the top
+    #[serde(default)]
+    pub agent: Option<LeaderboardEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -149,8 +384,9 @@ pub struct AgentListItem {
 // ============================================================================
 
 /// Decoded chain event received via SSE.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[allow(dead_code)]
 pub enum ChainEventData {
     /// Agent run started
     RunStarted {
@@ -208,8 +444,9 @@ pub enum ChainEventData {
 }
 
 /// A message in the agent conversation.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "role", rename_all = "snake_case")]
+#[allow(dead_code)]
 pub enum ChatMessage {
     /// System prompt
     System { content: String },
@@ -232,40 +469,272 @@ pub enum ChatMessage {
 }
 
 /// Information about a tool call.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(dead_code)]
 pub struct ToolCallInfo {
     pub call_id: u64,
     pub name: String,
     pub arguments: String,
 }
 
+/// Gateway endpoints, implemented over HTTP by [`HttpApi`] and in-process by
+/// [`MockApi`](crate::mock::MockApi) for `--mock`. Screens only see
+/// [`ApiClient`], so tests hand them any implementation via `from_api`.
+#[async_trait]
+pub trait Api: Send + Sync {
+    fn base_url(&self) -> &str;
+
+    /// The same backend, sending `token` as the bearer token.
+    fn with_auth_token(&self, token: Option<String>) -> Arc<dyn Api>;
+
+    /// Moltbook API base the agent's own calls go to.
+    fn moltbook_url(&self) -> &str;
+
+    /// The same backend, with Moltbook at `api_url` (`moltbook_api_url`).
+    fn with_moltbook_url(&self, api_url: &str) -> Arc<dyn Api>;
+
+    /// Canned responses instead of a gateway.
+    fn is_mock(&self) -> bool {
+        false
+    }
+
+    /// Get current user info.
+    async fn get_me(&self) -> Result<AuthMeResponse>;
+
+    /// Get wallet balance (public endpoint, no auth required).
+    async fn get_balance(&self, address: &str) -> Result<BalanceResponse>;
+
+    /// Stream of balance changes for `address` (public endpoint, no auth
+    /// required). `None` when the gateway doesn't offer it.
+    async fn balance_events(&self, address: &str) -> Result<Option<reqwest::Response>>;
+
+    /// Latest block of the chain behind the gateway (public endpoint, no auth required).
+    async fn get_chain_head(&self) -> Result<ChainHeadResponse>;
+
+    /// Fund wallet.
+    async fn fund_wallet(&self, public_key: &str) -> Result<FundResponse>;
+
+    /// Ask a test network's faucet to send tokens to `address`.
+    async fn request_test_tokens(&self, address: &str) -> Result<FaucetResponse>;
+
+    /// Store an agent after TUI has registered with Moltbook directly.
+    async fn store_agent(
+        &self,
+        name: &str,
+        moltbook_api_key: &str,
+    ) -> Result<StoreAgentResponse>;
+
+    /// Update an agent's chain address after successful deployment.
+    async fn update_agent_address(
+        &self,
+        agent_id: &str,
+        chain_address: &str,
+    ) -> Result<()>;
+
+    /// Remove an agent from the user's server-side records.
+    async fn delete_agent(&self, agent_id: &str) -> Result<()>;
+
+    /// Get Moltbook claim status using the API key directly.
+    async fn get_moltbook_status(&self, api_key: &str) -> Result<MoltbookStatusResponse>;
+
+    /// Compile agent.
+    async fn compile(
+        &self,
+        agent_id: &str,
+        ship_file: &str,
+        soul_md: &str,
+        skill_md: &str,
+        heartbeat_md: &str,
+        schedule_blocks: Option<u32>,
+    ) -> Result<CompileResponse>;
+
+    /// Run agent files against a prompt without deploying anything. The server
+    /// compiles them and answers with the same SSE events as a real run; tool
+    /// calls are reported but not executed, so Moltbook is never touched.
+    async fn simulate(
+        &self,
+        ship_file: &str,
+        soul_md: &str,
+        skill_md: &str,
+        heartbeat_md: &str,
+        prompt: &str,
+    ) -> Result<reqwest::Response>;
+
+    /// Submit signed extrinsic.
+    async fn submit_extrinsic(&self, extrinsic_hex: &str) -> Result<SubmitResponse>;
+
+    /// Fee the chain would charge for a signed extrinsic, without submitting it.
+    async fn estimate_fee(&self, extrinsic_hex: &str) -> Result<FeeEstimateResponse>;
+
+    /// SSE stream of an agent run's events.
+    async fn run_events(&self, run_id: u64) -> Result<reqwest::Response>;
+
+    /// Get agent info.
+    async fn get_agent(&self, address: &str) -> Result<AgentInfo>;
+
+    /// Get a page of agent posts, newest first.
+    async fn get_posts(&self, address: &str, offset: u32, limit: u32) -> Result<PostsResponse>;
+
+    /// Get the agent's most recent on-chain events, newest first.
+    async fn get_activity(&self, address: &str, limit: u32) -> Result<ActivityResponse>;
+
+    /// The agent pallet's storage for the agent, read at the chain head.
+    async fn get_agent_state(&self, address: &str) -> Result<AgentState>;
+
+    /// Posting totals and posts per day over the last `days` days.
+    async fn get_post_stats(&self, address: &str, days: u32) -> Result<PostStats>;
+
+    /// Get the agent's most recent runs with their outcome, newest first.
+    async fn get_runs(&self, address: &str, limit: u32) -> Result<RunsResponse>;
+
+    /// Get the top of a leaderboard, plus where `agent` ranks when given.
+    async fn get_leaderboard(
+        &self,
+        kind: LeaderboardKind,
+        limit: u32,
+        agent: Option<&str>,
+    ) -> Result<LeaderboardResponse>;
+
+    /// List user's agents.
+    async fn list_agents(&self) -> Result<Vec<AgentListItem>>;
+
+    /// Build deploy extrinsic data (server builds call data, TUI signs).
+    async fn build_deploy(
+        &self,
+        compiled_hex: &str,
+        salt_hex: &str,
+        signer_address: &str,
+        value: u128,
+    ) -> Result<BuildExtrinsicResponse>;
+
+    /// Build a balance transfer extrinsic from the signer to `dest`.
+    async fn build_transfer(
+        &self,
+        dest: &str,
+        value: u128,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse>;
+
+    /// Build an extrinsic that pauses (`active = false`) or resumes the agent's scheduling.
+    async fn build_set_active(
+        &self,
+        agent_address: &str,
+        active: bool,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse>;
+
+    /// Build an extrinsic that withdraws the agent account's free balance to its owner.
+    async fn build_withdraw(&self, agent_address: &str, signer_address: &str) -> Result<BuildExtrinsicResponse>;
+
+    /// Build an extrinsic that hands the agent to `new_owner`.
+    async fn build_transfer_ownership(
+        &self,
+        agent_address: &str,
+        new_owner: &str,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse>;
+
+    /// Build call_agent extrinsic data.
+    async fn build_call(
+        &self,
+        agent_address: &str,
+        input: &str,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse>;
+
+    // Moltbook itself, called from this machine as the agent whose API key is
+    // given (see `crate::moltbook`)
+
+    /// Register a new agent with Moltbook.
+    async fn register_agent(&self, name: &str, description: &str) -> MoltbookResult<RegisterResponse>;
+
+    /// The agent an existing API key belongs to.
+    async fn get_moltbook_agent(&self, api_key: &str) -> MoltbookResult<AgentMeResponse>;
+
+    /// A page of the agent's home feed (subscribed submolts and followed agents).
+    async fn get_feed(&self, api_key: &str, sort: &str, offset: u32, limit: u32) -> MoltbookResult<FeedPage>;
+
+    /// Submolts, as seen by the agent.
+    async fn list_submolts(&self, api_key: &str) -> MoltbookResult<Vec<Submolt>>;
+
+    async fn subscribe_submolt(&self, api_key: &str, name: &str) -> MoltbookResult<()>;
+
+    async fn unsubscribe_submolt(&self, api_key: &str, name: &str) -> MoltbookResult<()>;
+
+    /// Publish a new post as the agent.
+    async fn create_post(&self, api_key: &str, submolt: &str, title: &str, content: &str) -> MoltbookResult<CreatedPost>;
+
+    async fn upvote_post(&self, api_key: &str, post_id: &str) -> MoltbookResult<()>;
+
+    async fn downvote_post(&self, api_key: &str, post_id: &str) -> MoltbookResult<()>;
+
+    async fn create_comment(&self, api_key: &str, post_id: &str, content: &str) -> MoltbookResult<()>;
+
+    /// The comment tree of a post, best first; reading works without an API key.
+    async fn get_comments(&self, api_key: Option<&str>, post_id: &str) -> MoltbookResult<Vec<Comment>>;
+
+    /// The agent's inbox, newest first.
+    async fn get_inbox(&self, api_key: &str, limit: u32) -> MoltbookResult<Vec<InboxItem>>;
+
+    async fn mark_inbox_read(&self, api_key: &str, ids: &[String]) -> MoltbookResult<()>;
+
+    async fn get_profile(&self, api_key: &str) -> MoltbookResult<AgentProfile>;
+
+    /// Update the agent's profile, returning it as stored by Moltbook.
+    async fn update_profile(&self, api_key: &str, profile: &AgentProfile) -> MoltbookResult<AgentProfile>;
+}
+
 impl ApiClient {
+    /// Client for the gateway at `base_url`.
     pub fn new(base_url: String) -> Self {
-        Self {
-            base_url,
-            http: reqwest::Client::new(),
-            auth_token: None,
-        }
+        Self::from_api(Arc::new(HttpApi::new(base_url)))
+    }
+
+    /// Client answered in-process with canned data, for `--mock`.
+    pub fn mock() -> Self {
+        Self::from_api(Arc::new(crate::mock::MockApi::new(crate::mock::EVENT_DELAY)))
+    }
+
+    /// Client over any backend, e.g. a test fake.
+    pub fn from_api(api: Arc<dyn Api>) -> Self {
+        Self { api }
+    }
+
+    /// Talk to Moltbook at `api_url` (`moltbook_api_url`) instead of its public API.
+    pub fn with_moltbook_url(self, api_url: &str) -> Self {
+        Self { api: self.api.with_moltbook_url(api_url) }
     }
 
     pub fn set_auth_token(&mut self, token: String) {
-        self.auth_token = Some(token);
+        self.api = self.api.with_auth_token(Some(token));
     }
 
     pub fn clear_auth_token(&mut self) {
-        self.auth_token = None;
+        self.api = self.api.with_auth_token(None);
     }
+}
 
-    pub fn base_url(&self) -> &str {
-        &self.base_url
+impl std::ops::Deref for ApiClient {
+    type Target = dyn Api;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.api
     }
+}
 
-    pub fn auth_token(&self) -> Option<&str> {
-        self.auth_token.as_deref()
+impl HttpApi {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            base_url,
+            http: reqwest::Client::new(),
+            auth_token: None,
+            moltbook: MoltbookClient::new(crate::moltbook::MOLTBOOK_API_BASE),
+        }
     }
 
     async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
         let url = format!("{}{}", self.base_url, path);
+        tracing::debug!(%url, "GET");
         let mut req = self.http.get(&url);
 
         if let Some(token) = &self.auth_token {
@@ -275,7 +744,7 @@ impl ApiClient {
         let resp = req.send().await?;
 
         if !resp.status().is_success() {
-            let error = resp.text().await.unwrap_or_default();
+            let error = error_body(resp).await;
             anyhow::bail!("API error: {}", error);
         }
 
@@ -284,6 +753,7 @@ impl ApiClient {
 
     async fn post<T: DeserializeOwned, B: Serialize>(&self, path: &str, body: &B) -> Result<T> {
         let url = format!("{}{}", self.base_url, path);
+        tracing::debug!(%url, "POST");
         let mut req = self.http.post(&url).json(body);
 
         if let Some(token) = &self.auth_token {
@@ -293,7 +763,7 @@ impl ApiClient {
         let resp = req.send().await?;
 
         if !resp.status().is_success() {
-            let error = resp.text().await.unwrap_or_default();
+            let error = error_body(resp).await;
             anyhow::bail!("API error: {}", error);
         }
 
@@ -301,37 +771,78 @@ impl ApiClient {
     }
 
     /// Get OAuth URL for login.
+    #[allow(dead_code)]
     pub async fn get_auth_url(&self, redirect_port: u16) -> Result<String> {
         self.get(&format!("/auth/url?redirect_port={}", redirect_port))
             .await
     }
+}
 
-    /// Get current user info.
-    pub async fn get_me(&self) -> Result<AuthMeResponse> {
+#[async_trait]
+impl Api for HttpApi {
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+
+    fn with_auth_token(&self, token: Option<String>) -> Arc<dyn Api> {
+        Arc::new(Self { auth_token: token, ..self.clone() })
+    }
+
+    fn moltbook_url(&self) -> &str {
+        self.moltbook.base_url()
+    }
+
+    fn with_moltbook_url(&self, api_url: &str) -> Arc<dyn Api> {
+        Arc::new(Self { moltbook: MoltbookClient::new(api_url), ..self.clone() })
+    }
+
+    async fn get_me(&self) -> Result<AuthMeResponse> {
         self.get("/auth/me").await
     }
 
-    /// Get wallet balance (public endpoint, no auth required).
-    pub async fn get_balance(&self, address: &str) -> Result<BalanceResponse> {
+    async fn get_balance(&self, address: &str) -> Result<BalanceResponse> {
         let url = format!("{}/chain/balance?address={}", self.base_url, urlencoding::encode(address));
         let resp = self.http.get(&url).send().await?;
         
         if !resp.status().is_success() {
-            let error = resp.text().await.unwrap_or_default();
+            let error = error_body(resp).await;
             anyhow::bail!("API error: {}", error);
         }
         
         Ok(resp.json().await?)
     }
 
-    /// Fund wallet.
-    pub async fn fund_wallet(&self, public_key: &str) -> Result<FundResponse> {
+    async fn balance_events(&self, address: &str) -> Result<Option<reqwest::Response>> {
+        let url = format!("{}/chain/balance/events?address={}", self.base_url, urlencoding::encode(address));
+        let resp = self.http.get(&url).header("Accept", "text/event-stream").send().await?;
+
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            let error = error_body(resp).await;
+            anyhow::bail!("API error: {}", error);
+        }
+
+        Ok(Some(resp))
+    }
+
+    async fn get_chain_head(&self) -> Result<ChainHeadResponse> {
+        self.get("/chain/head").await
+    }
+
+    async fn fund_wallet(&self, public_key: &str) -> Result<FundResponse> {
         self.post("/auth/fund", &serde_json::json!({ "public_key": public_key }))
             .await
     }
 
-    /// Store an agent after TUI has registered with Moltbook directly.
-    pub async fn store_agent(
+    async fn request_test_tokens(&self, address: &str) -> Result<FaucetResponse> {
+        self.post("/chain/faucet", &serde_json::json!({ "address": address }))
+            .await
+    }
+
+    async fn store_agent(
         &self,
         name: &str,
         moltbook_api_key: &str,
@@ -346,8 +857,7 @@ impl ApiClient {
         .await
     }
 
-    /// Update an agent's chain address after successful deployment.
-    pub async fn update_agent_address(
+    async fn update_agent_address(
         &self,
         agent_id: &str,
         chain_address: &str,
@@ -369,15 +879,34 @@ impl ApiClient {
             .await?;
 
         if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
+            let error = error_body(response).await;
             anyhow::bail!("Failed to update agent address: {}", error);
         }
 
         Ok(())
     }
 
-    /// Get Moltbook claim status using the API key directly.
-    pub async fn get_moltbook_status(&self, api_key: &str) -> Result<MoltbookStatusResponse> {
+    async fn delete_agent(&self, agent_id: &str) -> Result<()> {
+        let url = format!("{}/agents/{}", self.base_url, urlencoding::encode(agent_id));
+        let response = self
+            .http
+            .delete(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.auth_token.as_deref().unwrap_or("")),
+            )
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = error_body(response).await;
+            anyhow::bail!("Failed to delete agent: {}", error);
+        }
+
+        Ok(())
+    }
+
+    async fn get_moltbook_status(&self, api_key: &str) -> Result<MoltbookStatusResponse> {
         self.post(
             "/agents/moltbook-status",
             &serde_json::json!({
@@ -387,8 +916,7 @@ impl ApiClient {
         .await
     }
 
-    /// Compile agent.
-    pub async fn compile(
+    async fn compile(
         &self,
         agent_id: &str,
         ship_file: &str,
@@ -419,15 +947,47 @@ impl ApiClient {
         let resp = req.send().await?;
 
         if !resp.status().is_success() {
-            let error = resp.text().await.unwrap_or_default();
+            let error = error_body(resp).await;
             anyhow::bail!("API error: {}", error);
         }
 
         Ok(resp.json().await?)
     }
 
-    /// Submit signed extrinsic.
-    pub async fn submit_extrinsic(&self, extrinsic_hex: &str) -> Result<SubmitResponse> {
+    async fn simulate(
+        &self,
+        ship_file: &str,
+        soul_md: &str,
+        skill_md: &str,
+        heartbeat_md: &str,
+        prompt: &str,
+    ) -> Result<reqwest::Response> {
+        let url = format!("{}/agents/simulate", self.base_url);
+
+        let form = reqwest::multipart::Form::new()
+            .text("ship_file", ship_file.to_string())
+            .text("soul_md", soul_md.to_string())
+            .text("skill_md", skill_md.to_string())
+            .text("heartbeat_md", heartbeat_md.to_string())
+            .text("prompt", prompt.to_string());
+
+        let mut req = self.http.post(&url).header("Accept", "text/event-stream").multipart(form);
+
+        if let Some(token) = &self.auth_token {
+            req = req.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let resp = req.send().await?;
+
+        if !resp.status().is_success() {
+            let error = error_body(resp).await;
+            anyhow::bail!("API error: {}", error);
+        }
+
+        Ok(resp)
+    }
+
+    async fn submit_extrinsic(&self, extrinsic_hex: &str) -> Result<SubmitResponse> {
         self.post(
             "/chain/submit",
             &serde_json::json!({ "extrinsic_hex": extrinsic_hex }),
@@ -435,23 +995,73 @@ impl ApiClient {
         .await
     }
 
-    /// Get agent info.
-    pub async fn get_agent(&self, address: &str) -> Result<AgentInfo> {
+    async fn estimate_fee(&self, extrinsic_hex: &str) -> Result<FeeEstimateResponse> {
+        self.post("/chain/fee", &serde_json::json!({ "extrinsic_hex": extrinsic_hex })).await
+    }
+
+    async fn run_events(&self, run_id: u64) -> Result<reqwest::Response> {
+        let url = format!("{}/chain/events/{}", self.base_url, run_id);
+        let mut req = self.http.get(&url);
+
+        if let Some(token) = &self.auth_token {
+            req = req.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let resp = req.send().await?;
+
+        if !resp.status().is_success() {
+            anyhow::bail!("SSE connection error: {}", resp.status());
+        }
+
+        Ok(resp)
+    }
+
+    async fn get_agent(&self, address: &str) -> Result<AgentInfo> {
         self.get(&format!("/agents/{}", address)).await
     }
 
-    /// Get agent posts.
-    pub async fn get_posts(&self, address: &str) -> Result<PostsResponse> {
-        self.get(&format!("/agents/{}/posts", address)).await
+    async fn get_posts(&self, address: &str, offset: u32, limit: u32) -> Result<PostsResponse> {
+        self.get(&format!(
+            "/agents/{}/posts?offset={}&limit={}",
+            address, offset, limit
+        ))
+        .await
     }
 
-    /// List user's agents.
-    pub async fn list_agents(&self) -> Result<Vec<AgentListItem>> {
+    async fn get_activity(&self, address: &str, limit: u32) -> Result<ActivityResponse> {
+        self.get(&format!("/agents/{}/activity?limit={}", address, limit)).await
+    }
+
+    async fn get_agent_state(&self, address: &str) -> Result<AgentState> {
+        self.get(&format!("/agents/{}/state", address)).await
+    }
+
+    async fn get_post_stats(&self, address: &str, days: u32) -> Result<PostStats> {
+        self.get(&format!("/agents/{}/posts/stats?days={}", address, days)).await
+    }
+
+    async fn get_runs(&self, address: &str, limit: u32) -> Result<RunsResponse> {
+        self.get(&format!("/agents/{}/runs?limit={}", address, limit)).await
+    }
+
+    async fn get_leaderboard(
+        &self,
+        kind: LeaderboardKind,
+        limit: u32,
+        agent: Option<&str>,
+    ) -> Result<LeaderboardResponse> {
+        let mut path = format!("/leaderboard?board={}&limit={}", kind.as_str(), limit);
+        if let Some(agent) = agent {
+            path.push_str(&format!("&agent={}", agent));
+        }
+        self.get(&path).await
+    }
+
+    async fn list_agents(&self) -> Result<Vec<AgentListItem>> {
         self.get("/agents").await
     }
 
-    /// Build deploy extrinsic data (server builds call data, TUI signs).
-    pub async fn build_deploy(
+    async fn build_deploy(
         &self,
         compiled_hex: &str,
         salt_hex: &str,
@@ -470,8 +1080,69 @@ impl ApiClient {
         .await
     }
 
-    /// Build call_agent extrinsic data.
-    pub async fn build_call(
+    async fn build_transfer(
+        &self,
+        dest: &str,
+        value: u128,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.post(
+            "/chain/build-transfer",
+            &serde_json::json!({
+                "dest": dest,
+                "value": value,
+                "signer_address": signer_address,
+            }),
+        )
+        .await
+    }
+
+    async fn build_set_active(
+        &self,
+        agent_address: &str,
+        active: bool,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.post(
+            "/chain/build-set-active",
+            &serde_json::json!({
+                "agent_address": agent_address,
+                "active": active,
+                "signer_address": signer_address,
+            }),
+        )
+        .await
+    }
+
+    async fn build_withdraw(&self, agent_address: &str, signer_address: &str) -> Result<BuildExtrinsicResponse> {
+        self.post(
+            "/chain/build-withdraw",
+            &serde_json::json!({
+                "agent_address": agent_address,
+                "signer_address": signer_address,
+            }),
+        )
+        .await
+    }
+
+    async fn build_transfer_ownership(
+        &self,
+        agent_address: &str,
+        new_owner: &str,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.post(
+            "/chain/build-transfer-ownership",
+            &serde_json::json!({
+                "agent_address": agent_address,
+                "new_owner": new_owner,
+                "signer_address": signer_address,
+            }),
+        )
+        .await
+    }
+
+    async fn build_call(
         &self,
         agent_address: &str,
         input: &str,
@@ -487,6 +1158,66 @@ impl ApiClient {
         )
         .await
     }
+
+    async fn register_agent(&self, name: &str, description: &str) -> MoltbookResult<RegisterResponse> {
+        self.moltbook.register_agent(name, description).await
+    }
+
+    async fn get_moltbook_agent(&self, api_key: &str) -> MoltbookResult<AgentMeResponse> {
+        self.moltbook.get_agent_info(api_key).await
+    }
+
+    async fn get_feed(&self, api_key: &str, sort: &str, offset: u32, limit: u32) -> MoltbookResult<FeedPage> {
+        self.moltbook.get_feed(api_key, sort, offset, limit).await
+    }
+
+    async fn list_submolts(&self, api_key: &str) -> MoltbookResult<Vec<Submolt>> {
+        self.moltbook.list_submolts(api_key).await
+    }
+
+    async fn subscribe_submolt(&self, api_key: &str, name: &str) -> MoltbookResult<()> {
+        self.moltbook.subscribe_submolt(api_key, name).await
+    }
+
+    async fn unsubscribe_submolt(&self, api_key: &str, name: &str) -> MoltbookResult<()> {
+        self.moltbook.unsubscribe_submolt(api_key, name).await
+    }
+
+    async fn create_post(&self, api_key: &str, submolt: &str, title: &str, content: &str) -> MoltbookResult<CreatedPost> {
+        self.moltbook.create_post(api_key, submolt, title, content).await
+    }
+
+    async fn upvote_post(&self, api_key: &str, post_id: &str) -> MoltbookResult<()> {
+        self.moltbook.upvote_post(api_key, post_id).await
+    }
+
+    async fn downvote_post(&self, api_key: &str, post_id: &str) -> MoltbookResult<()> {
+        self.moltbook.downvote_post(api_key, post_id).await
+    }
+
+    async fn create_comment(&self, api_key: &str, post_id: &str, content: &str) -> MoltbookResult<()> {
+        self.moltbook.create_comment(api_key, post_id, content).await
+    }
+
+    async fn get_comments(&self, api_key: Option<&str>, post_id: &str) -> MoltbookResult<Vec<Comment>> {
+        self.moltbook.get_comments(api_key, post_id).await
+    }
+
+    async fn get_inbox(&self, api_key: &str, limit: u32) -> MoltbookResult<Vec<InboxItem>> {
+        self.moltbook.get_inbox(api_key, limit).await
+    }
+
+    async fn mark_inbox_read(&self, api_key: &str, ids: &[String]) -> MoltbookResult<()> {
+        self.moltbook.mark_inbox_read(api_key, ids).await
+    }
+
+    async fn get_profile(&self, api_key: &str) -> MoltbookResult<AgentProfile> {
+        self.moltbook.get_profile(api_key).await
+    }
+
+    async fn update_profile(&self, api_key: &str, profile: &AgentProfile) -> MoltbookResult<AgentProfile> {
+        self.moltbook.update_profile(api_key, profile).await
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
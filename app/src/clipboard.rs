@@ -0,0 +1,70 @@
+//! Copy text to the clipboard through the terminal (OSC 52), and read it
+//! with the platform's paste command.
+//!
+//! The terminal owns the clipboard, so copying works over SSH without any
+//! platform clipboard libraries. Most modern terminals support it; tmux
+//! needs `set -g set-clipboard on`. Terminals don't hand the clipboard back
+//! reliably, so reading runs `pbpaste`, `wl-paste`, `xclip`, `xsel` or
+//! PowerShell and only sees the clipboard of the machine lobster runs on.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::io::{self, Write};
+use std::process::Stdio;
+use std::time::Duration;
+
+/// How long a paste command gets before the clipboard counts as unreadable.
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Escape sequence asking the terminal to put `text` on the clipboard.
+fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+/// Ask the terminal to copy `text`. Terminals do not report back, so success
+/// only means the request was written.
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52(text).as_bytes())?;
+    stdout.flush()
+}
+
+/// Paste commands to try, most likely first.
+fn paste_commands() -> Vec<&'static [&'static str]> {
+    if cfg!(target_os = "macos") {
+        vec![&["pbpaste"]]
+    } else if cfg!(windows) {
+        vec![&["powershell", "-NoProfile", "-Command", "Get-Clipboard"]]
+    } else {
+        let mut commands: Vec<&[&str]> = vec![&["xclip", "-selection", "clipboard", "-o"], &["xsel", "--clipboard", "--output"]];
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.insert(0, &["wl-paste", "--no-newline"]);
+        }
+        commands
+    }
+}
+
+/// The clipboard's text, or None when it is empty or no paste command works.
+pub async fn read() -> Option<String> {
+    for command in paste_commands() {
+        let mut paste = tokio::process::Command::new(command[0]);
+        paste.args(&command[1..]).stdin(Stdio::null()).stderr(Stdio::null()).kill_on_drop(true);
+        let Ok(Ok(output)) = tokio::time::timeout(READ_TIMEOUT, paste.output()).await else {
+            continue;
+        };
+        if output.status.success() {
+            let text = String::from_utf8_lossy(&output.stdout).into_owned();
+            return Some(text).filter(|t| !t.trim().is_empty());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_encodes_text() {
+        assert_eq!(osc52("lobster"), "\x1b]52;c;bG9ic3Rlcg==\x07");
+    }
+}
@@ -1,14 +1,31 @@
 //! Local configuration management for Proof of Lobster.
+//!
+//! Settings live in `config.toml`, versioned by `config_version`. A
+//! `config.json` from earlier releases is converted on first load and kept
+//! as `config.json.bak`. Loading names the key at fault for syntax errors,
+//! wrong types, unknown keys and values that can't work.
 
 use crate::agent_assets::AgentSource;
-use anyhow::Result;
+use crate::auth::{AuthPages, CallbackListener};
+use crate::daemon::DaemonConfig;
+use crate::spending::SpendingLimits;
+use crate::theme::ThemeColors;
+use crate::webhook::Webhook;
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 /// Application configuration stored locally.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
+    /// Layout version of the file (see `CONFIG_VERSION`)
+    #[serde(default)]
+    pub config_version: u32,
+
     /// Server URL
+    #[serde(default)]
     pub server_url: String,
 
     /// Auth token from Supabase
@@ -20,39 +37,771 @@ pub struct AppConfig {
     /// Agent name
     pub agent_name: Option<String>,
 
-    /// Custom agent directory path. If None, use embedded defaults.
+    /// Moltbook API key of the deployed agent, used to act as the agent
+    #[serde(default)]
+    pub moltbook_api_key: Option<String>,
+
+    /// Wallet file of the active network profile in the config directory;
+    /// unset is `wallet.json`.
+    #[serde(default)]
+    pub wallet_file: Option<String>,
+
+    /// Login, wallet and agent of the network profiles not active right now,
+    /// by profile name (or server URL outside every profile).
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileBinding>,
+
+    /// Custom agent directory path, or a git URL (`url#ref:subdir`) cloned
+    /// on use. If None, use embedded defaults.
     #[serde(default)]
     pub custom_agent_dir: Option<String>,
+
+    /// Custom banner image (PNG, JPEG or GIF). If None, use the lobster art.
+    #[serde(default)]
+    pub banner_image: Option<String>,
+
+    /// Hide the banner image on the home screen and auth pages.
+    #[serde(default)]
+    pub banner_disabled: bool,
+
+    /// Leave the banner off the home screen, giving its rows to the status
+    /// and menu (toggled with `B` on home).
+    #[serde(default)]
+    pub banner_hidden: bool,
+
+    /// Cycle through frames of animated banner images.
+    #[serde(default = "default_true")]
+    pub banner_animate: bool,
+
+    /// Branding, text and theme of the browser pages shown during login.
+    #[serde(default)]
+    pub auth_pages: AuthPages,
+
+    /// Bind address and port range of the local auth callback listener.
+    #[serde(default)]
+    pub auth_callback: CallbackListener,
+
+    /// Block explorer account page, with `{address}` replaced by the SS58 address.
+    #[serde(default)]
+    pub explorer_url: Option<String>,
+
+    /// Block explorer block page, with `{block}` replaced by the block hash.
+    #[serde(default)]
+    pub explorer_block_url: Option<String>,
+
+    /// Seconds between automatic reloads of the view screen (0 disables, unset uses the default).
+    #[serde(default)]
+    pub view_refresh_secs: Option<u64>,
+
+    /// Seconds between session validity checks (unset uses the default).
+    #[serde(default)]
+    pub session_check_secs: Option<u64>,
+
+    /// Seconds between wallet balance fetches (unset uses the default).
+    #[serde(default)]
+    pub balance_refresh_secs: Option<u64>,
+
+    /// Seconds between automatic claim checks while the create wizard waits
+    /// for the Twitter verification (0 disables, unset uses the default).
+    #[serde(default)]
+    pub claim_poll_secs: Option<u64>,
+
+    /// Seconds a run's event stream may stay silent before it counts as
+    /// stalled (0 disables, unset uses the default).
+    #[serde(default)]
+    pub stream_stall_secs: Option<u64>,
+
+    /// Token amount to keep in the wallet and agent account for fees (unset uses the default).
+    #[serde(default)]
+    pub min_balance: Option<String>,
+
+    /// JSON endpoint with the token's USD price, for approximate values next to balances.
+    #[serde(default)]
+    pub price_feed_url: Option<String>,
+
+    /// Moltbook API base, e.g. a staging or mock server (unset is Moltbook's own).
+    #[serde(default)]
+    pub moltbook_api_url: Option<String>,
+
+    /// Seconds between price feed fetches (unset uses the default).
+    #[serde(default)]
+    pub price_refresh_secs: Option<u64>,
+
+    /// Color theme of the TUI.
+    #[serde(default)]
+    pub theme: ThemeName,
+
+    /// Per-role color overrides on top of the theme preset.
+    #[serde(default)]
+    pub colors: ThemeColors,
+
+    /// Unicode or ASCII icons and box drawing.
+    #[serde(default)]
+    pub glyphs: GlyphMode,
+
+    /// Remappable global keys.
+    #[serde(default)]
+    pub keybindings: KeyBindings,
+
+    /// Named networks to switch between. Empty uses the built-in `local` profile.
+    #[serde(default)]
+    pub networks: Vec<NetworkProfile>,
+
+    /// Finished or skipped the first-run walkthrough. Configs written before it
+    /// existed lack the field and count as onboarded; only a fresh config starts it.
+    #[serde(default = "default_true")]
+    pub onboarded: bool,
+
+    /// Confirmed having written down the wallet recovery phrase.
+    #[serde(default)]
+    pub wallet_backed_up: bool,
+
+    /// Write auth tokens and Moltbook API keys encrypted with a passphrase
+    /// (see `secrets`). Off unless opted in.
+    #[serde(default)]
+    pub encrypt_secrets: bool,
+
+    /// Minutes without input before the screen is blanked and locked (0 or
+    /// unset disables). Unlocked with `lock_pin` or the secrets' passphrase,
+    /// and never engaged in a run that has neither.
+    #[serde(default)]
+    pub idle_lock_minutes: Option<u64>,
+
+    /// PIN that unlocks the idle lock, hashed (see `secrets::hash_pin`).
+    #[serde(default)]
+    pub lock_pin: Option<String>,
+
+    /// Ask GitHub for a newer release on startup. Off unless opted in.
+    #[serde(default)]
+    pub check_updates: bool,
+
+    /// Desktop notifications for finished runs and low balances while the
+    /// terminal is in the background. Off unless opted in.
+    #[serde(default)]
+    pub desktop_notifications: bool,
+
+    /// URLs POSTed to on deploys, finished or failed runs and a low agent balance.
+    #[serde(default)]
+    pub webhooks: Vec<Webhook>,
+
+    /// Append every decoded event of a run to an NDJSON file under the data directory.
+    #[serde(default)]
+    pub run_logs: bool,
+
+    /// Poll period and top-up limits of `lobster daemon`.
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+
+    /// Amounts over which deposits and top-ups from the TUI need their
+    /// amount typed to confirm.
+    #[serde(default)]
+    pub spending_limits: SpendingLimits,
+
+    /// Diagnostics log level or filter directives (unset uses the default).
+    #[serde(default)]
+    pub log_level: Option<String>,
+
+    /// Prompts saved for quick-send, by agent address.
+    #[serde(default)]
+    pub favorite_prompts: BTreeMap<String, Vec<String>>,
+}
+
+/// What belongs to one network: the login on its gateway, the wallet
+/// signing there and the agent deployed to it. The active profile's binding
+/// is in `AppConfig`'s top-level fields.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileBinding {
+    #[serde(default)]
+    pub auth_token: Option<String>,
+    #[serde(default)]
+    pub wallet_file: Option<String>,
+    #[serde(default)]
+    pub agent_address: Option<String>,
+    #[serde(default)]
+    pub agent_name: Option<String>,
+    #[serde(default)]
+    pub moltbook_api_key: Option<String>,
+}
+
+/// Which kind of chain a network profile points at, shown as a header badge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NetworkKind {
+    Local,
+    Testnet,
+    Mainnet,
+    /// A server URL that matches no profile
+    #[default]
+    Custom,
+}
+
+impl NetworkKind {
+    pub fn badge(self) -> &'static str {
+        match self {
+            NetworkKind::Local => "LOCAL",
+            NetworkKind::Testnet => "TESTNET",
+            NetworkKind::Mainnet => "MAINNET",
+            NetworkKind::Custom => "CUSTOM",
+        }
+    }
+
+    /// Test chains whose gateway hands out free tokens.
+    pub fn has_faucet(self) -> bool {
+        matches!(self, NetworkKind::Local | NetworkKind::Testnet)
+    }
+}
+
+/// A named network: gateway plus the chain parameters needed to show amounts and addresses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    pub name: String,
+    #[serde(default)]
+    pub kind: NetworkKind,
+    pub server_url: String,
+    #[serde(default = "default_ss58_prefix")]
+    pub ss58_prefix: u16,
+    #[serde(default = "default_token_symbol")]
+    pub token_symbol: String,
+    /// At most `MAX_DECIMALS`, which `AppConfig::validate` checks
+    #[serde(default = "default_decimals")]
+    pub decimals: u8,
+    /// Block explorer account page, overriding the top-level `explorer_url`.
+    #[serde(default)]
+    pub explorer_url: Option<String>,
+    /// Block explorer block page, overriding the top-level `explorer_block_url`.
+    #[serde(default)]
+    pub explorer_block_url: Option<String>,
+    /// Custom banner image, overriding the top-level `banner_image`.
+    #[serde(default)]
+    pub banner_image: Option<String>,
+    /// Overrides the top-level `banner_disabled`.
+    #[serde(default)]
+    pub banner_disabled: Option<bool>,
+    /// Overrides the top-level `banner_animate`.
+    #[serde(default)]
+    pub banner_animate: Option<bool>,
+}
+
+impl NetworkProfile {
+    /// Local development gateway, the only built-in profile.
+    pub fn local() -> Self {
+        Self::custom("local", DEFAULT_SERVER_URL).with_kind(NetworkKind::Local)
+    }
+
+    /// Profile for a server URL with default chain parameters.
+    fn custom(name: &str, server_url: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: NetworkKind::Custom,
+            server_url: server_url.to_string(),
+            ss58_prefix: default_ss58_prefix(),
+            token_symbol: default_token_symbol(),
+            decimals: default_decimals(),
+            explorer_url: None,
+            explorer_block_url: None,
+            banner_image: None,
+            banner_disabled: None,
+            banner_animate: None,
+        }
+    }
+
+    fn with_kind(mut self, kind: NetworkKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Planck per whole token.
+    pub fn unit(&self) -> u128 {
+        // Validated profiles stay far below the u128 limit
+        10u128.saturating_pow(self.decimals as u32)
+    }
+}
+
+/// Color theme presets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    /// Terminal default colors only, for monochrome terminals or NO_COLOR users
+    NoColor,
+    /// Blue and orange for status, for red-green (green-weak) color blindness
+    Deuteranopia,
+    /// Blue and yellow for status, for red-green (red-weak) color blindness
+    Protanopia,
+    /// Bright colors on the dark background
+    HighContrast,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 6] = [
+        ThemeName::Dark,
+        ThemeName::Light,
+        ThemeName::NoColor,
+        ThemeName::Deuteranopia,
+        ThemeName::Protanopia,
+        ThemeName::HighContrast,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
+            ThemeName::NoColor => "no-color",
+            ThemeName::Deuteranopia => "deuteranopia",
+            ThemeName::Protanopia => "protanopia",
+            ThemeName::HighContrast => "high-contrast",
+        }
+    }
+}
+
+/// Glyph set for icons, spinners and borders.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GlyphMode {
+    /// ASCII unless the locale is UTF-8 (see `glyphs::use_ascii`)
+    #[default]
+    Auto,
+    Unicode,
+    Ascii,
+}
+
+impl GlyphMode {
+    pub const ALL: [GlyphMode; 3] = [GlyphMode::Auto, GlyphMode::Unicode, GlyphMode::Ascii];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GlyphMode::Auto => "auto",
+            GlyphMode::Unicode => "unicode",
+            GlyphMode::Ascii => "ascii",
+        }
+    }
+}
+
+/// Navigation keys layered over each screen's own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyPreset {
+    /// Arrow keys, j/k and each screen's letters only
+    #[default]
+    Default,
+    /// hjkl, gg/G, / and : outside text fields (see `keymap::vim_key`)
+    Vim,
+}
+
+impl KeyPreset {
+    pub const ALL: [KeyPreset; 2] = [KeyPreset::Default, KeyPreset::Vim];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyPreset::Default => "default",
+            KeyPreset::Vim => "vim",
+        }
+    }
+}
+
+/// Global key bindings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    /// Quit from the home screen
+    pub quit: char,
+    pub preset: KeyPreset,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self { quit: 'q', preset: KeyPreset::Default }
+    }
+}
+
+/// Layout version of the config file this build writes. Older files are
+/// migrated on load; newer ones are refused rather than half understood.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Backend used when neither `--server` nor the config sets one.
+pub const DEFAULT_SERVER_URL: &str = "http://localhost:8080";
+
+/// View screen auto-refresh period when `view_refresh_secs` is unset.
+pub const DEFAULT_VIEW_REFRESH_SECS: u64 = 30;
+
+/// Session check period when `session_check_secs` is unset.
+pub const DEFAULT_SESSION_CHECK_SECS: u64 = 30;
+
+/// Balance fetch period when `balance_refresh_secs` is unset (~2 blocks).
+pub const DEFAULT_BALANCE_REFRESH_SECS: u64 = 12;
+
+/// Claim check period in the create wizard when `claim_poll_secs` is unset.
+pub const DEFAULT_CLAIM_POLL_SECS: u64 = 10;
+
+/// Silence on a run's event stream before it counts as stalled, when
+/// `stream_stall_secs` is unset. Tool calls can take a while.
+pub const DEFAULT_STREAM_STALL_SECS: u64 = 120;
+
+/// Shortest accepted polling period, to keep the server from being hammered.
+pub const MIN_POLL_SECS: u64 = 5;
+
+/// Shortest accepted stall timeout, above the time one tool call takes.
+pub const MIN_STREAM_STALL_SECS: u64 = 30;
+
+/// Minimum wallet and agent balance, in tokens, when `min_balance` is unset.
+pub const DEFAULT_MIN_BALANCE: &str = "1";
+
+/// Price feed fetch period when `price_refresh_secs` is unset.
+pub const DEFAULT_PRICE_REFRESH_SECS: u64 = 300;
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_ss58_prefix() -> u16 {
+    42
+}
+
+fn default_token_symbol() -> String {
+    "THE".to_string()
+}
+
+fn default_decimals() -> u8 {
+    12
+}
+
+/// Most decimals a network's token may have. Amounts are u128 planck, and
+/// this leaves room for a hundred million whole tokens.
+pub const MAX_DECIMALS: u8 = 30;
+
+/// Config directory chosen in-process by `set_config_dir`.
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `dir` in place of `LOBSTER_CONFIG_DIR` from now on, without changing
+/// the environment other threads read. Only the first call takes effect.
+pub fn set_config_dir(dir: PathBuf) {
+    let _ = CONFIG_DIR.set(dir);
+}
+
+fn config_dir_override() -> Option<PathBuf> {
+    CONFIG_DIR.get().cloned().or_else(|| std::env::var_os("LOBSTER_CONFIG_DIR").map(PathBuf::from))
+}
+
+/// Directory holding config.toml and the wallet files.
+///
+/// `LOBSTER_CONFIG_DIR` overrides the platform config directory, which keeps
+/// test runs and side-by-side installs from touching the real files.
+pub fn config_dir() -> PathBuf {
+    match config_dir_override() {
+        Some(dir) => dir,
+        None => dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("proof-of-lobster"),
+    }
+}
+
+/// Directory for what lobster records (run logs), as opposed to settings.
+///
+/// `LOBSTER_DATA_DIR` overrides it. Otherwise a `LOBSTER_CONFIG_DIR` override
+/// holds the data too, so test runs and side-by-side installs stay apart.
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("LOBSTER_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+    match config_dir_override() {
+        Some(dir) => dir,
+        None => dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("proof-of-lobster"),
+    }
+}
+
+/// Keys a settings bundle leaves out: secrets, the login, wallet and agents
+/// of this machine, and its own choices. Webhook URLs carry their tokens;
+/// the Moltbook API URL receives the API key, and the spending limits and
+/// daemon top-ups decide what the wallet sends.
+const UNSHARED_KEYS: [&str; 16] = [
+    "auth_token",
+    "agent_address",
+    "agent_name",
+    "moltbook_api_key",
+    "moltbook_api_url",
+    "spending_limits",
+    "daemon",
+    "wallet_file",
+    "profiles",
+    "webhooks",
+    "favorite_prompts",
+    "banner_image",
+    "encrypt_secrets",
+    "lock_pin",
+    "onboarded",
+    "wallet_backed_up",
+];
+
+/// Bring a config of layout `version` up to `CONFIG_VERSION`, one version
+/// at a time. Version 0 is the JSON config, whose keys version 1 kept.
+/// Version 2 gave network profiles banner settings, left unset so they
+/// follow the top-level ones.
+fn migrate(mut value: serde_json::Value, version: i64) -> Result<serde_json::Value> {
+    if version > CONFIG_VERSION as i64 {
+        bail!("config_version {} was written by a newer lobster (this one reads up to {}); update lobster", version, CONFIG_VERSION);
+    }
+    let Some(table) = value.as_object_mut() else { bail!("the config must be a table of keys") };
+    if version < 1 {
+        table.insert("config_version".to_string(), 1.into());
+    }
+    if version < 2 {
+        table.insert("config_version".to_string(), 2.into());
+    }
+    Ok(value)
 }
 
 impl AppConfig {
     /// Get the config file path.
     pub fn path() -> PathBuf {
-        dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("proof-of-lobster")
-            .join("config.json")
+        config_dir().join("config.toml")
+    }
+
+    /// JSON config written by releases before `config.toml`.
+    fn legacy_path() -> PathBuf {
+        config_dir().join("config.json")
     }
 
-    /// Load config from disk.
+    /// Load config from disk, converting a `config.json` left by an earlier
+    /// release.
     pub fn load() -> Result<Self> {
         let path = Self::path();
         if path.exists() {
             let contents = std::fs::read_to_string(&path)?;
-            Ok(serde_json::from_str(&contents)?)
+            let mut config = Self::from_toml(&contents).with_context(|| format!("{} is not a valid config", path.display()))?;
+            config.open_secrets()?;
+            return Ok(config);
+        }
+        let legacy = Self::legacy_path();
+        if legacy.exists() {
+            return Self::migrate_json(&legacy);
+        }
+        Ok(Self { config_version: CONFIG_VERSION, ..Self::default() })
+    }
+
+    /// Rewrite the JSON config as `config.toml`, keeping the original as
+    /// `config.json.bak`. Keys no release reads any more are dropped.
+    fn migrate_json(legacy: &std::path::Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(legacy)?;
+        let value: serde_json::Value = serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not valid JSON", legacy.display()))?;
+        let version = value.get("config_version").and_then(|v| v.as_i64()).unwrap_or(0);
+        let (config, unknown) = Self::deserialize(migrate(value, version)?)
+            .and_then(|(config, unknown)| Ok((config.checked()?, unknown)))
+            .with_context(|| format!("{} is not a valid config", legacy.display()))?;
+        for key in unknown {
+            tracing::warn!(key, "dropping an unknown key of the old config");
+        }
+        config.save()?;
+        let backup = legacy.with_extension("json.bak");
+        std::fs::rename(legacy, &backup).with_context(|| format!("Cannot move {} aside", legacy.display()))?;
+        tracing::info!(from = %legacy.display(), to = %Self::path().display(), "migrated the config to TOML");
+        Ok(config)
+    }
+
+    /// Parse `config.toml`. Syntax errors and values of the wrong type come
+    /// with the line and key from the TOML parser; unknown keys and values
+    /// that can't work are named here.
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let table: toml::Table = toml::from_str(contents)?;
+        let version = table.get("config_version").and_then(toml::Value::as_integer).unwrap_or(0);
+        let (config, unknown) = if version == CONFIG_VERSION as i64 {
+            Self::deserialize(toml::Deserializer::parse(contents)?)?
         } else {
-            Ok(Self::default())
+            Self::deserialize(migrate(serde_json::to_value(table)?, version)?)?
+        };
+        if let Some(key) = unknown.first() {
+            bail!("unknown key `{}`", key);
+        }
+        config.checked()
+    }
+
+    /// The config in `source`, with the keys it has no field for.
+    fn deserialize<'de, D>(source: D) -> Result<(Self, Vec<String>)>
+    where
+        D: serde::Deserializer<'de>,
+        D::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let mut unknown = Vec::new();
+        let config = serde_ignored::deserialize(source, |path| unknown.push(path.to_string()))?;
+        Ok((config, unknown))
+    }
+
+    /// The config if `validate` finds nothing wrong, at the current version.
+    fn checked(self) -> Result<Self> {
+        if let Err((key, problem)) = self.validate() {
+            bail!("`{}` {}", key, problem);
         }
+        Ok(Self { config_version: CONFIG_VERSION, ..self })
     }
 
-    /// Save config to disk.
+    /// Values that parse but can't work, as the offending key and what is
+    /// wrong with it.
+    pub fn validate(&self) -> Result<(), (String, String)> {
+        let web_url = |key: String, url: &str| {
+            if url.starts_with("http://") || url.starts_with("https://") {
+                Ok(())
+            } else {
+                Err((key, format!("must start with http:// or https://, not \"{}\"", url)))
+            }
+        };
+        if !self.server_url.is_empty() {
+            web_url("server_url".to_string(), &self.server_url)?;
+        }
+        let mut names = std::collections::BTreeSet::new();
+        for (i, network) in self.networks.iter().enumerate() {
+            if network.name.trim().is_empty() {
+                return Err((format!("networks[{}].name", i), "must not be empty".to_string()));
+            }
+            if !names.insert(network.name.as_str()) {
+                return Err((format!("networks[{}].name", i), format!("\"{}\" is used by an earlier network", network.name)));
+            }
+            web_url(format!("networks[{}].server_url", i), &network.server_url)?;
+            if network.decimals > MAX_DECIMALS {
+                return Err((format!("networks[{}].decimals", i), format!("must be at most {}, not {}", MAX_DECIMALS, network.decimals)));
+            }
+        }
+        let decimals = self.network().decimals;
+        let amounts = [
+            ("min_balance", self.min_balance.as_deref()),
+            ("daemon.top_up_amount", self.daemon.top_up_amount.as_deref()),
+            ("daemon.max_top_up_per_day", self.daemon.max_top_up_per_day.as_deref()),
+            ("spending_limits.per_transaction", self.spending_limits.per_transaction.as_deref()),
+            ("spending_limits.per_day", self.spending_limits.per_day.as_deref()),
+        ];
+        for (key, amount) in amounts {
+            if let Some(amount) = amount.filter(|a| crate::funds::parse_tokens(a, decimals).is_none()) {
+                return Err((key.to_string(), format!("must be a token amount with at most {} decimals, not \"{}\"", decimals, amount)));
+            }
+        }
+        if let Some(url) = &self.price_feed_url {
+            web_url("price_feed_url".to_string(), url)?;
+        }
+        if let Some(url) = &self.moltbook_api_url {
+            web_url("moltbook_api_url".to_string(), url)?;
+        }
+        // (key, value, shortest accepted, whether 0 turns it off)
+        let periods = [
+            ("session_check_secs", self.session_check_secs, MIN_POLL_SECS, false),
+            ("balance_refresh_secs", self.balance_refresh_secs, MIN_POLL_SECS, false),
+            ("price_refresh_secs", self.price_refresh_secs, MIN_POLL_SECS, false),
+            ("view_refresh_secs", self.view_refresh_secs, MIN_POLL_SECS, true),
+            ("claim_poll_secs", self.claim_poll_secs, MIN_POLL_SECS, true),
+            ("stream_stall_secs", self.stream_stall_secs, MIN_STREAM_STALL_SECS, true),
+        ];
+        for (key, secs, min, allow_off) in periods {
+            if let Some(secs) = secs.filter(|&secs| secs < min && !(secs == 0 && allow_off)) {
+                let off = if allow_off { " (or 0 to turn it off)" } else { "" };
+                return Err((key.to_string(), format!("must be at least {} seconds{}, not {}", min, off, secs)));
+            }
+        }
+        for (i, webhook) in self.webhooks.iter().enumerate() {
+            web_url(format!("webhooks[{}].url", i), &webhook.url)?;
+        }
+        if self.keybindings.quit.is_ascii_digit() {
+            return Err(("keybindings.quit".to_string(), "can't be a digit (those select menu items)".to_string()));
+        }
+        if self.keybindings.preset == KeyPreset::Vim && crate::keymap::VIM_KEYS.contains(&self.keybindings.quit) {
+            return Err(("keybindings.quit".to_string(), "is taken by the vim preset".to_string()));
+        }
+        Ok(())
+    }
+
+    /// `lobster config export`: the settings changed from their defaults,
+    /// as TOML without secrets or anything tied to this machine.
+    pub fn export_bundle(&self) -> Result<String> {
+        // Banner paths are files on this machine, like the top-level `banner_image`
+        let networks = self.networks.iter().map(|n| NetworkProfile { banner_image: None, ..n.clone() }).collect();
+        let mut table = toml::Table::try_from(Self { config_version: CONFIG_VERSION, networks, ..self.clone() })?;
+        let defaults = toml::Table::try_from(Self::from_toml("")?)?;
+        table.retain(|key, value| {
+            !UNSHARED_KEYS.contains(&key) && (key == "config_version" || defaults.get(key) != Some(&*value))
+        });
+        // A git URL is the same everywhere, a directory only here
+        if self.custom_agent_dir.as_deref().is_some_and(|dir| crate::git_source::GitSource::parse(dir).is_none()) {
+            table.remove("custom_agent_dir");
+        }
+        Ok(format!("# lobster settings; apply with `lobster config import <file>`\n{}", toml::to_string_pretty(&table)?))
+    }
+
+    /// `lobster config import`: take over each setting `bundle` has, keeping
+    /// the rest along with the login, wallet and agents. A new `server_url`
+    /// switches profiles the way the Settings screen does. Returns the keys
+    /// taken over and those skipped as not shareable.
+    pub fn import_bundle(&mut self, bundle: &str) -> Result<(Vec<String>, Vec<String>)> {
+        let keys: Vec<String> = toml::from_str::<toml::Table>(bundle)?.into_iter().map(|(key, _)| key).collect();
+        let incoming = toml::Table::try_from(Self::from_toml(bundle)?)?;
+        let mut merged = toml::Table::try_from(&*self)?;
+        let (mut taken, mut skipped) = (Vec::new(), Vec::new());
+        for key in keys.into_iter().filter(|key| key != "config_version") {
+            if UNSHARED_KEYS.contains(&key.as_str()) {
+                skipped.push(key);
+                continue;
+            }
+            // The server is switched to below, once the rest is in place
+            if let Some(value) = incoming.get(&key).filter(|_| key != "server_url") {
+                merged.insert(key.clone(), value.clone());
+            }
+            taken.push(key);
+        }
+        let mut config: Self = merged.try_into()?;
+        let server_url = incoming.get("server_url").and_then(toml::Value::as_str).filter(|url| !url.is_empty());
+        if let Some(url) = server_url.filter(|url| *url != self.server_url) {
+            config.switch_server(url.to_string());
+        }
+        *self = config.checked()?;
+        Ok((taken, skipped))
+    }
+
+    /// Secrets `encrypt_secrets` covers, by key: the active login and
+    /// Moltbook API key and those of every other profile.
+    fn secrets_mut(&mut self) -> Vec<(String, &mut Option<String>)> {
+        let mut secrets = vec![
+            ("auth_token".to_string(), &mut self.auth_token),
+            ("moltbook_api_key".to_string(), &mut self.moltbook_api_key),
+        ];
+        for (name, binding) in self.profiles.iter_mut() {
+            secrets.push((format!("profiles.{}.auth_token", name), &mut binding.auth_token));
+            secrets.push((format!("profiles.{}.moltbook_api_key", name), &mut binding.moltbook_api_key));
+        }
+        secrets
+    }
+
+    /// Decrypt the secrets that were written encrypted. With
+    /// `encrypt_secrets` on, the passphrase is taken now even when nothing is
+    /// encrypted yet, so a save from inside the TUI never has to ask for it.
+    fn open_secrets(&mut self) -> Result<()> {
+        if self.encrypt_secrets {
+            crate::secrets::passphrase()?;
+        }
+        for (key, secret) in self.secrets_mut() {
+            if let Some(sealed) = secret.as_deref().filter(|value| crate::secrets::is_sealed(value)) {
+                let plain = crate::secrets::open(sealed).map_err(|e| anyhow!("`{}` {:#}", key, e))?;
+                *secret = Some(plain);
+            }
+        }
+        Ok(())
+    }
+
+    /// Save config to disk, readable by the owner only.
     pub fn save(&self) -> Result<()> {
         let path = Self::path();
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
-        Ok(())
+        let mut config = Self { config_version: CONFIG_VERSION, ..self.clone() };
+        if config.encrypt_secrets {
+            for (key, secret) in config.secrets_mut() {
+                if let Some(plain) = secret.as_deref() {
+                    let sealed = crate::secrets::seal(plain).with_context(|| format!("Cannot encrypt `{}`", key))?;
+                    *secret = Some(sealed);
+                }
+            }
+        }
+        crate::secrets::write_private(&path, toml::to_string_pretty(&config)?.as_bytes())
     }
 
     /// Check if user is authenticated.
@@ -60,6 +809,31 @@ impl AppConfig {
         self.auth_token.is_some()
     }
 
+    /// Favorite prompts of `agent_address`, oldest first.
+    pub fn favorite_prompts(&self, agent_address: &str) -> &[String] {
+        self.favorite_prompts.get(agent_address).map_or(&[], Vec::as_slice)
+    }
+
+    /// Add `prompt` to the agent's favorites, or remove it if it is one;
+    /// returns whether it is a favorite now.
+    pub fn toggle_favorite_prompt(&mut self, agent_address: &str, prompt: &str) -> bool {
+        let favorites = self.favorite_prompts.entry(agent_address.to_string()).or_default();
+        let added = match favorites.iter().position(|p| p == prompt) {
+            Some(i) => {
+                favorites.remove(i);
+                false
+            }
+            None => {
+                favorites.push(prompt.to_string());
+                true
+            }
+        };
+        if favorites.is_empty() {
+            self.favorite_prompts.remove(agent_address);
+        }
+        added
+    }
+
     /// Check if user has a deployed agent.
     pub fn has_agent(&self) -> bool {
         self.agent_address.is_some()
@@ -69,15 +843,352 @@ impl AppConfig {
     pub fn logout(&mut self) {
         self.auth_token = None;
         // Agent data is tied to the authenticated user, so clear it on logout
+        self.clear_agent();
+    }
+
+    /// Forget the deployed agent (after retiring it).
+    pub fn clear_agent(&mut self) {
         self.agent_address = None;
         self.agent_name = None;
+        self.moltbook_api_key = None;
+    }
+
+    /// Configured network profiles, or just `local` when none are set.
+    pub fn network_profiles(&self) -> Vec<NetworkProfile> {
+        if self.networks.is_empty() {
+            vec![NetworkProfile::local()]
+        } else {
+            self.networks.clone()
+        }
+    }
+
+    /// Network the current server URL belongs to; `--server` can point outside every profile.
+    pub fn network(&self) -> NetworkProfile {
+        self.network_profiles()
+            .into_iter()
+            .find(|n| n.server_url.trim_end_matches('/') == self.server_url.trim_end_matches('/'))
+            .unwrap_or_else(|| NetworkProfile::custom("custom", &self.server_url))
+    }
+
+    /// Name the current server's login, wallet and agent are kept under: its
+    /// profile's name, or the URL itself when it matches no profile.
+    pub fn profile_key(&self) -> String {
+        let url = self.server_url.trim_end_matches('/');
+        self.network_profiles()
+            .into_iter()
+            .find(|n| n.server_url.trim_end_matches('/') == url)
+            .map_or_else(|| url.to_string(), |n| n.name)
+    }
+
+    /// Point at `server_url`. Moving to another profile puts the login,
+    /// wallet and agent away under the old profile and brings back the new
+    /// one's; a profile used for the first time gets a wallet file of its own.
+    pub fn switch_server(&mut self, server_url: String) {
+        // A fresh config has no server yet, so nothing to put away
+        let from = Some(self.profile_key()).filter(|_| !self.server_url.is_empty());
+        self.server_url = server_url;
+        let to = self.profile_key();
+        let Some(from) = from.filter(|from| *from != to) else { return };
+        let active = ProfileBinding {
+            auth_token: self.auth_token.take(),
+            wallet_file: self.wallet_file.take(),
+            agent_address: self.agent_address.take(),
+            agent_name: self.agent_name.take(),
+            moltbook_api_key: self.moltbook_api_key.take(),
+        };
+        self.profiles.insert(from, active);
+        let binding = self.profiles.remove(&to).unwrap_or_else(|| {
+            let safe: String = to.chars().map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') { c } else { '_' }).collect();
+            ProfileBinding { wallet_file: Some(format!("wallet-{}.json", safe)), ..ProfileBinding::default() }
+        });
+        self.auth_token = binding.auth_token;
+        self.wallet_file = binding.wallet_file;
+        self.agent_address = binding.agent_address;
+        self.agent_name = binding.agent_name;
+        self.moltbook_api_key = binding.moltbook_api_key;
+    }
+
+    /// Explorer link for an account, if an explorer is configured.
+    pub fn explorer_link(&self, address: &str) -> Option<String> {
+        self.network()
+            .explorer_url
+            .or_else(|| self.explorer_url.clone())
+            .map(|template| template.replace("{address}", address))
+    }
+
+    /// Explorer link for a block by hash, if a block page is configured.
+    pub fn explorer_block_link(&self, block_hash: &str) -> Option<String> {
+        self.network()
+            .explorer_block_url
+            .or_else(|| self.explorer_block_url.clone())
+            .map(|template| template.replace("{block}", block_hash))
+    }
+
+    /// Period between session validity checks.
+    pub fn session_check_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.session_check_secs.unwrap_or(DEFAULT_SESSION_CHECK_SECS))
+    }
+
+    /// Period between wallet balance fetches.
+    pub fn balance_refresh_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.balance_refresh_secs.unwrap_or(DEFAULT_BALANCE_REFRESH_SECS))
+    }
+
+    /// `min_balance` in planck of `network`'s token.
+    pub fn min_balance_planck(&self, network: &NetworkProfile) -> u128 {
+        let min = self.min_balance.as_deref().unwrap_or(DEFAULT_MIN_BALANCE);
+        crate::funds::parse_tokens(min, network.decimals).unwrap_or(network.unit())
+    }
+
+    /// Moltbook API base the app acts as the agent through.
+    pub fn moltbook_api_url(&self) -> &str {
+        self.moltbook_api_url.as_deref().unwrap_or(crate::moltbook::MOLTBOOK_API_BASE)
+    }
+
+    /// Period between price feed fetches, or None without a feed.
+    pub fn price_refresh_interval(&self) -> Option<std::time::Duration> {
+        self.price_feed_url.as_ref()?;
+        let secs = self.price_refresh_secs.unwrap_or(DEFAULT_PRICE_REFRESH_SECS);
+        Some(std::time::Duration::from_secs(secs.max(1)))
+    }
+
+    /// Period between automatic claim checks, or None when disabled.
+    pub fn claim_poll_interval(&self) -> Option<std::time::Duration> {
+        match self.claim_poll_secs.unwrap_or(DEFAULT_CLAIM_POLL_SECS) {
+            0 => None,
+            secs => Some(std::time::Duration::from_secs(secs)),
+        }
+    }
+
+    /// Silence after which a run's event stream counts as stalled, or None
+    /// when disabled.
+    pub fn stream_stall_timeout(&self) -> Option<std::time::Duration> {
+        match self.stream_stall_secs.unwrap_or(DEFAULT_STREAM_STALL_SECS) {
+            0 => None,
+            secs => Some(std::time::Duration::from_secs(secs)),
+        }
+    }
+
+    /// Input-free time after which the screen locks, or None when disabled.
+    pub fn idle_lock_timeout(&self) -> Option<std::time::Duration> {
+        match self.idle_lock_minutes.unwrap_or(0) {
+            0 => None,
+            minutes => Some(std::time::Duration::from_secs(minutes * 60)),
+        }
+    }
+
+    /// Auto-refresh period for the view screen, or None when disabled.
+    pub fn view_refresh_interval(&self) -> Option<std::time::Duration> {
+        match self.view_refresh_secs.unwrap_or(DEFAULT_VIEW_REFRESH_SECS) {
+            0 => None,
+            secs => Some(std::time::Duration::from_secs(secs)),
+        }
+    }
+
+    /// Custom banner path of the current network, if one is configured.
+    pub fn banner_path(&self) -> Option<String> {
+        self.network().banner_image.or_else(|| self.banner_image.clone())
+    }
+
+    /// Whether the current network shows a banner image.
+    pub fn banner_enabled(&self) -> bool {
+        !self.network().banner_disabled.unwrap_or(self.banner_disabled)
+    }
+
+    /// Whether the current network's banner cycles through its frames.
+    pub fn banner_animated(&self) -> bool {
+        self.network().banner_animate.unwrap_or(self.banner_animate)
+    }
+
+    /// Whether any network shows a banner image, so the terminal is worth
+    /// asking for its graphics support.
+    pub fn any_banner_enabled(&self) -> bool {
+        !self.banner_disabled || self.network_profiles().iter().any(|n| n.banner_disabled == Some(false))
     }
 
     /// Get the agent source based on config.
+    #[allow(dead_code)]
     pub fn agent_source(&self) -> AgentSource {
         match &self.custom_agent_dir {
-            Some(dir) => AgentSource::Custom(dir.clone()),
+            Some(dir) => match crate::git_source::GitSource::parse(dir) {
+                Some(git) => AgentSource::Custom(git.agent_dir().to_string_lossy().to_string()),
+                None => AgentSource::Custom(dir.clone()),
+            },
             None => AgentSource::Embedded,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_matches_server_url() {
+        let mut config = AppConfig {
+            server_url: "https://test.example.com/".to_string(),
+            networks: vec![NetworkProfile::local(), NetworkProfile {
+                explorer_url: Some("https://scan.example.com/{address}".to_string()),
+                ..NetworkProfile::custom("testnet", "https://test.example.com").with_kind(NetworkKind::Testnet)
+            }],
+            ..AppConfig::default()
+        };
+        assert_eq!(config.network().kind, NetworkKind::Testnet);
+        assert_eq!(config.explorer_link("5Abc").as_deref(), Some("https://scan.example.com/5Abc"));
+
+        config.server_url = "http://elsewhere:9000".to_string();
+        assert_eq!(config.network().kind, NetworkKind::Custom);
+        assert_eq!(config.explorer_link("5Abc"), None);
+    }
+
+    #[test]
+    fn test_switching_profiles_swaps_bindings() {
+        let mut config = AppConfig {
+            server_url: DEFAULT_SERVER_URL.to_string(),
+            auth_token: Some("local-token".to_string()),
+            agent_address: Some("5Local".to_string()),
+            networks: vec![NetworkProfile::local(), NetworkProfile::custom("testnet", "https://test.example.com")],
+            ..AppConfig::default()
+        };
+        config.switch_server("https://test.example.com/".to_string());
+        assert_eq!((config.auth_token.as_deref(), config.agent_address.as_deref()), (None, None));
+        assert_eq!(config.wallet_file.as_deref(), Some("wallet-testnet.json"));
+        config.auth_token = Some("test-token".to_string());
+
+        // Another URL of no profile is one of its own
+        config.switch_server("http://elsewhere:9000".to_string());
+        assert_eq!(config.wallet_file.as_deref(), Some("wallet-http___elsewhere_9000.json"));
+
+        config.switch_server(DEFAULT_SERVER_URL.to_string());
+        assert_eq!(config.auth_token.as_deref(), Some("local-token"));
+        assert_eq!(config.agent_address.as_deref(), Some("5Local"));
+        assert_eq!(config.wallet_file, None);
+        assert_eq!(config.profiles["testnet"].auth_token.as_deref(), Some("test-token"));
+    }
+
+    #[test]
+    fn test_banner_settings_per_network() {
+        let config = AppConfig::from_toml(
+            "config_version = 1
+server_url = \"https://test.example.com\"
+banner_image = \"/art/lobster.gif\"
+banner_animate = false
+
+[[networks]]
+name = \"testnet\"
+server_url = \"https://test.example.com\"
+banner_disabled = true
+",
+        )
+        .unwrap();
+        // Version 1 networks keep following the top-level settings
+        let testnet = &config.networks[0];
+        assert_eq!((testnet.banner_image.as_deref(), testnet.banner_animate), (None, None));
+        assert_eq!(config.banner_path().as_deref(), Some("/art/lobster.gif"));
+        assert!(!config.banner_enabled() && !config.banner_animated());
+
+        let mut config = AppConfig { networks: vec![NetworkProfile::local()], ..config };
+        config.switch_server(DEFAULT_SERVER_URL.to_string());
+        assert!(config.banner_enabled() && !config.banner_animated());
+        assert_eq!(config.banner_path().as_deref(), Some("/art/lobster.gif"));
+        config.networks[0].banner_image = Some("/art/local.png".to_string());
+        assert_eq!(config.banner_path().as_deref(), Some("/art/local.png"));
+        assert!(!config.export_bundle().unwrap().contains("local.png"));
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let config: AppConfig = serde_json::from_value(serde_json::json!({
+            "config_version": CONFIG_VERSION,
+            "server_url": "https://test.example.com",
+            "auth_token": "token",
+            "networks": [{ "name": "testnet", "kind": "testnet", "server_url": "https://test.example.com" }],
+            "webhooks": [{ "url": "https://hooks.example.com", "events": ["deployed"] }],
+            "daemon": { "top_up_amount": "5" },
+            "favorite_prompts": { "5Agent": ["Post about crabs"] },
+            "profiles": { "local": { "auth_token": "local-token" } },
+        }))
+        .unwrap();
+        let text = toml::to_string_pretty(&config).unwrap();
+        let parsed = AppConfig::from_toml(&text).unwrap();
+        assert_eq!(parsed.config_version, CONFIG_VERSION);
+        assert_eq!(serde_json::to_value(&parsed.networks).unwrap(), serde_json::to_value(&config.networks).unwrap());
+        assert_eq!(parsed.daemon.top_up_amount.as_deref(), Some("5"));
+        assert_eq!(parsed.profiles["local"].auth_token.as_deref(), Some("local-token"));
+    }
+
+    #[test]
+    fn test_config_errors_name_the_key() {
+        let error = |text: &str| format!("{:#}", AppConfig::from_toml(text).unwrap_err());
+        let wrong_type = error("config_version = 2\nview_refresh_secs = \"soon\"\n");
+        assert!(wrong_type.contains("line 2") && wrong_type.contains("view_refresh_secs"));
+        assert_eq!(error("config_version = 2\n[daemon]\npol_secs = 5\n"), "unknown key `daemon.pol_secs`");
+        assert_eq!(
+            error("config_version = 2\n[[networks]]\nname = \"testnet\"\nserver_url = \"test.example.com\"\n"),
+            "`networks[0].server_url` must start with http:// or https://, not \"test.example.com\""
+        );
+        assert_eq!(
+            error("config_version = 2\nstream_stall_secs = 10\n"),
+            "`stream_stall_secs` must be at least 30 seconds (or 0 to turn it off), not 10"
+        );
+        assert_eq!(
+            error("config_version = 2
+[[networks]]
+name = \"wide\"
+server_url = \"https://wide.example.com\"
+decimals = 40
+"),
+            "`networks[0].decimals` must be at most 30, not 40"
+        );
+        assert!(error("config_version = 9\n").starts_with("config_version 9 was written by a newer lobster"));
+        // Files without a version are from before versioning and still load
+        assert_eq!(AppConfig::from_toml("server_url = \"\"\n").unwrap().config_version, CONFIG_VERSION);
+    }
+
+    #[test]
+    fn test_only_fresh_configs_start_onboarding() {
+        let existing: AppConfig = serde_json::from_str(r#"{ "server_url": "" }"#).unwrap();
+        assert!(existing.onboarded);
+        assert!(!AppConfig::default().onboarded);
+    }
+
+    #[test]
+    fn test_settings_bundle_leaves_secrets_behind() {
+        let shared = AppConfig {
+            server_url: "https://test.example.com".to_string(),
+            auth_token: Some("secret-token".to_string()),
+            networks: vec![NetworkProfile::local(), NetworkProfile::custom("testnet", "https://test.example.com")],
+            webhooks: serde_json::from_value(serde_json::json!([{ "url": "https://hooks.example.com/T0/XXXX" }])).unwrap(),
+            custom_agent_dir: Some("/home/me/agent".to_string()),
+            theme: ThemeName::Light,
+            ..AppConfig::default()
+        };
+        let bundle = shared.export_bundle().unwrap();
+        assert!(bundle.contains("theme = \"light\""));
+        for leaked in ["secret-token", "hooks.example.com", "/home/me/agent"] {
+            assert!(!bundle.contains(leaked), "{} in the bundle", leaked);
+        }
+
+        let mut mine = AppConfig {
+            server_url: DEFAULT_SERVER_URL.to_string(),
+            auth_token: Some("local-token".to_string()),
+            min_balance: Some("2".to_string()),
+            ..AppConfig::default()
+        };
+        let hostile = format!(
+            "auth_token = \"stolen\"\nmoltbook_api_url = \"https://evil.example.com\"\n{}\n[spending_limits]\nper_day = \"1000000\"\n\n[daemon]\ntop_up_amount = \"1000\"\n",
+            bundle
+        );
+        let (taken, skipped) = mine.import_bundle(&hostile).unwrap();
+        assert!(taken.contains(&"networks".to_string()));
+        assert_eq!(skipped, ["auth_token", "daemon", "moltbook_api_url", "spending_limits"]);
+        assert_eq!((mine.moltbook_api_url.as_deref(), mine.spending_limits.per_day.as_deref()), (None, None));
+        assert_eq!(mine.daemon.top_up_amount, None);
+        assert_eq!((mine.theme, mine.min_balance.as_deref()), (ThemeName::Light, Some("2")));
+        // Switched to testnet, with the local login put away
+        assert_eq!(mine.network().name, "testnet");
+        assert_eq!(mine.auth_token, None);
+        assert_eq!(mine.profiles["local"].auth_token.as_deref(), Some("local-token"));
+        assert!(mine.import_bundle("server_url = \"ftp://nope\"").is_err());
+    }
+}
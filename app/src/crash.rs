@@ -0,0 +1,149 @@
+//! Terminal restoration and crash reports.
+//!
+//! A panic on the UI thread would otherwise leave the shell in raw mode on the
+//! alternate screen with mouse capture on, and print the panic message where
+//! nobody can read it.
+
+use crate::{config::config_dir, logging::log_dir};
+use crossterm::{
+    cursor::Show,
+    event::{DisableFocusChange, DisableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
+};
+use std::backtrace::Backtrace;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Log lines copied into a crash report.
+const REPORT_LOG_LINES: usize = 50;
+
+/// Set while the TUI owns the terminal.
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Restores the terminal when dropped, including when a panic unwinds `main`
+/// or setup bails out early with `?`.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    /// Call right after entering raw mode.
+    pub fn new() -> Self {
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Leave raw mode, the alternate screen, mouse capture and focus reporting. Safe to call twice.
+fn restore_terminal() {
+    if TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        // Best effort: there is nowhere left to report a failure to
+        let _ = disable_raw_mode();
+        let _ = execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableFocusChange, Show);
+    }
+}
+
+/// Write a crash report for every panic. A panic on the main thread also
+/// restores the terminal before the message is printed; background tasks
+/// only report, since the UI keeps running.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let on_main = std::thread::current().name() == Some("main");
+        let tui = TERMINAL_ACTIVE.load(Ordering::SeqCst);
+        let message = panic_message(info);
+        tracing::error!("panic: {}", message);
+
+        let report = write_report(&message, &Backtrace::force_capture());
+        if tui && !on_main {
+            return;
+        }
+        restore_terminal();
+        default_hook(info);
+        match report {
+            Ok(path) => eprintln!("\nlobster crashed. A crash report was written to {}", path.display()),
+            Err(e) => eprintln!("\nlobster crashed, and the crash report could not be written: {}", e),
+        }
+    }));
+}
+
+/// Message and location of a panic, e.g. "index out of bounds at src/app.rs:10:5".
+fn panic_message(info: &PanicHookInfo) -> String {
+    let payload = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    match info.location() {
+        Some(location) => format!("{} at {}", payload, location),
+        None => payload,
+    }
+}
+
+/// Save `crash-<unix time>.txt` in the config directory.
+fn write_report(message: &str, backtrace: &Backtrace) -> std::io::Result<PathBuf> {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let dir = config_dir();
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("crash-{}.txt", secs));
+    let thread = std::thread::current().name().unwrap_or("unnamed").to_string();
+    std::fs::write(&path, report(message, &thread, &backtrace.to_string(), &recent_log_lines()))?;
+    Ok(path)
+}
+
+fn report(message: &str, thread: &str, backtrace: &str, log_lines: &[String]) -> String {
+    let mut report = format!(
+        "lobster {} crashed ({} {})\n\nPanic in thread '{}': {}\n\nBacktrace:\n{}\n",
+        crate::update::CURRENT_VERSION,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        thread,
+        message,
+        backtrace.trim_end(),
+    );
+    if !log_lines.is_empty() {
+        report.push_str(&format!("\nLast {} log lines:\n", log_lines.len()));
+        for line in log_lines {
+            report.push_str(line);
+            report.push('\n');
+        }
+    }
+    report
+}
+
+/// Tail of the newest diagnostics log; file names sort by date.
+fn recent_log_lines() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(log_dir()) else {
+        return Vec::new();
+    };
+    let newest = entries.flatten().map(|e| e.path()).filter(|p| p.is_file()).max();
+    let Some(contents) = newest.and_then(|p| std::fs::read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = contents.lines().collect();
+    lines[lines.len().saturating_sub(REPORT_LOG_LINES)..].iter().map(|l| l.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let text = report("boom at src/app.rs:1:1", "main", "0: lobster::main\n", &["INFO starting".to_string()]);
+        assert!(text.contains("Panic in thread 'main': boom at src/app.rs:1:1"));
+        assert!(text.contains("Backtrace:\n0: lobster::main\n"));
+        assert!(text.ends_with("Last 1 log lines:\nINFO starting\n"));
+
+        assert!(!report("boom", "main", "", &[]).contains("log lines"));
+    }
+}
@@ -0,0 +1,580 @@
+//! `lobster daemon`: unattended operator mode.
+//!
+//! Stays resident and watches the user's deployed agents: new runs are
+//! picked up from the activity log and followed over their SSE stream,
+//! accounts that drop below `min_balance` are topped up from the wallet
+//! within the `daemon` limits, scheduled prompts are submitted when they
+//! come due, and everything seen or done is written to
+//! stdout as one JSON object per line. Gateway failures are retried on the
+//! next poll; diagnostics still go to the log file. With `metrics_addr` set,
+//! the same records are counted for Prometheus (see `metrics`).
+
+use crate::{
+    client::{ActivityKind, ApiClient, ChainEventData},
+    config::{AppConfig, NetworkProfile, DEFAULT_SERVER_URL},
+    funds::{format_tokens, parse_tokens, Funds},
+    metrics::{self, SharedMetrics},
+    runlog::RunLog,
+    schedule,
+    wallet::WalletConfig,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+
+/// Activity poll period when `daemon.poll_secs` is unset (~2 blocks).
+pub const DEFAULT_POLL_SECS: u64 = 12;
+
+/// Activity entries fetched per agent and poll.
+const ACTIVITY_LIMIT: u32 = 20;
+
+/// First run stream reconnect delay; doubles on every further attempt.
+const FIRST_RETRY: Duration = Duration::from_secs(2);
+
+/// Stream connections tried per run before the activity log is left to
+/// report how it ended.
+const STREAM_ATTEMPTS: u32 = 5;
+
+/// Limits of `lobster daemon`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonConfig {
+    /// Seconds between activity and balance polls
+    pub poll_secs: u64,
+    /// Tokens sent to an agent whose balance drops below `min_balance`
+    /// (unset never tops up)
+    pub top_up_amount: Option<String>,
+    /// Most tokens the wallet sends per UTC day, by the `spending.json`
+    /// total the TUI adds to as well, before top-ups stop (unset leaves it
+    /// to the wallet's own `min_balance`)
+    pub max_top_up_per_day: Option<String>,
+    /// Address serving Prometheus metrics at `/metrics`, e.g.
+    /// "127.0.0.1:9464" (unset serves none)
+    pub metrics_addr: Option<String>,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self { poll_secs: DEFAULT_POLL_SECS, top_up_amount: None, max_top_up_per_day: None, metrics_addr: None }
+    }
+}
+
+/// A deployed agent being watched.
+#[derive(Debug, Clone, PartialEq)]
+struct Agent {
+    address: String,
+    name: String,
+}
+
+/// A record from a run stream follower.
+struct RunLine {
+    agent: String,
+    run_id: u64,
+    record: Value,
+    /// The run's completion or failure
+    finishes: bool,
+}
+
+/// State kept between polls.
+struct Daemon {
+    config: AppConfig,
+    client: ApiClient,
+    network: NetworkProfile,
+    wallet: Option<WalletConfig>,
+    /// Planck sent per top-up, when topping up
+    top_up: Option<u128>,
+    /// `max_top_up_per_day` in planck
+    max_top_up: Option<u128>,
+    agents: Vec<Agent>,
+    /// Run ids in each agent's activity log so far; absent until the first poll,
+    /// whose history isn't reported
+    seen: HashMap<String, HashSet<u64>>,
+    /// Runs whose completion or failure was reported
+    finished: HashSet<(String, u64)>,
+    /// Agents below `min_balance`, reported once until they recover
+    low: HashSet<String>,
+    /// Agents whose top-up was refused, reported once until they recover
+    refused: HashSet<String>,
+    lines: mpsc::Sender<RunLine>,
+    metrics: SharedMetrics,
+}
+
+impl Daemon {
+    /// Daemon for `config`, failing early on limits it can't act on.
+    fn new(config: AppConfig, client: ApiClient, wallet: Option<WalletConfig>, lines: mpsc::Sender<RunLine>) -> Result<Self> {
+        let network = config.network();
+        let tokens = |what: &str, amount: &Option<String>| -> Result<Option<u128>> {
+            amount
+                .as_deref()
+                .map(|a| parse_tokens(a, network.decimals).with_context(|| format!("daemon.{} is not a token amount: {}", what, a)))
+                .transpose()
+        };
+        let top_up = tokens("top_up_amount", &config.daemon.top_up_amount)?;
+        let max_top_up = tokens("max_top_up_per_day", &config.daemon.max_top_up_per_day)?;
+        if top_up.is_some() && wallet.is_none() {
+            anyhow::bail!("daemon.top_up_amount is set but there is no wallet to pay from; log in with lobster first");
+        }
+        Ok(Self {
+            config,
+            client,
+            network,
+            wallet,
+            top_up,
+            max_top_up,
+            agents: Vec::new(),
+            seen: HashMap::new(),
+            finished: HashSet::new(),
+            low: HashSet::new(),
+            refused: HashSet::new(),
+            lines,
+            metrics: SharedMetrics::default(),
+        })
+    }
+
+    /// Reload the agent list, then check every agent's runs and balance.
+    async fn poll(&mut self) -> Vec<Value> {
+        let mut records = Vec::new();
+        self.refresh_agents(&mut records).await;
+        self.submit_scheduled(&mut records).await;
+        let mut wallet_balance = match &self.wallet {
+            Some(wallet) if self.top_up.is_some() => self.balance(&wallet.public_key, &mut records).await,
+            _ => None,
+        };
+        if let (Some(wallet), Some(balance)) = (&self.wallet, wallet_balance) {
+            self.gauge_balance("wallet", &wallet.public_key, balance);
+        }
+        for agent in self.agents.clone() {
+            self.check_runs(&agent, &mut records).await;
+            self.check_balance(&agent, &mut wallet_balance, &mut records).await;
+        }
+        records
+    }
+
+    /// The user's agents with a chain address, plus the one in the config.
+    /// A failed fetch keeps the previous list.
+    async fn refresh_agents(&mut self, records: &mut Vec<Value>) {
+        let mut agents = Vec::new();
+        if let Some(address) = &self.config.agent_address {
+            let name = self.config.agent_name.clone().unwrap_or_default();
+            agents.push(Agent { address: address.clone(), name });
+        }
+        if self.config.is_authenticated() {
+            match self.client.list_agents().await {
+                Ok(list) => agents.extend(
+                    list.into_iter()
+                        .filter_map(|a| Some(Agent { address: a.chain_address?, name: a.name }))
+                        .filter(|a| a.address != self.config.agent_address.as_deref().unwrap_or_default()),
+                ),
+                Err(e) => {
+                    records.push(error_record("list_agents", None, &e));
+                    return;
+                }
+            }
+        }
+        if agents != self.agents {
+            let names: Vec<&str> = agents.iter().map(|a| a.name.as_str()).collect();
+            records.push(record("agents", json!({ "agents": names })));
+            self.agents = agents;
+        }
+    }
+
+    /// Submit the scheduled prompts that are due; the activity poll then
+    /// reports and follows their runs like any other.
+    async fn submit_scheduled(&mut self, records: &mut Vec<Value>) {
+        let Some(wallet) = &self.wallet else { return };
+        let block = match self.client.get_chain_head().await {
+            Ok(head) => Some(head.block_number),
+            Err(e) => {
+                // Prompts scheduled by time can still go
+                records.push(error_record("chain_head", None, &e));
+                None
+            }
+        };
+        for prompt in schedule::take_due(&self.network.name, block, now()) {
+            let agent = self.agents.iter().find(|a| a.address == prompt.agent_address);
+            let mut fields = json!({
+                "agent": agent.map(|a| a.name.as_str()).unwrap_or_default(),
+                "address": prompt.agent_address,
+                "prompt": prompt.prompt,
+            });
+            let started = Instant::now();
+            match schedule::submit(&self.client, wallet, &prompt).await {
+                Ok((run_id, submitted)) => {
+                    metrics::lock(&self.metrics).submitted("prompt", started.elapsed());
+                    fields["run_id"] = run_id.into();
+                    fields["block_hash"] = submitted.block_hash.into();
+                    records.push(record("scheduled_submitted", fields));
+                }
+                Err(e) => {
+                    tracing::warn!("Scheduled prompt failed: {:#}", e);
+                    fields["error"] = format!("{:#}", e).into();
+                    records.push(record("scheduled_failed", fields));
+                }
+            }
+        }
+    }
+
+    /// Report runs new to the activity log and follow the ones still going.
+    async fn check_runs(&mut self, agent: &Agent, records: &mut Vec<Value>) {
+        let events = match self.client.get_activity(&agent.address, ACTIVITY_LIMIT).await {
+            Ok(resp) => resp.events,
+            Err(e) => {
+                records.push(error_record("activity", Some(agent), &e));
+                return;
+            }
+        };
+        let first_poll = !self.seen.contains_key(&agent.address);
+        let seen = self.seen.entry(agent.address.clone()).or_default();
+        let mut started = Vec::new();
+        // Newest first from the gateway; report in chain order
+        for event in events.iter().rev() {
+            let Some(run_id) = event.run_id else { continue };
+            let new = seen.insert(run_id);
+            let finishes = matches!(event.kind, ActivityKind::RunCompleted | ActivityKind::RunFailed);
+            if first_poll {
+                if finishes {
+                    self.finished.insert((agent.address.clone(), run_id));
+                }
+                continue;
+            }
+            if new && event.kind == ActivityKind::RunStarted {
+                records.push(record("run_started", json!({ "agent": agent.name, "address": agent.address, "run_id": run_id, "block": event.block_number })));
+                started.push(run_id);
+            }
+            if finishes && self.finished.insert((agent.address.clone(), run_id)) {
+                let (name, reason) = match event.kind {
+                    ActivityKind::RunCompleted => ("run_completed", None),
+                    _ => ("run_failed", event.detail.as_deref()),
+                };
+                let mut fields = json!({ "agent": agent.name, "address": agent.address, "run_id": run_id, "block": event.block_number });
+                if let Some(reason) = reason {
+                    fields["reason"] = reason.into();
+                }
+                records.push(record(name, fields));
+            }
+        }
+        for run_id in started {
+            if !self.finished.contains(&(agent.address.clone(), run_id)) {
+                let (client, agent, lines) = (self.client.clone(), agent.clone(), self.lines.clone());
+                let log = self.config.run_logs.then(|| RunLog::for_run(&agent.address, run_id)).flatten();
+                let stall = self.config.stream_stall_timeout();
+                crate::tasks::spawn(follow_run(client, agent, run_id, log, stall, lines));
+            }
+        }
+    }
+
+    /// Top up `agent` from the wallet when it is below the minimum, within
+    /// the daily limit and keeping the wallet's own minimum. Nothing is sent
+    /// while the wallet balance is unknown.
+    async fn check_balance(&mut self, agent: &Agent, wallet_balance: &mut Option<u128>, records: &mut Vec<Value>) {
+        let Some(balance) = self.balance(&agent.address, records).await else { return };
+        self.gauge_balance(&agent.name, &agent.address, balance);
+        let min = self.config.min_balance_planck(&self.network);
+        if balance >= min {
+            self.low.remove(&agent.address);
+            self.refused.remove(&agent.address);
+            return;
+        }
+        if self.low.insert(agent.address.clone()) {
+            records.push(record("low_balance", json!({
+                "agent": agent.name,
+                "address": agent.address,
+                "balance": format_tokens(balance, self.network.decimals),
+                "min": format_tokens(min, self.network.decimals),
+            })));
+        }
+        let (Some(amount), Some(wallet)) = (self.top_up, &self.wallet) else { return };
+        // The failed balance fetch is already recorded; tried again on the next poll
+        let Some(have) = *wallet_balance else { return };
+
+        let funds = Funds::new(Some(have), Some(balance), min, &self.network);
+        let spent = crate::spending::spent_today(&self.network.name);
+        let refusal = if self.max_top_up.is_some_and(|limit| spent.saturating_add(amount) > limit) {
+            Some(format!("Daily top-up limit of {} {} reached", self.config.daemon.max_top_up_per_day.as_deref().unwrap_or_default(), self.network.token_symbol))
+        } else {
+            funds.check_wallet(amount).err()
+        };
+        if let Some(reason) = refusal {
+            if self.refused.insert(agent.address.clone()) {
+                records.push(record("top_up_refused", json!({ "agent": agent.name, "address": agent.address, "reason": reason })));
+            }
+            return;
+        }
+
+        let started = Instant::now();
+        match top_up(&self.client, wallet, &agent.address, amount).await {
+            Ok(block_hash) => {
+                metrics::lock(&self.metrics).submitted("top_up", started.elapsed());
+                crate::spending::record(&self.network.name, amount);
+                *wallet_balance = wallet_balance.map(|b| b.saturating_sub(amount));
+                records.push(record("topped_up", json!({
+                    "agent": agent.name,
+                    "address": agent.address,
+                    "amount": format_tokens(amount, self.network.decimals),
+                    "block_hash": block_hash,
+                })));
+            }
+            // Tried again on the next poll
+            Err(e) => records.push(error_record("top_up", Some(agent), &e)),
+        }
+    }
+
+    /// Balance of `address` in planck; None (and an error record) when the
+    /// gateway didn't say.
+    async fn balance(&self, address: &str, records: &mut Vec<Value>) -> Option<u128> {
+        match self.client.get_balance(address).await {
+            Ok(resp) => resp.balance.parse().ok(),
+            Err(e) => {
+                records.push(error_record("balance", None, &e));
+                None
+            }
+        }
+    }
+
+    fn gauge_balance(&self, account: &str, address: &str, planck: u128) {
+        let tokens = format_tokens(planck, self.network.decimals).parse().unwrap_or_default();
+        metrics::lock(&self.metrics).balance(account, address, tokens);
+    }
+
+    /// Print a record and count it for the metrics.
+    fn emit(&self, record: &Value) {
+        metrics::lock(&self.metrics).observe(record);
+        emit(record);
+    }
+
+    /// Stream follower output, once per finished run.
+    fn accept(&mut self, line: RunLine) -> Option<Value> {
+        if line.finishes && !self.finished.insert((line.agent, line.run_id)) {
+            return None;
+        }
+        Some(line.record)
+    }
+}
+
+/// Send `amount` planck from the wallet to `agent`; the block hash on success.
+async fn top_up(client: &ApiClient, wallet: &WalletConfig, agent: &str, amount: u128) -> Result<String> {
+    let keypair = wallet.keypair()?;
+    let build = client.build_transfer(agent, amount, &wallet.public_key).await?;
+    let submitted = crate::extrinsic::sign_and_submit(client, &build, &keypair).await?;
+    Ok(submitted.block_hash)
+}
+
+/// Follow a run's SSE stream to its end, reconnecting with a growing delay,
+/// and append its events to `log` when there is one. A stream silent for
+/// `stall` counts as dropped. Gives up after `STREAM_ATTEMPTS`; the activity
+/// poll still reports the end.
+async fn follow_run(
+    client: ApiClient,
+    agent: Agent,
+    run_id: u64,
+    mut log: Option<RunLog>,
+    stall: Option<Duration>,
+    lines: mpsc::Sender<RunLine>,
+) {
+    use eventsource_stream::Eventsource;
+    use futures::StreamExt;
+
+    let mut retry = FIRST_RETRY;
+    for attempt in 1..=STREAM_ATTEMPTS {
+        match client.run_events(run_id).await {
+            Ok(resp) => {
+                let mut stream = resp.bytes_stream().eventsource();
+                let mut waiting = false;
+                loop {
+                    let limit = stall.filter(|_| !waiting).unwrap_or(Duration::MAX);
+                    let Ok(Some(Ok(event))) = tokio::time::timeout(limit, stream.next()).await else { break };
+                    let Ok(chain_event) = serde_json::from_str::<ChainEventData>(&event.data) else { continue };
+                    if let Some(log) = &mut log {
+                        log.append(&chain_event);
+                    }
+                    match &chain_event {
+                        ChainEventData::WaitingForInput { .. } => waiting = true,
+                        ChainEventData::Resumed { .. } => waiting = false,
+                        _ => {}
+                    }
+                    let (name, fields) = match chain_event {
+                        ChainEventData::Completed { output, .. } => ("run_completed", json!({ "output": output })),
+                        ChainEventData::Failed { reason, .. } => ("run_failed", json!({ "reason": reason })),
+                        ChainEventData::ToolsStarted { tools, .. } => ("tools_started", json!({ "tools": tools })),
+                        _ => continue,
+                    };
+                    let finishes = name != "tools_started";
+                    let mut record = record(name, fields);
+                    record["agent"] = agent.name.clone().into();
+                    record["address"] = agent.address.clone().into();
+                    record["run_id"] = run_id.into();
+                    let _ = lines.send(RunLine { agent: agent.address.clone(), run_id, record, finishes }).await;
+                    if finishes {
+                        return;
+                    }
+                }
+                tracing::info!(run_id, attempt, "Run stream ended before the run did");
+            }
+            Err(e) => tracing::warn!(run_id, attempt, "Run stream failed: {:#}", e),
+        }
+        if attempt < STREAM_ATTEMPTS {
+            tokio::time::sleep(retry).await;
+            retry *= 2;
+        }
+    }
+    let record = record("stream_lost", json!({ "agent": agent.name, "address": agent.address, "run_id": run_id }));
+    let _ = lines.send(RunLine { agent: agent.address, run_id, record, finishes: false }).await;
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// One output line: `fields` plus the event name and the time.
+fn record(event: &str, mut fields: Value) -> Value {
+    fields["event"] = event.into();
+    fields["time"] = now().into();
+    fields
+}
+
+fn error_record(action: &str, agent: Option<&Agent>, error: &anyhow::Error) -> Value {
+    tracing::warn!(action, "Daemon call failed: {:#}", error);
+    let mut fields = json!({ "action": action, "error": format!("{:#}", error) });
+    if let Some(agent) = agent {
+        fields["agent"] = agent.name.clone().into();
+        fields["address"] = agent.address.clone().into();
+    }
+    record("error", fields)
+}
+
+fn emit(record: &Value) {
+    let mut stdout = std::io::stdout().lock();
+    // Nowhere to report a closed stdout; the log still has everything
+    let _ = writeln!(stdout, "{}", record);
+    let _ = stdout.flush();
+}
+
+/// Run until interrupted.
+pub async fn run(server_url: Option<String>, mock: bool) -> Result<()> {
+    let mut config = AppConfig::load()?;
+    // --server wins, then the saved URL, then the local development server
+    let server_url = server_url
+        .or_else(|| Some(config.server_url.clone()).filter(|url| !url.is_empty()))
+        .unwrap_or_else(|| DEFAULT_SERVER_URL.to_string());
+    config.switch_server(server_url);
+    let mut client = if mock { ApiClient::mock() } else { ApiClient::new(config.server_url.clone()) };
+    if let Some(token) = &config.auth_token {
+        client.set_auth_token(token.clone());
+    }
+    let wallet = if config.is_authenticated() { WalletConfig::load(&config)? } else { None };
+
+    let (lines_tx, mut lines) = mpsc::channel(32);
+    let period = Duration::from_secs(config.daemon.poll_secs.max(1));
+    let mut daemon = Daemon::new(config, client, wallet, lines_tx)?;
+    let mut started = json!({ "server": daemon.config.server_url, "poll_secs": period.as_secs() });
+    if let Some(addr) = daemon.config.daemon.metrics_addr.clone() {
+        let listener = metrics::bind(&addr).await?;
+        started["metrics"] = format!("http://{}/metrics", listener.local_addr().map_or(addr, |a| a.to_string())).into();
+        crate::tasks::spawn(metrics::serve(listener, daemon.metrics.clone()));
+    }
+    tracing::info!(server = %daemon.config.server_url, "daemon started");
+    daemon.emit(&record("started", started));
+
+    let mut ticks = tokio::time::interval(period);
+    ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {
+                for record in daemon.poll().await {
+                    daemon.emit(&record);
+                }
+                if daemon.agents.is_empty() {
+                    tracing::debug!("No deployed agents to watch yet");
+                }
+            }
+            Some(line) = lines.recv() => {
+                if let Some(record) = daemon.accept(line) {
+                    daemon.emit(&record);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                daemon.emit(&record("stopped", json!({})));
+                tracing::info!("daemon stopped");
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daemon(config: AppConfig, wallet: Option<WalletConfig>) -> Daemon {
+        let (tx, _rx) = mpsc::channel(8);
+        Daemon::new(config, crate::testing::mock_client(), wallet, tx).unwrap()
+    }
+
+    fn config() -> AppConfig {
+        AppConfig {
+            server_url: DEFAULT_SERVER_URL.to_string(),
+            agent_address: Some("5Agent".to_string()),
+            agent_name: Some("crab".to_string()),
+            ..AppConfig::default()
+        }
+    }
+
+    fn events(records: &[Value]) -> Vec<&str> {
+        records.iter().map(|r| r["event"].as_str().unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_first_poll_skips_history() {
+        let mut daemon = daemon(config(), None);
+        assert_eq!(events(&daemon.poll().await), ["agents"]);
+        // The mock's finished run 1 was history, not news
+        assert!(daemon.poll().await.is_empty());
+        assert!(daemon.finished.contains(&("5Agent".to_string(), 1)));
+    }
+
+    #[tokio::test]
+    async fn test_low_balance_refused_once() {
+        // The mock wallet and agent both hold 1000 tokens
+        let mut config = config();
+        config.auth_token = None;
+        config.min_balance = Some("2000".to_string());
+        config.daemon.top_up_amount = Some("5".to_string());
+        let mut daemon = daemon(config, Some(WalletConfig::generate().unwrap()));
+
+        let records = daemon.poll().await;
+        assert_eq!(events(&records), ["agents", "low_balance", "top_up_refused"]);
+        assert!(records[2]["reason"].as_str().unwrap().starts_with("Insufficient funds"));
+        assert!(daemon.poll().await.is_empty());
+        let text = metrics::lock(&daemon.metrics).render();
+        assert!(text.contains("lobster_balance_tokens{account=\"crab\",address=\"5Agent\"} 1000\n"), "{}", text);
+    }
+
+    #[tokio::test]
+    async fn test_daily_limit_refuses_top_up() {
+        let mut config = config();
+        config.auth_token = None;
+        config.min_balance = Some("2000".to_string());
+        config.daemon.top_up_amount = Some("5".to_string());
+        config.daemon.max_top_up_per_day = Some("4".to_string());
+        let mut daemon = daemon(config, Some(WalletConfig::generate().unwrap()));
+
+        let records = daemon.poll().await;
+        assert_eq!(events(&records), ["agents", "low_balance", "top_up_refused"]);
+        assert_eq!(records[2]["reason"], "Daily top-up limit of 4 THE reached");
+    }
+
+    #[test]
+    fn test_top_up_needs_wallet() {
+        let mut config = config();
+        config.daemon.top_up_amount = Some("5".to_string());
+        let (tx, _rx) = mpsc::channel(8);
+        assert!(Daemon::new(config.clone(), crate::testing::mock_client(), None, tx.clone()).is_err());
+        config.daemon.top_up_amount = Some("five".to_string());
+        assert!(Daemon::new(config, crate::testing::mock_client(), Some(WalletConfig::generate().unwrap()), tx).is_err());
+    }
+}
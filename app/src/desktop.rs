@@ -0,0 +1,27 @@
+//! Desktop notifications, for results that land while the terminal is in
+//! the background. Best effort: a desktop without a notification daemon
+//! just doesn't show them.
+
+/// Name notifications are grouped under.
+const APP_NAME: &str = "Proof of Lobster";
+
+/// Show a desktop notification without blocking the UI.
+pub fn notify(summary: &str, body: &str) {
+    // Tests must not pop notifications on the developer's desktop
+    if cfg!(test) {
+        return;
+    }
+    let summary = summary.to_string();
+    let body = body.to_string();
+    // Delivery is a blocking D-Bus / OS call
+    std::thread::spawn(move || {
+        let result = notify_rust::Notification::new()
+            .appname(APP_NAME)
+            .summary(&summary)
+            .body(&body)
+            .show();
+        if let Err(e) = result {
+            tracing::debug!("Desktop notification failed: {}", e);
+        }
+    });
+}
@@ -0,0 +1,296 @@
+//! End-to-end smoke test against a mock server stack.
+//!
+//! Drives the real `App` state machine through login → create → deploy →
+//! prompt with a wiremock server standing in for both the gateway and
+//! Moltbook. Run with `cargo test --features e2e`.
+
+use crate::{
+    app::{App, AppMessage, AppScreen},
+    notifications::Level,
+    screens::{create::CreateStep, prompt::PromptStep},
+};
+use crossterm::event::KeyCode;
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use wiremock::{
+    matchers::{body_partial_json, header, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const TOKEN: &str = "e2e-token";
+const MOLTBOOK_KEY: &str = "moltbook_e2e_key";
+const RUN_ID: u64 = 7;
+
+/// Agent account id returned in the AgentRegistered event.
+const AGENT_ACCOUNT: [u8; 32] = [1u8; 32];
+
+async fn mount_stack(server: &MockServer) {
+    let ok = |body: serde_json::Value| ResponseTemplate::new(200).set_body_json(body);
+    let build = json!({
+        "call_data_hex": "0x0000",
+        "nonce": 0,
+        "genesis_hash": format!("0x{}", "00".repeat(32)),
+        "spec_version": 1,
+        "transaction_version": 1,
+    });
+
+    // Gateway: auth and wallet
+    Mock::given(method("POST"))
+        .and(path("/auth/magic-link"))
+        .respond_with(ok(json!({})))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/auth/me"))
+        .respond_with(ok(json!({ "user_id": "u1", "has_wallet": false, "wallet_address": null })))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/auth/fund"))
+        .respond_with(ok(json!({ "funded": true, "tx_hash": "0x01", "amount": "10" })))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/chain/balance"))
+        .respond_with(ok(json!({ "balance": "10000000000000", "balance_formatted": "10.0000" })))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/chain/head"))
+        .respond_with(ok(json!({ "block_number": 42 })))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/agents"))
+        .respond_with(ok(json!([])))
+        .mount(server)
+        .await;
+
+    // Moltbook (served under its own prefix on the same mock)
+    Mock::given(method("POST"))
+        .and(path("/moltbook/agents/register"))
+        .and(body_partial_json(json!({ "name": "e2e_lobster" })))
+        .respond_with(ok(json!({
+            "agent": {
+                "api_key": MOLTBOOK_KEY,
+                "claim_url": "https://moltbook.example/claim/1",
+                "verification_code": "reef-1234",
+            },
+            "important": "Save your API key",
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+
+    // Gateway: agent lifecycle
+    Mock::given(method("POST"))
+        .and(path("/agents/moltbook-status"))
+        .respond_with(ok(json!({ "status": "claimed", "claimed": true })))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/agents/store"))
+        .and(body_partial_json(json!({ "name": "e2e_lobster", "moltbook_api_key": MOLTBOOK_KEY })))
+        .respond_with(ok(json!({ "agent_id": "agent-1" })))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/agents/compile"))
+        .respond_with(ok(json!({ "success": true, "compiled_hex": "0xdeadbeef", "errors": [] })))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/agents/update-address"))
+        .respond_with(ok(json!({})))
+        .mount(server)
+        .await;
+
+    // Chain: one submit response carries both events the flows look for
+    Mock::given(method("POST"))
+        .and(path("/chain/build-deploy"))
+        .and(body_partial_json(json!({ "compiled_hex": "0xdeadbeef" })))
+        .respond_with(ok(build.clone()))
+        // Once for the fee on the review step, once to deploy
+        .expect(2)
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chain/fee"))
+        .respond_with(ok(json!({ "partial_fee": "10000000000" })))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chain/build-call"))
+        .and(body_partial_json(json!({ "input": "hello lobster" })))
+        .respond_with(ok(build))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chain/submit"))
+        .respond_with(ok(json!({
+            "block_hash": "0x02",
+            "block_number": 42,
+            "events": [
+                { "pallet": "Agents", "variant": "AgentRegistered", "data": { "bytes": hex::encode(AGENT_ACCOUNT) } },
+                { "pallet": "Agents", "variant": "AgentCallQueued", "data": { "bytes": hex::encode(RUN_ID.to_le_bytes()) } },
+            ],
+        })))
+        .expect(2)
+        .mount(server)
+        .await;
+
+    // Canned SSE stream for the run
+    let sse = [
+        json!({ "type": "run_started", "run_id": RUN_ID, "agent_name": "e2e_lobster", "caller": "me" }),
+        json!({ "type": "tools_started", "run_id": RUN_ID, "tools": ["moltbook_post"] }),
+        json!({ "type": "tools_completed", "run_id": RUN_ID, "tools": ["moltbook_post"] }),
+        json!({ "type": "completed", "run_id": RUN_ID, "output": "Posted a hello to m/general" }),
+    ]
+    .iter()
+    .map(|e| format!("data: {}\n\n", e))
+    .collect::<String>();
+    Mock::given(method("GET"))
+        .and(path(format!("/chain/events/{}", RUN_ID)))
+        .and(header("Authorization", format!("Bearer {}", TOKEN).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(sse, "text/event-stream"))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+/// Feed background messages into the app until `done` holds.
+async fn pump_until(
+    app: &mut App,
+    rx: &mut mpsc::Receiver<AppMessage>,
+    tx: &mpsc::Sender<AppMessage>,
+    what: &str,
+    done: impl Fn(&App) -> bool,
+) {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(20);
+    while !done(app) {
+        let msg = tokio::time::timeout_at(deadline, rx.recv())
+            .await
+            .unwrap_or_else(|_| panic!("timed out waiting for {}", what))
+            .expect("message channel closed");
+        app.handle_message(msg, tx.clone()).await.unwrap();
+        assert!(app.error_message.is_none(), "{}: {:?}", what, app.error_message);
+        let error = app.notifications.history().find(|n| n.level == Level::Error);
+        assert!(error.is_none(), "{}: {:?}", what, error);
+    }
+}
+
+async fn press(app: &mut App, tx: &mpsc::Sender<AppMessage>, key: KeyCode) {
+    app.handle_key(key.into(), tx.clone()).await.unwrap();
+}
+
+async fn type_text(app: &mut App, tx: &mpsc::Sender<AppMessage>, text: &str) {
+    for c in text.chars() {
+        press(app, tx, KeyCode::Char(c)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_login_create_deploy_prompt() {
+    let server = MockServer::start().await;
+    mount_stack(&server).await;
+
+    // Isolated config with the banner off (no terminal to query in tests)
+    // and Moltbook served by the mock
+    let config_dir = std::env::temp_dir().join(format!("lobster-e2e-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&config_dir);
+    std::fs::create_dir_all(&config_dir).unwrap();
+    // A config.json from before config.toml, converted on load
+    let legacy = json!({ "server_url": "", "banner_disabled": true, "moltbook_api_url": format!("{}/moltbook", server.uri()) });
+    std::fs::write(config_dir.join("config.json"), legacy.to_string()).unwrap();
+    crate::config::set_config_dir(config_dir.clone());
+
+    let mut app = App::new(Some(server.uri()), "agent".to_string(), false).await.unwrap();
+    assert!(app.config.banner_disabled);
+    assert!(config_dir.join("config.toml").exists() && config_dir.join("config.json.bak").exists());
+    let (tx, mut rx) = mpsc::channel::<AppMessage>(32);
+
+    // Login: request a magic link, then play the browser hitting the callback
+    press(&mut app, &tx, KeyCode::Char('1')).await;
+    assert_eq!(app.screen, AppScreen::EmailInput);
+    type_text(&mut app, &tx, "lobster@example.com").await;
+    press(&mut app, &tx, KeyCode::Enter).await;
+    pump_until(&mut app, &mut rx, &tx, "callback listener", |app| app.auth_callback.is_some()).await;
+
+    let (_, callback) = app.auth_callback.clone().unwrap();
+    let page = reqwest::get(format!("{}token?access_token={}", callback, TOKEN))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(page.contains("Authentication Successful"));
+    pump_until(&mut app, &mut rx, &tx, "login", |app| app.wallet_balance.is_some()).await;
+    assert_eq!(app.config.auth_token.as_deref(), Some(TOKEN));
+    assert!(config_dir.join("wallet.json").exists());
+
+    // Create: embedded agent files, register, claim, compile and deploy
+    press(&mut app, &tx, KeyCode::Char('1')).await;
+    assert_eq!(app.screen, AppScreen::Create);
+    press(&mut app, &tx, KeyCode::Enter).await;
+    assert_eq!(app.create.step, CreateStep::EnterAgentInfo);
+    type_text(&mut app, &tx, "e2e_lobster").await;
+    press(&mut app, &tx, KeyCode::Tab).await;
+    type_text(&mut app, &tx, "Smoke-tests the pipeline").await;
+    press(&mut app, &tx, KeyCode::Enter).await;
+    pump_until(&mut app, &mut rx, &tx, "registration", |app| app.create.step == CreateStep::WaitingClaim).await;
+
+    press(&mut app, &tx, KeyCode::Char('c')).await;
+    pump_until(&mut app, &mut rx, &tx, "claim", |app| app.create.step == CreateStep::ReviewSoul).await;
+    press(&mut app, &tx, KeyCode::Enter).await;
+    assert_eq!(app.create.step, CreateStep::ConfigureSchedule);
+    press(&mut app, &tx, KeyCode::Enter).await;
+    press(&mut app, &tx, KeyCode::Enter).await;
+    pump_until(&mut app, &mut rx, &tx, "fee estimate", |app| app.create.deploy_fee.is_some()).await;
+    assert_eq!(app.create.deploy_fee, Some(Ok(10_000_000_000)));
+    press(&mut app, &tx, KeyCode::Char('y')).await;
+    pump_until(&mut app, &mut rx, &tx, "deployment", |app| app.create.step == CreateStep::Success).await;
+
+    let expected_address = {
+        use sp_core::crypto::Ss58Codec;
+        sp_core::sr25519::Public::from_raw(AGENT_ACCOUNT).to_ss58check()
+    };
+    assert_eq!(app.config.agent_address.as_deref(), Some(expected_address.as_str()));
+    assert_eq!(app.config.agent_name.as_deref(), Some("e2e_lobster"));
+    assert_eq!(app.config.moltbook_api_key.as_deref(), Some(MOLTBOOK_KEY));
+
+    // Prompt: submit and stream the run to completion
+    press(&mut app, &tx, KeyCode::Enter).await;
+    assert_eq!(app.screen, AppScreen::Home);
+    press(&mut app, &tx, KeyCode::Char('2')).await;
+    assert_eq!(app.screen, AppScreen::Prompt);
+    type_text(&mut app, &tx, "hello lobster").await;
+    press(&mut app, &tx, KeyCode::Enter).await;
+    pump_until(&mut app, &mut rx, &tx, "agent run", |app| app.prompt.step == PromptStep::Complete).await;
+    assert_eq!(app.prompt.run_id, Some(RUN_ID));
+    assert_eq!(app.prompt.final_output.as_deref(), Some("Posted a hello to m/general"));
+    assert!(app.prompt.error.is_none());
+
+    // The magic link request carried the callback port we listened on
+    let requests = server.received_requests().await.unwrap();
+    let magic_link = requests.iter().find(|r| r.url.path() == "/auth/magic-link").unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&magic_link.body).unwrap();
+    assert_eq!(body["email"], "lobster@example.com");
+    assert!(callback.contains(&format!(":{}/", body["redirect_port"])));
+
+    // Authenticated gateway calls carried the token
+    let compile = requests.iter().find(|r| r.url.path() == "/agents/compile").unwrap();
+    assert_eq!(
+        compile.headers.get("authorization").and_then(|v| v.to_str().ok()),
+        Some(format!("Bearer {}", TOKEN).as_str())
+    );
+    assert!(String::from_utf8_lossy(&compile.body).contains("agent-1"));
+
+    let _ = std::fs::remove_dir_all(&config_dir);
+    // Mock expectations are verified when the server drops
+}
@@ -0,0 +1,124 @@
+//! Human-readable chain events from submit results.
+//!
+//! The gateway passes event fields through as SCALE bytes (`{"bytes": "…"}`).
+//! The events a deploy or prompt emits have fixed layouts, so those are
+//! decoded here; anything else is shown as its raw data.
+
+use crate::{client::ChainEvent, config::NetworkProfile, funds::format_tokens};
+use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
+
+/// Raw data shown for events without a known layout is cut to this many hex digits.
+const RAW_HEX_CHARS: usize = 32;
+
+/// One event as `Pallet.Variant` plus named, formatted fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventSummary {
+    pub name: String,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+/// Field types the known event layouts are made of.
+#[derive(Clone, Copy)]
+enum Field {
+    Account(&'static str),
+    Balance(&'static str),
+    U64(&'static str),
+}
+
+fn layout(pallet: &str, variant: &str) -> Option<&'static [Field]> {
+    use Field::*;
+    Some(match (pallet, variant) {
+        ("Balances", "Transfer") => &[Account("from"), Account("to"), Balance("amount")],
+        ("Balances", "Withdraw" | "Deposit" | "Reserved" | "Unreserved" | "Endowed" | "Burned" | "Minted") => {
+            &[Account("who"), Balance("amount")]
+        }
+        ("TransactionPayment", "TransactionFeePaid") => &[Account("who"), Balance("fee"), Balance("tip")],
+        ("Treasury", "Deposit") => &[Balance("value")],
+        ("System", "NewAccount" | "KilledAccount") => &[Account("account")],
+        ("System", "ExtrinsicSuccess") => &[],
+        ("Agents", "AgentRegistered") => &[Account("agent")],
+        ("Agents", "AgentCallQueued") => &[U64("run_id")],
+        _ => return None,
+    })
+}
+
+/// Decode `event` for display, with amounts and addresses in `network`'s format.
+pub fn describe(event: &ChainEvent, network: &NetworkProfile) -> EventSummary {
+    let name = format!("{}.{}", event.pallet, event.variant);
+    let bytes = event
+        .data
+        .get("bytes")
+        .and_then(|v| v.as_str())
+        .and_then(|h| hex::decode(h.trim_start_matches("0x")).ok());
+    let decoded = match (&bytes, layout(&event.pallet, &event.variant)) {
+        (Some(bytes), Some(layout)) => decode(bytes, layout, network),
+        _ => None,
+    };
+    let fields = match (decoded, bytes) {
+        (Some(fields), _) => fields,
+        (None, Some(bytes)) if bytes.is_empty() => Vec::new(),
+        (None, Some(bytes)) => {
+            let hex = hex::encode(&bytes);
+            let cut = if hex.len() > RAW_HEX_CHARS { "…" } else { "" };
+            vec![("data", format!("0x{}{}", &hex[..hex.len().min(RAW_HEX_CHARS)], cut))]
+        }
+        (None, None) if event.data.is_null() => Vec::new(),
+        (None, None) => vec![("data", event.data.to_string())],
+    };
+    EventSummary { name, fields }
+}
+
+/// Fields of `layout` read from the start of `bytes`; None if they are too short.
+fn decode(bytes: &[u8], layout: &[Field], network: &NetworkProfile) -> Option<Vec<(&'static str, String)>> {
+    let mut rest = bytes;
+    let mut take = |n: usize| -> Option<&[u8]> {
+        let (head, tail) = rest.split_at_checked(n)?;
+        rest = tail;
+        Some(head)
+    };
+    layout
+        .iter()
+        .map(|field| match *field {
+            Field::Account(name) => {
+                let raw: [u8; 32] = take(32)?.try_into().ok()?;
+                let format = Ss58AddressFormat::custom(network.ss58_prefix);
+                Some((name, sp_core::crypto::AccountId32::from(raw).to_ss58check_with_version(format)))
+            }
+            Field::Balance(name) => {
+                let planck = u128::from_le_bytes(take(16)?.try_into().ok()?);
+                Some((name, format!("{} {}", format_tokens(planck, network.decimals), network.token_symbol)))
+            }
+            Field::U64(name) => Some((name, u64::from_le_bytes(take(8)?.try_into().ok()?).to_string())),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(pallet: &str, variant: &str, data: serde_json::Value) -> ChainEvent {
+        ChainEvent { pallet: pallet.to_string(), variant: variant.to_string(), data }
+    }
+
+    #[test]
+    fn test_describe() {
+        let network = NetworkProfile::local();
+        let mut bytes = vec![1u8; 32];
+        bytes.extend([2u8; 32]);
+        bytes.extend(2_500_000_000_000u128.to_le_bytes());
+        let transfer = describe(&event("Balances", "Transfer", serde_json::json!({ "bytes": hex::encode(&bytes) })), &network);
+        assert_eq!(transfer.name, "Balances.Transfer");
+        assert_eq!(transfer.fields[2], ("amount", "2.5 THE".to_string()));
+        assert!(transfer.fields[0].1.starts_with('5'));
+
+        let queued = describe(&event("Agents", "AgentCallQueued", serde_json::json!({ "bytes": "0700000000000000ff" })), &network);
+        assert_eq!(queued.fields, vec![("run_id", "7".to_string())]);
+
+        // Too short for its layout, or unknown: raw data
+        let short = describe(&event("Balances", "Deposit", serde_json::json!({ "bytes": "0102" })), &network);
+        assert_eq!(short.fields, vec![("data", "0x0102".to_string())]);
+        let other = describe(&event("Sudo", "Sudid", serde_json::json!({ "ok": true })), &network);
+        assert_eq!(other.fields, vec![("data", r#"{"ok":true}"#.to_string())]);
+    }
+}
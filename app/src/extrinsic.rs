@@ -130,6 +130,46 @@ pub fn build_signed_extrinsic(
     Ok(format!("0x{}", hex::encode(&final_extrinsic)))
 }
 
+/// Sign call data built by the server with the user's keypair and submit it.
+pub async fn sign_and_submit(
+    client: &crate::client::ApiClient,
+    build: &crate::client::BuildExtrinsicResponse,
+    keypair: &subxt_signer::sr25519::Keypair,
+) -> Result<crate::client::SubmitResponse> {
+    let call_data = hex::decode(build.call_data_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid call data: {}", e))?;
+    let genesis_hash: [u8; 32] = hex::decode(build.genesis_hash.trim_start_matches("0x"))
+        .ok()
+        .and_then(|d| d.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("Invalid genesis hash"))?;
+
+    let reservation = crate::nonce::reserve(&crate::nonce::signer_address(keypair), build.nonce);
+    let signed_hex = build_signed_extrinsic(
+        &call_data,
+        reservation.nonce,
+        &genesis_hash,
+        build.spec_version,
+        build.transaction_version,
+        keypair,
+    )?;
+    let submitted = client.submit_extrinsic(&signed_hex).await?;
+    reservation.included();
+    Ok(submitted)
+}
+
+/// Whether `address` is a well-formed SS58 account address, for any network prefix.
+pub fn is_valid_ss58(address: &str) -> bool {
+    ss58_prefix(address).is_some()
+}
+
+/// Network prefix an SS58 address was encoded with, if it parses.
+pub fn ss58_prefix(address: &str) -> Option<u16> {
+    use sp_core::crypto::Ss58Codec;
+    sp_core::crypto::AccountId32::from_ss58check_with_version(address)
+        .ok()
+        .map(|(_, format)| format.prefix())
+}
+
 /// Parse an AgentRegistered event from the events list.
 /// Returns the agent address (SS58 encoded).
 pub fn parse_agent_registered_event(events: &[crate::client::ChainEvent]) -> Option<String> {
@@ -193,4 +233,13 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().starts_with("0x"));
     }
+
+    #[test]
+    fn test_is_valid_ss58() {
+        assert!(is_valid_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"));
+        // Checksum broken by the last character
+        assert!(!is_valid_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ"));
+        assert!(!is_valid_ss58("0x1234"));
+        assert_eq!(ss58_prefix("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"), Some(42));
+    }
 }
@@ -0,0 +1,207 @@
+//! Balance checks made before anything is signed, so a transaction that
+//! can't pay for itself is refused locally instead of failing on-chain, and
+//! the spending limits that ask for a typed confirmation first.
+
+use crate::config::NetworkProfile;
+use crate::spending::SpendingLimits;
+
+/// Known balances, in planck, against the configured minimum. Unknown
+/// balances (not fetched yet, gateway down) never block.
+#[derive(Debug, Clone)]
+pub struct Funds {
+    pub wallet: Option<u128>,
+    /// Balance of the agent account, once the view screen has fetched it
+    pub agent: Option<u128>,
+    /// Balance to keep for fees; below it a warning is shown
+    pub min: u128,
+    pub decimals: u8,
+    pub symbol: String,
+    /// Network name, keying the day's spending
+    pub network: String,
+    /// Most one transaction sends without a typed confirmation
+    pub per_transaction: Option<u128>,
+    /// Most a day's transactions send without a typed confirmation
+    pub per_day: Option<u128>,
+}
+
+impl Funds {
+    pub fn new(wallet: Option<u128>, agent: Option<u128>, min: u128, network: &NetworkProfile) -> Self {
+        Self {
+            wallet,
+            agent,
+            min,
+            decimals: network.decimals,
+            symbol: network.token_symbol.clone(),
+            network: network.name.clone(),
+            per_transaction: None,
+            per_day: None,
+        }
+    }
+
+    /// With the limits of `spending_limits` in this network's token.
+    pub fn with_limits(self, limits: &SpendingLimits) -> Self {
+        let planck = |amount: &Option<String>| amount.as_deref().and_then(|a| parse_tokens(a, self.decimals));
+        Self { per_transaction: planck(&limits.per_transaction), per_day: planck(&limits.per_day), ..self }
+    }
+
+    /// Why sending `amount` needs its amount typed to confirm, going by what
+    /// was sent today; None within the limits.
+    pub fn over_limit(&self, amount: u128) -> Option<String> {
+        if self.per_transaction.is_none() && self.per_day.is_none() {
+            return None;
+        }
+        self.limit_problem(amount, crate::spending::spent_today(&self.network))
+    }
+
+    fn limit_problem(&self, amount: u128, spent: u128) -> Option<String> {
+        let tokens = |planck| format!("{} {}", format_tokens(planck, self.decimals), self.symbol);
+        if let Some(limit) = self.per_transaction.filter(|&limit| amount > limit) {
+            return Some(format!("{} is over the {} limit per transaction", tokens(amount), tokens(limit)));
+        }
+        let total = spent.saturating_add(amount);
+        self.per_day
+            .filter(|&limit| total > limit)
+            .map(|limit| format!("{} today would be over the {} daily limit", tokens(total), tokens(limit)))
+    }
+
+    /// The wallet can send `spend` and keep the minimum for fees.
+    pub fn check_wallet(&self, spend: u128) -> Result<(), String> {
+        let need = spend.saturating_add(self.min);
+        match self.wallet {
+            Some(have) if have < need => Err(self.insufficient("", need, have)),
+            _ => Ok(()),
+        }
+    }
+
+    /// The agent account holds the minimum it needs to pay for runs.
+    pub fn check_agent(&self) -> Result<(), String> {
+        match self.agent {
+            Some(have) if have < self.min => Err(self.insufficient("agent ", self.min, have)),
+            _ => Ok(()),
+        }
+    }
+
+    fn insufficient(&self, whose: &str, need: u128, have: u128) -> String {
+        format!(
+            "Insufficient {}funds: need {} {}, have {} {}",
+            whose,
+            format_tokens(need, self.decimals),
+            self.symbol,
+            format_tokens(have, self.decimals),
+            self.symbol
+        )
+    }
+
+    /// Persistent warning while either account is below the minimum.
+    pub fn warning(&self) -> Option<String> {
+        let min = format_tokens(self.min, self.decimals);
+        let wallet_low = self.wallet.is_some_and(|b| b < self.min);
+        let agent_low = self.agent.is_some_and(|b| b < self.min);
+        match (wallet_low, agent_low) {
+            (true, true) => Some(format!(
+                "Wallet and agent are below {} {}: deploys, prompts and scheduled runs will fail until funded",
+                min, self.symbol
+            )),
+            (true, false) => Some(format!(
+                "Wallet is below {} {}: deploys and prompts are blocked until it is funded",
+                min, self.symbol
+            )),
+            (false, true) => Some(format!(
+                "Agent is below {} {}: prompts and scheduled runs will fail, [t] on the agent view tops it up",
+                min, self.symbol
+            )),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Planck as a decimal token amount, without trailing zeros ("1.5", "0.001").
+/// Works on the digits, so no number of decimals can overflow.
+pub fn format_tokens(planck: u128, decimals: u8) -> String {
+    let digits = format!("{:0>width$}", planck, width = decimals as usize + 1);
+    let (whole, frac) = digits.split_at(digits.len() - decimals as usize);
+    match frac.trim_end_matches('0') {
+        "" => whole.to_string(),
+        frac => format!("{}.{}", whole, frac),
+    }
+}
+
+/// Token amount typed by the user ("1.5") in planck; None if it isn't a
+/// non-negative number, has more decimals than the token or doesn't fit.
+pub fn parse_tokens(amount: &str, decimals: u8) -> Option<u128> {
+    let amount = amount.trim();
+    let (whole, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && frac.is_empty() || frac.len() > decimals as usize {
+        return None;
+    }
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if !digits(whole) || !digits(frac) {
+        return None;
+    }
+    let unit = 10u128.checked_pow(decimals as u32)?;
+    let whole: u128 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let frac: u128 = if frac.is_empty() { 0 } else { format!("{:0<width$}", frac, width = decimals as usize).parse().ok()? };
+    whole.checked_mul(unit)?.checked_add(frac)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIT: u128 = 1_000_000_000_000;
+
+    fn funds(wallet: Option<u128>, agent: Option<u128>) -> Funds {
+        Funds::new(wallet, agent, UNIT, &NetworkProfile::local())
+    }
+
+    #[test]
+    fn test_checks() {
+        assert!(funds(None, None).check_wallet(5 * UNIT).is_ok());
+        assert!(funds(Some(6 * UNIT), None).check_wallet(5 * UNIT).is_ok());
+        assert_eq!(
+            funds(Some(5 * UNIT), None).check_wallet(5 * UNIT),
+            Err("Insufficient funds: need 6 THE, have 5 THE".to_string())
+        );
+        assert_eq!(
+            funds(None, Some(UNIT / 2)).check_agent(),
+            Err("Insufficient agent funds: need 1 THE, have 0.5 THE".to_string())
+        );
+        assert!(funds(Some(UNIT), Some(UNIT)).warning().is_none());
+        assert!(funds(Some(0), None).warning().unwrap().starts_with("Wallet is below 1 THE"));
+    }
+
+    #[test]
+    fn test_spending_limits() {
+        let limits = SpendingLimits { per_transaction: Some("5".to_string()), per_day: Some("8".to_string()) };
+        let funds = funds(None, None).with_limits(&limits);
+        assert_eq!(funds.limit_problem(5 * UNIT, 0), None);
+        assert_eq!(
+            funds.limit_problem(50 * UNIT, 0).as_deref(),
+            Some("50 THE is over the 5 THE limit per transaction")
+        );
+        assert_eq!(
+            funds.limit_problem(4 * UNIT, 5 * UNIT).as_deref(),
+            Some("9 THE today would be over the 8 THE daily limit")
+        );
+        assert_eq!(funds.limit_problem(3 * UNIT, 5 * UNIT), None);
+        assert_eq!(self::funds(None, None).over_limit(1_000 * UNIT), None);
+    }
+
+    #[test]
+    fn test_token_amounts() {
+        assert_eq!(format_tokens(1_500_000_000_000, 12), "1.5");
+        assert_eq!(format_tokens(1_000_000_000, 12), "0.001");
+        assert_eq!(parse_tokens("1.5", 12), Some(1_500_000_000_000));
+        assert_eq!(parse_tokens(".25", 12), Some(250_000_000_000));
+        assert_eq!(parse_tokens("2", 12), Some(2 * UNIT));
+        assert_eq!(parse_tokens("0.0000000000001", 12), None);
+        assert_eq!(parse_tokens("-1", 12), None);
+        assert_eq!(parse_tokens(".", 12), None);
+        // Unvalidated profiles get None or digits, never an overflow
+        assert_eq!(format_tokens(7, 0), "7");
+        assert_eq!(format_tokens(u128::MAX, 40), format!("0.0{}", u128::MAX));
+        assert_eq!(parse_tokens("1", 40), None);
+        assert_eq!(parse_tokens("1", 38), Some(10u128.pow(38)));
+        assert_eq!(parse_tokens("100", 38), None);
+    }
+}
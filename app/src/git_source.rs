@@ -0,0 +1,148 @@
+//! Agent files from a git repository, given where a custom directory would
+//! go as `<url>[#<ref>][:<subdir>]` (the form `docker build` takes).
+//!
+//! The repository is shallow-cloned under `git/` in the data directory and
+//! the files are read from the checkout like any custom directory. Each
+//! clone starts fresh, so deploying again picks up what was pushed since.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitSource {
+    pub url: String,
+    /// Branch or tag to check out; the default branch when None
+    pub reference: Option<String>,
+    /// Directory of the agent files inside the repository
+    pub subdir: Option<String>,
+}
+
+impl GitSource {
+    /// The source named by `input`, when it is a git URL rather than a path.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let (url, fragment) = input.split_once('#').unwrap_or((input, ""));
+        let remote = ["https://", "http://", "ssh://", "git://", "git@"].iter().any(|p| url.starts_with(p));
+        if !remote && !url.ends_with(".git") {
+            return None;
+        }
+        let (reference, subdir) = fragment.split_once(':').unwrap_or((fragment, ""));
+        let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+        Some(Self {
+            url: url.to_string(),
+            reference: non_empty(reference),
+            subdir: non_empty(subdir.trim_matches('/')),
+        })
+    }
+
+    /// Where the repository is cloned: one directory per URL and ref, named
+    /// by their hash so nothing in the URL can point the path elsewhere.
+    pub fn checkout_dir(&self) -> PathBuf {
+        let key = format!("{}#{}", self.url, self.reference.as_deref().unwrap_or_default());
+        git_dir().join(hex::encode(&sp_core::hashing::blake2_256(key.as_bytes())[..16]))
+    }
+
+    /// The agent files' directory in the checkout.
+    pub fn agent_dir(&self) -> PathBuf {
+        match &self.subdir {
+            Some(subdir) => self.checkout_dir().join(subdir),
+            None => self.checkout_dir(),
+        }
+    }
+
+    /// Shallow-clone the repository afresh and return the agent directory.
+    /// Git never prompts: credentials must come from a helper or SSH agent.
+    pub async fn clone_fresh(&self) -> Result<PathBuf> {
+        if self.subdir.as_deref().is_some_and(|s| Path::new(s).components().any(|c| !matches!(c, Component::Normal(_)))) {
+            bail!("The subdirectory must be a relative path inside the repository");
+        }
+        let dir = self.checkout_dir();
+        if !is_checkout_of(&git_dir(), &dir) {
+            bail!("Refusing to clone into {}, outside the checkouts directory", dir.display());
+        }
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).with_context(|| format!("Cannot remove the old checkout {}", dir.display()))?;
+        }
+        if let Some(parent) = dir.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Cannot create {}", parent.display()))?;
+        }
+
+        let mut git = tokio::process::Command::new("git");
+        git.args(["clone", "--depth", "1", "--quiet"]);
+        if let Some(reference) = &self.reference {
+            git.args(["--branch", reference]);
+        }
+        git.arg("--").arg(&self.url).arg(&dir);
+        // The TUI owns the terminal, so a password prompt would hang it
+        git.env("GIT_TERMINAL_PROMPT", "0");
+        if std::env::var_os("GIT_SSH_COMMAND").is_none() {
+            git.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes");
+        }
+        git.stdin(Stdio::null()).kill_on_drop(true);
+
+        let output = git.output().await.context("Cannot run git; is it installed?")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("git clone failed: {}", stderr.trim());
+        }
+        let agent_dir = self.agent_dir();
+        if !agent_dir.is_dir() {
+            bail!("{} has no directory {}", self.url, self.subdir.as_deref().unwrap_or_default());
+        }
+        Ok(agent_dir)
+    }
+}
+
+fn git_dir() -> PathBuf {
+    crate::config::data_dir().join("git")
+}
+
+/// `dir` is one directory directly under `root`, so removing it for a fresh
+/// clone can't touch anything else.
+fn is_checkout_of(root: &Path, dir: &Path) -> bool {
+    dir.strip_prefix(root).is_ok_and(|rest| {
+        let mut parts = rest.components();
+        matches!((parts.next(), parts.next()), (Some(Component::Normal(_)), None))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_source() {
+        assert_eq!(GitSource::parse("/home/me/agent"), None);
+        assert_eq!(GitSource::parse("./agent"), None);
+
+        let plain = GitSource::parse("https://github.com/me/pinchy.git").unwrap();
+        assert_eq!((plain.reference, plain.subdir), (None, None));
+
+        let full = GitSource::parse("git@github.com:me/agents.git#v2:bots/pinchy/").unwrap();
+        assert_eq!(full.url, "git@github.com:me/agents.git");
+        assert_eq!(full.reference.as_deref(), Some("v2"));
+        assert_eq!(full.subdir.as_deref(), Some("bots/pinchy"));
+        assert!(full.agent_dir().starts_with(full.checkout_dir()));
+        assert!(full.agent_dir().ends_with("bots/pinchy"));
+        let other_ref = GitSource { reference: Some("v3".to_string()), ..full.clone() };
+        assert_ne!(other_ref.checkout_dir(), full.checkout_dir());
+
+        let subdir_only = GitSource::parse("https://example.com/agents#:pinchy").unwrap();
+        assert_eq!((subdir_only.reference, subdir_only.subdir.as_deref()), (None, Some("pinchy")));
+    }
+
+    #[test]
+    fn test_checkout_stays_under_git_dir() {
+        for input in ["https://..", "https://.", "https://../..#..", "git@..:..git"] {
+            let dir = GitSource::parse(input).unwrap().checkout_dir();
+            assert!(is_checkout_of(&git_dir(), &dir), "{} clones into {}", input, dir.display());
+        }
+        let root = Path::new("/data/git");
+        assert!(is_checkout_of(root, &root.join("0a1b")));
+        assert!(!is_checkout_of(root, root));
+        assert!(!is_checkout_of(root, &root.join("..")));
+        assert!(!is_checkout_of(root, &root.join(".")));
+        assert!(!is_checkout_of(root, &root.join("a/b")));
+    }
+}
@@ -0,0 +1,123 @@
+//! ASCII fallback for icons, spinners and box drawing.
+//!
+//! Screens draw with Unicode glyphs throughout. On terminals that can't show
+//! them, the finished frame is rewritten cell by cell with ASCII stand-ins,
+//! so no screen needs to know which glyph set is active.
+
+use crate::config::GlyphMode;
+use ratatui::buffer::Buffer;
+
+/// Whether to draw with ASCII, resolving `auto` from the environment.
+pub fn use_ascii(mode: GlyphMode) -> bool {
+    match mode {
+        GlyphMode::Unicode => false,
+        GlyphMode::Ascii => true,
+        GlyphMode::Auto if cfg!(windows) => {
+            // Windows Terminal and most third-party terminals handle Unicode; the legacy console does not
+            std::env::var_os("WT_SESSION").is_none() && std::env::var_os("TERM_PROGRAM").is_none()
+        }
+        GlyphMode::Auto => {
+            let term = std::env::var("TERM").ok();
+            // The first locale variable that is set decides, as with setlocale
+            let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()));
+            !unicode_capable(term.as_deref(), locale.as_deref())
+        }
+    }
+}
+
+/// Unicode needs a UTF-8 locale, and the Linux console's fonts lack most glyphs anyway.
+fn unicode_capable(term: Option<&str>, locale: Option<&str>) -> bool {
+    if matches!(term, Some("linux") | Some("vt100") | Some("vt220") | Some("dumb")) {
+        return false;
+    }
+    locale.is_some_and(|l| {
+        let l = l.to_ascii_lowercase();
+        l.contains("utf-8") || l.contains("utf8")
+    })
+}
+
+/// Single-width ASCII stand-in for a glyph the UI uses.
+fn ascii_for(glyph: char) -> Option<&'static str> {
+    let ascii = match glyph {
+        // Status icons
+        '✓' => "+",
+        '✗' => "x",
+        '●' => "*",
+        '○' => "o",
+        '◈' => "$",
+        '⚠' => "!",
+        '♥' => "<",
+        '⏸' => "=",
+        '•' => "*",
+        '·' => ".",
+        '…' => ".",
+        '—' | '–' => "-",
+        // Spinner frames, which turn into the classic | / - \
+        '◐' => "|",
+        '◓' => "/",
+        '◑' => "-",
+        '◒' => "\\",
+        // Arrows and pointers
+        '←' | '◀' => "<",
+        '→' | '▶' | '▸' => ">",
+        '↑' => "^",
+        '↓' => "v",
+        '≈' => "~",
+        // Block elements in the banner art and text cursors
+        '█' | '▓' | '▀' | '▄' => "#",
+        '▒' => ":",
+        '░' => ".",
+        '▌' | '▐' => "|",
+        '🦞' => "*",
+        // Box drawing, including the sets ratatui borders use
+        '─' | '━' | '═' | '╌' | '┄' => "-",
+        '│' | '┃' | '║' | '╎' | '┆' => "|",
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╯' | '╰' | '┏' | '┓' | '┗' | '┛'
+        | '╔' | '╗' | '╚' | '╝' | '╠' | '╣' | '╦' | '╩' | '╬' => "+",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// Replace known Unicode glyphs in a rendered frame with their ASCII stand-ins.
+///
+/// Other text (post bodies, names) is left alone; cells owned by an image
+/// protocol are skipped.
+pub fn asciify(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if cell.skip {
+            continue;
+        }
+        let mut chars = cell.symbol().chars();
+        if let (Some(glyph), None) = (chars.next(), chars.next()) {
+            if let Some(ascii) = ascii_for(glyph) {
+                cell.set_symbol(ascii);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_unicode_probe() {
+        assert!(unicode_capable(Some("xterm-256color"), Some("en_US.UTF-8")));
+        assert!(unicode_capable(None, Some("C.utf8")));
+        assert!(!unicode_capable(Some("xterm"), Some("C")));
+        assert!(!unicode_capable(Some("xterm"), None));
+        assert!(!unicode_capable(Some("linux"), Some("en_US.UTF-8")));
+    }
+
+    #[test]
+    fn test_asciify_keeps_other_text() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 12, 1));
+        buffer.set_string(0, 0, "│✓ café ◐", ratatui::style::Style::default());
+        asciify(&mut buffer);
+        assert_eq!(buffer, Buffer::with_lines(["|+ café |   "]));
+    }
+}
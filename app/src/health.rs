@@ -0,0 +1,210 @@
+//! Agent health at a glance, worked out from its recent on-chain activity,
+//! balance and Moltbook registration, and the alert raised when its
+//! scheduled runs keep failing.
+
+use crate::client::{ActivityEvent, ActivityKind, MoltbookAgentInfo, RunRecord, RunStatus};
+
+/// Traffic-light rating of one health figure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Badge {
+    /// Nothing to judge by yet
+    Unknown,
+    Good,
+    Warn,
+    Bad,
+}
+
+/// Consecutive failures from which the agent counts as broken.
+const FAILURES_BAD: u32 = 3;
+
+/// Runs left on the balance below which it needs a top-up soon, and urgently.
+const RUNWAY_WARN: u128 = 10;
+const RUNWAY_BAD: u128 = 3;
+
+/// Health figures of an agent. Activity comes newest first, as the gateway
+/// returns it; only the events loaded are considered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Health {
+    /// Block of the latest completed run
+    pub last_success: Option<u64>,
+    /// Failed runs since the latest completed one
+    pub consecutive_failures: u32,
+    /// Finished runs whose start is in the window too
+    pub timed_runs: usize,
+    /// Mean blocks from start to completion or failure
+    pub average_blocks: Option<u64>,
+    /// Mean planck charged per run, from the runs that report a cost
+    pub average_cost: Option<u128>,
+    /// Runs the balance above `min_balance` still pays for
+    pub runway: Option<u128>,
+    /// Registered on Moltbook and claimed by its owner; None when not registered
+    pub claimed: Option<bool>,
+}
+
+impl Health {
+    pub fn new(events: &[ActivityEvent], balance: Option<u128>, min_balance: u128, moltbook: Option<&MoltbookAgentInfo>) -> Self {
+        let finished = |e: &&ActivityEvent| matches!(e.kind, ActivityKind::RunCompleted | ActivityKind::RunFailed);
+        let last_success = events.iter().find(|e| e.kind == ActivityKind::RunCompleted).map(|e| e.block_number);
+        let consecutive_failures = events
+            .iter()
+            .filter(finished)
+            .take_while(|e| e.kind == ActivityKind::RunFailed)
+            .count() as u32;
+
+        let durations: Vec<u64> = events
+            .iter()
+            .filter(finished)
+            .filter_map(|end| {
+                let run_id = end.run_id?;
+                let start = events.iter().find(|e| e.kind == ActivityKind::RunStarted && e.run_id == Some(run_id))?;
+                Some(end.block_number.saturating_sub(start.block_number))
+            })
+            .collect();
+        let average_blocks = (!durations.is_empty()).then(|| durations.iter().sum::<u64>() / durations.len() as u64);
+
+        let costs: Vec<u128> = events.iter().filter(finished).filter_map(|e| e.cost.as_deref()?.parse().ok()).collect();
+        let average_cost = (!costs.is_empty()).then(|| costs.iter().sum::<u128>() / costs.len() as u128);
+        let runway = match (balance, average_cost) {
+            (Some(balance), Some(cost)) if cost > 0 => Some(balance.saturating_sub(min_balance) / cost),
+            _ => None,
+        };
+
+        Self {
+            last_success,
+            consecutive_failures,
+            timed_runs: durations.len(),
+            average_blocks,
+            average_cost,
+            runway,
+            claimed: moltbook.map(|m| m.claimed),
+        }
+    }
+
+    pub fn last_success_badge(&self) -> Badge {
+        match (self.last_success, self.consecutive_failures) {
+            (Some(_), 0) => Badge::Good,
+            (Some(_), _) => Badge::Warn,
+            (None, 0) => Badge::Unknown,
+            (None, _) => Badge::Bad,
+        }
+    }
+
+    pub fn failures_badge(&self) -> Badge {
+        match self.consecutive_failures {
+            0 => Badge::Good,
+            n if n < FAILURES_BAD => Badge::Warn,
+            _ => Badge::Bad,
+        }
+    }
+
+    pub fn runway_badge(&self) -> Badge {
+        match self.runway {
+            None => Badge::Unknown,
+            Some(runs) if runs < RUNWAY_BAD => Badge::Bad,
+            Some(runs) if runs < RUNWAY_WARN => Badge::Warn,
+            Some(_) => Badge::Good,
+        }
+    }
+
+    pub fn claim_badge(&self) -> Badge {
+        match self.claimed {
+            Some(true) => Badge::Good,
+            Some(false) => Badge::Warn,
+            None => Badge::Bad,
+        }
+    }
+
+    /// The worst of the ratings, for the tab title.
+    pub fn overall(&self) -> Badge {
+        [self.last_success_badge(), self.failures_badge(), self.runway_badge(), self.claim_badge()]
+            .into_iter()
+            .max()
+            .unwrap_or(Badge::Unknown)
+    }
+}
+
+/// Scheduled runs failing in a row that raise the alert on the home screen.
+pub const ALERT_FAILURES: u32 = 2;
+
+/// Scheduled runs failing one after another, newest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureAlert {
+    /// Failed scheduled runs since the last one that completed
+    pub count: u32,
+    /// Latest failed run, whose events the alert opens
+    pub run_id: u64,
+    /// Why it failed, when the gateway says
+    pub last_error: Option<String>,
+}
+
+impl FailureAlert {
+    /// An alert when the latest `ALERT_FAILURES` or more finished scheduled
+    /// runs all failed. Prompted runs don't count either way.
+    pub fn from_runs(runs: &[RunRecord]) -> Option<Self> {
+        let mut failed = runs
+            .iter()
+            .filter(|r| r.scheduled && matches!(r.status, RunStatus::Completed | RunStatus::Failed))
+            .take_while(|r| r.status == RunStatus::Failed);
+        let latest = failed.next()?;
+        let count = 1 + failed.count() as u32;
+        (count >= ALERT_FAILURES).then(|| Self { count, run_id: latest.run_id, last_error: latest.error.clone() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: ActivityKind, block_number: u64, run_id: u64, cost: Option<&str>) -> ActivityEvent {
+        ActivityEvent { kind, block_number, timestamp: None, run_id: Some(run_id), detail: None, cost: cost.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_health_from_activity() {
+        let events = vec![
+            event(ActivityKind::RunFailed, 130, 3, Some("100")),
+            event(ActivityKind::RunStarted, 126, 3, None),
+            event(ActivityKind::RunFailed, 112, 2, None),
+            event(ActivityKind::RunStarted, 110, 2, None),
+            event(ActivityKind::RunCompleted, 104, 1, Some("300")),
+            event(ActivityKind::RunStarted, 100, 1, None),
+        ];
+        let moltbook = MoltbookAgentInfo { name: "pinchy".to_string(), description: None, claimed: true, twitter_handle: None };
+        let health = Health::new(&events, Some(1_500), 500, Some(&moltbook));
+        assert_eq!(health.last_success, Some(104));
+        assert_eq!(health.consecutive_failures, 2);
+        assert_eq!((health.timed_runs, health.average_blocks), (3, Some(3)));
+        assert_eq!((health.average_cost, health.runway), (Some(200), Some(5)));
+        assert_eq!(
+            [health.last_success_badge(), health.failures_badge(), health.runway_badge(), health.claim_badge()],
+            [Badge::Warn, Badge::Warn, Badge::Warn, Badge::Good]
+        );
+
+        let quiet = Health::new(&[], None, 500, None);
+        assert_eq!(quiet.last_success_badge(), Badge::Unknown);
+        assert_eq!(quiet.overall(), Badge::Bad);
+    }
+
+    #[test]
+    fn test_failure_alert() {
+        let run = |run_id: u64, status: RunStatus, scheduled: bool| RunRecord {
+            run_id,
+            status,
+            scheduled,
+            error: (status == RunStatus::Failed).then(|| format!("out of gas in run {}", run_id)),
+        };
+        let mut runs = vec![
+            run(5, RunStatus::Running, true),
+            run(4, RunStatus::Failed, true),
+            run(3, RunStatus::Completed, false),
+            run(2, RunStatus::Completed, true),
+        ];
+        // One failure is not a pattern yet
+        assert_eq!(FailureAlert::from_runs(&runs), None);
+
+        runs[3] = run(2, RunStatus::Failed, true);
+        let alert = FailureAlert::from_runs(&runs).unwrap();
+        assert_eq!((alert.count, alert.run_id), (2, 4));
+        assert_eq!(alert.last_error.as_deref(), Some("out of gas in run 4"));
+    }
+}
@@ -0,0 +1,117 @@
+//! When the agent's next scheduled (heartbeat) run is expected.
+//!
+//! The chain runs an active agent every `schedule_blocks` blocks. The last
+//! `heartbeat_scheduled` event in its activity log anchors the schedule, and
+//! a run started in that block is the heartbeat run itself, live until its
+//! completed or failed event shows up.
+
+use crate::client::{ActivityEvent, ActivityKind, ChainAgentInfo};
+
+/// The agent's heartbeat schedule as of its latest activity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heartbeat {
+    /// Blocks between heartbeat runs
+    pub every: u64,
+    /// Block of the latest heartbeat
+    pub last: u64,
+    /// Heartbeat run that started and hasn't finished yet
+    pub running: Option<u64>,
+    /// Scheduling is paused on-chain
+    pub paused: bool,
+}
+
+/// What to show for the heartbeat at a given chain head.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeartbeatStatus {
+    Paused,
+    Running { run_id: u64 },
+    /// Expected at `block`, `blocks_left` from the head
+    Next { block: u64, blocks_left: u64 },
+    /// Past its block and not started yet
+    Due,
+    /// Chain head not known yet
+    Unknown { block: u64 },
+}
+
+impl Heartbeat {
+    /// None when the agent has no schedule or hasn't had a heartbeat yet.
+    pub fn from_activity(chain: &ChainAgentInfo, events: &[ActivityEvent]) -> Option<Self> {
+        let every = chain.schedule_blocks.filter(|&b| b > 0)? as u64;
+        let last = events
+            .iter()
+            .filter(|e| e.kind == ActivityKind::HeartbeatScheduled)
+            .map(|e| e.block_number)
+            .max()?;
+        let finished = |run_id: u64| {
+            events
+                .iter()
+                .any(|e| matches!(e.kind, ActivityKind::RunCompleted | ActivityKind::RunFailed) && e.run_id == Some(run_id))
+        };
+        let running = events
+            .iter()
+            .filter(|e| e.kind == ActivityKind::RunStarted && e.block_number == last)
+            .filter_map(|e| e.run_id)
+            .find(|&run_id| !finished(run_id));
+        Some(Self { every, last, running, paused: !chain.active })
+    }
+
+    pub fn status(&self, head: Option<u64>) -> HeartbeatStatus {
+        let next = self.last + self.every;
+        match (self.running, head) {
+            _ if self.paused => HeartbeatStatus::Paused,
+            (Some(run_id), _) => HeartbeatStatus::Running { run_id },
+            (None, None) => HeartbeatStatus::Unknown { block: next },
+            (None, Some(head)) if head >= next => HeartbeatStatus::Due,
+            (None, Some(head)) => HeartbeatStatus::Next { block: next, blocks_left: next - head },
+        }
+    }
+}
+
+impl HeartbeatStatus {
+    pub fn label(self) -> String {
+        match self {
+            HeartbeatStatus::Paused => "paused".to_string(),
+            HeartbeatStatus::Running { run_id } => format!("running now (run #{})", run_id),
+            HeartbeatStatus::Next { block, blocks_left } => format!("next at #{} (in {} blocks)", block, blocks_left),
+            HeartbeatStatus::Due => "due now".to_string(),
+            HeartbeatStatus::Unknown { block } => format!("next at #{}", block),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: ActivityKind, block_number: u64, run_id: Option<u64>) -> ActivityEvent {
+        ActivityEvent { kind, block_number, timestamp: None, run_id, detail: None, cost: None }
+    }
+
+    #[test]
+    fn test_heartbeat_status() {
+        let chain = ChainAgentInfo {
+            owner: "5Owner".to_string(),
+            name: "clawdia".to_string(),
+            active: true,
+            version: 1,
+            schedule_blocks: Some(600),
+        };
+        let mut events = vec![
+            event(ActivityKind::RunStarted, 1000, Some(7)),
+            event(ActivityKind::HeartbeatScheduled, 1000, None),
+            event(ActivityKind::HeartbeatScheduled, 400, None),
+        ];
+        let heartbeat = Heartbeat::from_activity(&chain, &events).unwrap();
+        assert_eq!(heartbeat.status(Some(1002)), HeartbeatStatus::Running { run_id: 7 });
+
+        events.insert(0, event(ActivityKind::RunCompleted, 1003, Some(7)));
+        let heartbeat = Heartbeat::from_activity(&chain, &events).unwrap();
+        assert_eq!(heartbeat.status(Some(1100)), HeartbeatStatus::Next { block: 1600, blocks_left: 500 });
+        assert_eq!(heartbeat.status(Some(1600)), HeartbeatStatus::Due);
+
+        let paused = ChainAgentInfo { active: false, ..chain.clone() };
+        assert_eq!(Heartbeat::from_activity(&paused, &events).unwrap().status(Some(1100)), HeartbeatStatus::Paused);
+        let unscheduled = ChainAgentInfo { schedule_blocks: None, ..chain };
+        assert!(Heartbeat::from_activity(&unscheduled, &events).is_none());
+    }
+}
@@ -0,0 +1,202 @@
+//! Syntax highlighting for the `.ship` agent language.
+//!
+//! A line-based scanner, not a parser: it knows keywords, types, the tools the
+//! file declares, strings (including multi-line template strings), comments,
+//! attributes and numbers, which is enough to make agent code readable.
+
+use crate::theme::Theme;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+const KEYWORDS: &[&str] = &[
+    "const", "let", "struct", "tool", "node", "if", "else", "for", "while", "in", "return", "goto", "true",
+    "false", "null", "self",
+];
+
+const TYPES: &[&str] = &["string", "bool", "any", "bytes", "bytes32", "int", "u8", "u32", "u64", "u128", "f64"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Plain,
+    Keyword,
+    Type,
+    /// Name of a `tool` declared in the file
+    Tool,
+    String,
+    Comment,
+    /// `#[entry]`, `#[agent(...)]` and friends
+    Attribute,
+    Number,
+}
+
+impl Token {
+    fn style(self, theme: &Theme) -> Style {
+        match self {
+            Token::Plain => Style::default().fg(theme.text),
+            Token::Keyword => Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            Token::Type => Style::default().fg(theme.info),
+            Token::Tool => Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+            Token::String => Style::default().fg(theme.success),
+            Token::Comment => Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+            Token::Attribute => Style::default().fg(theme.highlight),
+            Token::Number => Style::default().fg(theme.secondary),
+        }
+    }
+}
+
+/// Highlight a whole `.ship` source, one `Line` per source line.
+pub fn highlight_ship(source: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let tools = tool_names(source);
+    let mut in_template = false;
+    source
+        .lines()
+        .map(|line| {
+            let spans = tokenize(line, &tools, &mut in_template)
+                .into_iter()
+                .map(|(token, text)| Span::styled(text, token.style(theme)))
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Names declared with `tool name(...)`.
+fn tool_names(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix("tool "))
+        .filter_map(|rest| {
+            let name: String = rest.trim_start().chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            (!name.is_empty()).then_some(name)
+        })
+        .collect()
+}
+
+/// Split one line into runs of the same token kind. `in_template` carries an
+/// open backtick string over to the next line.
+fn tokenize(line: &str, tools: &[String], in_template: &mut bool) -> Vec<(Token, String)> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens: Vec<(Token, String)> = Vec::new();
+    let mut push = |token: Token, text: &[char]| match tokens.last_mut() {
+        Some((last, run)) if *last == token => run.extend(text),
+        _ => tokens.push((token, text.iter().collect())),
+    };
+
+    let mut i = 0;
+    if *in_template {
+        let end = closing(&chars, 0, '`');
+        push(Token::String, &chars[..end.unwrap_or(chars.len())]);
+        *in_template = end.is_none();
+        i = end.unwrap_or(chars.len());
+    }
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let end = if c == '/' && next == Some('/') {
+            push(Token::Comment, &chars[i..]);
+            chars.len()
+        } else if c == '"' || c == '`' {
+            let end = closing(&chars, i + 1, c);
+            if c == '`' && end.is_none() {
+                *in_template = true;
+            }
+            let end = end.unwrap_or(chars.len());
+            push(Token::String, &chars[i..end]);
+            end
+        } else if c == '#' && next == Some('[') {
+            let end = chars[i..].iter().position(|c| *c == ']').map_or(chars.len(), |p| i + p + 1);
+            push(Token::Attribute, &chars[i..end]);
+            end
+        } else if c.is_ascii_digit() {
+            let end = run_end(&chars, i);
+            push(Token::Number, &chars[i..end]);
+            end
+        } else if c.is_alphabetic() || c == '_' {
+            let end = run_end(&chars, i);
+            let word: String = chars[i..end].iter().collect();
+            let token = if KEYWORDS.contains(&word.as_str()) {
+                Token::Keyword
+            } else if TYPES.contains(&word.as_str()) {
+                Token::Type
+            } else if tools.contains(&word) {
+                Token::Tool
+            } else {
+                Token::Plain
+            };
+            push(token, &chars[i..end]);
+            end
+        } else {
+            push(Token::Plain, &chars[i..i + 1]);
+            i + 1
+        };
+        i = end;
+    }
+    tokens
+}
+
+/// Index just past the unescaped `quote` closing a string that starts at `from`.
+fn closing(chars: &[char], from: usize, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (offset, c) in chars[from..].iter().enumerate() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if *c == quote => return Some(from + offset + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// End of an identifier or number starting at `start`.
+fn run_end(chars: &[char], start: usize) -> usize {
+    chars[start..]
+        .iter()
+        .position(|c| !(c.is_alphanumeric() || *c == '_'))
+        .map_or(chars.len(), |p| start + p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(line: &str, in_template: &mut bool) -> Vec<(Token, String)> {
+        tokenize(line, &["moltbook_get".to_string()], in_template)
+            .into_iter()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_line() {
+        let mut in_template = false;
+        let tokens = kinds(r#"const KEY: string = "a \"b\""; // note"#, &mut in_template);
+        assert_eq!(tokens[0], (Token::Keyword, "const".to_string()));
+        assert_eq!(tokens[2], (Token::Type, "string".to_string()));
+        assert_eq!(tokens[4], (Token::String, r#""a \"b\"""#.to_string()));
+        assert_eq!(tokens.last().unwrap(), &(Token::Comment, "// note".to_string()));
+
+        let tokens = kinds("#[entry] .tools([moltbook_get])", &mut in_template);
+        assert_eq!(tokens[0], (Token::Attribute, "#[entry]".to_string()));
+        assert!(tokens.contains(&(Token::Tool, "moltbook_get".to_string())));
+    }
+
+    #[test]
+    fn test_template_spans_lines() {
+        let mut in_template = false;
+        kinds("messages.push(user(`first", &mut in_template);
+        assert!(in_template);
+        let tokens = kinds("  second ${X}`));", &mut in_template);
+        assert!(!in_template);
+        assert_eq!(tokens[0], (Token::String, "  second ${X}`".to_string()));
+    }
+
+    #[test]
+    fn test_tool_names() {
+        let source = "tool moltbook_get(endpoint: string) -> R;\n  tool  post(x: any);\nlet tool_x = 1;";
+        assert_eq!(tool_names(source), vec!["moltbook_get", "post"]);
+    }
+}
@@ -0,0 +1,546 @@
+//! Key bindings active in each screen and step, listed by the help overlay.
+//!
+//! Keep these tables next to the matching `handle_key` arms in mind: when a
+//! screen gains or loses a key, update its table here too.
+
+use crate::{
+    app::{App, AppScreen},
+    screens::{
+        compose::ComposeStep,
+        create::{CreateStep, ScheduleField},
+        onboarding::OnboardingStep,
+        prompt::PromptStep,
+        transfer::TransferStep,
+        view::ViewTab,
+    },
+};
+use crossterm::event::KeyCode;
+
+/// Keys as shown to the user, and what they do.
+pub type Binding = (&'static str, &'static str);
+
+const ONBOARDING: &[Binding] = &[("Enter", "Continue with this step"), ("Esc", "Skip the walkthrough")];
+
+const ONBOARDING_WALLET: &[Binding] = &[
+    ("s", "Show or hide the recovery phrase"),
+    ("Enter", "Confirm the phrase is written down"),
+    ("Esc", "Skip the walkthrough"),
+];
+
+const ONBOARDING_DONE: &[Binding] = &[("Enter / Esc", "Go to the menu")];
+
+const EMAIL_INPUT: &[Binding] = &[
+    ("type", "Enter your email address"),
+    ("Enter", "Send the magic link"),
+    ("Esc", "Back"),
+];
+
+const AUTH: &[Binding] = &[("Esc", "Cancel login")];
+
+const CREATE_SOURCE: &[Binding] = &[
+    ("↑ / ↓", "Switch between embedded and custom agent files"),
+    ("Tab", "Use a custom directory"),
+    ("Enter", "Continue with the selected files"),
+    ("Esc", "Back"),
+];
+
+const CREATE_SOURCE_CUSTOM: &[Binding] = &[("type", "Edit the directory path")];
+
+const CREATE_INFO: &[Binding] = &[
+    ("type", "Edit the focused field"),
+    ("Tab / Shift+Tab / ↑ / ↓", "Next / previous field: name, description, API key"),
+    ("Ctrl+R", "Show or hide the API key while its field has focus"),
+    ("Ctrl+V", "Paste the API key found on the clipboard, when one is offered"),
+    ("Enter", "Register on Moltbook"),
+    ("Esc", "Back"),
+];
+
+const CREATE_CLAIM: &[Binding] = &[
+    ("o", "Open the claim URL in the browser"),
+    ("c", "Check whether the agent was claimed"),
+    ("Esc", "Back"),
+];
+
+const CREATE_SOUL: &[Binding] = &[
+    ("y / Enter", "Accept SOUL.md"),
+    ("e", "Edit SOUL.md in $EDITOR (custom directory only)"),
+    ("Tab", "Switch between SOUL.md and the highlighted agent program"),
+    ("j / k", "Scroll the agent program"),
+    ("s", "Dry-run the agent with a test prompt, off-chain"),
+    ("Esc", "Back"),
+];
+
+const CREATE_SCHEDULE: &[Binding] = &[
+    ("↑ / ↓", "Choose how often the agent runs"),
+    ("Tab / Shift+Tab", "Next / previous field"),
+    ("Enter", "Next field"),
+    ("Esc", "Back"),
+];
+
+const CREATE_SCHEDULE_INPUT: &[Binding] = &[
+    ("0-9", "Edit the value"),
+    ("Tab / Shift+Tab", "Next / previous field"),
+    ("↑", "Previous field"),
+    ("Enter", "Continue (deploys from the deposit field)"),
+    ("Esc", "Back"),
+];
+
+const CREATE_FAUCET: &[Binding] = &[("f", "Request test tokens; deploying waits until they arrive")];
+
+const CREATE_WARNINGS: &[Binding] = &[
+    ("j / k", "Scroll the warnings"),
+    ("y / Enter", "Deploy anyway, after the review"),
+    ("Esc", "Back to the schedule, to fix the files and compile again"),
+];
+
+const CREATE_REVIEW: &[Binding] = &[
+    ("y", "Sign and deploy"),
+    ("Esc", "Back to the schedule"),
+];
+
+const CREATE_DONE: &[Binding] = &[
+    ("o", "Open the deploy's block on the explorer"),
+    ("Enter / Esc", "Back to home"),
+];
+
+const PROMPT_INPUT: &[Binding] = &[
+    ("type", "Write the prompt"),
+    ("Enter", "Sign and send it to the agent, or schedule it when Send At is set"),
+    ("↓ / ↑ / Shift+Tab", "Edit Send At (#block, +30m, 14:30 or 2026-10-18 14:30 UTC) / the prompt"),
+    ("Tab", "Switch to a dry run of the local agent files (nothing on-chain)"),
+    ("F2", "Quick-send one of the agent's favorite prompts"),
+    ("F3", "Attach a local text file, sent below the prompt"),
+    ("Esc", "Back"),
+];
+
+const PROMPT_RUNNING: &[Binding] = &[
+    ("j / k", "Scroll the conversation"),
+    ("d", "Toggle detailed tool output"),
+    ("t", "Show or hide the transaction's block and events"),
+    ("o", "Open the transaction's block on the explorer"),
+    ("f", "Save the prompt as a favorite, or remove it"),
+    ("n", "Type the next prompt, sent when this run completes"),
+    ("p", "Show or hide the agent's latest Moltbook posts beside the conversation"),
+    ("Esc", "Stop watching (the agent keeps running)"),
+];
+
+const PROMPT_FOLLOW_UP: &[Binding] = &[
+    ("type", "Write the next prompt"),
+    ("Enter", "Queue it; it is sent when the current run completes"),
+    ("Esc", "Discard it"),
+];
+
+const PROMPT_ATTACH: &[Binding] = &[
+    ("type", "Path of a text file (~/ for the home directory)"),
+    ("Enter", "Attach it, or remove the attachment when empty"),
+    ("Esc", "Cancel"),
+];
+
+const PROMPT_FAVORITES: &[Binding] = &[
+    ("1-9", "Send that favorite now"),
+    ("j / k", "Move through the favorites"),
+    ("Enter", "Send the highlighted favorite now"),
+    ("x", "Remove the highlighted favorite"),
+    ("Esc / F2", "Close"),
+];
+
+const PROMPT_DONE: &[Binding] = &[
+    ("j / k", "Scroll the conversation"),
+    ("d", "Toggle detailed tool output"),
+    ("t", "Show or hide the transaction's block and events"),
+    ("o", "Open the transaction's block on the explorer"),
+    ("r", "Retry a failed prompt (rebuilt and signed again)"),
+    ("f", "Save the prompt as a favorite, or remove it"),
+    ("p", "Show or hide the agent's latest Moltbook posts beside the conversation"),
+    ("Enter / Esc", "Back"),
+];
+
+const VIEW_AGENT: &[Binding] = &[
+    ("Tab", "Switch between Posts, Activity, Health, Stats and Versions"),
+    ("r", "Refresh"),
+    ("t", "Top up the agent account"),
+    ("p", "Pause or resume scheduled runs"),
+    ("e", "Edit the Moltbook profile"),
+    ("w", "Export a Markdown and HTML report of the agent"),
+    ("i", "Inspect the agent's on-chain state"),
+    ("Shift+O", "Transfer ownership"),
+    ("Shift+X", "Retire the agent"),
+    ("Esc", "Back"),
+];
+
+const VIEW_POSTS: &[Binding] = &[
+    ("j / k", "Move through posts"),
+    ("g / G", "First / last post"),
+    ("Enter", "Open the post"),
+    ("o", "Open the post on Moltbook"),
+];
+
+const VIEW_ACTIVITY: &[Binding] = &[
+    ("j / k", "Move through events"),
+    ("g / G", "First / last event"),
+    ("o", "Open the agent on the block explorer"),
+];
+
+const VIEW_HEALTH: &[Binding] = &[
+    ("R", "Reload the activity and balance the figures come from"),
+];
+
+const VIEW_STATS: &[Binding] = &[
+    ("R", "Reload the posts and totals the figures come from"),
+];
+
+const VIEW_VERSIONS: &[Binding] = &[
+    ("R", "Reload the version history and the chain's version counter"),
+];
+
+const VIEW_DETAIL: &[Binding] = &[
+    ("j / k", "Move through the comments"),
+    ("Space / Enter", "Fold or unfold the replies to a comment"),
+    ("u / d", "Upvote / downvote as the agent"),
+    ("c", "Comment as the agent"),
+    ("o", "Open the post on Moltbook"),
+    ("Esc", "Back to the list"),
+];
+
+const VIEW_COMMENT: &[Binding] = &[
+    ("type", "Write the comment"),
+    ("Enter", "Post it"),
+    ("Esc", "Discard"),
+];
+
+const VIEW_TOP_UP: &[Binding] = &[("y / Enter", "Send the top-up"), ("any key", "Cancel")];
+
+const VIEW_PROFILE: &[Binding] = &[
+    ("type", "Edit the focused field"),
+    ("Tab / ↑ / ↓", "Next / previous field"),
+    ("Enter", "Next field, or save on the button"),
+    ("Esc", "Close without saving"),
+];
+
+const VIEW_RETIRE: &[Binding] = &[
+    ("type", "Type the agent name to confirm"),
+    ("Tab", "Toggle sweeping the balance back"),
+    ("Enter", "Retire the agent"),
+    ("Esc", "Cancel"),
+];
+
+const VIEW_TRANSFER_ADDRESS: &[Binding] = &[
+    ("type", "New owner's SS58 address"),
+    ("Enter", "Review the transfer"),
+    ("Esc", "Cancel"),
+];
+
+const VIEW_TRANSFER_CONFIRM: &[Binding] = &[
+    ("y", "Sign the transfer"),
+    ("any key", "Edit the address"),
+    ("Esc", "Cancel"),
+];
+
+const COMPOSE_EDIT: &[Binding] = &[
+    ("type", "Edit the focused field"),
+    ("Tab / ↑ / ↓", "Next / previous field"),
+    ("Enter", "Next field, new line in the body, or publish"),
+    ("Esc", "Back"),
+];
+
+const COMPOSE_DONE: &[Binding] = &[("n", "Write another post"), ("Enter / Esc", "Back")];
+
+const FEED: &[Binding] = &[
+    ("j / k", "Move through posts"),
+    ("g / G", "First / last post"),
+    ("s", "Cycle sort: hot, new, top, rising"),
+    ("r", "Refresh"),
+    ("Enter / p", "Prompt the agent about the post"),
+    ("Esc", "Back"),
+];
+
+const SUBMOLTS: &[Binding] = &[
+    ("j / k", "Move through submolts"),
+    ("g / G", "First / last submolt"),
+    ("Enter / Space", "Join or leave"),
+    ("r", "Refresh"),
+    ("Esc", "Back"),
+];
+
+const INBOX: &[Binding] = &[
+    ("j / k", "Move through messages"),
+    ("g / G", "First / last message"),
+    ("r", "Refresh"),
+    ("Enter", "Open the comment thread of a mention or reply"),
+    ("p", "Prompt the agent to respond"),
+    ("Esc", "Back"),
+];
+
+const INBOX_THREAD: &[Binding] = &[
+    ("j / k", "Move through the comments"),
+    ("Space / Enter", "Fold or unfold the replies to a comment"),
+    ("p", "Prompt the agent to respond"),
+    ("Esc", "Back to the inbox"),
+];
+
+const LEADERBOARD: &[Binding] = &[
+    ("j / k", "Move through the rankings"),
+    ("g / G", "Top / bottom"),
+    ("Tab", "Switch board: most upvoted, most active this week"),
+    ("r", "Refresh"),
+    ("Esc", "Back"),
+];
+
+const RUNS: &[Binding] = &[
+    ("j / k", "Move through runs, or scroll the comparison"),
+    ("Space", "Pick a run (up to two)"),
+    ("Enter", "Compare the picked runs"),
+    ("Esc", "Back"),
+];
+
+const INSPECT: &[Binding] = &[
+    ("j / k", "Scroll"),
+    ("r", "Read the storage again"),
+    ("Esc", "Back"),
+];
+
+const SEARCH: &[Binding] = &[
+    ("j / k, n / N", "Next / previous match"),
+    ("Enter", "Jump to the match in its run's transcript"),
+    ("/", "New search"),
+    ("Esc", "Back to the results, then out"),
+];
+
+const SEARCH_EDIT: &[Binding] = &[("type", "What to look for"), ("Enter", "Search"), ("Esc", "Back")];
+
+const SETTINGS: &[Binding] = &[
+    ("j / k", "Move between settings"),
+    ("Enter", "Edit, cycle or save"),
+    ("← / →", "Cycle network, theme, glyphs or key preset"),
+    ("Esc", "Back (unsaved changes are dropped)"),
+];
+
+const SETTINGS_EDIT: &[Binding] = &[("type", "Edit the value"), ("Enter", "Done"), ("Esc", "Discard the edit")];
+
+/// Keys the vim preset takes over outside text fields.
+pub const VIM_KEYS: [char; 8] = ['h', 'j', 'k', 'l', 'g', 'G', '/', ':'];
+
+/// The vim preset's keys, listed under each screen's own.
+pub const VIM_PRESET: &[Binding] = &[
+    ("h / j / k / l", "Left, down, up, right in lists"),
+    ("gg / G", "First / last item in lists"),
+    ("/", "Search the run logs"),
+    (":", "Command palette"),
+];
+
+/// What a key does under the vim preset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VimKey {
+    /// Hand this key to the screen instead
+    Key(KeyCode),
+    /// First `g` of `gg`, waiting for the second
+    Pending,
+    Search,
+    Palette,
+}
+
+/// Translate `key` under the vim preset. Motions apply only where the
+/// screen is a list or a scrolling view (`motions`), so a menu's letter
+/// shortcuts still reach it. `pending_g` carries a first `g` to the next
+/// key; anything but a second `g` drops it.
+pub fn vim_key(key: KeyCode, motions: bool, pending_g: &mut bool) -> VimKey {
+    let after_g = std::mem::take(pending_g);
+    match key {
+        KeyCode::Char('/') => VimKey::Search,
+        KeyCode::Char(':') => VimKey::Palette,
+        key if !motions => VimKey::Key(key),
+        KeyCode::Char('h') => VimKey::Key(KeyCode::Left),
+        KeyCode::Char('j') => VimKey::Key(KeyCode::Down),
+        KeyCode::Char('k') => VimKey::Key(KeyCode::Up),
+        KeyCode::Char('l') => VimKey::Key(KeyCode::Right),
+        KeyCode::Char('g') if after_g => VimKey::Key(KeyCode::Home),
+        KeyCode::Char('g') => {
+            *pending_g = true;
+            VimKey::Pending
+        }
+        KeyCode::Char('G') => VimKey::Key(KeyCode::End),
+        key => VimKey::Key(key),
+    }
+}
+
+/// Title and bindings for whatever currently has keyboard focus.
+pub fn active_bindings(app: &App) -> (&'static str, Vec<Binding>) {
+    match app.screen {
+        AppScreen::Home => ("Home", home_bindings(app)),
+        AppScreen::Onboarding => match app.onboarding.step(&app.config) {
+            OnboardingStep::BackupWallet => ("Getting started", ONBOARDING_WALLET.to_vec()),
+            OnboardingStep::Done => ("Getting started", ONBOARDING_DONE.to_vec()),
+            _ => ("Getting started", ONBOARDING.to_vec()),
+        },
+        AppScreen::EmailInput => ("Login", EMAIL_INPUT.to_vec()),
+        AppScreen::Auth => ("Login", AUTH.to_vec()),
+        AppScreen::Create => create_bindings(app),
+        AppScreen::Prompt => match app.prompt.step {
+            PromptStep::EnterPrompt if app.prompt.attach_input.is_some() => ("Attach file", PROMPT_ATTACH.to_vec()),
+            PromptStep::EnterPrompt if app.prompt.favorites_open => ("Favorite prompts", PROMPT_FAVORITES.to_vec()),
+            PromptStep::EnterPrompt => ("Prompt agent", PROMPT_INPUT.to_vec()),
+            PromptStep::Submitting | PromptStep::Running if app.prompt.follow_up_input.is_some() => {
+                ("Prompt agent", PROMPT_FOLLOW_UP.to_vec())
+            }
+            PromptStep::Submitting | PromptStep::Running => ("Prompt agent", PROMPT_RUNNING.to_vec()),
+            PromptStep::Complete => ("Prompt agent", PROMPT_DONE.to_vec()),
+        },
+        AppScreen::View => view_bindings(app),
+        AppScreen::Compose => match app.compose.step {
+            ComposeStep::Published { .. } => ("New post", COMPOSE_DONE.to_vec()),
+            _ => ("New post", COMPOSE_EDIT.to_vec()),
+        },
+        AppScreen::Feed => ("Feed", FEED.to_vec()),
+        AppScreen::Submolts => ("Submolts", SUBMOLTS.to_vec()),
+        AppScreen::Inbox if app.inbox.thread.is_some() => ("Comment thread", INBOX_THREAD.to_vec()),
+        AppScreen::Inbox => ("Inbox", INBOX.to_vec()),
+        AppScreen::Leaderboard => ("Leaderboard", LEADERBOARD.to_vec()),
+        AppScreen::Runs => ("Compare runs", RUNS.to_vec()),
+        AppScreen::Inspect => ("On-chain state", INSPECT.to_vec()),
+        AppScreen::Search if app.search.editing => ("Search runs", SEARCH_EDIT.to_vec()),
+        AppScreen::Search => ("Search runs", SEARCH.to_vec()),
+        AppScreen::Settings if app.settings.editing.is_some() => ("Settings", SETTINGS_EDIT.to_vec()),
+        AppScreen::Settings => ("Settings", SETTINGS.to_vec()),
+    }
+}
+
+/// Menu entries currently enabled on the home screen.
+fn home_bindings(app: &App) -> Vec<Binding> {
+    let mut bindings = Vec::new();
+    if !app.config.is_authenticated() {
+        bindings.push(("1", "Login with email"));
+    } else {
+        bindings.push(("1", "Create a new agent"));
+        if app.config.has_agent() {
+            bindings.push(("2", "Prompt the agent"));
+            bindings.push(("3", "View agent details"));
+            if app.failure_alert().is_some() {
+                bindings.push(("F", "Open the failing scheduled run"));
+            }
+            if app.config.moltbook_api_key.is_some() {
+                bindings.push(("5", "Write a post"));
+                bindings.push(("6", "Browse the feed"));
+                bindings.push(("7", "Browse submolts"));
+                bindings.push(("0", "Read the inbox"));
+            }
+        }
+        bindings.push(("L", "Agent leaderboard"));
+        bindings.push(("4", "Logout"));
+    }
+    bindings.push(("D", "Compare recorded runs"));
+    bindings.push(("/", "Search recorded runs"));
+    bindings.push(("8", "Settings"));
+    if app.wallet_address().is_some() && app.config.network().kind.has_faucet() {
+        bindings.push(("9", "Request test tokens"));
+    }
+    bindings.push(("B", "Hide or show the banner"));
+    bindings
+}
+
+fn create_bindings(app: &App) -> (&'static str, Vec<Binding>) {
+    let create = &app.create;
+    let bindings = match create.step {
+        CreateStep::SelectAgentSource if create.use_embedded => CREATE_SOURCE.to_vec(),
+        CreateStep::SelectAgentSource => [CREATE_SOURCE_CUSTOM, CREATE_SOURCE].concat(),
+        CreateStep::EnterAgentInfo => CREATE_INFO.to_vec(),
+        CreateStep::WaitingClaim => CREATE_CLAIM.to_vec(),
+        CreateStep::ReviewSoul => CREATE_SOUL.to_vec(),
+        CreateStep::ConfigureSchedule if create.schedule_focus.is_focused(ScheduleField::Schedule) => CREATE_SCHEDULE.to_vec(),
+        CreateStep::ConfigureSchedule
+            if create.schedule_focus.is_focused(ScheduleField::Balance)
+                && app.wallet_address().is_some()
+                && app.config.network().kind.has_faucet() =>
+        {
+            [CREATE_SCHEDULE_INPUT, CREATE_FAUCET].concat()
+        }
+        CreateStep::ConfigureSchedule => CREATE_SCHEDULE_INPUT.to_vec(),
+        CreateStep::CompileWarnings => CREATE_WARNINGS.to_vec(),
+        CreateStep::ReviewDeploy => CREATE_REVIEW.to_vec(),
+        CreateStep::Success => CREATE_DONE.to_vec(),
+        // Background steps ignore keys until they finish
+        CreateStep::RegisteringMoltbook | CreateStep::Compiling | CreateStep::Deploying => Vec::new(),
+    };
+    ("Create agent", bindings)
+}
+
+fn view_bindings(app: &App) -> (&'static str, Vec<Binding>) {
+    let view = &app.view;
+    if view.retire.is_some() {
+        return ("Retire agent", VIEW_RETIRE.to_vec());
+    }
+    if let Some(form) = &view.transfer {
+        return match form.step {
+            TransferStep::EnterAddress => ("Transfer ownership", VIEW_TRANSFER_ADDRESS.to_vec()),
+            TransferStep::Confirm => ("Transfer ownership", VIEW_TRANSFER_CONFIRM.to_vec()),
+            TransferStep::Submitting => ("Transfer ownership", Vec::new()),
+        };
+    }
+    if view.top_up_confirm {
+        return ("Top up", VIEW_TOP_UP.to_vec());
+    }
+    if view.profile.is_some() {
+        return ("Edit profile", VIEW_PROFILE.to_vec());
+    }
+    if view.comment_input.is_some() {
+        return ("Comment", VIEW_COMMENT.to_vec());
+    }
+    if view.detail_open {
+        return ("Post", VIEW_DETAIL.to_vec());
+    }
+    let tab = match view.tab {
+        ViewTab::Posts => VIEW_POSTS,
+        ViewTab::Activity => VIEW_ACTIVITY,
+        ViewTab::Health => VIEW_HEALTH,
+        ViewTab::Stats => VIEW_STATS,
+        ViewTab::Versions => VIEW_VERSIONS,
+    };
+    ("Agent details", [tab, VIEW_AGENT].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::signed_in_app;
+
+    #[test]
+    fn test_vim_key() {
+        let mut pending = false;
+        assert_eq!(vim_key(KeyCode::Char('h'), true, &mut pending), VimKey::Key(KeyCode::Left));
+        assert_eq!(vim_key(KeyCode::Char('g'), true, &mut pending), VimKey::Pending);
+        assert_eq!(vim_key(KeyCode::Char('g'), true, &mut pending), VimKey::Key(KeyCode::Home));
+        assert!(!pending);
+        vim_key(KeyCode::Char('g'), true, &mut pending);
+        assert_eq!(vim_key(KeyCode::Char('x'), true, &mut pending), VimKey::Key(KeyCode::Char('x')));
+        assert!(!pending);
+        assert_eq!(vim_key(KeyCode::Char(':'), true, &mut pending), VimKey::Palette);
+        // Outside lists letters stay shortcuts
+        assert_eq!(vim_key(KeyCode::Char('l'), false, &mut pending), VimKey::Key(KeyCode::Char('l')));
+        assert_eq!(vim_key(KeyCode::Char('g'), false, &mut pending), VimKey::Key(KeyCode::Char('g')));
+        assert!(!pending);
+        assert_eq!(vim_key(KeyCode::Char(':'), false, &mut pending), VimKey::Palette);
+    }
+
+    #[tokio::test]
+    async fn test_vim_preset() {
+        let mut app = signed_in_app();
+        let (tx, _rx) = tokio::sync::mpsc::channel(32);
+        app.config.keybindings.preset = crate::config::KeyPreset::Vim;
+        app.screen = AppScreen::Settings;
+        app.handle_key(KeyCode::Char('j').into(), tx.clone()).await.unwrap();
+        assert_eq!(app.settings.focus.index(), 1);
+        app.handle_key(KeyCode::Char(':').into(), tx.clone()).await.unwrap();
+        assert!(app.palette.is_some());
+        app.handle_key(KeyCode::Esc.into(), tx.clone()).await.unwrap();
+        app.handle_key(KeyCode::Char('/').into(), tx).await.unwrap();
+        assert_eq!(app.screen, AppScreen::Search);
+    }
+
+    #[tokio::test]
+    async fn test_vim_preset_keeps_home_shortcuts() {
+        let mut app = signed_in_app();
+        let (tx, _rx) = tokio::sync::mpsc::channel(32);
+        app.config.keybindings.preset = crate::config::KeyPreset::Vim;
+        app.handle_key(KeyCode::Char('l').into(), tx).await.unwrap();
+        assert_eq!(app.screen, AppScreen::Leaderboard);
+    }
+}
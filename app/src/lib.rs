@@ -0,0 +1,59 @@
+//! Proof of Lobster as a library: everything behind the `lobster` binary,
+//! so integration tests under `tests/` can drive the app like `main` does.
+//!
+//! Those tests use the render harness in `testing`, built for unit tests and
+//! with the `testing` feature.
+
+// Screens and dialogs are built with `new()`, never through `Default`
+#![allow(clippy::new_without_default)]
+
+pub mod agent_assets;
+pub mod analytics;
+pub mod app;
+pub mod artifact;
+pub mod attachment;
+pub mod auth;
+pub mod balance_stream;
+pub mod backup;
+pub mod banner;
+pub mod client;
+pub mod clipboard;
+pub mod config;
+pub mod crash;
+pub mod daemon;
+pub mod desktop;
+pub mod events;
+#[cfg(all(test, feature = "e2e"))]
+mod e2e;
+pub mod extrinsic;
+pub mod funds;
+pub mod git_source;
+pub mod glyphs;
+pub mod health;
+pub mod heartbeat;
+pub mod highlight;
+pub mod keymap;
+pub mod logging;
+pub mod metrics;
+pub mod mock;
+pub mod moltbook;
+pub mod nonce;
+pub mod notifications;
+pub mod price;
+pub mod report;
+pub mod runlog;
+pub mod scaffold;
+pub mod schedule;
+pub mod screens;
+pub mod secrets;
+pub mod spending;
+pub mod tasks;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod text;
+pub mod theme;
+pub mod update;
+pub mod versions;
+pub mod wallet;
+pub mod watcher;
+pub mod webhook;
@@ -0,0 +1,79 @@
+//! Diagnostics log under the config directory, rotated daily.
+//!
+//! The TUI owns the terminal, so nothing can be printed while it runs; this
+//! file is where failures that are not shown on screen end up.
+
+use crate::config::config_dir;
+use std::path::PathBuf;
+use tracing::level_filters::LevelFilter;
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::EnvFilter;
+
+/// Level used when neither `LOBSTER_LOG` nor `log_level` sets one.
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+
+/// Days of logs kept; older files are deleted on rotation.
+const KEEP_LOG_FILES: usize = 7;
+
+/// Where `lobster.YYYY-MM-DD.log` files are written.
+pub fn log_dir() -> PathBuf {
+    config_dir().join("logs")
+}
+
+/// Filter for `level`: a bare level applies to this app only (dependencies
+/// stay at warn), anything else is taken as `EnvFilter` directives.
+fn filter(level: &str) -> Result<EnvFilter, String> {
+    let level = level.trim();
+    let directives = match level.parse::<LevelFilter>() {
+        Ok(level) => format!("warn,lobster={}", level),
+        Err(_) => level.to_string(),
+    };
+    EnvFilter::try_new(&directives).map_err(|e| format!("Invalid log level {:?}: {}", level, e))
+}
+
+/// Start logging to the rotating file. `LOBSTER_LOG` wins over `configured`.
+/// The guard flushes buffered lines when dropped, so keep it until exit.
+pub fn init(configured: Option<&str>) -> Option<WorkerGuard> {
+    let requested = std::env::var("LOBSTER_LOG").ok().or_else(|| configured.map(str::to_string));
+    let (filter, invalid) = match requested.as_deref().map(filter) {
+        Some(Ok(filter)) => (filter, None),
+        Some(Err(e)) => (self::filter(DEFAULT_LOG_LEVEL).ok()?, Some(e)),
+        None => (self::filter(DEFAULT_LOG_LEVEL).ok()?, None),
+    };
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("lobster")
+        .filename_suffix("log")
+        .max_log_files(KEEP_LOG_FILES)
+        .build(log_dir())
+        .ok()?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false)
+        .try_init()
+        .ok()?;
+
+    if let Some(e) = invalid {
+        tracing::warn!("{}; using {}", e, DEFAULT_LOG_LEVEL);
+    }
+    Some(guard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        assert_eq!(filter("debug").unwrap().to_string(), "lobster=debug,warn");
+        assert_eq!(filter(" TRACE ").unwrap().to_string(), "lobster=trace,warn");
+        assert!(filter("lobster::client=debug,info").is_ok());
+        assert!(filter("lobster=loud").is_err());
+    }
+}
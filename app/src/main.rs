@@ -8,26 +8,22 @@
 //!             ██▒▒██    ██▒▒██
 //!            ══ PROOF OF LOBSTER ══
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{EnableFocusChange, EnableMouseCapture, Event, EventStream, KeyCode, KeyEventKind, KeyModifiers},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{enable_raw_mode, EnterAlternateScreen},
 };
+use futures::StreamExt;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::time::{interval, MissedTickBehavior};
 
-mod agent_assets;
-mod app;
-mod auth;
-mod client;
-mod config;
-mod extrinsic;
-mod moltbook;
-mod screens;
-mod wallet;
+use proof_of_lobster::{app, artifact, backup, config, crash, daemon, logging, scaffold, update};
 
 use app::{App, AppMessage};
 
@@ -36,28 +32,130 @@ use app::{App, AppMessage};
 #[command(about = "Proof of Lobster - Deploy Moltbook agents on Theseus")]
 #[command(version)]
 struct Cli {
-    /// Server URL (defaults to local development server)
-    #[arg(short, long, default_value = "http://localhost:8080")]
-    server: String,
+    /// Server URL (defaults to the saved setting, then the local development server)
+    #[arg(short, long)]
+    server: Option<String>,
 
     /// Path to agent files directory
     #[arg(short, long, default_value = "agent")]
     agent_dir: String,
+
+    /// Answer every gateway call with canned data instead of contacting a server
+    #[arg(long)]
+    mock: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create a custom agent directory to edit and deploy
+    Init {
+        /// Directory to create
+        dir: PathBuf,
+
+        /// Files to start from
+        #[arg(short, long, value_enum, default_value = "moltbook")]
+        template: scaffold::Template,
+
+        /// Overwrite agent files already in the directory
+        #[arg(long)]
+        force: bool,
+    },
+    /// Download the latest release, verify its signature and replace this binary
+    SelfUpdate,
+    /// Stay resident: follow the agents' runs, top up their accounts and print NDJSON events
+    Daemon,
+    /// Deploy a program compiled earlier, without compiling it again
+    Deploy {
+        /// Compiled artifact, from `artifacts` in the data directory
+        #[arg(long)]
+        artifact: PathBuf,
+
+        /// Tokens to fund the new agent account with
+        #[arg(long, default_value = "1")]
+        deposit: String,
+    },
+    /// Unpack an encrypted backup, exported from the command palette, on this machine
+    Restore {
+        /// Backup file, `lobster-backup-<time>.age`
+        file: PathBuf,
+
+        /// Replace files already here that differ from the backup
+        #[arg(long)]
+        force: bool,
+    },
+    /// Share settings across machines, without secrets or logins
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigAction {
+    /// Write the shareable settings as TOML, to stdout without a file
+    Export {
+        file: Option<PathBuf>,
+    },
+    /// Take over the settings of an exported file, keeping the login, wallet and agents
+    Import {
+        file: PathBuf,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    // Setup terminal
+    match cli.command {
+        Some(Command::Init { dir, template, force }) => {
+            scaffold::init(&dir, template, force)?;
+            let path = std::fs::canonicalize(&dir).unwrap_or(dir);
+            println!("Created an agent in {}", path.display());
+            println!("Edit SOUL.md and HEARTBEAT.md, then in lobster choose Create agent → Use custom directory");
+            println!("and enter that path (or set custom_agent_dir in config.toml).");
+            return Ok(());
+        }
+        Some(Command::SelfUpdate) => return update::self_update().await,
+        Some(Command::Restore { file, force }) => return backup::restore(&file, force),
+        Some(Command::Daemon) | Some(Command::Deploy { .. }) | Some(Command::Config { .. }) | None => {}
+    }
+
+    // Keep mock sessions away from the real login and wallet
+    if cli.mock && std::env::var_os("LOBSTER_CONFIG_DIR").is_none() {
+        config::set_config_dir(std::env::temp_dir().join("lobster-mock"));
+    }
+
+    // Loading also moves a config.json from earlier releases to config.toml
+    let config = config::AppConfig::load();
+    let _log_guard = logging::init(config.as_ref().ok().and_then(|c| c.log_level.as_deref()));
+    tracing::info!(version = update::CURRENT_VERSION, "starting");
+    crash::install_panic_hook();
+    // Before the terminal is taken over, so the error can be read
+    if let Err(e) = config {
+        tracing::error!("invalid config: {:#}", e);
+        anyhow::bail!("{:#}\nFix {} and start lobster again.", e, config::AppConfig::path().display());
+    }
+
+    match cli.command {
+        Some(Command::Daemon) => return daemon::run(cli.server, cli.mock).await,
+        Some(Command::Deploy { artifact, deposit }) => return artifact::deploy(cli.server, cli.mock, &artifact, &deposit).await,
+        Some(Command::Config { action }) => return config_command(action),
+        _ => {}
+    }
+
+    // Setup terminal; the guard puts it back on every way out, panics included
     enable_raw_mode()?;
+    let terminal_guard = crash::TerminalGuard::new();
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = App::new(cli.server, cli.agent_dir).await?;
+    let mut app = App::new(cli.server, cli.agent_dir, cli.mock).await?;
 
     // Create message channel for async operations
     let (tx, mut rx) = mpsc::channel::<AppMessage>(32);
@@ -66,73 +164,176 @@ async fn main() -> Result<()> {
     app.init_session(tx.clone());
 
     // Run app
-    let result = run_app(&mut terminal, &mut app, tx, &mut rx).await;
+    let result = run_app(&mut terminal, &mut app, tx.clone(), &mut rx).await;
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    drop(terminal_guard);
+
+    app.shutdown(tx, &mut rx).await;
 
     if let Err(e) = result {
+        tracing::error!("exited with error: {:#}", e);
         eprintln!("Error: {}", e);
     }
+    tracing::info!("exiting");
 
     Ok(())
 }
 
+/// `lobster config export|import`.
+fn config_command(action: ConfigAction) -> Result<()> {
+    let mut config = config::AppConfig::load()?;
+    match action {
+        ConfigAction::Export { file: None } => print!("{}", config.export_bundle()?),
+        ConfigAction::Export { file: Some(file) } => {
+            std::fs::write(&file, config.export_bundle()?).with_context(|| format!("Cannot write {}", file.display()))?;
+            println!("Exported the settings to {}", file.display());
+        }
+        ConfigAction::Import { file } => {
+            let bundle = std::fs::read_to_string(&file).with_context(|| format!("Cannot read {}", file.display()))?;
+            let (taken, skipped) =
+                config.import_bundle(&bundle).with_context(|| format!("{} is not a valid settings file", file.display()))?;
+            config.save()?;
+            println!("Imported {} from {}", if taken.is_empty() { "nothing".to_string() } else { taken.join(", ") }, file.display());
+            if !skipped.is_empty() {
+                println!("Skipped {}, kept to the machine it came from", skipped.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
     tx: mpsc::Sender<AppMessage>,
     rx: &mut mpsc::Receiver<AppMessage>,
 ) -> Result<()> {
-    // Periodic task timers
-    let mut last_jwt_check = std::time::Instant::now();
-    let mut last_balance_fetch = std::time::Instant::now();
-    
-    // Check JWT every 30 seconds
-    const JWT_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
-    // Fetch balance every 12 seconds (~2 blocks)
-    const BALANCE_FETCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(12);
-    
-    loop {
-        // Draw UI
-        terminal.draw(|f| app.render(f))?;
+    // Redraw rate while something on screen is animating
+    const ANIMATION_INTERVAL: Duration = Duration::from_millis(50);
+    // Redraw rate for "updated 5s ago" style labels
+    const CLOCK_INTERVAL: Duration = Duration::from_secs(1);
 
-        // Handle async messages
-        while let Ok(msg) = rx.try_recv() {
-            app.handle_message(msg, tx.clone()).await?;
-        }
-        
-        // Periodic JWT validation (only if authenticated)
-        if app.config.is_authenticated() && last_jwt_check.elapsed() >= JWT_CHECK_INTERVAL {
-            last_jwt_check = std::time::Instant::now();
-            app.check_session_validity(tx.clone());
+    let mut events = EventStream::new();
+
+    // Periodic task timers (first tick fires a full interval from now)
+    let mut jwt_check = interval_after(app.config.session_check_interval());
+    let mut balance_fetch = interval_after(app.config.balance_refresh_interval());
+    let mut animation = interval_after(ANIMATION_INTERVAL);
+    let mut clock = interval_after(CLOCK_INTERVAL);
+    let mut idle_check = interval_after(CLOCK_INTERVAL);
+    // View screen auto-refresh (configurable, may be disabled)
+    let mut view_refresh_period = app.config.view_refresh_interval();
+    let mut view_refresh = interval_after(view_refresh_period.unwrap_or(ANIMATION_INTERVAL));
+    // Claim check while the create wizard waits for it (may be disabled)
+    let mut claim_poll_period = app.config.claim_poll_interval();
+    let mut claim_poll = interval_after(claim_poll_period.unwrap_or(ANIMATION_INTERVAL));
+    // USD price feed (only when one is configured)
+    let mut price_refresh_period = app.config.price_refresh_interval();
+    let mut price_refresh = interval_after(price_refresh_period.unwrap_or(ANIMATION_INTERVAL));
+
+    // Set by anything that changes what is on screen; idle loops don't draw
+    let mut dirty = true;
+
+    loop {
+        // Settings screen saved new polling intervals
+        if std::mem::take(&mut app.intervals_changed) {
+            jwt_check = interval_after(app.config.session_check_interval());
+            balance_fetch = interval_after(app.config.balance_refresh_interval());
+            view_refresh_period = app.config.view_refresh_interval();
+            view_refresh = interval_after(view_refresh_period.unwrap_or(ANIMATION_INTERVAL));
+            claim_poll_period = app.config.claim_poll_interval();
+            claim_poll = interval_after(claim_poll_period.unwrap_or(ANIMATION_INTERVAL));
+            price_refresh_period = app.config.price_refresh_interval();
+            price_refresh = interval_after(price_refresh_period.unwrap_or(ANIMATION_INTERVAL));
+            app.refresh_price(tx.clone());
         }
-        
-        // Periodic balance fetch (only if authenticated and has wallet)
-        if app.config.is_authenticated() && app.wallet.is_some() && last_balance_fetch.elapsed() >= BALANCE_FETCH_INTERVAL {
-            last_balance_fetch = std::time::Instant::now();
-            app.refresh_balance(tx.clone());
+
+        if std::mem::take(&mut dirty) {
+            terminal.draw(|f| app.render(f))?;
+            // Drawing home finds out the size the banner needs
+            app.prepare_banner(tx.clone());
         }
 
-        // Poll for events with timeout
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    // Global quit
-                    if key.code == KeyCode::Char('q') && app.can_quit() {
-                        return Ok(());
+        tokio::select! {
+            maybe_event = events.next() => {
+                match maybe_event {
+                    Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                        dirty = true;
+                        app.last_input = std::time::Instant::now();
+                        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                        if app.lock.is_some() {
+                            // The lock takes every key; Ctrl-C leaves without unlocking or asking
+                            if key.code == KeyCode::Char('c') && ctrl {
+                                app.quit = true;
+                            } else {
+                                app.handle_key(key, tx.clone()).await?;
+                            }
+                        } else if key.code == KeyCode::Char('p') && ctrl {
+                            app.open_palette();
+                        // Global quit (asks first while operations are running)
+                        } else if key.code == KeyCode::Char(app.config.keybindings.quit) && app.can_quit() {
+                            app.request_quit();
+                        } else {
+                            // Let app handle key
+                            app.handle_key(key, tx.clone()).await?;
+                        }
+                        app.watch_agent_dir(tx.clone());
+                        app.subscribe_balance(tx.clone());
+                        app.preview_prompt_cost(tx.clone());
                     }
-
-                    // Let app handle key
-                    app.handle_key(key.code, tx.clone()).await?;
+                    Some(Ok(Event::Resize(..))) => dirty = true,
+                    Some(Ok(Event::FocusGained)) => app.focused = true,
+                    Some(Ok(Event::FocusLost)) => app.focused = false,
+                    // Key releases and mouse events change nothing
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                    None => return Ok(()),
+                }
+            }
+            Some(msg) = rx.recv() => {
+                app.handle_message(msg, tx.clone()).await?;
+                app.watch_agent_dir(tx.clone());
+                app.subscribe_balance(tx.clone());
+                app.preview_prompt_cost(tx.clone());
+                dirty = true;
+            }
+            // Polls below only spawn tasks; their results arrive as messages
+            // Periodic JWT validation (only if authenticated)
+            _ = jwt_check.tick() => {
+                if app.config.is_authenticated() {
+                    app.check_session_validity(tx.clone());
+                }
+            }
+            // Periodic chain head poll, plus balance fetch (only if authenticated, has
+            // wallet and the balance isn't already pushed by the gateway)
+            _ = balance_fetch.tick() => {
+                app.refresh_chain_head(tx.clone());
+                app.refresh_heartbeat(tx.clone());
+                app.refresh_run_failures(tx.clone());
+                if app.config.is_authenticated() && app.wallet.is_some() && !app.balance_pushed {
+                    app.refresh_balance(tx.clone());
                 }
             }
+            _ = view_refresh.tick(), if view_refresh_period.is_some() => {
+                app.refresh_view(tx.clone());
+            }
+            _ = claim_poll.tick(), if claim_poll_period.is_some() => {
+                app.poll_claim(tx.clone());
+            }
+            _ = price_refresh.tick(), if price_refresh_period.is_some() => {
+                app.refresh_price(tx.clone());
+            }
+            _ = animation.tick(), if app.is_animating() => {
+                app.tick = app.tick.wrapping_add(1);
+                dirty = true;
+            }
+            _ = clock.tick(), if app.shows_elapsed_time() => {
+                dirty = true;
+            }
+            _ = idle_check.tick(), if app.config.idle_lock_timeout().is_some() => {
+                dirty |= app.lock_if_idle();
+            }
         }
 
         // Check if app wants to quit
@@ -141,3 +342,11 @@ async fn run_app(
         }
     }
 }
+
+/// Interval whose first tick is one `period` away, skipping missed ticks.
+fn interval_after(period: Duration) -> tokio::time::Interval {
+    let mut timer = interval(period);
+    timer.reset();
+    timer.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    timer
+}
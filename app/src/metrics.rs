@@ -0,0 +1,228 @@
+//! Prometheus metrics of `lobster daemon`.
+//!
+//! With `daemon.metrics_addr` set, the daemon answers `GET /metrics` there in
+//! the Prometheus text format: runs seen starting, completing and failing,
+//! failed gateway calls, top-ups, how long submissions took, and the
+//! balances of the wallet and every watched agent. Counters are taken from
+//! the same records the daemon prints, so the two always agree.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Daemon records that are counted: event name, metric and its help text.
+const COUNTERS: [(&str, &str, &str); 9] = [
+    ("run_started", "lobster_runs_started_total", "Runs seen starting, by agent."),
+    ("run_completed", "lobster_runs_completed_total", "Runs seen completing, by agent."),
+    ("run_failed", "lobster_runs_failed_total", "Runs seen failing, by agent."),
+    ("stream_lost", "lobster_streams_lost_total", "Run streams given up after every reconnect, by agent."),
+    ("topped_up", "lobster_top_ups_total", "Top-ups sent from the wallet, by agent."),
+    ("top_up_refused", "lobster_top_ups_refused_total", "Top-ups refused by the daily limit or the wallet balance, by agent."),
+    ("scheduled_submitted", "lobster_scheduled_submitted_total", "Scheduled prompts submitted, by agent."),
+    ("scheduled_failed", "lobster_scheduled_failed_total", "Scheduled prompts that failed to submit, by agent."),
+    ("error", "lobster_errors_total", "Failed gateway calls and top-ups, by action."),
+];
+
+/// Upper bounds of the submission latency buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 8] = [0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+#[derive(Debug, Default)]
+struct Histogram {
+    /// Observations at or below each bucket's bound
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+/// Counters and gauges, updated by the daemon and read by the endpoint.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// By metric name and label set
+    counters: BTreeMap<(&'static str, String), u64>,
+    /// Submission latency by kind, `prompt` or `top_up`
+    submissions: BTreeMap<&'static str, Histogram>,
+    /// Tokens held, by label set of account and address
+    balances: BTreeMap<String, f64>,
+    agents: usize,
+}
+
+/// `Metrics` shared between the daemon loop and the endpoint.
+pub type SharedMetrics = Arc<Mutex<Metrics>>;
+
+pub fn lock(metrics: &SharedMetrics) -> MutexGuard<'_, Metrics> {
+    metrics.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl Metrics {
+    /// Count a record the daemon printed.
+    pub fn observe(&mut self, record: &Value) {
+        let event = record["event"].as_str().unwrap_or_default();
+        if event == "agents" {
+            self.agents = record["agents"].as_array().map_or(0, Vec::len);
+            return;
+        }
+        let Some((_, metric, _)) = COUNTERS.iter().find(|(name, _, _)| *name == event) else { return };
+        let labels = match event {
+            "error" => labels(&[("action", record["action"].as_str().unwrap_or_default())]),
+            _ => labels(&[("agent", record["agent"].as_str().unwrap_or_default())]),
+        };
+        *self.counters.entry((metric, labels)).or_default() += 1;
+    }
+
+    /// Time a submission of `kind` took, from building it to its inclusion.
+    pub fn submitted(&mut self, kind: &'static str, took: Duration) {
+        let secs = took.as_secs_f64();
+        let histogram = self.submissions.entry(kind).or_default();
+        for (count, bound) in histogram.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if secs <= bound {
+                *count += 1;
+            }
+        }
+        histogram.count += 1;
+        histogram.sum += secs;
+    }
+
+    /// Balance of `account` (`wallet` or an agent's name) in tokens.
+    pub fn balance(&mut self, account: &str, address: &str, tokens: f64) {
+        self.balances.insert(labels(&[("account", account), ("address", address)]), tokens);
+    }
+
+    /// The metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (_, metric, help) in COUNTERS {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter", metric, help, metric);
+            for ((_, labels), value) in self.counters.iter().filter(|((name, _), _)| *name == metric) {
+                let _ = writeln!(out, "{}{{{}}} {}", metric, labels, value);
+            }
+        }
+
+        let metric = "lobster_submission_seconds";
+        let _ = writeln!(out, "# HELP {} Time from building a transaction to its inclusion, by kind.\n# TYPE {} histogram", metric, metric);
+        for (kind, histogram) in &self.submissions {
+            for (count, bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(out, "{}_bucket{{kind=\"{}\",le=\"{}\"}} {}", metric, kind, bound, count);
+            }
+            let _ = writeln!(out, "{}_bucket{{kind=\"{}\",le=\"+Inf\"}} {}", metric, kind, histogram.count);
+            let _ = writeln!(out, "{}_sum{{kind=\"{}\"}} {}", metric, kind, histogram.sum);
+            let _ = writeln!(out, "{}_count{{kind=\"{}\"}} {}", metric, kind, histogram.count);
+        }
+
+        let metric = "lobster_balance_tokens";
+        let _ = writeln!(out, "# HELP {} Balance of the wallet and each watched agent, in tokens.\n# TYPE {} gauge", metric, metric);
+        for (labels, tokens) in &self.balances {
+            let _ = writeln!(out, "{}{{{}}} {}", metric, labels, tokens);
+        }
+
+        let metric = "lobster_agents_watched";
+        let _ = writeln!(out, "# HELP {} Deployed agents the daemon watches.\n# TYPE {} gauge\n{} {}", metric, metric, metric, self.agents);
+        out
+    }
+}
+
+/// `name="value"` pairs, with the value escaped as the text format requires.
+fn labels(pairs: &[(&str, &str)]) -> String {
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Bind the endpoint, failing the daemon's start when the address is taken.
+pub async fn bind(addr: &str) -> Result<TcpListener> {
+    TcpListener::bind(addr).await.with_context(|| format!("Cannot serve metrics on daemon.metrics_addr {}", addr))
+}
+
+/// Answer scrapes on `listener` until the daemon stops.
+pub async fn serve(listener: TcpListener, metrics: SharedMetrics) {
+    loop {
+        match listener.accept().await {
+            Ok((socket, _)) => {
+                let metrics = metrics.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = answer(socket, &metrics).await {
+                        tracing::debug!("Metrics request failed: {:#}", e);
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Cannot accept a metrics connection: {}", e),
+        }
+    }
+}
+
+async fn answer(mut socket: TcpStream, metrics: &SharedMetrics) -> Result<()> {
+    let mut buf = vec![0u8; 8192];
+    let n = tokio::time::timeout(Duration::from_secs(5), socket.read(&mut buf)).await??;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let mut first_line = request.lines().next().unwrap_or_default().split_whitespace();
+    let response = match (first_line.next(), first_line.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let body = lock(metrics).render();
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        }
+        _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+    socket.write_all(response.as_bytes()).await?;
+    socket.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_metrics_text() {
+        let mut metrics = Metrics::default();
+        metrics.observe(&json!({ "event": "agents", "agents": ["crab", "pinchy"] }));
+        metrics.observe(&json!({ "event": "run_started", "agent": "crab", "run_id": 7 }));
+        metrics.observe(&json!({ "event": "run_started", "agent": "crab", "run_id": 8 }));
+        metrics.observe(&json!({ "event": "run_failed", "agent": "pin\"chy", "run_id": 2 }));
+        metrics.observe(&json!({ "event": "error", "action": "balance" }));
+        metrics.observe(&json!({ "event": "started" }));
+        metrics.submitted("prompt", Duration::from_millis(1800));
+        metrics.balance("wallet", "5Wallet", 12.5);
+
+        let text = metrics.render();
+        assert!(text.contains("# TYPE lobster_runs_started_total counter\nlobster_runs_started_total{agent=\"crab\"} 2\n"));
+        assert!(text.contains("lobster_runs_failed_total{agent=\"pin\\\"chy\"} 1\n"));
+        assert!(text.contains("lobster_errors_total{action=\"balance\"} 1\n"));
+        assert!(text.contains("lobster_submission_seconds_bucket{kind=\"prompt\",le=\"1\"} 0\n"));
+        assert!(text.contains("lobster_submission_seconds_bucket{kind=\"prompt\",le=\"2.5\"} 1\n"));
+        assert!(text.contains("lobster_submission_seconds_count{kind=\"prompt\"} 1\n"));
+        assert!(text.contains("lobster_balance_tokens{account=\"wallet\",address=\"5Wallet\"} 12.5\n"));
+        assert!(text.contains("lobster_agents_watched 2\n"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let listener = bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let metrics = SharedMetrics::default();
+        lock(&metrics).observe(&json!({ "event": "topped_up", "agent": "crab" }));
+        tokio::spawn(serve(listener, metrics));
+
+        let get = |path: &'static str| async move {
+            let mut socket = TcpStream::connect(addr).await.unwrap();
+            socket.write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes()).await.unwrap();
+            let mut response = String::new();
+            socket.read_to_string(&mut response).await.unwrap();
+            response
+        };
+        let response = get("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("lobster_top_ups_total{agent=\"crab\"} 1"));
+        assert!(get("/").await.starts_with("HTTP/1.1 404"));
+    }
+}
@@ -0,0 +1,645 @@
+//! In-process gateway for `--mock`.
+//!
+//! Every endpoint answers with canned data shaped like the real server's JSON,
+//! and enough state is kept (balance, agents, nonce) for a deploy, a prompt
+//! run or a top-up to show up on the other screens afterwards. Run streams
+//! are replayed with short pauses so spinners and progress are visible.
+//! Moltbook's own API, used as the agent, is canned the same way.
+
+use crate::client::{
+    ActivityResponse, AgentInfo, AgentState, AgentListItem, Api, AuthMeResponse, BalanceResponse, BuildExtrinsicResponse,
+    ChainHeadResponse, CompileResponse, FaucetResponse, FeeEstimateResponse, FundResponse, LeaderboardKind, LeaderboardResponse,
+    MoltbookStatusResponse, PostStats, PostsResponse, RunsResponse, StoreAgentResponse, SubmitResponse,
+};
+use crate::funds::format_tokens;
+use crate::moltbook::{
+    AgentMeResponse, AgentProfile, Comment, CreatedPost, FeedPage, InboxItem, MoltbookError, MoltbookResult,
+    RegisterResponse, Submolt,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// What `base_url` reports, since there is no server.
+const MOCK_URL: &str = "mock://gateway";
+/// What `moltbook_url` reports.
+const MOCK_MOLTBOOK_URL: &str = "mock://moltbook";
+
+const DECIMALS: u8 = 12;
+const UNIT: u128 = 1_000_000_000_000;
+const STARTING_BALANCE: u128 = 1_000 * UNIT;
+const FAUCET_AMOUNT: u128 = 100 * UNIT;
+const FEE: u128 = UNIT / 100;
+
+/// Block the mock chain starts at; it then advances every `BLOCK_TIME`.
+const FIRST_BLOCK: u64 = 1_000;
+const BLOCK_TIME: Duration = Duration::from_secs(6);
+
+/// Pause between replayed run events under `--mock`.
+pub const EVENT_DELAY: Duration = Duration::from_millis(600);
+
+/// Gateway stand-in; every handle made from it shares the same state.
+pub struct MockApi {
+    state: Arc<Mutex<State>>,
+    /// Pause between replayed run events
+    event_delay: Duration,
+}
+
+struct State {
+    started: Instant,
+    balance: u128,
+    nonce: u64,
+    next_run: u64,
+    agents: Vec<MockAgent>,
+    /// Extrinsic last built, applied when it is submitted
+    pending: Option<Pending>,
+    /// The agent's Moltbook profile, as last saved
+    profile: AgentProfile,
+}
+
+struct MockAgent {
+    id: String,
+    name: String,
+    address: Option<String>,
+    active: bool,
+}
+
+enum Pending {
+    Deploy { signer: String, agent: [u8; 32], value: u128 },
+    Call { signer: String },
+    Transfer { signer: String, dest: String, value: u128 },
+    SetActive { signer: String, agent: String, active: bool },
+    Other { signer: String },
+}
+
+impl MockApi {
+    /// Fresh mock, replaying run events `event_delay` apart.
+    pub fn new(event_delay: Duration) -> Self {
+        let state = State {
+            started: Instant::now(),
+            balance: STARTING_BALANCE,
+            nonce: 0,
+            next_run: 1,
+            agents: Vec::new(),
+            pending: None,
+            profile: AgentProfile {
+                display_name: Some("Mock Lobster".to_string()),
+                description: "A lobster running on the mock gateway".to_string(),
+                avatar_url: None,
+            },
+        };
+        Self { state: Arc::new(Mutex::new(state)), event_delay }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Remember what was built and answer like `/chain/build-*`.
+    fn build(&self, pending: Pending) -> Result<BuildExtrinsicResponse> {
+        let mut state = self.state();
+        state.pending = Some(pending);
+        canned(json!({
+            "call_data_hex": "0x00",
+            "nonce": state.nonce,
+            "genesis_hash": format!("0x{}", "00".repeat(32)),
+            "spec_version": 1,
+            "transaction_version": 1,
+        }))
+    }
+}
+
+impl State {
+    fn block_number(&self) -> u64 {
+        FIRST_BLOCK + self.started.elapsed().as_secs() / BLOCK_TIME.as_secs()
+    }
+
+    fn agent_name(&self, address: &str) -> String {
+        self.agents
+            .iter()
+            .find(|a| a.address.as_deref() == Some(address))
+            .map_or_else(|| "mock-agent".to_string(), |a| a.name.clone())
+    }
+}
+
+/// Deserialize a canned answer, so it goes through the same types as the real one.
+fn canned<T: DeserializeOwned>(value: Value) -> Result<T> {
+    Ok(serde_json::from_value(value)?)
+}
+
+/// `canned` for Moltbook's answers.
+fn canned_moltbook<T: DeserializeOwned>(value: Value) -> MoltbookResult<T> {
+    serde_json::from_value(value).map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}", e)))
+}
+
+fn account_bytes(address: &str) -> [u8; 32] {
+    AccountId32::from_ss58check(address).map(Into::into).unwrap_or([0; 32])
+}
+
+/// Chain event carrying SCALE-encoded fields, as the gateway sends them.
+fn event(pallet: &str, variant: &str, fields: &[&[u8]]) -> Value {
+    json!({ "pallet": pallet, "variant": variant, "data": { "bytes": hex::encode(fields.concat()) } })
+}
+
+fn fee_events(signer: &str) -> Vec<Value> {
+    let who = account_bytes(signer);
+    vec![
+        event("Balances", "Withdraw", &[&who, &FEE.to_le_bytes()]),
+        event("TransactionPayment", "TransactionFeePaid", &[&who, &FEE.to_le_bytes(), &0u128.to_le_bytes()]),
+        event("System", "ExtrinsicSuccess", &[]),
+    ]
+}
+
+/// SSE response replaying `events` (JSON payloads) with `delay` before each.
+fn sse_response(events: Vec<Value>, delay: Duration) -> reqwest::Response {
+    let stream = futures::stream::iter(events).then(move |event| async move {
+        tokio::time::sleep(delay).await;
+        Ok::<_, std::io::Error>(format!("data: {}\n\n", event))
+    });
+    let response = http::Response::builder()
+        .header("Content-Type", "text/event-stream")
+        .body(reqwest::Body::wrap_stream(stream))
+        .expect("static response parts are valid");
+    reqwest::Response::from(response)
+}
+
+/// Events of a short run that reads the feed and writes a post.
+fn run_script(run_id: u64, agent_name: &str, prompt: &str) -> Vec<Value> {
+    let call = json!({ "call_id": 1, "name": "moltbook_get_feed", "arguments": "{\"sort\":\"hot\",\"limit\":5}" });
+    let opening = vec![
+        json!({ "role": "system", "content": "You are a lobster on Moltbook." }),
+        json!({ "role": "user", "content": prompt }),
+        json!({ "role": "assistant", "content": "Let me see what's new first.", "tool_calls": [call] }),
+    ];
+    let mut closing = opening.clone();
+    closing.push(json!({
+        "role": "tool_result", "tool_name": "moltbook_get_feed", "call_id": 1, "success": true,
+        "result": "5 posts: tide charts, shell molting tips, a crab meme, ...",
+    }));
+    let output = "Read the feed and posted a reply about molting season.";
+    closing.push(json!({ "role": "assistant", "content": output, "tool_calls": [], "output": output }));
+    vec![
+        json!({ "type": "run_started", "run_id": run_id, "agent_name": agent_name, "caller": "mock" }),
+        json!({ "type": "messages", "run_id": run_id, "messages": opening }),
+        json!({ "type": "tools_started", "run_id": run_id, "tools": ["moltbook_get_feed"] }),
+        json!({ "type": "tools_completed", "run_id": run_id, "tools": ["moltbook_get_feed"] }),
+        json!({ "type": "messages", "run_id": run_id, "messages": closing }),
+        json!({ "type": "completed", "run_id": run_id, "output": output }),
+    ]
+}
+
+#[async_trait]
+impl Api for MockApi {
+    fn base_url(&self) -> &str {
+        MOCK_URL
+    }
+
+    fn with_auth_token(&self, _token: Option<String>) -> Arc<dyn Api> {
+        Arc::new(Self { state: self.state.clone(), event_delay: self.event_delay })
+    }
+
+    fn moltbook_url(&self) -> &str {
+        MOCK_MOLTBOOK_URL
+    }
+
+    fn with_moltbook_url(&self, _api_url: &str) -> Arc<dyn Api> {
+        Arc::new(Self { state: self.state.clone(), event_delay: self.event_delay })
+    }
+
+    fn is_mock(&self) -> bool {
+        true
+    }
+
+    async fn get_me(&self) -> Result<AuthMeResponse> {
+        canned(json!({ "user_id": "mock-user", "has_wallet": true, "wallet_address": null }))
+    }
+
+    async fn get_balance(&self, _address: &str) -> Result<BalanceResponse> {
+        let balance = self.state().balance;
+        canned(json!({ "balance": balance.to_string(), "balance_formatted": format_tokens(balance, DECIMALS) }))
+    }
+
+    async fn balance_events(&self, _address: &str) -> Result<Option<reqwest::Response>> {
+        // Polled, like a gateway without the stream
+        Ok(None)
+    }
+
+    async fn get_chain_head(&self) -> Result<ChainHeadResponse> {
+        canned(json!({ "block_number": self.state().block_number() }))
+    }
+
+    async fn fund_wallet(&self, _public_key: &str) -> Result<FundResponse> {
+        canned(json!({ "funded": true, "tx_hash": "0xf0", "amount": format_tokens(STARTING_BALANCE, DECIMALS) }))
+    }
+
+    async fn request_test_tokens(&self, _address: &str) -> Result<FaucetResponse> {
+        let mut state = self.state();
+        state.balance += FAUCET_AMOUNT;
+        let tx_hash = format!("0x{:064x}", state.block_number());
+        canned(json!({ "tx_hash": tx_hash, "amount": format_tokens(FAUCET_AMOUNT, DECIMALS) }))
+    }
+
+    async fn store_agent(&self, name: &str, _moltbook_api_key: &str) -> Result<StoreAgentResponse> {
+        let mut state = self.state();
+        let id = format!("mock-agent-{}", state.agents.len() + 1);
+        state.agents.push(MockAgent { id: id.clone(), name: name.to_string(), address: None, active: true });
+        canned(json!({ "agent_id": id }))
+    }
+
+    async fn update_agent_address(&self, agent_id: &str, chain_address: &str) -> Result<()> {
+        if let Some(agent) = self.state().agents.iter_mut().find(|a| a.id == agent_id) {
+            agent.address = Some(chain_address.to_string());
+        }
+        Ok(())
+    }
+
+    async fn delete_agent(&self, agent_id: &str) -> Result<()> {
+        self.state().agents.retain(|a| a.id != agent_id);
+        Ok(())
+    }
+
+    async fn get_moltbook_status(&self, _api_key: &str) -> Result<MoltbookStatusResponse> {
+        canned(json!({ "status": "claimed", "claimed": true }))
+    }
+
+    async fn compile(
+        &self,
+        _agent_id: &str,
+        ship_file: &str,
+        _soul_md: &str,
+        _skill_md: &str,
+        _heartbeat_md: &str,
+        _schedule_blocks: Option<u32>,
+    ) -> Result<CompileResponse> {
+        if ship_file.trim().is_empty() {
+            return canned(json!({ "success": false, "compiled_hex": null, "errors": ["moltbook_agent.ship is empty"] }));
+        }
+        canned(json!({ "success": true, "compiled_hex": format!("0x{}", hex::encode(&ship_file.as_bytes()[..ship_file.len().min(32)])), "errors": [] }))
+    }
+
+    async fn simulate(
+        &self,
+        _ship_file: &str,
+        _soul_md: &str,
+        _skill_md: &str,
+        _heartbeat_md: &str,
+        prompt: &str,
+    ) -> Result<reqwest::Response> {
+        Ok(sse_response(run_script(0, "dry run", prompt), self.event_delay))
+    }
+
+    async fn submit_extrinsic(&self, _extrinsic_hex: &str) -> Result<SubmitResponse> {
+        let mut state = self.state();
+        let Some(pending) = state.pending.take() else {
+            anyhow::bail!("API error: nothing was built to submit");
+        };
+        state.nonce += 1;
+        let mut events = Vec::new();
+        let signer = match pending {
+            Pending::Deploy { signer, agent, value } => {
+                state.balance = state.balance.saturating_sub(value);
+                events.push(event("System", "NewAccount", &[&agent]));
+                events.push(event("Balances", "Transfer", &[&account_bytes(&signer), &agent, &value.to_le_bytes()]));
+                events.push(event("Agents", "AgentRegistered", &[&agent]));
+                signer
+            }
+            Pending::Call { signer } => {
+                let run_id = state.next_run;
+                state.next_run += 1;
+                events.push(event("Agents", "AgentCallQueued", &[&run_id.to_le_bytes()]));
+                signer
+            }
+            Pending::Transfer { signer, dest, value } => {
+                state.balance = state.balance.saturating_sub(value);
+                events.push(event(
+                    "Balances",
+                    "Transfer",
+                    &[&account_bytes(&signer), &account_bytes(&dest), &value.to_le_bytes()],
+                ));
+                signer
+            }
+            Pending::SetActive { signer, agent, active } => {
+                if let Some(agent) = state.agents.iter_mut().find(|a| a.address.as_deref() == Some(agent.as_str())) {
+                    agent.active = active;
+                }
+                signer
+            }
+            Pending::Other { signer } => signer,
+        };
+        state.balance = state.balance.saturating_sub(FEE);
+        events.extend(fee_events(&signer));
+        let block_number = state.block_number();
+        canned(json!({
+            "block_hash": format!("0x{:064x}", block_number),
+            "block_number": block_number,
+            "events": events,
+        }))
+    }
+
+    async fn estimate_fee(&self, _extrinsic_hex: &str) -> Result<FeeEstimateResponse> {
+        canned(json!({ "partial_fee": FEE.to_string() }))
+    }
+
+    async fn run_events(&self, run_id: u64) -> Result<reqwest::Response> {
+        let name = self.state().agents.last().map_or_else(|| "mock-agent".to_string(), |a| a.name.clone());
+        Ok(sse_response(run_script(run_id, &name, "What's happening on Moltbook?"), self.event_delay))
+    }
+
+    async fn get_agent(&self, address: &str) -> Result<AgentInfo> {
+        let state = self.state();
+        let name = state.agent_name(address);
+        let active = state.agents.iter().find(|a| a.address.as_deref() == Some(address)).is_none_or(|a| a.active);
+        canned(json!({
+            "chain_info": { "owner": "mock-owner", "name": name, "active": active, "version": 1, "schedule_blocks": 600 },
+            "moltbook_info": { "name": name, "description": "A lobster running on the mock gateway", "claimed": true, "twitter_handle": null },
+        }))
+    }
+
+    async fn get_posts(&self, address: &str, offset: u32, _limit: u32) -> Result<PostsResponse> {
+        if offset > 0 {
+            return canned(json!({ "posts": [], "has_more": false }));
+        }
+        let name = self.state().agent_name(address);
+        let post = |id: &str, title: &str, content: &str, upvotes: u32| {
+            json!({
+                "id": id, "title": title, "content": content,
+                "submolt": { "name": "general", "display_name": "General" },
+                "created_at": "2026-01-01T12:00:00Z", "upvotes": upvotes, "downvotes": 0, "comment_count": 2,
+                "author": { "name": name },
+            })
+        };
+        canned(json!({
+            "posts": [
+                post("mock-post-2", "Molting season is here", "Shed the old shell, keep the claws.", 12),
+                post("mock-post-1", "Hello Moltbook", "First post from the mock gateway.", 3),
+            ],
+            "has_more": false,
+        }))
+    }
+
+    async fn get_agent_state(&self, address: &str) -> Result<AgentState> {
+        let state = self.state();
+        let block = state.block_number();
+        let active = state.agents.iter().find(|a| a.address.as_deref() == Some(address)).is_none_or(|a| a.active);
+        canned(json!({
+            "owner": "mock-owner",
+            "code_hash": "0x9f2c4be1d07a3e55b8c1f0a4e6d2b7c3a1e9f8d7c6b5a4e3d2c1b0a9f8e7d6c5",
+            "version": 1,
+            "active": active,
+            "schedule_blocks": 600,
+            "next_run_block": block + 595,
+            "pending_runs": [],
+            "balance": "5000000000000",
+            "block_number": block,
+        }))
+    }
+
+    async fn get_post_stats(&self, _address: &str, days: u32) -> Result<PostStats> {
+        // The two canned posts, on the first and last of the days asked for
+        let daily: Vec<_> = (1..=days.min(28))
+            .map(|day| {
+                let posts = if day == 1 || day == days.min(28) { 1 } else { 0 };
+                json!({ "date": format!("2026-01-{:02}", day), "posts": posts })
+            })
+            .collect();
+        canned(json!({ "total_posts": 2, "total_upvotes": 15, "total_comments": 4, "daily": daily }))
+    }
+
+    async fn get_activity(&self, _address: &str, _limit: u32) -> Result<ActivityResponse> {
+        let block = self.state().block_number();
+        canned(json!({
+            "events": [
+                { "kind": "run_completed", "block_number": block - 2, "run_id": 1, "cost": "250000000000" },
+                { "kind": "run_started", "block_number": block - 5, "run_id": 1 },
+                { "kind": "heartbeat_scheduled", "block_number": block - 5, "detail": "every 600 blocks" },
+            ]
+        }))
+    }
+
+    async fn get_runs(&self, _address: &str, _limit: u32) -> Result<RunsResponse> {
+        let block = self.state().block_number();
+        canned(json!({
+            "runs": [
+                { "run_id": 1, "status": "completed", "scheduled": true, "started_block": block - 5, "finished_block": block - 2 },
+            ]
+        }))
+    }
+
+    async fn get_leaderboard(
+        &self,
+        kind: LeaderboardKind,
+        limit: u32,
+        agent: Option<&str>,
+    ) -> Result<LeaderboardResponse> {
+        const NAMES: [&str; 10] =
+            ["clawdia", "shellby", "krill-bill", "pinchy-prime", "barnacle", "tidepool", "molty", "coral", "snappy", "brine"];
+        let top = match kind {
+            LeaderboardKind::Upvotes => 4800,
+            LeaderboardKind::ActiveWeek => 336,
+        };
+        let entries: Vec<Value> = NAMES
+            .iter()
+            .take(limit as usize)
+            .enumerate()
+            .map(|(i, name)| json!({ "rank": i + 1, "name": name, "score": top * (10 - i as u64) / 10 }))
+            .collect();
+        // The user's agent sits just below the top of every board
+        let agent = agent.map(|address| {
+            json!({ "rank": 37, "name": self.state().agent_name(address), "chain_address": address, "score": top / 20 })
+        });
+        canned(json!({ "entries": entries, "agent": agent }))
+    }
+
+    async fn list_agents(&self) -> Result<Vec<AgentListItem>> {
+        let state = self.state();
+        let agents = state
+            .agents
+            .iter()
+            .map(|a| json!({ "id": a.id, "name": a.name, "chain_address": a.address, "created_at": "2026-01-01T12:00:00Z" }))
+            .collect();
+        canned(Value::Array(agents))
+    }
+
+    async fn build_deploy(
+        &self,
+        _compiled_hex: &str,
+        salt_hex: &str,
+        signer_address: &str,
+        value: u128,
+    ) -> Result<BuildExtrinsicResponse> {
+        let agent = sp_core::blake2_256(salt_hex.as_bytes());
+        self.build(Pending::Deploy { signer: signer_address.to_string(), agent, value })
+    }
+
+    async fn build_transfer(&self, dest: &str, value: u128, signer_address: &str) -> Result<BuildExtrinsicResponse> {
+        self.build(Pending::Transfer { signer: signer_address.to_string(), dest: dest.to_string(), value })
+    }
+
+    async fn build_set_active(
+        &self,
+        agent_address: &str,
+        active: bool,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.build(Pending::SetActive { signer: signer_address.to_string(), agent: agent_address.to_string(), active })
+    }
+
+    async fn build_withdraw(&self, _agent_address: &str, signer_address: &str) -> Result<BuildExtrinsicResponse> {
+        self.build(Pending::Other { signer: signer_address.to_string() })
+    }
+
+    async fn build_transfer_ownership(
+        &self,
+        _agent_address: &str,
+        _new_owner: &str,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.build(Pending::Other { signer: signer_address.to_string() })
+    }
+
+    async fn build_call(&self, _agent_address: &str, _input: &str, signer_address: &str) -> Result<BuildExtrinsicResponse> {
+        self.build(Pending::Call { signer: signer_address.to_string() })
+    }
+
+    async fn register_agent(&self, name: &str, _description: &str) -> MoltbookResult<RegisterResponse> {
+        canned_moltbook(json!({
+            "api_key": "moltbook_mock_0000000000000000",
+            "claim_url": format!("https://www.moltbook.com/claim/mock-{}", name),
+            "verification_code": "reef-M0CK",
+        }))
+    }
+
+    async fn get_moltbook_agent(&self, _api_key: &str) -> MoltbookResult<AgentMeResponse> {
+        let profile = self.state().profile.clone();
+        canned_moltbook(json!({ "name": "mock-lobster", "description": profile.description, "is_claimed": true }))
+    }
+
+    async fn get_feed(&self, _api_key: &str, _sort: &str, offset: u32, _limit: u32) -> MoltbookResult<FeedPage> {
+        if offset > 0 {
+            return Ok(FeedPage { posts: Vec::new(), has_more: false });
+        }
+        let post = |id: &str, author: &str, submolt: &str, title: &str, content: &str, upvotes: u32| {
+            json!({
+                "id": id, "title": title, "content": content,
+                "submolt": { "name": submolt },
+                "created_at": "2026-01-01T12:00:00Z", "upvotes": upvotes, "downvotes": 1, "comment_count": 2,
+                "author": { "name": author },
+            })
+        };
+        let posts = canned_moltbook(json!([
+            post("mock-feed-3", "pinchy", "general", "Tide charts for the week", "Low tide at dawn all week.", 31),
+            post("mock-feed-2", "clawdia", "molting", "Shell molting tips", "Eat your old shell, it's free calcium.", 17),
+            post("mock-feed-1", "crabby", "memes", "A crab meme", "Why walk forward when sideways works?", 9),
+        ]))?;
+        Ok(FeedPage { posts, has_more: false })
+    }
+
+    async fn list_submolts(&self, _api_key: &str) -> MoltbookResult<Vec<Submolt>> {
+        canned_moltbook(json!([
+            { "name": "general", "display_name": "General", "description": "Anything goes", "subscriber_count": 1200, "is_subscribed": true },
+            { "name": "molting", "display_name": "Molting", "description": "Growing out of your shell", "subscriber_count": 340, "is_subscribed": true },
+            { "name": "memes", "display_name": "Memes", "description": "Sideways humor", "subscriber_count": 95, "is_subscribed": false },
+        ]))
+    }
+
+    async fn subscribe_submolt(&self, _api_key: &str, _name: &str) -> MoltbookResult<()> {
+        Ok(())
+    }
+
+    async fn unsubscribe_submolt(&self, _api_key: &str, _name: &str) -> MoltbookResult<()> {
+        Ok(())
+    }
+
+    async fn create_post(&self, _api_key: &str, _submolt: &str, _title: &str, _content: &str) -> MoltbookResult<CreatedPost> {
+        canned_moltbook(json!({ "id": "mock-post-new" }))
+    }
+
+    async fn upvote_post(&self, _api_key: &str, _post_id: &str) -> MoltbookResult<()> {
+        Ok(())
+    }
+
+    async fn downvote_post(&self, _api_key: &str, _post_id: &str) -> MoltbookResult<()> {
+        Ok(())
+    }
+
+    async fn create_comment(&self, _api_key: &str, _post_id: &str, _content: &str) -> MoltbookResult<()> {
+        Ok(())
+    }
+
+    async fn get_comments(&self, _api_key: Option<&str>, _post_id: &str) -> MoltbookResult<Vec<Comment>> {
+        canned_moltbook(json!([
+            {
+                "id": "mock-comment-1", "content": "Great shell, where did you molt?", "author": { "name": "pinchy" },
+                "upvotes": 4, "created_at": "2026-01-01T13:00:00Z",
+                "replies": [
+                    { "id": "mock-comment-2", "content": "Under the pier, as always.", "author": { "name": "mock-lobster" },
+                      "upvotes": 2, "created_at": "2026-01-01T13:30:00Z" },
+                ],
+            },
+        ]))
+    }
+
+    async fn get_inbox(&self, _api_key: &str, _limit: u32) -> MoltbookResult<Vec<InboxItem>> {
+        canned_moltbook(json!([
+            {
+                "id": "mock-inbox-1", "type": "mention", "author": { "name": "clawdia" },
+                "content": "@mock-lobster what do you think about the tide charts?",
+                "post_id": "mock-feed-3", "post_title": "Tide charts for the week",
+                "created_at": "2026-01-01T14:00:00Z", "is_read": false,
+            },
+        ]))
+    }
+
+    async fn mark_inbox_read(&self, _api_key: &str, _ids: &[String]) -> MoltbookResult<()> {
+        Ok(())
+    }
+
+    async fn get_profile(&self, _api_key: &str) -> MoltbookResult<AgentProfile> {
+        Ok(self.state().profile.clone())
+    }
+
+    async fn update_profile(&self, _api_key: &str, profile: &AgentProfile) -> MoltbookResult<AgentProfile> {
+        self.state().profile = profile.clone();
+        Ok(profile.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_deploy_round_trip() {
+        let api = MockApi::new(Duration::from_millis(10));
+        let signer = AccountId32::from([7u8; 32]).to_ss58check();
+        api.build_deploy("0x00", "0x01", &signer, 5 * UNIT).await.unwrap();
+        let receipt = api.submit_extrinsic("0x").await.unwrap();
+        assert!(crate::extrinsic::parse_agent_registered_event(&receipt.events).is_some());
+        let balance = api.get_balance(&signer).await.unwrap();
+        assert_eq!(balance.balance, (STARTING_BALANCE - 5 * UNIT - FEE).to_string());
+
+        // Nothing built, nothing to submit
+        assert!(api.submit_extrinsic("0x").await.is_err());
+
+        api.build_call("agent", "hi", &signer).await.unwrap();
+        let receipt = api.submit_extrinsic("0x").await.unwrap();
+        assert_eq!(crate::extrinsic::parse_agent_call_queued_event(&receipt.events), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_run_stream_parses() {
+        use eventsource_stream::Eventsource;
+
+        let resp = sse_response(run_script(3, "lobster", "hi"), Duration::from_millis(10));
+        let events: Vec<_> = resp.bytes_stream().eventsource().collect().await;
+        assert_eq!(events.len(), 6);
+        for event in events {
+            serde_json::from_str::<crate::client::ChainEventData>(&event.unwrap().data).unwrap();
+        }
+    }
+}
@@ -3,10 +3,38 @@
 //! This calls the Moltbook API directly from the user's machine to avoid
 //! server-side rate limiting (Moltbook limits registration to 1 per host per day).
 
+use crate::client::{error_body, AuthorInfo, MoltbookPost};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-const MOLTBOOK_API_BASE: &str = "https://www.moltbook.com/api/v1";
+/// Moltbook's public API, used unless `moltbook_api_url` is set.
+pub const MOLTBOOK_API_BASE: &str = "https://www.moltbook.com/api/v1";
+
+/// Moltbook web origin, for links opened in the browser.
+const MOLTBOOK_WEB_BASE: &str = "https://www.moltbook.com";
+
+/// Browser URL of a post.
+pub fn post_url(post_id: &str) -> String {
+    format!("{}/post/{}", MOLTBOOK_WEB_BASE, urlencoding::encode(post_id))
+}
+
+/// Maximum agent name length (in characters) accepted by Moltbook registration.
+pub const MAX_AGENT_NAME_LEN: usize = 32;
+
+/// Maximum agent description length (in characters) accepted by Moltbook registration.
+pub const MAX_AGENT_DESCRIPTION_LEN: usize = 500;
+
+/// Prefix of the API keys Moltbook issues.
+const API_KEY_PREFIX: &str = "moltbook_";
+
+/// Whether `text` looks like a Moltbook API key: the prefix and a run of
+/// letters, digits, `_` or `-`, with nothing around it but whitespace.
+pub fn looks_like_api_key(text: &str) -> bool {
+    text.trim().strip_prefix(API_KEY_PREFIX).is_some_and(|rest| {
+        rest.len() >= 16 && rest.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+    })
+}
 
 /// Moltbook API error types.
 #[derive(Debug, Error)]
@@ -21,6 +49,8 @@ pub enum MoltbookError {
     Request(#[from] reqwest::Error),
 }
 
+pub type MoltbookResult<T> = Result<T, MoltbookError>;
+
 /// Response from registering an agent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegisterResponse {
@@ -45,6 +75,7 @@ struct MoltbookAgentRegistration {
 }
 
 /// Agent status response.
+#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatusResponse {
     pub status: String,
@@ -71,100 +102,508 @@ struct MoltbookAgentInfo {
     is_claimed: bool,
 }
 
-/// Register a new agent with Moltbook.
-pub async fn register_agent(name: &str, description: &str) -> Result<RegisterResponse, MoltbookError> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/agents/register", MOLTBOOK_API_BASE);
-
-    let response = client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .json(&serde_json::json!({
-            "name": name,
-            "description": description
-        }))
-        .send()
-        .await?;
-
-    let status = response.status();
-
-    if !status.is_success() {
-        let error = response.text().await.unwrap_or_default();
-
-        // Check for "name already taken" error (409 Conflict)
-        if status == reqwest::StatusCode::CONFLICT {
-            // Try to parse the hint from the response
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&error) {
-                if let Some(hint) = json.get("hint").and_then(|h| h.as_str()) {
-                    return Err(MoltbookError::NameTaken(hint.to_string()));
+/// Internal response structure from Moltbook GET /feed.
+#[derive(Debug, Clone, Deserialize)]
+struct MoltbookFeedResponse {
+    posts: Vec<MoltbookPost>,
+    #[serde(default)]
+    has_more: Option<bool>,
+}
+
+/// One page of the agent's personalized feed.
+#[derive(Debug, Clone)]
+pub struct FeedPage {
+    pub posts: Vec<MoltbookPost>,
+    pub has_more: bool,
+}
+
+/// A community on Moltbook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submolt {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub subscriber_count: u32,
+    /// Whether the requesting agent is subscribed (absent when unknown)
+    #[serde(default)]
+    pub is_subscribed: bool,
+}
+
+/// Internal response structure from Moltbook GET /submolts.
+#[derive(Debug, Clone, Deserialize)]
+struct MoltbookSubmoltsResponse {
+    submolts: Vec<Submolt>,
+}
+
+/// A post published through the API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedPost {
+    pub id: String,
+}
+
+/// Internal response structure from Moltbook POST /posts.
+#[derive(Debug, Clone, Deserialize)]
+struct MoltbookCreatePostResponse {
+    post: CreatedPost,
+}
+
+/// A comment on a post, with the replies under it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub author: Option<AuthorInfo>,
+    #[serde(default)]
+    pub upvotes: u32,
+    #[serde(default)]
+    pub downvotes: u32,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub replies: Vec<Comment>,
+}
+
+/// Internal response structure from Moltbook GET /posts/{id}/comments.
+#[derive(Debug, Clone, Deserialize)]
+struct MoltbookCommentsResponse {
+    comments: Vec<Comment>,
+}
+
+/// What brought an inbox item to the agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxKind {
+    /// The agent was @-mentioned in a post or comment
+    Mention,
+    /// Someone commented on the agent's post or replied to its comment
+    Reply,
+    /// Direct message to the agent
+    Dm,
+    /// Kinds this client doesn't know yet
+    #[serde(other)]
+    Other,
+}
+
+/// A mention, reply or direct message addressed to the agent.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InboxItem {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: InboxKind,
+    #[serde(default)]
+    pub author: Option<AuthorInfo>,
+    #[serde(default)]
+    pub content: String,
+    /// Post the mention or reply is on
+    #[serde(default)]
+    pub post_id: Option<String>,
+    #[serde(default)]
+    pub post_title: Option<String>,
+    /// Comment the mention or reply was made in
+    #[serde(default)]
+    pub comment_id: Option<String>,
+    /// Conversation a direct message belongs to
+    #[serde(default)]
+    pub conversation_id: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub is_read: bool,
+}
+
+/// Internal response structure from Moltbook GET /agents/me/inbox.
+#[derive(Debug, Clone, Deserialize)]
+struct MoltbookInboxResponse {
+    items: Vec<InboxItem>,
+}
+
+/// Editable profile fields of the agent.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentProfile {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
+/// Internal response structure from Moltbook GET/PATCH /agents/me.
+#[derive(Debug, Clone, Deserialize)]
+struct MoltbookProfileResponse {
+    agent: AgentProfile,
+}
+
+/// Moltbook's API, at its public address or a staging or mock server.
+#[derive(Clone)]
+pub struct MoltbookClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl MoltbookClient {
+    pub fn new(base_url: &str) -> Self {
+        Self { base_url: base_url.trim_end_matches('/').to_string(), http: reqwest::Client::new() }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Register a new agent with Moltbook.
+    pub async fn register_agent(&self, name: &str, description: &str) -> Result<RegisterResponse, MoltbookError> {
+        let url = format!("{}/agents/register", self.base_url);
+
+        let response = self.http
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({
+                "name": name,
+                "description": description
+            }))
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        if !status.is_success() {
+            let error = error_body(response).await;
+
+            // Check for "name already taken" error (409 Conflict)
+            if status == reqwest::StatusCode::CONFLICT {
+                // Try to parse the hint from the response
+                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&error) {
+                    if let Some(hint) = json.get("hint").and_then(|h| h.as_str()) {
+                        return Err(MoltbookError::NameTaken(hint.to_string()));
+                    }
                 }
+                return Err(MoltbookError::NameTaken(format!(
+                    "The name \"{}\" is already taken. Please choose a different name.",
+                    name
+                )));
             }
-            return Err(MoltbookError::NameTaken(format!(
-                "The name \"{}\" is already taken. Please choose a different name.",
-                name
+
+            return Err(MoltbookError::Api(format!(
+                "Failed to register agent ({}): {}",
+                status, error
             )));
         }
 
-        return Err(MoltbookError::Api(format!(
-            "Failed to register agent ({}): {}",
-            status, error
-        )));
+        // Parse the response
+        let body_text = response.text().await?;
+        // The body carries the new API key, so it is not quoted whole
+        let moltbook_resp: MoltbookRegisterResponse = serde_json::from_str(&body_text).map_err(|e| {
+            MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, crate::secrets::redact_json(&body_text)))
+        })?;
+
+        Ok(RegisterResponse {
+            api_key: moltbook_resp.agent.api_key,
+            claim_url: moltbook_resp.agent.claim_url,
+            verification_code: moltbook_resp.agent.verification_code,
+        })
     }
 
-    // Parse the response
-    let body_text = response.text().await?;
-    let moltbook_resp: MoltbookRegisterResponse = serde_json::from_str(&body_text)
-        .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
+    /// Check agent claim status with Moltbook.
+    #[allow(dead_code)]
+    pub async fn get_status(&self, api_key: &str) -> Result<StatusResponse, MoltbookError> {
+        let url = format!("{}/agents/status", self.base_url);
 
-    Ok(RegisterResponse {
-        api_key: moltbook_resp.agent.api_key,
-        claim_url: moltbook_resp.agent.claim_url,
-        verification_code: moltbook_resp.agent.verification_code,
-    })
-}
+        let response = self.http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("Failed to get status: {}", error)));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Fetch a page of the agent's home feed (subscribed submolts and followed agents).
+    pub async fn get_feed(&self, api_key: &str, sort: &str, offset: u32, limit: u32) -> Result<FeedPage, MoltbookError> {
+        let url = format!(
+            "{}/feed?sort={}&offset={}&limit={}",
+            self.base_url, sort, offset, limit
+        );
 
-/// Check agent claim status with Moltbook.
-pub async fn get_status(api_key: &str) -> Result<StatusResponse, MoltbookError> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/agents/status", MOLTBOOK_API_BASE);
+        let response = self.http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("Failed to get feed: {}", error)));
+        }
 
-    let response = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .send()
-        .await?;
+        let body_text = response.text().await?;
+        let resp: MoltbookFeedResponse = serde_json::from_str(&body_text)
+            .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
 
-    if !response.status().is_success() {
-        let error = response.text().await.unwrap_or_default();
-        return Err(MoltbookError::Api(format!("Failed to get status: {}", error)));
+        // A full page implies there may be more when the API doesn't say
+        let has_more = resp.has_more.unwrap_or(resp.posts.len() as u32 >= limit);
+        Ok(FeedPage { posts: resp.posts, has_more })
     }
 
-    Ok(response.json().await?)
-}
+    /// List submolts, as seen by the agent.
+    pub async fn list_submolts(&self, api_key: &str) -> Result<Vec<Submolt>, MoltbookError> {
+        let url = format!("{}/submolts", self.base_url);
+
+        let response = self.http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .send()
+            .await?;
 
-/// Get agent info using an existing API key.
-pub async fn get_agent_info(api_key: &str) -> Result<AgentMeResponse, MoltbookError> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/agents/me", MOLTBOOK_API_BASE);
+        if !response.status().is_success() {
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("Failed to list submolts: {}", error)));
+        }
 
-    let response = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .send()
-        .await?;
+        let body_text = response.text().await?;
+        let resp: MoltbookSubmoltsResponse = serde_json::from_str(&body_text)
+            .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
 
-    if !response.status().is_success() {
-        let error = response.text().await.unwrap_or_default();
-        return Err(MoltbookError::Api(format!("Invalid API key or agent not found: {}", error)));
+        Ok(resp.submolts)
     }
 
-    let body_text = response.text().await?;
-    let resp: MoltbookAgentMeResponse = serde_json::from_str(&body_text)
-        .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
+    /// Publish a new post as the agent.
+    pub async fn create_post(
+        &self,
+        api_key: &str,
+        submolt: &str,
+        title: &str,
+        content: &str,
+    ) -> Result<CreatedPost, MoltbookError> {
+        let url = format!("{}/posts", self.base_url);
 
-    Ok(AgentMeResponse {
-        name: resp.agent.name,
-        description: resp.agent.description,
-        is_claimed: resp.agent.is_claimed,
-    })
+        let response = self.http
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&serde_json::json!({
+                "submolt": submolt,
+                "title": title,
+                "content": content
+            }))
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        if !status.is_success() {
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("Failed to publish post ({}): {}", status, error)));
+        }
+
+        let body_text = response.text().await?;
+        let resp: MoltbookCreatePostResponse = serde_json::from_str(&body_text)
+            .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
+
+        Ok(resp.post)
+    }
+
+    /// Upvote a post as the agent.
+    pub async fn upvote_post(&self, api_key: &str, post_id: &str) -> Result<(), MoltbookError> {
+        self.agent_action(api_key, Method::POST, &format!("posts/{}/upvote", post_id), None).await
+    }
+
+    /// Downvote a post as the agent.
+    pub async fn downvote_post(&self, api_key: &str, post_id: &str) -> Result<(), MoltbookError> {
+        self.agent_action(api_key, Method::POST, &format!("posts/{}/downvote", post_id), None).await
+    }
+
+    /// Comment on a post as the agent.
+    pub async fn create_comment(&self, api_key: &str, post_id: &str, content: &str) -> Result<(), MoltbookError> {
+        self.agent_action(
+            api_key,
+            Method::POST,
+            &format!("posts/{}/comments", post_id),
+            Some(serde_json::json!({ "content": content })),
+        )
+        .await
+    }
+
+    /// Fetch the comment tree of a post, best first. Reading works without an
+    /// API key; with one, Moltbook answers as the agent.
+    pub async fn get_comments(&self, api_key: Option<&str>, post_id: &str) -> Result<Vec<Comment>, MoltbookError> {
+        let url = format!("{}/posts/{}/comments?sort=top", self.base_url, urlencoding::encode(post_id));
+
+        let mut request = self.http.get(&url);
+        if let Some(api_key) = api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("Failed to get comments: {}", error)));
+        }
+
+        let body_text = response.text().await?;
+        let resp: MoltbookCommentsResponse = serde_json::from_str(&body_text)
+            .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
+
+        Ok(resp.comments)
+    }
+
+    /// Subscribe the agent to a submolt.
+    pub async fn subscribe_submolt(&self, api_key: &str, name: &str) -> Result<(), MoltbookError> {
+        self.agent_action(api_key, Method::POST, &format!("submolts/{}/subscribe", name), None).await
+    }
+
+    /// Unsubscribe the agent from a submolt.
+    pub async fn unsubscribe_submolt(&self, api_key: &str, name: &str) -> Result<(), MoltbookError> {
+        self.agent_action(api_key, Method::DELETE, &format!("submolts/{}/subscribe", name), None).await
+    }
+
+    /// Call an authenticated Moltbook endpoint, discarding the response body.
+    async fn agent_action(
+        &self,
+        api_key: &str,
+        method: Method,
+        endpoint: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<(), MoltbookError> {
+        let url = format!("{}/{}", self.base_url, endpoint);
+
+        let mut request = self.http
+            .request(method, &url)
+            .header("Authorization", format!("Bearer {}", api_key));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("{} failed ({}): {}", endpoint, status, error)));
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the agent's inbox, newest first.
+    pub async fn get_inbox(&self, api_key: &str, limit: u32) -> Result<Vec<InboxItem>, MoltbookError> {
+        let url = format!("{}/agents/me/inbox?limit={}", self.base_url, limit);
+
+        let response = self.http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("Failed to get inbox: {}", error)));
+        }
+
+        let body_text = response.text().await?;
+        let resp: MoltbookInboxResponse = serde_json::from_str(&body_text)
+            .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
+
+        Ok(resp.items)
+    }
+
+    /// Mark inbox items as read.
+    pub async fn mark_inbox_read(&self, api_key: &str, ids: &[String]) -> Result<(), MoltbookError> {
+        self.agent_action(
+            api_key,
+            Method::POST,
+            "agents/me/inbox/read",
+            Some(serde_json::json!({ "ids": ids })),
+        )
+        .await
+    }
+
+    /// Fetch the agent's current profile.
+    pub async fn get_profile(&self, api_key: &str) -> Result<AgentProfile, MoltbookError> {
+        self.profile_request(api_key, Method::GET, None).await
+    }
+
+    /// Update the agent's profile, returning it as stored by Moltbook.
+    pub async fn update_profile(&self, api_key: &str, profile: &AgentProfile) -> Result<AgentProfile, MoltbookError> {
+        let body = serde_json::to_value(profile)
+            .map_err(|e| MoltbookError::Api(format!("Failed to encode profile: {}", e)))?;
+        self.profile_request(api_key, Method::PATCH, Some(body)).await
+    }
+
+    async fn profile_request(
+        &self,
+        api_key: &str,
+        method: Method,
+        body: Option<serde_json::Value>,
+    ) -> Result<AgentProfile, MoltbookError> {
+        let url = format!("{}/agents/me", self.base_url);
+
+        let mut request = self.http
+            .request(method, &url)
+            .header("Authorization", format!("Bearer {}", api_key));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+
+        if !status.is_success() {
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("Profile request failed ({}): {}", status, error)));
+        }
+
+        let body_text = response.text().await?;
+        let resp: MoltbookProfileResponse = serde_json::from_str(&body_text)
+            .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
+
+        Ok(resp.agent)
+    }
+
+    /// Get agent info using an existing API key.
+    pub async fn get_agent_info(&self, api_key: &str) -> Result<AgentMeResponse, MoltbookError> {
+        let url = format!("{}/agents/me", self.base_url);
+
+        let response = self.http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = error_body(response).await;
+            return Err(MoltbookError::Api(format!("Invalid API key or agent not found: {}", error)));
+        }
+
+        let body_text = response.text().await?;
+        let resp: MoltbookAgentMeResponse = serde_json::from_str(&body_text)
+            .map_err(|e| MoltbookError::Api(format!("Failed to parse response: {}. Body: {}", e, body_text)))?;
+
+        Ok(AgentMeResponse {
+            name: resp.agent.name,
+            description: resp.agent.description,
+            is_claimed: resp.agent.is_claimed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_api_key() {
+        assert!(looks_like_api_key("moltbook_sk_4f9c2a7e81b3d6f0\n"));
+        assert!(!looks_like_api_key("moltbook_short"));
+        assert!(!looks_like_api_key("moltbook_sk_4f9c2a7e81b3d6f0 and more"));
+        assert!(!looks_like_api_key("sk_4f9c2a7e81b3d6f0aaaaaaaa"));
+    }
 }
@@ -0,0 +1,123 @@
+//! Local account nonce tracker.
+//!
+//! The gateway builds every extrinsic with the nonce the chain reports, which
+//! is stale while an earlier transaction from the same account is still
+//! waiting for its block. Submissions reserve the next nonce here instead, so
+//! a second one queues behind the first rather than being rejected as a
+//! duplicate, and the status bar can show what is in flight.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+static TRACKER: Mutex<Option<Tracker>> = Mutex::new(None);
+
+#[derive(Debug, Default)]
+struct Tracker {
+    accounts: HashMap<String, Account>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Account {
+    /// Nonce the next submission will use
+    next: u64,
+    /// Submitted, not yet in a block
+    in_flight: usize,
+}
+
+impl Tracker {
+    fn reserve(&mut self, signer: &str, chain_nonce: u64) -> u64 {
+        let account = self.accounts.entry(signer.to_string()).or_default();
+        // Nothing pending means the chain's count is authoritative (it may have
+        // moved on through another client); otherwise ours is ahead of it
+        let nonce = if account.in_flight == 0 { chain_nonce } else { chain_nonce.max(account.next) };
+        account.next = nonce + 1;
+        account.in_flight += 1;
+        nonce
+    }
+
+    fn release(&mut self, signer: &str, nonce: u64, included: bool) {
+        let Some(account) = self.accounts.get_mut(signer) else {
+            return;
+        };
+        account.in_flight = account.in_flight.saturating_sub(1);
+        // A rejected transaction didn't use its nonce; hand it out again unless
+        // later ones were already built on top of it
+        if !included && account.next == nonce + 1 {
+            account.next = nonce;
+        }
+    }
+
+    fn status(&self, signer: &str) -> Option<Account> {
+        self.accounts.get(signer).copied()
+    }
+}
+
+fn with_tracker<T>(f: impl FnOnce(&mut Tracker) -> T) -> T {
+    let mut tracker = TRACKER.lock().unwrap_or_else(|e| e.into_inner());
+    f(tracker.get_or_insert_with(Tracker::default))
+}
+
+/// A nonce handed out for one submission. Call `included` once the
+/// transaction is in a block; dropping it otherwise frees the nonce.
+pub struct Reservation {
+    signer: String,
+    pub nonce: u64,
+    included: bool,
+}
+
+impl Reservation {
+    pub fn included(mut self) {
+        self.included = true;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        with_tracker(|t| t.release(&self.signer, self.nonce, self.included));
+    }
+}
+
+/// Reserve the nonce for a transaction signed by `signer` (SS58), given the
+/// nonce the gateway built it with.
+pub fn reserve(signer: &str, chain_nonce: u64) -> Reservation {
+    let nonce = with_tracker(|t| t.reserve(signer, chain_nonce));
+    if nonce != chain_nonce {
+        tracing::info!(signer, chain_nonce, nonce, "transaction queued behind one still in flight");
+    }
+    Reservation { signer: signer.to_string(), nonce, included: false }
+}
+
+/// SS58 address of `keypair`, as wallets store it.
+pub fn signer_address(keypair: &subxt_signer::sr25519::Keypair) -> String {
+    use sp_core::crypto::Ss58Codec;
+    sp_core::sr25519::Public::from_raw(keypair.public_key().0).to_ss58check()
+}
+
+/// Next nonce and number of in-flight transactions for `signer`, once it
+/// has submitted anything this session.
+pub fn status(signer: &str) -> Option<(u64, usize)> {
+    with_tracker(|t| t.status(signer)).map(|a| (a.next, a.in_flight))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker() {
+        let mut tracker = Tracker::default();
+        assert_eq!(tracker.reserve("alice", 4), 4);
+        // Gateway still reports 4 while the first is pending
+        assert_eq!(tracker.reserve("alice", 4), 5);
+        assert_eq!(tracker.status("alice"), Some(Account { next: 6, in_flight: 2 }));
+
+        // The second is rejected: its nonce is reused
+        tracker.release("alice", 5, false);
+        tracker.release("alice", 4, true);
+        assert_eq!(tracker.status("alice"), Some(Account { next: 5, in_flight: 0 }));
+
+        // Idle again, so the chain's nonce wins even if it went backwards
+        assert_eq!(tracker.reserve("alice", 3), 3);
+        assert_eq!(tracker.status("bob"), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! Notification queue for results of background work.
+//!
+//! Every notification lands in a bounded history; those pushed as toasts are
+//! also shown in a corner of the screen for a few seconds.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a toast stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Toasts shown at once; older ones remain in the history.
+const MAX_TOASTS: usize = 3;
+
+/// Notifications kept for the history panel.
+const HISTORY_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Info,
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub level: Level,
+    pub message: String,
+    pub at: Instant,
+    /// Shown as a toast, not just recorded in the history
+    toast: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Notifications {
+    /// Newest first
+    history: VecDeque<Notification>,
+}
+
+impl Notifications {
+    /// Show a toast and keep it in the history.
+    pub fn push(&mut self, level: Level, message: impl Into<String>) {
+        self.add(level, message.into(), true);
+    }
+
+    /// Keep in the history only, for results the current screen already shows.
+    pub fn record(&mut self, level: Level, message: impl Into<String>) {
+        self.add(level, message.into(), false);
+    }
+
+    fn add(&mut self, level: Level, message: String, toast: bool) {
+        match level {
+            Level::Error => tracing::warn!("{}", message),
+            Level::Info | Level::Success => tracing::info!("{}", message),
+        }
+        self.history.push_front(Notification { level, message, at: Instant::now(), toast });
+        self.history.truncate(HISTORY_LIMIT);
+    }
+
+    /// Toasts still on screen, newest first.
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.toasts_at(Instant::now())
+    }
+
+    fn toasts_at(&self, now: Instant) -> impl Iterator<Item = &Notification> {
+        self.history
+            .iter()
+            .take_while(move |n| now.duration_since(n.at) < TOAST_DURATION)
+            .filter(|n| n.toast)
+            .take(MAX_TOASTS)
+    }
+
+    /// Whether any toast is visible (the screen needs redraws until they expire).
+    pub fn has_toasts(&self) -> bool {
+        self.toasts().next().is_some()
+    }
+
+    /// Hide the visible toasts; they stay in the history.
+    pub fn dismiss_toasts(&mut self) {
+        for n in &mut self.history {
+            n.toast = false;
+        }
+    }
+
+    /// All kept notifications, newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_expire_but_history_stays() {
+        let mut notifications = Notifications::default();
+        notifications.push(Level::Success, "deployed");
+        notifications.record(Level::Info, "balance changed");
+        for i in 0..4 {
+            notifications.push(Level::Error, format!("failure {}", i));
+        }
+
+        let now = Instant::now();
+        let toasts: Vec<_> = notifications.toasts_at(now).map(|n| n.message.as_str()).collect();
+        assert_eq!(toasts, ["failure 3", "failure 2", "failure 1"]);
+        assert_eq!(notifications.toasts_at(now + TOAST_DURATION).count(), 0);
+        assert_eq!(notifications.history().count(), 6);
+
+        for i in 0..HISTORY_LIMIT {
+            notifications.record(Level::Info, format!("{}", i));
+        }
+        assert_eq!(notifications.history().count(), HISTORY_LIMIT);
+    }
+}
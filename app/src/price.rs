@@ -0,0 +1,78 @@
+//! Optional USD price feed, for approximate fiat values next to balances.
+//!
+//! The feed is any URL answering JSON with a `usd` number somewhere in it, so
+//! both `{"usd": 0.42}` and CoinGecko's `{"theseus": {"usd": 0.42}}` work.
+
+use anyhow::{Context, Result};
+
+/// Fetch the current USD price of one token from `url`.
+pub async fn fetch_usd_price(url: &str) -> Result<f64> {
+    let resp = reqwest::get(url).await?;
+    if !resp.status().is_success() {
+        let error = crate::client::error_body(resp).await;
+        anyhow::bail!("Price feed error: {}", error);
+    }
+    let body: serde_json::Value = resp.json().await?;
+    find_usd(&body).context("Price feed answered without a usd price")
+}
+
+/// First `usd` number in the document, searching depth first.
+fn find_usd(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Object(map) => map
+            .get("usd")
+            .and_then(|v| v.as_f64())
+            .or_else(|| map.values().find_map(find_usd)),
+        serde_json::Value::Array(items) => items.iter().find_map(find_usd),
+        _ => None,
+    }
+}
+
+/// "≈ $12.34" for a token amount as the gateway formats it ("1,234.5").
+pub fn usd_value(amount: &str, price: f64) -> Option<String> {
+    let tokens: f64 = amount.trim().replace([',', '_'], "").parse().ok()?;
+    let usd = tokens * price;
+    if usd > 0.0 && usd < 0.01 {
+        return Some("≈ <$0.01".to_string());
+    }
+    Some(format!("≈ ${}", group_thousands(&format!("{:.2}", usd))))
+}
+
+/// Thousands separators for the integer part of a decimal string.
+fn group_thousands(number: &str) -> String {
+    let (int, frac) = number.split_once('.').unwrap_or((number, ""));
+    let mut grouped = String::new();
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    if frac.is_empty() {
+        grouped
+    } else {
+        format!("{}.{}", grouped, frac)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_usd() {
+        assert_eq!(find_usd(&serde_json::json!({ "usd": 0.42 })), Some(0.42));
+        assert_eq!(find_usd(&serde_json::json!({ "theseus": { "usd": 1.5, "eur": 1.4 } })), Some(1.5));
+        assert_eq!(find_usd(&serde_json::json!({ "usd": "0.42" })), None);
+        assert_eq!(find_usd(&serde_json::json!([{ "price": 1 }])), None);
+    }
+
+    #[test]
+    fn test_usd_value() {
+        assert_eq!(usd_value("12.5", 0.4).as_deref(), Some("≈ $5.00"));
+        assert_eq!(usd_value("1,234,567", 1.0).as_deref(), Some("≈ $1,234,567.00"));
+        assert_eq!(usd_value("0.001", 0.5).as_deref(), Some("≈ <$0.01"));
+        assert_eq!(usd_value("0", 0.5).as_deref(), Some("≈ $0.00"));
+        assert_eq!(usd_value("lots", 0.5), None);
+    }
+}
@@ -0,0 +1,317 @@
+//! Shareable summary of an agent, written as Markdown and HTML side by side
+//! from what the agent page has loaded: profile, chain info, schedule,
+//! recent runs, top posts and the balance with what the runs were charged.
+
+use crate::client::{ActivityEvent, ActivityKind, ChainAgentInfo, MoltbookAgentInfo, MoltbookPost};
+use crate::heartbeat::Heartbeat;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Posts listed, most upvoted first.
+const TOP_POSTS: usize = 5;
+
+/// Runs listed, newest first.
+const RECENT_RUNS: usize = 10;
+
+/// Where `<agent name>-<time>.md` and `.html` reports go.
+pub fn reports_dir() -> PathBuf {
+    crate::config::data_dir().join("reports")
+}
+
+/// A finished run from the activity log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunLine {
+    pub run_id: Option<u64>,
+    pub block: u64,
+    pub completed: bool,
+    /// Charge, already formatted with the token symbol
+    pub cost: Option<String>,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostLine {
+    pub title: String,
+    pub submolt: String,
+    pub upvotes: u32,
+    pub comments: u32,
+    pub url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub agent_name: String,
+    pub address: String,
+    pub network: String,
+    /// Unix seconds
+    pub generated_at: u64,
+    pub chain: Option<ChainAgentInfo>,
+    pub moltbook: Option<MoltbookAgentInfo>,
+    pub heartbeat: Option<Heartbeat>,
+    /// Agent account balance, formatted with the token symbol
+    pub balance: Option<String>,
+    pub runs: Vec<RunLine>,
+    pub posts: Vec<PostLine>,
+}
+
+impl Report {
+    /// `format_cost` turns a run's planck charge into tokens for display.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        agent_name: &str,
+        address: &str,
+        network: &str,
+        chain: Option<&ChainAgentInfo>,
+        moltbook: Option<&MoltbookAgentInfo>,
+        activity: &[ActivityEvent],
+        posts: &[MoltbookPost],
+        balance: Option<String>,
+        format_cost: impl Fn(u128) -> String,
+    ) -> Self {
+        let runs = activity
+            .iter()
+            .filter(|e| matches!(e.kind, ActivityKind::RunCompleted | ActivityKind::RunFailed))
+            .take(RECENT_RUNS)
+            .map(|e| RunLine {
+                run_id: e.run_id,
+                block: e.block_number,
+                completed: e.kind == ActivityKind::RunCompleted,
+                cost: e.cost.as_deref().and_then(|c| c.parse().ok()).map(&format_cost),
+                detail: e.detail.clone(),
+            })
+            .collect();
+
+        let mut top: Vec<&MoltbookPost> = posts.iter().collect();
+        top.sort_by_key(|p| std::cmp::Reverse(p.upvotes));
+        let posts = top
+            .into_iter()
+            .take(TOP_POSTS)
+            .map(|p| PostLine {
+                title: p.title.clone().or_else(|| p.content.clone()).unwrap_or_default(),
+                submolt: p.submolt.as_ref().map_or("general".to_string(), |s| s.name.clone()),
+                upvotes: p.upvotes,
+                comments: p.comment_count,
+                url: crate::moltbook::post_url(&p.id),
+            })
+            .collect();
+
+        Self {
+            agent_name: agent_name.to_string(),
+            address: address.to_string(),
+            network: network.to_string(),
+            generated_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            heartbeat: chain.and_then(|c| Heartbeat::from_activity(c, activity)),
+            chain: chain.cloned(),
+            moltbook: moltbook.cloned(),
+            balance,
+            runs,
+            posts,
+        }
+    }
+
+    /// Label/value rows of the overview, shared by both formats.
+    fn overview(&self) -> Vec<(&'static str, String)> {
+        let mut rows = vec![("Address", self.address.clone()), ("Network", self.network.clone())];
+        match &self.chain {
+            Some(chain) => {
+                rows.push(("Owner", chain.owner.clone()));
+                rows.push(("Status", if chain.active { "active" } else { "paused" }.to_string()));
+                rows.push(("Program version", chain.version.to_string()));
+            }
+            None => rows.push(("Status", "not on chain".to_string())),
+        }
+        rows.push((
+            "Schedule",
+            match (&self.heartbeat, self.chain.as_ref().and_then(|c| c.schedule_blocks)) {
+                (Some(h), _) => format!("every {} blocks, last heartbeat at #{}, {}", h.every, h.last, h.status(None).label()),
+                (None, Some(blocks)) => format!("every {} blocks", blocks),
+                (None, None) => "none".to_string(),
+            },
+        ));
+        rows.push(("Balance", self.balance.clone().unwrap_or_else(|| "unknown".to_string())));
+        if let Some(moltbook) = &self.moltbook {
+            rows.push(("Moltbook", format!("{}{}", moltbook.name, if moltbook.claimed { " (claimed)" } else { " (not claimed)" })));
+            if let Some(twitter) = &moltbook.twitter_handle {
+                rows.push(("Owner on X", format!("@{}", twitter)));
+            }
+        }
+        rows
+    }
+
+    fn run_outcome(run: &RunLine) -> &'static str {
+        if run.completed {
+            "completed"
+        } else {
+            "failed"
+        }
+    }
+
+    fn run_id(run: &RunLine) -> String {
+        run.run_id.map_or("-".to_string(), |id| format!("#{}", id))
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Agent report: {}\n\n", self.agent_name);
+        if let Some(description) = self.moltbook.as_ref().and_then(|m| m.description.as_deref()) {
+            out.push_str(&format!("> {}\n\n", description.replace('\n', " ")));
+        }
+        for (label, value) in self.overview() {
+            out.push_str(&format!("- **{}:** {}\n", label, value));
+        }
+
+        out.push_str("\n## Recent runs\n\n");
+        if self.runs.is_empty() {
+            out.push_str("No finished runs in the recent activity.\n");
+        } else {
+            out.push_str("| Run | Block | Outcome | Charged | Detail |\n|---|---|---|---|---|\n");
+            for run in &self.runs {
+                out.push_str(&format!(
+                    "| {} | #{} | {} | {} | {} |\n",
+                    Self::run_id(run),
+                    run.block,
+                    Self::run_outcome(run),
+                    run.cost.as_deref().unwrap_or("-"),
+                    run.detail.as_deref().unwrap_or("").replace('|', "\\|"),
+                ));
+            }
+        }
+
+        out.push_str("\n## Top posts\n\n");
+        if self.posts.is_empty() {
+            out.push_str("No posts yet.\n");
+        }
+        for (i, post) in self.posts.iter().enumerate() {
+            out.push_str(&format!(
+                "{}. [{}]({}) in m/{}: {} upvotes, {} comments\n",
+                i + 1,
+                post.title.replace(['[', ']'], ""),
+                post.url,
+                post.submolt,
+                post.upvotes,
+                post.comments
+            ));
+        }
+        out.push_str(&format!("\n_Generated by lobster at unix time {}._\n", self.generated_at));
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Agent report: {0}</title>\n</head>\n<body>\n<h1>Agent report: {0}</h1>\n",
+            escape(&self.agent_name)
+        );
+        if let Some(description) = self.moltbook.as_ref().and_then(|m| m.description.as_deref()) {
+            out.push_str(&format!("<blockquote>{}</blockquote>\n", escape(description)));
+        }
+        out.push_str("<ul>\n");
+        for (label, value) in self.overview() {
+            out.push_str(&format!("<li><strong>{}:</strong> {}</li>\n", label, escape(&value)));
+        }
+        out.push_str("</ul>\n<h2>Recent runs</h2>\n");
+        if self.runs.is_empty() {
+            out.push_str("<p>No finished runs in the recent activity.</p>\n");
+        } else {
+            out.push_str("<table>\n<tr><th>Run</th><th>Block</th><th>Outcome</th><th>Charged</th><th>Detail</th></tr>\n");
+            for run in &self.runs {
+                out.push_str(&format!(
+                    "<tr><td>{}</td><td>#{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    Self::run_id(run),
+                    run.block,
+                    Self::run_outcome(run),
+                    escape(run.cost.as_deref().unwrap_or("-")),
+                    escape(run.detail.as_deref().unwrap_or("")),
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("<h2>Top posts</h2>\n");
+        if self.posts.is_empty() {
+            out.push_str("<p>No posts yet.</p>\n");
+        } else {
+            out.push_str("<ol>\n");
+            for post in &self.posts {
+                out.push_str(&format!(
+                    "<li><a href=\"{}\">{}</a> in m/{}: {} upvotes, {} comments</li>\n",
+                    escape(&post.url),
+                    escape(&post.title),
+                    escape(&post.submolt),
+                    post.upvotes,
+                    post.comments
+                ));
+            }
+            out.push_str("</ol>\n");
+        }
+        out.push_str(&format!("<p><em>Generated by lobster at unix time {}.</em></p>\n</body>\n</html>\n", self.generated_at));
+        out
+    }
+
+    /// Write both formats to the reports directory; returns the Markdown path.
+    pub fn save(&self) -> Result<PathBuf> {
+        let dir = reports_dir();
+        std::fs::create_dir_all(&dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+        let name: String = self
+            .agent_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        let base = dir.join(format!("{}-{}", name, self.generated_at));
+        let markdown = base.with_extension("md");
+        std::fs::write(&markdown, self.to_markdown()).with_context(|| format!("Cannot write {}", markdown.display()))?;
+        let html = base.with_extension("html");
+        std::fs::write(&html, self.to_html()).with_context(|| format!("Cannot write {}", html.display()))?;
+        Ok(markdown)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_formats() {
+        let chain = ChainAgentInfo {
+            owner: "5Owner".to_string(),
+            name: "pinchy".to_string(),
+            active: true,
+            version: 2,
+            schedule_blocks: Some(600),
+        };
+        let activity: Vec<ActivityEvent> = serde_json::from_value(serde_json::json!([
+            { "kind": "run_failed", "block_number": 130, "run_id": 4, "detail": "out of gas" },
+            { "kind": "run_completed", "block_number": 120, "run_id": 3, "cost": "250" },
+            { "kind": "heartbeat_scheduled", "block_number": 100 },
+        ]))
+        .unwrap();
+        let posts: Vec<MoltbookPost> = serde_json::from_value(serde_json::json!([
+            { "id": "a", "title": "Small <talk>", "created_at": "", "upvotes": 1 },
+            { "id": "b", "title": "Molting season", "created_at": "", "upvotes": 12, "comment_count": 3 },
+        ]))
+        .unwrap();
+        let report = Report::new(
+            "pinchy",
+            "5Agent",
+            "testnet",
+            Some(&chain),
+            None,
+            &activity,
+            &posts,
+            Some("3 THE".to_string()),
+            |planck| format!("{} planck", planck),
+        );
+
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# Agent report: pinchy\n\n- **Address:** 5Agent\n"));
+        assert!(markdown.contains("- **Schedule:** every 600 blocks, last heartbeat at #100, next at #700\n"));
+        assert!(markdown.contains("| #4 | #130 | failed | - | out of gas |\n| #3 | #120 | completed | 250 planck |  |\n"));
+        assert!(markdown.contains("1. [Molting season](https://www.moltbook.com/post/b) in m/general: 12 upvotes, 3 comments\n"));
+
+        let html = report.to_html();
+        assert!(html.contains("<li><a href=\"https://www.moltbook.com/post/a\">Small &lt;talk&gt;</a>"));
+    }
+}
@@ -0,0 +1,282 @@
+//! Per-run NDJSON logs: every decoded chain event of a run, one JSON object
+//! per line in the gateway's own event format, tool arguments and results
+//! included, so runs can be analysed or replayed by other tools. Written
+//! only while `run_logs` is on.
+
+use crate::client::{ChainEventData, ChatMessage, ToolCallInfo};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Where `<agent address>-<run id>.ndjson` and `dry-run-<time>.ndjson` files go.
+pub fn runs_dir() -> PathBuf {
+    crate::config::data_dir().join("runs")
+}
+
+/// Open log file of one run.
+pub struct RunLog {
+    path: PathBuf,
+    file: File,
+}
+
+impl RunLog {
+    /// Log of run `run_id` of the agent at `agent_address`.
+    pub fn for_run(agent_address: &str, run_id: u64) -> Option<Self> {
+        Self::open_in(&runs_dir(), &format!("{}-{}", agent_address, run_id))
+    }
+
+    /// Log of a dry run, named after the time it started.
+    pub fn for_dry_run() -> Option<Self> {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::open_in(&runs_dir(), &format!("dry-run-{}", secs))
+    }
+
+    /// Append to `<dir>/<name>.ndjson`, creating both. None (and a log line)
+    /// when it can't be opened; the run goes on without a log.
+    pub fn open_in(dir: &Path, name: &str) -> Option<Self> {
+        let path = dir.join(format!("{}.ndjson", name));
+        let opened = std::fs::create_dir_all(dir)
+            .and_then(|()| std::fs::OpenOptions::new().create(true).append(true).open(&path));
+        match opened {
+            Ok(file) => Some(Self { path, file }),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "Cannot open the run log: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Write `event` as one line, straight to disk so an interrupted run
+    /// keeps what it had.
+    pub fn append(&mut self, event: &ChainEventData) {
+        let written = serde_json::to_string(event)
+            .map_err(std::io::Error::from)
+            .and_then(|line| writeln!(self.file, "{}", line));
+        if let Err(e) = written {
+            tracing::warn!(path = %self.path.display(), "Cannot write the run log: {}", e);
+        }
+    }
+}
+
+/// What a recorded run did, read back from its log.
+#[derive(Debug, Clone, Default)]
+pub struct RecordedRun {
+    /// File name without `.ndjson`
+    pub name: String,
+    /// Tool calls in the order the agent made them
+    pub tool_calls: Vec<ToolCallInfo>,
+    /// Final output of a completed run
+    pub output: Option<String>,
+    /// Reason a failed run gave
+    pub failure: Option<String>,
+    /// The conversation as the run last reported it
+    pub messages: Vec<ChatMessage>,
+}
+
+impl RecordedRun {
+    /// Read a log; lines that aren't chain events are skipped.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        Ok(Self::from_events(name, text.lines().filter_map(|l| serde_json::from_str(l).ok())))
+    }
+
+    fn from_events(name: String, events: impl Iterator<Item = ChainEventData>) -> Self {
+        let mut run = Self { name, ..Self::default() };
+        for event in events {
+            match event {
+                // Each batch repeats the conversation so far; the last one is complete
+                ChainEventData::Messages { messages, .. } => {
+                    run.tool_calls = messages
+                        .iter()
+                        .filter_map(|m| match m {
+                            ChatMessage::Assistant { tool_calls, .. } => Some(tool_calls.clone()),
+                            _ => None,
+                        })
+                        .flatten()
+                        .collect();
+                    run.messages = messages;
+                }
+                ChainEventData::Completed { output, .. } => run.output = Some(output),
+                ChainEventData::Failed { reason, .. } => run.failure = Some(reason),
+                _ => {}
+            }
+        }
+        run
+    }
+
+    /// The run as text, one line per line of each message: what was asked,
+    /// said, called and returned, then how it ended. System prompts are left out.
+    pub fn transcript(&self) -> Vec<TranscriptLine> {
+        let mut lines = Vec::new();
+        let mut push = |role: &'static str, text: &str| {
+            lines.extend(text.lines().map(|line| TranscriptLine { role, text: line.to_string() }));
+        };
+        for message in &self.messages {
+            match message {
+                ChatMessage::System { .. } => {}
+                ChatMessage::User { content } => push("user", content),
+                ChatMessage::Assistant { content, tool_calls, .. } => {
+                    push("agent", content.as_deref().unwrap_or_default());
+                    for call in tool_calls {
+                        push("call", &format!("{}({})", call.name, call.arguments));
+                    }
+                }
+                ChatMessage::ToolResult { tool_name, result, .. } => push("result", &format!("{}: {}", tool_name, result)),
+            }
+        }
+        if let Some(output) = &self.output {
+            push("output", output);
+        }
+        if let Some(reason) = &self.failure {
+            push("failed", reason);
+        }
+        lines
+    }
+}
+
+/// One line of a run's transcript.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptLine {
+    /// "user", "agent", "call", "result", "output" or "failed"
+    pub role: &'static str,
+    pub text: String,
+}
+
+/// Where `query` occurs in the `transcripts` of runs, in order: run index,
+/// transcript line index and byte range in that line. Matching ignores
+/// ASCII case.
+pub fn search(transcripts: &[Vec<TranscriptLine>], query: &str) -> Vec<SearchHit> {
+    let query = query.to_ascii_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut hits = Vec::new();
+    for (run, transcript) in transcripts.iter().enumerate() {
+        for (line, entry) in transcript.iter().enumerate() {
+            // ASCII lowercasing keeps byte offsets, so ranges index the original
+            let text = entry.text.to_ascii_lowercase();
+            hits.extend(text.match_indices(&query).map(|(start, _)| SearchHit { run, line, range: start..start + query.len() }));
+        }
+    }
+    hits
+}
+
+/// One match of a search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub run: usize,
+    pub line: usize,
+    pub range: std::ops::Range<usize>,
+}
+
+/// Logs in `runs_dir()` of the agent at `agent_address` and of dry runs,
+/// newest first.
+pub fn recorded_runs(agent_address: Option<&str>) -> Vec<PathBuf> {
+    logs(|stem| stem.starts_with("dry-run-") || agent_address.is_some_and(|a| stem.starts_with(&format!("{}-", a))))
+}
+
+/// Every log in `runs_dir()`, of any agent, newest first.
+pub fn all_recorded_runs() -> Vec<PathBuf> {
+    logs(|_| true)
+}
+
+fn logs(wanted: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(runs_dir()) else { return Vec::new() };
+    let mut runs: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "ndjson"))
+        .filter(|p| wanted(&p.file_stem().unwrap_or_default().to_string_lossy()))
+        .map(|p| (std::fs::metadata(&p).and_then(|m| m.modified()).unwrap_or(std::time::UNIX_EPOCH), p))
+        .collect();
+    runs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    runs.into_iter().map(|(_, p)| p).collect()
+}
+
+/// One row of a side-by-side diff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffRow<'a, T> {
+    /// Matched on both sides (the items may still differ beyond their key)
+    Both(&'a T, &'a T),
+    /// Only in the left run
+    Left(&'a T),
+    /// Only in the right run
+    Right(&'a T),
+}
+
+/// Align `left` and `right` on their longest common subsequence of keys.
+pub fn diff<'a, T, K: PartialEq>(left: &'a [T], right: &'a [T], key: impl Fn(&T) -> K) -> Vec<DiffRow<'a, T>> {
+    let (n, m) = (left.len(), right.len());
+    // common[i][j]: LCS length of left[i..] and right[j..]
+    let mut common = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            common[i][j] = if key(&left[i]) == key(&right[j]) {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut rows = Vec::new();
+    while i < n && j < m {
+        if key(&left[i]) == key(&right[j]) {
+            rows.push(DiffRow::Both(&left[i], &right[j]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            rows.push(DiffRow::Left(&left[i]));
+            i += 1;
+        } else {
+            rows.push(DiffRow::Right(&right[j]));
+            j += 1;
+        }
+    }
+    rows.extend(left[i..].iter().map(DiffRow::Left));
+    rows.extend(right[j..].iter().map(DiffRow::Right));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_aligns_common_items() {
+        let rows = diff(&["feed", "post", "reply"], &["feed", "search", "reply"], |s| *s);
+        assert_eq!(
+            rows,
+            [
+                DiffRow::Both(&"feed", &"feed"),
+                DiffRow::Left(&"post"),
+                DiffRow::Right(&"search"),
+                DiffRow::Both(&"reply", &"reply"),
+            ]
+        );
+        assert_eq!(diff(&[] as &[&str], &["a"], |s| *s), [DiffRow::Right(&"a")]);
+    }
+
+    #[test]
+    fn test_recorded_run_keeps_last_conversation() {
+        let events = [
+            r#"{"type":"messages","run_id":1,"messages":[{"role":"assistant","content":null,"tool_calls":[{"call_id":1,"name":"feed","arguments":"{}"}]}]}"#,
+            r#"{"type":"messages","run_id":1,"messages":[{"role":"assistant","content":null,"tool_calls":[{"call_id":1,"name":"feed","arguments":"{}"}]},{"role":"tool_result","tool_name":"feed","call_id":1,"success":true,"result":"ok"},{"role":"assistant","content":"done","tool_calls":[{"call_id":2,"name":"post","arguments":"{\"title\":\"hi\"}"}]}]}"#,
+            r#"{"type":"completed","run_id":1,"output":"Posted hi"}"#,
+        ];
+        let run = RecordedRun::from_events("5Agent-1".to_string(), events.iter().map(|e| serde_json::from_str(e).unwrap()));
+        let names: Vec<&str> = run.tool_calls.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["feed", "post"]);
+        assert_eq!(run.output.as_deref(), Some("Posted hi"));
+        assert!(run.failure.is_none());
+
+        let hits = search(&[run.transcript()], "HI");
+        let lines: Vec<usize> = hits.iter().map(|h| h.line).collect();
+        // post's arguments, then the output
+        assert_eq!(lines, [3, 4]);
+        assert_eq!(hits[1].range, 7..9);
+    }
+}
@@ -0,0 +1,148 @@
+//! `lobster init` - writes a new custom agent directory from a template.
+//!
+//! Every template uses the embedded agent program, which is what the gateway
+//! knows how to deploy; templates differ in the markdown they start from.
+
+use crate::agent_assets::AgentSource;
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use std::path::Path;
+
+/// Files written by `init`, in the order they are listed.
+const FILES: [&str; 4] = ["moltbook_agent.ship", "SOUL.md", "SKILL.md", "HEARTBEAT.md"];
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Template {
+    /// The built-in lobster agent, with guidance on what to change
+    Moltbook,
+    /// Headings and {{placeholders}} to fill in; the Files box warns until they are
+    Blank,
+}
+
+const SHIP_GUIDANCE: &str = "\
+// Scaffolded by `lobster init`.
+//
+// You rarely need to change this file: personality, API notes and the
+// scheduled routine live in the markdown files it includes. The deploy
+// wizard fills in API_KEY and the schedule. See the SHIP docs at
+// https://www.theseuschain.com/docs before changing the nodes below.
+
+";
+
+const SOUL_GUIDANCE: &str = "\
+<!--
+  SOUL.md is the agent's system prompt: who it is, what it cares about and how
+  it talks. Keep it concrete; a few specific opinions beat a list of virtues.
+  This comment is sent to the model too - delete it once you are done.
+-->
+
+";
+
+const SKILL_GUIDANCE: &str = "\
+<!--
+  SKILL.md teaches the agent the Moltbook API through the moltbook_get and
+  moltbook_post tools. Change it when you want the agent to use more or fewer
+  endpoints. This comment is sent to the model too - delete it once you are done.
+-->
+
+";
+
+const HEARTBEAT_GUIDANCE: &str = "\
+<!--
+  HEARTBEAT.md is the routine for scheduled runs (no owner prompt). Say what
+  to read, when to post or comment, and when to stop. This comment is sent to
+  the model too - delete it once you are done.
+-->
+
+";
+
+const BLANK_SOUL: &str = "\
+# Soul
+
+You are {{agent name}}, an AI agent on Moltbook.
+
+## Domain Expertise
+
+{{What the agent knows well and likes to talk about.}}
+
+## Personality
+
+{{How the agent writes: tone, length, humour, what it avoids.}}
+
+## Important Rules
+
+- Never share your API key.
+- {{Anything the agent must always or never do.}}
+";
+
+const BLANK_HEARTBEAT: &str = "\
+# Heartbeat
+
+Each scheduled run, work through this routine and stop when you are done.
+
+## Routine
+
+1. Read the newest posts in {{submolts to follow, e.g. m/ai}}.
+2. {{When to comment or upvote.}}
+3. {{When to write a post of your own, and how often.}}
+
+## When to Wait
+
+{{When doing nothing is the right call.}}
+";
+
+impl Template {
+    fn file(self, name: &str) -> String {
+        let embedded = || AgentSource::Embedded.read_file(name).unwrap_or_default();
+        match (self, name) {
+            (_, "moltbook_agent.ship") => format!("{}{}", SHIP_GUIDANCE, embedded()),
+            (Template::Moltbook, "SOUL.md") => format!("{}{}", SOUL_GUIDANCE, embedded()),
+            (Template::Blank, "SOUL.md") => format!("{}{}", SOUL_GUIDANCE, BLANK_SOUL),
+            // The API reference is the same for every agent
+            (_, "SKILL.md") => format!("{}{}", SKILL_GUIDANCE, embedded()),
+            (Template::Moltbook, "HEARTBEAT.md") => format!("{}{}", HEARTBEAT_GUIDANCE, embedded()),
+            (Template::Blank, "HEARTBEAT.md") => format!("{}{}", HEARTBEAT_GUIDANCE, BLANK_HEARTBEAT),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Write the template's files into `dir`, creating it if needed. Existing
+/// agent files are only replaced with `force`.
+pub fn init(dir: &Path, template: Template, force: bool) -> Result<()> {
+    if !force {
+        let existing: Vec<&str> = FILES.into_iter().filter(|name| dir.join(name).exists()).collect();
+        if !existing.is_empty() {
+            bail!("{} already has {}; use --force to overwrite", dir.display(), existing.join(", "));
+        }
+    }
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    for name in FILES {
+        let path = dir.join(name);
+        std::fs::write(&path, template.file(name)).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_writes_a_valid_agent() {
+        let dir = std::env::temp_dir().join(format!("lobster-init-{}", std::process::id()));
+        init(&dir, Template::Blank, false).unwrap();
+
+        let validation = AgentSource::Custom(dir.display().to_string()).validate();
+        assert!(validation.is_valid());
+        // Blank placeholders are flagged until filled in
+        assert!(validation.issues.iter().any(|i| i.message.contains("{{agent name}}")));
+
+        assert!(init(&dir, Template::Moltbook, false).is_err());
+        init(&dir, Template::Moltbook, true).unwrap();
+        let validation = AgentSource::Custom(dir.display().to_string()).validate();
+        assert_eq!(validation.issues, vec![]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,343 @@
+//! Prompts composed now and submitted later.
+//!
+//! A scheduled prompt waits in `scheduled.json` in the data directory until
+//! its block height or wall-clock time is reached. Whichever is running then,
+//! the TUI or `lobster daemon`, takes it off the list and builds, signs and
+//! submits the call at that moment, so the nonce is the account's current one
+//! rather than the one it had when the prompt was written. Each prompt keeps
+//! the network it was scheduled on and is only sent with that network's
+//! wallet. The list is read and rewritten under `scheduled.json.lock`, so a
+//! TUI and a daemon running side by side never both take the same prompt.
+
+use crate::client::{ApiClient, SubmitResponse};
+use crate::wallet::WalletConfig;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a lock file may be held before it is taken as left by a crash
+const STALE_LOCK: Duration = Duration::from_secs(10);
+
+/// When a scheduled prompt is due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum When {
+    /// Once the chain head reaches this block
+    Block(u64),
+    /// At this Unix time (seconds)
+    Time(u64),
+}
+
+impl When {
+    /// Parse what the user typed, relative to `now` (Unix seconds):
+    /// `#1234` or `block 1234`, `+30m` (also `s`, `h`, `d`), `14:30` (next
+    /// time it is that o'clock in UTC) or `2026-10-18 14:30` (UTC).
+    pub fn parse(input: &str, now: u64) -> Result<Self, String> {
+        let input = input.trim();
+        let block = input.strip_prefix('#').or_else(|| input.strip_prefix("block ")).map(str::trim);
+        if let Some(block) = block {
+            return block.parse().map(When::Block).map_err(|_| format!("Not a block number: {}", block));
+        }
+        if let Some(delay) = input.strip_prefix('+') {
+            return parse_delay(delay).map(|secs| When::Time(now + secs));
+        }
+        let at = match input.split_once([' ', 'T']) {
+            Some((date, time)) => {
+                let days = parse_date(date)?;
+                let minutes = parse_clock(time.trim_end_matches('Z'))?;
+                days * 86_400 + minutes * 60
+            }
+            None => {
+                let minutes = parse_clock(input)?;
+                let today = now - now % 86_400 + minutes * 60;
+                if today > now { today } else { today + 86_400 }
+            }
+        };
+        if at <= now {
+            return Err(format!("{} UTC has already passed", input));
+        }
+        Ok(When::Time(at))
+    }
+
+    /// Reached at chain head `block` (when known) and Unix time `now`.
+    pub fn is_due(self, block: Option<u64>, now: u64) -> bool {
+        match self {
+            When::Block(target) => block.is_some_and(|b| b >= target),
+            When::Time(at) => now >= at,
+        }
+    }
+
+    /// E.g. "block #1234" or "2026-10-18 14:30 UTC".
+    pub fn label(self) -> String {
+        match self {
+            When::Block(block) => format!("block #{}", block),
+            When::Time(at) => {
+                let (year, month, day) = civil_from_days(at / 86_400);
+                let minutes = at % 86_400 / 60;
+                format!("{}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+/// `30s`, `15m`, `2h` or `1d` in seconds.
+fn parse_delay(delay: &str) -> Result<u64, String> {
+    let invalid = || format!("Not a delay like +30m or +2h: +{}", delay);
+    let unit = match delay.chars().last() {
+        Some('s') => 1,
+        Some('m') => 60,
+        Some('h') => 3600,
+        Some('d') => 86_400,
+        _ => return Err(invalid()),
+    };
+    let count: u64 = delay[..delay.len() - 1].parse().map_err(|_| invalid())?;
+    count.checked_mul(unit).filter(|&secs| secs > 0).ok_or_else(invalid)
+}
+
+/// `HH:MM` in minutes after midnight.
+fn parse_clock(time: &str) -> Result<u64, String> {
+    let invalid = || format!("Not a block (#1234), delay (+30m) or UTC time (14:30): {}", time);
+    let (hours, minutes) = time.split_once(':').ok_or_else(invalid)?;
+    let hours: u64 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u64 = minutes.parse().map_err(|_| invalid())?;
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+/// `YYYY-MM-DD` in days since the Unix epoch.
+fn parse_date(date: &str) -> Result<u64, String> {
+    let invalid = || format!("Not a date like 2026-10-18: {}", date);
+    let mut parts = date.splitn(3, '-').map(|p| p.parse::<u64>().map_err(|_| invalid()));
+    let (year, month, day) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(y), Some(m), Some(d)) => (y?, m?, d?),
+        _ => return Err(invalid()),
+    };
+    if !(1970..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+    Ok(days_from_civil(year, month, day))
+}
+
+// Gregorian calendar <-> day count, after Howard Hinnant's `days_from_civil`.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// A prompt waiting for its moment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledPrompt {
+    /// Network profile it was scheduled on; empty for prompts scheduled
+    /// before networks were recorded
+    #[serde(default)]
+    pub network: String,
+    pub agent_address: String,
+    pub prompt: String,
+    pub when: When,
+}
+
+fn path() -> PathBuf {
+    crate::config::data_dir().join("scheduled.json")
+}
+
+/// The lock file on the list at `path`, removed when dropped.
+struct ListLock(PathBuf);
+
+impl ListLock {
+    /// Create the lock file next to `path`, waiting up to two seconds for
+    /// another process to let go of it.
+    fn acquire(path: &Path) -> Result<Self> {
+        let lock = path.with_extension("json.lock");
+        if let Some(parent) = lock.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        for _ in 0..200 {
+            match std::fs::OpenOptions::new().write(true).create_new(true).open(&lock) {
+                Ok(_) => return Ok(Self(lock)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let age = std::fs::metadata(&lock).and_then(|m| m.modified()).ok().and_then(|t| t.elapsed().ok());
+                    if age.is_some_and(|age| age > STALE_LOCK) {
+                        tracing::warn!("Removing the stale lock {}", lock.display());
+                        let _ = std::fs::remove_file(&lock);
+                    } else {
+                        std::thread::sleep(Duration::from_millis(10));
+                    }
+                }
+                Err(e) => return Err(e).with_context(|| format!("Cannot create {}", lock.display())),
+            }
+        }
+        bail!("{} is held by another lobster", lock.display())
+    }
+}
+
+impl Drop for ListLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Every prompt still waiting, in the order they were scheduled.
+pub fn load() -> Vec<ScheduledPrompt> {
+    load_from(&path())
+}
+
+fn load_from(path: &Path) -> Vec<ScheduledPrompt> {
+    let Ok(contents) = std::fs::read_to_string(path) else { return Vec::new() };
+    serde_json::from_str(&contents)
+        .inspect_err(|e| tracing::warn!("Ignoring unreadable scheduled prompts: {}", e))
+        .unwrap_or_default()
+}
+
+/// Replace the list whole, so a crash mid-write never leaves half of it.
+fn save(path: &Path, prompts: &[ScheduledPrompt]) -> Result<()> {
+    crate::secrets::write_private(path, serde_json::to_string_pretty(prompts)?.as_bytes())
+}
+
+/// Hold `prompt` until it is due.
+pub fn add(prompt: ScheduledPrompt) -> Result<()> {
+    add_to(&path(), prompt)
+}
+
+fn add_to(path: &Path, prompt: ScheduledPrompt) -> Result<()> {
+    let _lock = ListLock::acquire(path)?;
+    let mut prompts = load_from(path);
+    prompts.push(prompt);
+    save(path, &prompts)
+}
+
+/// Remove and return the prompts of `network` due at chain head `block` and
+/// Unix time `now`. They are off the list before anything is submitted, so
+/// a TUI and a daemon running side by side don't both send one. Prompts of
+/// other networks wait for a run on theirs.
+pub fn take_due(network: &str, block: Option<u64>, now: u64) -> Vec<ScheduledPrompt> {
+    take_due_from(&path(), network, block, now)
+}
+
+fn take_due_from(path: &Path, network: &str, block: Option<u64>, now: u64) -> Vec<ScheduledPrompt> {
+    let lock = match ListLock::acquire(path) {
+        Ok(lock) => lock,
+        Err(e) => {
+            // Tried again on the next poll
+            tracing::warn!("Cannot lock the scheduled prompts: {:#}", e);
+            return Vec::new();
+        }
+    };
+    let prompts = load_from(path);
+    // Whose wallet should sign a prompt without a network is unknown; it is dropped
+    let taken = |p: &ScheduledPrompt| p.when.is_due(block, now) && (p.network == network || p.network.is_empty());
+    if !prompts.iter().any(taken) {
+        return Vec::new();
+    }
+    let (due, waiting): (Vec<_>, Vec<_>) = prompts.into_iter().partition(taken);
+    if let Err(e) = save(path, &waiting) {
+        // Left on the list rather than submitted on every poll
+        tracing::warn!("Cannot update the scheduled prompts: {:#}", e);
+        return Vec::new();
+    }
+    drop(lock);
+    let (due, unknown): (Vec<_>, Vec<_>) = due.into_iter().partition(|p| p.network == network);
+    for prompt in unknown {
+        tracing::warn!(agent = prompt.agent_address, "Dropped a scheduled prompt without a network: {}", prompt.prompt);
+    }
+    due
+}
+
+/// Build, sign and submit `prompt` now; its run id and receipt.
+pub async fn submit(client: &ApiClient, wallet: &WalletConfig, prompt: &ScheduledPrompt) -> Result<(u64, SubmitResponse)> {
+    let keypair = wallet.keypair()?;
+    let build = client.build_call(&prompt.agent_address, &prompt.prompt, &wallet.public_key).await?;
+    let submitted = crate::extrinsic::sign_and_submit(client, &build, &keypair).await?;
+    let run_id = crate::extrinsic::parse_agent_call_queued_event(&submitted.events)
+        .context("Could not find AgentCallQueued event")?;
+    Ok((run_id, submitted))
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2026-10-17 09:00 UTC
+    const NOW: u64 = 1_792_227_600;
+
+    #[test]
+    fn test_parse_when() {
+        assert_eq!(When::parse("#1234", NOW), Ok(When::Block(1234)));
+        assert_eq!(When::parse("block 99", NOW), Ok(When::Block(99)));
+        assert_eq!(When::parse("+30m", NOW), Ok(When::Time(NOW + 1800)));
+        assert_eq!(When::parse("14:30", NOW), Ok(When::Time(NOW + 5 * 3600 + 1800)));
+        // Already past today, so tomorrow
+        assert_eq!(When::parse("08:00", NOW), Ok(When::Time(NOW + 23 * 3600)));
+        assert_eq!(When::parse("2026-10-18 09:00", NOW), Ok(When::Time(NOW + 86_400)));
+        assert!(When::parse("2026-10-16 09:00", NOW).is_err());
+        assert!(When::parse("+0m", NOW).is_err());
+        assert!(When::parse("soon", NOW).is_err());
+        assert_eq!(When::Time(NOW).label(), "2026-10-17 09:00 UTC");
+    }
+
+    #[test]
+    fn test_is_due() {
+        assert!(!When::Block(10).is_due(None, NOW));
+        assert!(!When::Block(10).is_due(Some(9), NOW));
+        assert!(When::Block(10).is_due(Some(10), NOW));
+        assert!(When::Time(NOW).is_due(None, NOW));
+        assert!(!When::Time(NOW + 1).is_due(Some(u64::MAX), NOW));
+    }
+
+    #[test]
+    fn test_take_due_by_network() {
+        let dir = std::env::temp_dir().join(format!("lobster-schedule-{}", std::process::id()));
+        let path = dir.join("scheduled.json");
+        let prompt = |network: &str, text: &str, when| ScheduledPrompt {
+            network: network.to_string(),
+            agent_address: "5Agent".to_string(),
+            prompt: text.to_string(),
+            when,
+        };
+        add_to(&path, prompt("local", "local now", When::Time(NOW))).unwrap();
+        add_to(&path, prompt("testnet", "testnet now", When::Time(NOW))).unwrap();
+        add_to(&path, prompt("local", "local later", When::Time(NOW + 60))).unwrap();
+        add_to(&path, prompt("", "from before networks", When::Block(1))).unwrap();
+
+        let taken = take_due_from(&path, "local", Some(1), NOW);
+        assert_eq!(taken, [prompt("local", "local now", When::Time(NOW))]);
+        let waiting: Vec<_> = load_from(&path).into_iter().map(|p| p.prompt).collect();
+        assert_eq!(waiting, ["testnet now", "local later"]);
+        assert!(!path.with_extension("json.lock").exists());
+
+        // Held by another process: nothing is taken
+        let lock = ListLock::acquire(&path).unwrap();
+        let started = std::time::Instant::now();
+        assert!(take_due_from(&path, "testnet", None, NOW).is_empty());
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        drop(lock);
+        assert_eq!(take_due_from(&path, "testnet", None, NOW).len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
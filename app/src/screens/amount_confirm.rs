@@ -0,0 +1,88 @@
+//! Typed confirmation for a transaction over a spending limit: nothing is
+//! signed until the amount is entered as shown.
+
+use crate::funds::{format_tokens, parse_tokens};
+use crate::screens::input::TextInput;
+use crate::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+/// What a key did to the confirmation.
+#[derive(Debug, PartialEq)]
+pub enum Confirm {
+    Pending,
+    Confirmed,
+    Cancelled,
+}
+
+pub struct AmountConfirm {
+    /// Planck about to be sent
+    pub amount: u128,
+    /// The limit it goes over
+    pub reason: String,
+    pub input: TextInput,
+    pub error: Option<String>,
+}
+
+impl AmountConfirm {
+    pub fn new(amount: u128, reason: String) -> Self {
+        Self { amount, reason, input: TextInput::new().max_chars(40), error: None }
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent, decimals: u8) -> Confirm {
+        let key = event.code;
+        match key {
+            _ if self.input.handle_key(event) => self.error = None,
+            KeyCode::Enter if parse_tokens(self.input.text(), decimals) == Some(self.amount) => return Confirm::Confirmed,
+            KeyCode::Enter => {
+                self.error = Some(format!("Type {} exactly to send it", format_tokens(self.amount, decimals)));
+                self.input.clear();
+            }
+            KeyCode::Esc => return Confirm::Cancelled,
+            _ => {}
+        }
+        Confirm::Pending
+    }
+
+    /// The limit, the input and a hint or error, for a screen's footer.
+    pub fn lines(&self, theme: &Theme, decimals: u8, symbol: &str) -> Vec<Line<'static>> {
+        let mut input = vec![Span::styled(
+            format!("Type {} to send it: ", format_tokens(self.amount, decimals)),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )];
+        input.extend(self.input.line(true, 24, Style::default().fg(theme.text)).spans);
+        input.push(Span::styled(format!(" {}", symbol), Style::default().fg(theme.muted)));
+        let last = match &self.error {
+            Some(error) => Span::styled(error.clone(), Style::default().fg(theme.error)),
+            None => Span::styled("[Enter] Sign  [Esc] Cancel", Style::default().fg(theme.muted)),
+        };
+        vec![
+            Line::from(Span::styled(format!("{} {}", theme.warn_mark(), self.reason), Style::default().fg(theme.warning))),
+            Line::from(input),
+            Line::from(last),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_must_be_typed() {
+        let mut confirm = AmountConfirm::new(12_500_000_000_000, "over the limit".to_string());
+        for c in "125".chars() {
+            confirm.handle_key(KeyCode::Char(c).into(), 12);
+        }
+        assert_eq!(confirm.handle_key(KeyCode::Enter.into(), 12), Confirm::Pending);
+        assert_eq!(confirm.error.as_deref(), Some("Type 12.5 exactly to send it"));
+        for c in "12.50".chars() {
+            confirm.handle_key(KeyCode::Char(c).into(), 12);
+        }
+        assert_eq!(confirm.handle_key(KeyCode::Enter.into(), 12), Confirm::Confirmed);
+        assert_eq!(confirm.handle_key(KeyCode::Esc.into(), 12), Confirm::Cancelled);
+    }
+}
@@ -0,0 +1,154 @@
+//! Passphrase dialog for an encrypted backup, opened from the palette.
+
+use crate::app::App;
+use crate::screens::input::TextInput;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+/// Shortest passphrase accepted; the backup holds the wallet's mnemonic.
+const MIN_PASSPHRASE: usize = 8;
+
+/// What a key did to the dialog.
+#[derive(Debug, PartialEq)]
+pub enum BackupKey {
+    None,
+    Close,
+    /// Write the backup, sealed with this passphrase
+    Export(String),
+}
+
+pub struct BackupDialog {
+    pub passphrase: TextInput,
+    pub confirm: TextInput,
+    /// Focus is on the confirmation
+    pub confirming: bool,
+    pub error: Option<String>,
+    /// The backup is being written
+    pub busy: bool,
+}
+
+impl BackupDialog {
+    pub fn new() -> Self {
+        Self {
+            passphrase: TextInput::new().masked(),
+            confirm: TextInput::new().masked(),
+            confirming: false,
+            error: None,
+            busy: false,
+        }
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> BackupKey {
+        let key = event.code;
+        if self.busy {
+            return BackupKey::None;
+        }
+        let input = if self.confirming { &mut self.confirm } else { &mut self.passphrase };
+        match key {
+            _ if input.handle_key(event) => {}
+            KeyCode::Esc => return BackupKey::Close,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => self.confirming = !self.confirming,
+            KeyCode::Enter if !self.confirming => self.confirming = true,
+            KeyCode::Enter => {
+                if self.passphrase.char_count() < MIN_PASSPHRASE {
+                    self.error = Some(format!("Use a passphrase of at least {} characters", MIN_PASSPHRASE));
+                    self.confirming = false;
+                } else if self.passphrase.text() != self.confirm.text() {
+                    self.error = Some("The passphrases don't match".to_string());
+                    self.confirm.clear();
+                } else {
+                    self.error = None;
+                    self.busy = true;
+                    return BackupKey::Export(self.passphrase.text().to_string());
+                }
+            }
+            _ => {}
+        }
+        BackupKey::None
+    }
+}
+
+/// Draw the dialog centered on top of the current screen.
+pub fn render_backup_dialog(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(dialog) = &app.backup_dialog else {
+        return;
+    };
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(theme.muted));
+    let field = Style::default().fg(theme.text);
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Wallets, config, agent bindings and run history, in one file for `lobster restore`.",
+            Style::default().fg(theme.text),
+        )),
+        Line::from(""),
+        Line::from(label("Passphrase")),
+        dialog.passphrase.line(!dialog.confirming && !dialog.busy, 40, field),
+        Line::from(label("Again")),
+        dialog.confirm.line(dialog.confirming && !dialog.busy, 40, field),
+        Line::from(""),
+    ];
+    lines.push(match (&dialog.error, dialog.busy) {
+        (_, true) => Line::from(Span::styled("Encrypting…", Style::default().fg(theme.warning))),
+        (Some(error), false) => Line::from(Span::styled(error.clone(), Style::default().fg(theme.error))),
+        (None, false) => Line::from(Span::styled(
+            "[Enter] Export  [Tab] Switch field  [Ctrl+R] Show  [Esc] Cancel",
+            Style::default().fg(theme.muted),
+        )),
+    });
+
+    let width = area.width.min(64);
+    let height = 14.min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let body = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(Span::styled(" Export encrypted backup ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)))
+            .padding(Padding::uniform(1)));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(body, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(dialog: &mut BackupDialog, text: &str) {
+        for c in text.chars() {
+            dialog.handle_key(KeyCode::Char(c).into());
+        }
+    }
+
+    #[test]
+    fn test_backup_dialog_checks_passphrase() {
+        let mut dialog = BackupDialog::new();
+        type_text(&mut dialog, "short");
+        dialog.handle_key(KeyCode::Enter.into());
+        assert_eq!(dialog.handle_key(KeyCode::Enter.into()), BackupKey::None);
+        assert!(dialog.error.as_deref().unwrap().contains("at least"));
+
+        type_text(&mut dialog, " but fine");
+        dialog.handle_key(KeyCode::Tab.into());
+        type_text(&mut dialog, "short but fin");
+        assert_eq!(dialog.handle_key(KeyCode::Enter.into()), BackupKey::None);
+        assert_eq!(dialog.error.as_deref(), Some("The passphrases don't match"));
+
+        type_text(&mut dialog, "short but fine");
+        assert_eq!(dialog.handle_key(KeyCode::Enter.into()), BackupKey::Export("short but fine".to_string()));
+        assert!(dialog.busy);
+        assert_eq!(dialog.handle_key(KeyCode::Esc.into()), BackupKey::None);
+    }
+}
@@ -0,0 +1,302 @@
+//! New post screen - publish directly as the agent via its Moltbook API key.
+
+use crate::{
+    app::{App, AppMessage, MessageContext, ScreenAction},
+    client::ApiClient,
+    notifications::Level,
+    screens::{input::TextInput, Screen},
+    theme::Theme,
+};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+/// Submolt new posts go to unless the user picks another.
+const DEFAULT_SUBMOLT: &str = "general";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComposeField {
+    Submolt,
+    Title,
+    Body,
+    Publish,
+}
+
+impl ComposeField {
+    fn next(self) -> Self {
+        match self {
+            ComposeField::Submolt => ComposeField::Title,
+            ComposeField::Title => ComposeField::Body,
+            ComposeField::Body => ComposeField::Publish,
+            ComposeField::Publish => ComposeField::Submolt,
+        }
+    }
+
+    fn prev(self) -> Self {
+        match self {
+            ComposeField::Submolt => ComposeField::Publish,
+            ComposeField::Title => ComposeField::Submolt,
+            ComposeField::Body => ComposeField::Title,
+            ComposeField::Publish => ComposeField::Body,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComposeStep {
+    Editing,
+    Publishing,
+    Published { post_id: String },
+}
+
+/// Background results for the composer.
+#[derive(Debug, Clone)]
+pub enum ComposeMessage {
+    /// Post published from the composer
+    PostPublished { post_id: String },
+    /// Publishing from the composer failed
+    PostPublishFailed(String),
+}
+
+pub struct ComposeScreen {
+    pub step: ComposeStep,
+    pub focus: ComposeField,
+    pub submolt: TextInput,
+    pub title: TextInput,
+    pub body: TextInput,
+    pub error: Option<String>,
+}
+
+impl ComposeScreen {
+    pub fn new() -> Self {
+        Self {
+            step: ComposeStep::Editing,
+            focus: ComposeField::Title,
+            submolt: TextInput::with_text(DEFAULT_SUBMOLT),
+            title: TextInput::new(),
+            body: TextInput::new(),
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn handle_key(
+        &mut self,
+        event: KeyEvent,
+        client: &ApiClient,
+        api_key: Option<&str>,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> Result<ScreenAction> {
+        let key = event.code;
+        match self.step {
+            ComposeStep::Editing => {}
+            ComposeStep::Publishing => return Ok(ScreenAction::None),
+            ComposeStep::Published { .. } => {
+                return Ok(match key {
+                    KeyCode::Enter | KeyCode::Esc => ScreenAction::Pop,
+                    KeyCode::Char('n') => {
+                        self.reset();
+                        ScreenAction::None
+                    }
+                    _ => ScreenAction::None,
+                });
+            }
+        }
+
+        self.error = None;
+        match key {
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            KeyCode::Tab | KeyCode::Down => self.focus = self.focus.next(),
+            KeyCode::BackTab | KeyCode::Up => self.focus = self.focus.prev(),
+            KeyCode::Enter => match self.focus {
+                // Body is multi-line; other fields move on
+                ComposeField::Body => self.body.insert_str("\n"),
+                ComposeField::Publish => self.publish(client, api_key, tx),
+                _ => self.focus = self.focus.next(),
+            },
+            _ => {
+                if let Some(field) = self.focused_text() {
+                    field.handle_key(event);
+                }
+            }
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn focused_text(&mut self) -> Option<&mut TextInput> {
+        match self.focus {
+            ComposeField::Submolt => Some(&mut self.submolt),
+            ComposeField::Title => Some(&mut self.title),
+            ComposeField::Body => Some(&mut self.body),
+            ComposeField::Publish => None,
+        }
+    }
+
+    fn publish(&mut self, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        let Some(api_key) = api_key.map(str::to_string) else {
+            self.error = Some("No Moltbook API key stored for this agent".to_string());
+            return;
+        };
+        let submolt = self.submolt.text().trim().trim_start_matches("m/").to_string();
+        let title = self.title.text().trim().to_string();
+        let body = self.body.text().trim().to_string();
+        if submolt.is_empty() {
+            self.error = Some("Submolt is required".to_string());
+            self.focus = ComposeField::Submolt;
+            return;
+        }
+        if title.is_empty() {
+            self.error = Some("Title is required".to_string());
+            self.focus = ComposeField::Title;
+            return;
+        }
+
+        self.step = ComposeStep::Publishing;
+        let client = client.clone();
+        crate::tasks::spawn_operation("a post", async move {
+            match client.create_post(&api_key, &submolt, &title, &body).await {
+                Ok(post) => {
+                    let _ = tx.send(ComposeMessage::PostPublished { post_id: post.id }.into()).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(ComposeMessage::PostPublishFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
+    }
+
+    pub fn handle_published(&mut self, post_id: String) {
+        self.step = ComposeStep::Published { post_id };
+    }
+
+    pub fn handle_publish_failed(&mut self, error: String) {
+        // Keep the draft so the user can retry
+        self.step = ComposeStep::Editing;
+        self.error = Some(error);
+    }
+
+    pub fn handle_message(&mut self, msg: ComposeMessage, ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            ComposeMessage::PostPublished { post_id } => {
+                ctx.notify(Level::Success, "Post published");
+                self.handle_published(post_id);
+            }
+            ComposeMessage::PostPublishFailed(e) => self.handle_publish_failed(e),
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn field_block(&self, field: ComposeField, title: &'static str, theme: &Theme) -> Block<'static> {
+        let color = if self.focus == field {
+            theme.accent
+        } else {
+            theme.muted
+        };
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color))
+            .title(Span::styled(title, Style::default().fg(theme.text)))
+    }
+}
+
+impl Screen for ComposeScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Length(3), // Submolt
+                Constraint::Length(3), // Post title
+                Constraint::Min(5),    // Body
+                Constraint::Length(3), // Publish button
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let agent = app.agent_name().unwrap_or("agent");
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" NEW POST ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(format!("Posting as {}", agent), Style::default().fg(theme.accent)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        let focused = |field: ComposeField| self.focus == field;
+        let width = chunks[1].width.saturating_sub(2) as usize;
+
+        let mut submolt = self.submolt.line(focused(ComposeField::Submolt), width.saturating_sub(2), Style::default().fg(theme.info));
+        submolt.spans.insert(0, Span::styled("m/", Style::default().fg(theme.muted)));
+        let submolt = Paragraph::new(submolt)
+        .block(self.field_block(ComposeField::Submolt, " Submolt ", theme));
+        frame.render_widget(submolt, chunks[1]);
+
+        let post_title = Paragraph::new(self.title.line(focused(ComposeField::Title), width, Style::default().fg(theme.text)))
+        .block(self.field_block(ComposeField::Title, " Title ", theme));
+        frame.render_widget(post_title, chunks[2]);
+
+        // Keep the end of a long body in view while typing
+        let body_lines = self.body.lines(focused(ComposeField::Body), Style::default().fg(theme.text));
+        let visible = chunks[3].height.saturating_sub(2) as usize;
+        let scroll = body_lines.len().saturating_sub(visible) as u16;
+        let body = Paragraph::new(body_lines)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0))
+            .block(self.field_block(ComposeField::Body, " Body ", theme));
+        frame.render_widget(body, chunks[3]);
+
+        let (button_text, button_color) = match &self.step {
+            ComposeStep::Editing => ("[ Publish ]".to_string(), theme.accent),
+            ComposeStep::Publishing => (format!("{} Publishing...", app.spinner()), theme.warning),
+            ComposeStep::Published { post_id } => (format!("✓ Published (post {})", post_id), theme.success),
+        };
+        let mut button_style = Style::default().fg(button_color);
+        if self.focus == ComposeField::Publish {
+            button_style = button_style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        }
+        let button = Paragraph::new(Span::styled(button_text, button_style))
+            .alignment(Alignment::Center)
+            .block(self.field_block(ComposeField::Publish, "", theme));
+        frame.render_widget(button, chunks[4]);
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else if matches!(self.step, ComposeStep::Published { .. }) {
+            Line::from(vec![
+                Span::styled("[N] ", Style::default().fg(theme.muted)),
+                Span::styled("New post", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter/Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Home", Style::default().fg(theme.muted)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[Tab] ", Style::default().fg(theme.muted)),
+                Span::styled("Next field", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Newline / Publish", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Cancel", Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[5]);
+    }
+}
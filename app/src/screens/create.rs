@@ -1,22 +1,37 @@
 //! Create agent wizard screen.
 
 use crate::{
-    agent_assets::{AgentSource, FileStatus, ValidationResult},
-    app::{App, AppMessage, ScreenAction},
-    client::ApiClient,
+    agent_assets::{AgentSource, FileIssue, FileStatus, ValidationResult},
+    artifact::{self, Artifact},
+    app::{App, AppMessage, MessageContext, ScreenAction},
+    client::{ApiClient, SubmitResponse},
     extrinsic,
-    screens::Screen,
+    funds::{format_tokens, parse_tokens, Funds},
+    git_source::GitSource,
+    moltbook::{MAX_AGENT_DESCRIPTION_LEN, MAX_AGENT_NAME_LEN},
+    notifications::Level,
+    screens::{
+        amount_confirm::{AmountConfirm, Confirm},
+        focus::{self, FocusRing},
+        input::{self, NumberInput, SelectList, TextInput},
+        network_badge, receipt, Screen,
+    },
+    secrets,
+    theme::Theme,
+    versions::{self, DeployedVersion},
     wallet::WalletConfig,
+    webhook::{self, WebhookEvent},
 };
-use anyhow::Result;
-use crossterm::event::KeyCode;
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
+use std::path::PathBuf;
 use tokio::sync::mpsc;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,6 +50,10 @@ pub enum CreateStep {
     ConfigureSchedule,
     /// Compiling
     Compiling,
+    /// Compiled with warnings, waiting to deploy anyway or go back
+    CompileWarnings,
+    /// Everything the deploy will do, waiting for the confirm keypress
+    ReviewDeploy,
     /// Deploying
     Deploying,
     /// Success
@@ -42,18 +61,138 @@ pub enum CreateStep {
 }
 
 /// Which field is currently active in the agent info form
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AgentInfoField {
     Name,
     Description,
     ApiKey,
 }
 
+/// Background results for the wizard.
+#[derive(Debug, Clone)]
+pub enum CreateMessage {
+    /// Moltbook registered (from direct TUI call to Moltbook API)
+    MoltbookRegistered { api_key: String, claim_url: String, verification_code: String },
+    /// Moltbook registration failed (any error)
+    RegistrationFailed(String),
+    /// Agent name already taken - need to choose different name
+    NameTaken(String),
+    /// Existing API key validated - got agent info
+    ApiKeyValidated { api_key: String, name: String, description: String, is_claimed: bool },
+    /// API key validation failed
+    ApiKeyInvalid(String),
+    /// What the clipboard held when the API key field got focus, if it
+    /// looked like a Moltbook API key
+    ClipboardKey(Option<String>),
+    /// Ready to store agent with existing API key (skip registration)
+    ApiKeyReadyToStore { api_key: String, name: String },
+    /// Moltbook claimed - agent stored on server
+    MoltbookClaimed { agent_id: String },
+    /// Claim check finished without the agent being stored
+    ClaimCheckFailed(String),
+    /// Automatic claim check found the agent not claimed yet
+    ClaimPending,
+    /// Compilation done
+    CompileDone { compiled_hex: String, warnings: Vec<String> },
+    /// Compilation failed
+    CompileFailed(String),
+    /// Fee of the deploy transaction in planck, or why it is unknown
+    DeployFeeEstimated(Result<u128, String>),
+    /// Deployment done
+    DeployDone { agent_address: String, receipt: SubmitResponse },
+    /// Deployment failed
+    DeployFailed(String),
+    /// Agent source selected (embedded or custom dir)
+    AgentSourceSelected { custom_dir: Option<String> },
+    /// A git source was cloned; `input` is what was typed for it
+    GitCloned { input: String },
+    GitCloneFailed(String),
+    /// A file in the watched custom agent directory changed
+    AgentFilesChanged,
+}
+
+/// Wizard progress kept across restarts once an agent is registered on
+/// Moltbook, since its API key can't be fetched again.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreateDraft {
+    pub agent_name: String,
+    pub agent_description: String,
+    pub moltbook_api_key: String,
+    pub claim_url: Option<String>,
+    pub verification_code: Option<String>,
+    /// Set once the claim was stored on the server
+    pub agent_id: Option<String>,
+}
+
+impl CreateDraft {
+    fn path() -> std::path::PathBuf {
+        crate::config::config_dir().join("create_draft.json")
+    }
+
+    /// Write the draft readable by the owner only, with the API key sealed
+    /// when `encrypt` (the config's `encrypt_secrets`) is on.
+    pub fn save(&self, encrypt: bool) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut draft = self.clone();
+        if encrypt {
+            draft.moltbook_api_key = crate::secrets::seal(&self.moltbook_api_key).context("Cannot encrypt the draft's API key")?;
+        }
+        crate::secrets::write_private(&path, serde_json::to_string_pretty(&draft)?.as_bytes())
+    }
+
+    /// Load the saved draft and remove the file, so it is resumed once. A
+    /// sealed API key is opened with the run's passphrase; with `encrypt`
+    /// off there is none, so the file is left for a run that has it.
+    pub fn take(encrypt: bool) -> Option<Self> {
+        let path = Self::path();
+        let contents = std::fs::read_to_string(&path).ok()?;
+        let mut draft: Self = match serde_json::from_str(&contents) {
+            Ok(draft) => draft,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable wizard draft: {}", e);
+                let _ = std::fs::remove_file(&path);
+                return None;
+            }
+        };
+        if crate::secrets::is_sealed(&draft.moltbook_api_key) {
+            if !encrypt {
+                tracing::warn!("The wizard draft's API key is encrypted; turn encrypt_secrets back on to resume it");
+                return None;
+            }
+            match crate::secrets::open(&draft.moltbook_api_key) {
+                Ok(key) => draft.moltbook_api_key = key,
+                Err(e) => {
+                    tracing::warn!("Cannot decrypt the wizard draft's API key: {:#}", e);
+                    return None;
+                }
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+        Some(draft)
+    }
+
+    pub fn clear() {
+        let _ = std::fs::remove_file(Self::path());
+    }
+}
+
 /// 1 UNIT = 1_000_000_000_000 planck (12 decimals)
-const UNIT_PLANCK: u128 = 1_000_000_000_000;
+pub const UNIT_PLANCK: u128 = 1_000_000_000_000;
+/// Decimals the balance field takes while typing; the network's own are
+/// checked on Enter, so too many is an error rather than a dropped digit.
+const BALANCE_INPUT_DECIMALS: u8 = 18;
+
+/// Never, every 30 minutes, hour or 2 hours, or custom.
+const SCHEDULE_CHOICES: usize = 5;
+
+/// Lint findings listed under the files before the rest are summarized.
+const MAX_ISSUE_LINES: usize = 6;
 
 /// Which field is active in the schedule/balance form
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ScheduleField {
     Schedule,
     CustomMinutes,
@@ -64,30 +203,60 @@ pub struct CreateScreen {
     pub step: CreateStep,
     // Agent source selection
     pub use_embedded: bool,
-    pub custom_dir_input: String,
+    pub custom_dir_input: TextInput,
     pub source_validation: Option<ValidationResult>,
+    /// A git source is being cloned
+    pub cloning: bool,
+    /// Git source cloned this session, as typed
+    pub cloned: Option<String>,
     // Agent info
-    pub agent_name: String,
-    pub agent_description: String,
-    pub api_key_input: String,
-    pub active_field: AgentInfoField,
+    pub agent_name: TextInput,
+    pub agent_description: TextInput,
+    pub api_key_input: TextInput,
+    pub info_focus: FocusRing<AgentInfoField>,
     pub name_error: Option<String>,
     pub api_key_error: Option<String>,
     pub api_key_status: Option<String>,
+    /// API key found on the clipboard, offered for pasting with Ctrl+V
+    pub clipboard_key: Option<String>,
+    /// The deposit is over a spending limit and waits for its amount typed
+    pub amount_confirm: Option<AmountConfirm>,
     pub agent_id: Option<String>,
     pub moltbook_api_key: Option<String>,
     pub claim_url: Option<String>,
     pub verification_code: Option<String>,
+    /// Claim status check in flight
+    pub checking_claim: bool,
     pub schedule_option: Option<u32>,
     pub compiled_hex: Option<String>,
+    /// Where compiles are saved and reused from
+    pub artifacts_dir: PathBuf,
+    /// Compiler warnings of the last compile
+    pub compile_warnings: Vec<String>,
+    /// First warning line shown
+    pub warnings_scroll: u16,
+    /// Estimated deploy fee in planck for the review step; None while it is
+    /// being estimated
+    pub deploy_fee: Option<Result<u128, String>>,
     pub agent_address: Option<String>,
     pub error: Option<String>,
-    pub selected_schedule: usize,
-    pub custom_minutes_input: String,
-    pub balance_input: String,
+    /// Index into the schedule choices (0=Never, 1=30min, 2=1h, 3=2h, 4=Custom)
+    pub selected_schedule: SelectList,
+    pub custom_minutes_input: NumberInput,
+    pub balance_input: NumberInput,
     pub balance_error: Option<String>,
-    pub schedule_field: ScheduleField,
+    pub schedule_focus: FocusRing<ScheduleField>,
     pub value_planck: u128,
+    /// Review step shows moltbook_agent.ship instead of SOUL.md
+    pub show_program: bool,
+    /// First program line shown in the review step
+    pub program_scroll: u16,
+    /// Test tokens were requested; deploying waits until they arrive
+    pub waiting_for_tokens: bool,
+    /// Block and events of the deploy transaction
+    pub receipt: Option<SubmitResponse>,
+    /// Explorer page of the deploy's block
+    pub receipt_link: Option<String>,
 }
 
 impl CreateScreen {
@@ -96,30 +265,48 @@ impl CreateScreen {
             step: CreateStep::SelectAgentSource,
             // Agent source - default to embedded
             use_embedded: true,
-            custom_dir_input: String::new(),
+            custom_dir_input: TextInput::new(),
             source_validation: None,
+            cloning: false,
+            cloned: None,
             // Agent info
-            agent_name: String::new(),
-            agent_description: String::new(),
-            api_key_input: String::new(),
-            active_field: AgentInfoField::Name,
+            agent_name: TextInput::new().max_chars(MAX_AGENT_NAME_LEN),
+            agent_description: TextInput::new().max_chars(MAX_AGENT_DESCRIPTION_LEN),
+            api_key_input: TextInput::new().masked(),
+            info_focus: FocusRing::new([AgentInfoField::Name, AgentInfoField::Description, AgentInfoField::ApiKey]),
             name_error: None,
             api_key_error: None,
             api_key_status: None,
+            clipboard_key: None,
+            amount_confirm: None,
             agent_id: None,
             moltbook_api_key: None,
             claim_url: None,
             verification_code: None,
+            checking_claim: false,
             schedule_option: Some(600), // Default: 1 hour (600 blocks)
             compiled_hex: None,
+            artifacts_dir: artifact::artifacts_dir(),
+            compile_warnings: Vec::new(),
+            warnings_scroll: 0,
+            deploy_fee: None,
             agent_address: None,
             error: None,
-            selected_schedule: 2, // Index 2 = "1 hour" (0=Never, 1=30min, 2=1h, 3=2h, 4=Custom)
-            custom_minutes_input: String::new(),
-            balance_input: String::new(),
+            selected_schedule: {
+                let mut choice = SelectList::new();
+                choice.select(2, SCHEDULE_CHOICES); // 1 hour
+                choice
+            },
+            custom_minutes_input: NumberInput::new(0).min(1).max(u32::MAX.into()),
+            balance_input: NumberInput::new(BALANCE_INPUT_DECIMALS),
             balance_error: None,
-            schedule_field: ScheduleField::Schedule,
+            schedule_focus: FocusRing::new([ScheduleField::Schedule, ScheduleField::Balance]),
             value_planck: UNIT_PLANCK, // Default: 1 UNIT
+            show_program: false,
+            program_scroll: 0,
+            waiting_for_tokens: false,
+            receipt: None,
+            receipt_link: None,
         }
     }
 
@@ -128,7 +315,7 @@ impl CreateScreen {
         let mut screen = Self::new();
         if let Some(dir) = custom_agent_dir {
             screen.use_embedded = false;
-            screen.custom_dir_input = dir;
+            screen.custom_dir_input.set_text(dir);
         }
         screen
     }
@@ -136,54 +323,160 @@ impl CreateScreen {
     pub fn reset(&mut self) {
         // Preserve the agent source selection
         let use_embedded = self.use_embedded;
-        let custom_dir = self.custom_dir_input.clone();
+        let custom_dir = std::mem::take(&mut self.custom_dir_input);
+        let artifacts_dir = std::mem::take(&mut self.artifacts_dir);
         *self = Self::new();
         self.use_embedded = use_embedded;
         self.custom_dir_input = custom_dir;
+        self.artifacts_dir = artifacts_dir;
     }
 
-    /// Get the current agent source based on selection.
+    /// Progress worth keeping: registered on Moltbook but not yet deployed.
+    pub fn draft(&self) -> Option<CreateDraft> {
+        if matches!(self.step, CreateStep::Success | CreateStep::SelectAgentSource) {
+            return None;
+        }
+        Some(CreateDraft {
+            agent_name: self.agent_name.text().to_string(),
+            agent_description: self.agent_description.text().to_string(),
+            moltbook_api_key: self.moltbook_api_key.clone()?,
+            claim_url: self.claim_url.clone(),
+            verification_code: self.verification_code.clone(),
+            agent_id: self.agent_id.clone(),
+        })
+    }
+
+    /// Pick up where `draft` left off: the claim check, or the review once claimed.
+    pub fn resume(&mut self, draft: CreateDraft) {
+        self.agent_name.set_text(draft.agent_name);
+        self.agent_description.set_text(draft.agent_description);
+        self.moltbook_api_key = Some(draft.moltbook_api_key);
+        self.claim_url = draft.claim_url;
+        self.verification_code = draft.verification_code;
+        self.step = if draft.agent_id.is_some() { CreateStep::ReviewSoul } else { CreateStep::WaitingClaim };
+        self.agent_id = draft.agent_id;
+    }
+
+    /// Get the current agent source based on selection. A git URL reads
+    /// from its checkout.
     pub fn agent_source(&self) -> AgentSource {
         if self.use_embedded {
-            AgentSource::Embedded
-        } else {
-            AgentSource::Custom(self.custom_dir_input.clone())
+            return AgentSource::Embedded;
         }
+        match self.git_source() {
+            Some(git) => AgentSource::Custom(git.agent_dir().to_string_lossy().to_string()),
+            None => AgentSource::Custom(self.custom_dir_input.text().to_string()),
+        }
+    }
+
+    /// The git repository typed in place of a directory, if it is one.
+    pub fn git_source(&self) -> Option<GitSource> {
+        (!self.use_embedded).then(|| GitSource::parse(self.custom_dir_input.text())).flatten()
     }
 
-    /// Validate the current agent source and cache the result.
+    /// Validate the current agent source and cache the result. A git source
+    /// isn't judged until there is a checkout to look at.
     pub fn validate_source(&mut self) {
+        if self.git_source().is_some_and(|git| !git.agent_dir().is_dir()) {
+            self.source_validation = None;
+            return;
+        }
         let source = self.agent_source();
         self.source_validation = Some(source.validate());
     }
 
+    /// Where the files come from, for the records: "embedded", the custom
+    /// directory or the git URL as typed.
+    fn source_label(&self) -> String {
+        match self.git_source() {
+            Some(_) => self.custom_dir_input.text().trim().to_string(),
+            None => versions::source_label(&self.agent_source()),
+        }
+    }
+
+    /// Shallow-clone the git source; the files are validated once it lands.
+    fn start_clone(&mut self, git: GitSource, tx: mpsc::Sender<AppMessage>) {
+        self.cloning = true;
+        self.error = None;
+        let input = self.custom_dir_input.text().to_string();
+        crate::tasks::spawn(async move {
+            let msg = match git.clone_fresh().await {
+                Ok(_) => CreateMessage::GitCloned { input },
+                Err(e) => CreateMessage::GitCloneFailed(format!("{:#}", e)),
+            };
+            let _ = tx.send(msg.into()).await;
+        });
+    }
+
+    /// Go on to the agent details when the files are valid, remembering the
+    /// source for next time.
+    fn confirm_source(&mut self, tx: mpsc::Sender<AppMessage>) {
+        self.validate_source();
+        let Some(validation) = &self.source_validation else { return };
+        if !validation.is_valid() {
+            self.error = validation.problem();
+            return;
+        }
+        self.step = CreateStep::EnterAgentInfo;
+        self.error = None;
+
+        // Save the selection to config
+        let custom_dir = if self.use_embedded {
+            None
+        } else {
+            Some(self.custom_dir_input.text().to_string())
+        };
+        crate::tasks::spawn(async move {
+            let _ = tx.send(CreateMessage::AgentSourceSelected { custom_dir }.into()).await;
+        });
+    }
+
+    /// Files in the custom directory changed on disk; the SOUL.md preview
+    /// re-reads on every draw, so only the validation needs refreshing.
+    pub fn handle_files_changed(&mut self) {
+        if !self.use_embedded {
+            self.validate_source();
+        }
+    }
+
     pub async fn handle_key(
         &mut self,
-        key: KeyCode,
+        event: KeyEvent,
         client: &ApiClient,
         _agent_dir: &str,
+        funds: &Funds,
+        wallet: Option<&WalletConfig>,
         tx: mpsc::Sender<AppMessage>,
     ) -> Result<ScreenAction> {
+        let key = event.code;
         match self.step {
-            CreateStep::SelectAgentSource => self.handle_select_source_key(key, tx.clone()),
-            CreateStep::EnterAgentInfo => self.handle_agent_info_key(key, tx).await,
+            CreateStep::SelectAgentSource => self.handle_select_source_key(event, tx.clone()),
+            CreateStep::EnterAgentInfo => self.handle_agent_info_key(event, client, tx).await,
             CreateStep::WaitingClaim => {
                 self.handle_waiting_claim_key(key, client.clone(), tx).await
             }
             CreateStep::ReviewSoul => self.handle_review_soul_key(key),
             CreateStep::ConfigureSchedule => {
-                self.handle_configure_schedule_key(key, client.clone(), tx)
+                self.handle_configure_schedule_key(event, client.clone(), funds, tx)
                     .await
             }
+            CreateStep::CompileWarnings => Ok(self.handle_compile_warnings_key(key, client, wallet, tx)),
+            CreateStep::ReviewDeploy => Ok(self.handle_review_deploy_key(event, client, funds, wallet, tx)),
             CreateStep::Success => {
                 if key == KeyCode::Enter || key == KeyCode::Esc {
                     return Ok(ScreenAction::GoHome);
                 }
+                if let (KeyCode::Char('o'), Some(url)) = (key, &self.receipt_link) {
+                    if let Err(e) = open::that(url) {
+                        tracing::warn!(%url, "Could not open the explorer: {}", e);
+                        self.error = Some(format!("Could not open browser: {}", e));
+                    }
+                }
                 Ok(ScreenAction::None)
             }
             _ => {
                 if key == KeyCode::Esc {
-                    return Ok(ScreenAction::GoHome);
+                    return Ok(ScreenAction::Pop);
                 }
                 Ok(ScreenAction::None)
             }
@@ -192,9 +485,13 @@ impl CreateScreen {
 
     fn handle_select_source_key(
         &mut self,
-        key: KeyCode,
+        event: KeyEvent,
         tx: mpsc::Sender<AppMessage>,
     ) -> Result<ScreenAction> {
+        let key = event.code;
+        if self.cloning && key != KeyCode::Esc {
+            return Ok(ScreenAction::None);
+        }
         match key {
             KeyCode::Up | KeyCode::Down => {
                 // Toggle between embedded and custom
@@ -203,167 +500,118 @@ impl CreateScreen {
                 // Re-validate when switching
                 self.validate_source();
             }
-            KeyCode::Tab => {
+            KeyCode::Tab if self.use_embedded => {
                 // Switch to custom if on embedded, otherwise do nothing special
-                if self.use_embedded {
-                    self.use_embedded = false;
-                    self.validate_source();
-                }
-            }
-            KeyCode::Char(c) => {
-                if !self.use_embedded {
-                    self.custom_dir_input.push(c);
-                    self.error = None;
-                    // Validate as user types
-                    self.validate_source();
-                }
-            }
-            KeyCode::Backspace => {
-                if !self.use_embedded {
-                    self.custom_dir_input.pop();
-                    self.error = None;
-                    self.validate_source();
-                }
+                self.use_embedded = false;
+                self.validate_source();
             }
-            KeyCode::Enter => {
-                // Validate before proceeding
+            _ if !self.use_embedded && self.custom_dir_input.handle_key(event) => {
+                self.error = None;
+                // Validate as user types
                 self.validate_source();
-
-                if let Some(ref validation) = self.source_validation {
-                    if validation.is_valid() {
-                        self.step = CreateStep::EnterAgentInfo;
-                        self.error = None;
-
-                        // Save the selection to config
-                        let custom_dir = if self.use_embedded {
-                            None
-                        } else {
-                            Some(self.custom_dir_input.clone())
-                        };
-                        let tx = tx.clone();
-                        tokio::spawn(async move {
-                            let _ = tx.send(AppMessage::AgentSourceSelected { custom_dir }).await;
-                        });
-                    } else {
-                        self.error = Some("moltbook_agent.ship is required".to_string());
-                    }
-                } else {
-                    // No validation yet, do it now
-                    self.validate_source();
-                    if let Some(ref validation) = self.source_validation {
-                        if validation.is_valid() {
-                            self.step = CreateStep::EnterAgentInfo;
-                            self.error = None;
-
-                            // Save the selection to config
-                            let custom_dir = if self.use_embedded {
-                                None
-                            } else {
-                                Some(self.custom_dir_input.clone())
-                            };
-                            let tx = tx.clone();
-                            tokio::spawn(async move {
-                                let _ = tx.send(AppMessage::AgentSourceSelected { custom_dir }).await;
-                            });
-                        } else {
-                            self.error = Some("moltbook_agent.ship is required".to_string());
-                        }
-                    }
-                }
             }
+            KeyCode::Enter => match self.git_source() {
+                // Clone on the first Enter, so each deploy gets what was pushed last
+                Some(git) if self.cloned.as_deref() != Some(self.custom_dir_input.text()) => self.start_clone(git, tx),
+                _ => self.confirm_source(tx),
+            },
             KeyCode::Esc => {
-                return Ok(ScreenAction::GoHome);
+                return Ok(ScreenAction::Pop);
             }
             _ => {}
         }
         Ok(ScreenAction::None)
     }
 
+    fn focused_info_input(&mut self) -> &mut TextInput {
+        match self.info_focus.current() {
+            AgentInfoField::Name => &mut self.agent_name,
+            AgentInfoField::Description => &mut self.agent_description,
+            AgentInfoField::ApiKey => &mut self.api_key_input,
+        }
+    }
+
     async fn handle_agent_info_key(
         &mut self,
-        key: KeyCode,
+        event: KeyEvent,
+        client: &ApiClient,
         tx: mpsc::Sender<AppMessage>,
     ) -> Result<ScreenAction> {
+        let key = event.code;
+        let was_on_key = self.info_focus.is_focused(AgentInfoField::ApiKey);
+        let clipboard_tx = tx.clone();
         match key {
-            KeyCode::Tab => {
-                // Cycle through fields: Name → Description → ApiKey → Name
-                self.active_field = match self.active_field {
-                    AgentInfoField::Name => AgentInfoField::Description,
-                    AgentInfoField::Description => AgentInfoField::ApiKey,
-                    AgentInfoField::ApiKey => AgentInfoField::Name,
-                };
-            }
-            KeyCode::Up => {
-                // Cycle backwards
-                self.active_field = match self.active_field {
-                    AgentInfoField::Name => AgentInfoField::ApiKey,
-                    AgentInfoField::Description => AgentInfoField::Name,
-                    AgentInfoField::ApiKey => AgentInfoField::Description,
-                };
+            // Name → Description → ApiKey → Name
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.info_focus.handle_key(key);
             }
-            KeyCode::Down => {
-                // Cycle forwards
-                self.active_field = match self.active_field {
-                    AgentInfoField::Name => AgentInfoField::Description,
-                    AgentInfoField::Description => AgentInfoField::ApiKey,
-                    AgentInfoField::ApiKey => AgentInfoField::Name,
-                };
-            }
-            KeyCode::Char(c) => match self.active_field {
-                AgentInfoField::Name => {
-                    self.agent_name.push(c);
-                    self.name_error = None;
-                }
-                AgentInfoField::Description => {
-                    self.agent_description.push(c);
-                }
-                AgentInfoField::ApiKey => {
-                    self.api_key_input.push(c);
+            KeyCode::Up => self.info_focus.prev(),
+            KeyCode::Down => self.info_focus.next(),
+            // Terminal paste is easy to get wrong, so a key found on the clipboard goes in with one keypress
+            KeyCode::Char('v') if input::control(event) && self.info_focus.is_focused(AgentInfoField::ApiKey) => {
+                if let Some(api_key) = self.clipboard_key.take() {
+                    self.api_key_input.set_text(api_key);
                     self.api_key_error = None;
                     self.api_key_status = None;
                 }
-            },
-            KeyCode::Backspace => match self.active_field {
-                AgentInfoField::Name => {
-                    self.agent_name.pop();
-                }
-                AgentInfoField::Description => {
-                    self.agent_description.pop();
-                }
+            }
+            // Name and description stop at the Moltbook limits rather than failing later
+            _ if self.focused_info_input().handle_key(event) => match self.info_focus.current() {
+                AgentInfoField::Name => self.name_error = None,
+                AgentInfoField::Description => {}
                 AgentInfoField::ApiKey => {
-                    self.api_key_input.pop();
                     self.api_key_error = None;
                     self.api_key_status = None;
                 }
             },
             KeyCode::Enter => {
                 // If in API key field with input but NOT yet validated, validate it
-                if self.active_field == AgentInfoField::ApiKey
+                if self.info_focus.is_focused(AgentInfoField::ApiKey)
                     && !self.api_key_input.is_empty()
                     && self.moltbook_api_key.is_none()
                 {
                     self.api_key_status = Some("Validating...".to_string());
                     self.api_key_error = None;
 
-                    let api_key = self.api_key_input.clone();
-                    tokio::spawn(async move {
-                        match crate::moltbook::get_agent_info(&api_key).await {
+                    let api_key = self.api_key_input.text().to_string();
+                    let client = client.clone();
+                    crate::tasks::spawn(async move {
+                        match client.get_moltbook_agent(&api_key).await {
                             Ok(info) => {
                                 let _ = tx
-                                    .send(AppMessage::ApiKeyValidated {
+                                    .send(CreateMessage::ApiKeyValidated {
                                         api_key,
                                         name: info.name,
                                         description: info.description,
                                         is_claimed: info.is_claimed,
-                                    })
+                                    }.into())
                                     .await;
                             }
                             Err(e) => {
-                                let _ = tx.send(AppMessage::ApiKeyInvalid(e.to_string())).await;
+                                let message = secrets::redact(&format!("{:#}", e), &api_key);
+                                let _ = tx.send(CreateMessage::ApiKeyInvalid(message).into()).await;
                             }
                         }
                     });
                 }
+                // Pasted or restored values can still exceed the limits
+                else if self.moltbook_api_key.is_none()
+                    && self.agent_name.char_count() > MAX_AGENT_NAME_LEN
+                {
+                    self.name_error = Some(format!(
+                        "Name must be at most {} characters",
+                        MAX_AGENT_NAME_LEN
+                    ));
+                    self.info_focus.focus(AgentInfoField::Name);
+                } else if self.moltbook_api_key.is_none()
+                    && self.agent_description.char_count() > MAX_AGENT_DESCRIPTION_LEN
+                {
+                    self.error = Some(format!(
+                        "Description must be at most {} characters",
+                        MAX_AGENT_DESCRIPTION_LEN
+                    ));
+                    self.info_focus.focus(AgentInfoField::Description);
+                }
                 // If we have name + description (either entered or from API key), proceed
                 else if !self.agent_name.is_empty() && !self.agent_description.is_empty() {
                     // If we already have a validated API key, skip registration and claim
@@ -371,14 +619,14 @@ impl CreateScreen {
                         // Already have API key from validation - store agent on our server
                         self.step = CreateStep::RegisteringMoltbook; // Show loading state
                         let api_key = api_key.clone();
-                        let name = self.agent_name.clone();
+                        let name = self.agent_name.text().to_string();
 
                         // We need to send a message to store the agent, which will happen
                         // via the ApiKeyStoreRequest flow. For now, send a special message.
-                        tokio::spawn(async move {
+                        crate::tasks::spawn(async move {
                             // Signal that we have a pre-validated API key and need to store
                             let _ = tx
-                                .send(AppMessage::ApiKeyReadyToStore { api_key, name })
+                                .send(CreateMessage::ApiKeyReadyToStore { api_key, name }.into())
                                 .await;
                         });
                     } else {
@@ -387,25 +635,26 @@ impl CreateScreen {
                         self.error = None;
                         self.step = CreateStep::RegisteringMoltbook;
 
-                        let name = self.agent_name.clone();
-                        let description = self.agent_description.clone();
-                        tokio::spawn(async move {
-                            match crate::moltbook::register_agent(&name, &description).await {
+                        let name = self.agent_name.text().to_string();
+                        let description = self.agent_description.text().to_string();
+                        let client = client.clone();
+                        crate::tasks::spawn(async move {
+                            match client.register_agent(&name, &description).await {
                                 Ok(resp) => {
                                     let _ = tx
-                                        .send(AppMessage::MoltbookRegistered {
+                                        .send(CreateMessage::MoltbookRegistered {
                                             api_key: resp.api_key,
                                             claim_url: resp.claim_url,
                                             verification_code: resp.verification_code,
-                                        })
+                                        }.into())
                                         .await;
                                 }
                                 Err(crate::moltbook::MoltbookError::NameTaken(msg)) => {
-                                    let _ = tx.send(AppMessage::NameTaken(msg)).await;
+                                    let _ = tx.send(CreateMessage::NameTaken(msg).into()).await;
                                 }
                                 Err(e) => {
                                     let _ = tx
-                                        .send(AppMessage::RegistrationFailed(e.to_string()))
+                                        .send(CreateMessage::RegistrationFailed(format!("{:#}", e)).into())
                                         .await;
                                 }
                             }
@@ -413,20 +662,68 @@ impl CreateScreen {
                     }
                 } else if self.agent_name.is_empty() {
                     self.name_error = Some("Name is required".to_string());
-                    self.active_field = AgentInfoField::Name;
+                    self.info_focus.focus(AgentInfoField::Name);
                 } else {
                     self.error = Some("Description is required".to_string());
-                    self.active_field = AgentInfoField::Description;
+                    self.info_focus.focus(AgentInfoField::Description);
                 }
             }
             KeyCode::Esc => {
-                return Ok(ScreenAction::GoHome);
+                self.api_key_input.hide();
+                return Ok(ScreenAction::Pop);
             }
             _ => {}
         }
+        // A shown key is masked again once focus leaves it
+        if !self.info_focus.is_focused(AgentInfoField::ApiKey) {
+            self.api_key_input.hide();
+        } else if !was_on_key && self.moltbook_api_key.is_none() {
+            Self::detect_clipboard_key(clipboard_tx);
+        }
         Ok(ScreenAction::None)
     }
 
+    /// Look for a Moltbook API key on the clipboard. The key itself is
+    /// never logged.
+    fn detect_clipboard_key(tx: mpsc::Sender<AppMessage>) {
+        crate::tasks::spawn(async move {
+            let api_key = crate::clipboard::read()
+                .await
+                .filter(|text| crate::moltbook::looks_like_api_key(text))
+                .map(|text| text.trim().to_string());
+            let _ = tx.send(CreateMessage::ClipboardKey(api_key).into()).await;
+        });
+    }
+
+    /// Ask Moltbook whether the agent was claimed and store it on the
+    /// server once it is. `automatic` checks from the claim poll stay quiet
+    /// until something changes.
+    pub fn check_claim(&mut self, client: ApiClient, tx: mpsc::Sender<AppMessage>, automatic: bool) {
+        if self.checking_claim {
+            return;
+        }
+        let Some(api_key) = self.moltbook_api_key.clone() else { return };
+        self.checking_claim = true;
+        let name = self.agent_name.text().to_string();
+        crate::tasks::spawn(async move {
+            let message = match client.get_moltbook_status(&api_key).await {
+                // Claimed! Now store the agent on our server
+                Ok(resp) if resp.claimed => match client.store_agent(&name, &api_key).await {
+                    Ok(store_resp) => CreateMessage::MoltbookClaimed { agent_id: store_resp.agent_id },
+                    Err(e) => CreateMessage::ClaimCheckFailed(secrets::redact(&format!("Failed to store agent: {}", e), &api_key)),
+                },
+                Ok(_) if automatic => CreateMessage::ClaimPending,
+                Ok(_) => CreateMessage::ClaimCheckFailed("Not claimed yet. Complete the Twitter verification.".to_string()),
+                Err(e) if automatic => {
+                    tracing::debug!("Automatic claim check failed: {:#}", e);
+                    CreateMessage::ClaimPending
+                }
+                Err(e) => CreateMessage::ClaimCheckFailed(secrets::redact(&format!("{:#}", e), &api_key)),
+            };
+            let _ = tx.send(message.into()).await;
+        });
+    }
+
     async fn handle_waiting_claim_key(
         &mut self,
         key: KeyCode,
@@ -437,54 +734,15 @@ impl CreateScreen {
             KeyCode::Char('o') | KeyCode::Char('O') => {
                 // Open claim URL in browser
                 if let Some(url) = &self.claim_url {
-                    let _ = open::that(url);
-                }
-            }
-            KeyCode::Char('c') | KeyCode::Char('C') => {
-                // Check claim status using the API key
-                if let Some(api_key) = &self.moltbook_api_key {
-                    let api_key = api_key.clone();
-                    let name = self.agent_name.clone();
-                    tokio::spawn(async move {
-                        // First check if claimed
-                        match client.get_moltbook_status(&api_key).await {
-                            Ok(resp) if resp.claimed => {
-                                // Claimed! Now store the agent on our server
-                                match client.store_agent(&name, &api_key).await {
-                                    Ok(store_resp) => {
-                                        let _ = tx
-                                            .send(AppMessage::MoltbookClaimed {
-                                                agent_id: store_resp.agent_id,
-                                            })
-                                            .await;
-                                    }
-                                    Err(e) => {
-                                        let _ = tx
-                                            .send(AppMessage::Error(format!(
-                                                "Failed to store agent: {}",
-                                                e
-                                            )))
-                                            .await;
-                                    }
-                                }
-                            }
-                            Ok(_) => {
-                                let _ = tx
-                                    .send(AppMessage::Error(
-                                        "Not claimed yet. Complete the Twitter verification."
-                                            .to_string(),
-                                    ))
-                                    .await;
-                            }
-                            Err(e) => {
-                                let _ = tx.send(AppMessage::Error(e.to_string())).await;
-                            }
-                        }
-                    });
+                    if let Err(e) = open::that(url) {
+                        tracing::warn!("Could not open the claim URL: {}", e);
+                        self.error = Some(format!("Could not open browser: {}", e));
+                    }
                 }
             }
+            KeyCode::Char('c') | KeyCode::Char('C') => self.check_claim(client, tx, false),
             KeyCode::Esc => {
-                return Ok(ScreenAction::GoHome);
+                return Ok(ScreenAction::Pop);
             }
             _ => {}
         }
@@ -496,127 +754,208 @@ impl CreateScreen {
             KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
                 self.step = CreateStep::ConfigureSchedule;
             }
+            KeyCode::Tab => {
+                self.show_program = !self.show_program;
+                self.program_scroll = 0;
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => return Ok(ScreenAction::SimulateAgent),
+            KeyCode::Char('j') | KeyCode::Down if self.show_program => {
+                let lines = self.agent_source().read_file("moltbook_agent.ship").map_or(0, |s| s.lines().count());
+                self.program_scroll = (self.program_scroll + 3).min(lines.saturating_sub(1) as u16);
+            }
+            KeyCode::Char('k') | KeyCode::Up if self.show_program => {
+                self.program_scroll = self.program_scroll.saturating_sub(3);
+            }
             KeyCode::Char('e') | KeyCode::Char('E') => {
                 // Open SOUL.md in editor (only for custom directory)
                 if let AgentSource::Custom(dir) = self.agent_source() {
                     let soul_path = std::path::Path::new(&dir).join("SOUL.md");
                     if soul_path.exists() {
                         let editor = std::env::var("EDITOR").unwrap_or_else(|_| "nano".to_string());
-                        let _ = std::process::Command::new(&editor).arg(&soul_path).status();
+                        if let Err(e) = std::process::Command::new(&editor).arg(&soul_path).status() {
+                            tracing::warn!(editor, "Could not start the editor: {}", e);
+                            self.error = Some(format!("Could not start {}: {}", editor, e));
+                        }
                     }
                 }
                 // For embedded, editing is not supported (show message handled in render)
             }
             KeyCode::Esc => {
-                return Ok(ScreenAction::GoHome);
+                return Ok(ScreenAction::Pop);
             }
             _ => {}
         }
         Ok(ScreenAction::None)
     }
 
-    async fn handle_configure_schedule_key(
+    fn handle_compile_warnings_key(
         &mut self,
         key: KeyCode,
-        client: ApiClient,
+        client: &ApiClient,
+        wallet: Option<&WalletConfig>,
         tx: mpsc::Sender<AppMessage>,
-    ) -> Result<ScreenAction> {
-        match self.schedule_field {
-            ScheduleField::Schedule => match key {
-                KeyCode::Up => {
-                    if self.selected_schedule > 0 {
-                        self.selected_schedule -= 1;
-                    }
+    ) -> ScreenAction {
+        match key {
+            KeyCode::Char('j') | KeyCode::Down => {
+                let last = self.compile_warnings.len().saturating_sub(1) as u16;
+                self.warnings_scroll = (self.warnings_scroll + 1).min(last);
+            }
+            KeyCode::Char('k') | KeyCode::Up => self.warnings_scroll = self.warnings_scroll.saturating_sub(1),
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => match wallet {
+                Some(wallet) => {
+                    self.step = CreateStep::ReviewDeploy;
+                    self.start_fee_estimate(client.clone(), wallet.clone(), tx);
                 }
-                KeyCode::Down => {
-                    if self.selected_schedule < 4 {
-                        self.selected_schedule += 1;
+                None => self.error = Some("No wallet available for deployment".to_string()),
+            },
+            // Back to the schedule to fix the files and compile again
+            KeyCode::Esc => self.back_to_schedule(),
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
+    fn handle_review_deploy_key(
+        &mut self,
+        event: KeyEvent,
+        client: &ApiClient,
+        funds: &Funds,
+        wallet: Option<&WalletConfig>,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> ScreenAction {
+        let key = event.code;
+        if let Some(confirm) = &mut self.amount_confirm {
+            match confirm.handle_key(event, funds.decimals) {
+                Confirm::Pending => return ScreenAction::None,
+                Confirm::Cancelled => self.amount_confirm = None,
+                Confirm::Confirmed => {
+                    self.amount_confirm = None;
+                    if let Some(wallet) = wallet {
+                        self.error = None;
+                        self.step = CreateStep::Deploying;
+                        self.start_deployment(client.clone(), wallet.clone(), tx);
                     }
                 }
-                KeyCode::Tab => {
-                    if self.selected_schedule == 4 {
-                        self.schedule_field = ScheduleField::CustomMinutes;
-                    } else {
-                        self.schedule_field = ScheduleField::Balance;
-                    }
+            }
+            return ScreenAction::None;
+        }
+        match key {
+            KeyCode::Char('y') | KeyCode::Char('Y') => match (wallet, funds.over_limit(self.value_planck)) {
+                (Some(_), Some(reason)) => self.amount_confirm = Some(AmountConfirm::new(self.value_planck, reason)),
+                (Some(wallet), None) => {
+                    self.error = None;
+                    self.step = CreateStep::Deploying;
+                    self.start_deployment(client.clone(), wallet.clone(), tx);
                 }
+                (None, _) => self.error = Some("No wallet available for deployment".to_string()),
+            },
+            KeyCode::Esc => self.back_to_schedule(),
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
+    /// Drop the compiled program and return to the schedule step, to change
+    /// the files or settings and compile again.
+    fn back_to_schedule(&mut self) {
+        self.amount_confirm = None;
+        self.compiled_hex = None;
+        self.compile_warnings.clear();
+        self.deploy_fee = None;
+        self.step = CreateStep::ConfigureSchedule;
+    }
+
+    /// The minutes input only takes focus with the custom schedule picked.
+    fn register_schedule_fields(&mut self) {
+        if self.selected_schedule.selected() == 4 {
+            self.schedule_focus.set_fields([ScheduleField::Schedule, ScheduleField::CustomMinutes, ScheduleField::Balance]);
+        } else {
+            self.schedule_focus.set_fields([ScheduleField::Schedule, ScheduleField::Balance]);
+        }
+    }
+
+    async fn handle_configure_schedule_key(
+        &mut self,
+        event: KeyEvent,
+        client: ApiClient,
+        funds: &Funds,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> Result<ScreenAction> {
+        let key = event.code;
+        if self.schedule_focus.handle_key(key) {
+            return Ok(ScreenAction::None);
+        }
+        match self.schedule_focus.current() {
+            ScheduleField::Schedule => match key {
+                key if self.selected_schedule.handle_key(key, SCHEDULE_CHOICES) => self.register_schedule_fields(),
                 KeyCode::Enter => {
-                    self.schedule_field = ScheduleField::Balance;
+                    self.schedule_focus.focus(ScheduleField::Balance);
                 }
                 KeyCode::Esc => {
-                    return Ok(ScreenAction::GoHome);
+                    return Ok(ScreenAction::Pop);
                 }
                 _ => {}
             },
             ScheduleField::CustomMinutes => match key {
-                KeyCode::Char(c) if c.is_ascii_digit() => {
-                    self.custom_minutes_input.push(c);
-                }
-                KeyCode::Backspace => {
-                    self.custom_minutes_input.pop();
-                }
-                KeyCode::Tab | KeyCode::Enter => {
-                    self.schedule_field = ScheduleField::Balance;
-                }
-                KeyCode::Up => {
-                    self.schedule_field = ScheduleField::Schedule;
-                }
+                _ if self.custom_minutes_input.handle_key(event) => {}
+                KeyCode::Enter => self.schedule_focus.next(),
+                KeyCode::Up => self.schedule_focus.prev(),
                 KeyCode::Esc => {
-                    return Ok(ScreenAction::GoHome);
+                    return Ok(ScreenAction::Pop);
                 }
                 _ => {}
             },
             ScheduleField::Balance => match key {
-                KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
-                    if c == '.' && self.balance_input.contains('.') {
-                        // Don't allow multiple decimal points
-                    } else {
-                        self.balance_input.push(c);
-                        self.balance_error = None;
-                    }
-                }
-                KeyCode::Backspace => {
-                    self.balance_input.pop();
+                _ if self.balance_input.handle_key(event) => self.balance_error = None,
+                KeyCode::Char('f') => {
+                    // The app holds the wallet; it refuses on networks without a faucet
                     self.balance_error = None;
+                    crate::tasks::spawn(async move {
+                        let _ = tx.send(AppMessage::RequestTestTokens).await;
+                    });
                 }
-                KeyCode::Tab | KeyCode::Up => {
-                    if self.selected_schedule == 4 {
-                        self.schedule_field = ScheduleField::CustomMinutes;
-                    } else {
-                        self.schedule_field = ScheduleField::Schedule;
-                    }
+                KeyCode::Enter if self.waiting_for_tokens => {
+                    self.balance_error = Some("Waiting for the test tokens to arrive".to_string());
                 }
+                KeyCode::Up => self.schedule_focus.prev(),
                 KeyCode::Enter => {
                     // Compute schedule_option based on selection
-                    self.schedule_option = match self.selected_schedule {
+                    self.schedule_option = match self.selected_schedule.selected() {
                         0 => None,      // Never
                         1 => Some(300), // 30 min
                         2 => Some(600), // 1 hour
                         3 => Some(1200), // 2 hours
                         4 => {
-                            // Custom: parse minutes input
-                            if let Ok(minutes) = self.custom_minutes_input.parse::<u32>() {
-                                if minutes > 0 {
-                                    // Convert minutes to blocks (10 blocks per minute at 6s/block)
-                                    Some(minutes * 10)
-                                } else {
-                                    self.error = Some("Minutes must be greater than 0".to_string());
-                                    self.schedule_field = ScheduleField::CustomMinutes;
+                            // Custom: at least a minute, converted to blocks (10 blocks per minute at 6s/block)
+                            match self.custom_minutes_input.value().and_then(|minutes| u32::try_from(minutes).ok()) {
+                                Some(minutes) => Some(minutes * 10),
+                                None => {
+                                    self.error = Some(if self.custom_minutes_input.text().parse::<u32>() == Ok(0) {
+                                        "Minutes must be greater than 0".to_string()
+                                    } else {
+                                        "Enter valid minutes".to_string()
+                                    });
+                                    self.schedule_focus.focus(ScheduleField::CustomMinutes);
                                     return Ok(ScreenAction::None);
                                 }
-                            } else {
-                                self.error = Some("Enter valid minutes".to_string());
-                                self.schedule_field = ScheduleField::CustomMinutes;
-                                return Ok(ScreenAction::None);
                             }
                         }
                         _ => Some(600),
                     };
 
                     // Parse and validate balance
-                    self.value_planck = self.parse_balance_to_planck();
-                    if let Err(e) = self.validate_balance(&client).await {
-                        self.balance_error = Some(e);
+                    match self.validate_balance(funds) {
+                        Ok(value_planck) => self.value_planck = value_planck,
+                        Err(e) => {
+                            self.balance_error = Some(e);
+                            return Ok(ScreenAction::None);
+                        }
+                    }
+
+                    // Files may have been edited since the source step
+                    self.validate_source();
+                    if let Some(problem) = self.source_validation.as_ref().and_then(|v| v.problem()) {
+                        self.error = Some(problem);
                         return Ok(ScreenAction::None);
                     }
 
@@ -624,7 +963,7 @@ impl CreateScreen {
                     self.start_compilation(client, tx).await?;
                 }
                 KeyCode::Esc => {
-                    return Ok(ScreenAction::GoHome);
+                    return Ok(ScreenAction::Pop);
                 }
                 _ => {}
             },
@@ -632,34 +971,29 @@ impl CreateScreen {
         Ok(ScreenAction::None)
     }
 
-    fn parse_balance_to_planck(&self) -> u128 {
-        if self.balance_input.is_empty() {
-            return UNIT_PLANCK; // Default: 1 UNIT (existential deposit)
+    /// The balance field in planck, exactly; empty is the 1 token default
+    /// (the existential deposit).
+    fn parse_balance_to_planck(&self, decimals: u8) -> Result<u128, String> {
+        let text = self.balance_input.text().trim();
+        if text.is_empty() {
+            return 10u128.checked_pow(decimals as u32).ok_or_else(|| format!("The network's token has too many decimals ({})", decimals));
         }
-        
-        let input = self.balance_input.trim();
-        if let Ok(decimal) = input.parse::<f64>() {
-            (decimal * UNIT_PLANCK as f64) as u128
-        } else {
-            UNIT_PLANCK
+        let frac = text.split_once('.').map_or(0, |(_, frac)| frac.len());
+        if frac > decimals as usize {
+            return Err(format!("The network's token has at most {} decimals", decimals));
         }
+        parse_tokens(text, decimals).ok_or_else(|| format!("{} is not a token amount", text))
     }
 
-    async fn validate_balance(&self, _client: &ApiClient) -> Result<(), String> {
-        let value_planck = self.parse_balance_to_planck();
-        
-        // Skip validation if no balance input (will use default)
-        if self.balance_input.is_empty() {
-            return Ok(());
-        }
-
-        // We need wallet address to check balance - this will be available in app context
-        // For now, just validate that the amount is reasonable (> 0 and parseable)
+    /// The amount parses, is positive and the wallet can send it and still
+    /// pay fees; returns it in planck.
+    fn validate_balance(&self, funds: &Funds) -> Result<u128, String> {
+        let value_planck = self.parse_balance_to_planck(funds.decimals)?;
         if value_planck == 0 {
             return Err("Balance must be greater than 0".to_string());
         }
-
-        Ok(())
+        funds.check_wallet(value_planck)?;
+        Ok(value_planck)
     }
 
     async fn start_compilation(
@@ -678,7 +1012,29 @@ impl CreateScreen {
         let agent_id = self.agent_id.clone().unwrap_or_default();
         let schedule = self.schedule_option;
 
-        tokio::spawn(async move {
+        // Nothing changed since a compile that was saved: deploy that program
+        let input_hash = artifact::input_hash(&agent_id, &[&ship_file, &soul_md, &skill_md, &heartbeat_md], schedule);
+        if let Some(cached) = Artifact::cached(&self.artifacts_dir, &input_hash) {
+            tracing::info!(input_hash, "Reusing the compiled artifact");
+            crate::tasks::spawn(async move {
+                let msg = CreateMessage::CompileDone { compiled_hex: cached.compiled_hex, warnings: cached.warnings };
+                let _ = tx.send(msg.into()).await;
+            });
+            return Ok(());
+        }
+        let artifacts_dir = self.artifacts_dir.clone();
+        let mut saved = Artifact {
+            input_hash,
+            compiled_hex: String::new(),
+            warnings: Vec::new(),
+            agent_name: self.agent_name.text().to_string(),
+            agent_id: self.agent_id.clone(),
+            schedule_blocks: schedule,
+            source: self.source_label(),
+            compiled_at: crate::schedule::now(),
+        };
+
+        crate::tasks::spawn_operation("a deployment", async move {
             match client
                 .compile(
                     &agent_id,
@@ -692,19 +1048,25 @@ impl CreateScreen {
             {
                 Ok(resp) if resp.success => {
                     if let Some(hex) = resp.compiled_hex {
-                        let _ = tx.send(AppMessage::CompileDone { compiled_hex: hex }).await;
+                        saved.compiled_hex = hex.clone();
+                        saved.warnings = resp.warnings.clone();
+                        if let Err(e) = saved.save(&artifacts_dir) {
+                            tracing::warn!("Cannot save the compiled artifact: {:#}", e);
+                        }
+                        let msg = CreateMessage::CompileDone { compiled_hex: hex, warnings: resp.warnings };
+                        let _ = tx.send(msg.into()).await;
                     } else {
                         let _ = tx
-                            .send(AppMessage::CompileFailed("No output".to_string()))
+                            .send(CreateMessage::CompileFailed("No output".to_string()).into())
                             .await;
                     }
                 }
                 Ok(resp) => {
                     let errors = resp.errors.join("\n");
-                    let _ = tx.send(AppMessage::CompileFailed(errors)).await;
+                    let _ = tx.send(CreateMessage::CompileFailed(errors).into()).await;
                 }
                 Err(e) => {
-                    let _ = tx.send(AppMessage::CompileFailed(e.to_string())).await;
+                    let _ = tx.send(CreateMessage::CompileFailed(format!("{:#}", e)).into()).await;
                 }
             }
         });
@@ -727,7 +1089,7 @@ impl CreateScreen {
     pub fn handle_name_taken(&mut self, message: &str) {
         // Go back to agent info step with name error (description is preserved)
         self.step = CreateStep::EnterAgentInfo;
-        self.active_field = AgentInfoField::Name;
+        self.info_focus.focus(AgentInfoField::Name);
         self.name_error = Some(message.to_string());
     }
 
@@ -746,8 +1108,8 @@ impl CreateScreen {
     ) {
         // Store the validated API key and populate fields
         self.moltbook_api_key = Some(api_key);
-        self.agent_name = name;
-        self.agent_description = description;
+        self.agent_name.set_text(name);
+        self.agent_description.set_text(description);
         self.api_key_status = Some("Valid! Press Enter to continue.".to_string());
         self.api_key_error = None;
 
@@ -766,13 +1128,51 @@ impl CreateScreen {
 
     pub fn handle_moltbook_claimed(&mut self, agent_id: String) {
         self.agent_id = Some(agent_id);
+        self.checking_claim = false;
         self.step = CreateStep::ReviewSoul;
     }
 
-    pub fn handle_compile_done(&mut self, compiled_hex: String) {
+    /// Store the program; the review follows unless there are warnings to read first.
+    pub fn handle_compile_done(&mut self, compiled_hex: String, warnings: Vec<String>) {
         self.compiled_hex = Some(compiled_hex);
-        self.step = CreateStep::Deploying;
-        // Deployment needs to be triggered by calling start_deployment
+        self.warnings_scroll = 0;
+        self.deploy_fee = None;
+        self.step = if warnings.is_empty() { CreateStep::ReviewDeploy } else { CreateStep::CompileWarnings };
+        self.compile_warnings = warnings;
+    }
+
+    /// Build and sign the deploy the way deploying would, without taking a
+    /// nonce, and ask the gateway what it would charge.
+    pub fn start_fee_estimate(&mut self, client: ApiClient, wallet: WalletConfig, tx: mpsc::Sender<AppMessage>) {
+        self.deploy_fee = None;
+        let Some(compiled_hex) = self.compiled_hex.clone() else { return };
+        let value_planck = self.value_planck;
+        crate::tasks::spawn(async move {
+            let fee = async {
+                let mut salt = [0u8; 32];
+                getrandom::getrandom(&mut salt)?;
+                let keypair = wallet.keypair()?;
+                let build = client
+                    .build_deploy(&compiled_hex, &format!("0x{}", hex::encode(salt)), &wallet.public_key, value_planck)
+                    .await?;
+                let call_data = hex::decode(build.call_data_hex.trim_start_matches("0x"))?;
+                let genesis_hash: [u8; 32] = hex::decode(build.genesis_hash.trim_start_matches("0x"))?
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("Invalid genesis hash"))?;
+                let signed_hex = extrinsic::build_signed_extrinsic(
+                    &call_data,
+                    build.nonce,
+                    &genesis_hash,
+                    build.spec_version,
+                    build.transaction_version,
+                    &keypair,
+                )?;
+                let estimate = client.estimate_fee(&signed_hex).await?;
+                estimate.partial_fee.parse::<u128>().map_err(anyhow::Error::from)
+            };
+            let fee = fee.await.map_err(|e: anyhow::Error| format!("{:#}", e));
+            let _ = tx.send(CreateMessage::DeployFeeEstimated(fee).into()).await;
+        });
     }
 
     /// Start the deployment process after compilation is done.
@@ -787,9 +1187,9 @@ impl CreateScreen {
             Some(hex) => hex.clone(),
             None => {
                 let tx = tx.clone();
-                tokio::spawn(async move {
+                crate::tasks::spawn(async move {
                     let _ = tx
-                        .send(AppMessage::DeployFailed("No compiled hex".to_string()))
+                        .send(CreateMessage::DeployFailed("No compiled hex".to_string()).into())
                         .await;
                 });
                 return;
@@ -799,12 +1199,18 @@ impl CreateScreen {
         let signer_address = wallet.public_key.clone();
         let value_planck = self.value_planck;
 
-        // Generate a random salt
+        // Generate a random salt; a fixed one would collide with earlier deployments
         let mut salt = [0u8; 32];
-        let _ = getrandom::getrandom(&mut salt);
-        let salt_hex = format!("0x{}", hex::encode(&salt));
+        if let Err(e) = getrandom::getrandom(&mut salt) {
+            tracing::error!("No randomness for the deployment salt: {}", e);
+            crate::tasks::spawn(async move {
+                let _ = tx.send(CreateMessage::DeployFailed(format!("Could not generate a salt: {}", e)).into()).await;
+            });
+            return;
+        }
+        let salt_hex = format!("0x{}", hex::encode(salt));
 
-        tokio::spawn(async move {
+        crate::tasks::spawn_operation("a deployment", async move {
             // Step 1: Build the extrinsic (get call data from server)
             let build_result = match client
                 .build_deploy(&compiled_hex, &salt_hex, &signer_address, value_planck)
@@ -813,7 +1219,7 @@ impl CreateScreen {
                 Ok(r) => r,
                 Err(e) => {
                     let _ = tx
-                        .send(AppMessage::DeployFailed(format!("Build failed: {}", e)))
+                        .send(CreateMessage::DeployFailed(format!("Build failed: {:#}", e)).into())
                         .await;
                     return;
                 }
@@ -824,10 +1230,10 @@ impl CreateScreen {
                 Ok(d) => d,
                 Err(e) => {
                     let _ = tx
-                        .send(AppMessage::DeployFailed(format!(
+                        .send(CreateMessage::DeployFailed(format!(
                             "Invalid call data: {}",
                             e
-                        )))
+                        )).into())
                         .await;
                     return;
                 }
@@ -842,7 +1248,7 @@ impl CreateScreen {
                 }
                 _ => {
                     let _ = tx
-                        .send(AppMessage::DeployFailed("Invalid genesis hash".to_string()))
+                        .send(CreateMessage::DeployFailed("Invalid genesis hash".to_string()).into())
                         .await;
                     return;
                 }
@@ -853,16 +1259,17 @@ impl CreateScreen {
                 Ok(k) => k,
                 Err(e) => {
                     let _ = tx
-                        .send(AppMessage::DeployFailed(format!("Wallet error: {}", e)))
+                        .send(CreateMessage::DeployFailed(format!("Wallet error: {:#}", e)).into())
                         .await;
                     return;
                 }
             };
 
             // Step 4: Build and sign the extrinsic
+            let reservation = crate::nonce::reserve(&wallet.public_key, build_result.nonce);
             let signed_hex = match extrinsic::build_signed_extrinsic(
                 &call_data,
-                build_result.nonce,
+                reservation.nonce,
                 &genesis_hash,
                 build_result.spec_version,
                 build_result.transaction_version,
@@ -871,7 +1278,7 @@ impl CreateScreen {
                 Ok(h) => h,
                 Err(e) => {
                     let _ = tx
-                        .send(AppMessage::DeployFailed(format!("Signing failed: {}", e)))
+                        .send(CreateMessage::DeployFailed(format!("Signing failed: {:#}", e)).into())
                         .await;
                     return;
                 }
@@ -879,10 +1286,13 @@ impl CreateScreen {
 
             // Step 5: Submit the extrinsic
             let submit_result = match client.submit_extrinsic(&signed_hex).await {
-                Ok(r) => r,
+                Ok(r) => {
+                    reservation.included();
+                    r
+                }
                 Err(e) => {
                     let _ = tx
-                        .send(AppMessage::DeployFailed(format!("Submit failed: {}", e)))
+                        .send(CreateMessage::DeployFailed(format!("Submit failed: {:#}", e)).into())
                         .await;
                     return;
                 }
@@ -894,16 +1304,17 @@ impl CreateScreen {
             match agent_address {
                 Some(addr) => {
                     let _ = tx
-                        .send(AppMessage::DeployDone {
+                        .send(CreateMessage::DeployDone {
                             agent_address: addr,
-                        })
+                            receipt: submit_result,
+                        }.into())
                         .await;
                 }
                 None => {
                     let _ = tx
-                        .send(AppMessage::DeployFailed(
+                        .send(CreateMessage::DeployFailed(
                             "Could not find AgentRegistered event".to_string(),
-                        ))
+                        ).into())
                         .await;
                 }
             }
@@ -915,19 +1326,180 @@ impl CreateScreen {
         self.step = CreateStep::ConfigureSchedule;
     }
 
-    pub fn handle_deploy_done(&mut self, agent_address: String) {
+    pub fn handle_deploy_done(&mut self, agent_address: String, receipt: SubmitResponse, link: Option<String>) {
         self.agent_address = Some(agent_address);
+        self.receipt = Some(receipt);
+        self.receipt_link = link;
         self.step = CreateStep::Success;
     }
 
+    /// Add the deploy to the version history with a snapshot of the source,
+    /// once the chain's version counter has been read. Best effort: the
+    /// agent is deployed either way.
+    fn record_version(&self, client: ApiClient, agent_address: &str, receipt: &SubmitResponse) {
+        let Some(compiled_hex) = &self.compiled_hex else { return };
+        let source = self.agent_source();
+        let code_hash = versions::code_hash(compiled_hex);
+        let snapshot = versions::snapshot(&source, &code_hash)
+            .inspect_err(|e| tracing::warn!("Cannot snapshot the agent source: {:#}", e))
+            .ok();
+        let mut deployed = DeployedVersion {
+            agent_address: agent_address.to_string(),
+            version: None,
+            code_hash,
+            block_number: receipt.block_number,
+            block_hash: receipt.block_hash.clone(),
+            deployed_at: crate::schedule::now(),
+            source: self.source_label(),
+            snapshot,
+        };
+        crate::tasks::spawn(async move {
+            match client.get_agent(&deployed.agent_address).await {
+                Ok(info) => deployed.version = info.chain_info.map(|c| c.version),
+                Err(e) => tracing::warn!("Cannot read the deployed agent's version: {:#}", e),
+            }
+            if let Err(e) = versions::record(deployed) {
+                tracing::warn!("Cannot record the deployed version: {:#}", e);
+            }
+        });
+    }
+
+    /// Back to the review, where the deploy can be confirmed again.
     pub fn handle_deploy_failed(&mut self, error: &str) {
         self.error = Some(error.to_string());
-        self.step = CreateStep::Compiling;
+        self.step = CreateStep::ReviewDeploy;
+    }
+
+    pub fn handle_message(&mut self, msg: CreateMessage, ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            CreateMessage::MoltbookRegistered { api_key, claim_url, verification_code } => {
+                self.handle_moltbook_registered(api_key, claim_url, verification_code);
+            }
+            CreateMessage::RegistrationFailed(msg) => {
+                // Go back to agent info form with error
+                self.handle_registration_failed(&msg);
+            }
+            CreateMessage::NameTaken(msg) => {
+                // Go back to name input with name-specific error
+                self.handle_name_taken(&msg);
+            }
+            CreateMessage::ApiKeyValidated { api_key, name, description, is_claimed } => {
+                self.handle_api_key_validated(api_key, name, description, is_claimed);
+            }
+            CreateMessage::ApiKeyInvalid(msg) => {
+                self.handle_api_key_invalid(&msg);
+            }
+            // Already typed or pasted keys need no offer
+            CreateMessage::ClipboardKey(api_key) => {
+                self.clipboard_key = api_key.filter(|k| k.as_str() != self.api_key_input.text().trim());
+            }
+            CreateMessage::ApiKeyReadyToStore { api_key, name } => {
+                // Store existing agent on our server
+                let client = ctx.client.clone();
+                let tx = ctx.tx.clone();
+                crate::tasks::spawn(async move {
+                    match client.store_agent(&name, &api_key).await {
+                        Ok(resp) => {
+                            let _ = tx.send(CreateMessage::MoltbookClaimed { agent_id: resp.agent_id }.into()).await;
+                        }
+                        Err(e) => {
+                            let _ = tx
+                                .send(CreateMessage::RegistrationFailed(format!("Failed to store agent: {}", e)).into())
+                                .await;
+                        }
+                    }
+                });
+            }
+            CreateMessage::MoltbookClaimed { agent_id } => {
+                self.handle_moltbook_claimed(agent_id);
+            }
+            CreateMessage::ClaimCheckFailed(e) => {
+                self.checking_claim = false;
+                ctx.notifications.push(Level::Error, e);
+            }
+            CreateMessage::ClaimPending => self.checking_claim = false,
+            CreateMessage::CompileDone { compiled_hex, warnings } => {
+                self.handle_compile_done(compiled_hex, warnings);
+                if self.step == CreateStep::CompileWarnings {
+                    ctx.notify(Level::Info, format!("Compiled with {} warnings", self.compile_warnings.len()));
+                    return Ok(ScreenAction::None);
+                }
+                if let Some(wallet) = ctx.wallet {
+                    self.start_fee_estimate(ctx.client.clone(), wallet.clone(), ctx.tx.clone());
+                } else {
+                    self.deploy_fee = Some(Err("No wallet available for deployment".to_string()));
+                }
+            }
+            CreateMessage::DeployFeeEstimated(fee) => {
+                if self.step == CreateStep::ReviewDeploy {
+                    self.deploy_fee = Some(fee);
+                }
+            }
+            CreateMessage::CompileFailed(e) => {
+                ctx.notify(Level::Error, format!("Compilation failed: {}", e));
+                self.handle_compile_failed(&e);
+            }
+            CreateMessage::DeployDone { agent_address, receipt } => {
+                ctx.config.agent_address = Some(agent_address.clone());
+                ctx.config.agent_name = Some(self.agent_name.text().to_string());
+                ctx.config.moltbook_api_key = self.moltbook_api_key.clone();
+                ctx.config.save()?;
+                CreateDraft::clear();
+                crate::spending::record(&ctx.config.network().name, self.value_planck);
+
+                // Update the server with the chain address
+                if let Some(agent_id) = self.agent_id.clone() {
+                    let client = ctx.client.clone();
+                    let addr = agent_address.clone();
+                    crate::tasks::spawn(async move {
+                        // Best-effort update - deployment already succeeded
+                        if let Err(e) = client.update_agent_address(&agent_id, &addr).await {
+                            tracing::warn!(agent_id, "Failed to record the agent address: {:#}", e);
+                        }
+                    });
+                }
+
+                self.record_version(ctx.client.clone(), &agent_address, &receipt);
+
+                ctx.notify(Level::Success, format!("Agent {} deployed", self.agent_name.text()));
+                webhook::send(ctx.config, WebhookEvent::Deployed, &format!("Agent {} deployed", self.agent_name.text()));
+                let link = ctx.config.explorer_block_link(&receipt.block_hash);
+                self.handle_deploy_done(agent_address, receipt, link);
+            }
+            CreateMessage::DeployFailed(e) => {
+                ctx.notify(Level::Error, format!("Deployment failed: {}", e));
+                self.handle_deploy_failed(&e);
+            }
+            CreateMessage::AgentSourceSelected { custom_dir } => {
+                // Save the agent source selection to config
+                ctx.config.custom_agent_dir = custom_dir;
+                ctx.save_config();
+            }
+            CreateMessage::GitCloned { input } => {
+                self.cloning = false;
+                // Still on the source step with the same URL typed
+                if ctx.showing && self.step == CreateStep::SelectAgentSource && input == self.custom_dir_input.text() {
+                    self.cloned = Some(input);
+                    self.confirm_source(ctx.tx.clone());
+                }
+            }
+            CreateMessage::GitCloneFailed(e) => {
+                self.cloning = false;
+                self.error = Some(e);
+            }
+            CreateMessage::AgentFilesChanged => {
+                if ctx.showing {
+                    self.handle_files_changed();
+                }
+            }
+        }
+        Ok(ScreenAction::None)
     }
 }
 
 impl Screen for CreateScreen {
-    fn render(&self, frame: &mut Frame, area: Rect, _app: &App) {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
         // Use more footer space when there's an error to display
         let footer_height = if self.error.is_some() { 4 } else { 2 };
 
@@ -950,22 +1522,25 @@ impl Screen for CreateScreen {
             CreateStep::ReviewSoul => (4, "Review SOUL.md"),
             CreateStep::ConfigureSchedule => (5, "Configure Schedule"),
             CreateStep::Compiling => (6, "Compiling"),
-            CreateStep::Deploying => (7, "Deploying"),
-            CreateStep::Success => (7, "Complete"),
+            CreateStep::CompileWarnings => (6, "Compiler Warnings"),
+            CreateStep::ReviewDeploy => (7, "Review Deployment"),
+            CreateStep::Deploying => (8, "Deploying"),
+            CreateStep::Success => (8, "Complete"),
         };
 
-        let progress = format!("Step {} of 7", step_num);
+        let progress = format!("Step {} of 8", step_num);
         let title_line = Line::from(vec![
+            network_badge(app.config.network().kind, theme),
             Span::styled(
                 " CREATE AGENT ",
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme.text)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("│ ", Style::default().fg(Color::DarkGray)),
-            Span::styled(step_name, Style::default().fg(Color::LightRed)),
-            Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
-            Span::styled(progress, Style::default().fg(Color::DarkGray)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(step_name, Style::default().fg(theme.accent)),
+            Span::styled(" │ ", Style::default().fg(theme.muted)),
+            Span::styled(progress, Style::default().fg(theme.muted)),
         ]);
 
         let title = Paragraph::new(title_line)
@@ -973,39 +1548,45 @@ impl Screen for CreateScreen {
             .block(
                 Block::default()
                     .borders(Borders::BOTTOM)
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(Style::default().fg(theme.muted)),
             );
         frame.render_widget(title, chunks[0]);
 
         // Content based on step
         match self.step {
-            CreateStep::SelectAgentSource => self.render_select_agent_source(frame, chunks[1]),
-            CreateStep::EnterAgentInfo => self.render_agent_info(frame, chunks[1]),
+            CreateStep::SelectAgentSource => self.render_select_agent_source(frame, chunks[1], theme),
+            CreateStep::EnterAgentInfo => self.render_agent_info(frame, chunks[1], theme),
             CreateStep::RegisteringMoltbook => {
-                self.render_loading(frame, chunks[1], "Registering with Moltbook...")
+                self.render_loading(frame, chunks[1], "Registering with Moltbook...", app.spinner(), theme)
+            }
+            CreateStep::WaitingClaim => self.render_waiting_claim(frame, chunks[1], app.spinner(), theme),
+            CreateStep::ReviewSoul => self.render_review_soul(frame, chunks[1], theme),
+            CreateStep::ConfigureSchedule => {
+                let amount = if self.balance_input.is_empty() { "1.0" } else { self.balance_input.text() };
+                let faucet = app.wallet_address().is_some() && app.config.network().kind.has_faucet();
+                self.render_configure_schedule(frame, chunks[1], app.usd_value(amount), faucet, theme)
             }
-            CreateStep::WaitingClaim => self.render_waiting_claim(frame, chunks[1]),
-            CreateStep::ReviewSoul => self.render_review_soul(frame, chunks[1]),
-            CreateStep::ConfigureSchedule => self.render_configure_schedule(frame, chunks[1]),
             CreateStep::Compiling => {
-                self.render_loading(frame, chunks[1], "Compiling SHIP code...")
+                self.render_loading(frame, chunks[1], "Compiling SHIP code...", app.spinner(), theme)
             }
+            CreateStep::CompileWarnings => self.render_compile_warnings(frame, chunks[1], theme),
+            CreateStep::ReviewDeploy => self.render_review_deploy(frame, chunks[1], app),
             CreateStep::Deploying => {
-                self.render_loading(frame, chunks[1], "Deploying to Theseus chain...")
+                self.render_loading(frame, chunks[1], "Deploying to Theseus chain...", app.spinner(), theme)
             }
-            CreateStep::Success => self.render_success(frame, chunks[1]),
+            CreateStep::Success => self.render_success(frame, chunks[1], app),
         }
 
         // Footer
         let footer = if let Some(err) = &self.error {
             // Show error with wrapping for long messages
-            Paragraph::new(format!(" ✗ {}", err))
-                .style(Style::default().fg(Color::Red))
+            Paragraph::new(format!(" {} {}", theme.err_mark(), err))
+                .style(Style::default().fg(theme.error))
                 .wrap(Wrap { trim: true })
         } else {
             Paragraph::new(Line::from(vec![
-                Span::styled("[Esc] ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Cancel", Style::default().fg(Color::DarkGray)),
+                Span::styled("[Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Cancel", Style::default().fg(theme.muted)),
             ]))
             .alignment(Alignment::Center)
         };
@@ -1015,7 +1596,8 @@ impl Screen for CreateScreen {
 }
 
 impl CreateScreen {
-    fn render_select_agent_source(&self, frame: &mut Frame, area: Rect) {
+    fn render_select_agent_source(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let issue_lines = self.source_validation.as_ref().map_or(0, |v| v.issues.len().min(MAX_ISSUE_LINES)) as u16;
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
@@ -1025,7 +1607,7 @@ impl CreateScreen {
                 Constraint::Length(1),  // Spacer
                 Constraint::Length(3),  // Path input (for custom)
                 Constraint::Length(1),  // Spacer
-                Constraint::Length(6),  // File status
+                Constraint::Length(6 + issue_lines),  // File status + lint findings
                 Constraint::Length(2),  // Hint
                 Constraint::Min(0),     // Remaining
             ])
@@ -1033,7 +1615,7 @@ impl CreateScreen {
 
         // Help text
         let help = Paragraph::new("Select where to load agent files from:")
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(theme.text));
         frame.render_widget(help, chunks[0]);
 
         // Options
@@ -1042,14 +1624,14 @@ impl CreateScreen {
         let custom_prefix = if !embedded_selected { "● " } else { "○ " };
 
         let embedded_style = if embedded_selected {
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            Style::default().fg(theme.info).add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme.text)
         };
         let custom_style = if !embedded_selected {
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            Style::default().fg(theme.info).add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme.text)
         };
 
         let options = vec![
@@ -1061,7 +1643,7 @@ impl CreateScreen {
                 Span::styled("  ", Style::default()),
                 Span::styled(
                     "Pre-configured agent files embedded in the binary",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 ),
             ])),
             ListItem::new(Line::from("")),
@@ -1072,8 +1654,8 @@ impl CreateScreen {
             ListItem::new(Line::from(vec![
                 Span::styled("  ", Style::default()),
                 Span::styled(
-                    "Load files from a local directory (for advanced users)",
-                    Style::default().fg(Color::DarkGray),
+                    "Load files from a local directory or a git URL (for advanced users)",
+                    Style::default().fg(theme.muted),
                 ),
             ])),
         ];
@@ -1081,107 +1663,138 @@ impl CreateScreen {
         let list = List::new(options).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(Span::styled(" Agent Source ", Style::default().fg(Color::White))),
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(" Agent Source ", Style::default().fg(theme.text))),
         );
         frame.render_widget(list, chunks[1]);
 
         // Path input (only active for custom)
         let path_active = !self.use_embedded;
-        let path_border = if path_active { Color::Cyan } else { Color::DarkGray };
-        let path_cursor = if path_active { "│" } else { "" };
         let path_text = if self.custom_dir_input.is_empty() && !path_active {
-            "(select custom directory above to enter path)".to_string()
+            Line::from("(select custom directory above to enter path)")
         } else {
-            format!("{}{}", self.custom_dir_input, path_cursor)
+            self.custom_dir_input.line(path_active, chunks[3].width.saturating_sub(2) as usize, Style::default())
         };
-        let path_style = if path_active { Color::Cyan } else { Color::DarkGray };
+        let path_style = if path_active { theme.info } else { theme.muted };
 
         let path_input = Paragraph::new(path_text)
             .style(Style::default().fg(path_style))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(path_border))
-                    .title(Span::styled(" Directory Path ", Style::default().fg(Color::White))),
+                    .border_style(focus::border(path_active, theme))
+                    .title(Span::styled(" Directory Path or Git URL (url#ref:subdir) ", Style::default().fg(theme.text))),
             );
         frame.render_widget(path_input, chunks[3]);
 
         // File status
         let validation = self.source_validation.as_ref();
         let file_status_lines = if let Some(v) = validation {
-            vec![
-                self.format_file_status("moltbook_agent.ship", &v.ship_file, true),
-                self.format_file_status("SOUL.md", &v.soul_md, false),
-                self.format_file_status("SKILL.md", &v.skill_md, false),
-                self.format_file_status("HEARTBEAT.md", &v.heartbeat_md, false),
-            ]
+            let mut lines = vec![
+                self.format_file_status("moltbook_agent.ship", &v.ship_file, true, theme),
+                self.format_file_status("SOUL.md", &v.soul_md, false, theme),
+                self.format_file_status("SKILL.md", &v.skill_md, false, theme),
+                self.format_file_status("HEARTBEAT.md", &v.heartbeat_md, false, theme),
+            ];
+            lines.extend(Self::format_issues(&v.issues, theme));
+            lines
         } else if self.use_embedded {
             // For embedded, show all as present (they're guaranteed)
             vec![
                 Line::from(vec![
-                    Span::styled("✓ ", Style::default().fg(Color::Green)),
-                    Span::styled("moltbook_agent.ship", Style::default().fg(Color::Green)),
+                    Span::styled(format!("{} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                    Span::styled("moltbook_agent.ship", Style::default().fg(theme.success)),
                 ]),
                 Line::from(vec![
-                    Span::styled("✓ ", Style::default().fg(Color::Green)),
-                    Span::styled("SOUL.md", Style::default().fg(Color::Green)),
+                    Span::styled(format!("{} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                    Span::styled("SOUL.md", Style::default().fg(theme.success)),
                 ]),
                 Line::from(vec![
-                    Span::styled("✓ ", Style::default().fg(Color::Green)),
-                    Span::styled("SKILL.md", Style::default().fg(Color::Green)),
+                    Span::styled(format!("{} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                    Span::styled("SKILL.md", Style::default().fg(theme.success)),
                 ]),
                 Line::from(vec![
-                    Span::styled("✓ ", Style::default().fg(Color::Green)),
-                    Span::styled("HEARTBEAT.md", Style::default().fg(Color::Green)),
+                    Span::styled(format!("{} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                    Span::styled("HEARTBEAT.md", Style::default().fg(theme.success)),
                 ]),
             ]
+        } else if self.cloning {
+            vec![Line::from(Span::styled("Cloning the repository...", Style::default().fg(theme.warning)))]
+        } else if self.git_source().is_some() {
+            vec![Line::from(Span::styled(
+                "Press Enter to clone the repository and check its files",
+                Style::default().fg(theme.muted),
+            ))]
         } else {
             vec![Line::from(Span::styled(
                 "Enter a directory path above",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             ))]
         };
 
         let file_status = Paragraph::new(file_status_lines).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(Span::styled(" Files ", Style::default().fg(Color::White))),
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(" Files ", Style::default().fg(theme.text))),
         );
         frame.render_widget(file_status, chunks[5]);
 
         // Hint
         let hint = Line::from(vec![
-            Span::styled("[↑↓] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Switch option", Style::default().fg(Color::DarkGray)),
-            Span::styled("  [Enter] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Continue", Style::default().fg(Color::DarkGray)),
+            Span::styled("[↑↓] ", Style::default().fg(theme.muted)),
+            Span::styled("Switch option", Style::default().fg(theme.muted)),
+            Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+            Span::styled("Continue", Style::default().fg(theme.muted)),
         ]);
         let hint_p = Paragraph::new(hint).alignment(Alignment::Center);
         frame.render_widget(hint_p, chunks[6]);
     }
 
-    fn format_file_status<'a>(&self, name: &'a str, status: &FileStatus, _required: bool) -> Line<'a> {
+    /// Lint findings under the file list, blocking ones first.
+    fn format_issues(issues: &[FileIssue], theme: &Theme) -> Vec<Line<'static>> {
+        let mut sorted: Vec<&FileIssue> = issues.iter().collect();
+        sorted.sort_by_key(|i| !i.blocking);
+        let mut lines: Vec<Line> = sorted
+            .iter()
+            .take(MAX_ISSUE_LINES)
+            .map(|issue| {
+                let (marker, color) = if issue.blocking { (theme.err_mark(), theme.error) } else { (theme.warn_mark(), theme.warning) };
+                Line::from(vec![
+                    Span::styled(format!("{} ", marker), Style::default().fg(color)),
+                    Span::styled(format!("{}: ", issue.file), Style::default().fg(color)),
+                    Span::styled(issue.message.clone(), Style::default().fg(theme.muted)),
+                ])
+            })
+            .collect();
+        if issues.len() > MAX_ISSUE_LINES {
+            let more = issues.len() - MAX_ISSUE_LINES + 1;
+            lines.truncate(MAX_ISSUE_LINES - 1);
+            lines.push(Line::from(Span::styled(format!("  …and {} more", more), Style::default().fg(theme.muted))));
+        }
+        lines
+    }
+
+    fn format_file_status<'a>(&self, name: &'a str, status: &FileStatus, _required: bool, theme: &Theme) -> Line<'a> {
         match status {
             FileStatus::Present => Line::from(vec![
-                Span::styled("✓ ", Style::default().fg(Color::Green)),
-                Span::styled(name, Style::default().fg(Color::Green)),
+                Span::styled(format!("{} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                Span::styled(name, Style::default().fg(theme.success)),
             ]),
             FileStatus::Missing => Line::from(vec![
-                Span::styled("⚠ ", Style::default().fg(Color::Yellow)),
-                Span::styled(name, Style::default().fg(Color::Yellow)),
-                Span::styled(" (missing - will use empty)", Style::default().fg(Color::DarkGray)),
+                Span::styled("⚠ ", Style::default().fg(theme.warning)),
+                Span::styled(name, Style::default().fg(theme.warning)),
+                Span::styled(" (missing - will use empty)", Style::default().fg(theme.muted)),
             ]),
             FileStatus::RequiredMissing => Line::from(vec![
-                Span::styled("✗ ", Style::default().fg(Color::Red)),
-                Span::styled(name, Style::default().fg(Color::Red)),
-                Span::styled(" (required!)", Style::default().fg(Color::Red)),
+                Span::styled(format!("{} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(name, Style::default().fg(theme.error)),
+                Span::styled(" (required!)", Style::default().fg(theme.error)),
             ]),
         }
     }
 
-    fn render_agent_info(&self, frame: &mut Frame, area: Rect) {
+    fn render_agent_info(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
@@ -1202,147 +1815,164 @@ impl CreateScreen {
             ])
             .split(area);
 
-        // Name label
-        let name_label = Paragraph::new("Agent Name:").style(Style::default().fg(Color::White));
+        // Name label with live character counter
+        let name_label = Paragraph::new(Self::counter_label(
+            "Agent Name:",
+            self.agent_name.char_count(),
+            MAX_AGENT_NAME_LEN,
+            theme,
+        ));
         frame.render_widget(name_label, chunks[0]);
 
         // Name input
-        let name_active = self.active_field == AgentInfoField::Name;
-        let name_border_color = if name_active {
-            Color::Cyan
-        } else {
-            Color::DarkGray
-        };
-        let name_cursor = if name_active { "│" } else { "" };
+        let name_active = self.info_focus.is_focused(AgentInfoField::Name);
         let name_style = if self.moltbook_api_key.is_some() {
-            Color::Green
+            theme.success
         } else {
-            Color::Cyan
+            theme.info
         };
-        let name_input = Paragraph::new(format!("{}{}", self.agent_name, name_cursor))
+        let name_width = chunks[1].width.saturating_sub(2) as usize;
+        let name_input = Paragraph::new(self.agent_name.line(name_active, name_width, Style::default()))
             .style(Style::default().fg(name_style))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(name_border_color)),
+                    .border_style(focus::border(name_active, theme)),
             );
         frame.render_widget(name_input, chunks[1]);
 
         // Name error (inline, below name field)
         if let Some(err) = &self.name_error {
             let error_line = Paragraph::new(Line::from(vec![
-                Span::styled("✗ ", Style::default().fg(Color::Red)),
-                Span::styled(err.as_str(), Style::default().fg(Color::Red)),
+                Span::styled(format!("{} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
             ]));
             frame.render_widget(error_line, chunks[2]);
         }
 
         // Description label
-        let desc_label = Paragraph::new("Description (shown on Moltbook):")
-            .style(Style::default().fg(Color::White));
+        let desc_label = Paragraph::new(Self::counter_label(
+            "Description (shown on Moltbook):",
+            self.agent_description.char_count(),
+            MAX_AGENT_DESCRIPTION_LEN,
+            theme,
+        ));
         frame.render_widget(desc_label, chunks[3]);
 
         // Description input
-        let desc_active = self.active_field == AgentInfoField::Description;
-        let desc_border_color = if desc_active {
-            Color::Cyan
-        } else {
-            Color::DarkGray
-        };
-        let desc_cursor = if desc_active { "│" } else { "" };
+        let desc_active = self.info_focus.is_focused(AgentInfoField::Description);
         let desc_style = if self.moltbook_api_key.is_some() {
-            Color::Green
+            theme.success
         } else {
-            Color::Cyan
+            theme.info
         };
-        let desc_input = Paragraph::new(format!("{}{}", self.agent_description, desc_cursor))
+        let desc_width = chunks[4].width.saturating_sub(2) as usize;
+        let desc_input = Paragraph::new(self.agent_description.line(desc_active, desc_width, Style::default()))
             .style(Style::default().fg(desc_style))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(desc_border_color)),
+                    .border_style(focus::border(desc_active, theme)),
             );
         frame.render_widget(desc_input, chunks[4]);
 
         // Separator
         let separator = Paragraph::new("─────── or use existing API key ───────")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center);
         frame.render_widget(separator, chunks[6]);
 
         // API key label
         let api_label = Paragraph::new("Moltbook API Key (paste with Ctrl+V / Cmd+V):")
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(theme.text));
         frame.render_widget(api_label, chunks[7]);
 
         // API key input
-        let api_active = self.active_field == AgentInfoField::ApiKey;
-        let api_border_color = if api_active {
-            Color::Cyan
-        } else {
-            Color::DarkGray
-        };
-        let api_cursor = if api_active { "│" } else { "" };
-        // Mask the API key for display (show first 15 chars + ...)
-        let display_key = if self.api_key_input.len() > 20 {
-            format!("{}...{}", &self.api_key_input[..15], api_cursor)
-        } else {
-            format!("{}{}", self.api_key_input, api_cursor)
-        };
-        let api_input = Paragraph::new(display_key)
-            .style(Style::default().fg(Color::Cyan))
+        let api_active = self.info_focus.is_focused(AgentInfoField::ApiKey);
+        // Masked: one bullet per character, unless shown with Ctrl+R
+        let api_width = chunks[8].width.saturating_sub(2) as usize;
+        let api_input = Paragraph::new(self.api_key_input.line(api_active, api_width, Style::default()))
+            .style(Style::default().fg(theme.info))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(api_border_color)),
+                    .border_style(focus::border(api_active, theme)),
             );
         frame.render_widget(api_input, chunks[8]);
 
         // API key status/error
         if let Some(status) = &self.api_key_status {
             let status_line = Paragraph::new(Line::from(vec![
-                Span::styled("✓ ", Style::default().fg(Color::Green)),
-                Span::styled(status.as_str(), Style::default().fg(Color::Green)),
+                Span::styled(format!("{} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                Span::styled(status.as_str(), Style::default().fg(theme.success)),
             ]));
             frame.render_widget(status_line, chunks[9]);
         } else if let Some(err) = &self.api_key_error {
             let error_line = Paragraph::new(Line::from(vec![
-                Span::styled("✗ ", Style::default().fg(Color::Red)),
-                Span::styled(err.as_str(), Style::default().fg(Color::Red)),
+                Span::styled(format!("{} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
             ]));
             frame.render_widget(error_line, chunks[9]);
+        } else if self.clipboard_key.is_some() && api_active {
+            let offer = Paragraph::new(Line::from(vec![
+                Span::styled("API key found on the clipboard  ", Style::default().fg(theme.info)),
+                Span::styled("[Ctrl+V] ", Style::default().fg(theme.accent)),
+                Span::styled("Paste it", Style::default().fg(theme.muted)),
+            ]));
+            frame.render_widget(offer, chunks[9]);
         }
 
         // Hint
-        let hint = Line::from(vec![
-            Span::styled("[Tab] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Switch field", Style::default().fg(Color::DarkGray)),
-            Span::styled("  [Enter] ", Style::default().fg(Color::DarkGray)),
+        let mut hint = Line::from(vec![
+            Span::styled("[Tab] ", Style::default().fg(theme.muted)),
+            Span::styled("Switch field", Style::default().fg(theme.muted)),
+        ]);
+        if api_active {
+            hint.push_span(Span::styled("  [Ctrl+R] ", Style::default().fg(theme.muted)));
+            let reveal = if self.api_key_input.is_revealed() { "Hide key" } else { "Show key" };
+            hint.push_span(Span::styled(reveal, Style::default().fg(theme.muted)));
+        }
+        hint.spans.extend([
+            Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
             Span::styled(
                 if self.moltbook_api_key.is_some() {
                     "Continue"
                 } else {
                     "Register / Validate"
                 },
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             ),
         ]);
         let hint_p = Paragraph::new(hint).alignment(Alignment::Center);
         frame.render_widget(hint_p, chunks[11]);
     }
 
-    fn render_loading(&self, frame: &mut Frame, area: Rect, message: &str) {
-        let _spinner = "◐◓◑◒";
+    /// Field label followed by a "(used/max)" counter that turns yellow near the limit.
+    fn counter_label(label: &str, used: usize, max: usize, theme: &Theme) -> Line<'static> {
+        let counter_color = if used > max {
+            theme.error
+        } else if used * 10 >= max * 9 {
+            theme.warning
+        } else {
+            theme.muted
+        };
+        Line::from(vec![
+            Span::styled(label.to_string(), Style::default().fg(theme.text)),
+            Span::styled(format!(" ({}/{})", used, max), Style::default().fg(counter_color)),
+        ])
+    }
+
+    fn render_loading(&self, frame: &mut Frame, area: Rect, message: &str, spinner: &str, theme: &Theme) {
         let loading_lines = vec![
             Line::from(""),
             Line::from(""),
-            Line::from(Span::styled("⏳", Style::default().fg(Color::Yellow))),
+            Line::from(Span::styled(spinner.to_string(), Style::default().fg(theme.warning))),
             Line::from(""),
-            Line::from(Span::styled(message, Style::default().fg(Color::White))),
+            Line::from(Span::styled(message, Style::default().fg(theme.text))),
             Line::from(""),
             Line::from(Span::styled(
                 "Please wait...",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )),
         ];
 
@@ -1350,7 +1980,7 @@ impl CreateScreen {
         frame.render_widget(loading, area);
     }
 
-    fn render_waiting_claim(&self, frame: &mut Frame, area: Rect) {
+    fn render_waiting_claim(&self, frame: &mut Frame, area: Rect, spinner: &str, theme: &Theme) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
@@ -1366,20 +1996,20 @@ impl CreateScreen {
             vec![
                 Line::from(Span::styled(
                     "Verification Code",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 )),
                 Line::from(""),
                 Line::from(Span::styled(
                     code.clone(),
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.info)
                         .add_modifier(Modifier::BOLD),
                 )),
             ]
         } else {
             vec![Line::from(Span::styled(
                 "Loading...",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             ))]
         };
 
@@ -1388,7 +2018,7 @@ impl CreateScreen {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(Style::default().fg(theme.muted)),
             );
         frame.render_widget(code_box, chunks[0]);
 
@@ -1398,53 +2028,191 @@ impl CreateScreen {
                 Span::styled(
                     " [O] ",
                     Style::default()
-                        .fg(Color::LightRed)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     "Open claim URL in browser",
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme.text),
                 ),
             ]),
             Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    " [C] ",
-                    Style::default()
-                        .fg(Color::LightRed)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(
-                    "Check verification status",
-                    Style::default().fg(Color::White),
-                ),
-            ]),
+            if self.checking_claim {
+                Line::from(Span::styled(
+                    format!(" {} Checking verification status...", spinner),
+                    Style::default().fg(theme.warning),
+                ))
+            } else {
+                Line::from(vec![
+                    Span::styled(
+                        " [C] ",
+                        Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        "Check verification status",
+                        Style::default().fg(theme.text),
+                    ),
+                ])
+            },
             Line::from(""),
             Line::from(Span::styled(
                 "Post the code on Twitter, then verify on Moltbook",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )),
         ];
 
         let inst_box = Paragraph::new(instructions).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(Style::default().fg(theme.muted))
                 .title(Span::styled(
                     " Instructions ",
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme.text),
                 )),
         );
         frame.render_widget(inst_box, chunks[2]);
     }
 
-    fn render_review_soul(&self, frame: &mut Frame, area: Rect) {
-        let source = self.agent_source();
-        let soul_content = source
-            .read_file("SOUL.md")
-            .unwrap_or_else(|| "Could not read SOUL.md".to_string());
+    fn render_compile_warnings(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(6), Constraint::Length(3)])
+            .split(area);
 
-        let preview: String = soul_content.lines().take(12).collect::<Vec<_>>().join("\n");
+        let items: Vec<ListItem> = self
+            .compile_warnings
+            .iter()
+            .skip(self.warnings_scroll as usize)
+            .map(|warning| {
+                let mut lines = warning.lines();
+                let mut item = vec![Line::from(vec![
+                    Span::styled("⚠ ", Style::default().fg(theme.warning)),
+                    Span::styled(lines.next().unwrap_or_default().to_string(), Style::default().fg(theme.text)),
+                ])];
+                item.extend(lines.map(|line| Line::from(Span::styled(format!("  {}", line), Style::default().fg(theme.muted)))));
+                ListItem::new(item)
+            })
+            .collect();
+        let title = format!(
+            " Compiled with {} warning{} ({}/{}) ",
+            self.compile_warnings.len(),
+            if self.compile_warnings.len() == 1 { "" } else { "s" },
+            (self.warnings_scroll as usize + 1).min(self.compile_warnings.len()),
+            self.compile_warnings.len()
+        );
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning))
+                .title(Span::styled(title, Style::default().fg(theme.text))),
+        );
+        frame.render_widget(list, chunks[0]);
+
+        let options = Line::from(vec![
+            Span::styled(" [Y] ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            Span::styled("Deploy anyway", Style::default().fg(theme.text)),
+            Span::styled("    [j/k] ", Style::default().fg(theme.muted)),
+            Span::styled("Scroll", Style::default().fg(theme.muted)),
+            Span::styled("    [Esc] ", Style::default().fg(theme.muted)),
+            Span::styled("Back to fix them", Style::default().fg(theme.muted)),
+        ]);
+        frame.render_widget(Paragraph::new(options).alignment(Alignment::Center), chunks[1]);
+    }
+
+    fn render_review_deploy(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let network = app.config.network();
+        let tokens = |planck: u128| format!("{} {}", format_tokens(planck, network.decimals), network.token_symbol);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(12), Constraint::Length(3)])
+            .split(area);
+
+        let row = |label: &str, value: String, color| {
+            Line::from(vec![
+                Span::styled(format!("  {:<18}", label), Style::default().fg(theme.muted)),
+                Span::styled(value, Style::default().fg(color)),
+            ])
+        };
+        let code = self.compiled_hex.as_deref().unwrap_or_default();
+        let code_size = hex::decode(code.trim_start_matches("0x")).map_or(code.len() / 2, |bytes| bytes.len());
+        let moltbook = match &self.agent_id {
+            Some(id) => format!("claimed, agent id {}", id),
+            None => "not claimed".to_string(),
+        };
+        let schedule = match self.schedule_option {
+            Some(blocks) => format!("every {} blocks (~{} min)", blocks, blocks / 10),
+            None => "none, runs only when prompted".to_string(),
+        };
+        let balance = match app.usd_value(&format_tokens(self.value_planck, network.decimals)) {
+            Some(usd) => format!("{} ({})", tokens(self.value_planck), usd),
+            None => tokens(self.value_planck),
+        };
+        let (fee, total) = match &self.deploy_fee {
+            None => (format!("{} estimating...", app.spinner()), None),
+            Some(Ok(fee)) => (format!("~{}", tokens(*fee)), Some(self.value_planck.saturating_add(*fee))),
+            Some(Err(e)) => (format!("unknown: {}", e), None),
+        };
+
+        let mut lines = vec![
+            row("Agent", self.agent_name.text().to_string(), theme.text),
+            row("Moltbook", moltbook, if self.agent_id.is_some() { theme.success } else { theme.warning }),
+            row("Schedule", schedule, theme.text),
+            row("Initial balance", balance, theme.text),
+            row("Code size", format!("{} bytes", code_size), theme.text),
+            row("Code hash", versions::code_hash(code), theme.text),
+            row("Estimated fee", fee, if matches!(self.deploy_fee, Some(Err(_))) { theme.warning } else { theme.text }),
+        ];
+        if let Some(total) = total {
+            let problem = app.funds().check_wallet(total).err();
+            lines.push(row(
+                "Deposit + fee",
+                match &problem {
+                    Some(problem) => format!("~{}: {}", tokens(total), problem),
+                    None => format!("~{} from the wallet", tokens(total)),
+                },
+                if problem.is_some() { theme.error } else { theme.text },
+            ));
+        }
+        lines.push(row("Signer", app.wallet_address().unwrap_or("no wallet").to_string(), theme.info));
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<18}", "Network"), Style::default().fg(theme.muted)),
+            network_badge(network.kind, theme),
+            Span::styled(format!(" {}", network.name), Style::default().fg(theme.text)),
+            Span::styled(
+                Some(&app.config.server_url).filter(|url| !url.is_empty()).map_or(String::new(), |url| format!(" at {}", url)),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+
+        let summary = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent))
+                .title(Span::styled(" Review before signing ", Style::default().fg(theme.text))),
+        );
+        frame.render_widget(summary, chunks[0]);
+
+        if let Some(confirm) = &self.amount_confirm {
+            let lines = confirm.lines(theme, network.decimals, &network.token_symbol);
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), chunks[1]);
+            return;
+        }
+        let options = Line::from(vec![
+            Span::styled(" [Y] ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            Span::styled("Sign and deploy", Style::default().fg(theme.text)),
+            Span::styled("    [Esc] ", Style::default().fg(theme.muted)),
+            Span::styled("Back to the schedule", Style::default().fg(theme.muted)),
+        ]);
+        frame.render_widget(Paragraph::new(options).alignment(Alignment::Center), chunks[1]);
+    }
+
+    fn render_review_soul(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let source = self.agent_source();
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -1452,20 +2220,43 @@ impl CreateScreen {
             .constraints([Constraint::Min(8), Constraint::Length(3)])
             .split(area);
 
-        let content = Paragraph::new(preview)
-            .style(Style::default().fg(Color::White))
-            .wrap(Wrap { trim: true })
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::DarkGray))
-                    .title(Span::styled(
-                        " SOUL.md Preview ",
-                        Style::default().fg(Color::White),
-                    )),
-            );
+        let content = if self.show_program {
+            let program = source
+                .read_file("moltbook_agent.ship")
+                .unwrap_or_else(|| "// Could not read moltbook_agent.ship".to_string());
+            Paragraph::new(crate::highlight::highlight_ship(&program, theme))
+                .scroll((self.program_scroll, 0))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(theme.muted))
+                        .title(Span::styled(
+                            " moltbook_agent.ship ",
+                            Style::default().fg(theme.text),
+                        )),
+                )
+        } else {
+            let soul_content = source
+                .read_file("SOUL.md")
+                .unwrap_or_else(|| "Could not read SOUL.md".to_string());
+            let preview: String = soul_content.lines().take(12).collect::<Vec<_>>().join("\n");
+            Paragraph::new(preview)
+                .style(Style::default().fg(theme.text))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(theme.muted))
+                        .title(Span::styled(
+                            " SOUL.md Preview ",
+                            Style::default().fg(theme.text),
+                        )),
+                )
+        };
         frame.render_widget(content, chunks[0]);
 
+        let toggle = if self.show_program { "Show SOUL.md" } else { "Show program" };
+
         // Edit option only available for custom directory
         let can_edit = matches!(source, AgentSource::Custom(_));
         let options = if can_edit {
@@ -1473,32 +2264,48 @@ impl CreateScreen {
                 Span::styled(
                     " [Y] ",
                     Style::default()
-                        .fg(Color::LightRed)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled("Continue", Style::default().fg(Color::White)),
+                Span::styled("Continue", Style::default().fg(theme.text)),
                 Span::styled("    ", Style::default()),
                 Span::styled(
                     " [E] ",
                     Style::default()
-                        .fg(Color::LightRed)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled("Edit in $EDITOR", Style::default().fg(Color::White)),
+                Span::styled("Edit in $EDITOR", Style::default().fg(theme.text)),
+                Span::styled("    ", Style::default()),
+                Span::styled(
+                    " [Tab] ",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(toggle, Style::default().fg(theme.text)),
             ])
         } else {
             Line::from(vec![
                 Span::styled(
                     " [Y] ",
                     Style::default()
-                        .fg(Color::LightRed)
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("Continue", Style::default().fg(theme.text)),
+                Span::styled("    ", Style::default()),
+                Span::styled(
+                    " [Tab] ",
+                    Style::default()
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled("Continue", Style::default().fg(Color::White)),
+                Span::styled(toggle, Style::default().fg(theme.text)),
                 Span::styled("    ", Style::default()),
                 Span::styled(
                     "(using embedded defaults)",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 ),
             ])
         };
@@ -1506,8 +2313,9 @@ impl CreateScreen {
         frame.render_widget(options_p, chunks[1]);
     }
 
-    fn render_configure_schedule(&self, frame: &mut Frame, area: Rect) {
-        let options = vec![
+    /// `usd` is the approximate value of the initial balance, when a price feed is set.
+    fn render_configure_schedule(&self, frame: &mut Frame, area: Rect, usd: Option<String>, faucet: bool, theme: &Theme) {
+        let options = [
             "Never (only runs when prompted)",
             "Every 30 minutes",
             "Every 1 hour",
@@ -1531,48 +2339,41 @@ impl CreateScreen {
             .split(area);
 
         let help = Paragraph::new("How often should your agent check in?")
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(theme.text));
         frame.render_widget(help, chunks[0]);
 
         // Build schedule options with custom minutes input inline
-        let schedule_active = self.schedule_field == ScheduleField::Schedule;
-        let schedule_border = if schedule_active { Color::Cyan } else { Color::DarkGray };
+        let schedule_active = self.schedule_focus.is_focused(ScheduleField::Schedule);
         
         let items: Vec<ListItem> = options
             .iter()
             .enumerate()
             .map(|(i, label)| {
-                let is_selected = i == self.selected_schedule;
+                let is_selected = i == self.selected_schedule.selected();
                 let (prefix, style) = if is_selected {
                     (
                         "● ",
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme.info)
                             .add_modifier(Modifier::BOLD),
                     )
                 } else {
-                    ("○ ", Style::default().fg(Color::White))
+                    ("○ ", Style::default().fg(theme.text))
                 };
                 
                 // For custom option, show the input field inline
                 if i == 4 {
-                    let custom_active = self.schedule_field == ScheduleField::CustomMinutes;
-                    let cursor = if custom_active { "│" } else { "" };
+                    let custom_active = self.schedule_focus.is_focused(ScheduleField::CustomMinutes);
                     let input_style = if custom_active {
-                        Style::default().fg(Color::Cyan)
+                        Style::default().fg(theme.info)
                     } else {
-                        Style::default().fg(Color::DarkGray)
+                        Style::default().fg(theme.muted)
                     };
                     
-                    ListItem::new(Line::from(vec![
-                        Span::styled(prefix, style),
-                        Span::styled("Custom: ", style),
-                        Span::styled(
-                            format!("{}{}", self.custom_minutes_input, cursor),
-                            input_style,
-                        ),
-                        Span::styled(" minutes", Style::default().fg(Color::DarkGray)),
-                    ]))
+                    let mut spans = vec![Span::styled(prefix, style), Span::styled("Custom: ", style)];
+                    spans.extend(self.custom_minutes_input.line(custom_active, 10, input_style).spans);
+                    spans.push(Span::styled(" minutes", Style::default().fg(theme.muted)));
+                    ListItem::new(Line::from(spans))
                 } else {
                     ListItem::new(Line::from(vec![
                         Span::styled(prefix, style),
@@ -1585,23 +2386,21 @@ impl CreateScreen {
         let list = List::new(items).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(schedule_border))
+                .border_style(focus::border(schedule_active, theme))
                 .title(Span::styled(
                     " Schedule ",
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme.text),
                 )),
         );
         frame.render_widget(list, chunks[1]);
 
         // Balance input section
-        let balance_active = self.schedule_field == ScheduleField::Balance;
-        let balance_border = if balance_active { Color::Cyan } else { Color::DarkGray };
-        let balance_cursor = if balance_active { "│" } else { "" };
-        
+        let balance_active = self.schedule_focus.is_focused(ScheduleField::Balance);
+        let balance_style = Style::default().fg(theme.info);
         let balance_display = if self.balance_input.is_empty() {
-            format!("1.0{} (default)", balance_cursor)
+            Line::from(Span::styled(format!("1.0{} (default)", focus::cursor(balance_active)), balance_style))
         } else {
-            format!("{}{}", self.balance_input, balance_cursor)
+            self.balance_input.line(balance_active, 30, balance_style)
         };
         
         let balance_chunks = Layout::default()
@@ -1610,73 +2409,89 @@ impl CreateScreen {
             .split(chunks[3]);
             
         let balance_label = Paragraph::new("Initial balance for agent (in UNITS):")
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(theme.text));
         frame.render_widget(balance_label, balance_chunks[0]);
         
-        let balance_input = Paragraph::new(balance_display)
-            .style(Style::default().fg(Color::Cyan))
+        let mut balance_spans = balance_display.spans;
+        if let Some(usd) = usd {
+            balance_spans.push(Span::styled(format!("  {}", usd), Style::default().fg(theme.muted)));
+        }
+        let balance_input = Paragraph::new(Line::from(balance_spans))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(balance_border)),
+                    .border_style(focus::border(balance_active, theme)),
             );
         frame.render_widget(balance_input, balance_chunks[1]);
 
         // Balance error
-        if let Some(err) = &self.balance_error {
+        if self.waiting_for_tokens {
+            let waiting = Paragraph::new(Line::from(Span::styled(
+                "○ Waiting for test tokens from the faucet...",
+                Style::default().fg(theme.warning),
+            )));
+            frame.render_widget(waiting, chunks[4]);
+        } else if let Some(err) = &self.balance_error {
             let error_line = Paragraph::new(Line::from(vec![
-                Span::styled("✗ ", Style::default().fg(Color::Red)),
-                Span::styled(err.as_str(), Style::default().fg(Color::Red)),
+                Span::styled(format!("{} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
             ]));
             frame.render_widget(error_line, chunks[4]);
         }
 
         // Info text about scheduled runs
-        let info_text = if self.selected_schedule == 0 {
+        let info_text = if self.selected_schedule.selected() == 0 {
             "Agent will only run when you prompt it manually."
         } else {
             "Scheduled runs cost gas. Ensure agent has enough balance."
         };
         let info = Paragraph::new(vec![
-            Line::from(Span::styled(info_text, Style::default().fg(Color::Yellow))),
+            Line::from(Span::styled(info_text, Style::default().fg(theme.warning))),
             Line::from(Span::styled(
                 "Tip: Keep some balance in your wallet for future deployments.",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )),
         ])
         .wrap(Wrap { trim: true });
         frame.render_widget(info, chunks[5]);
 
-        let hint = Line::from(vec![
-            Span::styled("[↑↓] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Navigate", Style::default().fg(Color::DarkGray)),
-            Span::styled("  [Tab] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Switch field", Style::default().fg(Color::DarkGray)),
-            Span::styled("  [Enter] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Deploy", Style::default().fg(Color::DarkGray)),
-        ]);
+        let mut hint = vec![
+            Span::styled("[↑↓] ", Style::default().fg(theme.muted)),
+            Span::styled("Navigate", Style::default().fg(theme.muted)),
+            Span::styled("  [Tab] ", Style::default().fg(theme.muted)),
+            Span::styled("Switch field", Style::default().fg(theme.muted)),
+            Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+            Span::styled("Deploy", Style::default().fg(theme.muted)),
+        ];
+        if faucet && self.schedule_focus.is_focused(ScheduleField::Balance) {
+            hint.push(Span::styled("  [f] ", Style::default().fg(theme.muted)));
+            hint.push(Span::styled("Test tokens", Style::default().fg(theme.muted)));
+        }
+        let hint = Line::from(hint);
         let hint_p = Paragraph::new(hint).alignment(Alignment::Center);
         frame.render_widget(hint_p, chunks[6]);
     }
 
-    fn render_success(&self, frame: &mut Frame, area: Rect) {
+    fn render_success(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(2)
             .constraints([
                 Constraint::Length(3), // Success header
                 Constraint::Length(4), // Address
+                Constraint::Length(self.receipt.as_ref().map_or(0, receipt::height)), // Transaction
                 Constraint::Min(3),    // Message
             ])
             .split(area);
 
         // Success header
         let header = Paragraph::new(Line::from(vec![
-            Span::styled("✓ ", Style::default().fg(Color::Green)),
+            Span::styled(format!("{} ", theme.ok_mark()), Style::default().fg(theme.success)),
             Span::styled(
                 "AGENT DEPLOYED SUCCESSFULLY",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
         ]))
@@ -1688,27 +2503,188 @@ impl CreateScreen {
             let addr_lines = vec![
                 Line::from(Span::styled(
                     "Agent Address",
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 )),
-                Line::from(Span::styled(addr.clone(), Style::default().fg(Color::Cyan))),
+                Line::from(Span::styled(addr.clone(), Style::default().fg(theme.info))),
             ];
             let addr_box = Paragraph::new(addr_lines)
                 .alignment(Alignment::Center)
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::DarkGray)),
+                        .border_style(Style::default().fg(theme.muted)),
                 );
             frame.render_widget(addr_box, chunks[1]);
         }
 
+        if let Some(receipt) = &self.receipt {
+            receipt::render_receipt(frame, chunks[2], receipt, app);
+        }
+
         // Continue message
         let msg = Paragraph::new(Line::from(vec![
-            Span::styled("Press ", Style::default().fg(Color::DarkGray)),
-            Span::styled("[Enter]", Style::default().fg(Color::White)),
-            Span::styled(" to continue", Style::default().fg(Color::DarkGray)),
+            Span::styled("Press ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.text)),
+            Span::styled(" to continue", Style::default().fg(theme.muted)),
         ]))
         .alignment(Alignment::Center);
-        frame.render_widget(msg, chunks[2]);
+        frame.render_widget(msg, chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppScreen;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app};
+    use crate::config::NetworkProfile;
+    use crate::spending::SpendingLimits;
+
+    async fn next_message(rx: &mut mpsc::Receiver<AppMessage>) -> AppMessage {
+        tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv()).await.unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_draft_resume() {
+        let mut screen = CreateScreen::new();
+        screen.step = CreateStep::EnterAgentInfo;
+        screen.agent_name.set_text("pinchy");
+        // Nothing registered yet, nothing worth keeping
+        assert_eq!(screen.draft(), None);
+
+        screen.handle_moltbook_registered("key".to_string(), "https://claim".to_string(), "reef-42".to_string());
+        let draft = screen.draft().unwrap();
+        let mut resumed = CreateScreen::new();
+        resumed.resume(draft.clone());
+        assert_eq!(resumed.step, CreateStep::WaitingClaim);
+        assert_eq!(resumed.draft(), Some(draft));
+
+        resumed.handle_moltbook_claimed("agent-1".to_string());
+        let mut claimed = CreateScreen::new();
+        claimed.resume(resumed.draft().unwrap());
+        assert_eq!(claimed.step, CreateStep::ReviewSoul);
+    }
+
+    #[test]
+    fn test_balance_parsed_exactly() {
+        let funds = Funds::new(Some(10 * UNIT_PLANCK), None, 0, &NetworkProfile::local());
+        let mut screen = CreateScreen::new();
+        assert_eq!(screen.validate_balance(&funds), Ok(UNIT_PLANCK));
+
+        for c in "0.1000000000001".chars() {
+            screen.balance_input.handle_key(KeyCode::Char(c).into());
+        }
+        assert_eq!(screen.validate_balance(&funds), Err("The network's token has at most 12 decimals".to_string()));
+        screen.balance_input.handle_key(KeyCode::Backspace.into());
+        assert_eq!(screen.validate_balance(&funds), Ok(100_000_000_000));
+
+        let mut dot = CreateScreen::new();
+        dot.balance_input.handle_key(KeyCode::Char('.').into());
+        assert_eq!(dot.validate_balance(&funds), Err(". is not a token amount".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_key_pasted_with_one_key() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut screen = CreateScreen::new();
+        screen.step = CreateStep::EnterAgentInfo;
+        // Focusing the key field looks at the clipboard
+        screen.handle_agent_info_key(KeyCode::BackTab.into(), &crate::testing::mock_client(), tx.clone()).await.unwrap();
+        assert!(matches!(next_message(&mut rx).await, AppMessage::Create(CreateMessage::ClipboardKey(_))));
+
+        screen.clipboard_key = Some("moltbook_sk_4f9c2a7e81b3d6f0".to_string());
+        let paste = KeyEvent::new(KeyCode::Char('v'), crossterm::event::KeyModifiers::CONTROL);
+        screen.handle_agent_info_key(paste, &crate::testing::mock_client(), tx).await.unwrap();
+        assert_eq!(screen.api_key_input.text(), "moltbook_sk_4f9c2a7e81b3d6f0");
+        assert!(screen.clipboard_key.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_claim_then_compile() {
+        let client = crate::testing::mock_client();
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut screen = CreateScreen::new();
+        // The compile is saved; keep it out of the real data directory
+        screen.artifacts_dir = std::env::temp_dir().join(format!("lobster-artifacts-{}", std::process::id()));
+        screen.step = CreateStep::WaitingClaim;
+        screen.agent_name.set_text("pinchy");
+        screen.moltbook_api_key = Some("moltbook_key".to_string());
+
+        screen.handle_waiting_claim_key(KeyCode::Char('c'), client.clone(), tx.clone()).await.unwrap();
+        let AppMessage::Create(CreateMessage::MoltbookClaimed { agent_id }) = next_message(&mut rx).await else {
+            panic!("expected the claim to be stored");
+        };
+        screen.handle_moltbook_claimed(agent_id);
+        assert_eq!(screen.step, CreateStep::ReviewSoul);
+
+        screen.start_compilation(client, tx.clone()).await.unwrap();
+        let AppMessage::Create(CreateMessage::CompileDone { compiled_hex, warnings }) = next_message(&mut rx).await else {
+            panic!("expected the embedded agent to compile");
+        };
+        screen.handle_compile_done(compiled_hex, warnings);
+        assert!(screen.compiled_hex.is_some());
+        assert_eq!(std::fs::read_dir(&screen.artifacts_dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&screen.artifacts_dir).unwrap();
+        // Nothing is signed until the review is confirmed
+        assert_eq!(screen.step, CreateStep::ReviewDeploy);
+
+        let wallet = WalletConfig::generate().unwrap();
+        screen.start_fee_estimate(crate::testing::mock_client(), wallet.clone(), tx.clone());
+        let AppMessage::Create(CreateMessage::DeployFeeEstimated(fee)) = next_message(&mut rx).await else {
+            panic!("expected a fee estimate");
+        };
+        assert!(fee.is_ok_and(|fee| fee > 0));
+        let funds = Funds::new(None, None, 0, &NetworkProfile::local());
+        screen.handle_review_deploy_key(KeyCode::Enter.into(), &crate::testing::mock_client(), &funds, Some(&wallet), tx.clone());
+        assert_eq!(screen.step, CreateStep::ReviewDeploy);
+        // A deposit over the limit waits for its amount typed out
+        let limits = SpendingLimits { per_transaction: Some("0.5".to_string()), per_day: None };
+        let limited = funds.clone().with_limits(&limits);
+        screen.handle_review_deploy_key(KeyCode::Char('y').into(), &crate::testing::mock_client(), &limited, Some(&wallet), tx.clone());
+        assert!(screen.amount_confirm.is_some());
+        screen.handle_review_deploy_key(KeyCode::Esc.into(), &crate::testing::mock_client(), &limited, Some(&wallet), tx.clone());
+        assert_eq!((screen.amount_confirm.is_none(), &screen.step), (true, &CreateStep::ReviewDeploy));
+        screen.handle_review_deploy_key(KeyCode::Char('y').into(), &crate::testing::mock_client(), &funds, Some(&wallet), tx);
+        assert_eq!(screen.step, CreateStep::Deploying);
+    }
+
+    #[test]
+    fn test_compile_warnings_wait_for_the_user() {
+        let (tx, _rx) = mpsc::channel(8);
+        let mut screen = CreateScreen::new();
+        let warnings = vec!["unused variable `mood`".to_string(), "loop could be unrolled".to_string()];
+        screen.handle_compile_done("0x00".to_string(), warnings);
+        assert_eq!(screen.step, CreateStep::CompileWarnings);
+
+        screen.handle_compile_warnings_key(KeyCode::Char('j'), &crate::testing::mock_client(), None, tx.clone());
+        assert_eq!(screen.warnings_scroll, 1);
+        // Deploying needs a wallet to sign with
+        screen.handle_compile_warnings_key(KeyCode::Enter, &crate::testing::mock_client(), None, tx.clone());
+        assert_eq!(screen.step, CreateStep::CompileWarnings);
+        assert!(screen.error.is_some());
+
+        screen.handle_compile_warnings_key(KeyCode::Esc, &crate::testing::mock_client(), None, tx);
+        assert_eq!(screen.step, CreateStep::ConfigureSchedule);
+        assert_eq!(screen.compiled_hex, None);
+    }
+
+    #[test]
+    fn test_create_select_source() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Create;
+        assert_snapshot("create_select_source", &screen_text(&mut app));
+    }
+
+    #[test]
+    fn test_create_review_deploy() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Create;
+        app.create.step = CreateStep::ReviewDeploy;
+        app.create.agent_name.set_text("pinchy");
+        app.create.agent_id = Some("agent-1".to_string());
+        app.create.compiled_hex = Some("0x0061736d01000000".to_string());
+        app.create.value_planck = 5_000_000_000_000;
+        app.create.deploy_fee = Some(Ok(10_000_000_000));
+        assert_snapshot("create_review_deploy", &screen_text(&mut app));
     }
 }
@@ -0,0 +1,105 @@
+//! Error details modal - the full error text, scrollable, with a copy action.
+
+use crate::app::App;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+pub struct ErrorModal {
+    /// Full error, including its chain of causes
+    pub text: String,
+    /// Screen the error was raised on, for the title and bug reports
+    pub source: &'static str,
+    pub scroll: u16,
+    /// Feedback for the last copy
+    pub status: Option<String>,
+}
+
+impl ErrorModal {
+    pub fn new(text: String, source: &'static str) -> Self {
+        Self { text, source, scroll: 0, status: None }
+    }
+
+    /// Handle a key; returns true when the modal should close.
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::Char('c') | KeyCode::Char('y') => {
+                self.status = Some(match crate::clipboard::copy(&self.report()) {
+                    Ok(()) => "Copied to the clipboard".to_string(),
+                    Err(e) => format!("Copy failed: {}", e),
+                });
+            }
+            _ => return true,
+        }
+        false
+    }
+
+    /// Text put on the clipboard, with enough context for a bug report.
+    fn report(&self) -> String {
+        format!("lobster {} ({})\n{}", env!("CARGO_PKG_VERSION"), self.source, self.text)
+    }
+}
+
+/// Draw the modal centered on top of the current screen.
+///
+/// Takes the app mutably to keep the scroll offset inside the wrapped text.
+pub fn render_error_modal(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme;
+    let Some(modal) = app.error_modal.as_mut() else {
+        return;
+    };
+
+    let width = area.width.saturating_sub(8).min(100);
+    let height = area.height.saturating_sub(4).min(24);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    // Wrapped line count, to stop scrolling once the end is visible
+    let text_width = width.saturating_sub(4).max(1) as usize;
+    let wrapped: usize = modal.text.lines().map(|l| l.chars().count().div_ceil(text_width).max(1)).sum();
+    let visible = height.saturating_sub(4) as usize;
+    modal.scroll = modal.scroll.min(wrapped.saturating_sub(visible) as u16);
+
+    let lines: Vec<Line> = modal
+        .text
+        .lines()
+        .map(|l| Line::from(Span::styled(l, Style::default().fg(theme.text))))
+        .collect();
+    let footer = match &modal.status {
+        Some(status) => Line::from(Span::styled(format!(" {} ", status), Style::default().fg(theme.success))),
+        None => Line::from(Span::styled(
+            " [j/k] Scroll  [c] Copy  [Esc] Close ",
+            Style::default().fg(theme.muted),
+        )),
+    };
+
+    let body = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((modal.scroll, 0))
+        .alignment(Alignment::Left)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.error))
+            .title(Span::styled(
+                format!(" Error: {} ", modal.source),
+                Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+            ))
+            .title_bottom(footer.right_aligned())
+            .padding(Padding::uniform(1)));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(body, popup);
+}
@@ -0,0 +1,300 @@
+//! Feed screen - browse the agent's Moltbook home feed.
+
+use crate::{
+    app::{App, AppMessage, MessageContext, ScreenAction},
+    client::{ApiClient, MoltbookPost},
+    screens::{input::SelectList, Screen},
+    text::truncate_width,
+};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+/// Number of feed posts requested per page.
+const FEED_PAGE_SIZE: u32 = 25;
+
+/// Start loading the next page when the selection is this close to the end.
+const PREFETCH_THRESHOLD: usize = 5;
+
+/// Feed orderings supported by Moltbook.
+const SORTS: [&str; 4] = ["hot", "new", "top", "rising"];
+
+/// Background results for the feed.
+#[derive(Debug, Clone)]
+pub enum FeedMessage {
+    /// Page of the agent's home feed fetched (offset 0 replaces the list)
+    FeedFetched { posts: Vec<MoltbookPost>, offset: u32, has_more: bool },
+    /// Feed fetch failed
+    FeedFetchFailed(String),
+}
+
+pub struct FeedScreen {
+    pub posts: Vec<MoltbookPost>,
+    pub selected: SelectList,
+    /// Index into `SORTS`
+    pub sort: usize,
+    pub loading: bool,
+    pub loading_more: bool,
+    pub has_more: bool,
+    pub error: Option<String>,
+}
+
+impl FeedScreen {
+    pub fn new() -> Self {
+        Self {
+            posts: Vec::new(),
+            selected: SelectList::new(),
+            sort: 0,
+            loading: false,
+            loading_more: false,
+            has_more: false,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn handle_key(
+        &mut self,
+        key: KeyCode,
+        client: &ApiClient,
+        api_key: Option<&str>,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> Result<ScreenAction> {
+        match key {
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            key if self.selected.handle_key(key, self.posts.len()) => {}
+            KeyCode::Char('s') => {
+                self.sort = (self.sort + 1) % SORTS.len();
+                self.refresh(client, api_key, tx);
+                return Ok(ScreenAction::None);
+            }
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                self.refresh(client, api_key, tx);
+                return Ok(ScreenAction::None);
+            }
+            KeyCode::Enter | KeyCode::Char('p') => {
+                if let Some(post) = self.posts.get(self.selected.selected()) {
+                    return Ok(ScreenAction::PromptAgent(prompt_for_post(post)));
+                }
+            }
+            _ => {}
+        }
+
+        self.maybe_fetch_more(client, api_key, tx);
+        Ok(ScreenAction::None)
+    }
+
+    /// Reload the feed from the first page.
+    pub fn refresh(&mut self, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        let Some(api_key) = api_key else {
+            self.error = Some("No Moltbook API key stored for this agent".to_string());
+            return;
+        };
+        self.loading = true;
+        self.loading_more = false;
+        self.error = None;
+        self.selected = SelectList::new();
+        Self::fetch_page(client.clone(), api_key.to_string(), SORTS[self.sort], 0, tx);
+    }
+
+    fn maybe_fetch_more(&mut self, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        let Some(api_key) = api_key else {
+            return;
+        };
+        let near_end = self.selected.selected() + PREFETCH_THRESHOLD >= self.posts.len();
+        if near_end && self.has_more && !self.loading_more && !self.loading {
+            self.loading_more = true;
+            let offset = self.posts.len() as u32;
+            Self::fetch_page(client.clone(), api_key.to_string(), SORTS[self.sort], offset, tx);
+        }
+    }
+
+    fn fetch_page(client: ApiClient, api_key: String, sort: &'static str, offset: u32, tx: mpsc::Sender<AppMessage>) {
+        crate::tasks::spawn(async move {
+            match client.get_feed(&api_key, sort, offset, FEED_PAGE_SIZE).await {
+                Ok(page) => {
+                    let _ = tx
+                        .send(FeedMessage::FeedFetched { posts: page.posts, offset, has_more: page.has_more }.into())
+                        .await;
+                }
+                Err(e) => {
+                    let _ = tx.send(FeedMessage::FeedFetchFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
+    }
+
+    pub fn handle_feed(&mut self, posts: Vec<MoltbookPost>, offset: u32, has_more: bool) {
+        if offset == 0 {
+            self.posts = posts;
+        } else {
+            self.posts.extend(posts);
+        }
+        self.has_more = has_more;
+        self.loading = false;
+        self.loading_more = false;
+        self.selected.clamp(self.posts.len());
+    }
+
+    pub fn handle_fetch_error(&mut self, error: String) {
+        self.error = Some(error);
+        self.loading = false;
+        self.loading_more = false;
+    }
+
+    pub fn handle_message(&mut self, msg: FeedMessage, _ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            FeedMessage::FeedFetched { posts, offset, has_more } => self.handle_feed(posts, offset, has_more),
+            FeedMessage::FeedFetchFailed(e) => self.handle_fetch_error(e),
+        }
+        Ok(ScreenAction::None)
+    }
+}
+
+/// Prompt text asking the agent to engage with a feed post.
+fn prompt_for_post(post: &MoltbookPost) -> String {
+    let author = post.author.as_ref().map(|a| a.name.as_str()).unwrap_or("unknown");
+    let submolt = post.submolt.as_ref().map(|s| s.name.as_str()).unwrap_or("general");
+    let title = post.title.as_deref().or(post.content.as_deref()).unwrap_or("");
+    format!(
+        "Read the Moltbook post \"{}\" by {} in m/{} (post id {}) and respond to it if you have something to add.",
+        truncate_width(title, 120),
+        author,
+        submolt,
+        post.id
+    )
+}
+
+impl Screen for FeedScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(6),    // Posts
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let agent = app.agent_name().unwrap_or("agent");
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" FEED ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(agent, Style::default().fg(theme.accent)),
+            Span::styled(format!(" • sorted by {}", SORTS[self.sort]), Style::default().fg(theme.muted)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        let block = |title: String| {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(title, Style::default().fg(theme.text)))
+        };
+
+        if self.loading {
+            let loading = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("{} Loading feed...", app.spinner()), Style::default().fg(theme.warning))),
+            ])
+            .alignment(Alignment::Center)
+            .block(block(" Feed ".to_string()));
+            frame.render_widget(loading, chunks[1]);
+        } else if self.posts.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled("The feed is empty", Style::default().fg(theme.muted))),
+                Line::from(Span::styled(
+                    "Subscribe to submolts or follow agents to fill it",
+                    Style::default().fg(theme.muted),
+                )),
+            ])
+            .alignment(Alignment::Center)
+            .block(block(" Feed ".to_string()));
+            frame.render_widget(empty, chunks[1]);
+        } else {
+            let preview_width = chunks[1].width.saturating_sub(6) as usize;
+            let items: Vec<ListItem> = self
+                .posts
+                .iter()
+                .map(|p| {
+                    let submolt = p.submolt.as_ref().map(|s| s.name.as_str()).unwrap_or("general");
+                    let author = p.author.as_ref().map(|a| a.name.as_str()).unwrap_or("unknown");
+                    let text = p.title.as_deref().or(p.content.as_deref()).unwrap_or("");
+                    ListItem::new(vec![
+                        Line::from(vec![
+                            Span::styled(format!("  m/{} ", submolt), Style::default().fg(theme.accent)),
+                            Span::styled("• ", Style::default().fg(theme.muted)),
+                            Span::styled(author, Style::default().fg(theme.info)),
+                            Span::styled(" • ", Style::default().fg(theme.muted)),
+                            Span::styled(format!("↑{}", p.upvotes), Style::default().fg(theme.success)),
+                            Span::styled(format!(" ↓{}", p.downvotes), Style::default().fg(theme.error)),
+                            Span::styled(format!(" • {} comments", p.comment_count), Style::default().fg(theme.muted)),
+                        ]),
+                        Line::from(Span::styled(
+                            format!("  {}", truncate_width(text, preview_width)),
+                            Style::default().fg(theme.text),
+                        )),
+                        Line::from(""),
+                    ])
+                })
+                .collect();
+
+            let count = if self.has_more {
+                format!("{}/{}+", self.selected.selected() + 1, self.posts.len())
+            } else {
+                format!("{}/{}", self.selected.selected() + 1, self.posts.len())
+            };
+            let title = if self.loading_more {
+                format!(" Feed ({}) loading more... ", count)
+            } else {
+                format!(" Feed ({}) ", count)
+            };
+
+            let list = List::new(items)
+                .block(block(title))
+                .highlight_symbol("▌")
+                .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+            let mut state = self.selected.state();
+            frame.render_stateful_widget(list, chunks[1], &mut state);
+        }
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Navigate", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Prompt agent about post", Style::default().fg(theme.muted)),
+                Span::styled("  [S] ", Style::default().fg(theme.muted)),
+                Span::styled("Sort", Style::default().fg(theme.muted)),
+                Span::styled("  [R] ", Style::default().fg(theme.muted)),
+                Span::styled("Refresh", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back", Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[2]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Keyboard focus across a form's inputs.
+//!
+//! A form registers its fields in Tab order in a `FocusRing`, hands it the
+//! keys it doesn't use itself, and asks it which field to draw focused.
+//! Focused inputs share one look: an accent border and a text cursor.
+
+use crate::theme::Theme;
+use crossterm::event::KeyCode;
+use ratatui::style::Style;
+
+/// A form's fields in Tab order and the one with focus.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusRing<F> {
+    fields: Vec<F>,
+    current: usize,
+}
+
+impl<F: Copy + PartialEq> FocusRing<F> {
+    /// Focus starts on the first field.
+    pub fn new(fields: impl Into<Vec<F>>) -> Self {
+        let fields = fields.into();
+        assert!(!fields.is_empty(), "a focus ring needs a field");
+        Self { fields, current: 0 }
+    }
+
+    pub fn current(&self) -> F {
+        self.fields[self.current]
+    }
+
+    /// Position of the focused field in Tab order.
+    pub fn index(&self) -> usize {
+        self.current
+    }
+
+    pub fn is_focused(&self, field: F) -> bool {
+        self.current() == field
+    }
+
+    /// Move focus to `field`; ignored when it isn't registered.
+    pub fn focus(&mut self, field: F) {
+        if let Some(i) = self.fields.iter().position(|f| *f == field) {
+            self.current = i;
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.current = (self.current + 1) % self.fields.len();
+    }
+
+    pub fn prev(&mut self) {
+        self.current = (self.current + self.fields.len() - 1) % self.fields.len();
+    }
+
+    /// Register a new set of fields, for forms whose inputs come and go.
+    /// The focused field keeps focus when it is still there.
+    pub fn set_fields(&mut self, fields: impl Into<Vec<F>>) {
+        let current = self.current();
+        *self = Self::new(fields);
+        self.focus(current);
+    }
+
+    /// Tab and Shift-Tab move focus; true when the key was one of them.
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Tab => self.next(),
+            KeyCode::BackTab => self.prev(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Border of an input, highlighted while it has focus.
+pub fn border(focused: bool, theme: &Theme) -> Style {
+    Style::default().fg(if focused { theme.accent } else { theme.muted })
+}
+
+/// Text cursor drawn after a focused input's text.
+pub fn cursor(focused: bool) -> &'static str {
+    if focused { "│" } else { "" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_ring_cycles_registered_fields() {
+        let mut ring = FocusRing::new(['a', 'b', 'c']);
+        assert!(ring.handle_key(KeyCode::BackTab));
+        assert_eq!(ring.current(), 'c');
+        assert!(ring.handle_key(KeyCode::Tab));
+        assert!(!ring.handle_key(KeyCode::Enter));
+        assert!(ring.is_focused('a'));
+
+        ring.focus('z');
+        assert_eq!(ring.current(), 'a');
+        ring.focus('c');
+        ring.set_fields(['a', 'c']);
+        assert_eq!((ring.current(), ring.index()), ('c', 1));
+        ring.set_fields(['a', 'b']);
+        assert_eq!(ring.current(), 'a');
+    }
+}
@@ -0,0 +1,96 @@
+//! Help overlay - the key bindings active on the current screen.
+
+use crate::{app::App, config::KeyPreset, keymap};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Draw the overlay centered on top of the current screen.
+pub fn render_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let (title, bindings) = keymap::active_bindings(app);
+
+    let vim = if app.config.keybindings.preset == KeyPreset::Vim { keymap::VIM_PRESET } else { &[] };
+    let key_width = bindings.iter().chain(vim).map(|(keys, _)| keys.chars().count()).max().unwrap_or(0).max(7);
+    let mut lines = vec![Line::from("")];
+    if bindings.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  Nothing to press right now - waiting for a background task.",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    for (keys, action) in &bindings {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:>width$}  ", keys, width = key_width),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(*action, Style::default().fg(theme.text)),
+        ]));
+    }
+
+    if !vim.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("  Vim preset, outside text fields", Style::default().fg(theme.muted))));
+        for (keys, action) in vim {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:>width$}  ", keys, width = key_width), Style::default().fg(theme.muted)),
+                Span::styled(*action, Style::default().fg(theme.muted)),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(format!("  {:>width$}  ", "? / F1", width = key_width), Style::default().fg(theme.muted)),
+        Span::styled("Toggle this help", Style::default().fg(theme.muted)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(format!("  {:>width$}  ", "Shift+N", width = key_width), Style::default().fg(theme.muted)),
+        Span::styled("Notification history", Style::default().fg(theme.muted)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(format!("  {:>width$}  ", "!", width = key_width), Style::default().fg(theme.muted)),
+        Span::styled("Full text of the latest error, with copy", Style::default().fg(theme.muted)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(format!("  {:>width$}  ", "Ctrl+P", width = key_width), Style::default().fg(theme.muted)),
+        Span::styled("Command palette", Style::default().fg(theme.muted)),
+    ]));
+    if matches!(app.screen, crate::app::AppScreen::Home | crate::app::AppScreen::Onboarding) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:>width$}  ", app.config.keybindings.quit, width = key_width),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled("Quit", Style::default().fg(theme.muted)),
+        ]));
+    }
+
+    let width = area.width.min(72);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let help = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .alignment(Alignment::Left)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(Span::styled(
+                format!(" Keys: {} ", title),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ))
+            .title_bottom(Line::from(Span::styled(" any key to close ", Style::default().fg(theme.muted))).right_aligned()));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(help, popup);
+}
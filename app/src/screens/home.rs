@@ -1,9 +1,14 @@
 //! Home screen with Proof of Lobster branding.
 
-use crate::{app::App, screens::Screen};
+use crate::{
+    app::App,
+    screens::{heartbeat_span, network_badge, Screen},
+    text::{shorten_middle, truncate_width},
+    theme::Theme,
+};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Padding, Paragraph},
     Frame,
@@ -63,6 +68,9 @@ const LOBSTER_ASCII: &str = r#"
                   ████████              
 "#;
 
+/// Rows of the banner header (image + title).
+const BANNER_HEIGHT: u16 = 16;
+
 pub struct HomeScreen;
 
 impl HomeScreen {
@@ -73,23 +81,29 @@ impl HomeScreen {
 
 impl Screen for HomeScreen {
     fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
         // This is the fallback render - the main render is render_home_with_image
+        let collapsed = banner_collapsed(app, area);
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
             .constraints(vec![
-                Constraint::Length(14), // Header
-                Constraint::Length(5),  // Status
+                Constraint::Length(if collapsed { 1 } else { 14 }), // Header
+                Constraint::Length(status_height(app)), // Status
                 Constraint::Min(6),     // Menu
                 Constraint::Length(2),  // Footer
             ])
             .split(area);
 
-        // Header - ASCII fallback
-        let header = Paragraph::new(LOBSTER_ASCII)
-            .style(Style::default().fg(Color::Red))
-            .alignment(Alignment::Center);
-        frame.render_widget(header, chunks[0]);
+        if collapsed {
+            render_compact_title(frame, chunks[0], theme);
+        } else {
+            // Header - ASCII fallback
+            let header = Paragraph::new(LOBSTER_ASCII)
+                .style(Style::default().fg(theme.error))
+                .alignment(Alignment::Center);
+            frame.render_widget(header, chunks[0]);
+        }
 
         render_status_menu_footer(frame, &chunks, app);
     }
@@ -97,19 +111,29 @@ impl Screen for HomeScreen {
 
 /// Render the home screen with mutable access to app (for image state).
 pub fn render_home_with_image(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme;
+    let collapsed = banner_collapsed(app, area);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints(vec![
-            Constraint::Length(16), // Banner header (image + title)
-            Constraint::Length(5),  // Status
+            Constraint::Length(if collapsed { 1 } else { BANNER_HEIGHT }), // Banner header (image + title)
+            Constraint::Length(status_height(app)), // Status
             Constraint::Min(6),     // Menu
             Constraint::Length(2),  // Footer
         ])
         .split(area);
 
+    if collapsed {
+        render_compact_title(frame, chunks[0], &theme);
+        render_status_menu_footer(frame, &chunks, app);
+        return;
+    }
+
     // Banner area - split horizontally: image left, title right
-    if let Some(ref mut image_state) = app.lobster_image {
+    // (image cells would be garbled by the ASCII glyph pass)
+    let show_image = !app.ascii && (app.banner.is_some() || app.banner_loading);
+    if show_image {
         let banner_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(vec![
@@ -118,15 +142,18 @@ pub fn render_home_with_image(frame: &mut Frame, area: Rect, app: &mut App) {
             ])
             .split(chunks[0]);
 
-        // Render the image on the left
+        // Render the image on the left, once decoded and encoded for this
+        // size (see `App::prepare_banner`); blank until then
         let image_area = Rect::new(
             banner_chunks[0].x + 1,
             banner_chunks[0].y,
             banner_chunks[0].width.saturating_sub(2).min(32),
             banner_chunks[0].height,
         );
-        let image_widget = StatefulImage::default();
-        frame.render_stateful_widget(image_widget, image_area, image_state);
+        if let Some(image_state) = app.banner.as_mut().and_then(|b| b.frame(image_area)) {
+            let image_widget = StatefulImage::default();
+            frame.render_stateful_widget(image_widget, image_area, image_state);
+        }
 
         // Render ASCII title on the right - choose based on width
         let title_area = banner_chunks[1];
@@ -141,7 +168,7 @@ pub fn render_home_with_image(frame: &mut Frame, area: Rect, app: &mut App) {
         };
 
         let title = Paragraph::new(title_text)
-            .style(Style::default().fg(Color::LightRed))
+            .style(Style::default().fg(theme.accent))
             .alignment(Alignment::Left);
         frame.render_widget(title, title_area);
     } else {
@@ -152,12 +179,12 @@ pub fn render_home_with_image(frame: &mut Frame, area: Rect, app: &mut App) {
             .split(chunks[0]);
 
         let lobster = Paragraph::new(LOBSTER_ASCII)
-            .style(Style::default().fg(Color::Red))
+            .style(Style::default().fg(theme.error))
             .alignment(Alignment::Center);
         frame.render_widget(lobster, fallback_chunks[0]);
 
         let title = Paragraph::new(TITLE_COMPACT)
-            .style(Style::default().fg(Color::LightRed))
+            .style(Style::default().fg(theme.accent))
             .alignment(Alignment::Left);
         frame.render_widget(title, fallback_chunks[1]);
     }
@@ -165,8 +192,37 @@ pub fn render_home_with_image(frame: &mut Frame, area: Rect, app: &mut App) {
     render_status_menu_footer(frame, &chunks, app);
 }
 
+/// Whether the banner gives way to a one-line title: hidden with `B`, or
+/// the terminal is too short for it above the whole menu.
+fn banner_collapsed(app: &App, area: Rect) -> bool {
+    let menu_height = menu_items(app, &app.theme).len() as u16 + 2;
+    // Margins and footer around the banner, status and menu
+    let needed = 2 + BANNER_HEIGHT + status_height(app) + menu_height + 2;
+    app.config.banner_hidden || area.height < needed
+}
+
+/// Title line shown in place of the banner.
+fn render_compact_title(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled("🦞 PROOF OF LOBSTER", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        Span::styled("  Deploy AI Agents on Theseus", Style::default().fg(theme.muted)),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(title, area);
+}
+
+/// Status box height: borders and three lines, plus the failure alert.
+fn status_height(app: &App) -> u16 {
+    if app.config.is_authenticated() && app.failure_alert().is_some() {
+        6
+    } else {
+        5
+    }
+}
+
 /// Helper to render the status, menu, and footer sections
 fn render_status_menu_footer(frame: &mut Frame, chunks: &[Rect], app: &App) {
+    let theme = &app.theme;
     // Status section
     let auth_icon = if app.config.is_authenticated() {
         "●"
@@ -179,37 +235,41 @@ fn render_status_menu_footer(frame: &mut Frame, chunks: &[Rect], app: &App) {
         "Not logged in"
     };
     let auth_color = if app.config.is_authenticated() {
-        Color::Green
+        theme.success
     } else {
-        Color::Yellow
+        theme.warning
     };
 
     // Only show agent info when authenticated
     let (agent_text, agent_color) = if app.config.is_authenticated() {
         if let Some(addr) = app.agent_address() {
-            let short = if addr.len() > 20 {
-                format!("{}...{}", &addr[..10], &addr[addr.len() - 8..])
-            } else {
-                addr.to_string()
-            };
-            (format!("● Agent: {}", short), Color::Green)
+            let short = shorten_middle(addr, 10, 8);
+            match app.agent_name() {
+                Some(name) => (
+                    format!("● Agent: {} ({})", truncate_width(name, 24), short),
+                    theme.success,
+                ),
+                None => (format!("● Agent: {}", short), theme.success),
+            }
         } else {
-            ("○ No agent deployed".to_string(), Color::DarkGray)
+            ("○ No agent deployed".to_string(), theme.muted)
         }
     } else {
         // Not authenticated - don't show agent status
-        ("".to_string(), Color::DarkGray)
+        ("".to_string(), theme.muted)
     };
 
+    let network = app.config.network();
     let status_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(theme.muted))
         .title(Span::styled(
             " Status ",
             Style::default()
-                .fg(Color::White)
+                .fg(theme.text)
                 .add_modifier(Modifier::BOLD),
         ))
+        .title(Line::from(network_badge(network.kind, theme)).right_aligned())
         .padding(Padding::horizontal(1));
 
     // Build status lines
@@ -220,34 +280,44 @@ fn render_status_menu_footer(frame: &mut Frame, chunks: &[Rect], app: &App) {
 
     // Only show wallet if authenticated
     if let Some(wallet_short) = app.wallet_short_address() {
-        status_lines.push(Line::from(vec![
-            Span::styled("◈ ", Style::default().fg(Color::Cyan)),
+        let mut wallet_line = vec![
+            Span::styled("◈ ", Style::default().fg(theme.info)),
             Span::styled(
                 format!("Wallet: {}", wallet_short),
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.info),
             ),
-        ]));
-
-        // Show balance if available
-        if let Some(balance) = &app.wallet_balance {
-            status_lines.push(Line::from(Span::styled(
-                format!("  Balance: {} THE", balance),
-                Style::default().fg(Color::Yellow),
-            )));
-        } else {
-            status_lines.push(Line::from(Span::styled(
-                "  Balance: loading...".to_string(),
-                Style::default().fg(Color::DarkGray),
-            )));
+        ];
+        if let Some(balance) = app.wallet_balance.as_ref().map(|b| b.balance_formatted.as_str()) {
+            wallet_line.push(Span::styled(
+                format!("  {} {}", balance, network.token_symbol),
+                Style::default().fg(theme.warning),
+            ));
+            if let Some(usd) = app.usd_value(balance) {
+                wallet_line.push(Span::styled(format!(" ({})", usd), Style::default().fg(theme.muted)));
+            }
         }
+        status_lines.push(Line::from(wallet_line));
     }
 
     // Only show agent line if authenticated
     if !agent_text.is_empty() {
-        status_lines.push(Line::from(Span::styled(
-            &agent_text,
-            Style::default().fg(agent_color),
-        )));
+        let mut agent_line = vec![Span::styled(&agent_text, Style::default().fg(agent_color))];
+        if let Some(heartbeat) = app.heartbeat() {
+            agent_line.push(Span::raw("  "));
+            agent_line.push(heartbeat_span(heartbeat.status(app.block_number), theme));
+        }
+        status_lines.push(Line::from(agent_line));
+        if let Some(alert) = app.failure_alert() {
+            let error_style = Style::default().fg(theme.error);
+            status_lines.push(Line::from(vec![
+                Span::styled(format!("⚠ {} scheduled runs failed in a row", alert.count), error_style.add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(" - last: {}", truncate_width(alert.last_error.as_deref().unwrap_or("no reason given"), 60)),
+                    error_style,
+                ),
+                Span::styled(format!("  [F] Open run #{}", alert.run_id), Style::default().fg(theme.muted)),
+            ]));
+        }
     }
 
     let status_content = Paragraph::new(status_lines).block(status_block);
@@ -257,15 +327,54 @@ fn render_status_menu_footer(frame: &mut Frame, chunks: &[Rect], app: &App) {
     // Menu section
     let menu_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(theme.muted))
         .title(Span::styled(
             " Menu ",
             Style::default()
-                .fg(Color::White)
+                .fg(theme.text)
                 .add_modifier(Modifier::BOLD),
         ))
         .padding(Padding::horizontal(1));
 
+    let menu = List::new(menu_items(app, theme)).block(menu_block);
+    frame.render_widget(menu, chunks[2]);
+
+    // Footer - status messages or help
+    let footer_content = if let Some(err) = &app.error_message {
+        Line::from(vec![
+            Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+            Span::styled(err.as_str(), Style::default().fg(theme.error)),
+        ])
+    } else if let Some(status) = &app.status_message {
+        Line::from(vec![
+            Span::styled(format!(" {} ", theme.ok_mark()), Style::default().fg(theme.success)),
+            Span::styled(status.as_str(), Style::default().fg(theme.success)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" [0-9] ", Style::default().fg(theme.muted)),
+            Span::styled("Select option", Style::default().fg(theme.muted)),
+            Span::styled("  •  ", Style::default().fg(theme.muted)),
+            Span::styled("[?] ", Style::default().fg(theme.muted)),
+            Span::styled("Help", Style::default().fg(theme.muted)),
+            Span::styled("  •  ", Style::default().fg(theme.muted)),
+            Span::styled("[B] ", Style::default().fg(theme.muted)),
+            Span::styled("Banner", Style::default().fg(theme.muted)),
+            Span::styled("  •  ", Style::default().fg(theme.muted)),
+            Span::styled(
+                format!("[{}] ", app.config.keybindings.quit.to_ascii_uppercase()),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled("Quit", Style::default().fg(theme.muted)),
+        ])
+    };
+
+    let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+    frame.render_widget(footer, chunks[3]);
+}
+
+/// Entries of the home menu for the current login and agent.
+fn menu_items(app: &App, theme: &Theme) -> Vec<ListItem<'static>> {
     let mut items = Vec::new();
 
     if !app.config.is_authenticated() {
@@ -273,26 +382,26 @@ fn render_status_menu_footer(frame: &mut Frame, chunks: &[Rect], app: &App) {
             Span::styled(
                 " [1] ",
                 Style::default()
-                    .fg(Color::LightRed)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("Login with Email", Style::default().fg(Color::White)),
-            Span::styled(" (magic link)", Style::default().fg(Color::DarkGray)),
+            Span::styled("Login with Email", Style::default().fg(theme.text)),
+            Span::styled(" (magic link)", Style::default().fg(theme.muted)),
         ])));
         items.push(ListItem::new(Line::from(vec![
-            Span::styled(" [2] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Login with Twitter", Style::default().fg(Color::DarkGray)),
-            Span::styled(" (coming soon)", Style::default().fg(Color::DarkGray)),
+            Span::styled(" [2] ", Style::default().fg(theme.muted)),
+            Span::styled("Login with Twitter", Style::default().fg(theme.muted)),
+            Span::styled(" (coming soon)", Style::default().fg(theme.muted)),
         ])));
     } else {
         items.push(ListItem::new(Line::from(vec![
             Span::styled(
                 " [1] ",
                 Style::default()
-                    .fg(Color::LightRed)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("Create New Agent", Style::default().fg(Color::White)),
+            Span::styled("Create New Agent", Style::default().fg(theme.text)),
         ])));
 
         if app.config.has_agent() {
@@ -300,52 +409,112 @@ fn render_status_menu_footer(frame: &mut Frame, chunks: &[Rect], app: &App) {
                 Span::styled(
                     " [2] ",
                     Style::default()
-                        .fg(Color::LightRed)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled("Prompt Agent", Style::default().fg(Color::White)),
+                Span::styled("Prompt Agent", Style::default().fg(theme.text)),
             ])));
             items.push(ListItem::new(Line::from(vec![
                 Span::styled(
                     " [3] ",
                     Style::default()
-                        .fg(Color::LightRed)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled("View Agent Details", Style::default().fg(Color::White)),
+                Span::styled("View Agent Details", Style::default().fg(theme.text)),
             ])));
+            // Acting as the agent on Moltbook needs its API key
+            let has_api_key = app.config.moltbook_api_key.is_some();
+            items.push(api_key_menu_item("[5]", "New Post", has_api_key, theme));
+            items.push(api_key_menu_item("[6]", "Browse Feed", has_api_key, theme));
+            items.push(api_key_menu_item("[7]", "Submolts", has_api_key, theme));
+            items.push(api_key_menu_item("[0]", "Inbox", has_api_key, theme));
         }
 
         items.push(ListItem::new(Line::from(vec![
-            Span::styled(" [4] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Logout", Style::default().fg(Color::DarkGray)),
+            Span::styled(" [L] ", Style::default().fg(theme.muted)),
+            Span::styled("Leaderboard", Style::default().fg(theme.muted)),
+        ])));
+
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(" [4] ", Style::default().fg(theme.muted)),
+            Span::styled("Logout", Style::default().fg(theme.muted)),
         ])));
     }
 
-    let menu = List::new(items).block(menu_block);
-    frame.render_widget(menu, chunks[2]);
+    items.push(ListItem::new(Line::from(vec![
+        Span::styled(" [D] ", Style::default().fg(theme.muted)),
+        Span::styled("Compare Runs", Style::default().fg(theme.muted)),
+    ])));
 
-    // Footer - status messages or help
-    let footer_content = if let Some(err) = &app.error_message {
-        Line::from(vec![
-            Span::styled(" ✗ ", Style::default().fg(Color::Red)),
-            Span::styled(err.as_str(), Style::default().fg(Color::Red)),
-        ])
-    } else if let Some(status) = &app.status_message {
-        Line::from(vec![
-            Span::styled(" ✓ ", Style::default().fg(Color::Green)),
-            Span::styled(status.as_str(), Style::default().fg(Color::Green)),
-        ])
+    items.push(ListItem::new(Line::from(vec![
+        Span::styled(" [8] ", Style::default().fg(theme.muted)),
+        Span::styled("Settings", Style::default().fg(theme.muted)),
+    ])));
+
+    if app.wallet_address().is_some() && app.config.network().kind.has_faucet() {
+        let label = if app.faucet.is_some() { "Waiting for test tokens..." } else { "Request test tokens" };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(" [9] ", Style::default().fg(theme.muted)),
+            Span::styled(label, Style::default().fg(theme.muted)),
+        ])));
+    }
+    items
+}
+
+/// Menu item that is only available when the agent's Moltbook API key is stored.
+fn api_key_menu_item(key: &'static str, label: &'static str, enabled: bool, theme: &Theme) -> ListItem<'static> {
+    if enabled {
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                format!(" {} ", key),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(label, Style::default().fg(theme.text)),
+        ]))
     } else {
-        Line::from(vec![
-            Span::styled(" [1-4] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Select option", Style::default().fg(Color::DarkGray)),
-            Span::styled("  •  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("[Q] ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Quit", Style::default().fg(Color::DarkGray)),
-        ])
-    };
+        ListItem::new(Line::from(vec![
+            Span::styled(format!(" {} ", key), Style::default().fg(theme.muted)),
+            Span::styled(label, Style::default().fg(theme.muted)),
+            Span::styled(" (no API key stored)", Style::default().fg(theme.muted)),
+        ]))
+    }
+}
 
-    let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
-    frame.render_widget(footer, chunks[3]);
+#[cfg(test)]
+mod tests {
+    use crate::health::FailureAlert;
+    use crate::heartbeat::Heartbeat;
+    use crate::testing::{app, assert_snapshot, config, screen_text, signed_in_app, AGENT_ADDRESS};
+
+    #[test]
+    fn test_home_signed_out() {
+        let mut app = app(config());
+        assert_snapshot("home_signed_out", &screen_text(&mut app));
+    }
+
+    #[test]
+    fn test_home_signed_in() {
+        let mut app = signed_in_app();
+        assert_snapshot("home_signed_in", &screen_text(&mut app));
+    }
+
+    #[test]
+    fn test_home_heartbeat_running() {
+        let mut app = signed_in_app();
+        app.block_number = Some(1002);
+        let heartbeat = Heartbeat { every: 600, last: 1000, running: Some(7), paused: false };
+        app.heartbeat = Some((AGENT_ADDRESS.to_string(), heartbeat));
+        assert_snapshot("home_heartbeat", &screen_text(&mut app));
+    }
+
+    #[test]
+    fn test_home_failure_alert() {
+        let mut app = signed_in_app();
+        let alert = FailureAlert { count: 3, run_id: 41, last_error: Some("Out of gas".to_string()) };
+        app.run_failures = Some((AGENT_ADDRESS.to_string(), alert));
+        assert_snapshot("home_failure_alert", &screen_text(&mut app));
+    }
 }
@@ -0,0 +1,444 @@
+//! Inbox screen - mentions, replies and direct messages addressed to the agent.
+
+use crate::{
+    app::{App, AppMessage, MessageContext, ScreenAction},
+    client::ApiClient,
+    moltbook::{InboxItem, InboxKind},
+    screens::{
+        input::SelectList,
+        thread::{CommentThread, ThreadMessage},
+        Screen,
+    },
+    text::truncate_width,
+};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+/// Number of inbox items requested.
+const INBOX_LIMIT: u32 = 50;
+
+/// Background results for the inbox.
+#[derive(Debug, Clone)]
+pub enum InboxMessage {
+    /// Inbox fetched; unread items are being marked read
+    InboxFetched(Vec<InboxItem>),
+    /// Inbox fetch failed
+    InboxFetchFailed(String),
+    /// Marking fetched items read failed (they stay unread on Moltbook)
+    MarkReadFailed(String),
+    /// Comments of the post a mention or reply is on
+    Thread(ThreadMessage),
+}
+
+pub struct InboxScreen {
+    pub items: Vec<InboxItem>,
+    pub selected: SelectList,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Comment thread of the selected item's post, shown instead of the list
+    pub thread: Option<CommentThread>,
+}
+
+impl InboxScreen {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            selected: SelectList::new(),
+            loading: false,
+            error: None,
+            thread: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn handle_key(
+        &mut self,
+        key: KeyCode,
+        client: &ApiClient,
+        api_key: Option<&str>,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> Result<ScreenAction> {
+        if let Some(thread) = &mut self.thread {
+            match key {
+                KeyCode::Esc => self.thread = None,
+                KeyCode::Char('p') => {
+                    if let Some(item) = self.items.get(self.selected.selected()) {
+                        return Ok(ScreenAction::PromptAgent(prompt_for_item(item)));
+                    }
+                }
+                _ => {
+                    thread.handle_key(key);
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        match key {
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            key if self.selected.handle_key(key, self.items.len()) => {}
+            KeyCode::Char('r') | KeyCode::Char('R') => self.refresh(client, api_key, tx),
+            KeyCode::Enter if self.items.get(self.selected.selected()).is_some_and(|i| i.kind != InboxKind::Dm && i.post_id.is_some()) => {
+                let item = &self.items[self.selected.selected()];
+                let post_id = item.post_id.as_deref().unwrap_or_default();
+                let mut thread = CommentThread::new(post_id, item.comment_id.clone());
+                thread.fetch(client, api_key, tx, |m| InboxMessage::Thread(m).into());
+                self.thread = Some(thread);
+            }
+            KeyCode::Enter | KeyCode::Char('p') => {
+                if let Some(item) = self.items.get(self.selected.selected()) {
+                    return Ok(ScreenAction::PromptAgent(prompt_for_item(item)));
+                }
+            }
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    /// Reload the inbox, then mark what was unread as read.
+    pub fn refresh(&mut self, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        let Some(api_key) = api_key.map(str::to_string) else {
+            self.error = Some("No Moltbook API key stored for this agent".to_string());
+            return;
+        };
+        self.loading = true;
+        self.error = None;
+        let client = client.clone();
+        crate::tasks::spawn(async move {
+            let items = match client.get_inbox(&api_key, INBOX_LIMIT).await {
+                Ok(items) => items,
+                Err(e) => {
+                    let _ = tx.send(InboxMessage::InboxFetchFailed(format!("{:#}", e)).into()).await;
+                    return;
+                }
+            };
+            let unread: Vec<String> = items.iter().filter(|i| !i.is_read).map(|i| i.id.clone()).collect();
+            let _ = tx.send(InboxMessage::InboxFetched(items).into()).await;
+            if unread.is_empty() {
+                return;
+            }
+            if let Err(e) = client.mark_inbox_read(&api_key, &unread).await {
+                let _ = tx.send(InboxMessage::MarkReadFailed(format!("{:#}", e)).into()).await;
+            }
+        });
+    }
+
+    pub fn handle_items(&mut self, items: Vec<InboxItem>) {
+        self.items = items;
+        self.loading = false;
+        self.selected.clamp(self.items.len());
+    }
+
+    pub fn handle_fetch_error(&mut self, error: String) {
+        self.error = Some(error);
+        self.loading = false;
+    }
+
+    /// Items that arrived unread on this visit (still highlighted after being marked read).
+    pub fn new_count(&self) -> usize {
+        self.items.iter().filter(|i| !i.is_read).count()
+    }
+
+    pub fn handle_message(&mut self, msg: InboxMessage, _ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            InboxMessage::InboxFetched(items) => self.handle_items(items),
+            InboxMessage::InboxFetchFailed(e) => self.handle_fetch_error(e),
+            InboxMessage::MarkReadFailed(e) => {
+                self.error = Some(format!("Couldn't mark items read: {}", e));
+            }
+            InboxMessage::Thread(msg) => {
+                if let Some(thread) = &mut self.thread {
+                    thread.handle_message(msg);
+                }
+            }
+        }
+        Ok(ScreenAction::None)
+    }
+}
+
+fn author_name(item: &InboxItem) -> &str {
+    item.author.as_ref().map(|a| a.name.as_str()).unwrap_or("unknown")
+}
+
+/// Short description of where an item came from, e.g. `mention on "Title"`.
+fn describe(item: &InboxItem) -> String {
+    let on_post = |what: &str| match item.post_title.as_deref() {
+        Some(title) => format!("{} on \"{}\"", what, truncate_width(title, 40)),
+        None => what.to_string(),
+    };
+    match item.kind {
+        InboxKind::Mention => on_post("mention"),
+        InboxKind::Reply => on_post("reply"),
+        InboxKind::Dm => "direct message".to_string(),
+        InboxKind::Other => on_post("notification"),
+    }
+}
+
+/// Where on Moltbook the item lives, for the agent to find it.
+fn location(item: &InboxItem) -> String {
+    let mut ids = Vec::new();
+    if let Some(post_id) = &item.post_id {
+        ids.push(format!("post id {}", post_id));
+    }
+    if let Some(comment_id) = &item.comment_id {
+        ids.push(format!("comment id {}", comment_id));
+    }
+    if let Some(conversation_id) = &item.conversation_id {
+        ids.push(format!("conversation id {}", conversation_id));
+    }
+    if ids.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", ids.join(", "))
+    }
+}
+
+/// Prompt text asking the agent to answer an inbox item.
+fn prompt_for_item(item: &InboxItem) -> String {
+    let author = author_name(item);
+    let content = truncate_width(&item.content, 280);
+    let post = item
+        .post_title
+        .as_deref()
+        .map(|t| format!(" \"{}\"", truncate_width(t, 120)))
+        .unwrap_or_default();
+    match item.kind {
+        InboxKind::Mention => format!(
+            "{} mentioned you on the Moltbook post{}{}: \"{}\". Reply with a comment if you have something to add.",
+            author, post, location(item), content
+        ),
+        InboxKind::Reply => format!(
+            "{} replied to you on the Moltbook post{}{}: \"{}\". Reply with a comment if you have something to add.",
+            author, post, location(item), content
+        ),
+        InboxKind::Dm => format!(
+            "{} sent you a direct message on Moltbook{}: \"{}\". Write them a reply.",
+            author, location(item), content
+        ),
+        InboxKind::Other => format!(
+            "You have a Moltbook notification from {}{}: \"{}\". Respond to it if it calls for a response.",
+            author, location(item), content
+        ),
+    }
+}
+
+impl Screen for InboxScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(6),    // Items
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let agent = app.agent_name().unwrap_or("agent");
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" INBOX ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(agent, Style::default().fg(theme.accent)),
+            Span::styled(format!(" • {} new", self.new_count()), Style::default().fg(theme.muted)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(format!(" Inbox ({}) ", self.items.len()), Style::default().fg(theme.text)));
+
+        if let Some(thread) = &self.thread {
+            let post = self.items.get(self.selected.selected()).and_then(|i| i.post_title.as_deref()).unwrap_or("Comments");
+            thread.render(frame, chunks[1], &truncate_width(post, 60), app.spinner(), theme);
+        } else if self.loading {
+            let loading = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("{} Loading inbox...", app.spinner()), Style::default().fg(theme.warning))),
+            ])
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(loading, chunks[1]);
+        } else if self.items.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled("Nothing addressed to the agent yet", Style::default().fg(theme.muted))),
+            ])
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(empty, chunks[1]);
+        } else {
+            let preview_width = chunks[1].width.saturating_sub(6) as usize;
+            let items: Vec<ListItem> = self
+                .items
+                .iter()
+                .map(|item| {
+                    let (marker, marker_color) = if item.is_read { ("  ", theme.muted) } else { ("● ", theme.accent) };
+                    let kind_color = match item.kind {
+                        InboxKind::Dm => theme.info,
+                        _ => theme.secondary,
+                    };
+                    let mut header = vec![
+                        Span::styled(format!("  {}", marker), Style::default().fg(marker_color)),
+                        Span::styled(author_name(item), Style::default().fg(theme.info)),
+                        Span::styled(" • ", Style::default().fg(theme.muted)),
+                        Span::styled(describe(item), Style::default().fg(kind_color)),
+                    ];
+                    if let Some(created_at) = &item.created_at {
+                        header.push(Span::styled(format!(" • {}", created_at), Style::default().fg(theme.muted)));
+                    }
+                    ListItem::new(vec![
+                        Line::from(header),
+                        Line::from(Span::styled(
+                            format!("    {}", truncate_width(&item.content, preview_width)),
+                            Style::default().fg(theme.text),
+                        )),
+                        Line::from(""),
+                    ])
+                })
+                .collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_symbol("▌")
+                .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+            let mut state = self.selected.state();
+            frame.render_stateful_widget(list, chunks[1], &mut state);
+        }
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else if self.thread.is_some() {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Navigate", Style::default().fg(theme.muted)),
+                Span::styled("  [Space] ", Style::default().fg(theme.muted)),
+                Span::styled("Fold", Style::default().fg(theme.muted)),
+                Span::styled("  [P] ", Style::default().fg(theme.muted)),
+                Span::styled("Prompt agent to respond", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back to inbox", Style::default().fg(theme.muted)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Navigate", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Thread", Style::default().fg(theme.muted)),
+                Span::styled("  [P] ", Style::default().fg(theme.muted)),
+                Span::styled("Prompt agent to respond", Style::default().fg(theme.muted)),
+                Span::styled("  [R] ", Style::default().fg(theme.muted)),
+                Span::styled("Refresh", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back", Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppScreen;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app};
+
+    fn item(json: serde_json::Value) -> InboxItem {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_prompt_for_mention() {
+        let mention = item(serde_json::json!({
+            "id": "n1",
+            "type": "mention",
+            "author": { "name": "crabby" },
+            "content": "what does @pinchy think?",
+            "post_id": "p1",
+            "post_title": "Shell prices",
+            "comment_id": "c1"
+        }));
+        assert_eq!(
+            prompt_for_item(&mention),
+            "crabby mentioned you on the Moltbook post \"Shell prices\" (post id p1, comment id c1): \
+             \"what does @pinchy think?\". Reply with a comment if you have something to add."
+        );
+        assert_eq!(describe(&mention), "mention on \"Shell prices\"");
+    }
+
+    #[test]
+    fn test_prompt_for_dm() {
+        let dm = item(serde_json::json!({
+            "id": "n2",
+            "type": "dm",
+            "author": { "name": "crabby" },
+            "content": "hi",
+            "conversation_id": "conv9",
+            "is_read": true
+        }));
+        assert_eq!(
+            prompt_for_item(&dm),
+            "crabby sent you a direct message on Moltbook (conversation id conv9): \"hi\". Write them a reply."
+        );
+    }
+
+    #[test]
+    fn test_unknown_kind() {
+        let other = item(serde_json::json!({ "id": "n3", "type": "follow" }));
+        assert_eq!(other.kind, InboxKind::Other);
+        assert_eq!(describe(&other), "notification");
+        assert!(!other.is_read);
+    }
+
+    #[test]
+    fn test_inbox_comment_thread() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Inbox;
+        let reply = serde_json::json!({
+            "id": "n1", "type": "reply", "author": { "name": "crabby" }, "content": "Not so fast",
+            "post_id": "p1", "post_title": "Shell prices", "comment_id": "c3", "is_read": true,
+        });
+        app.inbox.items = vec![serde_json::from_value(reply).unwrap()];
+        let comments = serde_json::json!([
+            { "id": "c1", "content": "Shells are up again this week, and nobody saw it coming.", "upvotes": 4,
+              "author": { "name": "pinchy" }, "replies": [
+                { "id": "c2", "content": "Tide was low", "upvotes": 2, "author": { "name": "clawdia" }, "replies": [
+                    { "id": "c3", "content": "Not so fast", "downvotes": 1, "author": { "name": "crabby" } },
+                ] },
+            ] },
+            { "id": "c4", "content": "Buying the dip", "author": { "name": "shrimpy" }, "replies": [
+                { "id": "c5", "content": "Same" },
+            ] },
+        ]);
+        let mut thread = CommentThread::new("p1", Some("c3".to_string()));
+        thread.handle_message(ThreadMessage::CommentsFetched {
+            post_id: "p1".to_string(),
+            comments: serde_json::from_value(comments).unwrap(),
+        });
+        assert_eq!(thread.selected.selected(), 2);
+        thread.handle_key(KeyCode::End);
+        thread.handle_key(KeyCode::Up);
+        thread.handle_key(KeyCode::Char(' '));
+        app.inbox.thread = Some(thread);
+        assert_snapshot("inbox_comment_thread", &screen_text(&mut app));
+    }
+}
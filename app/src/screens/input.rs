@@ -0,0 +1,447 @@
+//! Input widgets shared by the screens' forms.
+//!
+//! `TextInput` edits a line of text with a movable cursor and a selection
+//! (Shift with the arrows, Home or End; Ctrl+A selects everything), and can
+//! mask what it holds, shown while focused after Ctrl+R. `NumberInput` only takes a non-negative amount with
+//! at most so many decimals, within bounds. `SelectList` is the cursor over
+//! a list whose items the screen owns.
+//!
+//! The inputs take the whole `KeyEvent`, since Shift and Ctrl change what
+//! a key does; screens forward the event they were given.
+
+use crate::screens::focus;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::ListState,
+};
+use std::ops::Range;
+
+/// Whether Ctrl was held with `key`, for screens binding Ctrl keys of their own.
+pub fn control(key: KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Drawn in place of each character of a masked input.
+const MASK: char = '•';
+
+/// A single line of editable text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextInput {
+    text: String,
+    /// Cursor position, in chars
+    cursor: usize,
+    /// Where the selection started, when there is one
+    anchor: Option<usize>,
+    masked: bool,
+    /// A masked input's text is shown while it has focus
+    revealed: bool,
+    max_chars: Option<usize>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starting with `text`, the cursor at its end.
+    pub fn with_text(text: impl Into<String>) -> Self {
+        let mut input = Self::new();
+        input.set_text(text);
+        input
+    }
+
+    /// Draw a bullet per character instead of the text.
+    pub fn masked(mut self) -> Self {
+        self.masked = true;
+        self
+    }
+
+    /// Stop taking characters past `max`. Longer text can still be set.
+    pub fn max_chars(mut self, max: usize) -> Self {
+        self.max_chars = Some(max);
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    /// Mask the text again, as when the input loses focus.
+    pub fn hide(&mut self) {
+        self.revealed = false;
+    }
+
+    /// Replace the text, with the cursor at its end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.char_count();
+        self.anchor = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_text(String::new());
+    }
+
+    /// The text, leaving the input empty.
+    pub fn take(&mut self) -> String {
+        let text = std::mem::take(&mut self.text);
+        self.clear();
+        text
+    }
+
+    /// The selected chars, when any are.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|&a| a != self.cursor)?;
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.char_count();
+    }
+
+    fn byte_index(&self, chars: usize) -> usize {
+        self.text.char_indices().nth(chars).map_or(self.text.len(), |(i, _)| i)
+    }
+
+    /// Remove the selection, leaving the cursor where it was; false when
+    /// nothing was selected.
+    fn delete_selection(&mut self) -> bool {
+        let Some(range) = self.selection() else {
+            self.anchor = None;
+            return false;
+        };
+        let bytes = self.byte_index(range.start)..self.byte_index(range.end);
+        self.text.replace_range(bytes, "");
+        self.cursor = range.start;
+        self.anchor = None;
+        true
+    }
+
+    /// Type `text` at the cursor, over the selection. Characters past the
+    /// limit are dropped.
+    pub fn insert_str(&mut self, text: &str) {
+        self.delete_selection();
+        let room = self.max_chars.map_or(usize::MAX, |max| max.saturating_sub(self.char_count()));
+        let text: String = text.chars().take(room).collect();
+        let at = self.byte_index(self.cursor);
+        self.text.insert_str(at, &text);
+        self.cursor += text.chars().count();
+    }
+
+    /// Move the cursor to `to`, extending the selection when `select` (Shift is held).
+    fn move_to(&mut self, to: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = to.min(self.char_count());
+    }
+
+    /// Edit or move the cursor; false for keys the input doesn't use.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = control(key);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.select_all(),
+            KeyCode::Char('r') if self.masked && ctrl => self.revealed = !self.revealed,
+            KeyCode::Char(_) if ctrl => return false,
+            KeyCode::Char(c) => self.insert_str(c.encode_utf8(&mut [0; 4])),
+            KeyCode::Backspace => {
+                if !self.delete_selection() && self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.text.remove(self.byte_index(self.cursor));
+                }
+            }
+            KeyCode::Delete => {
+                if !self.delete_selection() && self.cursor < self.char_count() {
+                    self.text.remove(self.byte_index(self.cursor));
+                }
+            }
+            KeyCode::Left => self.move_to(self.cursor.saturating_sub(1), shift),
+            KeyCode::Right => self.move_to(self.cursor + 1, shift),
+            KeyCode::Home => self.move_to(0, shift),
+            KeyCode::End => self.move_to(self.char_count(), shift),
+            _ => return false,
+        }
+        true
+    }
+
+    /// The text to draw in `width` cells, scrolled to keep the cursor in
+    /// view. A focused input shows its cursor and selection.
+    pub fn line(&self, focused: bool, width: usize, style: Style) -> Line<'static> {
+        let chars: Vec<char> = if self.masked && !(self.revealed && focused) {
+            vec![MASK; self.char_count()]
+        } else {
+            self.text.chars().collect()
+        };
+        // The cursor past the end takes a cell of its own
+        let width = width.max(1);
+        let start = self.cursor.saturating_sub(width - 1);
+        let end = chars.len().min(start + width);
+        let selection = self.selection().filter(|_| focused).unwrap_or(self.cursor..self.cursor);
+        let reversed = style.add_modifier(Modifier::REVERSED);
+
+        let mut spans = Vec::new();
+        let mut run = String::new();
+        let mut run_reversed = false;
+        for (i, c) in chars.iter().enumerate().take(end).skip(start) {
+            let is_reversed = selection.contains(&i) || (focused && selection.is_empty() && i == self.cursor);
+            if is_reversed != run_reversed && !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), if run_reversed { reversed } else { style }));
+            }
+            run_reversed = is_reversed;
+            run.push(*c);
+        }
+        if !run.is_empty() {
+            spans.push(Span::styled(run, if run_reversed { reversed } else { style }));
+        }
+        if self.cursor >= chars.len() {
+            spans.push(Span::styled(focus::cursor(focused), style));
+        }
+        Line::from(spans)
+    }
+
+    /// All of the text, one `Line` per line of it, for a wrapping
+    /// `Paragraph` to draw.
+    pub fn lines(&self, focused: bool, style: Style) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::default()];
+        for span in self.line(focused, usize::MAX, style).spans {
+            let mut parts = span.content.split('\n');
+            if let Some(first) = parts.next().filter(|p| !p.is_empty()) {
+                lines.last_mut().expect("starts with a line").push_span(Span::styled(first.to_string(), span.style));
+            }
+            for part in parts {
+                lines.push(Line::from(Span::styled(part.to_string(), span.style)));
+            }
+        }
+        lines
+    }
+}
+
+/// A non-negative amount with up to `decimals` decimals, such as a token
+/// amount or a count of minutes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberInput {
+    input: TextInput,
+    decimals: u8,
+    min: Option<u128>,
+    max: Option<u128>,
+}
+
+impl NumberInput {
+    pub fn new(decimals: u8) -> Self {
+        Self { decimals, ..Self::default() }
+    }
+
+    /// Smallest value accepted, in the smallest unit (see `value`).
+    pub fn min(mut self, min: u128) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Largest value accepted, in the smallest unit.
+    pub fn max(mut self, max: u128) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        self.input.text()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// The amount in the smallest unit (the text times 10^decimals), when
+    /// it parses and is within bounds.
+    pub fn value(&self) -> Option<u128> {
+        crate::funds::parse_tokens(self.input.text(), self.decimals)
+            .filter(|v| self.min.is_none_or(|min| *v >= min) && self.max.is_none_or(|max| *v <= max))
+    }
+
+    /// Like `TextInput::handle_key`, refusing characters that can't make a
+    /// number (false, so the screen can bind them).
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if let KeyCode::Char(c) = key.code {
+            if control(key) {
+                return self.input.handle_key(key);
+            }
+            let mut typed = self.input.clone();
+            typed.insert_str(c.encode_utf8(&mut [0; 4]));
+            let text = typed.text();
+            let (whole, frac) = text.split_once('.').unwrap_or((text, ""));
+            let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+            let fits = digits(whole) && digits(frac) && frac.len() <= self.decimals as usize;
+            if !fits || (c == '.' && self.decimals == 0) {
+                return false;
+            }
+            self.input = typed;
+            return true;
+        }
+        self.input.handle_key(key)
+    }
+
+    pub fn line(&self, focused: bool, width: usize, style: Style) -> Line<'static> {
+        self.input.line(focused, width, style)
+    }
+}
+
+/// Which item of a list is selected; the screen keeps the items.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SelectList {
+    selected: usize,
+}
+
+impl SelectList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select item `index` of `len`, or the last one.
+    pub fn select(&mut self, index: usize, len: usize) {
+        self.selected = index.min(len.saturating_sub(1));
+    }
+
+    /// Keep the selection inside a list that shrank to `len`.
+    pub fn clamp(&mut self, len: usize) {
+        self.select(self.selected, len);
+    }
+
+    pub fn next(&mut self, len: usize) {
+        self.select(self.selected + 1, len);
+    }
+
+    pub fn prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Whether the last of `len` items is selected.
+    pub fn at_end(&self, len: usize) -> bool {
+        self.selected + 1 >= len
+    }
+
+    /// ↑/↓ and j/k move by one, Home/g and End/G to the ends; false for
+    /// other keys.
+    pub fn handle_key(&mut self, key: KeyCode, len: usize) -> bool {
+        match key {
+            KeyCode::Down | KeyCode::Char('j') => self.next(len),
+            KeyCode::Up | KeyCode::Char('k') => self.prev(),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => self.select(len.saturating_sub(1), len),
+            _ => return false,
+        }
+        true
+    }
+
+    /// State for drawing the list with ratatui's `List`.
+    pub fn state(&self) -> ListState {
+        ListState::default().with_selected(Some(self.selected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_text_input_editing() {
+        let mut input = TextInput::with_text("lobstr").max_chars(7);
+        input.handle_key(KeyCode::Left.into());
+        input.handle_key(KeyCode::Char('e').into());
+        input.handle_key(KeyCode::Char('s').into());
+        assert_eq!(input.text(), "lobster");
+
+        input.handle_key(KeyEvent::new(KeyCode::Home, KeyModifiers::SHIFT));
+        assert_eq!(input.selection(), Some(0..6));
+        input.handle_key(KeyCode::Char('P').into());
+        assert_eq!((input.text(), input.selection()), ("Pr", None));
+        input.handle_key(KeyCode::Home.into());
+        input.handle_key(KeyCode::Delete.into());
+        assert_eq!(input.text(), "r");
+
+        assert!(!input.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL)));
+        input.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL));
+        input.handle_key(KeyCode::Backspace.into());
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn test_text_input_line() {
+        let mut input = TextInput::with_text("hunter2").masked();
+        assert_eq!(text(&input.line(true, 20, Style::default())), "•••••••│");
+        assert_eq!(text(&input.line(false, 20, Style::default())), "•••••••");
+        assert!(input.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)));
+        assert_eq!(text(&input.line(true, 20, Style::default())), "hunter2│");
+        // Shown only while focused
+        assert_eq!(text(&input.line(false, 20, Style::default())), "•••••••");
+        input.hide();
+        assert!(!input.is_revealed());
+
+        // Scrolled to keep the cursor in view
+        let mut input = TextInput::with_text("abcdefgh");
+        assert_eq!(text(&input.line(true, 4, Style::default())), "fgh│");
+        input.handle_key(KeyCode::Home.into());
+        let line = input.line(true, 4, Style::default());
+        assert_eq!(text(&line), "abcd");
+        assert!(line.spans[0].style.add_modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_number_input() {
+        let mut balance = NumberInput::new(2).min(1);
+        for c in "1.2.5x".chars() {
+            balance.handle_key(KeyCode::Char(c).into());
+        }
+        assert_eq!((balance.text(), balance.value()), ("1.25", Some(125)));
+        assert!(!balance.handle_key(KeyCode::Char('9').into()));
+        for _ in 0..4 {
+            balance.handle_key(KeyCode::Backspace.into());
+        }
+        balance.handle_key(KeyCode::Char('0').into());
+        assert_eq!(balance.value(), None);
+
+        let mut minutes = NumberInput::new(0).max(60);
+        assert!(!minutes.handle_key(KeyCode::Char('.').into()));
+        minutes.handle_key(KeyCode::Char('6').into());
+        minutes.handle_key(KeyCode::Char('1').into());
+        assert_eq!(minutes.value(), None);
+    }
+
+    #[test]
+    fn test_select_list() {
+        let mut list = SelectList::new();
+        list.handle_key(KeyCode::Up, 3);
+        assert_eq!(list.selected(), 0);
+        list.handle_key(KeyCode::End, 3);
+        list.handle_key(KeyCode::Char('j'), 3);
+        assert!(list.at_end(3));
+        list.clamp(2);
+        assert_eq!(list.selected(), 1);
+        assert!(!list.handle_key(KeyCode::Enter, 2));
+    }
+}
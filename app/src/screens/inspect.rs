@@ -0,0 +1,328 @@
+//! State inspector - the agent pallet's storage for the agent, decoded, with
+//! what in it keeps the agent from running.
+
+use crate::{
+    app::{App, AppMessage, MessageContext, ScreenAction},
+    client::{AgentState, ApiClient},
+    funds::format_tokens,
+    health::Badge,
+    screens::{badge_color, badge_mark, Screen},
+};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+/// Blocks a queued run may wait before it counts as stuck.
+const STUCK_BLOCKS: u64 = 50;
+
+/// Background results for the inspector.
+#[derive(Debug, Clone)]
+pub enum InspectMessage {
+    /// Storage read for the agent (stale reads for another agent are ignored)
+    StateFetched { agent_address: String, state: AgentState },
+    StateFetchFailed(String),
+}
+
+pub struct InspectScreen {
+    pub agent_address: Option<String>,
+    pub state: Option<AgentState>,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub scroll: u16,
+}
+
+impl InspectScreen {
+    pub fn new() -> Self {
+        Self {
+            agent_address: None,
+            state: None,
+            loading: false,
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode, client: &ApiClient, tx: mpsc::Sender<AppMessage>) -> Result<ScreenAction> {
+        match key {
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                if let Some(address) = self.agent_address.clone() {
+                    self.refresh(client, &address, tx);
+                }
+            }
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    /// Read the agent's storage from the gateway.
+    pub fn refresh(&mut self, client: &ApiClient, agent_address: &str, tx: mpsc::Sender<AppMessage>) {
+        self.agent_address = Some(agent_address.to_string());
+        self.loading = true;
+        self.error = None;
+        let client = client.clone();
+        let agent_address = agent_address.to_string();
+        crate::tasks::spawn(async move {
+            match client.get_agent_state(&agent_address).await {
+                Ok(state) => {
+                    let _ = tx.send(InspectMessage::StateFetched { agent_address, state }.into()).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(InspectMessage::StateFetchFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
+    }
+
+    pub fn handle_state(&mut self, agent_address: String, state: AgentState) {
+        if self.agent_address.as_deref() != Some(agent_address.as_str()) {
+            return;
+        }
+        self.state = Some(state);
+        self.loading = false;
+    }
+
+    pub fn handle_message(&mut self, msg: InspectMessage, _ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            InspectMessage::StateFetched { agent_address, state } => self.handle_state(agent_address, state),
+            InspectMessage::StateFetchFailed(e) => {
+                self.error = Some(e);
+                self.loading = false;
+            }
+        }
+        Ok(ScreenAction::None)
+    }
+}
+
+/// What in the stored state stops the agent from running, worst first, or
+/// a single all-clear.
+pub fn findings(state: &AgentState, min_balance: u128) -> Vec<(Badge, String)> {
+    let mut found = Vec::new();
+    if state.code_hash.is_none() {
+        found.push((Badge::Bad, "No program code is stored; deploy the agent again".to_string()));
+    }
+    if !state.active {
+        found.push((Badge::Bad, "Scheduled runs are paused; resume them with P on the agent page".to_string()));
+    }
+    match state.balance.parse::<u128>() {
+        Ok(balance) if balance <= min_balance => found.push((
+            Badge::Bad,
+            "Balance is at or below min_balance, so runs can't be paid for; top the agent up".to_string(),
+        )),
+        Ok(_) => {}
+        Err(_) => found.push((Badge::Warn, format!("Balance \"{}\" could not be read", state.balance))),
+    }
+    if state.schedule_blocks.is_none() {
+        found.push((Badge::Warn, "No schedule: the agent only runs when prompted".to_string()));
+    }
+    if let Some(next) = state.next_run_block.filter(|&next| next < state.block_number && state.active) {
+        if state.pending_runs.is_empty() {
+            found.push((Badge::Warn, format!("The next heartbeat run is overdue by {} blocks", state.block_number - next)));
+        }
+    }
+    for run in &state.pending_runs {
+        let waiting = state.block_number.saturating_sub(run.queued_block);
+        if waiting >= STUCK_BLOCKS {
+            found.push((Badge::Warn, format!("Run #{} was queued {} blocks ago and hasn't started", run.run_id, waiting)));
+        }
+    }
+    if found.is_empty() {
+        found.push((Badge::Good, "Nothing in the stored state keeps the agent from running".to_string()));
+    }
+    found.sort_by_key(|(badge, _)| std::cmp::Reverse(*badge));
+    found
+}
+
+impl Screen for InspectScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(6),    // State
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let agent = app.agent_name().unwrap_or("agent");
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" ON-CHAIN STATE ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(agent, Style::default().fg(theme.accent)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        let block = |title: String| {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(title, Style::default().fg(theme.text)))
+        };
+
+        match &self.state {
+            None if self.loading => {
+                let loading = Paragraph::new(vec![
+                    Line::from(""),
+                    Line::from(Span::styled(format!("{} Reading storage...", app.spinner()), Style::default().fg(theme.warning))),
+                ])
+                .alignment(Alignment::Center)
+                .block(block(" Storage ".to_string()));
+                frame.render_widget(loading, chunks[1]);
+            }
+            None => {
+                let empty = Paragraph::new(vec![
+                    Line::from(""),
+                    Line::from(Span::styled("No state read yet", Style::default().fg(theme.muted))),
+                ])
+                .alignment(Alignment::Center)
+                .block(block(" Storage ".to_string()));
+                frame.render_widget(empty, chunks[1]);
+            }
+            Some(state) => {
+                let network = app.config.network();
+                let min_balance = app.config.min_balance_planck(&network);
+                let row = |label: &str, value: String| {
+                    Line::from(vec![
+                        Span::styled(format!("  {:<16}", label), Style::default().fg(theme.muted)),
+                        Span::styled(value, Style::default().fg(theme.text)),
+                    ])
+                };
+
+                let mut lines = vec![Line::from(Span::styled(" Why isn't it running?", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)))];
+                for (badge, finding) in findings(state, min_balance) {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {} ", badge_mark(badge, theme)), Style::default().fg(badge_color(badge, theme))),
+                        Span::styled(finding, Style::default().fg(theme.text)),
+                    ]));
+                }
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(" Storage", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))));
+                lines.push(row("Owner", state.owner.clone()));
+                lines.push(row("Code hash", state.code_hash.clone().unwrap_or_else(|| "none".to_string())));
+                lines.push(row("Version", state.version.to_string()));
+                lines.push(row("Active", if state.active { "yes" } else { "no, paused" }.to_string()));
+                lines.push(row(
+                    "Schedule",
+                    state.schedule_blocks.map_or("none".to_string(), |blocks| format!("every {} blocks", blocks)),
+                ));
+                lines.push(row(
+                    "Next run",
+                    match state.next_run_block {
+                        Some(next) if next >= state.block_number => format!("#{} (in {} blocks)", next, next - state.block_number),
+                        Some(next) => format!("#{} ({} blocks ago)", next, state.block_number - next),
+                        None => "not scheduled".to_string(),
+                    },
+                ));
+                let balance = match state.balance.parse::<u128>() {
+                    Ok(planck) => format!(
+                        "{} {} (min_balance {})",
+                        format_tokens(planck, network.decimals),
+                        network.token_symbol,
+                        format_tokens(min_balance, network.decimals)
+                    ),
+                    Err(_) => state.balance.clone(),
+                };
+                lines.push(row("Balance", balance));
+                lines.push(row("Pending runs", state.pending_runs.len().to_string()));
+                for run in &state.pending_runs {
+                    lines.push(Line::from(Span::styled(
+                        format!(
+                            "    #{} queued at #{} ({} blocks ago){}",
+                            run.run_id,
+                            run.queued_block,
+                            state.block_number.saturating_sub(run.queued_block),
+                            if run.scheduled { ", scheduled" } else { ", prompted" }
+                        ),
+                        Style::default().fg(theme.text),
+                    )));
+                }
+
+                let title = if self.loading {
+                    format!(" Storage at block #{} {} ", state.block_number, app.spinner())
+                } else {
+                    format!(" Storage at block #{} ", state.block_number)
+                };
+                let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).scroll((self.scroll, 0)).block(block(title));
+                frame.render_widget(paragraph, chunks[1]);
+            }
+        }
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Scroll", Style::default().fg(theme.muted)),
+                Span::styled("  [R] ", Style::default().fg(theme.muted)),
+                Span::styled("Read again", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back", Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppScreen;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app, AGENT_ADDRESS, WALLET_ADDRESS};
+
+    #[test]
+    fn test_findings() {
+        let mut state: AgentState = serde_json::from_value(serde_json::json!({
+            "owner": "5Owner", "code_hash": "0xabc", "version": 1, "active": true, "schedule_blocks": 600,
+            "next_run_block": 1600, "balance": "900", "block_number": 1000,
+        }))
+        .unwrap();
+        assert_eq!(findings(&state, 500), [(Badge::Good, "Nothing in the stored state keeps the agent from running".to_string())]);
+
+        state.active = false;
+        state.balance = "500".to_string();
+        state.next_run_block = Some(900);
+        state.pending_runs = serde_json::from_value(serde_json::json!([{ "run_id": 7, "queued_block": 940 }])).unwrap();
+        let badges: Vec<Badge> = findings(&state, 500).into_iter().map(|(badge, _)| badge).collect();
+        assert_eq!(badges, [Badge::Bad, Badge::Bad, Badge::Warn]);
+        assert!(findings(&state, 500)[2].1.starts_with("Run #7 was queued 60 blocks ago"));
+    }
+
+    #[test]
+    fn test_inspect_stuck_agent() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Inspect;
+        let state = serde_json::from_value(serde_json::json!({
+            "owner": WALLET_ADDRESS, "code_hash": "0x9f2c41d7e0b35a68", "version": 2, "active": true,
+            "schedule_blocks": 600, "next_run_block": 1200, "balance": "1000000000000",
+            "pending_runs": [{ "run_id": 41, "queued_block": 1190, "scheduled": true }],
+            "block_number": 1260,
+        }))
+        .unwrap();
+        app.inspect.agent_address = Some(AGENT_ADDRESS.to_string());
+        app.inspect.handle_state(AGENT_ADDRESS.to_string(), state);
+        assert_snapshot("inspect_stuck_agent", &screen_text(&mut app));
+    }
+}
@@ -0,0 +1,280 @@
+//! Leaderboard screen - top agents across the network and where ours ranks.
+
+use crate::{
+    app::{App, AppMessage, MessageContext, ScreenAction},
+    client::{ApiClient, LeaderboardEntry, LeaderboardKind, LeaderboardResponse},
+    screens::{input::SelectList, Screen},
+    text::truncate_width,
+};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+/// Number of top entries requested per board.
+const LEADERBOARD_SIZE: u32 = 25;
+
+/// Width of the name column.
+const NAME_WIDTH: usize = 24;
+
+/// Background results for the leaderboard.
+#[derive(Debug, Clone)]
+pub enum LeaderboardMessage {
+    /// Board fetched (stale boards are ignored)
+    LeaderboardFetched { kind: LeaderboardKind, board: LeaderboardResponse },
+    /// Leaderboard fetch failed
+    LeaderboardFetchFailed(String),
+}
+
+pub struct LeaderboardScreen {
+    /// Index into `LeaderboardKind::ALL`
+    pub board: usize,
+    pub entries: Vec<LeaderboardEntry>,
+    /// The user's agent on this board
+    pub agent: Option<LeaderboardEntry>,
+    pub selected: SelectList,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+impl LeaderboardScreen {
+    pub fn new() -> Self {
+        Self {
+            board: 0,
+            entries: Vec::new(),
+            agent: None,
+            selected: SelectList::new(),
+            loading: false,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn kind(&self) -> LeaderboardKind {
+        LeaderboardKind::ALL[self.board]
+    }
+
+    pub fn handle_key(
+        &mut self,
+        key: KeyCode,
+        client: &ApiClient,
+        agent_address: Option<&str>,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> Result<ScreenAction> {
+        match key {
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            key if self.selected.handle_key(key, self.entries.len()) => {}
+            KeyCode::Tab | KeyCode::Right | KeyCode::Left | KeyCode::Char('b') => {
+                self.board = (self.board + 1) % LeaderboardKind::ALL.len();
+                self.refresh(client, agent_address, tx);
+            }
+            KeyCode::Char('r') | KeyCode::Char('R') => self.refresh(client, agent_address, tx),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    /// Fetch the current board.
+    pub fn refresh(&mut self, client: &ApiClient, agent_address: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        self.loading = true;
+        self.error = None;
+        let client = client.clone();
+        let kind = self.kind();
+        let agent_address = agent_address.map(str::to_string);
+        crate::tasks::spawn(async move {
+            match client.get_leaderboard(kind, LEADERBOARD_SIZE, agent_address.as_deref()).await {
+                Ok(board) => {
+                    let _ = tx.send(LeaderboardMessage::LeaderboardFetched { kind, board }.into()).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(LeaderboardMessage::LeaderboardFetchFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
+    }
+
+    pub fn handle_board(&mut self, kind: LeaderboardKind, board: LeaderboardResponse) {
+        // Switched boards while this one was loading
+        if kind != self.kind() {
+            return;
+        }
+        self.entries = board.entries;
+        self.agent = board.agent;
+        self.loading = false;
+        // Start on our agent when it made the top
+        let ours = self.entries.iter().position(|e| self.is_ours(e)).unwrap_or(0);
+        self.selected.select(ours, self.entries.len());
+    }
+
+    pub fn handle_fetch_error(&mut self, error: String) {
+        self.error = Some(error);
+        self.loading = false;
+    }
+
+    pub fn handle_message(&mut self, msg: LeaderboardMessage, _ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            LeaderboardMessage::LeaderboardFetched { kind, board } => self.handle_board(kind, board),
+            LeaderboardMessage::LeaderboardFetchFailed(e) => self.handle_fetch_error(e),
+        }
+        Ok(ScreenAction::None)
+    }
+
+    /// Whether `entry` is the user's agent.
+    fn is_ours(&self, entry: &LeaderboardEntry) -> bool {
+        self.agent.as_ref().is_some_and(|a| match (&a.chain_address, &entry.chain_address) {
+            (Some(ours), Some(theirs)) => ours == theirs,
+            _ => a.rank == entry.rank && a.name == entry.name,
+        })
+    }
+}
+
+impl Screen for LeaderboardScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(6),    // Rankings
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let kind = self.kind();
+        let mut title = vec![
+            Span::styled(" LEADERBOARD ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(kind.label(), Style::default().fg(theme.accent)),
+        ];
+        match (&self.agent, app.agent_name()) {
+            (Some(agent), _) => title.push(Span::styled(
+                format!(" • {} ranks #{}", agent.name, agent.rank),
+                Style::default().fg(theme.success),
+            )),
+            (None, Some(name)) if !self.loading => {
+                title.push(Span::styled(format!(" • {} is unranked", name), Style::default().fg(theme.muted)));
+            }
+            _ => {}
+        }
+        let title = Paragraph::new(Line::from(title))
+            .alignment(Alignment::Center)
+            .block(Block::default()
+                .borders(Borders::BOTTOM)
+                .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(format!(" Top {} ", self.entries.len()), Style::default().fg(theme.text)));
+
+        if self.loading {
+            let loading = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!("{} Loading leaderboard...", app.spinner()),
+                    Style::default().fg(theme.warning),
+                )),
+            ])
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(loading, chunks[1]);
+        } else if self.entries.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled("No agents ranked yet", Style::default().fg(theme.muted))),
+            ])
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(empty, chunks[1]);
+        } else {
+            let row = |entry: &LeaderboardEntry| {
+                let ours = self.is_ours(entry);
+                let name_style = if ours {
+                    Style::default().fg(theme.success).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let mut spans = vec![
+                    Span::styled(format!("  #{:<4}", entry.rank), Style::default().fg(theme.muted)),
+                    Span::styled(
+                        format!("{:<width$}", truncate_width(&entry.name, NAME_WIDTH), width = NAME_WIDTH),
+                        name_style,
+                    ),
+                    Span::styled(format!("{:>8} {}", entry.score, kind.unit()), Style::default().fg(theme.info)),
+                ];
+                if ours {
+                    spans.push(Span::styled("  ◀ your agent", Style::default().fg(theme.success)));
+                }
+                ListItem::new(Line::from(spans))
+            };
+
+            let mut items: Vec<ListItem> = self.entries.iter().map(row).collect();
+            // Our agent below the top still gets a row, after a gap
+            if let Some(agent) = self.agent.as_ref().filter(|_| !self.entries.iter().any(|e| self.is_ours(e))) {
+                items.push(ListItem::new(Line::from(Span::styled("  ⋮", Style::default().fg(theme.muted)))));
+                items.push(row(agent));
+            }
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_symbol("▌")
+                .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+            let mut state = self.selected.state();
+            frame.render_stateful_widget(list, chunks[1], &mut state);
+        }
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Navigate", Style::default().fg(theme.muted)),
+                Span::styled("  [Tab] ", Style::default().fg(theme.muted)),
+                Span::styled("Switch board", Style::default().fg(theme.muted)),
+                Span::styled("  [R] ", Style::default().fg(theme.muted)),
+                Span::styled("Refresh", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back", Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::AppScreen;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app, AGENT_ADDRESS};
+
+    #[test]
+    fn test_leaderboard() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Leaderboard;
+        let board = serde_json::from_value(serde_json::json!({
+            "entries": [
+                { "rank": 1, "name": "clawdia", "score": 4800 },
+                { "rank": 2, "name": "shellby", "score": 4320 },
+                { "rank": 3, "name": "krill-bill", "score": 3840 },
+            ],
+            "agent": { "rank": 37, "name": "pinchy", "chain_address": AGENT_ADDRESS, "score": 240 },
+        }))
+        .unwrap();
+        app.leaderboard.handle_board(crate::client::LeaderboardKind::Upvotes, board);
+        assert_snapshot("leaderboard", &screen_text(&mut app));
+    }
+}
@@ -0,0 +1,93 @@
+//! Idle lock - a blank screen asking for the lock PIN or the secrets'
+//! passphrase after `idle_lock_minutes` without input.
+
+use crate::app::App;
+use crate::screens::input::TextInput;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+pub struct LockScreen {
+    pub input: TextInput,
+    /// Wrong entries since the lock
+    pub failures: u32,
+}
+
+impl LockScreen {
+    pub fn new() -> Self {
+        Self { input: TextInput::new().masked(), failures: 0 }
+    }
+
+    /// Handle a key; returns true when the entry unlocks.
+    pub fn handle_key(&mut self, event: KeyEvent, lock_pin: Option<&str>) -> bool {
+        let key = event.code;
+        match key {
+            _ if self.input.handle_key(event) => {}
+            KeyCode::Enter if !self.input.is_empty() => {
+                if crate::secrets::unlocks(&self.input.take(), lock_pin) {
+                    return true;
+                }
+                self.failures += 1;
+            }
+            KeyCode::Esc => self.input.clear(),
+            _ => {}
+        }
+        false
+    }
+}
+
+/// Cover the whole terminal, so nothing of the screen underneath shows.
+pub fn render_lock(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(lock) = &app.lock else {
+        return;
+    };
+    let status = match lock.failures {
+        0 => Line::from(Span::styled("[Enter] Unlock  [Ctrl+R] Show  [Ctrl+C] Quit", Style::default().fg(theme.muted))),
+        1 => Line::from(Span::styled(format!("{} Wrong PIN or passphrase", theme.err_mark()), Style::default().fg(theme.error))),
+        n => Line::from(Span::styled(
+            format!("{} Wrong PIN or passphrase ({} tries)", theme.err_mark(), n),
+            Style::default().fg(theme.error),
+        )),
+    };
+    let lines = vec![
+        Line::from(Span::styled("LOCKED", Style::default().fg(theme.text).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(Span::styled("Enter the lock PIN or the secrets passphrase", Style::default().fg(theme.muted))),
+        Line::from(""),
+        lock.input.line(true, 32, Style::default().fg(theme.text)).centered(),
+        Line::from(""),
+        status,
+    ];
+    let height = (lines.len() as u16).min(area.height);
+    let body = Rect::new(area.x, area.y + (area.height - height) / 2, area.width, height);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), body);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_needs_the_pin() {
+        let stored = crate::secrets::hash_pin("2468").unwrap();
+        let mut lock = LockScreen::new();
+        for c in "1357".chars() {
+            lock.handle_key(KeyCode::Char(c).into(), Some(&stored));
+        }
+        assert!(!lock.handle_key(KeyCode::Enter.into(), Some(&stored)));
+        assert_eq!(lock.failures, 1);
+        assert!(lock.input.is_empty());
+
+        for c in "2468".chars() {
+            lock.handle_key(KeyCode::Char(c).into(), Some(&stored));
+        }
+        assert!(lock.handle_key(KeyCode::Enter.into(), Some(&stored)));
+    }
+}
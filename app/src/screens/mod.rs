@@ -1,14 +1,114 @@
 //! Screen modules for the TUI.
 
+pub mod amount_confirm;
+pub mod backup;
+pub mod compose;
 pub mod create;
+pub mod error_modal;
+pub mod feed;
+pub mod focus;
+pub mod help;
 pub mod home;
+pub mod inbox;
+pub mod input;
+pub mod inspect;
+pub mod leaderboard;
+pub mod lock;
+pub mod notifications;
+pub mod onboarding;
+pub mod palette;
+pub mod profile;
 pub mod prompt;
+pub mod quit_confirm;
+pub mod receipt;
+pub mod retire;
+pub mod runs;
+pub mod search;
+pub mod settings;
+pub mod status_bar;
+pub mod submolts;
+pub mod thread;
+pub mod transfer;
 pub mod view;
 
-use crate::App;
-use ratatui::{layout::Rect, Frame};
+use crate::{
+    app::App,
+    config::NetworkKind,
+    health::Badge,
+    heartbeat::HeartbeatStatus,
+    theme::Theme,
+};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Span,
+    Frame,
+};
 
 /// Trait for TUI screens.
 pub trait Screen {
     fn render(&self, frame: &mut Frame, area: Rect, app: &App);
 }
+
+/// Header badge naming the network, loud for anything that holds real funds.
+///
+/// Drawn reversed so it still stands out when the theme has no colors.
+pub fn network_badge(kind: NetworkKind, theme: &Theme) -> Span<'static> {
+    let style = match kind {
+        NetworkKind::Mainnet => Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+        NetworkKind::Testnet => Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+        NetworkKind::Local | NetworkKind::Custom => Style::default().fg(theme.muted),
+    };
+    Span::styled(format!(" {} ", kind.badge()), style.add_modifier(Modifier::REVERSED))
+}
+
+/// "♥ Heartbeat next at #1600 (in 500 blocks)", loud while the run is live.
+pub fn heartbeat_span(status: HeartbeatStatus, theme: &Theme) -> Span<'static> {
+    let style = match status {
+        HeartbeatStatus::Running { .. } => Style::default().fg(theme.success).add_modifier(Modifier::BOLD),
+        HeartbeatStatus::Due => Style::default().fg(theme.warning),
+        _ => Style::default().fg(theme.muted),
+    };
+    Span::styled(format!("♥ Heartbeat {}", status.label()), style)
+}
+
+/// Green, yellow or red for a health badge.
+pub fn badge_color(badge: Badge, theme: &Theme) -> Color {
+    match badge {
+        Badge::Unknown => theme.muted,
+        Badge::Good => theme.success,
+        Badge::Warn => theme.warning,
+        Badge::Bad => theme.error,
+    }
+}
+
+/// Dot for a health badge, or its mark and word when the theme labels status.
+pub fn badge_mark(badge: Badge, theme: &Theme) -> &'static str {
+    match badge {
+        Badge::Unknown => "",
+        _ if !theme.labels => "●",
+        Badge::Good => theme.ok_mark(),
+        Badge::Warn => theme.warn_mark(),
+        Badge::Bad => theme.err_mark(),
+    }
+}
+
+/// Frames of the loading spinner.
+const SPINNER_FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
+
+/// Animation ticks each spinner frame stays on screen.
+const TICKS_PER_FRAME: u64 = 3;
+
+/// Spinner frame for an animation tick (see `App::tick`).
+pub fn spinner(tick: u64) -> &'static str {
+    SPINNER_FRAMES[(tick / TICKS_PER_FRAME) as usize % SPINNER_FRAMES.len()]
+}
+
+/// Short relative time, e.g. "42s ago" or "3h ago".
+pub fn format_age(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        _ => format!("{}h ago", secs / 3600),
+    }
+}
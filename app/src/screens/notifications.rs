@@ -0,0 +1,93 @@
+//! Toasts in the top-right corner and the notification history panel.
+
+use crate::{
+    app::App,
+    notifications::{Level, Notification},
+    screens::format_age,
+    theme::Theme,
+};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Widest a toast gets.
+const TOAST_WIDTH: u16 = 48;
+
+fn level_style(level: Level, theme: &Theme) -> (&'static str, Color) {
+    match level {
+        Level::Info => ("•", theme.info),
+        Level::Success => (theme.ok_mark(), theme.success),
+        Level::Error => (theme.err_mark(), theme.error),
+    }
+}
+
+/// Stack the visible toasts down the top-right corner.
+pub fn render_toasts(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let width = TOAST_WIDTH.min(area.width);
+    let mut y = area.y + 1;
+    for toast in app.notifications.toasts() {
+        let (icon, color) = level_style(toast.level, theme);
+        // Message wraps inside the borders and the icon column
+        let text_width = (width as usize).saturating_sub(3 + icon.chars().count()).max(1);
+        let height = (toast.message.chars().count().div_ceil(text_width) as u16).clamp(1, 3) + 2;
+        if y + height > area.bottom() {
+            break;
+        }
+        let rect = Rect::new(area.right().saturating_sub(width + 1), y, width, height);
+        let body = Paragraph::new(Line::from(vec![
+            Span::styled(format!("{} ", icon), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(toast.message.as_str(), Style::default().fg(theme.text)),
+        ]))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color)));
+        frame.render_widget(Clear, rect);
+        frame.render_widget(body, rect);
+        y += height;
+    }
+}
+
+/// Full-screen-ish panel listing past notifications, newest first.
+pub fn render_history(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let lines: Vec<Line> = if app.notifications.history().next().is_none() {
+        vec![
+            Line::from(""),
+            Line::from(Span::styled("  No notifications yet.", Style::default().fg(theme.muted))),
+        ]
+    } else {
+        app.notifications.history().map(|n| history_line(n, theme)).collect()
+    };
+
+    let width = area.width.saturating_sub(8).min(96);
+    let height = area.height.saturating_sub(4);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let panel = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .alignment(Alignment::Left)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(Span::styled(" Notifications ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)))
+            .title_bottom(Line::from(Span::styled(" any key to close ", Style::default().fg(theme.muted))).right_aligned()));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(panel, popup);
+}
+
+fn history_line<'a>(n: &'a Notification, theme: &Theme) -> Line<'a> {
+    let (icon, color) = level_style(n.level, theme);
+    Line::from(vec![
+        Span::styled(format!(" {:>9} ", format_age(n.at.elapsed().as_secs())), Style::default().fg(theme.muted)),
+        Span::styled(format!("{} ", icon), Style::default().fg(color)),
+        Span::styled(n.message.as_str(), Style::default().fg(theme.text)),
+    ])
+}
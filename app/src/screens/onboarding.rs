@@ -0,0 +1,322 @@
+//! First-run walkthrough - what Proof of Lobster is, login, wallet backup and first deploy.
+//!
+//! Progress is derived from the config and wallet rather than stored, so the
+//! walkthrough picks up where it left off after a restart or a detour through
+//! the login and create screens.
+
+use crate::{
+    app::{App, AppScreen, ScreenAction},
+    config::AppConfig,
+    screens::{network_badge, Screen},
+    wallet::WalletConfig,
+};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum OnboardingStep {
+    Welcome,
+    Login,
+    BackupWallet,
+    Deploy,
+    Done,
+}
+
+impl OnboardingStep {
+    const ALL: [OnboardingStep; 5] = [
+        OnboardingStep::Welcome,
+        OnboardingStep::Login,
+        OnboardingStep::BackupWallet,
+        OnboardingStep::Deploy,
+        OnboardingStep::Done,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            OnboardingStep::Welcome => "Welcome",
+            OnboardingStep::Login => "Log in",
+            OnboardingStep::BackupWallet => "Back up your wallet",
+            OnboardingStep::Deploy => "Deploy your agent",
+            OnboardingStep::Done => "Done",
+        }
+    }
+}
+
+pub struct OnboardingScreen {
+    /// Welcome page was read
+    pub welcomed: bool,
+    /// Recovery phrase is shown instead of masked
+    pub show_phrase: bool,
+}
+
+impl OnboardingScreen {
+    pub fn new() -> Self {
+        Self {
+            welcomed: false,
+            show_phrase: false,
+        }
+    }
+
+    /// First step that is not done yet.
+    pub fn step(&self, config: &AppConfig) -> OnboardingStep {
+        if !self.welcomed {
+            OnboardingStep::Welcome
+        } else if !config.is_authenticated() {
+            OnboardingStep::Login
+        } else if !config.wallet_backed_up {
+            OnboardingStep::BackupWallet
+        } else if !config.has_agent() {
+            OnboardingStep::Deploy
+        } else {
+            OnboardingStep::Done
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode, config: &mut AppConfig, has_wallet: bool) -> Result<ScreenAction> {
+        let step = self.step(config);
+        match key {
+            // Skipping is for good; the menu has everything the walkthrough does
+            KeyCode::Esc => return Self::finish(config),
+            KeyCode::Char('s') | KeyCode::Char('S') if step == OnboardingStep::BackupWallet => {
+                self.show_phrase = !self.show_phrase;
+            }
+            KeyCode::Enter => match step {
+                OnboardingStep::Welcome => self.welcomed = true,
+                OnboardingStep::Login => return Ok(ScreenAction::Push(AppScreen::EmailInput)),
+                OnboardingStep::BackupWallet if has_wallet => {
+                    config.wallet_backed_up = true;
+                    config.save()?;
+                    self.show_phrase = false;
+                }
+                OnboardingStep::BackupWallet => {}
+                OnboardingStep::Deploy => return Ok(ScreenAction::Push(AppScreen::Create)),
+                OnboardingStep::Done => return Self::finish(config),
+            },
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn finish(config: &mut AppConfig) -> Result<ScreenAction> {
+        config.onboarded = true;
+        config.save()?;
+        Ok(ScreenAction::GoHome)
+    }
+
+    fn step_body(&self, step: OnboardingStep, app: &App) -> Vec<Line<'static>> {
+        let theme = &app.theme;
+        let text = |s: &str| Line::from(Span::styled(s.to_string(), Style::default().fg(theme.text)));
+        let muted = |s: &str| Line::from(Span::styled(s.to_string(), Style::default().fg(theme.muted)));
+
+        match step {
+            OnboardingStep::Welcome => vec![
+                text("Proof of Lobster deploys AI agents to Moltbook, the social network for AI agents."),
+                Line::from(""),
+                text("Your agent runs on the Theseus chain on a schedule: it reads the feed, posts and \
+                      comments with a consistent identity, and every action it takes is recorded on-chain."),
+                Line::from(""),
+                muted("This walkthrough takes you through logging in, backing up your wallet and \
+                       deploying your first agent. It takes a few minutes."),
+            ],
+            OnboardingStep::Login => vec![
+                text("Log in with your email. A magic link is sent to your inbox; clicking it \
+                      completes the login and brings you back here."),
+                Line::from(""),
+                muted("Your login ties the agent to you on the server, so you can manage it from \
+                       any machine."),
+            ],
+            OnboardingStep::BackupWallet => match &app.wallet {
+                Some(wallet) => self.wallet_body(wallet, app),
+                None => vec![Line::from(Span::styled(
+                    format!("{} Setting up your wallet...", app.spinner()),
+                    Style::default().fg(theme.warning),
+                ))],
+            },
+            OnboardingStep::Deploy => vec![
+                text("Now create your agent. The wizard walks through:"),
+                Line::from(""),
+                text("  1. Choosing the agent files (the built-in lobster, or your own directory)"),
+                text("  2. Registering a name on Moltbook"),
+                text("  3. Claiming the agent from your Moltbook account"),
+                text("  4. Reviewing its personality (SOUL.md) and schedule"),
+                text("  5. Compiling and deploying it on-chain"),
+                Line::from(""),
+                muted("Deploying is paid from your wallet, which was funded when you logged in."),
+            ],
+            OnboardingStep::Done => vec![
+                Line::from(Span::styled(
+                    format!("{} is live.", app.agent_name().unwrap_or("Your agent")),
+                    Style::default().fg(theme.success).add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+                text("From the menu you can prompt it, watch its posts and on-chain activity, \
+                      browse the feed and tune it in Settings."),
+                Line::from(""),
+                muted("Press ? on any screen to see the keys it understands."),
+            ],
+        }
+    }
+
+    fn wallet_body(&self, wallet: &WalletConfig, app: &App) -> Vec<Line<'static>> {
+        let theme = &app.theme;
+        let phrase = if self.show_phrase {
+            wallet.mnemonic.clone()
+        } else {
+            wallet.mnemonic.split_whitespace().map(|_| "•••••").collect::<Vec<_>>().join(" ")
+        };
+        vec![
+            Line::from(Span::styled(
+                "A wallet was created for you. It pays for deployments and signs your prompts.",
+                Style::default().fg(theme.text),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Address:  ", Style::default().fg(theme.muted)),
+                Span::styled(wallet.public_key.clone(), Style::default().fg(theme.info)),
+            ]),
+            Line::from(vec![
+                Span::styled("Stored:   ", Style::default().fg(theme.muted)),
+                Span::styled(WalletConfig::path(&app.config).display().to_string(), Style::default().fg(theme.text)),
+            ]),
+            Line::from(vec![
+                Span::styled("Phrase:   ", Style::default().fg(theme.muted)),
+                Span::styled(phrase, Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                "The 12-word recovery phrase is the only way to restore this wallet if the file is \
+                 lost. Write it down and keep it somewhere safe; anyone who has it controls the wallet.",
+                Style::default().fg(theme.text),
+            )),
+        ]
+    }
+}
+
+impl Screen for OnboardingScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let step = self.step(&app.config);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(8),    // Progress + step
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let position = OnboardingStep::ALL.iter().position(|s| *s == step).unwrap_or(0) + 1;
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" GETTING STARTED ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(
+                format!("Step {} of {}", position, OnboardingStep::ALL.len()),
+                Style::default().fg(theme.accent),
+            ),
+            Span::raw(" "),
+            network_badge(app.config.network().kind, theme),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(28), Constraint::Min(30)])
+            .split(chunks[1]);
+
+        let progress: Vec<Line> = OnboardingStep::ALL
+            .iter()
+            .map(|s| {
+                let (marker, style) = if *s < step {
+                    ("✓", Style::default().fg(theme.success))
+                } else if *s == step {
+                    ("▸", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+                } else {
+                    ("○", Style::default().fg(theme.muted))
+                };
+                Line::from(Span::styled(format!("{} {}", marker, s.label()), style))
+            })
+            .collect();
+        let progress = Paragraph::new(progress).block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(" Progress ", Style::default().fg(theme.text)))
+            .padding(Padding::horizontal(1)));
+        frame.render_widget(progress, body[0]);
+
+        let content = Paragraph::new(self.step_body(step, app))
+            .wrap(Wrap { trim: false })
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent))
+                .title(Span::styled(
+                    format!(" {} ", step.label()),
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                ))
+                .padding(Padding::uniform(1)));
+        frame.render_widget(content, body[1]);
+
+        let enter = match step {
+            OnboardingStep::Welcome => "Get started",
+            OnboardingStep::Login => "Log in",
+            OnboardingStep::BackupWallet => "I've saved the phrase",
+            OnboardingStep::Deploy => "Create agent",
+            OnboardingStep::Done => "Go to the menu",
+        };
+        let mut footer = vec![
+            Span::styled("[Enter] ", Style::default().fg(theme.muted)),
+            Span::styled(enter, Style::default().fg(theme.muted)),
+        ];
+        if step == OnboardingStep::BackupWallet {
+            let toggle = if self.show_phrase { "Hide phrase" } else { "Show phrase" };
+            footer.push(Span::styled("  [S] ", Style::default().fg(theme.muted)));
+            footer.push(Span::styled(toggle, Style::default().fg(theme.muted)));
+        }
+        if step != OnboardingStep::Done {
+            footer.push(Span::styled("  [Esc] ", Style::default().fg(theme.muted)));
+            footer.push(Span::styled("Skip walkthrough", Style::default().fg(theme.muted)));
+        }
+        let footer = Paragraph::new(Line::from(footer)).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppScreen;
+    use crate::testing::{config, app, assert_snapshot, screen_text};
+
+    #[test]
+    fn test_step_follows_progress() {
+        let mut screen = OnboardingScreen::new();
+        let mut config = AppConfig::default();
+        assert_eq!(screen.step(&config), OnboardingStep::Welcome);
+        screen.welcomed = true;
+        assert_eq!(screen.step(&config), OnboardingStep::Login);
+        config.auth_token = Some("token".to_string());
+        assert_eq!(screen.step(&config), OnboardingStep::BackupWallet);
+        config.wallet_backed_up = true;
+        assert_eq!(screen.step(&config), OnboardingStep::Deploy);
+        config.agent_address = Some("5Abc".to_string());
+        assert_eq!(screen.step(&config), OnboardingStep::Done);
+    }
+
+    #[test]
+    fn test_onboarding() {
+        let mut app = app(AppConfig { onboarded: false, ..config() });
+        assert_eq!(app.screen, AppScreen::Onboarding);
+        assert_snapshot("onboarding", &screen_text(&mut app));
+    }
+}
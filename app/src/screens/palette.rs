@@ -0,0 +1,299 @@
+//! Command palette (Ctrl+P) - fuzzy search over every action available right now.
+
+use crate::{
+    app::App,
+    config::AppConfig,
+    screens::input::{SelectList, TextInput},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+/// Matches shown at once.
+const MAX_RESULTS: usize = 12;
+
+/// An app-wide action, run from the palette or the home menu.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Home,
+    Login,
+    CreateAgent,
+    PromptAgent,
+    SimulateAgent,
+    ViewAgent,
+    InspectAgent,
+    OpenFailingRun,
+    NewPost,
+    Feed,
+    Submolts,
+    Inbox,
+    Leaderboard,
+    CompareRuns,
+    SearchRuns,
+    Settings,
+    Logout,
+    RequestTestTokens,
+    CopyWalletAddress,
+    CopyAgentAddress,
+    OpenExplorer,
+    Notifications,
+    ErrorDetails,
+    ExportBackup,
+    Help,
+    /// Point the app at another network profile, by name
+    SwitchNetwork(String),
+    Quit,
+}
+
+impl Command {
+    pub fn label(&self) -> String {
+        match self {
+            Command::Home => "Go to home".to_string(),
+            Command::Login => "Log in with email".to_string(),
+            Command::CreateAgent => "Create and deploy a new agent".to_string(),
+            Command::PromptAgent => "Prompt the agent".to_string(),
+            Command::SimulateAgent => "Dry-run the agent files with a test prompt".to_string(),
+            Command::ViewAgent => "View agent details".to_string(),
+            Command::InspectAgent => "Inspect the agent's on-chain state".to_string(),
+            Command::OpenFailingRun => "Open the failing scheduled run's events".to_string(),
+            Command::NewPost => "Write a post".to_string(),
+            Command::Feed => "Browse the feed".to_string(),
+            Command::Submolts => "Browse submolts".to_string(),
+            Command::Inbox => "Read mentions, replies and messages".to_string(),
+            Command::Leaderboard => "Show the agent leaderboard".to_string(),
+            Command::CompareRuns => "Compare two recorded runs".to_string(),
+            Command::SearchRuns => "Search recorded runs".to_string(),
+            Command::Settings => "Open settings".to_string(),
+            Command::Logout => "Log out".to_string(),
+            Command::RequestTestTokens => "Request test tokens from the faucet".to_string(),
+            Command::CopyWalletAddress => "Copy wallet address".to_string(),
+            Command::CopyAgentAddress => "Copy agent address".to_string(),
+            Command::OpenExplorer => "Open the agent on the block explorer".to_string(),
+            Command::Notifications => "Show notification history".to_string(),
+            Command::ErrorDetails => "Show the latest error in full".to_string(),
+            Command::ExportBackup => "Export an encrypted backup for another machine".to_string(),
+            Command::Help => "Show keys for this screen".to_string(),
+            Command::SwitchNetwork(name) => format!("Switch network to {}", name),
+            Command::Quit => "Quit".to_string(),
+        }
+    }
+
+    /// Home menu key that runs the same action, shown as a hint.
+    fn menu_key(&self) -> Option<&'static str> {
+        match self {
+            Command::Login | Command::CreateAgent => Some("1"),
+            Command::PromptAgent => Some("2"),
+            Command::ViewAgent => Some("3"),
+            Command::OpenFailingRun => Some("F"),
+            Command::Logout => Some("4"),
+            Command::NewPost => Some("5"),
+            Command::Feed => Some("6"),
+            Command::Submolts => Some("7"),
+            Command::Inbox => Some("0"),
+            Command::Leaderboard => Some("L"),
+            Command::CompareRuns => Some("D"),
+            Command::SearchRuns => Some("/"),
+            Command::Settings => Some("8"),
+            Command::RequestTestTokens => Some("9"),
+            Command::Notifications => Some("N"),
+            Command::ErrorDetails => Some("!"),
+            Command::Help => Some("?"),
+            _ => None,
+        }
+    }
+}
+
+/// Commands that make sense in the current state, in menu order.
+pub fn available_commands(app: &App) -> Vec<Command> {
+    let config = &app.config;
+    let mut commands = vec![Command::Home];
+    if !config.is_authenticated() {
+        commands.push(Command::Login);
+    } else {
+        commands.extend([Command::CreateAgent, Command::SimulateAgent]);
+        if app.has_agent() {
+            commands.extend([Command::PromptAgent, Command::ViewAgent, Command::InspectAgent]);
+            if app.failure_alert().is_some() {
+                commands.push(Command::OpenFailingRun);
+            }
+            if config.moltbook_api_key.is_some() {
+                commands.extend([Command::NewPost, Command::Feed, Command::Submolts, Command::Inbox]);
+            }
+        }
+        commands.push(Command::Leaderboard);
+    }
+    commands.extend([Command::CompareRuns, Command::SearchRuns]);
+    commands.push(Command::Settings);
+    if app.wallet_address().is_some() {
+        if config.network().kind.has_faucet() {
+            commands.push(Command::RequestTestTokens);
+        }
+        commands.push(Command::CopyWalletAddress);
+    }
+    if let Some(address) = app.agent_address() {
+        commands.push(Command::CopyAgentAddress);
+        if config.explorer_link(address).is_some() {
+            commands.push(Command::OpenExplorer);
+        }
+    }
+    commands.extend(switchable_networks(config).into_iter().map(Command::SwitchNetwork));
+    commands.extend([Command::Notifications, Command::ErrorDetails, Command::ExportBackup, Command::Help]);
+    if config.is_authenticated() {
+        commands.push(Command::Logout);
+    }
+    commands.push(Command::Quit);
+    commands
+}
+
+/// Names of the network profiles other than the current one.
+fn switchable_networks(config: &AppConfig) -> Vec<String> {
+    let current = config.network().name;
+    config
+        .network_profiles()
+        .into_iter()
+        .filter(|n| n.name != current)
+        .map(|n| n.name)
+        .collect()
+}
+
+/// Score `text` against a fuzzy `query`: every query character must appear in
+/// order. Consecutive runs and word starts score higher; None means no match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + text[position..].iter().position(|c| *c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || text[found - 1] == ' ' {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    // Prefer shorter labels among equal matches
+    Some(score * 100 - text.len() as i32)
+}
+
+pub struct CommandPalette {
+    pub query: TextInput,
+    pub selected: SelectList,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self { query: TextInput::new(), selected: SelectList::new() }
+    }
+
+    /// Commands matching the query, best first.
+    pub fn matches(&self, commands: Vec<Command>) -> Vec<Command> {
+        if self.query.text().trim().is_empty() {
+            return commands;
+        }
+        let mut scored: Vec<(i32, Command)> = commands
+            .into_iter()
+            .filter_map(|c| fuzzy_score(self.query.text(), &c.label()).map(|s| (s, c)))
+            .collect();
+        // Stable, so ties keep menu order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Handle a key; returns the command to run, and whether the palette stays open.
+    pub fn handle_key(&mut self, event: KeyEvent, matches: &[Command]) -> (Option<Command>, bool) {
+        let key = event.code;
+        match key {
+            KeyCode::Esc => return (None, false),
+            KeyCode::Enter => return (matches.get(self.selected.selected()).cloned(), false),
+            KeyCode::Down | KeyCode::Tab => self.selected.next(matches.len()),
+            KeyCode::Up | KeyCode::BackTab => self.selected.prev(),
+            _ => {
+                let before = self.query.text().to_string();
+                // A new query ranks the commands afresh
+                if self.query.handle_key(event) && self.query.text() != before {
+                    self.selected = SelectList::new();
+                }
+            }
+        }
+        (None, true)
+    }
+}
+
+/// Draw the palette near the top of the screen, over the current screen.
+pub fn render_palette(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(palette) = &app.palette else {
+        return;
+    };
+    let theme = &app.theme;
+    let matches = palette.matches(available_commands(app));
+
+    let width = area.width.saturating_sub(4).min(64);
+    let shown = matches.len().clamp(1, MAX_RESULTS) as u16;
+    let height = (shown + 4).min(area.height);
+    let popup = Rect::new(area.x + (area.width - width) / 2, area.y + area.height / 6, width, height);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(Span::styled(" Commands ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)))
+        .title_bottom(
+            Line::from(Span::styled(" [↑/↓] Select  [Enter] Run  [Esc] Close ", Style::default().fg(theme.muted)))
+                .right_aligned(),
+        );
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let mut query = palette.query.line(true, inner.width.saturating_sub(2) as usize, Style::default().fg(theme.text));
+    query.spans.insert(0, Span::styled("> ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)));
+    let input = Paragraph::new(query);
+    frame.render_widget(input, Rect::new(inner.x, inner.y, inner.width, 1));
+
+    let list_area = Rect::new(inner.x, inner.y + 2, inner.width, inner.height.saturating_sub(2));
+    if matches.is_empty() {
+        let empty = Paragraph::new(Span::styled("  No matching commands", Style::default().fg(theme.muted)));
+        frame.render_widget(empty, list_area);
+        return;
+    }
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|command| {
+            let mut spans = vec![Span::styled(command.label(), Style::default().fg(theme.text))];
+            if let Some(key) = command.menu_key() {
+                spans.push(Span::styled(format!("  [{}]", key), Style::default().fg(theme.muted)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_symbol("▌ ")
+        .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+    let mut selected = palette.selected;
+    selected.clamp(matches.len());
+    let mut state = selected.state();
+    frame.render_stateful_widget(list, list_area, &mut state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("xyz", "View agent details").is_none());
+        assert!(fuzzy_score("vad", "View agent details").is_some());
+        // Word starts and runs beat scattered letters
+        let set = fuzzy_score("set", "Open settings").unwrap();
+        let scattered = fuzzy_score("set", "Show the latest error in full").unwrap();
+        assert!(set > scattered);
+    }
+}
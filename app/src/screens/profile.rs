@@ -0,0 +1,241 @@
+//! Profile form on the view screen - edit the agent's Moltbook profile.
+
+use crate::{
+    app::AppMessage,
+    client::ApiClient,
+    moltbook::{AgentProfile, MAX_AGENT_DESCRIPTION_LEN},
+    screens::{input::TextInput, view::ViewMessage},
+    theme::Theme,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileField {
+    DisplayName,
+    Description,
+    AvatarUrl,
+    Save,
+}
+
+impl ProfileField {
+    fn next(self) -> Self {
+        match self {
+            ProfileField::DisplayName => ProfileField::Description,
+            ProfileField::Description => ProfileField::AvatarUrl,
+            ProfileField::AvatarUrl => ProfileField::Save,
+            ProfileField::Save => ProfileField::DisplayName,
+        }
+    }
+
+    fn prev(self) -> Self {
+        match self {
+            ProfileField::DisplayName => ProfileField::Save,
+            ProfileField::Description => ProfileField::DisplayName,
+            ProfileField::AvatarUrl => ProfileField::Description,
+            ProfileField::Save => ProfileField::AvatarUrl,
+        }
+    }
+}
+
+pub struct ProfileForm {
+    pub focus: ProfileField,
+    pub display_name: TextInput,
+    pub description: TextInput,
+    pub avatar_url: TextInput,
+    /// Current profile is still being fetched
+    pub loading: bool,
+    pub saving: bool,
+    pub error: Option<String>,
+}
+
+impl ProfileForm {
+    /// Open the form and fetch the current profile to pre-fill it.
+    pub fn open(client: &ApiClient, api_key: &str, tx: mpsc::Sender<AppMessage>) -> Self {
+        let client = client.clone();
+        let api_key = api_key.to_string();
+        crate::tasks::spawn(async move {
+            match client.get_profile(&api_key).await {
+                Ok(profile) => {
+                    let _ = tx.send(ViewMessage::ProfileFetched(profile).into()).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(ViewMessage::ProfileFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
+
+        Self {
+            focus: ProfileField::Description,
+            display_name: TextInput::new(),
+            description: TextInput::new(),
+            avatar_url: TextInput::new(),
+            loading: true,
+            saving: false,
+            error: None,
+        }
+    }
+
+    /// Handle a key; returns false once the form should close.
+    pub fn handle_key(&mut self, event: KeyEvent, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) -> bool {
+        let key = event.code;
+        if self.saving {
+            return true;
+        }
+
+        self.error = None;
+        match key {
+            KeyCode::Esc => return false,
+            KeyCode::Tab | KeyCode::Down => self.focus = self.focus.next(),
+            KeyCode::BackTab | KeyCode::Up => self.focus = self.focus.prev(),
+            KeyCode::Enter => match self.focus {
+                ProfileField::Save => self.save(client, api_key, tx),
+                _ => self.focus = self.focus.next(),
+            },
+            // Typing waits for the fetched profile, which would replace it
+            KeyCode::Char(_) if self.loading => {}
+            _ => {
+                if let Some(field) = self.focused_text() {
+                    field.handle_key(event);
+                }
+            }
+        }
+        true
+    }
+
+    fn focused_text(&mut self) -> Option<&mut TextInput> {
+        match self.focus {
+            ProfileField::DisplayName => Some(&mut self.display_name),
+            ProfileField::Description => Some(&mut self.description),
+            ProfileField::AvatarUrl => Some(&mut self.avatar_url),
+            ProfileField::Save => None,
+        }
+    }
+
+    fn save(&mut self, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        let Some(api_key) = api_key.map(str::to_string) else {
+            self.error = Some("No Moltbook API key stored for this agent".to_string());
+            return;
+        };
+        if self.loading {
+            return;
+        }
+
+        let description = self.description.text().trim().to_string();
+        let display_name = self.display_name.text().trim();
+        let avatar_url = self.avatar_url.text().trim();
+        if description.chars().count() > MAX_AGENT_DESCRIPTION_LEN {
+            self.error = Some(format!("Description must be at most {} characters", MAX_AGENT_DESCRIPTION_LEN));
+            self.focus = ProfileField::Description;
+            return;
+        }
+        if !avatar_url.is_empty() && !avatar_url.starts_with("https://") && !avatar_url.starts_with("http://") {
+            self.error = Some("Avatar URL must start with http:// or https://".to_string());
+            self.focus = ProfileField::AvatarUrl;
+            return;
+        }
+
+        let profile = AgentProfile {
+            display_name: (!display_name.is_empty()).then(|| display_name.to_string()),
+            description,
+            avatar_url: (!avatar_url.is_empty()).then(|| avatar_url.to_string()),
+        };
+        self.saving = true;
+        let client = client.clone();
+        crate::tasks::spawn_operation("a profile save", async move {
+            match client.update_profile(&api_key, &profile).await {
+                Ok(saved) => {
+                    let _ = tx.send(ViewMessage::ProfileSaved(saved).into()).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(ViewMessage::ProfileFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
+    }
+
+    pub fn handle_profile(&mut self, profile: AgentProfile) {
+        self.display_name.set_text(profile.display_name.unwrap_or_default());
+        self.description.set_text(profile.description);
+        self.avatar_url.set_text(profile.avatar_url.unwrap_or_default());
+        self.loading = false;
+    }
+
+    pub fn handle_failed(&mut self, error: String) {
+        // A failed fetch still lets the user write a profile from scratch
+        self.loading = false;
+        self.saving = false;
+        self.error = Some(error);
+    }
+
+    fn field_block(&self, field: ProfileField, title: &'static str, theme: &Theme) -> Block<'static> {
+        let color = if self.focus == field {
+            theme.accent
+        } else {
+            theme.muted
+        };
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color))
+            .title(Span::styled(title, Style::default().fg(theme.text)))
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, spinner: &str, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Display name
+                Constraint::Min(4),    // Description
+                Constraint::Length(3), // Avatar URL
+                Constraint::Length(3), // Save button
+            ])
+            .split(area);
+
+        let focused = |field: ProfileField| self.focus == field;
+        let width = chunks[0].width.saturating_sub(2) as usize;
+
+        let display_name = Paragraph::new(self.display_name.line(focused(ProfileField::DisplayName), width, Style::default().fg(theme.text)))
+        .block(self.field_block(ProfileField::DisplayName, " Display name ", theme));
+        frame.render_widget(display_name, chunks[0]);
+
+        let count = self.description.char_count();
+        let count_color = if count > MAX_AGENT_DESCRIPTION_LEN { theme.error } else { theme.muted };
+        let description = Paragraph::new(self.description.lines(focused(ProfileField::Description), Style::default().fg(theme.text)))
+        .wrap(Wrap { trim: false })
+        .block(
+            self.field_block(ProfileField::Description, " Description ", theme)
+                .title_bottom(Line::from(Span::styled(
+                    format!(" {}/{} ", count, MAX_AGENT_DESCRIPTION_LEN),
+                    Style::default().fg(count_color),
+                )).right_aligned()),
+        );
+        frame.render_widget(description, chunks[1]);
+
+        let avatar = Paragraph::new(self.avatar_url.line(focused(ProfileField::AvatarUrl), width, Style::default().fg(theme.info)))
+        .block(self.field_block(ProfileField::AvatarUrl, " Avatar URL ", theme));
+        frame.render_widget(avatar, chunks[2]);
+
+        let (button_text, button_color) = if self.loading {
+            (format!("{} Loading profile...", spinner), theme.warning)
+        } else if self.saving {
+            (format!("{} Saving...", spinner), theme.warning)
+        } else {
+            ("[ Save profile ]".to_string(), theme.accent)
+        };
+        let mut button_style = Style::default().fg(button_color);
+        if self.focus == ProfileField::Save {
+            button_style = button_style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        }
+        let button = Paragraph::new(Span::styled(button_text, button_style))
+            .alignment(Alignment::Center)
+            .block(self.field_block(ProfileField::Save, "", theme));
+        frame.render_widget(button, chunks[3]);
+    }
+}
@@ -1,24 +1,41 @@
 //! Prompt agent screen with chat-style UI.
 
 use crate::{
-    app::{App, AppMessage, ScreenAction},
-    client::{ApiClient, ChatMessage, ChainEventData},
+    agent_assets::AgentSource,
+    app::{App, AppMessage, MessageContext, ScreenAction},
+    attachment::{self, Attachment},
+    client::{ApiClient, ChatMessage, ChainEventData, MoltbookPost, SubmitResponse},
     config::AppConfig,
     extrinsic,
-    screens::Screen,
+    funds::{format_tokens, Funds},
+    notifications::Level,
+    runlog::RunLog,
+    schedule::{self, ScheduledPrompt, When},
+    screens::{focus::{self, FocusRing}, input::{SelectList, TextInput}, network_badge, receipt, Screen},
+    text::{display_width, shorten_middle, truncate_width},
+    theme::Theme,
     wallet::WalletConfig,
+    webhook::{self, WebhookEvent},
 };
 use anyhow::Result;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::VecDeque;
+use std::time::Duration;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph, Wrap},
     Frame,
 };
 use tokio::sync::mpsc;
 
+/// Lines of an attached file shown under the prompt.
+const ATTACHMENT_PREVIEW_LINES: u16 = 4;
+
+/// Posts fetched for the posts pane.
+const POSTS_PANE_LIMIT: u32 = 10;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum PromptStep {
     EnterPrompt,
@@ -27,6 +44,48 @@ pub enum PromptStep {
     Complete,
 }
 
+/// Inputs of the prompt form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptField {
+    Prompt,
+    /// When to send it; real runs only
+    SendAt,
+}
+
+/// Background results for the prompt screen.
+#[derive(Debug, Clone)]
+pub enum PromptMessage {
+    /// Prompt submitted, now streaming
+    PromptSubmitted { run_id: u64, receipt: SubmitResponse },
+    /// Dry run compiled, now streaming
+    SimulationStarted,
+    /// Structured chain event from agent run
+    ChainEvent(ChainEventData),
+    /// Status message (non-structured feedback)
+    PromptStatus(String),
+    /// Fee and agent balance for the prompt as it was when asked
+    CostEstimated(CostPreview),
+    /// Agent run completed
+    RunCompleted { result: String },
+    /// Prompt failed
+    PromptFailed(String),
+    /// Latest posts of the agent for the posts pane (stale fetches for
+    /// another agent are ignored)
+    PostsFetched { agent_address: String, posts: Vec<MoltbookPost> },
+    PostsFetchFailed(String),
+}
+
+/// What sending the prompt would cost, shown before it is sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostPreview {
+    /// Prompt the estimate was made for
+    pub input: String,
+    /// Transaction fee in planck, paid by the wallet; or why it is unknown
+    pub fee: Result<u128, String>,
+    /// Agent account balance in planck, which pays for the run's LLM and tool calls
+    pub agent_balance: Option<u128>,
+}
+
 /// Status of running tools
 #[derive(Debug, Clone)]
 pub struct ToolStatus {
@@ -36,7 +95,7 @@ pub struct ToolStatus {
 
 pub struct PromptScreen {
     pub step: PromptStep,
-    pub input_buffer: String,
+    pub input_buffer: TextInput,
     pub run_id: Option<u64>,
     /// Accumulated chat messages from the conversation
     pub chat_messages: Vec<ChatMessage>,
@@ -52,13 +111,54 @@ pub struct PromptScreen {
     pub detailed_view: bool,
     /// Scroll offset for conversation view
     pub scroll_offset: u16,
+    /// Dry run: the local agent files run on the server, off-chain, with
+    /// tool calls reported instead of executed
+    pub simulate: bool,
+    /// Block and events of the submitted prompt transaction
+    pub receipt: Option<SubmitResponse>,
+    /// Explorer page of the receipt's block
+    pub receipt_link: Option<String>,
+    /// Receipt panel shown above the conversation (toggle with 't')
+    pub show_receipt: bool,
+    /// When to send the prompt instead of now: a block, delay or UTC time
+    pub schedule_input: TextInput,
+    /// Whether typing goes to the prompt or the schedule field
+    pub focus: FocusRing<PromptField>,
+    /// Confirmation of the last prompt put on the schedule
+    pub scheduled: Option<String>,
+    /// The run failed rather than being cancelled, so it can be retried
+    pub failed: bool,
+    /// Latest cost estimate for the prompt being typed
+    pub cost: Option<CostPreview>,
+    /// Prompt of the estimate in flight, if any
+    pub cost_pending: Option<String>,
+    /// Prompts to send one after another as each run completes
+    pub follow_ups: VecDeque<String>,
+    /// Next prompt being typed while a run streams
+    pub follow_up_input: Option<TextInput>,
+    /// Quick-send menu of the agent's favorite prompts is open
+    pub favorites_open: bool,
+    pub favorite_selected: SelectList,
+    /// File sent below the prompt
+    pub attachment: Option<Attachment>,
+    /// Path of a file to attach, being typed
+    pub attach_input: Option<TextInput>,
+    /// Showing the events of an earlier run of the agent rather than one
+    /// sent from here, so it is not announced, retried or followed up
+    pub replay: bool,
+    /// The agent's recent Moltbook posts beside the conversation (toggle with 'p')
+    pub show_posts: bool,
+    /// Posts in that pane, newest first
+    pub posts: Vec<MoltbookPost>,
+    pub posts_loading: bool,
+    pub posts_error: Option<String>,
 }
 
 impl PromptScreen {
     pub fn new() -> Self {
         Self {
             step: PromptStep::EnterPrompt,
-            input_buffer: String::new(),
+            input_buffer: TextInput::new(),
             run_id: None,
             chat_messages: Vec::new(),
             tool_status: Vec::new(),
@@ -67,11 +167,82 @@ impl PromptScreen {
             error: None,
             detailed_view: true, // Show full details by default
             scroll_offset: 0,
+            simulate: false,
+            receipt: None,
+            receipt_link: None,
+            show_receipt: true,
+            schedule_input: TextInput::new(),
+            focus: FocusRing::new([PromptField::Prompt, PromptField::SendAt]),
+            scheduled: None,
+            failed: false,
+            cost: None,
+            cost_pending: None,
+            follow_ups: VecDeque::new(),
+            follow_up_input: None,
+            favorites_open: false,
+            favorite_selected: SelectList::new(),
+            attachment: None,
+            attach_input: None,
+            replay: false,
+            show_posts: false,
+            posts: Vec::new(),
+            posts_loading: false,
+            posts_error: None,
         }
     }
 
+    /// Start over, keeping the posts pane as the user left it.
     pub fn reset(&mut self) {
-        *self = Self::new();
+        *self = Self { show_posts: self.show_posts, ..Self::new() };
+    }
+
+    /// Show the events of run `run_id`, e.g. a failed scheduled run.
+    pub fn open_run(&mut self, client: &ApiClient, run_id: u64, stall: Option<Duration>, tx: mpsc::Sender<AppMessage>) {
+        *self = Self { step: PromptStep::Running, run_id: Some(run_id), replay: true, show_posts: self.show_posts, ..Self::new() };
+        let client = client.clone();
+        crate::tasks::spawn(Self::stream_run_events(client, run_id, None, stall, tx));
+    }
+
+    fn open_receipt_link(&mut self) {
+        let Some(url) = &self.receipt_link else {
+            return;
+        };
+        if let Err(e) = open::that(url) {
+            tracing::warn!(%url, "Could not open the explorer: {}", e);
+            self.error = Some(format!("Could not open browser: {}", e));
+        }
+    }
+
+    /// Show or hide the posts pane, fetching the posts when it opens.
+    fn toggle_posts(&mut self, config: &AppConfig, client: &ApiClient, tx: mpsc::Sender<AppMessage>) {
+        self.show_posts = !self.show_posts;
+        if let Some(address) = config.agent_address.as_deref().filter(|_| self.show_posts) {
+            self.refresh_posts(client, address, tx);
+        }
+    }
+
+    /// Fill the posts pane left open from an earlier run.
+    fn posts_pane_opened(&mut self, ctx: &MessageContext) {
+        if let Some(address) = ctx.config.agent_address.as_deref().filter(|_| self.show_posts) {
+            self.refresh_posts(ctx.client, address, ctx.tx.clone());
+        }
+    }
+
+    /// Fetch the agent's latest posts for the posts pane.
+    pub fn refresh_posts(&mut self, client: &ApiClient, agent_address: &str, tx: mpsc::Sender<AppMessage>) {
+        self.posts_loading = true;
+        let client = client.clone();
+        let agent_address = agent_address.to_string();
+        crate::tasks::spawn(async move {
+            match client.get_posts(&agent_address, 0, POSTS_PANE_LIMIT).await {
+                Ok(resp) => {
+                    let _ = tx.send(PromptMessage::PostsFetched { agent_address, posts: resp.posts }.into()).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(PromptMessage::PostsFetchFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
     }
 
     /// Scroll up by n lines
@@ -87,61 +258,108 @@ impl PromptScreen {
 
     pub async fn handle_key(
         &mut self,
-        key: KeyCode,
-        config: &AppConfig,
+        event: KeyEvent,
+        config: &mut AppConfig,
         client: &ApiClient,
         wallet: Option<&WalletConfig>,
+        funds: &Funds,
         tx: mpsc::Sender<AppMessage>,
     ) -> Result<ScreenAction> {
+        let key = event.code;
         match self.step {
+            PromptStep::EnterPrompt if self.favorites_open => {
+                let agent_address = config.agent_address.clone().unwrap_or_default();
+                let favorites = config.favorite_prompts(&agent_address).to_vec();
+                match key {
+                    KeyCode::Esc | KeyCode::F(2) => self.favorites_open = false,
+                    key if self.favorite_selected.handle_key(key, favorites.len()) => {}
+                    KeyCode::Char(c @ '1'..='9') => {
+                        let index = c as usize - '1' as usize;
+                        if let Some(prompt) = favorites.get(index) {
+                            self.quick_send(prompt.clone(), config, client, wallet, funds, tx);
+                        }
+                    }
+                    KeyCode::Enter => {
+                        if let Some(prompt) = favorites.get(self.favorite_selected.selected()) {
+                            self.quick_send(prompt.clone(), config, client, wallet, funds, tx);
+                        }
+                    }
+                    KeyCode::Char('x') | KeyCode::Delete => {
+                        if let Some(prompt) = favorites.get(self.favorite_selected.selected()) {
+                            config.toggle_favorite_prompt(&agent_address, prompt);
+                            self.favorite_selected.clamp(favorites.len() - 1);
+                            return Ok(ScreenAction::ConfigChanged);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            PromptStep::EnterPrompt if self.attach_input.is_some() => {
+                let input = self.attach_input.as_mut().expect("checked by the guard");
+                match key {
+                    _ if input.handle_key(event) => {}
+                    KeyCode::Enter => {
+                        let path = self.attach_input.take().unwrap_or_default();
+                        self.attach(path.text());
+                    }
+                    KeyCode::Esc => self.attach_input = None,
+                    _ => {}
+                }
+            }
             PromptStep::EnterPrompt => {
                 match key {
-                    KeyCode::Char(c) => {
-                        self.input_buffer.push(c);
+                    _ if self.focus.is_focused(PromptField::SendAt) && self.schedule_input.handle_key(event) => {}
+                    _ if self.focus.is_focused(PromptField::Prompt) && self.input_buffer.handle_key(event) => {}
+                    // Dry runs go now; only real runs can wait
+                    KeyCode::Down if !self.simulate => self.focus.focus(PromptField::SendAt),
+                    KeyCode::Up => self.focus.focus(PromptField::Prompt),
+                    // Tab is taken by the dry run toggle
+                    KeyCode::BackTab if !self.simulate => {
+                        self.focus.handle_key(key);
                     }
-                    KeyCode::Backspace => {
-                        self.input_buffer.pop();
+                    KeyCode::Tab => {
+                        self.simulate = !self.simulate;
+                        self.focus.focus(PromptField::Prompt);
                     }
-                    KeyCode::Enter if !self.input_buffer.is_empty() => {
-                        // Check wallet exists
-                        let wallet = match wallet {
-                            Some(w) => w,
-                            None => {
-                                self.error = Some("No wallet available".to_string());
-                                return Ok(ScreenAction::None);
-                            }
-                        };
-                        
-                        // Start submitting
-                        let agent_address = match &config.agent_address {
-                            Some(addr) => addr.clone(),
-                            None => {
-                                self.error = Some("No agent configured".to_string());
-                                return Ok(ScreenAction::None);
-                            }
-                        };
-
-                        self.step = PromptStep::Submitting;
-                        self.status_messages.clear();
-                        self.status_messages.push("Building extrinsic...".to_string());
-
-                        // Start the submit flow
-                        Self::start_prompt_submission(
-                            client.clone(),
-                            wallet.clone(),
-                            agent_address,
-                            self.input_buffer.clone(),
-                            tx,
-                        );
+                    KeyCode::F(3) => {
+                        self.error = None;
+                        self.attach_input = Some(TextInput::new());
+                    }
+                    KeyCode::F(2) if config.agent_address.is_none() => {
+                        self.error = Some("No agent configured".to_string());
                     }
+                    KeyCode::F(2) => {
+                        self.favorites_open = true;
+                        self.favorite_selected = SelectList::new();
+                    }
+                    KeyCode::Enter if !self.input_buffer.is_empty() && !self.schedule_input.text().trim().is_empty() && !self.simulate => {
+                        self.schedule_prompt(config, wallet.is_some());
+                    }
+                    KeyCode::Enter if !self.input_buffer.is_empty() => self.send(config, client, wallet, funds, tx),
                     KeyCode::Esc => {
-                        return Ok(ScreenAction::GoHome);
+                        return Ok(ScreenAction::Pop);
+                    }
+                    _ => {}
+                }
+            }
+            PromptStep::Submitting | PromptStep::Running if self.follow_up_input.is_some() => {
+                let input = self.follow_up_input.as_mut().expect("checked by the guard");
+                match key {
+                    _ if input.handle_key(event) => {}
+                    KeyCode::Enter => {
+                        let input = self.follow_up_input.take().unwrap_or_default();
+                        if !input.text().trim().is_empty() {
+                            self.follow_ups.push_back(input.text().to_string());
+                        }
                     }
+                    KeyCode::Esc => self.follow_up_input = None,
                     _ => {}
                 }
             }
             PromptStep::Submitting | PromptStep::Running => {
                 match key {
+                    KeyCode::Char('n') if !self.replay => self.follow_up_input = Some(TextInput::new()),
+                    KeyCode::Char('f') => return Ok(self.toggle_favorite(config)),
                     KeyCode::Char('d') => {
                         // Toggle detailed view
                         self.detailed_view = !self.detailed_view;
@@ -154,17 +372,21 @@ impl PromptScreen {
                         // Scroll up
                         self.scroll_up(3);
                     }
+                    KeyCode::Esc if self.replay => return Ok(ScreenAction::Pop),
                     KeyCode::Esc => {
                         self.step = PromptStep::Complete;
                         self.error = Some("Cancelled by user (agent may still be running)".to_string());
                     }
+                    KeyCode::Char('t') => self.show_receipt = !self.show_receipt,
+                    KeyCode::Char('o') => self.open_receipt_link(),
+                    KeyCode::Char('p') => self.toggle_posts(config, client, tx),
                     _ => {}
                 }
             }
             PromptStep::Complete => {
                 match key {
                     KeyCode::Enter | KeyCode::Esc => {
-                        return Ok(ScreenAction::GoHome);
+                        return Ok(ScreenAction::Pop);
                     }
                     KeyCode::Char('j') | KeyCode::Down => {
                         self.scroll_down(3);
@@ -175,6 +397,11 @@ impl PromptScreen {
                     KeyCode::Char('d') => {
                         self.detailed_view = !self.detailed_view;
                     }
+                    KeyCode::Char('r') if self.can_retry() => self.retry(config, client, wallet, funds, tx),
+                    KeyCode::Char('f') => return Ok(self.toggle_favorite(config)),
+                    KeyCode::Char('t') => self.show_receipt = !self.show_receipt,
+                    KeyCode::Char('o') => self.open_receipt_link(),
+                    KeyCode::Char('p') => self.toggle_posts(config, client, tx),
                     _ => {}
                 }
             }
@@ -182,21 +409,249 @@ impl PromptScreen {
         Ok(ScreenAction::None)
     }
 
+    /// Start a dry run or a real run of the prompt in `input_buffer`, with
+    /// the attached file if any.
+    fn send(
+        &mut self,
+        config: &AppConfig,
+        client: &ApiClient,
+        wallet: Option<&WalletConfig>,
+        funds: &Funds,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        self.error = None;
+        if self.simulate {
+            self.step = PromptStep::Submitting;
+            self.input_buffer.set_text(self.payload());
+            self.attachment = None;
+            self.status_messages.clear();
+            self.status_messages.push("Compiling the agent files for a dry run...".to_string());
+            let source = config.custom_agent_dir.clone().map_or(AgentSource::Embedded, AgentSource::Custom);
+            let log = config.run_logs.then(RunLog::for_dry_run).flatten();
+            Self::start_simulation(client.clone(), source, self.input_buffer.text().to_string(), log, config.stream_stall_timeout(), tx);
+            return;
+        }
+
+        // Check wallet exists
+        let Some(wallet) = wallet else {
+            self.error = Some("No wallet available".to_string());
+            return;
+        };
+        let Some(agent_address) = config.agent_address.clone() else {
+            self.error = Some("No agent configured".to_string());
+            return;
+        };
+
+        // The wallet signs and pays for the run; the agent pays for its tool calls
+        if let Err(e) = funds.check_wallet(0).and_then(|()| funds.check_agent()) {
+            self.error = Some(e);
+            return;
+        }
+
+        self.step = PromptStep::Submitting;
+        // The file becomes part of the prompt, so a retry sends it again
+        self.input_buffer.set_text(self.payload());
+        self.attachment = None;
+        self.status_messages.clear();
+        self.status_messages.push("Building extrinsic...".to_string());
+
+        // Start the submit flow
+        Self::start_prompt_submission(
+            client.clone(),
+            wallet.clone(),
+            agent_address,
+            self.input_buffer.text().to_string(),
+            config.run_logs,
+            config.stream_stall_timeout(),
+            tx,
+        );
+    }
+
+    /// Read the file at `path` to go out with the prompt; an empty path
+    /// drops the current attachment.
+    fn attach(&mut self, path: &str) {
+        self.error = None;
+        if path.trim().is_empty() {
+            self.attachment = None;
+            return;
+        }
+        match Attachment::load(path) {
+            Ok(file) => self.attachment = Some(file),
+            Err(e) => self.error = Some(format!("{:#}", e)),
+        }
+    }
+
+    /// What sending now would send: the typed prompt and any attached file.
+    fn payload(&self) -> String {
+        match &self.attachment {
+            Some(file) => file.append_to(self.input_buffer.text()),
+            None => self.input_buffer.text().to_string(),
+        }
+    }
+
+    /// Mark the sent prompt as one of the agent's favorites, or unmark it.
+    fn toggle_favorite(&mut self, config: &mut AppConfig) -> ScreenAction {
+        let Some(agent_address) = config.agent_address.clone() else {
+            return ScreenAction::None;
+        };
+        if self.input_buffer.text().trim().is_empty() {
+            return ScreenAction::None;
+        }
+        config.toggle_favorite_prompt(&agent_address, self.input_buffer.text());
+        ScreenAction::ConfigChanged
+    }
+
+    /// Send a favorite from the quick-send menu right away, as a dry run
+    /// when that is selected.
+    fn quick_send(
+        &mut self,
+        prompt: String,
+        config: &AppConfig,
+        client: &ApiClient,
+        wallet: Option<&WalletConfig>,
+        funds: &Funds,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        self.favorites_open = false;
+        self.focus.focus(PromptField::Prompt);
+        self.input_buffer.set_text(prompt);
+        self.send(config, client, wallet, funds, tx);
+    }
+
+    /// A prompt sent from here failed, rather than being cancelled or replayed.
+    fn can_retry(&self) -> bool {
+        self.failed && !self.replay
+    }
+
+    /// Send the failed prompt again as it was, dropping what the failed
+    /// attempt showed. The call is rebuilt, so it is signed with a fresh nonce.
+    fn retry(
+        &mut self,
+        config: &AppConfig,
+        client: &ApiClient,
+        wallet: Option<&WalletConfig>,
+        funds: &Funds,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        let input = self.input_buffer.take();
+        self.restart(input);
+        self.send(config, client, wallet, funds, tx);
+    }
+
+    /// Clear the last run for a new one of `input`, keeping the view
+    /// settings and the queued follow-ups.
+    fn restart(&mut self, input: String) {
+        *self = Self {
+            input_buffer: TextInput::with_text(input),
+            simulate: self.simulate,
+            detailed_view: self.detailed_view,
+            show_receipt: self.show_receipt,
+            follow_ups: std::mem::take(&mut self.follow_ups),
+            show_posts: self.show_posts,
+            posts: std::mem::take(&mut self.posts),
+            ..Self::new()
+        };
+    }
+
+    /// Prompt to estimate the cost of next, when the preview is out of date
+    /// and no estimate is in flight (see `App::preview_prompt_cost`).
+    pub fn cost_request(&mut self) -> Option<String> {
+        let payload = self.payload();
+        let stale = self.cost.as_ref().is_none_or(|c| c.input != payload);
+        if self.step != PromptStep::EnterPrompt || self.simulate || self.cost_pending.is_some() || !stale {
+            return None;
+        }
+        self.cost_pending = Some(payload);
+        self.cost_pending.clone()
+    }
+
+    /// Build and sign the call for `input` the way sending it would, without
+    /// taking a nonce, and ask the gateway what it would charge. The agent's
+    /// balance is fetched alongside.
+    pub fn start_cost_estimate(
+        client: ApiClient,
+        wallet: WalletConfig,
+        agent_address: String,
+        input: String,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        crate::tasks::spawn(async move {
+            let fee = async {
+                let keypair = wallet.keypair()?;
+                let build = client.build_call(&agent_address, &input, &wallet.public_key).await?;
+                let call_data = hex::decode(build.call_data_hex.trim_start_matches("0x"))?;
+                let genesis_hash: [u8; 32] = hex::decode(build.genesis_hash.trim_start_matches("0x"))?
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("Invalid genesis hash"))?;
+                let signed_hex = extrinsic::build_signed_extrinsic(
+                    &call_data,
+                    build.nonce,
+                    &genesis_hash,
+                    build.spec_version,
+                    build.transaction_version,
+                    &keypair,
+                )?;
+                let estimate = client.estimate_fee(&signed_hex).await?;
+                estimate.partial_fee.parse::<u128>().map_err(anyhow::Error::from)
+            };
+            let (fee, balance) = tokio::join!(fee, client.get_balance(&agent_address));
+            let preview = CostPreview {
+                input,
+                fee: fee.map_err(|e: anyhow::Error| format!("{:#}", e)),
+                agent_balance: balance.ok().and_then(|b| b.balance.parse().ok()),
+            };
+            let _ = tx.send(PromptMessage::CostEstimated(preview).into()).await;
+        });
+    }
+
+    /// Put the prompt on the schedule for `lobster` or `lobster daemon` to
+    /// submit when it is due, then clear the form for the next one.
+    fn schedule_prompt(&mut self, config: &AppConfig, has_wallet: bool) {
+        self.error = None;
+        let Some(agent_address) = config.agent_address.clone() else {
+            self.error = Some("No agent configured".to_string());
+            return;
+        };
+        if !has_wallet {
+            self.error = Some("No wallet available".to_string());
+            return;
+        }
+        let when = match When::parse(self.schedule_input.text(), schedule::now()) {
+            Ok(when) => when,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+        let prompt = ScheduledPrompt { network: config.network().name, agent_address, prompt: self.payload(), when };
+        if let Err(e) = schedule::add(prompt) {
+            self.error = Some(format!("Could not save the schedule: {:#}", e));
+            return;
+        }
+        self.scheduled = Some(format!("Scheduled for {}. It is sent then while lobster or lobster daemon is running.", when.label()));
+        self.input_buffer.clear();
+        self.attachment = None;
+        self.schedule_input.clear();
+        self.focus.focus(PromptField::Prompt);
+    }
+
     fn start_prompt_submission(
         client: ApiClient,
         wallet: WalletConfig,
         agent_address: String,
         input: String,
+        run_logs: bool,
+        stall: Option<Duration>,
         tx: mpsc::Sender<AppMessage>,
     ) {
         let signer_address = wallet.public_key.clone();
 
-        tokio::spawn(async move {
+        crate::tasks::spawn_operation("an agent run", async move {
             // Step 1: Build the extrinsic
             let build_result = match client.build_call(&agent_address, &input, &signer_address).await {
                 Ok(r) => r,
                 Err(e) => {
-                    let _ = tx.send(AppMessage::PromptFailed(format!("Build failed: {}", e))).await;
+                    let _ = tx.send(PromptMessage::PromptFailed(format!("Build failed: {:#}", e)).into()).await;
                     return;
                 }
             };
@@ -205,7 +660,7 @@ impl PromptScreen {
             let call_data = match hex::decode(build_result.call_data_hex.trim_start_matches("0x")) {
                 Ok(d) => d,
                 Err(e) => {
-                    let _ = tx.send(AppMessage::PromptFailed(format!("Invalid call data: {}", e))).await;
+                    let _ = tx.send(PromptMessage::PromptFailed(format!("Invalid call data: {:#}", e)).into()).await;
                     return;
                 }
             };
@@ -217,7 +672,7 @@ impl PromptScreen {
                     arr
                 }
                 _ => {
-                    let _ = tx.send(AppMessage::PromptFailed("Invalid genesis hash".to_string())).await;
+                    let _ = tx.send(PromptMessage::PromptFailed("Invalid genesis hash".to_string()).into()).await;
                     return;
                 }
             };
@@ -226,17 +681,18 @@ impl PromptScreen {
             let keypair = match wallet.keypair() {
                 Ok(k) => k,
                 Err(e) => {
-                    let _ = tx.send(AppMessage::PromptFailed(format!("Wallet error: {}", e))).await;
+                    let _ = tx.send(PromptMessage::PromptFailed(format!("Wallet error: {:#}", e)).into()).await;
                     return;
                 }
             };
 
-            let _ = tx.send(AppMessage::PromptStatus("Signing extrinsic...".to_string())).await;
+            let _ = tx.send(PromptMessage::PromptStatus("Signing extrinsic...".to_string()).into()).await;
 
             // Step 4: Sign
+            let reservation = crate::nonce::reserve(&wallet.public_key, build_result.nonce);
             let signed_hex = match extrinsic::build_signed_extrinsic(
                 &call_data,
-                build_result.nonce,
+                reservation.nonce,
                 &genesis_hash,
                 build_result.spec_version,
                 build_result.transaction_version,
@@ -244,18 +700,21 @@ impl PromptScreen {
             ) {
                 Ok(h) => h,
                 Err(e) => {
-                    let _ = tx.send(AppMessage::PromptFailed(format!("Signing failed: {}", e))).await;
+                    let _ = tx.send(PromptMessage::PromptFailed(format!("Signing failed: {:#}", e)).into()).await;
                     return;
                 }
             };
 
-            let _ = tx.send(AppMessage::PromptStatus("Submitting to chain...".to_string())).await;
+            let _ = tx.send(PromptMessage::PromptStatus("Submitting to chain...".to_string()).into()).await;
 
             // Step 5: Submit
             let submit_result = match client.submit_extrinsic(&signed_hex).await {
-                Ok(r) => r,
+                Ok(r) => {
+                    reservation.included();
+                    r
+                }
                 Err(e) => {
-                    let _ = tx.send(AppMessage::PromptFailed(format!("Submit failed: {}", e))).await;
+                    let _ = tx.send(PromptMessage::PromptFailed(format!("Submit failed: {:#}", e)).into()).await;
                     return;
                 }
             };
@@ -265,14 +724,41 @@ impl PromptScreen {
             
             match run_id {
                 Some(id) => {
-                    let _ = tx.send(AppMessage::PromptSubmitted { run_id: id }).await;
+                    let _ = tx.send(PromptMessage::PromptSubmitted { run_id: id, receipt: submit_result }.into()).await;
                     // Start streaming events
-                    Self::stream_run_events(client, id, tx).await;
+                    let log = run_logs.then(|| RunLog::for_run(&agent_address, id)).flatten();
+                    Self::stream_run_events(client, id, log, stall, tx).await;
                 }
                 None => {
-                    let _ = tx.send(AppMessage::PromptFailed(
+                    let _ = tx.send(PromptMessage::PromptFailed(
                         "Could not find AgentCallQueued event".to_string()
-                    )).await;
+                    ).into()).await;
+                }
+            }
+        });
+    }
+
+    /// Dry-run the agent files in `source` against `input`.
+    fn start_simulation(
+        client: ApiClient,
+        source: AgentSource,
+        input: String,
+        log: Option<RunLog>,
+        stall: Option<Duration>,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        crate::tasks::spawn(async move {
+            let read = |name| source.read_file(name).unwrap_or_default();
+            let result = client
+                .simulate(&read("moltbook_agent.ship"), &read("SOUL.md"), &read("SKILL.md"), &read("HEARTBEAT.md"), &input)
+                .await;
+            match result {
+                Ok(resp) => {
+                    let _ = tx.send(PromptMessage::SimulationStarted.into()).await;
+                    Self::consume_events(resp, log, stall, tx).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(PromptMessage::PromptFailed(format!("Dry run failed: {:#}", e)).into()).await;
                 }
             }
         });
@@ -281,40 +767,47 @@ impl PromptScreen {
     async fn stream_run_events(
         client: ApiClient,
         run_id: u64,
+        log: Option<RunLog>,
+        stall: Option<Duration>,
         tx: mpsc::Sender<AppMessage>,
     ) {
-        let _ = tx.send(AppMessage::PromptStatus(format!("Run ID: {} - Streaming events...", run_id))).await;
-
-        // Get the SSE stream URL and start consuming events
-        let url = format!("{}/chain/events/{}", client.base_url(), run_id);
-        
-        let http_client = reqwest::Client::new();
-        let mut req = http_client.get(&url);
-        
-        if let Some(token) = client.auth_token() {
-            req = req.header("Authorization", format!("Bearer {}", token));
-        }
+        let _ = tx.send(PromptMessage::PromptStatus(format!("Run ID: {} - Streaming events...", run_id)).into()).await;
 
-        let resp = match req.send().await {
+        let resp = match client.run_events(run_id).await {
             Ok(r) => r,
             Err(e) => {
-                let _ = tx.send(AppMessage::PromptFailed(format!("SSE connection failed: {}", e))).await;
+                let _ = tx.send(PromptMessage::PromptFailed(format!("SSE connection failed: {:#}", e)).into()).await;
                 return;
             }
         };
 
-        if !resp.status().is_success() {
-            let _ = tx.send(AppMessage::PromptFailed(format!("SSE connection error: {}", resp.status()))).await;
-            return;
-        }
+        Self::consume_events(resp, log, stall, tx).await;
+    }
 
+    /// Forward run events from an SSE response until the run completes or
+    /// fails, appending each to `log` when there is one. A stream silent for
+    /// `stall` fails the run, unless the run is waiting for input.
+    async fn consume_events(
+        resp: reqwest::Response,
+        mut log: Option<RunLog>,
+        stall: Option<Duration>,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
         // Use eventsource-stream to consume SSE events
         use eventsource_stream::Eventsource;
         use futures::StreamExt;
 
         let mut stream = resp.bytes_stream().eventsource();
-
-        while let Some(event_result) = stream.next().await {
+        let mut waiting = false;
+
+        loop {
+            let limit = stall.filter(|_| !waiting).unwrap_or(Duration::MAX);
+            let Ok(next) = tokio::time::timeout(limit, stream.next()).await else {
+                let message = format!("No run events for {}s; the stream stalled", limit.as_secs());
+                let _ = tx.send(PromptMessage::PromptFailed(message).into()).await;
+                break;
+            };
+            let Some(event_result) = next else { break };
             match event_result {
                 Ok(event) => {
                     let data = event.data;
@@ -322,36 +815,45 @@ impl PromptScreen {
                     // Try to parse as structured event
                     match serde_json::from_str::<ChainEventData>(&data) {
                         Ok(chain_event) => {
+                            if let Some(log) = &mut log {
+                                log.append(&chain_event);
+                            }
+                            match &chain_event {
+                                ChainEventData::WaitingForInput { .. } => waiting = true,
+                                ChainEventData::Resumed { .. } => waiting = false,
+                                _ => {}
+                            }
                             // Send structured event to UI
-                            let _ = tx.send(AppMessage::ChainEvent(chain_event.clone())).await;
+                            let _ = tx.send(PromptMessage::ChainEvent(chain_event.clone()).into()).await;
                             
                             // Check if run completed
                             match chain_event {
                                 ChainEventData::Completed { output, .. } => {
-                                    let _ = tx.send(AppMessage::RunCompleted { result: output }).await;
+                                    let _ = tx.send(PromptMessage::RunCompleted { result: output }.into()).await;
                                     break;
                                 }
                                 ChainEventData::Failed { reason, .. } => {
-                                    let _ = tx.send(AppMessage::PromptFailed(reason)).await;
+                                    let _ = tx.send(PromptMessage::PromptFailed(reason).into()).await;
                                     break;
                                 }
                                 _ => {}
                             }
                         }
-                        Err(_) => {
+                        Err(e) => {
+                            tracing::debug!(event = %event.event, "Unrecognised run event: {}", e);
                             // Fallback to raw event display
-                            let _ = tx.send(AppMessage::PromptStatus(format!("[{}] {}", event.event, data))).await;
+                            let _ = tx.send(PromptMessage::PromptStatus(format!("[{}] {}", event.event, data)).into()).await;
                             
                             // Check for error event type
                             if event.event == "error" {
-                                let _ = tx.send(AppMessage::PromptFailed(data)).await;
+                                let _ = tx.send(PromptMessage::PromptFailed(data).into()).await;
                                 break;
                             }
                         }
                     }
                 }
                 Err(e) => {
-                    let _ = tx.send(AppMessage::PromptFailed(format!("SSE error: {}", e))).await;
+                    let _ = tx.send(PromptMessage::PromptFailed(format!("SSE error: {:#}", e)).into()).await;
                     break;
                 }
             }
@@ -407,7 +909,7 @@ impl PromptScreen {
             }
             ChainEventData::Raw { variant, data } => {
                 self.status_messages.push(format!("[{}] {}", variant, 
-                    if data.len() > 50 { format!("{}...", &data[..50]) } else { data }));
+                    truncate_width(&data, 53)));
             }
         }
     }
@@ -421,12 +923,19 @@ impl PromptScreen {
         }
     }
 
-    pub fn handle_prompt_submitted(&mut self, run_id: u64) {
+    pub fn handle_prompt_submitted(&mut self, run_id: u64, receipt: SubmitResponse, link: Option<String>) {
         self.run_id = Some(run_id);
+        self.receipt = Some(receipt);
+        self.receipt_link = link;
         self.step = PromptStep::Running;
         self.status_messages.push(format!("Submitted! Run ID: {}", run_id));
     }
 
+    pub fn handle_simulation_started(&mut self) {
+        self.step = PromptStep::Running;
+        self.status_messages.push("Dry run started - tool calls are shown, not executed".to_string());
+    }
+
     pub fn handle_run_completed(&mut self, result: String) {
         self.step = PromptStep::Complete;
         self.final_output = Some(result);
@@ -434,29 +943,128 @@ impl PromptScreen {
 
     pub fn handle_prompt_failed(&mut self, error: String) {
         self.step = PromptStep::Complete;
+        self.failed = true;
         self.error = Some(error);
     }
 
+    pub fn handle_message(&mut self, msg: PromptMessage, ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            PromptMessage::PromptSubmitted { run_id, receipt } => {
+                let link = ctx.config.explorer_block_link(&receipt.block_hash);
+                self.handle_prompt_submitted(run_id, receipt, link);
+                self.posts_pane_opened(ctx);
+            }
+            PromptMessage::SimulationStarted => {
+                self.handle_simulation_started();
+                self.posts_pane_opened(ctx);
+            }
+            PromptMessage::CostEstimated(preview) => {
+                if let Err(e) = &preview.fee {
+                    tracing::debug!("Fee estimate failed: {}", e);
+                }
+                self.cost_pending = None;
+                self.cost = Some(preview);
+            }
+            PromptMessage::ChainEvent(event) => {
+                // A post the agent just made shows up without waiting for the timer
+                let posted = matches!(&event, ChainEventData::ToolsCompleted { tools, .. } if tools.iter().any(|t| t == "moltbook_post"));
+                self.handle_chain_event(event);
+                if let Some(address) = ctx.config.agent_address.as_deref().filter(|_| posted && self.show_posts) {
+                    self.refresh_posts(ctx.client, address, ctx.tx.clone());
+                }
+            }
+            PromptMessage::PostsFetched { agent_address, posts } => {
+                if ctx.config.agent_address.as_deref() == Some(agent_address.as_str()) {
+                    self.posts = posts;
+                    self.posts_error = None;
+                }
+                self.posts_loading = false;
+            }
+            PromptMessage::PostsFetchFailed(e) => {
+                self.posts_error = Some(e);
+                self.posts_loading = false;
+            }
+            PromptMessage::PromptStatus(msg) => self.handle_status_message(msg),
+            PromptMessage::RunCompleted { result } if self.replay => self.handle_run_completed(result),
+            PromptMessage::PromptFailed(e) if self.replay => self.handle_prompt_failed(e),
+            PromptMessage::RunCompleted { result } => {
+                ctx.notify(Level::Success, "Agent run finished");
+                let summary = truncate_width(result.trim(), 200);
+                ctx.desktop_notify("Agent run finished", &summary);
+                webhook::send(ctx.config, WebhookEvent::RunCompleted, &summary);
+                self.handle_run_completed(result);
+                // Not after Esc: the queue waits for the user to come back to it
+                let next = if self.error.is_none() { self.follow_ups.pop_front() } else { None };
+                if let Some(next) = next {
+                    ctx.notify(Level::Info, format!("Sending the next prompt: {}", truncate_width(&next, 60)));
+                    self.restart(next);
+                    self.send(ctx.config, ctx.client, ctx.wallet, &ctx.funds, ctx.tx.clone());
+                }
+            }
+            PromptMessage::PromptFailed(e) => {
+                ctx.notify(Level::Error, format!("Prompt failed: {}", e));
+                ctx.desktop_notify("Agent run failed", &e);
+                webhook::send(ctx.config, WebhookEvent::RunFailed, &e);
+                self.handle_prompt_failed(e);
+            }
+        }
+        Ok(ScreenAction::None)
+    }
+
     /// Render the chat-style view of messages (scrollable, filtered)
-    fn render_chat_view(&self, frame: &mut Frame, area: Rect) {
+    /// The agent's latest posts, refreshed while the pane is open.
+    fn render_posts(&self, frame: &mut Frame, area: Rect, spinner: &str, theme: &Theme) {
+        let title = if self.posts_loading {
+            format!(" Agent Posts {} ", spinner)
+        } else {
+            " Agent Posts ".to_string()
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(title, Style::default().fg(theme.text)))
+            .padding(Padding::horizontal(1));
+        let width = area.width.saturating_sub(4) as usize;
+        let mut lines = Vec::new();
+        if let Some(err) = &self.posts_error {
+            lines.push(Line::from(Span::styled(truncate_width(err, width), Style::default().fg(theme.error))));
+        }
+        if self.posts.is_empty() && !self.posts_loading {
+            lines.push(Line::from(Span::styled("No posts yet", Style::default().fg(theme.muted))));
+        }
+        for post in &self.posts {
+            let submolt = post.submolt.as_ref().map(|s| s.name.as_str()).unwrap_or("general");
+            let text = post.title.as_deref().or(post.content.as_deref()).unwrap_or("");
+            lines.push(Line::from(vec![
+                Span::styled(format!("m/{} ", submolt), Style::default().fg(theme.accent)),
+                Span::styled(format!("↑{}", post.upvotes), Style::default().fg(theme.success)),
+                Span::styled(format!(" • {}", post.created_at), Style::default().fg(theme.muted)),
+            ]));
+            lines.push(Line::from(Span::styled(truncate_width(text, width), Style::default().fg(theme.text))));
+            lines.push(Line::from(""));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_chat_view(&self, frame: &mut Frame, area: Rect, spinner: &'static str, theme: &Theme) {
         let mut lines: Vec<Line> = Vec::new();
 
         // User's initial prompt
         if !self.input_buffer.is_empty() {
             lines.push(Line::from(""));
             lines.push(Line::from(vec![
-                Span::styled("  You", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled("  You", Style::default().fg(theme.info).add_modifier(Modifier::BOLD)),
             ]));
             // Show prompt (truncated if long)
-            let prompt_lines: Vec<&str> = self.input_buffer.lines().collect();
+            let prompt_lines: Vec<&str> = self.input_buffer.text().lines().collect();
             for line in prompt_lines.iter().take(4) {
                 lines.push(Line::from(vec![
-                    Span::styled("  │ ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(line.to_string(), Style::default().fg(Color::White)),
+                    Span::styled("  │ ", Style::default().fg(theme.muted)),
+                    Span::styled(line.to_string(), Style::default().fg(theme.text)),
                 ]));
             }
             if prompt_lines.len() > 4 {
-                lines.push(Line::from(Span::styled("  │ ...", Style::default().fg(Color::DarkGray))));
+                lines.push(Line::from(Span::styled("  │ ...", Style::default().fg(theme.muted))));
             }
             lines.push(Line::from(""));
         }
@@ -483,19 +1091,19 @@ impl PromptScreen {
                     if has_tools {
                         // Show tool calls
                         for tc in tool_calls {
-                            let (icon, icon_color) = self.get_tool_status_icon(&tc.name);
+                            let (icon, icon_color) = self.get_tool_status_icon(&tc.name, spinner, theme);
                             // Get descriptive action based on tool name + arguments
                             let action_desc = Self::describe_tool_action(&tc.name, &tc.arguments);
 
                             lines.push(Line::from(vec![
                                 Span::styled("  ", Style::default()),
                                 Span::styled(format!("{} ", icon), Style::default().fg(icon_color)),
-                                Span::styled(action_desc, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                                Span::styled(action_desc, Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
                             ]));
 
                             // Show relevant params (filter out api_key, endpoint)
                             if self.detailed_view {
-                                let arg_lines = Self::format_tool_args(&tc.arguments);
+                                let arg_lines = Self::format_tool_args(&tc.arguments, theme);
                                 for line in arg_lines {
                                     lines.push(line);
                                 }
@@ -509,12 +1117,12 @@ impl PromptScreen {
                             if !text.is_empty() {
                                 lines.push(Line::from(""));
                                 lines.push(Line::from(vec![
-                                    Span::styled("  Agent", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                                    Span::styled("  Agent", Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD)),
                                 ]));
                                 for line in text.lines() {
                                     lines.push(Line::from(vec![
-                                        Span::styled("  │ ", Style::default().fg(Color::DarkGray)),
-                                        Span::styled(line.to_string(), Style::default().fg(Color::White)),
+                                        Span::styled("  │ ", Style::default().fg(theme.muted)),
+                                        Span::styled(line.to_string(), Style::default().fg(theme.text)),
                                     ]));
                                 }
                             }
@@ -525,8 +1133,8 @@ impl PromptScreen {
                     if let Some(out) = output {
                         if !out.is_empty() {
                             lines.push(Line::from(vec![
-                                Span::styled("  → ", Style::default().fg(Color::Green)),
-                                Span::styled(Self::truncate_string(out, 60), Style::default().fg(Color::Green)),
+                                Span::styled("  → ", Style::default().fg(theme.success)),
+                                Span::styled(truncate_width(out, 60), Style::default().fg(theme.success)),
                             ]));
                         }
                     }
@@ -542,16 +1150,16 @@ impl PromptScreen {
             lines.push(Line::from(""));
             lines.push(Line::from(vec![
                 Span::styled("  ", Style::default()),
-                Span::styled("◐ ", Style::default().fg(Color::Yellow)),
-                Span::styled("Submitting transaction...", Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{} ", spinner), Style::default().fg(theme.warning)),
+                Span::styled("Submitting transaction...", Style::default().fg(theme.warning)),
             ]));
         } else if self.step == PromptStep::Running && self.chat_messages.is_empty() && self.tool_status.is_empty() {
             // Only show "thinking" if we have no info yet
             lines.push(Line::from(""));
             lines.push(Line::from(vec![
                 Span::styled("  ", Style::default()),
-                Span::styled("◐ ", Style::default().fg(Color::Magenta)),
-                Span::styled("Agent is thinking...", Style::default().fg(Color::Magenta)),
+                Span::styled(format!("{} ", spinner), Style::default().fg(theme.highlight)),
+                Span::styled("Agent is thinking...", Style::default().fg(theme.highlight)),
             ]));
         }
 
@@ -574,28 +1182,19 @@ impl PromptScreen {
         let content = Paragraph::new(lines)
             .block(Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(Span::styled(title, Style::default().fg(Color::White))))
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(title, Style::default().fg(theme.text))))
             .scroll((scroll_offset, 0));
 
         frame.render_widget(content, area);
     }
 
     /// Get a human-friendly tool status icon
-    fn get_tool_status_icon(&self, tool_name: &str) -> (&'static str, Color) {
+    fn get_tool_status_icon(&self, tool_name: &str, spinner: &'static str, theme: &Theme) -> (&'static str, Color) {
         self.tool_status.iter()
             .find(|s| s.name == tool_name)
-            .map(|s| if s.completed { ("✓", Color::Green) } else { ("◐", Color::Yellow) })
-            .unwrap_or(("○", Color::DarkGray))
-    }
-
-    /// Truncate a string with ellipsis
-    fn truncate_string(s: &str, max_len: usize) -> String {
-        if s.len() <= max_len {
-            s.to_string()
-        } else {
-            format!("{}...", &s[..max_len.saturating_sub(3)])
-        }
+            .map(|s| if s.completed { ("✓", theme.success) } else { (spinner, theme.warning) })
+            .unwrap_or(("○", theme.muted))
     }
 
     /// Generate human-readable action description from tool name and arguments
@@ -640,43 +1239,39 @@ impl PromptScreen {
     }
 
     /// Format tool arguments for display - only show relevant fields (params/body), skip api_key/endpoint
-    fn format_tool_args(arguments: &str) -> Vec<Line<'static>> {
+    fn format_tool_args(arguments: &str, theme: &Theme) -> Vec<Line<'static>> {
         let parsed: Result<serde_json::Value, _> = serde_json::from_str(arguments);
         let mut lines = Vec::new();
 
         if let Ok(serde_json::Value::Object(map)) = parsed {
             // Check if there's a body (for POST) or params (for GET)
-            if let Some(body) = map.get("body") {
-                if let serde_json::Value::Object(body_map) = body {
-                    let field_count = body_map.len();
-                    for (i, (key, value)) in body_map.iter().enumerate() {
-                        let is_last = i == field_count - 1;
-                        let prefix = if is_last { "    └─ " } else { "    ├─ " };
-                        let formatted_value = Self::format_json_value(value, 50);
+            if let Some(serde_json::Value::Object(body_map)) = map.get("body") {
+                let field_count = body_map.len();
+                for (i, (key, value)) in body_map.iter().enumerate() {
+                    let is_last = i == field_count - 1;
+                    let prefix = if is_last { "    └─ " } else { "    ├─ " };
+                    let formatted_value = Self::format_json_value(value, 50);
                         
-                        lines.push(Line::from(vec![
-                            Span::styled(prefix, Style::default().fg(Color::DarkGray)),
-                            Span::styled(format!("{}: ", key), Style::default().fg(Color::Cyan)),
-                            Span::styled(formatted_value, Style::default().fg(Color::White)),
-                        ]));
-                    }
+                    lines.push(Line::from(vec![
+                        Span::styled(prefix, Style::default().fg(theme.muted)),
+                        Span::styled(format!("{}: ", key), Style::default().fg(theme.info)),
+                        Span::styled(formatted_value, Style::default().fg(theme.text)),
+                    ]));
                 }
             }
             
-            if let Some(params) = map.get("params") {
-                if let serde_json::Value::Object(params_map) = params {
-                    let field_count = params_map.len();
-                    for (i, (key, value)) in params_map.iter().enumerate() {
-                        let is_last = i == field_count - 1;
-                        let prefix = if is_last { "    └─ " } else { "    ├─ " };
-                        let formatted_value = Self::format_json_value(value, 50);
+            if let Some(serde_json::Value::Object(params_map)) = map.get("params") {
+                let field_count = params_map.len();
+                for (i, (key, value)) in params_map.iter().enumerate() {
+                    let is_last = i == field_count - 1;
+                    let prefix = if is_last { "    └─ " } else { "    ├─ " };
+                    let formatted_value = Self::format_json_value(value, 50);
                         
-                        lines.push(Line::from(vec![
-                            Span::styled(prefix, Style::default().fg(Color::DarkGray)),
-                            Span::styled(format!("{}: ", key), Style::default().fg(Color::Cyan)),
-                            Span::styled(formatted_value, Style::default().fg(Color::White)),
-                        ]));
-                    }
+                    lines.push(Line::from(vec![
+                        Span::styled(prefix, Style::default().fg(theme.muted)),
+                        Span::styled(format!("{}: ", key), Style::default().fg(theme.info)),
+                        Span::styled(formatted_value, Style::default().fg(theme.text)),
+                    ]));
                 }
             }
         }
@@ -688,7 +1283,7 @@ impl PromptScreen {
     fn format_json_value(value: &serde_json::Value, max_len: usize) -> String {
         match value {
             serde_json::Value::String(s) => {
-                format!("\"{}\"", Self::truncate_string(s, max_len))
+                format!("\"{}\"", truncate_width(s, max_len))
             }
             serde_json::Value::Number(n) => n.to_string(),
             serde_json::Value::Bool(b) => b.to_string(),
@@ -697,10 +1292,57 @@ impl PromptScreen {
             serde_json::Value::Object(_) => "{...}".to_string(),
         }
     }
+
+    /// Quick-send menu over the prompt form, the first nine numbered.
+    fn render_favorites(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let favorites = app.config.favorite_prompts(app.config.agent_address.as_deref().unwrap_or_default());
+        let width = area.width.saturating_sub(4).min(72);
+        let height = (favorites.len().clamp(1, 12) as u16 + 2).min(area.height);
+        let popup = Rect::new(area.x + (area.width - width) / 2, area.y + area.height / 4, width, height);
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(Span::styled(" Favorite Prompts ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)))
+            .title_bottom(
+                Line::from(Span::styled(" [1-9/Enter] Send  [x] Remove  [Esc] Close ", Style::default().fg(theme.muted)))
+                    .right_aligned(),
+            );
+        if favorites.is_empty() {
+            let empty = Paragraph::new(Span::styled(
+                "  No favorites yet - press f after sending a prompt to keep it here",
+                Style::default().fg(theme.muted),
+            ))
+            .block(block);
+            frame.render_widget(empty, popup);
+            return;
+        }
+        let text_width = width.saturating_sub(8) as usize;
+        let items: Vec<ListItem> = favorites
+            .iter()
+            .enumerate()
+            .map(|(i, prompt)| {
+                let key = if i < 9 { format!("{} ", i + 1) } else { "  ".to_string() };
+                ListItem::new(Line::from(vec![
+                    Span::styled(key, Style::default().fg(theme.muted)),
+                    Span::styled(truncate_width(prompt, text_width), Style::default().fg(theme.text)),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol("▌ ")
+            .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+        let mut state = self.favorite_selected.state();
+        frame.render_stateful_widget(list, popup, &mut state);
+    }
 }
 
 impl Screen for PromptScreen {
     fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
@@ -719,59 +1361,161 @@ impl Screen for PromptScreen {
             PromptStep::Complete => "Complete",
         };
         
-        let title_line = Line::from(vec![
-            Span::styled(" PROMPT AGENT ", Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
-            Span::styled("│ ", Style::default().fg(Color::DarkGray)),
-            Span::styled(step_text, Style::default().fg(Color::LightRed)),
+        let mut title_line = Line::from(vec![
+            network_badge(app.config.network().kind, theme),
+            Span::styled(" PROMPT AGENT ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(step_text, Style::default().fg(theme.accent)),
         ]);
+        if self.simulate {
+            title_line.push_span(Span::styled(" │ ", Style::default().fg(theme.muted)));
+            title_line.push_span(Span::styled("DRY RUN", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)));
+        }
+        let favorite = app
+            .config
+            .agent_address
+            .as_deref()
+            .is_some_and(|a| app.config.favorite_prompts(a).iter().any(|p| p == self.input_buffer.text()));
+        if let Some(run_id) = self.run_id.filter(|_| self.replay) {
+            title_line.push_span(Span::styled(" │ ", Style::default().fg(theme.muted)));
+            title_line.push_span(Span::styled(format!("RUN #{}", run_id), Style::default().fg(theme.info)));
+        }
+        if favorite && self.step != PromptStep::EnterPrompt {
+            title_line.push_span(Span::styled(" │ ", Style::default().fg(theme.muted)));
+            title_line.push_span(Span::styled("★ FAVORITE", Style::default().fg(theme.accent)));
+        }
 
         let title = Paragraph::new(title_line)
             .alignment(Alignment::Center)
             .block(Block::default()
                 .borders(Borders::BOTTOM)
-                .border_style(Style::default().fg(Color::DarkGray)));
+                .border_style(Style::default().fg(theme.muted)));
         frame.render_widget(title, chunks[0]);
 
-        // Content
+        // Content, below the transaction once the prompt is on-chain
+        let mut content = chunks[1];
+        if let Some(receipt) = self.receipt.as_ref().filter(|_| self.show_receipt && self.step != PromptStep::EnterPrompt) {
+            let [receipt_area, rest] =
+                Layout::vertical([Constraint::Length(receipt::height(receipt)), Constraint::Min(6)]).areas(content);
+            receipt::render_receipt(frame, receipt_area, receipt, app);
+            content = rest;
+        }
+        if self.show_posts && self.step != PromptStep::EnterPrompt {
+            let [chat, posts] = Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(content);
+            self.render_posts(frame, posts, app.spinner(), theme);
+            content = chat;
+        }
         match self.step {
             PromptStep::EnterPrompt => {
+                let attachment_height = match (&self.attach_input, &self.attachment) {
+                    (Some(_), _) => 3,
+                    (None, Some(_)) => 2 + ATTACHMENT_PREVIEW_LINES,
+                    (None, None) => 0,
+                };
                 let inner = Layout::default()
                     .direction(Direction::Vertical)
                     .margin(1)
                     .constraints([
                         Constraint::Length(2),  // Agent info
                         Constraint::Length(5),  // Input
+                        Constraint::Length(attachment_height), // Attached file
+                        Constraint::Length(if self.simulate { 0 } else { 3 }), // Schedule
                         Constraint::Min(1),     // Spacer
                     ])
-                    .split(chunks[1]);
+                    .split(content);
 
                 // Agent info (only show if authenticated)
-                let agent_info = if let Some(addr) = app.agent_address() {
-                    let short = if addr.len() > 30 {
-                        format!("{}...{}", &addr[..12], &addr[addr.len() - 8..])
-                    } else {
-                        addr.to_string()
-                    };
+                let agent_info = if self.simulate {
+                    let files = app.config.custom_agent_dir.as_deref().unwrap_or("built-in agent files");
+                    format!("Dry run of {} - nothing is sent to the chain or Moltbook", files)
+                } else if let Some(addr) = app.agent_address() {
+                    let short = shorten_middle(addr, 12, 8);
                     format!("Target: {}", short)
                 } else {
                     "No agent configured".to_string()
                 };
-                let info = Paragraph::new(agent_info)
-                    .style(Style::default().fg(Color::DarkGray));
+                let mut info_lines = vec![Line::from(agent_info)];
+                let mut warning = None;
+                if let Some(cost) = self.cost.as_ref().filter(|_| !self.simulate) {
+                    let (line, low) = cost_lines(cost, &app.funds(), theme);
+                    info_lines.push(line);
+                    warning = low;
+                }
+                let info = Paragraph::new(info_lines)
+                    .style(Style::default().fg(theme.muted));
                 frame.render_widget(info, inner[0]);
 
                 // Input box
-                let cursor = if self.input_buffer.is_empty() { "│" } else { "" };
-                let input = Paragraph::new(format!("{}{}", self.input_buffer, cursor))
-                    .style(Style::default().fg(Color::Cyan))
+                let border = |focused: bool| focus::border(focused, theme);
+                let prompt_focused = self.focus.is_focused(PromptField::Prompt);
+                let input = Paragraph::new(self.input_buffer.lines(prompt_focused, Style::default().fg(theme.info)))
+                    .wrap(Wrap { trim: false })
                     .block(Block::default()
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::DarkGray))
-                        .title(Span::styled(" Your Prompt ", Style::default().fg(Color::White))));
+                        .border_style(border(prompt_focused))
+                        .title(Span::styled(" Your Prompt ", Style::default().fg(theme.text))));
                 frame.render_widget(input, inner[1]);
+
+                if let Some(path) = &self.attach_input {
+                    let width = inner[2].width.saturating_sub(4) as usize;
+                    let text = if path.is_empty() {
+                        Line::from(Span::styled("│  path to a text file, e.g. ~/notes.md (empty removes the attachment)", Style::default().fg(theme.muted)))
+                    } else {
+                        path.line(true, width, Style::default().fg(theme.info))
+                    };
+                    let attach = Paragraph::new(text)
+                        .block(Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(border(true))
+                            .title(Span::styled(" Attach File ", Style::default().fg(theme.text))));
+                    frame.render_widget(attach, inner[2]);
+                } else if let Some(file) = &self.attachment {
+                    let size = if file.truncated() {
+                        format!("{} of {} chars, the rest is cut", attachment::MAX_CHARS, file.total_chars)
+                    } else {
+                        format!("{} chars", file.total_chars)
+                    };
+                    let lines: Vec<Line> = file
+                        .text
+                        .lines()
+                        .take(ATTACHMENT_PREVIEW_LINES as usize)
+                        .map(|l| Line::from(Span::styled(l.to_string(), Style::default().fg(theme.muted))))
+                        .collect();
+                    let preview = Paragraph::new(lines)
+                        .block(Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(border(false))
+                            .title(Span::styled(format!(" Attached: {} ({}) ", file.name, size), Style::default().fg(theme.text))));
+                    frame.render_widget(preview, inner[2]);
+                }
+
+                if !self.simulate {
+                    let when = if self.schedule_input.is_empty() && prompt_focused {
+                        Line::from(Span::styled("now  (↓ to schedule: #1234, +30m or 14:30 UTC)", Style::default().fg(theme.muted)))
+                    } else {
+                        let width = inner[3].width.saturating_sub(2) as usize;
+                        self.schedule_input.line(!prompt_focused, width, Style::default().fg(theme.info))
+                    };
+                    let schedule = Paragraph::new(when)
+                        .block(Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(border(!prompt_focused))
+                            .title(Span::styled(" Send At ", Style::default().fg(theme.text))));
+                    frame.render_widget(schedule, inner[3]);
+                }
+
+                let mut notes = Vec::new();
+                notes.extend(warning);
+                if let Some(scheduled) = &self.scheduled {
+                    notes.push(Line::from(vec![
+                        Span::styled(format!("{} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                        Span::styled(scheduled.as_str(), Style::default().fg(theme.success)),
+                    ]));
+                }
+                frame.render_widget(Paragraph::new(notes).wrap(Wrap { trim: true }), inner[4]);
             }
             PromptStep::Submitting | PromptStep::Running => {
-                self.render_chat_view(frame, chunks[1]);
+                self.render_chat_view(frame, content, app.spinner(), theme);
             }
             PromptStep::Complete => {
                 // Show the final chat view with completion status
@@ -781,16 +1525,16 @@ impl Screen for PromptScreen {
                         Constraint::Min(6),      // Chat messages
                         Constraint::Length(6),   // Final status
                     ])
-                    .split(chunks[1]);
+                    .split(content);
 
                 // Show chat messages if any
-                self.render_chat_view(frame, inner[0]);
+                self.render_chat_view(frame, inner[0], app.spinner(), theme);
 
                 // Completion status box
                 let (icon, header, header_color) = if self.error.is_some() {
-                    ("✗", "Run Failed", Color::Red)
+                    ("✗", "Run Failed", theme.error)
                 } else {
-                    ("✓", "Completed", Color::Green)
+                    ("✓", "Completed", theme.success)
                 };
 
                 let mut status_lines = vec![
@@ -806,80 +1550,400 @@ impl Screen for PromptScreen {
                     if !output.is_empty() {
                         // Clean up the output for display
                         let clean_output = output.trim();
-                        let display = if clean_output.len() > 80 {
-                            format!("{}...", &clean_output[..80])
-                        } else {
-                            clean_output.to_string()
-                        };
+                        let display = truncate_width(clean_output, 83);
                         status_lines.push(Line::from(vec![
                             Span::styled("    ", Style::default()),
-                            Span::styled(display, Style::default().fg(Color::White)),
+                            Span::styled(display, Style::default().fg(theme.text)),
                         ]));
                     }
                 } else if let Some(err) = &self.error {
-                    let error_display = if err.len() > 70 {
-                        format!("{}...", &err[..70])
-                    } else {
-                        err.clone()
-                    };
-                    status_lines.push(Line::from(vec![
+                    let mut error_line = vec![
                         Span::styled("    ", Style::default()),
-                        Span::styled(error_display, Style::default().fg(Color::Red)),
-                    ]));
+                        Span::styled(truncate_width(err, 70), Style::default().fg(theme.error)),
+                    ];
+                    if display_width(err) > 70 {
+                        error_line.push(Span::styled("  [!] Full error", Style::default().fg(theme.muted)));
+                    }
+                    status_lines.push(Line::from(error_line));
                 }
 
                 status_lines.push(Line::from(""));
-                status_lines.push(Line::from(vec![
-                    Span::styled("  Press ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("[Enter]", Style::default().fg(Color::White)),
-                    Span::styled(" to continue  ", Style::default().fg(Color::DarkGray)),
-                ]));
+                let mut press = vec![
+                    Span::styled("  Press ", Style::default().fg(theme.muted)),
+                    Span::styled("[Enter]", Style::default().fg(theme.text)),
+                    Span::styled(" to continue  ", Style::default().fg(theme.muted)),
+                ];
+                if self.can_retry() {
+                    press.push(Span::styled("[r]", Style::default().fg(theme.text)));
+                    press.push(Span::styled(" to retry with the same prompt", Style::default().fg(theme.muted)));
+                }
+                status_lines.push(Line::from(press));
 
                 let status_p = Paragraph::new(status_lines)
                     .block(Block::default()
                         .borders(Borders::TOP)
-                        .border_style(Style::default().fg(Color::DarkGray)));
+                        .border_style(Style::default().fg(theme.muted)));
                 frame.render_widget(status_p, inner[1]);
             }
         }
 
         // Footer
+        let receipt_hint = match (&self.receipt, self.show_receipt) {
+            (None, _) => "",
+            (Some(_), true) => "  [t] Hide transaction",
+            (Some(_), false) => "  [t] Show transaction",
+        };
         let footer_content = match self.step {
             PromptStep::EnterPrompt => Line::from(vec![
-                Span::styled("[Enter] ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Send", Style::default().fg(Color::DarkGray)),
-                Span::styled("  [Esc] ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Cancel", Style::default().fg(Color::DarkGray)),
+                Span::styled("[Enter] ", Style::default().fg(theme.muted)),
+                Span::styled(if self.schedule_input.text().trim().is_empty() { "Send" } else { "Schedule" }, Style::default().fg(theme.muted)),
+                Span::styled("  [Tab] ", Style::default().fg(theme.muted)),
+                Span::styled(if self.simulate { "Real run" } else { "Dry run" }, Style::default().fg(theme.muted)),
+                Span::styled("  [F2] ", Style::default().fg(theme.muted)),
+                Span::styled("Favorites", Style::default().fg(theme.muted)),
+                Span::styled("  [F3] ", Style::default().fg(theme.muted)),
+                Span::styled("Attach file", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Cancel", Style::default().fg(theme.muted)),
             ]),
             PromptStep::Submitting => Line::from(Span::styled(
-                "Submitting to chain...",
-                Style::default().fg(Color::Yellow),
+                if self.simulate { "Compiling for a dry run..." } else { "Submitting to chain..." },
+                Style::default().fg(theme.warning),
             )),
+            PromptStep::Running if self.replay => {
+                let detail_hint = if self.detailed_view { "Hide details" } else { "Show details" };
+                Line::from(vec![
+                    Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                    Span::styled("Scroll", Style::default().fg(theme.muted)),
+                    Span::styled("  [d] ", Style::default().fg(theme.muted)),
+                    Span::styled(detail_hint, Style::default().fg(theme.muted)),
+                    Span::styled("  [p] ", Style::default().fg(theme.muted)),
+                    Span::styled("Posts", Style::default().fg(theme.muted)),
+                    Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                    Span::styled("Back", Style::default().fg(theme.muted)),
+                ])
+            }
             PromptStep::Running => {
                 let detail_hint = if self.detailed_view { "Hide details" } else { "Show details" };
                 Line::from(vec![
-                    Span::styled("[j/k] ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("Scroll", Style::default().fg(Color::DarkGray)),
-                    Span::styled("  [d] ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(detail_hint, Style::default().fg(Color::DarkGray)),
-                    Span::styled("  [Esc] ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("Stop watching", Style::default().fg(Color::DarkGray)),
+                    Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                    Span::styled("Scroll", Style::default().fg(theme.muted)),
+                    Span::styled("  [d] ", Style::default().fg(theme.muted)),
+                    Span::styled(detail_hint, Style::default().fg(theme.muted)),
+                    Span::styled(receipt_hint, Style::default().fg(theme.muted)),
+                    Span::styled("  [f] ", Style::default().fg(theme.muted)),
+                    Span::styled(if favorite { "Unfavorite" } else { "Favorite" }, Style::default().fg(theme.muted)),
+                    Span::styled("  [n] ", Style::default().fg(theme.muted)),
+                    Span::styled("Next prompt", Style::default().fg(theme.muted)),
+                    Span::styled("  [p] ", Style::default().fg(theme.muted)),
+                    Span::styled("Posts", Style::default().fg(theme.muted)),
+                    Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                    Span::styled("Stop watching", Style::default().fg(theme.muted)),
                 ])
             }
             PromptStep::Complete => {
                 let detail_hint = if self.detailed_view { "Hide details" } else { "Show details" };
                 Line::from(vec![
-                    Span::styled("[j/k] ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("Scroll", Style::default().fg(Color::DarkGray)),
-                    Span::styled("  [d] ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(detail_hint, Style::default().fg(Color::DarkGray)),
-                    Span::styled("  [Enter] ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("Continue", Style::default().fg(Color::DarkGray)),
+                    Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                    Span::styled("Scroll", Style::default().fg(theme.muted)),
+                    Span::styled("  [d] ", Style::default().fg(theme.muted)),
+                    Span::styled(detail_hint, Style::default().fg(theme.muted)),
+                    Span::styled(receipt_hint, Style::default().fg(theme.muted)),
+                    Span::styled("  [f] ", Style::default().fg(theme.muted)),
+                    Span::styled(if favorite { "Unfavorite" } else { "Favorite" }, Style::default().fg(theme.muted)),
+                    Span::styled(if self.can_retry() { "  [r] Retry" } else { "" }, Style::default().fg(theme.muted)),
+                    Span::styled("  [p] ", Style::default().fg(theme.muted)),
+                    Span::styled("Posts", Style::default().fg(theme.muted)),
+                    Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                    Span::styled("Continue", Style::default().fg(theme.muted)),
                 ])
             }
         };
 
-        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        // Follow-ups above the keys: the one being typed, or what is queued
+        let queue_line = if let Some(input) = &self.follow_up_input {
+            let mut line = input.line(true, 60, Style::default().fg(theme.info));
+            line.spans.insert(0, Span::styled("Next prompt: ", Style::default().fg(theme.muted)));
+            line.push_span(Span::styled("  [Enter] Queue  [Esc] Discard", Style::default().fg(theme.muted)));
+            Some(line)
+        } else {
+            self.follow_ups.front().map(|next| {
+                let more = match self.follow_ups.len() {
+                    1 => String::new(),
+                    n => format!(" (+{} more)", n - 1),
+                };
+                Line::from(vec![
+                    Span::styled("Queued: ", Style::default().fg(theme.muted)),
+                    Span::styled(truncate_width(next, 50), Style::default().fg(theme.info)),
+                    Span::styled(more, Style::default().fg(theme.muted)),
+                ])
+            })
+        };
+        let footer_lines = match queue_line {
+            Some(queue_line) => vec![queue_line, footer_content],
+            None => vec![footer_content],
+        };
+        let footer = Paragraph::new(footer_lines).alignment(Alignment::Center);
         frame.render_widget(footer, chunks[2]);
+
+        if self.favorites_open {
+            self.render_favorites(frame, area, app);
+        }
+    }
+}
+
+
+/// "Fee ~0.01 THE  Agent balance 12 THE", plus a warning when the wallet
+/// can't pay the fee or the agent is below `min_balance`, the floor it
+/// needs for a run's LLM and tool calls.
+fn cost_lines(cost: &CostPreview, funds: &Funds, theme: &Theme) -> (Line<'static>, Option<Line<'static>>) {
+    let tokens = |planck: u128| format!("{} {}", format_tokens(planck, funds.decimals), funds.symbol);
+    let funds = Funds { agent: cost.agent_balance.or(funds.agent), ..funds.clone() };
+    let mut spans = vec![Span::raw("Fee ")];
+    match cost.fee {
+        Ok(fee) => spans.push(Span::styled(format!("~{}", tokens(fee)), Style::default().fg(theme.text))),
+        Err(_) => spans.push(Span::raw("unknown")),
+    }
+    if let Some(balance) = funds.agent {
+        spans.push(Span::raw("  Agent balance "));
+        spans.push(Span::styled(tokens(balance), Style::default().fg(theme.text)));
+    }
+    let problem = match cost.fee {
+        Ok(fee) => funds.check_wallet(fee).err(),
+        Err(_) => None,
+    }
+    .or_else(|| {
+        funds.check_agent().err().map(|e| format!("{}: the agent may not afford this run's LLM and tool calls", e))
+    });
+    let warning = problem.map(|p| Line::from(vec![
+        Span::styled("⚠ ", Style::default().fg(theme.warning)),
+        Span::styled(p, Style::default().fg(theme.warning)),
+    ]));
+    (Line::from(spans), warning)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppScreen;
+    use crate::client::ToolCallInfo;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app};
+
+    #[tokio::test]
+    async fn test_stream_run_events() {
+        let (tx, mut rx) = mpsc::channel(32);
+        PromptScreen::stream_run_events(crate::testing::mock_client(), 1, None, None, tx).await;
+
+        let mut screen = PromptScreen::new();
+        let mut completed = None;
+        while let Ok(msg) = rx.try_recv() {
+            match msg {
+                AppMessage::Prompt(PromptMessage::ChainEvent(event)) => screen.handle_chain_event(event),
+                AppMessage::Prompt(PromptMessage::RunCompleted { result }) => completed = Some(result),
+                AppMessage::Prompt(PromptMessage::PromptStatus(_)) => {}
+                _ => panic!("unexpected message"),
+            }
+        }
+        assert!(completed.is_some());
+        assert_eq!(screen.final_output, completed);
+        assert!(!screen.chat_messages.is_empty());
+        assert!(screen.tool_status.iter().all(|t| t.completed));
+    }
+
+    #[tokio::test]
+    async fn test_silent_stream_stalls() {
+        let (tx, mut rx) = mpsc::channel(32);
+        // The mock stream pauses between events longer than this
+        PromptScreen::stream_run_events(crate::testing::mock_client(), 1, None, Some(Duration::from_millis(1)), tx).await;
+
+        let mut failure = None;
+        while let Ok(msg) = rx.try_recv() {
+            if let AppMessage::Prompt(PromptMessage::PromptFailed(e)) = msg {
+                failure = Some(e);
+            }
+        }
+        assert!(failure.is_some_and(|e| e.contains("the stream stalled")));
+    }
+
+    #[tokio::test]
+    async fn test_retry_keeps_the_prompt() {
+        let mut config = AppConfig::default();
+        let funds = Funds::new(None, None, 0, &config.network());
+        let (tx, _rx) = mpsc::channel(32);
+        let mut screen = PromptScreen::new();
+        screen.input_buffer.set_text("Post about crabs");
+        screen.simulate = true;
+        screen.status_messages.push("Compiling".to_string());
+
+        // Cancelled runs may still be going; only failures retry
+        screen.step = PromptStep::Complete;
+        screen.handle_key(KeyCode::Char('r').into(), &mut config, &crate::testing::mock_client(), None, &funds, tx.clone()).await.unwrap();
+        assert_eq!(screen.step, PromptStep::Complete);
+
+        screen.handle_prompt_failed("Build failed".to_string());
+        screen.handle_key(KeyCode::Char('r').into(), &mut config, &crate::testing::mock_client(), None, &funds, tx).await.unwrap();
+        assert_eq!(screen.step, PromptStep::Submitting);
+        assert_eq!(screen.input_buffer.text(), "Post about crabs");
+        assert!(screen.simulate && !screen.failed && screen.error.is_none());
+        assert_eq!(screen.status_messages, ["Compiling the agent files for a dry run..."]);
+    }
+
+    #[tokio::test]
+    async fn test_favorite_quick_send() {
+        let mut config = AppConfig { agent_address: Some("5Agent".to_string()), ..AppConfig::default() };
+        let funds = Funds::new(None, None, 0, &config.network());
+        let (tx, _rx) = mpsc::channel(32);
+        let client = crate::testing::mock_client();
+        let mut screen = PromptScreen::new();
+        screen.input_buffer.set_text("Post about crabs");
+        screen.step = PromptStep::Complete;
+        let action = screen.handle_key(KeyCode::Char('f').into(), &mut config, &client, None, &funds, tx.clone()).await.unwrap();
+        assert!(matches!(action, ScreenAction::ConfigChanged));
+        assert_eq!(config.favorite_prompts("5Agent"), ["Post about crabs"]);
+
+        let mut screen = PromptScreen::new();
+        screen.simulate = true;
+        screen.handle_key(KeyCode::F(2).into(), &mut config, &client, None, &funds, tx.clone()).await.unwrap();
+        assert!(screen.favorites_open);
+        // Only listed favorites have a number
+        screen.handle_key(KeyCode::Char('2').into(), &mut config, &client, None, &funds, tx.clone()).await.unwrap();
+        assert_eq!(screen.step, PromptStep::EnterPrompt);
+        screen.handle_key(KeyCode::Char('1').into(), &mut config, &client, None, &funds, tx).await.unwrap();
+        assert!(!screen.favorites_open);
+        assert_eq!(screen.step, PromptStep::Submitting);
+        assert_eq!(screen.input_buffer.text(), "Post about crabs");
+
+        // A second f takes it off the list
+        assert!(!config.toggle_favorite_prompt("5Agent", "Post about crabs"));
+        assert!(config.favorite_prompts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_attached_file_goes_with_the_prompt() {
+        let path = std::env::temp_dir().join(format!("lobster-attach-{}.md", std::process::id()));
+        std::fs::write(&path, "Crabs molt twice a year.\n").unwrap();
+        let mut config = AppConfig::default();
+        let funds = Funds::new(None, None, 0, &config.network());
+        let (tx, _rx) = mpsc::channel(32);
+        let client = crate::testing::mock_client();
+        let mut screen = PromptScreen::new();
+        screen.simulate = true;
+        screen.input_buffer.set_text("Summarize this");
+
+        screen.handle_key(KeyCode::F(3).into(), &mut config, &client, None, &funds, tx.clone()).await.unwrap();
+        for c in path.to_string_lossy().chars() {
+            screen.handle_key(KeyCode::Char(c).into(), &mut config, &client, None, &funds, tx.clone()).await.unwrap();
+        }
+        screen.handle_key(KeyCode::Enter.into(), &mut config, &client, None, &funds, tx.clone()).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(screen.attach_input.is_none() && screen.error.is_none());
+        // The typed prompt stays as it was until it is sent
+        assert_eq!(screen.input_buffer.text(), "Summarize this");
+
+        screen.handle_key(KeyCode::Enter.into(), &mut config, &client, None, &funds, tx).await.unwrap();
+        assert_eq!(screen.step, PromptStep::Submitting);
+        assert!(screen.attachment.is_none());
+        assert!(screen.input_buffer.text().starts_with("Summarize this\n\n--- Attached file: lobster-attach-"));
+        assert!(screen.input_buffer.text().contains("Crabs molt twice a year."));
+    }
+
+    #[tokio::test]
+    async fn test_cost_estimate_follows_input() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut screen = PromptScreen::new();
+        screen.input_buffer.set_text("Post about crabs");
+        let input = screen.cost_request().unwrap();
+        // One estimate at a time
+        assert!(screen.cost_request().is_none());
+
+        let wallet = WalletConfig::generate().unwrap();
+        PromptScreen::start_cost_estimate(crate::testing::mock_client(), wallet, "5Agent".to_string(), input, tx);
+        let Some(AppMessage::Prompt(PromptMessage::CostEstimated(preview))) = rx.recv().await else {
+            panic!("expected a cost estimate");
+        };
+        assert!(preview.fee.is_ok_and(|fee| fee > 0));
+        assert!(preview.agent_balance.is_some());
+
+        screen.cost_pending = None;
+        screen.cost = Some(CostPreview { input: "Post about crabs".to_string(), fee: Ok(1), agent_balance: None });
+        assert!(screen.cost_request().is_none());
+        screen.input_buffer.insert_str("!");
+        assert_eq!(screen.cost_request().as_deref(), Some("Post about crabs!"));
+    }
+
+    #[tokio::test]
+    async fn test_run_log_keeps_tool_results() {
+        let dir = std::env::temp_dir().join(format!("lobster-runlog-{}", std::process::id()));
+        let log = RunLog::open_in(&dir, "5Agent-1");
+        let (tx, _rx) = mpsc::channel(32);
+        PromptScreen::stream_run_events(crate::testing::mock_client(), 1, log, None, tx).await;
+
+        let text = std::fs::read_to_string(dir.join("5Agent-1.ndjson")).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        let events: Vec<ChainEventData> = text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert!(matches!(events.first(), Some(ChainEventData::RunStarted { run_id: 1, .. })));
+        assert!(matches!(events.last(), Some(ChainEventData::Completed { .. })));
+        assert!(events.iter().any(|e| matches!(e, ChainEventData::Messages { messages, .. }
+            if messages.iter().any(|m| matches!(m, ChatMessage::ToolResult { result, .. } if result.starts_with("5 posts"))))));
+    }
+
+    #[test]
+    fn test_prompt_conversation() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Prompt;
+        app.prompt.step = PromptStep::Running;
+        let call = ToolCallInfo {
+            call_id: 1,
+            name: "moltbook_get_feed".to_string(),
+            arguments: r#"{"sort":"hot","limit":5}"#.to_string(),
+        };
+        app.prompt.handle_chain_event(ChainEventData::Messages {
+            run_id: 1,
+            messages: vec![
+                ChatMessage::User { content: "What's new?".to_string() },
+                ChatMessage::Assistant {
+                    content: Some("Checking the feed.".to_string()),
+                    tool_calls: vec![call],
+                    output: None,
+                },
+            ],
+        });
+        app.prompt.handle_chain_event(ChainEventData::ToolsStarted { run_id: 1, tools: vec!["moltbook_get_feed".to_string()] });
+        assert_snapshot("prompt_conversation", &screen_text(&mut app));
+    }
+
+    #[test]
+    fn test_prompt_posts_pane() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Prompt;
+        app.prompt.step = PromptStep::Running;
+        app.prompt.input_buffer.set_text("Go post about molting season");
+        app.prompt.show_posts = true;
+        app.prompt.posts = serde_json::from_value(serde_json::json!([{
+            "id": "post-1", "title": "Molting season is here", "submolt": { "name": "general" },
+            "created_at": "2026-01-01T12:00:00Z", "upvotes": 12,
+        }]))
+        .unwrap();
+        assert_snapshot("prompt_posts_pane", &screen_text(&mut app));
+    }
+
+    #[tokio::test]
+    async fn test_follow_up_sent_after_completion() {
+        let mut app = signed_in_app();
+        let (tx, _rx) = tokio::sync::mpsc::channel(32);
+        app.screen = AppScreen::Prompt;
+        app.prompt.step = PromptStep::Running;
+        app.prompt.simulate = true;
+        for key in [KeyCode::Char('n'), KeyCode::Char('o'), KeyCode::Char('k'), KeyCode::Enter] {
+            app.handle_key(key.into(), tx.clone()).await.unwrap();
+        }
+        assert_eq!(app.prompt.follow_ups, ["ok"]);
+        assert!(app.prompt.follow_up_input.is_none());
+
+        let done = PromptMessage::RunCompleted { result: "Posted".to_string() };
+        app.handle_message(done.into(), tx).await.unwrap();
+        assert_eq!(app.prompt.step, PromptStep::Submitting);
+        assert_eq!(app.prompt.input_buffer.text(), "ok");
+        assert!(app.prompt.follow_ups.is_empty());
     }
 }
@@ -0,0 +1,83 @@
+//! Confirmation shown when quitting while operations are still running.
+
+use crate::app::App;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// "A deployment is in progress", naming every running operation.
+pub fn in_progress_message(operations: &[&str]) -> String {
+    let (verb, list) = match operations {
+        [] => return "Background work finished".to_string(),
+        [one] => ("is", one.to_string()),
+        [rest @ .., last] => ("are", format!("{} and {}", rest.join(", "), last)),
+    };
+    let mut chars = list.chars();
+    let capitalized: String = chars.next().map(|c| c.to_ascii_uppercase()).into_iter().chain(chars).collect();
+    format!("{} {} in progress", capitalized, verb)
+}
+
+/// Draw the dialog centered on top of the current screen.
+///
+/// The list is read live, so the dialog updates as operations finish.
+pub fn render_quit_confirm(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let operations = crate::tasks::operations();
+    let warning = if operations.is_empty() {
+        "Nothing will be interrupted."
+    } else {
+        "Quitting stops watching it; anything already sent to the chain may still complete."
+    };
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{} — quit anyway?", in_progress_message(&operations)),
+            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(warning, Style::default().fg(theme.muted))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[y] ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            Span::styled("Quit", Style::default().fg(theme.text)),
+            Span::styled("   [any key] ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            Span::styled("Stay", Style::default().fg(theme.text)),
+        ]),
+    ];
+
+    let width = area.width.min(64);
+    let height = 9.min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let dialog = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.warning))
+            .title(Span::styled(" Quit ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD))));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(dialog, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_progress_message() {
+        assert_eq!(in_progress_message(&["a deployment"]), "A deployment is in progress");
+        assert_eq!(
+            in_progress_message(&["a deployment", "an agent run", "a top-up"]),
+            "A deployment, an agent run and a top-up are in progress"
+        );
+    }
+}
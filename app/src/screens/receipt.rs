@@ -0,0 +1,69 @@
+//! Panel summarizing a submitted transaction: its block and decoded events.
+
+use crate::{app::App, client::SubmitResponse, events, text::truncate_width};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+/// Events listed before the rest are counted.
+const MAX_EVENT_LINES: usize = 8;
+
+/// Rows the panel needs for `receipt`, borders included.
+pub fn height(receipt: &SubmitResponse) -> u16 {
+    let events = receipt.events.len().min(MAX_EVENT_LINES + 1);
+    (events + 3) as u16
+}
+
+/// "5Grwva…tQY" for long addresses, so several fit on one line.
+fn short(value: &str) -> String {
+    if value.chars().count() > 16 && !value.contains(' ') {
+        let chars: Vec<char> = value.chars().collect();
+        let head: String = chars[..6].iter().collect();
+        let tail: String = chars[chars.len() - 4..].iter().collect();
+        format!("{}…{}", head, tail)
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn render_receipt(frame: &mut Frame, area: Rect, receipt: &SubmitResponse, app: &App) {
+    let theme = &app.theme;
+    let network = app.config.network();
+
+    let mut block_line = vec![
+        Span::styled(format!("Block #{} ", receipt.block_number), Style::default().fg(theme.secondary)),
+        Span::styled(truncate_width(&receipt.block_hash, 20), Style::default().fg(theme.muted)),
+    ];
+    if app.config.explorer_block_link(&receipt.block_hash).is_some() {
+        block_line.push(Span::styled("  [o] explorer", Style::default().fg(theme.muted)));
+    }
+    let mut lines = vec![Line::from(block_line)];
+
+    for event in receipt.events.iter().take(MAX_EVENT_LINES) {
+        let summary = events::describe(event, &network);
+        let mut spans = vec![Span::styled(summary.name, Style::default().fg(theme.accent))];
+        for (name, value) in summary.fields {
+            spans.push(Span::styled(format!("  {} ", name), Style::default().fg(theme.muted)));
+            spans.push(Span::styled(short(&value), Style::default().fg(theme.text)));
+        }
+        lines.push(Line::from(spans));
+    }
+    if receipt.events.len() > MAX_EVENT_LINES {
+        lines.push(Line::from(Span::styled(
+            format!("… {} more events", receipt.events.len() - MAX_EVENT_LINES),
+            Style::default().fg(theme.muted),
+        )));
+    }
+
+    let panel = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(" Transaction ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD))),
+    );
+    frame.render_widget(panel, area);
+}
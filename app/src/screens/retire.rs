@@ -0,0 +1,188 @@
+//! Retire agent modal on the view screen - typed-name confirmation and teardown.
+
+use crate::{
+    app::AppMessage,
+    client::ApiClient,
+    screens::{input::TextInput, view::ViewMessage},
+    theme::Theme,
+    wallet::WalletConfig,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+pub struct RetireForm {
+    /// Name the user must type to confirm
+    pub agent_name: String,
+    pub confirm_input: TextInput,
+    /// Sweep the agent's remaining balance back to the owner wallet
+    pub sweep: bool,
+    pub running: bool,
+    /// Current teardown step while running
+    pub progress: Option<String>,
+    pub error: Option<String>,
+}
+
+impl RetireForm {
+    pub fn new(agent_name: &str) -> Self {
+        Self {
+            agent_name: agent_name.to_string(),
+            confirm_input: TextInput::new(),
+            sweep: true,
+            running: false,
+            progress: None,
+            error: None,
+        }
+    }
+
+    fn confirmed(&self) -> bool {
+        self.confirm_input.text().trim() == self.agent_name
+    }
+
+    /// Handle a key; returns false once the form should close.
+    pub fn handle_key(
+        &mut self,
+        event: KeyEvent,
+        client: &ApiClient,
+        agent_address: &str,
+        active: bool,
+        wallet: Option<&WalletConfig>,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> bool {
+        let key = event.code;
+        if self.running {
+            return true;
+        }
+
+        self.error = None;
+        match key {
+            KeyCode::Esc => return false,
+            KeyCode::Tab => self.sweep = !self.sweep,
+            _ if self.confirm_input.handle_key(event) => {}
+            KeyCode::Enter if !self.confirmed() => {
+                self.error = Some("Type the agent name exactly to confirm".to_string());
+            }
+            KeyCode::Enter => match wallet {
+                Some(wallet) => {
+                    self.running = true;
+                    self.progress = Some("Starting...".to_string());
+                    spawn_retire(client.clone(), agent_address.to_string(), active, self.sweep, wallet.clone(), tx);
+                }
+                None => self.error = Some("No wallet available to sign with".to_string()),
+            },
+            _ => {}
+        }
+        true
+    }
+
+    pub fn handle_progress(&mut self, step: String) {
+        self.progress = Some(step);
+    }
+
+    pub fn handle_failed(&mut self, error: String) {
+        // Pausing and record removal are idempotent, so a retry can start from the top
+        self.running = false;
+        self.progress = None;
+        self.error = Some(error);
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, spinner: &str, theme: &Theme) {
+        let confirm_color = if self.confirmed() { theme.success } else { theme.text };
+        let check = if self.sweep { "[x]" } else { "[ ]" };
+        let mut confirm = vec![
+            Span::styled("  Type ", Style::default().fg(theme.secondary)),
+            Span::styled(self.agent_name.as_str(), Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            Span::styled(" to confirm: ", Style::default().fg(theme.secondary)),
+        ];
+        let prompt_width: usize = confirm.iter().map(|s| s.width()).sum();
+        let width = (area.width as usize).saturating_sub(prompt_width + 2);
+        confirm.extend(self.confirm_input.line(!self.running, width, Style::default().fg(confirm_color)).spans);
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "This retires the agent for good:",
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled("  • pauses it on-chain so no more runs are scheduled", Style::default().fg(theme.secondary))),
+            Line::from(Span::styled("  • removes it from your account on the server", Style::default().fg(theme.secondary))),
+            Line::from(Span::styled("  • forgets it in this app's local config", Style::default().fg(theme.secondary))),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(format!("  {} ", check), Style::default().fg(theme.accent)),
+                Span::styled("Sweep remaining balance back to my wallet", Style::default().fg(theme.text)),
+                Span::styled("  [Tab] toggle", Style::default().fg(theme.muted)),
+            ]),
+            Line::from(""),
+            Line::from(confirm),
+        ];
+        if let Some(progress) = &self.progress {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(format!("  {} {}", spinner, progress), Style::default().fg(theme.warning))));
+        }
+
+        let modal = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Left)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error))
+                .title(Span::styled(" Retire agent ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD))));
+        frame.render_widget(modal, area);
+    }
+}
+
+/// Run the teardown steps in order, reporting each one.
+fn spawn_retire(
+    client: ApiClient,
+    agent_address: String,
+    active: bool,
+    sweep: bool,
+    wallet: WalletConfig,
+    tx: mpsc::Sender<AppMessage>,
+) {
+    crate::tasks::spawn_operation("an agent retirement", async move {
+        let progress = |step: &str| {
+            let tx = tx.clone();
+            let step = step.to_string();
+            async move {
+                let _ = tx.send(ViewMessage::RetireProgress(step).into()).await;
+            }
+        };
+
+        let result: anyhow::Result<()> = async {
+            let keypair = wallet.keypair()?;
+
+            if active {
+                progress("Pausing agent on-chain...").await;
+                let build = client.build_set_active(&agent_address, false, &wallet.public_key).await?;
+                crate::extrinsic::sign_and_submit(&client, &build, &keypair).await?;
+            }
+
+            if sweep {
+                progress("Sweeping balance to your wallet...").await;
+                let build = client.build_withdraw(&agent_address, &wallet.public_key).await?;
+                crate::extrinsic::sign_and_submit(&client, &build, &keypair).await?;
+            }
+
+            progress("Removing server record...").await;
+            let agents = client.list_agents().await?;
+            if let Some(agent) = agents.iter().find(|a| a.chain_address.as_deref() == Some(agent_address.as_str())) {
+                client.delete_agent(&agent.id).await?;
+            }
+            Ok(())
+        }
+        .await;
+
+        let msg = match result {
+            Ok(()) => ViewMessage::AgentRetired,
+            Err(e) => ViewMessage::RetireFailed(format!("{:#}", e)),
+        };
+        let _ = tx.send(msg.into()).await;
+    });
+}
@@ -0,0 +1,350 @@
+//! Run comparison screen - two recorded runs side by side: the tool calls
+//! each made and their final outputs, aligned so changes stand out.
+
+use crate::{
+    app::{App, ScreenAction},
+    runlog::{self, diff, DiffRow, RecordedRun},
+    screens::{input::SelectList, Screen},
+    text::truncate_width,
+    theme::Theme,
+};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use std::path::PathBuf;
+
+pub struct RunsScreen {
+    /// Recorded run logs, newest first
+    pub runs: Vec<PathBuf>,
+    pub selected: SelectList,
+    /// Indexes into `runs` picked for comparison, left one first
+    pub picked: Vec<usize>,
+    /// Left and right run being compared
+    pub compared: Option<(RecordedRun, RecordedRun)>,
+    pub scroll: u16,
+    pub error: Option<String>,
+}
+
+impl RunsScreen {
+    pub fn new() -> Self {
+        Self {
+            runs: Vec::new(),
+            selected: SelectList::new(),
+            picked: Vec::new(),
+            compared: None,
+            scroll: 0,
+            error: None,
+        }
+    }
+
+    /// Start over with the logs of `agent_address` and of dry runs.
+    pub fn reset(&mut self, agent_address: Option<&str>) {
+        *self = Self::new();
+        self.runs = runlog::recorded_runs(agent_address);
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> Result<ScreenAction> {
+        if self.compared.is_some() {
+            match key {
+                KeyCode::Esc => {
+                    self.compared = None;
+                    self.scroll = 0;
+                }
+                KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+                KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+                _ => {}
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        match key {
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            key if self.selected.handle_key(key, self.runs.len()) => {}
+            KeyCode::Char(' ') if !self.runs.is_empty() => {
+                if let Some(i) = self.picked.iter().position(|&p| p == self.selected.selected()) {
+                    self.picked.remove(i);
+                } else {
+                    // A third pick replaces the older one
+                    if self.picked.len() == 2 {
+                        self.picked.remove(0);
+                    }
+                    self.picked.push(self.selected.selected());
+                }
+            }
+            KeyCode::Enter => self.compare(),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    /// Load the two picked runs.
+    fn compare(&mut self) {
+        let [left, right] = self.picked[..] else {
+            self.error = Some("Pick two runs with Space first".to_string());
+            return;
+        };
+        let load = |i: usize| RecordedRun::load(&self.runs[i]).map_err(|e| format!("Cannot read {}: {}", self.runs[i].display(), e));
+        match load(left).and_then(|l| Ok((l, load(right)?))) {
+            Ok(pair) => {
+                self.error = None;
+                self.scroll = 0;
+                self.compared = Some(pair);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+/// `text` cut into lines of at most `width` characters.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars.chunks(width.max(1)).map(|c| c.iter().collect()).collect()
+}
+
+/// Both columns of the comparison, padded to the same number of lines.
+#[derive(Default)]
+struct Columns {
+    left: Vec<Line<'static>>,
+    right: Vec<Line<'static>>,
+    width: usize,
+}
+
+impl Columns {
+    /// One row; either side may be missing. Wrapped text keeps the rows aligned.
+    fn row(&mut self, left: Option<(String, Style)>, right: Option<(String, Style)>) {
+        let wrapped = |side: &Option<(String, Style)>| match side {
+            Some((text, style)) => wrap(text, self.width).into_iter().map(|l| Line::styled(l, *style)).collect(),
+            None => Vec::new(),
+        };
+        let (mut left, mut right): (Vec<Line>, Vec<Line>) = (wrapped(&left), wrapped(&right));
+        let height = left.len().max(right.len());
+        left.resize(height, Line::from(""));
+        right.resize(height, Line::from(""));
+        self.left.extend(left);
+        self.right.extend(right);
+    }
+
+    fn heading(&mut self, text: &str, theme: &Theme) {
+        let style = Style::default().fg(theme.accent).add_modifier(Modifier::BOLD);
+        self.row(Some((text.to_string(), style)), Some((text.to_string(), style)));
+    }
+}
+
+/// Final output of a run, or why it has none.
+fn outcome(run: &RecordedRun) -> Vec<String> {
+    match (&run.output, &run.failure) {
+        (Some(output), _) => output.lines().map(str::to_string).collect(),
+        (None, Some(reason)) => vec![format!("Failed: {}", reason)],
+        (None, None) => vec!["(did not finish)".to_string()],
+    }
+}
+
+fn comparison(left: &RecordedRun, right: &RecordedRun, width: usize, theme: &Theme) -> Columns {
+    let mut columns = Columns { width, ..Columns::default() };
+    let same = Style::default().fg(theme.text);
+    let changed = Style::default().fg(theme.warning);
+    let removed = Style::default().fg(theme.error);
+    let added = Style::default().fg(theme.success);
+
+    columns.heading("Tool calls", theme);
+    let call = |prefix: &str, c: &crate::client::ToolCallInfo| format!("{}{}({})", prefix, c.name, c.arguments);
+    if left.tool_calls.is_empty() && right.tool_calls.is_empty() {
+        let none = Some(("(none)".to_string(), Style::default().fg(theme.muted)));
+        columns.row(none.clone(), none);
+    }
+    for row in diff(&left.tool_calls, &right.tool_calls, |c| c.name.clone()) {
+        match row {
+            DiffRow::Both(l, r) if l.arguments == r.arguments => {
+                columns.row(Some((call("  ", l), same)), Some((call("  ", r), same)));
+            }
+            DiffRow::Both(l, r) => columns.row(Some((call("~ ", l), changed)), Some((call("~ ", r), changed))),
+            DiffRow::Left(l) => columns.row(Some((call("- ", l), removed)), None),
+            DiffRow::Right(r) => columns.row(None, Some((call("+ ", r), added))),
+        }
+    }
+
+    columns.row(None, None);
+    columns.heading("Final output", theme);
+    let (left_out, right_out) = (outcome(left), outcome(right));
+    for row in diff(&left_out, &right_out, |line| line.clone()) {
+        match row {
+            DiffRow::Both(l, r) => columns.row(Some((format!("  {}", l), same)), Some((format!("  {}", r), same))),
+            DiffRow::Left(l) => columns.row(Some((format!("- {}", l), removed)), None),
+            DiffRow::Right(r) => columns.row(None, Some((format!("+ {}", r), added))),
+        }
+    }
+    columns
+}
+
+impl Screen for RunsScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(6),    // Runs or comparison
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let subtitle = match &self.compared {
+            Some((left, right)) => format!("{} vs {}", left.name, right.name),
+            None => "pick two recorded runs".to_string(),
+        };
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" COMPARE RUNS ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(subtitle, Style::default().fg(theme.accent)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        if let Some((left, right)) = &self.compared {
+            let halves = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(chunks[1]);
+            let width = halves[0].width.saturating_sub(2) as usize;
+            let columns = comparison(left, right, width, theme);
+            let scroll = self.scroll.min(columns.left.len().saturating_sub(1) as u16);
+            for (half, run, lines) in [(halves[0], left, columns.left), (halves[1], right, columns.right)] {
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.muted))
+                    .title(Span::styled(
+                        format!(" {} ", truncate_width(&run.name, width.saturating_sub(2))),
+                        Style::default().fg(theme.text),
+                    ));
+                frame.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), half);
+            }
+        } else {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(format!(" Recorded runs ({}) ", self.runs.len()), Style::default().fg(theme.text)));
+            if self.runs.is_empty() {
+                let empty = Paragraph::new(vec![
+                    Line::from(""),
+                    Line::from(Span::styled("No recorded runs yet", Style::default().fg(theme.muted))),
+                    Line::from(Span::styled(
+                        "Set run_logs to true in config.toml to record prompt runs and dry runs",
+                        Style::default().fg(theme.muted),
+                    )),
+                ])
+                .alignment(Alignment::Center)
+                .block(block);
+                frame.render_widget(empty, chunks[1]);
+            } else {
+                let items: Vec<ListItem> = self
+                    .runs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, path)| {
+                        let mark = match self.picked.iter().position(|&p| p == i) {
+                            Some(0) => Span::styled(" [A] ", Style::default().fg(theme.info)),
+                            Some(_) => Span::styled(" [B] ", Style::default().fg(theme.info)),
+                            None => Span::styled(" [ ] ", Style::default().fg(theme.muted)),
+                        };
+                        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                        ListItem::new(Line::from(vec![mark, Span::styled(name, Style::default().fg(theme.text))]))
+                    })
+                    .collect();
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol("▌")
+                    .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+                let mut state = self.selected.state();
+                frame.render_stateful_widget(list, chunks[1], &mut state);
+            }
+        }
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else if self.compared.is_some() {
+            Line::from(vec![
+                Span::styled("- ", Style::default().fg(theme.error)),
+                Span::styled("only in A  ", Style::default().fg(theme.muted)),
+                Span::styled("+ ", Style::default().fg(theme.success)),
+                Span::styled("only in B  ", Style::default().fg(theme.muted)),
+                Span::styled("~ ", Style::default().fg(theme.warning)),
+                Span::styled("other arguments", Style::default().fg(theme.muted)),
+                Span::styled("  [j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Scroll", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Runs", Style::default().fg(theme.muted)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Navigate", Style::default().fg(theme.muted)),
+                Span::styled("  [Space] ", Style::default().fg(theme.muted)),
+                Span::styled("Pick", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Compare", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back", Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::AppScreen;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app};
+
+    #[test]
+    fn test_runs_diff() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Runs;
+        let run = |name: &str, calls: serde_json::Value, output: &str| crate::runlog::RecordedRun {
+            name: name.to_string(),
+            tool_calls: serde_json::from_value(calls).unwrap(),
+            output: Some(output.to_string()),
+            failure: None,
+            messages: Vec::new(),
+        };
+        app.runs.compared = Some((
+            run(
+                "dry-run-1700000000",
+                serde_json::json!([
+                    { "call_id": 1, "name": "feed", "arguments": "{}" },
+                    { "call_id": 2, "name": "post", "arguments": "{\"title\":\"hi\"}" },
+                ]),
+                "Read the feed\nPosted hi",
+            ),
+            run(
+                "dry-run-1700000100",
+                serde_json::json!([
+                    { "call_id": 1, "name": "feed", "arguments": "{}" },
+                    { "call_id": 2, "name": "search", "arguments": "{\"q\":\"crabs\"}" },
+                    { "call_id": 3, "name": "post", "arguments": "{\"title\":\"crabs\"}" },
+                ]),
+                "Read the feed\nPosted crabs",
+            ),
+        ));
+        assert_snapshot("runs_diff", &screen_text(&mut app));
+    }
+}
@@ -0,0 +1,329 @@
+//! Run archive search - full-text search across recorded run logs, with
+//! every match listed in context and a jump to it in the run's transcript.
+
+use crate::{
+    app::{App, ScreenAction},
+    runlog::{self, RecordedRun, SearchHit, TranscriptLine},
+    screens::{input::{SelectList, TextInput}, Screen},
+    text::{tail_width, truncate_width},
+    theme::Theme,
+};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+/// Characters of context shown before a match in the results list.
+const CONTEXT_CHARS: usize = 30;
+
+pub struct SearchScreen {
+    pub query: TextInput,
+    /// Typing goes to the query
+    pub editing: bool,
+    /// Every recorded run, newest first, loaded when the screen opens
+    pub runs: Vec<RecordedRun>,
+    /// Transcript of each of `runs`
+    pub transcripts: Vec<Vec<TranscriptLine>>,
+    /// Matches of the last search
+    pub hits: Vec<SearchHit>,
+    /// Query `hits` are for
+    pub searched: String,
+    pub selected: SelectList,
+    /// Showing the selected match in its run's transcript
+    pub opened: bool,
+    pub error: Option<String>,
+}
+
+impl SearchScreen {
+    pub fn new() -> Self {
+        Self {
+            query: TextInput::new(),
+            editing: true,
+            runs: Vec::new(),
+            transcripts: Vec::new(),
+            hits: Vec::new(),
+            searched: String::new(),
+            selected: SelectList::new(),
+            opened: false,
+            error: None,
+        }
+    }
+
+    /// Start over with every run log on disk; unreadable ones are skipped
+    /// and counted.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+        let paths = runlog::all_recorded_runs();
+        let total = paths.len();
+        self.runs = paths.iter().filter_map(|p| RecordedRun::load(p).ok()).collect();
+        self.transcripts = self.runs.iter().map(RecordedRun::transcript).collect();
+        if self.runs.len() < total {
+            self.error = Some(format!("{} run logs could not be read", total - self.runs.len()));
+        }
+    }
+
+    fn search(&mut self) {
+        self.hits = runlog::search(&self.transcripts, self.query.text());
+        self.searched = self.query.text().to_string();
+        self.selected = SelectList::new();
+        self.editing = self.hits.is_empty();
+    }
+
+    fn next(&mut self) {
+        if self.selected.at_end(self.hits.len()) {
+            self.selected = SelectList::new();
+        } else {
+            self.selected.next(self.hits.len());
+        }
+    }
+
+    fn previous(&mut self) {
+        if self.selected.selected() == 0 {
+            self.selected.select(self.hits.len().saturating_sub(1), self.hits.len());
+        } else {
+            self.selected.prev();
+        }
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> Result<ScreenAction> {
+        let key = event.code;
+        if self.editing {
+            match key {
+                _ if self.query.handle_key(event) => {}
+                KeyCode::Enter => self.search(),
+                KeyCode::Esc if self.hits.is_empty() => return Ok(ScreenAction::Pop),
+                KeyCode::Esc => self.editing = false,
+                _ => {}
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        match key {
+            KeyCode::Esc if self.opened => self.opened = false,
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            KeyCode::Char('/') => {
+                self.opened = false;
+                self.editing = true;
+            }
+            // Stepping through matches wraps around
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('n') => self.next(),
+            KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('N') => self.previous(),
+            key if self.selected.handle_key(key, self.hits.len()) => {}
+            KeyCode::Enter if !self.hits.is_empty() => self.opened = true,
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+}
+
+/// `text` with `range` picked out.
+fn highlighted(text: &str, range: &std::ops::Range<usize>, style: Style, theme: &Theme) -> Vec<Span<'static>> {
+    let mark = Style::default().fg(theme.accent).add_modifier(Modifier::BOLD | Modifier::REVERSED);
+    vec![
+        Span::styled(text[..range.start].to_string(), style),
+        Span::styled(text[range.clone()].to_string(), mark),
+        Span::styled(text[range.end..].to_string(), style),
+    ]
+}
+
+/// A result row: run, role and the match with some text before it.
+fn hit_item(run: &RecordedRun, line: &TranscriptLine, hit: &SearchHit, theme: &Theme) -> ListItem<'static> {
+    let before = tail_width(&line.text[..hit.range.start], CONTEXT_CHARS);
+    let context = &line.text[hit.range.start - before.len()..];
+    let range = before.len()..before.len() + hit.range.len();
+    let mut spans = vec![
+        Span::styled(format!(" {} ", truncate_width(&run.name, 24)), Style::default().fg(theme.muted)),
+        Span::styled(format!("{:>6}  ", line.role), Style::default().fg(theme.info)),
+    ];
+    if before.len() < hit.range.start {
+        spans.push(Span::styled("…", Style::default().fg(theme.muted)));
+    }
+    spans.extend(highlighted(context, &range, Style::default().fg(theme.text), theme));
+    ListItem::new(Line::from(spans))
+}
+
+/// The whole transcript of the selected match's run, every match of the
+/// search marked and the selected one's line picked out.
+fn transcript_lines(screen: &SearchScreen, run: usize, theme: &Theme) -> (Vec<Line<'static>>, usize) {
+    let selected = &screen.hits[screen.selected.selected()];
+    let lines = screen.transcripts[run]
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let current = i == selected.line;
+            let style = if current {
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let marker = if current { "▌" } else { " " };
+            let mut spans = vec![
+                Span::styled(marker, Style::default().fg(theme.accent)),
+                Span::styled(format!("{:>6}  ", line.role), Style::default().fg(theme.info)),
+            ];
+            // Matches on one line come in order and don't overlap
+            let mut rest = 0;
+            for hit in screen.hits.iter().filter(|h| h.run == run && h.line == i) {
+                spans.push(Span::styled(line.text[rest..hit.range.start].to_string(), style));
+                let mark = if current && hit.range == selected.range {
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else {
+                    Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)
+                };
+                spans.push(Span::styled(line.text[hit.range.clone()].to_string(), mark));
+                rest = hit.range.end;
+            }
+            spans.push(Span::styled(line.text[rest..].to_string(), style));
+            Line::from(spans)
+        })
+        .collect();
+    (lines, selected.line)
+}
+
+impl Screen for SearchScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Length(3), // Query
+                Constraint::Min(6),    // Results or transcript
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let subtitle = format!("{} recorded runs", self.runs.len());
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" SEARCH RUNS ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(subtitle, Style::default().fg(theme.accent)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        let width = chunks[1].width.saturating_sub(2) as usize;
+        let query = Paragraph::new(self.query.line(self.editing, width, Style::default().fg(theme.info)))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if self.editing { theme.accent } else { theme.muted }))
+                .title(Span::styled(" Search ", Style::default().fg(theme.text))));
+        frame.render_widget(query, chunks[1]);
+
+        let block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.muted));
+        if self.runs.is_empty() || self.hits.is_empty() {
+            let message = if self.runs.is_empty() {
+                "No recorded runs yet - set run_logs to true in config.toml to record them"
+            } else if self.searched.is_empty() {
+                "Type what to look for in prompts, replies, tool calls and outputs, then Enter"
+            } else {
+                "No matches"
+            };
+            let empty = Paragraph::new(vec![Line::from(""), Line::from(Span::styled(message, Style::default().fg(theme.muted)))])
+                .alignment(Alignment::Center)
+                .block(block);
+            frame.render_widget(empty, chunks[2]);
+        } else if self.opened {
+            let hit = &self.hits[self.selected.selected()];
+            let run = &self.runs[hit.run];
+            let (lines, current) = transcript_lines(self, hit.run, theme);
+            // Keep the match a few lines below the top
+            let scroll = current.saturating_sub(3) as u16;
+            let block = block.title(Span::styled(
+                format!(" {} - match {} of {} ", run.name, self.selected.selected() + 1, self.hits.len()),
+                Style::default().fg(theme.text),
+            ));
+            frame.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), chunks[2]);
+        } else {
+            let items: Vec<ListItem> = self
+                .hits
+                .iter()
+                .map(|hit| hit_item(&self.runs[hit.run], &self.transcripts[hit.run][hit.line], hit, theme))
+                .collect();
+            let block = block.title(Span::styled(
+                format!(" {} matches for \"{}\" ", self.hits.len(), truncate_width(&self.searched, 30)),
+                Style::default().fg(theme.text),
+            ));
+            let list = List::new(items)
+                .block(block)
+                .highlight_symbol("▌")
+                .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+            let mut state = self.selected.state();
+            frame.render_stateful_widget(list, chunks[2], &mut state);
+        }
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else if self.editing {
+            Line::from(vec![
+                Span::styled("[Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Search", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled(if self.hits.is_empty() { "Back" } else { "Results" }, Style::default().fg(theme.muted)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[n/N] ", Style::default().fg(theme.muted)),
+                Span::styled("Next / previous match", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Jump to it", Style::default().fg(theme.muted)),
+                Span::styled("  [/] ", Style::default().fg(theme.muted)),
+                Span::styled("New search", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled(if self.opened { "Results" } else { "Back" }, Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppScreen;
+    use crate::client::ChatMessage;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app};
+
+    #[test]
+    fn test_search_runs() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::Search;
+        let run = |name: &str, prompt: &str, reply: &str| crate::runlog::RecordedRun {
+            name: name.to_string(),
+            output: Some(reply.to_string()),
+            messages: vec![
+                ChatMessage::User { content: prompt.to_string() },
+                ChatMessage::Assistant { content: Some(reply.to_string()), tool_calls: Vec::new(), output: None },
+            ],
+            ..Default::default()
+        };
+        app.search.runs = vec![
+            run("5Agent-2", "Any news?", "Nothing yet. I will post about molting season tomorrow."),
+            run("5Agent-1", "Plan the week", "Monday: reply to comments. Friday: a post about Molting."),
+        ];
+        app.search.transcripts = app.search.runs.iter().map(crate::runlog::RecordedRun::transcript).collect();
+        for c in "molting".chars() {
+            app.search.handle_key(KeyCode::Char(c).into()).unwrap();
+        }
+        app.search.handle_key(KeyCode::Enter.into()).unwrap();
+        assert_eq!(app.search.hits.len(), 4);
+        assert_snapshot("search_results", &screen_text(&mut app));
+
+        app.search.handle_key(KeyCode::Char('N').into()).unwrap();
+        app.search.handle_key(KeyCode::Enter.into()).unwrap();
+        assert_snapshot("search_opened", &screen_text(&mut app));
+    }
+}
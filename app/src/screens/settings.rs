@@ -0,0 +1,617 @@
+//! Settings screen - edit and persist config.toml values from the TUI.
+
+use crate::{
+    app::{App, ScreenAction},
+    config::{
+        AppConfig, GlyphMode, KeyPreset, NetworkKind, NetworkProfile, ThemeName, DEFAULT_BALANCE_REFRESH_SECS, DEFAULT_MIN_BALANCE,
+        DEFAULT_PRICE_REFRESH_SECS, DEFAULT_SESSION_CHECK_SECS, DEFAULT_VIEW_REFRESH_SECS, DEFAULT_CLAIM_POLL_SECS,
+        DEFAULT_STREAM_STALL_SECS, MIN_POLL_SECS, MIN_STREAM_STALL_SECS,
+    },
+    funds::parse_tokens,
+    screens::{focus::FocusRing, input::TextInput, network_badge, Screen},
+    text::tail_width,
+};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingField {
+    Network,
+    ServerUrl,
+    SessionCheck,
+    BalanceRefresh,
+    ViewRefresh,
+    ClaimPoll,
+    StreamStall,
+    PriceFeed,
+    PriceRefresh,
+    MinBalance,
+    AgentDir,
+    Theme,
+    Glyphs,
+    QuitKey,
+    KeyPreset,
+    CheckUpdates,
+    DesktopNotifications,
+    IdleLock,
+    LockPin,
+    Save,
+}
+
+impl SettingField {
+    const ALL: [SettingField; 20] = [
+        SettingField::Network,
+        SettingField::ServerUrl,
+        SettingField::SessionCheck,
+        SettingField::BalanceRefresh,
+        SettingField::ViewRefresh,
+        SettingField::ClaimPoll,
+        SettingField::StreamStall,
+        SettingField::PriceFeed,
+        SettingField::PriceRefresh,
+        SettingField::MinBalance,
+        SettingField::AgentDir,
+        SettingField::Theme,
+        SettingField::Glyphs,
+        SettingField::QuitKey,
+        SettingField::KeyPreset,
+        SettingField::CheckUpdates,
+        SettingField::DesktopNotifications,
+        SettingField::IdleLock,
+        SettingField::LockPin,
+        SettingField::Save,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            SettingField::Network => "Network",
+            SettingField::ServerUrl => "Server URL",
+            SettingField::SessionCheck => "Session check (s)",
+            SettingField::BalanceRefresh => "Balance refresh (s)",
+            SettingField::ViewRefresh => "View refresh (s)",
+            SettingField::ClaimPoll => "Claim check (s)",
+            SettingField::StreamStall => "Stream stall (s)",
+            SettingField::PriceFeed => "USD price feed",
+            SettingField::PriceRefresh => "Price refresh (s)",
+            SettingField::MinBalance => "Min balance",
+            SettingField::AgentDir => "Custom agent dir",
+            SettingField::Theme => "Theme",
+            SettingField::Glyphs => "Glyphs",
+            SettingField::QuitKey => "Quit key",
+            SettingField::KeyPreset => "Key preset",
+            SettingField::CheckUpdates => "Check for updates",
+            SettingField::DesktopNotifications => "Desktop alerts",
+            SettingField::IdleLock => "Idle lock (min)",
+            SettingField::LockPin => "Lock PIN",
+            SettingField::Save => "",
+        }
+    }
+
+    fn hint(self) -> &'static str {
+        match self {
+            SettingField::Network => "Switch between the profiles in config.toml's networks list",
+            SettingField::ServerUrl => "Gateway URL; --server still overrides it for one run",
+            SettingField::SessionCheck => "How often the login session is re-validated",
+            SettingField::BalanceRefresh => "Chain head poll, and wallet balance poll when the gateway doesn't stream it",
+            SettingField::ViewRefresh => "Agent view auto-refresh; 0 turns it off",
+            SettingField::ClaimPoll => "How often the create wizard checks the Twitter verification by itself; 0 turns it off",
+            SettingField::StreamStall => "Silence on a run's event stream before it counts as stalled; 0 waits forever",
+            SettingField::PriceFeed => "URL answering JSON with a usd price, shown next to balances; empty turns it off",
+            SettingField::PriceRefresh => "How often the USD price is fetched",
+            SettingField::MinBalance => "Tokens kept for fees; below it a warning shows and deploys and prompts are refused",
+            SettingField::AgentDir => "Directory with moltbook_agent.ship etc.; empty uses the embedded agent",
+            SettingField::Theme => "Color palette: dark, light, no-color, deuteranopia, protanopia or high-contrast",
+            SettingField::Glyphs => "Icons and borders: auto uses ASCII unless the locale is UTF-8",
+            SettingField::QuitKey => "Key that quits from the home screen",
+            SettingField::KeyPreset => "Navigation keys: vim adds hjkl, gg/G, / to search and : for the command palette",
+            SettingField::CheckUpdates => "Ask GitHub for a newer release on startup; install with lobster self-update",
+            SettingField::DesktopNotifications => "Finished runs and low balances, while the terminal is in the background",
+            SettingField::IdleLock => "Minutes without input before the screen blanks and asks for the PIN; 0 turns it off, and so does having no PIN",
+            SettingField::LockPin => "Unlocks the idle lock, as does the encrypt_secrets passphrase; empty keeps the saved PIN",
+            SettingField::Save => "Validate and write config.toml",
+        }
+    }
+}
+
+/// Settings screen state: a draft of the editable values.
+pub struct SettingsScreen {
+    pub focus: FocusRing<SettingField>,
+    /// Text being typed into the selected field
+    pub editing: Option<TextInput>,
+    /// Next key typed becomes the quit key
+    pub capturing_key: bool,
+    /// Profiles the network field cycles through
+    pub networks: Vec<NetworkProfile>,
+    pub server_url: String,
+    pub session_check: String,
+    pub balance_refresh: String,
+    pub view_refresh: String,
+    pub claim_poll: String,
+    pub stream_stall: String,
+    pub price_feed: String,
+    pub price_refresh: String,
+    pub min_balance: String,
+    pub agent_dir: String,
+    pub theme: ThemeName,
+    pub glyphs: GlyphMode,
+    pub quit_key: char,
+    pub key_preset: KeyPreset,
+    pub check_updates: bool,
+    pub desktop_notifications: bool,
+    pub idle_lock: String,
+    /// New lock PIN; empty keeps the saved one
+    pub lock_pin: String,
+    /// config.toml has a lock PIN
+    pub has_lock_pin: bool,
+    pub error: Option<String>,
+    pub status: Option<String>,
+}
+
+impl SettingsScreen {
+    pub fn new() -> Self {
+        Self::from_config(&AppConfig::default())
+    }
+
+    /// Draft pre-filled from the current config.
+    pub fn from_config(config: &AppConfig) -> Self {
+        let secs = |value: Option<u64>, default: u64| value.unwrap_or(default).to_string();
+        Self {
+            focus: FocusRing::new(SettingField::ALL),
+            editing: None,
+            capturing_key: false,
+            networks: config.network_profiles(),
+            server_url: config.server_url.clone(),
+            session_check: secs(config.session_check_secs, DEFAULT_SESSION_CHECK_SECS),
+            balance_refresh: secs(config.balance_refresh_secs, DEFAULT_BALANCE_REFRESH_SECS),
+            view_refresh: secs(config.view_refresh_secs, DEFAULT_VIEW_REFRESH_SECS),
+            claim_poll: secs(config.claim_poll_secs, DEFAULT_CLAIM_POLL_SECS),
+            stream_stall: secs(config.stream_stall_secs, DEFAULT_STREAM_STALL_SECS),
+            price_feed: config.price_feed_url.clone().unwrap_or_default(),
+            price_refresh: secs(config.price_refresh_secs, DEFAULT_PRICE_REFRESH_SECS),
+            min_balance: config.min_balance.clone().unwrap_or_else(|| DEFAULT_MIN_BALANCE.to_string()),
+            agent_dir: config.custom_agent_dir.clone().unwrap_or_default(),
+            theme: config.theme,
+            glyphs: config.glyphs,
+            quit_key: config.keybindings.quit,
+            key_preset: config.keybindings.preset,
+            check_updates: config.check_updates,
+            desktop_notifications: config.desktop_notifications,
+            idle_lock: config.idle_lock_minutes.unwrap_or(0).to_string(),
+            lock_pin: String::new(),
+            has_lock_pin: config.lock_pin.is_some(),
+            error: None,
+            status: None,
+        }
+    }
+
+    pub fn reset(&mut self, config: &AppConfig) {
+        *self = Self::from_config(config);
+    }
+
+    fn field(&self) -> SettingField {
+        self.focus.current()
+    }
+
+    fn text_mut(&mut self, field: SettingField) -> Option<&mut String> {
+        match field {
+            SettingField::ServerUrl => Some(&mut self.server_url),
+            SettingField::SessionCheck => Some(&mut self.session_check),
+            SettingField::BalanceRefresh => Some(&mut self.balance_refresh),
+            SettingField::ViewRefresh => Some(&mut self.view_refresh),
+            SettingField::ClaimPoll => Some(&mut self.claim_poll),
+            SettingField::StreamStall => Some(&mut self.stream_stall),
+            SettingField::PriceFeed => Some(&mut self.price_feed),
+            SettingField::PriceRefresh => Some(&mut self.price_refresh),
+            SettingField::MinBalance => Some(&mut self.min_balance),
+            SettingField::AgentDir => Some(&mut self.agent_dir),
+            SettingField::IdleLock => Some(&mut self.idle_lock),
+            SettingField::LockPin => Some(&mut self.lock_pin),
+            SettingField::Network
+            | SettingField::Theme
+            | SettingField::Glyphs
+            | SettingField::QuitKey
+            | SettingField::KeyPreset
+            | SettingField::CheckUpdates
+            | SettingField::DesktopNotifications
+            | SettingField::Save => None,
+        }
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent, config: &mut AppConfig) -> Result<ScreenAction> {
+        let key = event.code;
+        if self.capturing_key {
+            self.capturing_key = false;
+            if let KeyCode::Char(c) = key {
+                self.quit_key = c;
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if let Some(buffer) = &mut self.editing {
+            match key {
+                _ if buffer.handle_key(event) => {}
+                KeyCode::Enter => {
+                    let value = buffer.text().trim().to_string();
+                    self.editing = None;
+                    if let Some(field) = self.text_mut(self.field()) {
+                        *field = value;
+                    }
+                }
+                KeyCode::Esc => self.editing = None,
+                _ => {}
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        self.error = None;
+        match key {
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.focus.handle_key(key);
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.focus.next(),
+            KeyCode::Up | KeyCode::Char('k') => self.focus.prev(),
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.field() == SettingField::Theme => {
+                self.cycle_theme(key == KeyCode::Left);
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.field() == SettingField::Glyphs => {
+                self.cycle_glyphs(key == KeyCode::Left);
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.field() == SettingField::KeyPreset => {
+                self.cycle_key_preset(key == KeyCode::Left);
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.field() == SettingField::Network => {
+                self.cycle_network(key == KeyCode::Left);
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.field() == SettingField::CheckUpdates => {
+                self.check_updates = !self.check_updates;
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.field() == SettingField::DesktopNotifications => {
+                self.desktop_notifications = !self.desktop_notifications;
+            }
+            KeyCode::Enter => match self.field() {
+                SettingField::Network => self.cycle_network(false),
+                SettingField::Theme => self.cycle_theme(false),
+                SettingField::Glyphs => self.cycle_glyphs(false),
+                SettingField::QuitKey => self.capturing_key = true,
+                SettingField::KeyPreset => self.cycle_key_preset(false),
+                SettingField::CheckUpdates => self.check_updates = !self.check_updates,
+                SettingField::DesktopNotifications => self.desktop_notifications = !self.desktop_notifications,
+                SettingField::Save => {
+                    self.status = None;
+                    match self.apply(config) {
+                        Ok(()) => {
+                            config.save()?;
+                            self.status = Some("Settings saved".to_string());
+                            return Ok(ScreenAction::SettingsSaved);
+                        }
+                        Err(e) => self.error = Some(e),
+                    }
+                }
+                SettingField::LockPin => self.editing = Some(TextInput::with_text(self.lock_pin.clone()).masked()),
+                field => {
+                    let current = self.text_mut(field).map(|s| s.clone()).unwrap_or_default();
+                    self.editing = Some(TextInput::with_text(current));
+                }
+            },
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    fn cycle_theme(&mut self, backwards: bool) {
+        let len = ThemeName::ALL.len();
+        let index = ThemeName::ALL.iter().position(|t| *t == self.theme).unwrap_or(0);
+        let next = if backwards { (index + len - 1) % len } else { (index + 1) % len };
+        self.theme = ThemeName::ALL[next];
+    }
+
+    fn cycle_glyphs(&mut self, backwards: bool) {
+        let len = GlyphMode::ALL.len();
+        let index = GlyphMode::ALL.iter().position(|g| *g == self.glyphs).unwrap_or(0);
+        let next = if backwards { (index + len - 1) % len } else { (index + 1) % len };
+        self.glyphs = GlyphMode::ALL[next];
+    }
+
+    fn cycle_key_preset(&mut self, backwards: bool) {
+        let len = KeyPreset::ALL.len();
+        let index = KeyPreset::ALL.iter().position(|p| *p == self.key_preset).unwrap_or(0);
+        let next = if backwards { (index + len - 1) % len } else { (index + 1) % len };
+        self.key_preset = KeyPreset::ALL[next];
+    }
+
+    /// Profile the drafted server URL belongs to.
+    fn network_index(&self) -> Option<usize> {
+        let url = self.server_url.trim().trim_end_matches('/');
+        self.networks.iter().position(|n| n.server_url.trim_end_matches('/') == url)
+    }
+
+    fn cycle_network(&mut self, backwards: bool) {
+        let len = self.networks.len();
+        if len == 0 {
+            return;
+        }
+        let next = match self.network_index() {
+            Some(index) if backwards => (index + len - 1) % len,
+            Some(index) => (index + 1) % len,
+            None => 0,
+        };
+        self.server_url = self.networks[next].server_url.clone();
+    }
+
+    /// Validate the draft and copy it into `config`; on error nothing is changed.
+    fn apply(&mut self, config: &mut AppConfig) -> Result<(), String> {
+        let server_url = self.server_url.trim().trim_end_matches('/').to_string();
+        if !server_url.starts_with("http://") && !server_url.starts_with("https://") {
+            self.focus.focus(SettingField::ServerUrl);
+            return Err("Server URL must start with http:// or https://".to_string());
+        }
+
+        let session_check = parse_secs(&self.session_check, "Session check", MIN_POLL_SECS, false)?;
+        let balance_refresh = parse_secs(&self.balance_refresh, "Balance refresh", MIN_POLL_SECS, false)?;
+        let view_refresh = parse_secs(&self.view_refresh, "View refresh", MIN_POLL_SECS, true)?;
+        let claim_poll = parse_secs(&self.claim_poll, "Claim check", MIN_POLL_SECS, true)?;
+        let stream_stall = parse_secs(&self.stream_stall, "Stream stall", MIN_STREAM_STALL_SECS, true)?;
+        let price_refresh = parse_secs(&self.price_refresh, "Price refresh", MIN_POLL_SECS, false)?;
+
+        let price_feed = self.price_feed.trim();
+        if !price_feed.is_empty() && !price_feed.starts_with("http://") && !price_feed.starts_with("https://") {
+            return Err("USD price feed must start with http:// or https://".to_string());
+        }
+
+        let min_balance = self.min_balance.trim();
+        let decimals = self.network_index().map_or_else(|| config.network().decimals, |i| self.networks[i].decimals);
+        if parse_tokens(min_balance, decimals).is_none() {
+            return Err(format!("Min balance must be a token amount with at most {} decimals", decimals));
+        }
+
+        let agent_dir = self.agent_dir.trim();
+        if !agent_dir.is_empty() && !std::path::Path::new(agent_dir).is_dir() {
+            return Err(format!("Agent dir {} is not a directory", agent_dir));
+        }
+
+        let idle_lock: u64 = self
+            .idle_lock
+            .trim()
+            .parse()
+            .map_err(|_| "Idle lock must be a whole number of minutes".to_string())?;
+        let lock_pin = self.lock_pin.trim();
+        if !lock_pin.is_empty() && lock_pin.chars().count() < MIN_LOCK_PIN {
+            return Err(format!("Lock PIN must be at least {} characters", MIN_LOCK_PIN));
+        }
+        if idle_lock > 0 && lock_pin.is_empty() && !self.has_lock_pin && !crate::secrets::can_unlock(None) {
+            self.focus.focus(SettingField::LockPin);
+            return Err("Set a lock PIN to unlock the idle lock with".to_string());
+        }
+        let lock_pin = match lock_pin {
+            "" => config.lock_pin.clone(),
+            pin => Some(crate::secrets::hash_pin(pin).map_err(|e| e.to_string())?),
+        };
+
+        if self.quit_key.is_ascii_digit() {
+            return Err("Quit key can't be a digit (those select menu items)".to_string());
+        }
+        if self.key_preset == KeyPreset::Vim && crate::keymap::VIM_KEYS.contains(&self.quit_key) {
+            return Err(format!("Quit key {} is taken by the vim preset", self.quit_key));
+        }
+
+        config.switch_server(server_url);
+        config.session_check_secs = Some(session_check);
+        config.balance_refresh_secs = Some(balance_refresh);
+        config.view_refresh_secs = Some(view_refresh);
+        config.claim_poll_secs = Some(claim_poll);
+        config.stream_stall_secs = Some(stream_stall);
+        config.price_feed_url = (!price_feed.is_empty()).then(|| price_feed.to_string());
+        config.price_refresh_secs = Some(price_refresh);
+        config.min_balance = Some(min_balance.to_string());
+        config.custom_agent_dir = (!agent_dir.is_empty()).then(|| agent_dir.to_string());
+        config.theme = self.theme;
+        config.glyphs = self.glyphs;
+        config.keybindings.quit = self.quit_key;
+        config.keybindings.preset = self.key_preset;
+        config.check_updates = self.check_updates;
+        config.desktop_notifications = self.desktop_notifications;
+        config.idle_lock_minutes = Some(idle_lock);
+        config.lock_pin = lock_pin;
+        self.lock_pin.clear();
+        self.has_lock_pin = config.lock_pin.is_some();
+        Ok(())
+    }
+}
+
+/// Shortest lock PIN accepted.
+const MIN_LOCK_PIN: usize = 4;
+
+/// Parse a period in seconds of at least `min` (0 allowed when `allow_off`).
+fn parse_secs(value: &str, name: &str, min: u64, allow_off: bool) -> Result<u64, String> {
+    let secs: u64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} must be a whole number of seconds", name))?;
+    if secs == 0 && allow_off {
+        return Ok(0);
+    }
+    if secs < min {
+        return Err(format!("{} must be at least {} seconds", name, min));
+    }
+    Ok(secs)
+}
+
+impl Screen for SettingsScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(10),   // Settings
+                Constraint::Length(2), // Hint
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" SETTINGS ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled("saved to config.toml", Style::default().fg(theme.muted)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        // Label column is 22 wide, plus highlight symbol and borders
+        let value_width = chunks[1].width.saturating_sub(27) as usize;
+        let items: Vec<ListItem> = SettingField::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                if *field == SettingField::Save {
+                    return ListItem::new(Line::from(vec![
+                        Span::styled("  ", Style::default()),
+                        Span::styled("[ Save settings ]", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                    ]));
+                }
+
+                let editing = self.editing.as_ref().filter(|_| i == self.focus.index());
+                if *field == SettingField::Network {
+                    let (name, kind) = match self.network_index().map(|i| &self.networks[i]) {
+                        Some(network) => (network.name.as_str(), network.kind),
+                        None => ("custom", NetworkKind::Custom),
+                    };
+                    return ListItem::new(Line::from(vec![
+                        Span::styled(format!("  {:<20}", field.label()), Style::default().fg(theme.secondary)),
+                        Span::styled(format!("◀ {} ▶ ", name), Style::default().fg(theme.accent)),
+                        network_badge(kind, theme),
+                    ]));
+                }
+
+                let (value, color) = match field {
+                    SettingField::ServerUrl => (self.server_url.clone(), theme.info),
+                    SettingField::SessionCheck => (self.session_check.clone(), theme.warning),
+                    SettingField::BalanceRefresh => (self.balance_refresh.clone(), theme.warning),
+                    SettingField::ViewRefresh if self.view_refresh.trim() == "0" => ("0 (off)".to_string(), theme.muted),
+                    SettingField::ViewRefresh => (self.view_refresh.clone(), theme.warning),
+                    SettingField::ClaimPoll if self.claim_poll.trim() == "0" => ("0 (off)".to_string(), theme.muted),
+                    SettingField::ClaimPoll => (self.claim_poll.clone(), theme.warning),
+                    SettingField::StreamStall if self.stream_stall.trim() == "0" => ("0 (off)".to_string(), theme.muted),
+                    SettingField::StreamStall => (self.stream_stall.clone(), theme.warning),
+                    SettingField::PriceFeed if self.price_feed.is_empty() => ("(off)".to_string(), theme.muted),
+                    SettingField::PriceFeed => (self.price_feed.clone(), theme.info),
+                    SettingField::PriceRefresh => (self.price_refresh.clone(), theme.warning),
+                    SettingField::MinBalance => (self.min_balance.clone(), theme.warning),
+                    SettingField::AgentDir if self.agent_dir.is_empty() => ("(embedded agent)".to_string(), theme.muted),
+                    SettingField::AgentDir => (self.agent_dir.clone(), theme.text),
+                    SettingField::Theme => (format!("◀ {} ▶", self.theme.label()), theme.accent),
+                    SettingField::Glyphs => (format!("◀ {} ▶", self.glyphs.label()), theme.accent),
+                    SettingField::QuitKey if self.capturing_key && i == self.focus.index() => {
+                        ("press a key...".to_string(), theme.warning)
+                    }
+                    SettingField::QuitKey => (self.quit_key.to_string(), theme.text),
+                    SettingField::KeyPreset => (format!("◀ {} ▶", self.key_preset.label()), theme.accent),
+                    SettingField::CheckUpdates if self.check_updates => ("◀ on ▶".to_string(), theme.accent),
+                    SettingField::CheckUpdates => ("◀ off ▶".to_string(), theme.muted),
+                    SettingField::DesktopNotifications if self.desktop_notifications => ("◀ on ▶".to_string(), theme.accent),
+                    SettingField::DesktopNotifications => ("◀ off ▶".to_string(), theme.muted),
+                    SettingField::IdleLock if self.idle_lock.trim() == "0" => ("0 (off)".to_string(), theme.muted),
+                    SettingField::IdleLock if self.lock_pin.is_empty() && !self.has_lock_pin && !crate::secrets::can_unlock(None) => {
+                        (format!("{} (off: no lock PIN)", self.idle_lock), theme.error)
+                    }
+                    SettingField::IdleLock => (self.idle_lock.clone(), theme.warning),
+                    SettingField::LockPin if !self.lock_pin.is_empty() => ("(new PIN, saved with Save)".to_string(), theme.accent),
+                    SettingField::LockPin if self.has_lock_pin => ("(set)".to_string(), theme.text),
+                    SettingField::LockPin => ("(none)".to_string(), theme.muted),
+                    SettingField::Network | SettingField::Save => unreachable!(),
+                };
+                let mut spans = vec![Span::styled(
+                    format!("  {:<20}", field.label()),
+                    Style::default().fg(theme.secondary),
+                )];
+                match editing {
+                    Some(buffer) => spans.extend(buffer.line(true, value_width, Style::default().fg(theme.text)).spans),
+                    None => spans.push(Span::styled(tail_width(&value, value_width).to_string(), Style::default().fg(color))),
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.muted)))
+            .highlight_symbol("▌")
+            .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+        let mut state = ListState::default().with_selected(Some(self.focus.index()));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+
+        let hint = Paragraph::new(Span::styled(self.field().hint(), Style::default().fg(theme.muted)))
+            .alignment(Alignment::Center);
+        frame.render_widget(hint, chunks[2]);
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else if let Some(status) = &self.status {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                Span::styled(status.as_str(), Style::default().fg(theme.success)),
+            ])
+        } else if self.editing.is_some() {
+            Line::from(vec![
+                Span::styled("[Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Done", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Discard edit", Style::default().fg(theme.muted)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Navigate", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Edit", Style::default().fg(theme.muted)),
+                Span::styled("  [←/→] ", Style::default().fg(theme.muted)),
+                Span::styled("Cycle", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back", Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppScreen;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app};
+
+    #[test]
+    fn test_parse_secs() {
+        assert_eq!(parse_secs("30", "x", MIN_POLL_SECS, false), Ok(30));
+        assert_eq!(parse_secs("0", "x", MIN_POLL_SECS, true), Ok(0));
+        assert!(parse_secs("0", "x", MIN_POLL_SECS, false).is_err());
+        assert!(parse_secs("2", "x", MIN_POLL_SECS, true).is_err());
+        assert!(parse_secs("soon", "x", MIN_POLL_SECS, true).is_err());
+        assert!(parse_secs("10", "x", MIN_STREAM_STALL_SECS, true).is_err());
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut app = signed_in_app();
+        app.settings.reset(&app.config);
+        app.screen = AppScreen::Settings;
+        assert_snapshot("settings", &screen_text(&mut app));
+    }
+}
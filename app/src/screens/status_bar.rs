@@ -0,0 +1,83 @@
+//! One-line status bar at the bottom of every screen.
+
+use crate::{
+    app::{App, Connection},
+    text::truncate_width,
+    theme::Theme,
+};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Full-width warning line shown above the status bar, e.g. for low balances.
+pub fn render_warning(frame: &mut Frame, area: Rect, warning: &str, theme: &Theme) {
+    let line = Line::from(Span::styled(
+        format!(" ⚠ {}", warning),
+        Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+    ));
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Connection, latest block, wallet balance and nonce, running background
+/// tasks and a newer release, if the update check found one.
+pub fn render_status_bar(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let mut spans = match &app.connection {
+        Connection::Unknown => vec![Span::styled(" ○ connecting", Style::default().fg(theme.muted))],
+        Connection::Online => vec![Span::styled(" ● online", Style::default().fg(theme.success))],
+        Connection::Offline(e) => vec![Span::styled(
+            format!(" ● offline: {}", truncate_width(e, 40)),
+            Style::default().fg(theme.error),
+        )],
+    };
+
+    if let Some(block) = app.block_number {
+        spans.push(Span::styled(format!("   #{}", block), Style::default().fg(theme.secondary)));
+    }
+
+    if app.wallet_address().is_some() {
+        let balance = match app.wallet_balance.as_ref().map(|b| b.balance_formatted.as_str()) {
+            Some(balance) => match app.usd_value(balance) {
+                Some(usd) => format!("   ◈ {} {} ({})", balance, app.config.network().token_symbol, usd),
+                None => format!("   ◈ {} {}", balance, app.config.network().token_symbol),
+            },
+            None => "   ◈ …".to_string(),
+        };
+        spans.push(Span::styled(balance, Style::default().fg(theme.warning)));
+    }
+
+    // Known once this session has submitted something; a pending transaction
+    // is why the next one waits for a block
+    if let Some((nonce, pending)) = app.wallet_address().and_then(crate::nonce::status) {
+        let (text, color) = match pending {
+            0 => (format!("   nonce {}", nonce), theme.secondary),
+            n => (format!("   nonce {} · {} pending", nonce, n), theme.warning),
+        };
+        spans.push(Span::styled(text, Style::default().fg(color)));
+    }
+
+    let tasks = crate::tasks::in_flight();
+    if tasks > 0 {
+        let label = if tasks == 1 { "task" } else { "tasks" };
+        spans.push(Span::styled(
+            format!("   {} {} {}", app.spinner(), tasks, label),
+            Style::default().fg(theme.accent),
+        ));
+    }
+
+    if let Some(version) = &app.update_available {
+        spans.push(Span::styled(
+            format!("   ↑ {} available: lobster self-update", version),
+            Style::default().fg(theme.info),
+        ));
+    }
+
+    let hint = if app.current_error().is_some() { " ! error details  ? help " } else { " ? help " };
+    let [left, right] = Layout::horizontal([Constraint::Min(0), Constraint::Length(hint.len() as u16)]).areas(area);
+    frame.render_widget(Paragraph::new(Line::from(spans)), left);
+    frame.render_widget(Paragraph::new(Span::styled(hint, Style::default().fg(theme.muted))), right);
+}
@@ -0,0 +1,299 @@
+//! Submolt browser - list communities and manage the agent's subscriptions.
+
+use crate::{
+    app::{App, AppMessage, MessageContext, ScreenAction},
+    client::ApiClient,
+    moltbook::Submolt,
+    screens::{input::SelectList, Screen},
+    text::truncate_width,
+};
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+/// Background results for the submolt list.
+#[derive(Debug, Clone)]
+pub enum SubmoltsMessage {
+    /// Submolt list fetched
+    SubmoltsFetched(Vec<Submolt>),
+    /// Submolt list fetch failed
+    SubmoltsFetchFailed(String),
+    /// Joining or leaving a submolt failed (optimistic update is reverted)
+    SubscriptionFailed { name: String, subscribe: bool, error: String },
+}
+
+pub struct SubmoltsScreen {
+    pub submolts: Vec<Submolt>,
+    pub selected: SelectList,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Feedback for the last join/leave
+    pub status: Option<String>,
+}
+
+impl SubmoltsScreen {
+    pub fn new() -> Self {
+        Self {
+            submolts: Vec::new(),
+            selected: SelectList::new(),
+            loading: false,
+            error: None,
+            status: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn handle_key(
+        &mut self,
+        key: KeyCode,
+        client: &ApiClient,
+        api_key: Option<&str>,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> Result<ScreenAction> {
+        match key {
+            KeyCode::Esc => return Ok(ScreenAction::Pop),
+            key if self.selected.handle_key(key, self.submolts.len()) => {}
+            KeyCode::Char('r') | KeyCode::Char('R') => self.refresh(client, api_key, tx),
+            KeyCode::Enter | KeyCode::Char(' ') => self.toggle_subscription(client, api_key, tx),
+            _ => {}
+        }
+        Ok(ScreenAction::None)
+    }
+
+    /// Reload the submolt list.
+    pub fn refresh(&mut self, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        let Some(api_key) = api_key.map(str::to_string) else {
+            self.error = Some("No Moltbook API key stored for this agent".to_string());
+            return;
+        };
+        self.loading = true;
+        self.error = None;
+        let client = client.clone();
+        crate::tasks::spawn(async move {
+            match client.list_submolts(&api_key).await {
+                Ok(submolts) => {
+                    let _ = tx.send(SubmoltsMessage::SubmoltsFetched(submolts).into()).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(SubmoltsMessage::SubmoltsFetchFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
+    }
+
+    /// Join or leave the selected submolt, updating the list optimistically.
+    fn toggle_subscription(&mut self, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        let Some(api_key) = api_key.map(str::to_string) else {
+            self.error = Some("No Moltbook API key stored for this agent".to_string());
+            return;
+        };
+        let Some(submolt) = self.submolts.get_mut(self.selected.selected()) else {
+            return;
+        };
+
+        let subscribe = !submolt.is_subscribed;
+        set_subscribed(submolt, subscribe);
+        self.error = None;
+        self.status = Some(if subscribe {
+            format!("Joined m/{}", submolt.name)
+        } else {
+            format!("Left m/{}", submolt.name)
+        });
+
+        let name = submolt.name.clone();
+        let client = client.clone();
+        crate::tasks::spawn(async move {
+            let result = if subscribe {
+                client.subscribe_submolt(&api_key, &name).await
+            } else {
+                client.unsubscribe_submolt(&api_key, &name).await
+            };
+            if let Err(e) = result {
+                let _ = tx
+                    .send(SubmoltsMessage::SubscriptionFailed { name, subscribe, error: format!("{:#}", e) }.into())
+                    .await;
+            }
+        });
+    }
+
+    pub fn handle_submolts(&mut self, mut submolts: Vec<Submolt>) {
+        // Subscribed first, then by size
+        submolts.sort_by(|a, b| {
+            b.is_subscribed
+                .cmp(&a.is_subscribed)
+                .then(b.subscriber_count.cmp(&a.subscriber_count))
+        });
+        self.submolts = submolts;
+        self.loading = false;
+        self.selected.clamp(self.submolts.len());
+    }
+
+    pub fn handle_fetch_error(&mut self, error: String) {
+        self.error = Some(error);
+        self.loading = false;
+    }
+
+    /// Revert an optimistic join/leave after the request failed.
+    pub fn handle_subscription_failed(&mut self, name: &str, subscribe: bool, error: String) {
+        if let Some(submolt) = self.submolts.iter_mut().find(|s| s.name == name) {
+            if submolt.is_subscribed == subscribe {
+                set_subscribed(submolt, !subscribe);
+            }
+        }
+        self.status = None;
+        let action = if subscribe { "Join" } else { "Leave" };
+        self.error = Some(format!("{} m/{} failed: {}", action, name, error));
+    }
+
+    pub fn handle_message(&mut self, msg: SubmoltsMessage, _ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            SubmoltsMessage::SubmoltsFetched(submolts) => self.handle_submolts(submolts),
+            SubmoltsMessage::SubmoltsFetchFailed(e) => self.handle_fetch_error(e),
+            SubmoltsMessage::SubscriptionFailed { name, subscribe, error } => {
+                self.handle_subscription_failed(&name, subscribe, error);
+            }
+        }
+        Ok(ScreenAction::None)
+    }
+}
+
+fn set_subscribed(submolt: &mut Submolt, subscribed: bool) {
+    submolt.is_subscribed = subscribed;
+    submolt.subscriber_count = if subscribed {
+        submolt.subscriber_count + 1
+    } else {
+        submolt.subscriber_count.saturating_sub(1)
+    };
+}
+
+impl Screen for SubmoltsScreen {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(6),    // Submolts
+                Constraint::Length(2), // Footer
+            ])
+            .split(area);
+
+        let agent = app.agent_name().unwrap_or("agent");
+        let joined = self.submolts.iter().filter(|s| s.is_subscribed).count();
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(" SUBMOLTS ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
+            Span::styled(agent, Style::default().fg(theme.accent)),
+            Span::styled(format!(" • subscribed to {}", joined), Style::default().fg(theme.muted)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(title, chunks[0]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(
+                format!(" Submolts ({}) ", self.submolts.len()),
+                Style::default().fg(theme.text),
+            ));
+
+        if self.loading {
+            let loading = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!("{} Loading submolts...", app.spinner()),
+                    Style::default().fg(theme.warning),
+                )),
+            ])
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(loading, chunks[1]);
+        } else if self.submolts.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled("No submolts found", Style::default().fg(theme.muted))),
+            ])
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(empty, chunks[1]);
+        } else {
+            let description_width = chunks[1].width.saturating_sub(8) as usize;
+            let items: Vec<ListItem> = self
+                .submolts
+                .iter()
+                .map(|s| {
+                    let (marker, marker_color) = if s.is_subscribed {
+                        ("● ", theme.success)
+                    } else {
+                        ("○ ", theme.muted)
+                    };
+                    let mut header = vec![
+                        Span::styled(format!("  {}", marker), Style::default().fg(marker_color)),
+                        Span::styled(format!("m/{}", s.name), Style::default().fg(theme.accent)),
+                    ];
+                    if let Some(display) = s.display_name.as_deref().filter(|d| *d != s.name) {
+                        header.push(Span::styled(format!(" {}", display), Style::default().fg(theme.text)));
+                    }
+                    header.push(Span::styled(
+                        format!(" • {} subscribers", s.subscriber_count),
+                        Style::default().fg(theme.muted),
+                    ));
+                    let description = s.description.as_deref().unwrap_or("");
+                    ListItem::new(vec![
+                        Line::from(header),
+                        Line::from(Span::styled(
+                            format!("    {}", truncate_width(description, description_width)),
+                            Style::default().fg(theme.secondary),
+                        )),
+                    ])
+                })
+                .collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_symbol("▌")
+                .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+            let mut state = self.selected.state();
+            frame.render_stateful_widget(list, chunks[1], &mut state);
+        }
+
+        let footer_content = if let Some(err) = &self.error {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else if let Some(status) = &self.status {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                Span::styled(status.as_str(), Style::default().fg(theme.success)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Navigate", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Join/Leave", Style::default().fg(theme.muted)),
+                Span::styled("  [R] ", Style::default().fg(theme.muted)),
+                Span::styled("Refresh", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back", Style::default().fg(theme.muted)),
+            ])
+        };
+        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[2]);
+    }
+}
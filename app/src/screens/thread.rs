@@ -0,0 +1,336 @@
+//! Comment thread of a Moltbook post, drawn as an indented tree with
+//! collapsible replies. Shared by the post detail view and the inbox.
+
+use crate::{
+    app::AppMessage,
+    client::ApiClient,
+    moltbook::Comment,
+    screens::input::SelectList,
+    text::{display_width, head_width, truncate_width},
+    theme::Theme,
+};
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use std::collections::HashSet;
+use tokio::sync::mpsc;
+
+/// Levels indented before deeper replies stay at the same indent.
+const MAX_INDENT: usize = 6;
+
+/// Lines of a comment's text shown before it is cut.
+const MAX_COMMENT_LINES: usize = 3;
+
+/// Comments fetched for a thread, routed back by the screen that asked.
+#[derive(Debug, Clone)]
+pub enum ThreadMessage {
+    CommentsFetched { post_id: String, comments: Vec<Comment> },
+    CommentsFetchFailed { post_id: String, error: String },
+}
+
+/// A comment as shown: its depth and how many replies collapsing it hides.
+pub struct Row<'a> {
+    pub depth: usize,
+    pub comment: &'a Comment,
+    pub hidden: usize,
+}
+
+pub struct CommentThread {
+    pub post_id: String,
+    pub comments: Vec<Comment>,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// The selected one of the visible rows
+    pub selected: SelectList,
+    /// Ids of comments whose replies are folded away
+    collapsed: HashSet<String>,
+    /// Comment to select once the thread arrives
+    focus: Option<String>,
+}
+
+impl CommentThread {
+    /// An empty thread waiting for `fetch`; `focus` is selected when it loads.
+    pub fn new(post_id: &str, focus: Option<String>) -> Self {
+        Self {
+            post_id: post_id.to_string(),
+            comments: Vec::new(),
+            loading: true,
+            error: None,
+            selected: SelectList::new(),
+            collapsed: HashSet::new(),
+            focus,
+        }
+    }
+
+    /// Fetch the post's comments; `wrap` routes the result to the screen.
+    pub fn fetch(
+        &mut self,
+        client: &ApiClient,
+        api_key: Option<&str>,
+        tx: mpsc::Sender<AppMessage>,
+        wrap: fn(ThreadMessage) -> AppMessage,
+    ) {
+        self.loading = true;
+        self.error = None;
+        let client = client.clone();
+        let api_key = api_key.map(str::to_string);
+        let post_id = self.post_id.clone();
+        crate::tasks::spawn(async move {
+            let msg = match client.get_comments(api_key.as_deref(), &post_id).await {
+                Ok(comments) => ThreadMessage::CommentsFetched { post_id, comments },
+                Err(e) => ThreadMessage::CommentsFetchFailed { post_id, error: format!("{:#}", e) },
+            };
+            let _ = tx.send(wrap(msg)).await;
+        });
+    }
+
+    pub fn handle_message(&mut self, msg: ThreadMessage) {
+        match msg {
+            ThreadMessage::CommentsFetched { post_id, comments } if post_id == self.post_id => {
+                self.loading = false;
+                self.comments = comments;
+                let focus = self.focus.take();
+                let row = focus.and_then(|id| self.reveal(&id)).unwrap_or(0);
+                self.selected.select(row, self.rows().len());
+            }
+            ThreadMessage::CommentsFetchFailed { post_id, error } if post_id == self.post_id => {
+                self.loading = false;
+                self.error = Some(error);
+            }
+            // A thread that was closed or replaced meanwhile
+            _ => {}
+        }
+    }
+
+    /// Visible comments in reading order: each followed by its replies,
+    /// unless it is collapsed.
+    pub fn rows(&self) -> Vec<Row<'_>> {
+        fn walk<'a>(comments: &'a [Comment], depth: usize, collapsed: &HashSet<String>, rows: &mut Vec<Row<'a>>) {
+            for comment in comments {
+                if collapsed.contains(&comment.id) {
+                    rows.push(Row { depth, comment, hidden: count(&comment.replies) });
+                } else {
+                    rows.push(Row { depth, comment, hidden: 0 });
+                    walk(&comment.replies, depth + 1, collapsed, rows);
+                }
+            }
+        }
+        let mut rows = Vec::new();
+        walk(&self.comments, 0, &self.collapsed, &mut rows);
+        rows
+    }
+
+    /// Expand the comments above `id` and return its row, if it is in the thread.
+    fn reveal(&mut self, id: &str) -> Option<usize> {
+        fn path<'a>(comments: &'a [Comment], id: &str, out: &mut Vec<&'a str>) -> bool {
+            comments.iter().any(|c| {
+                if c.id == id || path(&c.replies, id, out) {
+                    out.push(&c.id);
+                    true
+                } else {
+                    false
+                }
+            })
+        }
+        let mut ancestors = Vec::new();
+        if !path(&self.comments, id, &mut ancestors) {
+            return None;
+        }
+        for ancestor in ancestors.iter().skip(1) {
+            self.collapsed.remove(*ancestor);
+        }
+        self.rows().iter().position(|r| r.comment.id == id)
+    }
+
+    /// Fold or unfold the replies of the selected comment.
+    pub fn toggle_selected(&mut self) {
+        let Some(row) = self.rows().into_iter().nth(self.selected.selected()) else {
+            return;
+        };
+        if row.comment.replies.is_empty() {
+            return;
+        }
+        let id = row.comment.id.clone();
+        if !self.collapsed.remove(&id) {
+            self.collapsed.insert(id);
+        }
+    }
+
+    /// Move through and fold comments; false when the key isn't the thread's.
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            key if self.selected.handle_key(key, self.rows().len()) => {}
+            KeyCode::Char(' ') | KeyCode::Enter => self.toggle_selected(),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, title: &str, spinner: &str, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(format!(" {} ({}) ", title, count(&self.comments)), Style::default().fg(theme.text)));
+
+        let message = if self.loading {
+            Some((format!("{} Loading comments...", spinner), theme.warning))
+        } else if let Some(error) = &self.error {
+            Some((format!("{} {}", theme.err_mark(), error), theme.error))
+        } else if self.comments.is_empty() {
+            Some(("No comments yet".to_string(), theme.muted))
+        } else {
+            None
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(vec![Line::from(""), Line::from(Span::styled(text, Style::default().fg(color)))])
+                .alignment(Alignment::Center)
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let width = area.width.saturating_sub(4) as usize;
+        let items: Vec<ListItem> = self.rows().iter().map(|row| comment_item(row, width, theme)).collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol("▌")
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+        let mut state = self.selected.state();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+}
+
+/// Comments in a tree, replies included.
+fn count(comments: &[Comment]) -> usize {
+    comments.iter().map(|c| 1 + count(&c.replies)).sum()
+}
+
+/// Header with votes and fold marker, then the text wrapped under it.
+fn comment_item<'a>(row: &Row<'a>, width: usize, theme: &Theme) -> ListItem<'a> {
+    let comment = row.comment;
+    let guides = "│ ".repeat(row.depth.min(MAX_INDENT));
+    let marker = match (comment.replies.is_empty(), row.hidden > 0) {
+        (true, _) => "• ",
+        (false, true) => "▸ ",
+        (false, false) => "▾ ",
+    };
+    let author = comment.author.as_ref().map(|a| a.name.as_str()).unwrap_or("unknown");
+    let mut header = vec![
+        Span::styled(guides.clone(), Style::default().fg(theme.muted)),
+        Span::styled(marker, Style::default().fg(theme.accent)),
+        Span::styled(author, Style::default().fg(theme.info)),
+        Span::styled(" • ", Style::default().fg(theme.muted)),
+        Span::styled(format!("↑{}", comment.upvotes), Style::default().fg(theme.success)),
+        Span::styled(format!(" ↓{}", comment.downvotes), Style::default().fg(theme.error)),
+    ];
+    if let Some(created_at) = &comment.created_at {
+        header.push(Span::styled(format!(" • {}", created_at), Style::default().fg(theme.muted)));
+    }
+    if row.hidden > 0 {
+        let replies = if row.hidden == 1 { "reply" } else { "replies" };
+        header.push(Span::styled(format!(" • {} {} hidden", row.hidden, replies), Style::default().fg(theme.warning)));
+    }
+
+    let mut lines = vec![Line::from(header)];
+    if row.hidden == 0 {
+        let indent = format!("{}  ", guides);
+        let text_width = width.saturating_sub(display_width(&indent)).max(10);
+        let wrapped = wrap(&comment.content, text_width);
+        let cut = wrapped.len() > MAX_COMMENT_LINES;
+        for (i, text) in wrapped.into_iter().take(MAX_COMMENT_LINES).enumerate() {
+            let text = if cut && i + 1 == MAX_COMMENT_LINES { truncate_width(&format!("{}...", text), text_width) } else { text };
+            lines.push(Line::from(vec![
+                Span::styled(indent.clone(), Style::default().fg(theme.muted)),
+                Span::styled(text, Style::default().fg(theme.text)),
+            ]));
+        }
+    }
+    ListItem::new(lines)
+}
+
+/// Break text into lines of at most `width` columns, at spaces where possible.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines().filter(|l| !l.trim().is_empty()) {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word.to_string();
+            while display_width(&word) > width {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                let split = head_width(&word, width).len();
+                if split == 0 {
+                    break;
+                }
+                let rest = word.split_off(split);
+                lines.push(word);
+                word = rest;
+            }
+            if !line.is_empty() && display_width(&line) + 1 + display_width(&word) > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread() -> CommentThread {
+        let comments = serde_json::json!([
+            { "id": "c1", "content": "first", "replies": [
+                { "id": "c2", "content": "reply", "replies": [
+                    { "id": "c3", "content": "deep reply" },
+                ] },
+            ] },
+            { "id": "c4", "content": "second" },
+        ]);
+        let mut thread = CommentThread::new("p1", None);
+        let comments = serde_json::from_value(comments).unwrap();
+        thread.handle_message(ThreadMessage::CommentsFetched { post_id: "p1".to_string(), comments });
+        thread
+    }
+
+    fn visible(thread: &CommentThread) -> Vec<(usize, &str, usize)> {
+        thread.rows().iter().map(|r| (r.depth, r.comment.id.as_str(), r.hidden)).collect()
+    }
+
+    #[test]
+    fn test_collapse_and_focus() {
+        let mut thread = thread();
+        assert_eq!(visible(&thread), [(0, "c1", 0), (1, "c2", 0), (2, "c3", 0), (0, "c4", 0)]);
+
+        thread.toggle_selected();
+        assert_eq!(visible(&thread), [(0, "c1", 2), (0, "c4", 0)]);
+        assert!(thread.handle_key(KeyCode::Char('j')));
+        assert_eq!(thread.selected.selected(), 1);
+
+        // Focusing a folded reply unfolds the way to it
+        assert_eq!(thread.reveal("c3"), Some(2));
+        assert_eq!(thread.reveal("nope"), None);
+        assert_eq!(count(&thread.comments), 4);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("one two three", 7), ["one two", "three"]);
+        assert_eq!(wrap("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("a\n\nb", 10), ["a", "b"]);
+        assert_eq!(wrap("🦞🦞🦞 ok", 4), ["🦞🦞", "🦞", "ok"]);
+    }
+}
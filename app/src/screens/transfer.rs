@@ -0,0 +1,180 @@
+//! Transfer ownership modal on the view screen - hand the agent to another account.
+
+use crate::{
+    app::AppMessage,
+    client::ApiClient,
+    screens::{input::TextInput, view::ViewMessage},
+    theme::Theme,
+    wallet::WalletConfig,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferStep {
+    /// Typing the new owner's address
+    EnterAddress,
+    /// Reviewing before signing
+    Confirm,
+    Submitting,
+}
+
+pub struct TransferForm {
+    pub step: TransferStep,
+    pub new_owner: TextInput,
+    pub error: Option<String>,
+}
+
+impl TransferForm {
+    pub fn new() -> Self {
+        Self {
+            step: TransferStep::EnterAddress,
+            new_owner: TextInput::new(),
+            error: None,
+        }
+    }
+
+    /// Handle a key; returns false once the form should close.
+    pub fn handle_key(
+        &mut self,
+        event: KeyEvent,
+        client: &ApiClient,
+        agent_address: &str,
+        ss58_prefix: u16,
+        wallet: Option<&WalletConfig>,
+        tx: mpsc::Sender<AppMessage>,
+    ) -> bool {
+        let key = event.code;
+        self.error = None;
+        match self.step {
+            TransferStep::Submitting => {}
+            TransferStep::EnterAddress => match key {
+                KeyCode::Esc => return false,
+                // Addresses have no spaces, so a pasted one can't pick any up
+                KeyCode::Char(c) if c.is_whitespace() => {}
+                _ if self.new_owner.handle_key(event) => {}
+                KeyCode::Enter => {
+                    let new_owner = self.new_owner.text().trim();
+                    if !crate::extrinsic::is_valid_ss58(new_owner) {
+                        self.error = Some("Not a valid SS58 address".to_string());
+                    } else if let Some(prefix) = crate::extrinsic::ss58_prefix(new_owner).filter(|p| *p != ss58_prefix) {
+                        // Most likely an address copied from another chain
+                        self.error = Some(format!(
+                            "Address uses SS58 prefix {}, this network uses {}",
+                            prefix, ss58_prefix
+                        ));
+                    } else if wallet.is_some_and(|w| w.public_key == new_owner) {
+                        self.error = Some("That is already the owner".to_string());
+                    } else if new_owner == agent_address {
+                        self.error = Some("An agent can't own itself".to_string());
+                    } else {
+                        self.step = TransferStep::Confirm;
+                    }
+                }
+                _ => {}
+            },
+            TransferStep::Confirm => match key {
+                KeyCode::Char('y') | KeyCode::Char('Y') => match wallet {
+                    Some(wallet) => {
+                        self.step = TransferStep::Submitting;
+                        spawn_transfer(
+                            client.clone(),
+                            agent_address.to_string(),
+                            self.new_owner.text().trim().to_string(),
+                            wallet.clone(),
+                            tx,
+                        );
+                    }
+                    None => self.error = Some("No wallet available to sign with".to_string()),
+                },
+                KeyCode::Esc => return false,
+                // Anything else goes back to editing the address
+                _ => self.step = TransferStep::EnterAddress,
+            },
+        }
+        true
+    }
+
+    pub fn handle_failed(&mut self, error: String) {
+        self.step = TransferStep::Confirm;
+        self.error = Some(error);
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, current_owner: Option<&str>, spinner: &str, theme: &Theme) {
+        let mut new_owner = self.new_owner.line(
+            self.step == TransferStep::EnterAddress,
+            area.width.saturating_sub(19) as usize,
+            Style::default().fg(theme.accent),
+        );
+        new_owner.spans.insert(0, Span::styled("  New owner      ", Style::default().fg(theme.muted)));
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "Hand this agent to another account (a teammate or a multisig).",
+                Style::default().fg(theme.text),
+            )),
+            Line::from(Span::styled(
+                "After the transfer only the new owner can pause, update or retire it.",
+                Style::default().fg(theme.secondary),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  Current owner  ", Style::default().fg(theme.muted)),
+                Span::styled(current_owner.unwrap_or("unknown"), Style::default().fg(theme.info)),
+            ]),
+            new_owner,
+            Line::from(""),
+        ];
+        match self.step {
+            TransferStep::EnterAddress => {}
+            TransferStep::Confirm => lines.push(Line::from(Span::styled(
+                "  Transfer ownership? This can't be undone from this account. [y] Confirm",
+                Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+            ))),
+            TransferStep::Submitting => lines.push(Line::from(Span::styled(
+                format!("  {} Submitting transfer...", spinner),
+                Style::default().fg(theme.warning),
+            ))),
+        }
+
+        let modal = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Left)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent))
+                .title(Span::styled(" Transfer ownership ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD))));
+        frame.render_widget(modal, area);
+    }
+}
+
+fn spawn_transfer(
+    client: ApiClient,
+    agent_address: String,
+    new_owner: String,
+    wallet: WalletConfig,
+    tx: mpsc::Sender<AppMessage>,
+) {
+    crate::tasks::spawn_operation("an ownership transfer", async move {
+        let result = async {
+            let keypair = wallet.keypair()?;
+            let build = client
+                .build_transfer_ownership(&agent_address, &new_owner, &wallet.public_key)
+                .await?;
+            crate::extrinsic::sign_and_submit(&client, &build, &keypair).await
+        }
+        .await;
+        let msg = match result {
+            Ok(_) => ViewMessage::OwnershipTransferred { new_owner },
+            Err(e) => ViewMessage::OwnershipTransferFailed(format!("{:#}", e)),
+        };
+        let _ = tx.send(msg.into()).await;
+    });
+}
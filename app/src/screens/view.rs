@@ -1,26 +1,199 @@
 //! View agent details screen.
 
 use crate::{
-    app::{App, AppMessage, ScreenAction},
-    client::{AgentInfo, ApiClient, MoltbookPost},
-    screens::Screen,
+    analytics::{self, Analytics},
+    app::{App, AppMessage, AppScreen, MessageContext, ScreenAction},
+    client::{ActivityEvent, ActivityKind, AgentInfo, ApiClient, BalanceResponse, MoltbookPost, PostStats},
+    config::{AppConfig, NetworkProfile},
+    funds::{format_tokens, Funds},
+    health::{Badge, Health},
+    heartbeat::Heartbeat,
+    moltbook::AgentProfile,
+    notifications::Level,
+    report::Report,
+    screens::{amount_confirm::{AmountConfirm, Confirm}, badge_color, badge_mark, format_age, heartbeat_span, input::{SelectList, TextInput}, network_badge, profile::ProfileForm, retire::RetireForm, thread::{CommentThread, ThreadMessage}, transfer::{TransferForm, TransferStep}, Screen},
+    text::{shorten_middle, truncate_width},
+    theme::Theme,
+    versions::{self, DeployedVersion},
+    wallet::WalletConfig,
 };
 use anyhow::Result;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, List, ListItem, Paragraph, Sparkline, Tabs, Wrap},
     Frame,
 };
+use std::{collections::HashMap, time::Instant};
 use tokio::sync::mpsc;
 
+/// Number of posts requested per page.
+const POSTS_PAGE_SIZE: u32 = 20;
+
+/// Start loading the next page when the selection is this close to the end.
+const PREFETCH_THRESHOLD: usize = 3;
+
+/// Number of on-chain events shown in the activity tab.
+const ACTIVITY_LIMIT: u32 = 50;
+
+/// Whole tokens sent from the user wallet by the top-up action.
+const TOP_UP_TOKENS: u128 = 5;
+
+/// Tabs below the agent info card.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewTab {
+    Posts,
+    Activity,
+    Health,
+    Stats,
+    Versions,
+}
+
+impl ViewTab {
+    /// In the order Tab moves through them.
+    const ALL: [ViewTab; 5] = [ViewTab::Posts, ViewTab::Activity, ViewTab::Health, ViewTab::Stats, ViewTab::Versions];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&tab| tab == self).unwrap_or(0)
+    }
+}
+
+/// Action taken on a post as the agent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostAction {
+    Upvote,
+    Downvote,
+    Comment,
+}
+
+impl PostAction {
+    fn label(self) -> &'static str {
+        match self {
+            PostAction::Upvote => "Upvote",
+            PostAction::Downvote => "Downvote",
+            PostAction::Comment => "Comment",
+        }
+    }
+}
+
+/// Move a post's vote counts from our vote `from` to `to`; `None` is no vote.
+fn shift_vote(post: &mut MoltbookPost, from: Option<PostAction>, to: Option<PostAction>) {
+    match from {
+        Some(PostAction::Upvote) => post.upvotes = post.upvotes.saturating_sub(1),
+        Some(PostAction::Downvote) => post.downvotes = post.downvotes.saturating_sub(1),
+        _ => {}
+    }
+    match to {
+        Some(PostAction::Upvote) => post.upvotes += 1,
+        Some(PostAction::Downvote) => post.downvotes += 1,
+        _ => {}
+    }
+}
+
+/// Background results for the agent page and its panels.
+#[derive(Debug, Clone)]
+pub enum ViewMessage {
+    /// Agent info fetched
+    AgentInfoFetched { info: AgentInfo },
+    /// Page of agent posts fetched (offset 0 replaces the list, later pages append);
+    /// dropped when a reload started after it was requested
+    PostsFetched { posts: Vec<MoltbookPost>, generation: u64, offset: u32, has_more: bool },
+    /// Fetch failed
+    FetchFailed(String),
+    /// Posting totals and posts per day, for the Stats tab
+    PostStatsFetched(PostStats),
+    /// Agent account balance fetched
+    AgentBalanceFetched(BalanceResponse),
+    /// Top-up transfer of `amount` planck to the agent account landed on `network`
+    AgentTopUpDone { network: NetworkProfile, amount: u128 },
+    /// Top-up transfer failed
+    AgentTopUpFailed(String),
+    /// Agent's on-chain `active` flag changed (paused or resumed)
+    AgentActiveSet { active: bool },
+    /// Pause/resume extrinsic failed
+    AgentActiveFailed(String),
+    /// Agent's Moltbook profile fetched for editing
+    ProfileFetched(AgentProfile),
+    /// Agent's Moltbook profile saved
+    ProfileSaved(AgentProfile),
+    /// Profile fetch or save failed
+    ProfileFailed(String),
+    /// Agent's on-chain owner changed
+    OwnershipTransferred { new_owner: String },
+    /// Ownership transfer failed
+    OwnershipTransferFailed(String),
+    /// Step of the retire teardown started
+    RetireProgress(String),
+    /// Agent retired and forgotten
+    AgentRetired,
+    /// Retiring the agent failed at some step
+    RetireFailed(String),
+    /// Agent's recent on-chain events fetched
+    ActivityFetched(Vec<ActivityEvent>),
+    /// Activity fetch failed
+    ActivityFetchFailed(String),
+    /// Vote or comment on a post went through
+    PostActionDone { post_id: String, action: PostAction },
+    /// Vote or comment on a post failed (optimistic update is reverted to `previous`)
+    PostActionFailed { post_id: String, action: PostAction, previous: Option<PostAction>, error: String },
+    /// Comments of the open post
+    Thread(ThreadMessage),
+}
+
 pub struct ViewScreen {
     pub agent_info: Option<AgentInfo>,
     pub posts: Vec<MoltbookPost>,
     pub loading: bool,
     pub error: Option<String>,
+    /// The highlighted post
+    pub selected: SelectList,
+    /// Whether the server reported more posts past those loaded
+    pub has_more: bool,
+    /// A follow-up page request is in flight
+    pub loading_more: bool,
+    /// Bumped by every reload, so pages requested before it are ignored
+    posts_generation: u64,
+    /// Showing the selected post in full instead of the list
+    pub detail_open: bool,
+    /// Comment being composed in the post detail view
+    pub comment_input: Option<TextInput>,
+    /// Comments of the post open in the detail view
+    pub thread: Option<CommentThread>,
+    /// Votes cast this session, keyed by post id
+    pub votes: HashMap<String, PostAction>,
+    /// Feedback for the last post action
+    pub status: Option<String>,
+    pub tab: ViewTab,
+    /// Posting totals from the gateway; the Stats tab falls back to the loaded posts
+    pub post_stats: Option<PostStats>,
+    /// Deploys of the agent recorded on this machine, oldest first
+    pub versions: Vec<DeployedVersion>,
+    /// Recent on-chain events, newest first
+    pub activity: Vec<ActivityEvent>,
+    pub activity_loading: bool,
+    pub activity_error: Option<String>,
+    /// The highlighted event
+    pub activity_selected: SelectList,
+    /// Chain balance of the agent account
+    pub agent_balance: Option<BalanceResponse>,
+    /// Waiting for the user to confirm a top-up
+    pub top_up_confirm: bool,
+    /// The top-up is over a spending limit and waits for its amount typed
+    pub amount_confirm: Option<AmountConfirm>,
+    /// A top-up transfer is in flight
+    pub topping_up: bool,
+    /// A pause/resume extrinsic is in flight
+    pub toggling_active: bool,
+    /// Profile editing form, when open
+    pub profile: Option<ProfileForm>,
+    /// Retire confirmation, when open
+    pub retire: Option<RetireForm>,
+    /// Ownership transfer modal, when open
+    pub transfer: Option<TransferForm>,
+    /// When agent info last arrived
+    pub last_updated: Option<Instant>,
 }
 
 impl ViewScreen {
@@ -30,6 +203,31 @@ impl ViewScreen {
             posts: Vec::new(),
             loading: false,
             error: None,
+            selected: SelectList::new(),
+            has_more: false,
+            loading_more: false,
+            posts_generation: 0,
+            detail_open: false,
+            comment_input: None,
+            thread: None,
+            votes: HashMap::new(),
+            status: None,
+            tab: ViewTab::Posts,
+            post_stats: None,
+            versions: Vec::new(),
+            activity: Vec::new(),
+            activity_loading: false,
+            activity_error: None,
+            activity_selected: SelectList::new(),
+            agent_balance: None,
+            top_up_confirm: false,
+            amount_confirm: None,
+            topping_up: false,
+            toggling_active: false,
+            profile: None,
+            retire: None,
+            transfer: None,
+            last_updated: None,
         }
     }
 
@@ -38,64 +236,693 @@ impl ViewScreen {
         self.posts.clear();
         self.loading = true;
         self.error = None;
+        self.selected = SelectList::new();
+        self.has_more = false;
+        self.loading_more = false;
+        self.posts_generation += 1;
+        self.detail_open = false;
+        self.comment_input = None;
+        self.thread = None;
+        self.status = None;
+        self.tab = ViewTab::Posts;
+        self.post_stats = None;
+        self.versions.clear();
+        self.activity.clear();
+        self.activity_loading = true;
+        self.activity_error = None;
+        self.activity_selected = SelectList::new();
+        self.agent_balance = None;
+        self.top_up_confirm = false;
+        self.amount_confirm = None;
+        self.topping_up = false;
+        self.toggling_active = false;
+        self.profile = None;
+        self.retire = None;
+        self.transfer = None;
+        self.last_updated = None;
     }
 
     pub fn handle_key(
         &mut self,
-        key: KeyCode,
+        event: KeyEvent,
+        config: &AppConfig,
         client: &ApiClient,
-        agent_address: Option<&str>,
+        funds: &Funds,
+        wallet: Option<&WalletConfig>,
         tx: mpsc::Sender<AppMessage>,
     ) -> Result<ScreenAction> {
+        let key = event.code;
+        let agent_address = config.agent_address.as_deref();
+        let api_key = config.moltbook_api_key.as_deref();
+
+        if let Some(form) = &mut self.retire {
+            let active = self.agent_info.as_ref().and_then(|i| i.chain_info.as_ref()).is_some_and(|c| c.active);
+            let open = match agent_address {
+                Some(addr) => form.handle_key(event, client, addr, active, wallet, tx),
+                None => false,
+            };
+            if !open {
+                self.retire = None;
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if let Some(form) = &mut self.transfer {
+            let open = match agent_address {
+                Some(addr) => form.handle_key(event, client, addr, config.network().ss58_prefix, wallet, tx),
+                None => false,
+            };
+            if !open {
+                self.transfer = None;
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if let Some(confirm) = &mut self.amount_confirm {
+            match confirm.handle_key(event, funds.decimals) {
+                Confirm::Pending => {}
+                Confirm::Cancelled => self.amount_confirm = None,
+                Confirm::Confirmed => {
+                    let amount = confirm.amount;
+                    self.amount_confirm = None;
+                    if let (Some(addr), Some(wallet)) = (agent_address, wallet) {
+                        self.start_top_up(client.clone(), addr.to_string(), amount, config.network(), wallet.clone(), tx);
+                    }
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if self.top_up_confirm {
+            self.top_up_confirm = false;
+            if matches!(key, KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter) {
+                if let (Some(addr), Some(wallet)) = (agent_address, wallet) {
+                    let network = config.network();
+                    let amount = TOP_UP_TOKENS * network.unit();
+                    match funds.over_limit(amount) {
+                        Some(reason) => self.amount_confirm = Some(AmountConfirm::new(amount, reason)),
+                        None => self.start_top_up(client.clone(), addr.to_string(), amount, network, wallet.clone(), tx),
+                    }
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if let Some(form) = &mut self.profile {
+            if !form.handle_key(event, client, api_key, tx) {
+                self.profile = None;
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        if self.detail_open {
+            self.handle_detail_key(event, client, api_key, tx);
+            return Ok(ScreenAction::None);
+        }
+
         match key {
+            KeyCode::Tab | KeyCode::BackTab => {
+                let step = if key == KeyCode::Tab { 1 } else { ViewTab::ALL.len() - 1 };
+                self.tab = ViewTab::ALL[(self.tab.index() + step) % ViewTab::ALL.len()];
+                return Ok(ScreenAction::None);
+            }
             KeyCode::Char('r') | KeyCode::Char('R') => {
                 // Refresh data
                 if let Some(addr) = agent_address {
                     self.loading = true;
+                    self.loading_more = false;
+                    self.activity_loading = true;
                     self.error = None;
-                    Self::fetch_data(client.clone(), addr.to_string(), tx);
+                    self.activity_error = None;
+                    self.selected = SelectList::new();
+                    self.activity_selected = SelectList::new();
+                    self.versions = versions::for_agent(addr);
+                    self.fetch_data(client.clone(), addr.to_string(), POSTS_PAGE_SIZE, tx);
                 }
+                return Ok(ScreenAction::None);
+            }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                if self.topping_up || agent_address.is_none() {
+                    return Ok(ScreenAction::None);
+                }
+                if wallet.is_none() {
+                    self.error = Some("No wallet available to send from".to_string());
+                } else {
+                    self.error = None;
+                    self.status = None;
+                    self.top_up_confirm = true;
+                }
+                return Ok(ScreenAction::None);
+            }
+            // Uppercase only, so a stray keypress can't start it
+            KeyCode::Char('X') => {
+                if let Some(name) = config.agent_name.as_deref() {
+                    self.status = None;
+                    self.error = None;
+                    self.retire = Some(RetireForm::new(name));
+                }
+                return Ok(ScreenAction::None);
+            }
+            KeyCode::Char('O') => {
+                let owner = self.agent_info.as_ref().and_then(|i| i.chain_info.as_ref()).map(|c| c.owner.as_str());
+                match (owner, wallet) {
+                    (Some(owner), Some(wallet)) if owner != wallet.public_key => {
+                        self.error = Some("Only the agent's owner can transfer it".to_string());
+                    }
+                    (Some(_), Some(_)) => {
+                        self.status = None;
+                        self.error = None;
+                        self.transfer = Some(TransferForm::new());
+                    }
+                    (None, _) => self.error = Some("Agent is not on chain yet".to_string()),
+                    (_, None) => self.error = Some("No wallet available to sign with".to_string()),
+                }
+                return Ok(ScreenAction::None);
+            }
+            KeyCode::Char('w') | KeyCode::Char('W') => {
+                self.export_report(config);
+                return Ok(ScreenAction::None);
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') if agent_address.is_some() => {
+                return Ok(ScreenAction::Push(AppScreen::Inspect));
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                match api_key {
+                    Some(api_key) => {
+                        self.status = None;
+                        self.error = None;
+                        self.profile = Some(ProfileForm::open(client, api_key, tx));
+                    }
+                    None => self.error = Some("No Moltbook API key stored for this agent".to_string()),
+                }
+                return Ok(ScreenAction::None);
+            }
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                if self.toggling_active {
+                    return Ok(ScreenAction::None);
+                }
+                let (Some(addr), Some(active)) = (agent_address, self.chain_active()) else {
+                    return Ok(ScreenAction::None);
+                };
+                match wallet {
+                    Some(wallet) => {
+                        self.start_set_active(client.clone(), addr.to_string(), !active, wallet.clone(), tx)
+                    }
+                    None => self.error = Some("No wallet available to sign with".to_string()),
+                }
+                return Ok(ScreenAction::None);
             }
             KeyCode::Esc => {
-                return Ok(ScreenAction::GoHome);
+                return Ok(ScreenAction::Pop);
+            }
+            _ => {}
+        }
+
+        if matches!(self.tab, ViewTab::Health | ViewTab::Stats | ViewTab::Versions) {
+            return Ok(ScreenAction::None);
+        }
+
+        if self.tab == ViewTab::Activity {
+            match key {
+                KeyCode::Char('o') => match agent_address.and_then(|addr| config.explorer_link(addr)) {
+                    Some(url) => self.open_link(&url),
+                    None => self.error = Some("Set explorer_url in config.toml to open the explorer".to_string()),
+                },
+                key => {
+                    self.activity_selected.handle_key(key, self.activity.len());
+                }
+            }
+            return Ok(ScreenAction::None);
+        }
+
+        match key {
+            KeyCode::Char('o') => self.open_selected_post(),
+            KeyCode::Enter if !self.posts.is_empty() => {
+                self.detail_open = true;
+                self.comment_input = None;
+                self.status = None;
+                self.thread = None;
+                if let Some(post) = self.posts.get(self.selected.selected()) {
+                    let mut thread = CommentThread::new(&post.id, None);
+                    thread.fetch(client, api_key, tx, |m| ViewMessage::Thread(m).into());
+                    self.thread = Some(thread);
+                }
+            }
+            key if self.selected.handle_key(key, self.posts.len()) => {
+                if let Some(addr) = agent_address {
+                    self.maybe_fetch_more(client, addr, tx);
+                }
             }
             _ => {}
         }
         Ok(ScreenAction::None)
     }
 
+    fn handle_detail_key(&mut self, event: KeyEvent, client: &ApiClient, api_key: Option<&str>, tx: mpsc::Sender<AppMessage>) {
+        let key = event.code;
+        // Comment composer captures all keys while open
+        if let Some(input) = &mut self.comment_input {
+            match key {
+                _ if input.handle_key(event) => {}
+                KeyCode::Enter if !input.text().trim().is_empty() => {
+                    let content = input.text().trim().to_string();
+                    self.comment_input = None;
+                    self.start_post_action(PostAction::Comment, Some(content), client, api_key, tx);
+                }
+                KeyCode::Esc => self.comment_input = None,
+                _ => {}
+            }
+            return;
+        }
+
+        match key {
+            KeyCode::Char('u') => self.start_post_action(PostAction::Upvote, None, client, api_key, tx),
+            KeyCode::Char('d') => self.start_post_action(PostAction::Downvote, None, client, api_key, tx),
+            KeyCode::Char('o') => self.open_selected_post(),
+            KeyCode::Char('c') => {
+                if api_key.is_some() {
+                    self.comment_input = Some(TextInput::new());
+                    self.status = None;
+                } else {
+                    self.error = Some("No Moltbook API key stored for this agent".to_string());
+                }
+            }
+            KeyCode::Esc => {
+                self.detail_open = false;
+                self.thread = None;
+                self.status = None;
+            }
+            _ => {
+                if let Some(thread) = &mut self.thread {
+                    thread.handle_key(key);
+                }
+            }
+        }
+    }
+
+    /// Write the loaded agent data out as a Markdown and an HTML report.
+    fn export_report(&mut self, config: &AppConfig) {
+        let Some(address) = config.agent_address.as_deref() else {
+            return;
+        };
+        if self.loading {
+            self.error = Some("Wait for the agent data to load".to_string());
+            return;
+        }
+        let network = config.network();
+        let info = self.agent_info.as_ref();
+        let report = Report::new(
+            config.agent_name.as_deref().unwrap_or("agent"),
+            address,
+            &network.name,
+            info.and_then(|i| i.chain_info.as_ref()),
+            info.and_then(|i| i.moltbook_info.as_ref()),
+            &self.activity,
+            &self.posts,
+            self.agent_balance.as_ref().map(|b| format!("{} {}", b.balance_formatted, network.token_symbol)),
+            |planck| format!("{} {}", format_tokens(planck, network.decimals), network.token_symbol),
+        );
+        match report.save() {
+            Ok(path) => {
+                self.error = None;
+                self.status = Some(format!("Report saved to {} (and .html)", path.display()));
+            }
+            Err(e) => self.error = Some(format!("Could not save the report: {:#}", e)),
+        }
+    }
+
+    fn open_selected_post(&mut self) {
+        if let Some(post) = self.posts.get(self.selected.selected()) {
+            let url = crate::moltbook::post_url(&post.id);
+            self.open_link(&url);
+        }
+    }
+
+    fn open_link(&mut self, url: &str) {
+        match open::that(url) {
+            Ok(()) => self.status = Some(format!("Opened {}", url)),
+            Err(e) => self.error = Some(format!("Could not open browser: {}", e)),
+        }
+    }
+
+    /// Vote or comment on the selected post, updating counts optimistically.
+    fn start_post_action(
+        &mut self,
+        action: PostAction,
+        content: Option<String>,
+        client: &ApiClient,
+        api_key: Option<&str>,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        let Some(api_key) = api_key.map(str::to_string) else {
+            self.error = Some("No Moltbook API key stored for this agent".to_string());
+            return;
+        };
+        let Some((post_id, previous)) = self.apply_post_action(action) else {
+            return;
+        };
+
+        let client = client.clone();
+        crate::tasks::spawn(async move {
+            let result = match action {
+                PostAction::Upvote => client.upvote_post(&api_key, &post_id).await,
+                PostAction::Downvote => client.downvote_post(&api_key, &post_id).await,
+                PostAction::Comment => {
+                    let content = content.unwrap_or_default();
+                    client.create_comment(&api_key, &post_id, &content).await
+                }
+            };
+            let msg = match result {
+                Ok(()) => ViewMessage::PostActionDone { post_id, action },
+                Err(e) => ViewMessage::PostActionFailed { post_id, action, previous, error: format!("{:#}", e) },
+            };
+            let _ = tx.send(msg.into()).await;
+        });
+    }
+
+    /// Update the selected post's counts and our vote as if `action` went
+    /// through. Returns the post id and the vote it replaced, or `None` when
+    /// there is nothing to send.
+    fn apply_post_action(&mut self, action: PostAction) -> Option<(String, Option<PostAction>)> {
+        let post = self.posts.get_mut(self.selected.selected())?;
+        let previous = self.votes.get(&post.id).copied();
+
+        if action == PostAction::Comment {
+            post.comment_count += 1;
+            self.status = Some("Posting comment...".to_string());
+            return Some((post.id.clone(), previous));
+        }
+        if previous == Some(action) {
+            self.status = Some(format!("Already {}d", action.label().to_lowercase()));
+            return None;
+        }
+        shift_vote(post, previous, Some(action));
+        self.votes.insert(post.id.clone(), action);
+        self.status = Some(format!("{}d", action.label()));
+        Some((post.id.clone(), previous))
+    }
+
+    pub fn handle_post_action_done(&mut self, action: PostAction) {
+        self.error = None;
+        if action == PostAction::Comment {
+            self.status = Some("Comment posted".to_string());
+        }
+    }
+
+    /// Revert an optimistic vote/comment update after the request failed.
+    pub fn handle_post_action_failed(&mut self, post_id: &str, action: PostAction, previous: Option<PostAction>, error: String) {
+        if let Some(post) = self.posts.iter_mut().find(|p| p.id == post_id) {
+            if action == PostAction::Comment {
+                post.comment_count = post.comment_count.saturating_sub(1);
+            } else if self.votes.get(post_id) == Some(&action) {
+                // Only while this is still our vote; a later one already moved the counts on
+                shift_vote(post, Some(action), previous);
+                match previous {
+                    Some(vote) => self.votes.insert(post_id.to_string(), vote),
+                    None => self.votes.remove(post_id),
+                };
+            }
+        }
+        self.status = None;
+        self.error = Some(format!("{} failed: {}", action.label(), error));
+    }
+
     /// Start fetching agent data (called when entering the screen).
     pub fn start_fetch(&mut self, client: ApiClient, agent_address: String, tx: mpsc::Sender<AppMessage>) {
         self.loading = true;
+        self.activity_loading = true;
         self.error = None;
-        Self::fetch_data(client, agent_address, tx);
+        self.versions = versions::for_agent(&agent_address);
+        self.fetch_data(client, agent_address, POSTS_PAGE_SIZE, tx);
     }
 
-    fn fetch_data(client: ApiClient, agent_address: String, tx: mpsc::Sender<AppMessage>) {
+    /// Fetch agent info, activity, balance and the first `posts_limit` posts,
+    /// superseding any page still in flight.
+    fn fetch_data(&mut self, client: ApiClient, agent_address: String, posts_limit: u32, tx: mpsc::Sender<AppMessage>) {
+        self.posts_generation += 1;
         let addr = agent_address.clone();
         let tx_clone = tx.clone();
         let client_clone = client.clone();
         
-        tokio::spawn(async move {
+        crate::tasks::spawn(async move {
             // Fetch agent info
             match client_clone.get_agent(&addr).await {
                 Ok(info) => {
-                    let _ = tx_clone.send(AppMessage::AgentInfoFetched { info }).await;
+                    let _ = tx_clone.send(ViewMessage::AgentInfoFetched { info }.into()).await;
                 }
                 Err(e) => {
-                    let _ = tx_clone.send(AppMessage::FetchFailed(format!("Agent info: {}", e))).await;
+                    let _ = tx_clone.send(ViewMessage::FetchFailed(format!("Agent info: {:#}", e)).into()).await;
+                }
+            }
+        });
+
+        let activity_client = client.clone();
+        let activity_addr = agent_address.clone();
+        let activity_tx = tx.clone();
+        crate::tasks::spawn(async move {
+            match activity_client.get_activity(&activity_addr, ACTIVITY_LIMIT).await {
+                Ok(resp) => {
+                    let _ = activity_tx.send(ViewMessage::ActivityFetched(resp.events).into()).await;
+                }
+                Err(e) => {
+                    let _ = activity_tx.send(ViewMessage::ActivityFetchFailed(format!("{:#}", e)).into()).await;
+                }
+            }
+        });
+
+        let stats_client = client.clone();
+        let stats_addr = agent_address.clone();
+        let stats_tx = tx.clone();
+        crate::tasks::spawn(async move {
+            // Best effort: without it the Stats tab works from the loaded posts
+            match stats_client.get_post_stats(&stats_addr, analytics::DAYS).await {
+                Ok(stats) => {
+                    let _ = stats_tx.send(ViewMessage::PostStatsFetched(stats).into()).await;
+                }
+                Err(e) => tracing::debug!("Post stats unavailable: {:#}", e),
+            }
+        });
+
+        Self::fetch_agent_balance(client.clone(), agent_address.clone(), tx.clone());
+        Self::fetch_posts_page(client, agent_address, self.posts_generation, 0, posts_limit, tx);
+    }
+
+    /// Quietly reload on the auto-refresh timer, keeping loaded posts and the selection.
+    pub fn auto_refresh(&mut self, client: &ApiClient, agent_address: &str, tx: mpsc::Sender<AppMessage>) {
+        if self.is_busy() {
+            return;
+        }
+        let posts_limit = (self.posts.len() as u32).max(POSTS_PAGE_SIZE);
+        self.fetch_data(client.clone(), agent_address.to_string(), posts_limit, tx);
+    }
+
+    /// Whether a reload now would disrupt something in progress.
+    fn is_busy(&self) -> bool {
+        self.loading
+            || self.loading_more
+            || self.detail_open
+            || self.top_up_confirm
+            || self.amount_confirm.is_some()
+            || self.topping_up
+            || self.toggling_active
+            || self.profile.is_some()
+            || self.retire.is_some()
+            || self.transfer.is_some()
+    }
+
+    /// Fetch the agent account's chain balance.
+    pub fn fetch_agent_balance(client: ApiClient, agent_address: String, tx: mpsc::Sender<AppMessage>) {
+        crate::tasks::spawn(async move {
+            // Best-effort like the wallet balance; the card shows "unknown" until it arrives
+            match client.get_balance(&agent_address).await {
+                Ok(balance) => {
+                    let _ = tx.send(ViewMessage::AgentBalanceFetched(balance).into()).await;
                 }
+                Err(e) => tracing::warn!("Agent balance fetch failed: {:#}", e),
             }
         });
+    }
+
+    /// Transfer `amount` planck from the user wallet to the agent account on
+    /// `network`, the one its spending is recorded under.
+    fn start_top_up(
+        &mut self,
+        client: ApiClient,
+        agent_address: String,
+        amount: u128,
+        network: NetworkProfile,
+        wallet: WalletConfig,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        self.topping_up = true;
+        crate::tasks::spawn_operation("a top-up", async move {
+            let result = async {
+                let keypair = wallet.keypair()?;
+                let build = client
+                    .build_transfer(&agent_address, amount, &wallet.public_key)
+                    .await?;
+                crate::extrinsic::sign_and_submit(&client, &build, &keypair).await
+            }
+            .await;
+            let msg = match result {
+                Ok(_) => ViewMessage::AgentTopUpDone { network, amount },
+                Err(e) => ViewMessage::AgentTopUpFailed(format!("{:#}", e)),
+            };
+            let _ = tx.send(msg.into()).await;
+        });
+    }
+
+    /// Pause or resume the agent's scheduled runs on-chain.
+    fn start_set_active(
+        &mut self,
+        client: ApiClient,
+        agent_address: String,
+        active: bool,
+        wallet: WalletConfig,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        self.toggling_active = true;
+        self.error = None;
+        self.status = None;
+        crate::tasks::spawn_operation("a pause or resume", async move {
+            let result = async {
+                let keypair = wallet.keypair()?;
+                let build = client
+                    .build_set_active(&agent_address, active, &wallet.public_key)
+                    .await?;
+                crate::extrinsic::sign_and_submit(&client, &build, &keypair).await
+            }
+            .await;
+            let msg = match result {
+                Ok(_) => ViewMessage::AgentActiveSet { active },
+                Err(e) => ViewMessage::AgentActiveFailed(format!("{:#}", e)),
+            };
+            let _ = tx.send(msg.into()).await;
+        });
+    }
+
+    pub fn handle_active_set(&mut self, active: bool) {
+        self.toggling_active = false;
+        if let Some(chain) = self.agent_info.as_mut().and_then(|i| i.chain_info.as_mut()) {
+            chain.active = active;
+        }
+        self.status = Some(if active { "Agent resumed" } else { "Agent paused" }.to_string());
+    }
+
+    pub fn handle_active_failed(&mut self, error: String) {
+        self.toggling_active = false;
+        self.error = Some(format!("Pause/resume failed: {}", error));
+    }
+
+    /// On-chain `active` flag, once agent info has loaded.
+    fn chain_active(&self) -> Option<bool> {
+        self.agent_info.as_ref()?.chain_info.as_ref().map(|c| c.active)
+    }
+
+    pub fn handle_ownership_transferred(&mut self, new_owner: String) {
+        self.transfer = None;
+        self.status = Some(format!("Ownership transferred to {}", new_owner));
+        if let Some(chain) = self.agent_info.as_mut().and_then(|i| i.chain_info.as_mut()) {
+            chain.owner = new_owner;
+        }
+    }
+
+    pub fn handle_transfer_failed(&mut self, error: String) {
+        match &mut self.transfer {
+            Some(form) => form.handle_failed(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    pub fn handle_retire_progress(&mut self, step: String) {
+        if let Some(form) = &mut self.retire {
+            form.handle_progress(step);
+        }
+    }
+
+    pub fn handle_retire_failed(&mut self, error: String) {
+        match &mut self.retire {
+            Some(form) => form.handle_failed(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    pub fn handle_profile_fetched(&mut self, profile: AgentProfile) {
+        if let Some(form) = &mut self.profile {
+            form.handle_profile(profile);
+        }
+    }
+
+    pub fn handle_profile_saved(&mut self, profile: AgentProfile) {
+        self.profile = None;
+        if let Some(info) = self.agent_info.as_mut().and_then(|i| i.moltbook_info.as_mut()) {
+            info.description = Some(profile.description);
+        }
+        self.status = Some("Profile updated".to_string());
+    }
+
+    pub fn handle_profile_failed(&mut self, error: String) {
+        match &mut self.profile {
+            Some(form) => form.handle_failed(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    pub fn handle_agent_balance(&mut self, balance: BalanceResponse) {
+        self.agent_balance = Some(balance);
+    }
+
+    pub fn handle_top_up_done(&mut self, network: &NetworkProfile, amount: u128) {
+        crate::spending::record(&network.name, amount);
+        self.topping_up = false;
+        self.error = None;
+        self.status = Some(format!("Sent {} {} to the agent", TOP_UP_TOKENS, network.token_symbol));
+    }
+
+    pub fn handle_top_up_failed(&mut self, error: String) {
+        self.topping_up = false;
+        self.error = Some(format!("Top-up failed: {}", error));
+    }
+
+    /// Whether the agent's balance is known and below `min_planck` (scheduled runs draw on it).
+    fn balance_is_low(&self, min_planck: u128) -> bool {
+        self.agent_balance
+            .as_ref()
+            .and_then(|b| b.balance.parse::<u128>().ok())
+            .is_some_and(|planck| planck < min_planck)
+    }
 
-        tokio::spawn(async move {
-            // Fetch posts
-            match client.get_posts(&agent_address).await {
+    /// Request the next page once the selection nears the end of the loaded posts.
+    fn maybe_fetch_more(&mut self, client: &ApiClient, agent_address: &str, tx: mpsc::Sender<AppMessage>) {
+        let near_end = self.selected.selected() + PREFETCH_THRESHOLD >= self.posts.len();
+        if near_end && self.has_more && !self.loading_more && !self.loading {
+            self.loading_more = true;
+            let offset = self.posts.len() as u32;
+            Self::fetch_posts_page(client.clone(), agent_address.to_string(), self.posts_generation, offset, POSTS_PAGE_SIZE, tx);
+        }
+    }
+
+    fn fetch_posts_page(
+        client: ApiClient,
+        agent_address: String,
+        generation: u64,
+        offset: u32,
+        limit: u32,
+        tx: mpsc::Sender<AppMessage>,
+    ) {
+        crate::tasks::spawn(async move {
+            match client.get_posts(&agent_address, offset, limit).await {
                 Ok(resp) => {
-                    let _ = tx.send(AppMessage::PostsFetched { posts: resp.posts }).await;
+                    // Older servers don't report has_more; a full page implies there may be more
+                    let has_more = resp
+                        .has_more
+                        .unwrap_or(resp.posts.len() as u32 >= limit);
+                    let _ = tx
+                        .send(ViewMessage::PostsFetched { posts: resp.posts, generation, offset, has_more }.into())
+                        .await;
                 }
                 Err(e) => {
-                    let _ = tx.send(AppMessage::FetchFailed(format!("Posts: {}", e))).await;
+                    let _ = tx.send(ViewMessage::FetchFailed(format!("Posts: {:#}", e)).into()).await;
                 }
             }
         });
@@ -103,17 +930,120 @@ impl ViewScreen {
 
     pub fn handle_agent_info(&mut self, info: AgentInfo) {
         self.agent_info = Some(info);
+        self.last_updated = Some(Instant::now());
         self.check_loading_done();
     }
 
-    pub fn handle_posts(&mut self, posts: Vec<MoltbookPost>) {
-        self.posts = posts;
+    pub fn handle_posts(&mut self, posts: Vec<MoltbookPost>, generation: u64, offset: u32, has_more: bool) {
+        // A reload replaced the list since this page was asked for
+        if generation != self.posts_generation || (offset != 0 && offset as usize != self.posts.len()) {
+            return;
+        }
+        if offset == 0 {
+            self.posts = posts;
+        } else {
+            self.posts.extend(posts);
+        }
+        self.has_more = has_more;
+        self.loading_more = false;
+        self.selected.clamp(self.posts.len());
         self.check_loading_done();
     }
 
+    /// Heartbeat schedule from the loaded chain info and activity.
+    fn heartbeat(&self) -> Option<Heartbeat> {
+        let chain = self.agent_info.as_ref()?.chain_info.as_ref()?;
+        Heartbeat::from_activity(chain, &self.activity)
+    }
+
+    pub fn handle_activity(&mut self, events: Vec<ActivityEvent>) {
+        self.activity = events;
+        self.activity_loading = false;
+        self.activity_selected.clamp(self.activity.len());
+    }
+
+    pub fn handle_activity_error(&mut self, error: String) {
+        self.activity_error = Some(error);
+        self.activity_loading = false;
+    }
+
     pub fn handle_fetch_error(&mut self, error: String) {
         self.error = Some(error);
         self.loading = false;
+        self.loading_more = false;
+    }
+
+    pub fn handle_message(&mut self, msg: ViewMessage, ctx: &mut MessageContext) -> Result<ScreenAction> {
+        match msg {
+            ViewMessage::AgentInfoFetched { info } => self.handle_agent_info(info),
+            ViewMessage::PostsFetched { posts, generation, offset, has_more } => {
+                self.handle_posts(posts, generation, offset, has_more)
+            }
+            ViewMessage::PostStatsFetched(stats) => self.post_stats = Some(stats),
+            ViewMessage::FetchFailed(e) => self.handle_fetch_error(e),
+            ViewMessage::AgentBalanceFetched(balance) => self.handle_agent_balance(balance),
+            ViewMessage::AgentTopUpDone { network, amount } => {
+                ctx.notify(Level::Success, "Agent account topped up");
+                self.handle_top_up_done(&network, amount);
+                if let Some(addr) = ctx.config.agent_address.clone().filter(|_| ctx.config.is_authenticated()) {
+                    Self::fetch_agent_balance(ctx.client.clone(), addr, ctx.tx.clone());
+                }
+                ctx.refresh_balance = true;
+            }
+            ViewMessage::AgentTopUpFailed(e) => {
+                ctx.notify(Level::Error, format!("Top-up failed: {}", e));
+                self.handle_top_up_failed(e);
+            }
+            ViewMessage::AgentActiveSet { active } => {
+                let state = if active { "resumed" } else { "paused" };
+                ctx.notify(Level::Success, format!("Agent {}", state));
+                self.handle_active_set(active);
+            }
+            ViewMessage::AgentActiveFailed(e) => {
+                ctx.notify(Level::Error, format!("Pause/resume failed: {}", e));
+                self.handle_active_failed(e);
+            }
+            ViewMessage::ProfileFetched(profile) => self.handle_profile_fetched(profile),
+            ViewMessage::ProfileSaved(profile) => {
+                ctx.notify(Level::Success, "Moltbook profile saved");
+                self.handle_profile_saved(profile);
+            }
+            ViewMessage::ProfileFailed(e) => self.handle_profile_failed(e),
+            ViewMessage::OwnershipTransferred { new_owner } => {
+                ctx.notify(Level::Success, format!("Ownership transferred to {}", new_owner));
+                self.handle_ownership_transferred(new_owner);
+            }
+            ViewMessage::OwnershipTransferFailed(e) => self.handle_transfer_failed(e),
+            ViewMessage::RetireProgress(step) => self.handle_retire_progress(step),
+            ViewMessage::AgentRetired => {
+                let name = ctx.config.agent_name.clone().unwrap_or_default();
+                ctx.config.clear_agent();
+                ctx.config.save()?;
+                self.reset();
+                ctx.notifications.push(Level::Success, format!("Agent {} retired", name));
+                ctx.refresh_balance = true;
+                return Ok(ScreenAction::GoHome);
+            }
+            ViewMessage::RetireFailed(e) => self.handle_retire_failed(e),
+            ViewMessage::ActivityFetched(events) => self.handle_activity(events),
+            ViewMessage::ActivityFetchFailed(e) => self.handle_activity_error(e),
+            ViewMessage::PostActionDone { post_id, action } => {
+                self.handle_post_action_done(action);
+                // Reload the thread so the new comment shows in it
+                if let Some(thread) = self.thread.as_mut().filter(|t| action == PostAction::Comment && t.post_id == post_id) {
+                    thread.fetch(ctx.client, ctx.config.moltbook_api_key.as_deref(), ctx.tx.clone(), |m| ViewMessage::Thread(m).into());
+                }
+            }
+            ViewMessage::Thread(msg) => {
+                if let Some(thread) = &mut self.thread {
+                    thread.handle_message(msg);
+                }
+            }
+            ViewMessage::PostActionFailed { post_id, action, previous, error } => {
+                self.handle_post_action_failed(&post_id, action, previous, error);
+            }
+        }
+        Ok(ScreenAction::None)
     }
 
     fn check_loading_done(&mut self) {
@@ -130,155 +1060,908 @@ impl ViewScreen {
 
 impl Screen for ViewScreen {
     fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = &app.theme;
+        let network = app.config.network();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
             .constraints([
                 Constraint::Length(3),   // Title bar
-                Constraint::Length(5),   // Agent info
+                Constraint::Length(6),   // Agent info
                 Constraint::Min(8),      // Posts
-                Constraint::Length(2),   // Footer
+                Constraint::Length(if self.amount_confirm.is_some() { 3 } else { 2 }), // Footer
             ])
             .split(area);
 
         // Title bar
-        let title_line = Line::from(vec![
-            Span::styled(" AGENT DETAILS ", Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
-            Span::styled("│ ", Style::default().fg(Color::DarkGray)),
+        let mut title_line = Line::from(vec![
+            network_badge(network.kind, theme),
+            Span::styled(" AGENT DETAILS ", Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+            Span::styled("│ ", Style::default().fg(theme.muted)),
             Span::styled(
                 if self.loading { "Loading..." } else { "Ready" },
-                Style::default().fg(if self.loading { Color::Yellow } else { Color::Green }),
+                Style::default().fg(if self.loading { theme.warning } else { theme.success }),
             ),
         ]);
+        if let Some(updated) = self.last_updated.filter(|_| !self.loading) {
+            let ago = format_age(updated.elapsed().as_secs());
+            title_line.push_span(Span::styled(format!(" • updated {}", ago), Style::default().fg(theme.muted)));
+        }
 
         let title = Paragraph::new(title_line)
             .alignment(Alignment::Center)
             .block(Block::default()
                 .borders(Borders::BOTTOM)
-                .border_style(Style::default().fg(Color::DarkGray)));
+                .border_style(Style::default().fg(theme.muted)));
         frame.render_widget(title, chunks[0]);
 
         // Agent info card (only show if authenticated)
         let mut info_lines = vec![];
 
         if let Some(name) = app.agent_name() {
+            // Label column is 10 wide, plus borders
+            let name_width = chunks[1].width.saturating_sub(12) as usize;
             info_lines.push(Line::from(vec![
-                Span::styled("  Name    ", Style::default().fg(Color::DarkGray)),
-                Span::styled(name, Style::default().fg(Color::White)),
+                Span::styled("  Name    ", Style::default().fg(theme.muted)),
+                Span::styled(truncate_width(name, name_width), Style::default().fg(theme.text)),
             ]));
         }
 
         if let Some(addr) = app.agent_address() {
-            let short = if addr.len() > 40 {
-                format!("{}...{}", &addr[..16], &addr[addr.len() - 12..])
-            } else {
-                addr.to_string()
-            };
+            let short = shorten_middle(addr, 16, 12);
             info_lines.push(Line::from(vec![
-                Span::styled("  Address ", Style::default().fg(Color::DarkGray)),
-                Span::styled(short, Style::default().fg(Color::Cyan)),
+                Span::styled("  Address ", Style::default().fg(theme.muted)),
+                Span::styled(short, Style::default().fg(theme.info)),
             ]));
         }
 
-        info_lines.push(Line::from(vec![
-            Span::styled("  Status  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("● Active", Style::default().fg(Color::Green)),
-        ]));
+        let (status_text, status_color) = match self.chain_active() {
+            _ if self.toggling_active => (format!("{} Updating...", app.spinner()), theme.warning),
+            Some(true) => ("● Active".to_string(), theme.success),
+            Some(false) => ("⏸ Paused".to_string(), theme.warning),
+            None if self.loading => ("Loading...".to_string(), theme.muted),
+            None => ("○ Not on chain".to_string(), theme.muted),
+        };
+        let mut status_line = vec![
+            Span::styled("  Status  ", Style::default().fg(theme.muted)),
+            Span::styled(status_text, Style::default().fg(status_color)),
+        ];
+        if let Some(heartbeat) = self.heartbeat().filter(|h| !h.paused) {
+            status_line.push(Span::raw("  "));
+            status_line.push(heartbeat_span(heartbeat.status(app.block_number), theme));
+        }
+        info_lines.push(Line::from(status_line));
+
+        let mut balance_line = vec![Span::styled("  Balance ", Style::default().fg(theme.muted))];
+        let usd = self
+            .agent_balance
+            .as_ref()
+            .and_then(|b| app.usd_value(&b.balance_formatted))
+            .map(|usd| Span::styled(format!(" ({})", usd), Style::default().fg(theme.muted)));
+        match &self.agent_balance {
+            Some(balance) if self.balance_is_low(app.config.min_balance_planck(&network)) => {
+                balance_line.push(Span::styled(
+                    format!("{} {}", balance.balance_formatted, network.token_symbol),
+                    Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+                ));
+                balance_line.extend(usd);
+                balance_line.push(Span::styled(
+                    "  ⚠ low - scheduled runs may stop, [t] to top up",
+                    Style::default().fg(theme.error),
+                ));
+            }
+            Some(balance) => {
+                balance_line.push(Span::styled(
+                    format!("{} {}", balance.balance_formatted, network.token_symbol),
+                    Style::default().fg(theme.warning),
+                ));
+                balance_line.extend(usd);
+            }
+            None => {
+                balance_line.push(Span::styled("unknown", Style::default().fg(theme.muted)));
+            }
+        }
+        if self.topping_up {
+            balance_line.push(Span::styled(
+                format!("  {} topping up...", app.spinner()),
+                Style::default().fg(theme.warning),
+            ));
+        }
+        info_lines.push(Line::from(balance_line));
 
         let info = Paragraph::new(info_lines)
             .block(Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(Span::styled(" Agent ", Style::default().fg(Color::White))));
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(" Agent ", Style::default().fg(theme.text))));
         frame.render_widget(info, chunks[1]);
 
-        // Posts section
+        // Posts / activity section
+        if let Some(form) = &self.transfer {
+            let owner = self.agent_info.as_ref().and_then(|i| i.chain_info.as_ref()).map(|c| c.owner.as_str());
+            form.render(frame, chunks[2], owner, app.spinner(), theme);
+        } else if let Some(form) = &self.retire {
+            form.render(frame, chunks[2], app.spinner(), theme);
+        } else if let Some(form) = &self.profile {
+            form.render(frame, chunks[2], app.spinner(), theme);
+        } else if self.detail_open && !self.posts.is_empty() {
+            self.render_detail(frame, chunks[2], app.spinner(), theme);
+        } else {
+            let section = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(4)])
+                .split(chunks[2]);
+            let health = self.health(app);
+            let health_tab = Line::from(vec![
+                Span::raw(" Health "),
+                Span::styled(format!("{} ", badge_mark(health.overall(), theme)), Style::default().fg(badge_color(health.overall(), theme))),
+            ]);
+            let tabs = Tabs::new(vec![Line::from(" Posts "), Line::from(" Activity "), health_tab, Line::from(" Stats "), Line::from(" Versions ")])
+                .select(self.tab.index())
+                .style(Style::default().fg(theme.muted))
+                .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+                .divider("│");
+            frame.render_widget(tabs, section[0]);
+
+            match self.tab {
+                ViewTab::Posts => self.render_posts(frame, section[1], app.spinner(), theme),
+                ViewTab::Activity => self.render_activity(frame, section[1], app.spinner(), theme),
+                ViewTab::Health => self.render_health(frame, section[1], &health, app),
+                ViewTab::Stats => self.render_stats(frame, section[1], app.spinner(), theme),
+                ViewTab::Versions => self.render_versions(frame, section[1], theme),
+            }
+        }
+
+        // Footer
+        let footer_content = if self.top_up_confirm {
+            Line::from(vec![
+                Span::styled(
+                    match app.usd_value(&TOP_UP_TOKENS.to_string()) {
+                        Some(usd) => format!(
+                            "Send {} {} ({}) from your wallet to the agent? ",
+                            TOP_UP_TOKENS, network.token_symbol, usd
+                        ),
+                        None => format!("Send {} {} from your wallet to the agent? ", TOP_UP_TOKENS, network.token_symbol),
+                    },
+                    Style::default().fg(theme.warning),
+                ),
+                Span::styled("[y] ", Style::default().fg(theme.muted)),
+                Span::styled("Confirm", Style::default().fg(theme.muted)),
+                Span::styled("  [any key] ", Style::default().fg(theme.muted)),
+                Span::styled("Cancel", Style::default().fg(theme.muted)),
+            ])
+        } else if let Some(err) = self
+            .transfer
+            .as_ref()
+            .and_then(|f| f.error.as_ref())
+            .or(self.retire.as_ref().and_then(|f| f.error.as_ref()))
+            .or(self.profile.as_ref().and_then(|f| f.error.as_ref()))
+            .or(self.error.as_ref())
+        {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.err_mark()), Style::default().fg(theme.error)),
+                Span::styled(err.as_str(), Style::default().fg(theme.error)),
+            ])
+        } else if let Some(status) = &self.status {
+            Line::from(vec![
+                Span::styled(format!(" {} ", theme.ok_mark()), Style::default().fg(theme.success)),
+                Span::styled(status.as_str(), Style::default().fg(theme.success)),
+            ])
+        } else if let Some(form) = &self.transfer {
+            let (key, action) = if form.step == TransferStep::EnterAddress {
+                ("[Enter] ", "Review")
+            } else {
+                ("[y] ", "Transfer  [any key] Edit")
+            };
+            Line::from(vec![
+                Span::styled(key, Style::default().fg(theme.muted)),
+                Span::styled(action, Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Cancel", Style::default().fg(theme.muted)),
+            ])
+        } else if self.retire.is_some() {
+            Line::from(vec![
+                Span::styled("[Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Retire", Style::default().fg(theme.muted)),
+                Span::styled("  [Tab] ", Style::default().fg(theme.muted)),
+                Span::styled("Toggle sweep", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Cancel", Style::default().fg(theme.muted)),
+            ])
+        } else if self.profile.is_some() {
+            Line::from(vec![
+                Span::styled("[Tab] ", Style::default().fg(theme.muted)),
+                Span::styled("Next field", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Save", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Cancel", Style::default().fg(theme.muted)),
+            ])
+        } else if self.comment_input.is_some() {
+            Line::from(vec![
+                Span::styled("[Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Post comment", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Cancel", Style::default().fg(theme.muted)),
+            ])
+        } else if self.detail_open {
+            if app.config.moltbook_api_key.is_some() {
+                Line::from(vec![
+                    Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                    Span::styled("Comments", Style::default().fg(theme.muted)),
+                    Span::styled("  [Space] ", Style::default().fg(theme.muted)),
+                    Span::styled("Fold", Style::default().fg(theme.muted)),
+                    Span::styled("  [u] ", Style::default().fg(theme.muted)),
+                    Span::styled("Upvote", Style::default().fg(theme.muted)),
+                    Span::styled("  [d] ", Style::default().fg(theme.muted)),
+                    Span::styled("Downvote", Style::default().fg(theme.muted)),
+                    Span::styled("  [c] ", Style::default().fg(theme.muted)),
+                    Span::styled("Comment", Style::default().fg(theme.muted)),
+                    Span::styled("  [o] ", Style::default().fg(theme.muted)),
+                    Span::styled("Open in browser", Style::default().fg(theme.muted)),
+                    Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                    Span::styled("Back", Style::default().fg(theme.muted)),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::styled("No Moltbook API key stored - read only", Style::default().fg(theme.muted)),
+                    Span::styled("  [j/k] ", Style::default().fg(theme.muted)),
+                    Span::styled("Comments", Style::default().fg(theme.muted)),
+                    Span::styled("  [Space] ", Style::default().fg(theme.muted)),
+                    Span::styled("Fold", Style::default().fg(theme.muted)),
+                    Span::styled("  [o] ", Style::default().fg(theme.muted)),
+                    Span::styled("Open in browser", Style::default().fg(theme.muted)),
+                    Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                    Span::styled("Back", Style::default().fg(theme.muted)),
+                ])
+            }
+        } else {
+            Line::from(vec![
+                Span::styled("[j/k] ", Style::default().fg(theme.muted)),
+                Span::styled("Navigate", Style::default().fg(theme.muted)),
+                Span::styled("  [Enter] ", Style::default().fg(theme.muted)),
+                Span::styled("Open", Style::default().fg(theme.muted)),
+                Span::styled("  [Tab] ", Style::default().fg(theme.muted)),
+                Span::styled("Switch tab", Style::default().fg(theme.muted)),
+                Span::styled("  [o] ", Style::default().fg(theme.muted)),
+                Span::styled(
+                    if self.tab == ViewTab::Activity { "Explorer" } else { "Open in browser" },
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled("  [E] ", Style::default().fg(theme.muted)),
+                Span::styled("Edit profile", Style::default().fg(theme.muted)),
+                Span::styled("  [P] ", Style::default().fg(theme.muted)),
+                Span::styled(
+                    if self.chain_active() == Some(false) { "Resume" } else { "Pause" },
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled("  [T] ", Style::default().fg(theme.muted)),
+                Span::styled("Top up", Style::default().fg(theme.muted)),
+                Span::styled("  [W] ", Style::default().fg(theme.muted)),
+                Span::styled("Report", Style::default().fg(theme.muted)),
+                Span::styled("  [I] ", Style::default().fg(theme.muted)),
+                Span::styled("State", Style::default().fg(theme.muted)),
+                Span::styled("  [Shift+O] ", Style::default().fg(theme.muted)),
+                Span::styled("Transfer", Style::default().fg(theme.muted)),
+                Span::styled("  [Shift+X] ", Style::default().fg(theme.muted)),
+                Span::styled("Retire", Style::default().fg(theme.muted)),
+                Span::styled("  [R] ", Style::default().fg(theme.muted)),
+                Span::styled("Refresh", Style::default().fg(theme.muted)),
+                Span::styled("  [Esc] ", Style::default().fg(theme.muted)),
+                Span::styled("Back", Style::default().fg(theme.muted)),
+            ])
+        };
+
+        let footer = match &self.amount_confirm {
+            Some(confirm) => Paragraph::new(confirm.lines(theme, network.decimals, &network.token_symbol)),
+            None => Paragraph::new(footer_content),
+        };
+        frame.render_widget(footer.alignment(Alignment::Center), chunks[3]);
+    }
+}
+
+impl ViewScreen {
+    /// List of the agent's posts.
+    fn render_posts(&self, frame: &mut Frame, area: Rect, spinner: &str, theme: &Theme) {
         if self.loading {
             let loading = Paragraph::new(vec![
                 Line::from(""),
-                Line::from(Span::styled("⏳ Loading posts...", Style::default().fg(Color::Yellow))),
+                Line::from(Span::styled(format!("{} Loading posts...", spinner), Style::default().fg(theme.warning))),
             ])
             .alignment(Alignment::Center)
             .block(Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(Span::styled(" Recent Posts ", Style::default().fg(Color::White))));
-            frame.render_widget(loading, chunks[2]);
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(" Recent Posts ", Style::default().fg(theme.text))));
+            frame.render_widget(loading, area);
         } else if self.posts.is_empty() {
             let empty = Paragraph::new(vec![
                 Line::from(""),
-                Line::from(Span::styled("No posts yet", Style::default().fg(Color::DarkGray))),
+                Line::from(Span::styled("No posts yet", Style::default().fg(theme.muted))),
                 Line::from(""),
                 Line::from(vec![
-                    Span::styled("Press ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("[R]", Style::default().fg(Color::White)),
-                    Span::styled(" to refresh", Style::default().fg(Color::DarkGray)),
+                    Span::styled("Press ", Style::default().fg(theme.muted)),
+                    Span::styled("[R]", Style::default().fg(theme.text)),
+                    Span::styled(" to refresh", Style::default().fg(theme.muted)),
                 ]),
             ])
             .alignment(Alignment::Center)
             .block(Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(Span::styled(" Recent Posts ", Style::default().fg(Color::White))));
-            frame.render_widget(empty, chunks[2]);
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(" Recent Posts ", Style::default().fg(theme.text))));
+            frame.render_widget(empty, area);
         } else {
             let items: Vec<ListItem> = self
                 .posts
                 .iter()
-                .take(5)  // Limit displayed posts
                 .map(|p| {
                     let submolt = p.submolt.as_ref().map(|s| s.name.as_str()).unwrap_or("general");
                     // Use title if available, otherwise content
                     let text = p.title.as_deref()
                         .or(p.content.as_deref())
                         .unwrap_or("");
-                    let preview = if text.len() > 70 {
-                        format!("{}...", &text[..70])
-                    } else {
-                        text.to_string()
-                    };
+                    let preview = truncate_width(text, 73);
                     let votes = format!("↑{}", p.upvotes);
                     ListItem::new(vec![
                         Line::from(vec![
-                            Span::styled(format!("  m/{} ", submolt), Style::default().fg(Color::LightRed)),
-                            Span::styled("• ", Style::default().fg(Color::DarkGray)),
-                            Span::styled(votes, Style::default().fg(Color::Green)),
-                            Span::styled(" • ", Style::default().fg(Color::DarkGray)),
-                            Span::styled(&p.created_at, Style::default().fg(Color::DarkGray)),
+                            Span::styled(format!("  m/{} ", submolt), Style::default().fg(theme.accent)),
+                            Span::styled("• ", Style::default().fg(theme.muted)),
+                            Span::styled(votes, Style::default().fg(theme.success)),
+                            Span::styled(" • ", Style::default().fg(theme.muted)),
+                            Span::styled(&p.created_at, Style::default().fg(theme.muted)),
                         ]),
-                        Line::from(Span::styled(format!("  {}", preview), Style::default().fg(Color::White))),
+                        Line::from(Span::styled(format!("  {}", preview), Style::default().fg(theme.text))),
                         Line::from(""),
                     ])
                 })
                 .collect();
 
+            let count = if self.has_more {
+                format!("{}/{}+", self.selected.selected() + 1, self.posts.len())
+            } else {
+                format!("{}/{}", self.selected.selected() + 1, self.posts.len())
+            };
+            let title = if self.loading_more {
+                format!(" Recent Posts ({}) loading more... ", count)
+            } else {
+                format!(" Recent Posts ({}) ", count)
+            };
+
             let list = List::new(items)
                 .block(Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::DarkGray))
-                    .title(Span::styled(
-                        format!(" Recent Posts ({}) ", self.posts.len()),
-                        Style::default().fg(Color::White),
-                    )));
-            frame.render_widget(list, chunks[2]);
+                    .border_style(Style::default().fg(theme.muted))
+                    .title(Span::styled(title, Style::default().fg(theme.text))))
+                .highlight_symbol("▌")
+                .highlight_style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
+
+            // The list computes its own scroll offset to keep the selection visible
+            let mut state = self.selected.state();
+            frame.render_stateful_widget(list, area, &mut state);
         }
+    }
 
-        // Footer
-        let footer_content = if let Some(err) = &self.error {
-            Line::from(vec![
-                Span::styled(" ✗ ", Style::default().fg(Color::Red)),
-                Span::styled(err.as_str(), Style::default().fg(Color::Red)),
+    /// Recent on-chain events with block numbers and timestamps.
+    /// Health figures from the loaded activity, balance and Moltbook info.
+    fn health(&self, app: &App) -> Health {
+        let balance = self.agent_balance.as_ref().and_then(|b| b.balance.parse().ok());
+        let min = app.config.min_balance_planck(&app.config.network());
+        let moltbook = self.agent_info.as_ref().and_then(|i| i.moltbook_info.as_ref());
+        Health::new(&self.activity, balance, min, moltbook)
+    }
+
+    fn render_health(&self, frame: &mut Frame, area: Rect, health: &Health, app: &App) {
+        let theme = &app.theme;
+        let network = app.config.network();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(
+                format!(" Health (last {} events) ", self.activity.len()),
+                Style::default().fg(theme.text),
+            ));
+        if self.activity_loading || self.loading {
+            let loading = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("{} Loading...", app.spinner()), Style::default().fg(theme.warning))),
             ])
-        } else {
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(loading, area);
+            return;
+        }
+
+        let tokens = |planck: u128| format!("{} {}", format_tokens(planck, network.decimals), network.token_symbol);
+        let last_success = match health.last_success {
+            Some(block) => format!("block #{}", block),
+            None => "none in the loaded activity".to_string(),
+        };
+        let failures = match health.consecutive_failures {
+            0 => "none".to_string(),
+            n => format!("{} in a row", n),
+        };
+        let duration = match health.average_blocks {
+            Some(blocks) => format!("~{} blocks over {} runs", blocks, health.timed_runs),
+            None => "no finished runs yet".to_string(),
+        };
+        let runway = match (health.runway, health.average_cost) {
+            (Some(runs), Some(cost)) => format!("~{} runs at {} each, above min_balance", runs, tokens(cost)),
+            (None, Some(_)) => "agent balance unknown".to_string(),
+            _ => "no run costs reported yet".to_string(),
+        };
+        let claim = match health.claimed {
+            Some(true) => "claimed",
+            Some(false) => "not claimed yet - the owner must verify it on Moltbook",
+            None => "not registered on Moltbook",
+        };
+        let rows = [
+            ("Last successful run", health.last_success_badge(), last_success),
+            ("Consecutive failures", health.failures_badge(), failures),
+            ("Average run duration", Badge::Unknown, duration),
+            ("Balance runway", health.runway_badge(), runway),
+            ("Moltbook claim", health.claim_badge(), claim.to_string()),
+        ];
+        let mut lines = vec![Line::from("")];
+        for (label, badge, value) in rows {
+            let mut spans = vec![Span::styled(format!("  {:<22}", label), Style::default().fg(theme.muted))];
+            // Values line up whatever the mark's width
+            let mark_width = if theme.labels { 7 } else { 2 };
+            spans.push(Span::styled(
+                format!("{:<width$}", badge_mark(badge, theme), width = mark_width),
+                Style::default().fg(badge_color(badge, theme)),
+            ));
+            spans.push(Span::styled(value, Style::default().fg(theme.text)));
+            lines.push(Line::from(spans));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    /// Posting rhythm and engagement: totals, a sparkline of posts per day
+    /// and the best submolts by average upvotes.
+    fn render_stats(&self, frame: &mut Frame, area: Rect, spinner: &str, theme: &Theme) {
+        let analytics = Analytics::new(&self.posts, self.post_stats.as_ref());
+        let source = if analytics.from_stats { "all posts".to_string() } else { format!("{} loaded posts", analytics.sampled) };
+        let block = |title: String| {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(title, Style::default().fg(theme.text)))
+        };
+        if self.loading {
+            let loading = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("{} Loading...", spinner), Style::default().fg(theme.warning))),
+            ])
+            .alignment(Alignment::Center)
+            .block(block(" Stats ".to_string()));
+            frame.render_widget(loading, area);
+            return;
+        }
+        if analytics.posts == 0 {
+            let empty = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled("No posts to measure yet", Style::default().fg(theme.muted))),
+            ])
+            .alignment(Alignment::Center)
+            .block(block(" Stats ".to_string()));
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(4), Constraint::Length(5), Constraint::Min(5)])
+            .split(area);
+
+        let figure = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{:.1}", v));
+        let label = |text: &'static str| Span::styled(text, Style::default().fg(theme.muted));
+        let value = |text: String| Span::styled(text, Style::default().fg(theme.text).add_modifier(Modifier::BOLD));
+        let summary = Paragraph::new(vec![
             Line::from(vec![
-                Span::styled("[R] ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Refresh", Style::default().fg(Color::DarkGray)),
-                Span::styled("  [Esc] ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Back", Style::default().fg(Color::DarkGray)),
+                label("  Posts "),
+                value(analytics.posts.to_string()),
+                label("   Per day "),
+                value(figure(analytics.posts_per_day())),
+                label("   Avg upvotes "),
+                value(figure(analytics.average_upvotes)),
+                label("   Avg comments "),
+                value(figure(analytics.average_comments)),
+            ]),
+            Line::from(Span::styled(
+                match &analytics.span {
+                    Some((first, last)) => format!("  Per day from {} to {}", first, last),
+                    None => "  No posting history".to_string(),
+                },
+                Style::default().fg(theme.muted),
+            )),
+        ])
+        .block(block(format!(" Stats ({}) ", source)));
+        frame.render_widget(summary, chunks[0]);
+
+        let sparkline = Sparkline::default()
+            .data(&analytics.per_day)
+            .style(Style::default().fg(theme.accent))
+            .block(block(format!(" Posts per day (max {}) ", analytics.per_day.iter().max().unwrap_or(&0))));
+        frame.render_widget(sparkline, chunks[1]);
+
+        // Tenths of an upvote, so averages below 1 still show a bar
+        let bars: Vec<Bar> = analytics
+            .submolts
+            .iter()
+            .map(|s| {
+                Bar::default()
+                    .value((s.average_upvotes * 10.0).round() as u64)
+                    .text_value(format!("{:.1}", s.average_upvotes))
+                    .label(Line::from(format!("m/{} ({})", truncate_width(&s.name, 14), s.posts)))
+            })
+            .collect();
+        let chart = BarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(0)
+            .bar_style(Style::default().fg(theme.success))
+            .value_style(Style::default().fg(theme.text))
+            .label_style(Style::default().fg(theme.info))
+            .block(block(format!(" Best submolts by avg upvotes (from {} loaded posts) ", analytics.sampled)));
+        frame.render_widget(chart, chunks[2]);
+    }
+
+    /// Timeline of the agent's code: the chain's version counter, then the
+    /// deploys recorded here, newest first, with a gap for versions that
+    /// weren't deployed from this machine.
+    fn render_versions(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chain_version = self.agent_info.as_ref().and_then(|i| i.chain_info.as_ref()).map(|c| c.version);
+        let mut lines = vec![Line::from(vec![
+            Span::styled("  Chain version  ", Style::default().fg(theme.muted)),
+            Span::styled(
+                chain_version.map_or("unknown".to_string(), |v| format!("v{}", v)),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+        ])];
+        lines.push(Line::from(""));
+
+        if let Some((first, last)) = chain_version.and_then(|v| versions::unrecorded(&self.versions, v)) {
+            let range = if first == last { format!("v{}", last) } else { format!("v{} to v{}", first, last) };
+            lines.push(Line::from(Span::styled(
+                format!("  ◇ {}: deployed from elsewhere, no local record", range),
+                Style::default().fg(theme.warning),
+            )));
+        }
+        for deployed in self.versions.iter().rev() {
+            let version = deployed.version.map_or("v?".to_string(), |v| format!("v{}", v));
+            let current = deployed.version.is_some() && deployed.version == chain_version;
+            lines.push(Line::from(vec![
+                Span::styled(if current { "  ● " } else { "  ○ " }, Style::default().fg(theme.accent)),
+                Span::styled(format!("{:<4}", version), Style::default().fg(theme.text).add_modifier(Modifier::BOLD)),
+                Span::styled(format!(" block #{}", deployed.block_number), Style::default().fg(theme.text)),
+                Span::styled(
+                    format!("  {}", crate::schedule::When::Time(deployed.deployed_at).label()),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled(format!("  code {}", truncate_width(&deployed.code_hash, 18)), Style::default().fg(theme.info)),
+            ]));
+            let source = match &deployed.snapshot {
+                Some(dir) => format!("      from {}, snapshot in {}", deployed.source, dir.display()),
+                None => format!("      from {}, no snapshot", deployed.source),
+            };
+            lines.push(Line::from(Span::styled(source, Style::default().fg(theme.muted))));
+        }
+        if self.versions.is_empty() {
+            lines.push(Line::from(Span::styled("  No deploys of this agent recorded on this machine", Style::default().fg(theme.muted))));
+        }
+
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(" Versions ", Style::default().fg(theme.text))),
+        );
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_activity(&self, frame: &mut Frame, area: Rect, spinner: &str, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(
+                format!(" On-chain Activity ({}) ", self.activity.len()),
+                Style::default().fg(theme.text),
+            ));
+
+        if self.activity_loading {
+            let loading = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("{} Loading activity...", spinner), Style::default().fg(theme.warning))),
             ])
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(loading, area);
+            return;
+        }
+        if self.activity.is_empty() {
+            let message = match &self.activity_error {
+                Some(err) => Span::styled(format!("{} {}", theme.err_mark(), err), Style::default().fg(theme.error)),
+                None => Span::styled("No on-chain activity yet", Style::default().fg(theme.muted)),
+            };
+            let empty = Paragraph::new(vec![Line::from(""), Line::from(message)])
+                .alignment(Alignment::Center)
+                .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .activity
+            .iter()
+            .map(|event| {
+                let (icon, label, color) = match event.kind {
+                    ActivityKind::RunStarted => ("▶", "Run started", theme.info),
+                    ActivityKind::RunCompleted => ("✓", "Run completed", theme.success),
+                    ActivityKind::RunFailed => ("✗", "Run failed", theme.error),
+                    ActivityKind::HeartbeatScheduled => ("♥", "Heartbeat scheduled", theme.highlight),
+                    ActivityKind::Other => ("•", "Event", theme.secondary),
+                };
+                let mut spans = vec![
+                    Span::styled(format!("  #{:<9} ", event.block_number), Style::default().fg(theme.muted)),
+                    Span::styled(
+                        format!("{:<20} ", event.timestamp.as_deref().unwrap_or("-")),
+                        Style::default().fg(theme.muted),
+                    ),
+                    Span::styled(format!("{} {}", icon, label), Style::default().fg(color)),
+                ];
+                if let Some(run_id) = event.run_id {
+                    spans.push(Span::styled(format!(" (run {})", run_id), Style::default().fg(theme.muted)));
+                }
+                if let Some(detail) = &event.detail {
+                    spans.push(Span::styled(
+                        format!("  {}", truncate_width(detail, 60)),
+                        Style::default().fg(theme.text),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol("▌")
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+        let mut state = self.activity_selected.state();
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// Full view of the selected post and its comments, with the comment
+    /// composer when open.
+    fn render_detail(&self, frame: &mut Frame, area: Rect, spinner: &str, theme: &Theme) {
+        let Some(post) = self.posts.get(self.selected.selected()) else {
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(if self.comment_input.is_some() {
+                vec![Constraint::Min(5), Constraint::Min(6), Constraint::Length(3)]
+            } else {
+                vec![Constraint::Min(5), Constraint::Min(6)]
+            })
+            .split(area);
+
+        let submolt = post.submolt.as_ref().map(|s| s.name.as_str()).unwrap_or("general");
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(format!("m/{} ", submolt), Style::default().fg(theme.accent)),
+                Span::styled("• ", Style::default().fg(theme.muted)),
+                Span::styled(format!("↑{}", post.upvotes), Style::default().fg(theme.success)),
+                Span::styled(format!(" ↓{}", post.downvotes), Style::default().fg(theme.error)),
+                Span::styled(" • ", Style::default().fg(theme.muted)),
+                Span::styled(format!("{} comments", post.comment_count), Style::default().fg(theme.info)),
+                Span::styled(" • ", Style::default().fg(theme.muted)),
+                Span::styled(&post.created_at, Style::default().fg(theme.muted)),
+            ]),
+            Line::from(""),
+        ];
+        if let Some(title) = &post.title {
+            lines.push(Line::from(Span::styled(
+                title.as_str(),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(""));
+        }
+        if let Some(content) = &post.content {
+            lines.extend(content.lines().map(|l| Line::from(Span::styled(l, Style::default().fg(theme.text)))));
+        }
+
+        let detail = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.muted))
+                .title(Span::styled(" Post ", Style::default().fg(theme.text))));
+        frame.render_widget(detail, chunks[0]);
+
+        if let Some(thread) = &self.thread {
+            thread.render(frame, chunks[1], "Comments", spinner, theme);
+        }
+
+        if let Some(input) = &self.comment_input {
+            let width = chunks[2].width.saturating_sub(2) as usize;
+            let composer = Paragraph::new(input.line(true, width, Style::default().fg(theme.text)))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent))
+                .title(Span::styled(" Comment ", Style::default().fg(theme.text))));
+            frame.render_widget(composer, chunks[2]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{assert_snapshot, screen_text, signed_in_app, AGENT_ADDRESS, WALLET_ADDRESS};
+
+    fn screen_with_post() -> ViewScreen {
+        let mut screen = ViewScreen::new();
+        screen.posts = serde_json::from_value(serde_json::json!([{
+            "id": "post-1", "created_at": "2026-01-01T12:00:00Z", "upvotes": 5, "downvotes": 2,
+        }])).unwrap();
+        screen
+    }
+
+    fn counts(screen: &ViewScreen) -> (u32, u32) {
+        (screen.posts[0].upvotes, screen.posts[0].downvotes)
+    }
+
+    #[test]
+    fn test_switching_vote_moves_it() {
+        let mut screen = screen_with_post();
+        assert_eq!(screen.apply_post_action(PostAction::Upvote), Some(("post-1".to_string(), None)));
+        assert_eq!(counts(&screen), (6, 2));
+        assert_eq!(screen.apply_post_action(PostAction::Upvote), None);
+        assert_eq!(counts(&screen), (6, 2));
+
+        let switched = screen.apply_post_action(PostAction::Downvote);
+        assert_eq!(switched, Some(("post-1".to_string(), Some(PostAction::Upvote))));
+        assert_eq!(counts(&screen), (5, 3));
+        assert_eq!(screen.votes.get("post-1"), Some(&PostAction::Downvote));
+    }
+
+    #[test]
+    fn test_stale_page_ignored_after_reload() {
+        let mut screen = screen_with_post();
+        screen.loading_more = true;
+        let stale = screen.posts_generation;
+        screen.posts_generation += 1;
+        let page: Vec<MoltbookPost> = serde_json::from_value(serde_json::json!([{
+            "id": "post-2", "created_at": "2026-01-01T12:00:00Z",
+        }]))
+        .unwrap();
+
+        screen.handle_posts(page.clone(), stale, 1, false);
+        assert_eq!(screen.posts.len(), 1);
+        screen.handle_posts(page.clone(), screen.posts_generation, 1, false);
+        assert_eq!(screen.posts.len(), 2);
+        assert!(!screen.loading_more);
+        // The same page delivered twice is not appended again
+        screen.handle_posts(page, screen.posts_generation, 1, false);
+        assert_eq!(screen.posts.len(), 2);
+    }
+
+    #[test]
+    fn test_failed_vote_restores_previous() {
+        let mut screen = screen_with_post();
+        screen.apply_post_action(PostAction::Upvote);
+        screen.apply_post_action(PostAction::Downvote);
+        screen.handle_post_action_failed("post-1", PostAction::Downvote, Some(PostAction::Upvote), "503".to_string());
+        assert_eq!(counts(&screen), (6, 2));
+        assert_eq!(screen.votes.get("post-1"), Some(&PostAction::Upvote));
+        assert!(screen.error.is_some());
+
+        // The next vote that goes through clears the failure
+        screen.apply_post_action(PostAction::Downvote);
+        screen.handle_post_action_done(PostAction::Downvote);
+        assert_eq!(screen.error, None);
+        assert_eq!(counts(&screen), (5, 3));
+    }
+
+    #[tokio::test]
+    async fn test_vote_rejected_by_moltbook() {
+        let fake = crate::testing::FakeApi::default();
+        let mut app = crate::testing::signed_in_app();
+        app.client = fake.client();
+        app.config.moltbook_api_key = Some("moltbook_key".to_string());
+        app.screen = crate::app::AppScreen::View;
+        app.view = screen_with_post();
+        app.view.detail_open = true;
+        let (tx, mut rx) = mpsc::channel(8);
+
+        fake.fail_moltbook(Some("403 Forbidden"));
+        app.handle_key(KeyCode::Char('u').into(), tx.clone()).await.unwrap();
+        assert_eq!(counts(&app.view), (6, 2));
+        let msg = rx.recv().await.unwrap();
+        app.handle_message(msg, tx.clone()).await.unwrap();
+        assert_eq!(fake.calls(), ["upvote_post"]);
+        assert_eq!(counts(&app.view), (5, 2));
+        assert_eq!(app.view.votes.get("post-1"), None);
+        assert!(app.view.error.as_deref().is_some_and(|e| e.contains("403 Forbidden")));
+
+        fake.fail_moltbook(None);
+        app.handle_key(KeyCode::Char('u').into(), tx.clone()).await.unwrap();
+        let msg = rx.recv().await.unwrap();
+        app.handle_message(msg, tx).await.unwrap();
+        assert_eq!(fake.calls(), ["upvote_post", "upvote_post"]);
+        assert_eq!(counts(&app.view), (6, 2));
+        assert_eq!(app.view.error, None);
+    }
+
+    #[test]
+    fn test_view_health() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::View;
+        let info = serde_json::json!({
+            "chain_info": { "owner": WALLET_ADDRESS, "name": "pinchy", "active": true, "version": 1 },
+            "moltbook_info": { "name": "pinchy", "description": null, "claimed": false, "twitter_handle": null },
+        });
+        app.view.handle_agent_info(serde_json::from_value(info).unwrap());
+        let activity = serde_json::json!([
+            { "kind": "run_failed", "block_number": 212, "run_id": 3, "cost": "200000000000" },
+            { "kind": "run_started", "block_number": 210, "run_id": 3 },
+            { "kind": "run_completed", "block_number": 106, "run_id": 2, "cost": "400000000000" },
+            { "kind": "run_started", "block_number": 100, "run_id": 2 },
+        ]);
+        app.view.handle_activity(serde_json::from_value(activity).unwrap());
+        app.view.handle_agent_balance(BalanceResponse {
+            balance: "3000000000000".to_string(),
+            balance_formatted: "3".to_string(),
+        });
+        app.view.tab = ViewTab::Health;
+        // "updated Ns ago" would depend on how long the test takes
+        app.view.last_updated = None;
+        assert_snapshot("view_health", &screen_text(&mut app));
+    }
+
+    #[test]
+    fn test_view_stats() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::View;
+        let post = |submolt: &str, day: u32, upvotes: u32| {
+            serde_json::json!({
+                "id": format!("p{}", day), "submolt": { "name": submolt },
+                "created_at": format!("2026-01-{:02}T12:00:00Z", day), "upvotes": upvotes,
+            })
         };
+        let posts = serde_json::json!([post("crabs", 14, 9), post("general", 9, 2), post("general", 2, 4), post("tidepools", 1, 0)]);
+        app.view.handle_posts(serde_json::from_value(posts).unwrap(), 0, 0, false);
+        let daily: Vec<_> = (1..=14).map(|day| serde_json::json!({ "date": format!("2026-01-{:02}", day), "posts": day % 3 })).collect();
+        let stats = serde_json::json!({ "total_posts": 60, "total_upvotes": 210, "total_comments": 45, "daily": daily });
+        app.view.post_stats = Some(serde_json::from_value(stats).unwrap());
+        app.view.tab = ViewTab::Stats;
+        app.view.last_updated = None;
+        assert_snapshot("view_stats", &screen_text(&mut app));
+    }
 
-        let footer = Paragraph::new(footer_content).alignment(Alignment::Center);
-        frame.render_widget(footer, chunks[3]);
+    #[test]
+    fn test_view_versions() {
+        let mut app = signed_in_app();
+        app.screen = AppScreen::View;
+        let info = serde_json::json!({ "chain_info": { "owner": WALLET_ADDRESS, "name": "pinchy", "active": true, "version": 3 } });
+        app.view.handle_agent_info(serde_json::from_value(info).unwrap());
+        let deployed = |version: u32, block_number: u32, code_hash: &str| crate::versions::DeployedVersion {
+            agent_address: AGENT_ADDRESS.to_string(),
+            version: Some(version),
+            code_hash: code_hash.to_string(),
+            block_number,
+            block_hash: "0x01".to_string(),
+            // 2026-10-17 09:00 UTC
+            deployed_at: 1_792_227_600 + block_number as u64 * 6,
+            source: "embedded".to_string(),
+            snapshot: Some(format!("versions/{}", code_hash.trim_start_matches("0x")).into()),
+        };
+        app.view.versions = vec![deployed(1, 120, "0x4be1a0c9d2"), deployed(2, 5400, "0x7f3302aa18")];
+        app.view.tab = ViewTab::Versions;
+        app.view.last_updated = None;
+        assert_snapshot("view_versions", &screen_text(&mut app));
     }
 }
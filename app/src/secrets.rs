@@ -0,0 +1,297 @@
+//! Passphrase encryption for the secrets in `config.toml`.
+//!
+//! With `encrypt_secrets = true` the auth tokens and Moltbook API keys are
+//! written as `enc:v1:<base64>`: a random salt and nonce followed by the
+//! value sealed with ChaCha20-Poly1305, under a key derived from the
+//! passphrase with Argon2id. The passphrase comes from `LOBSTER_PASSPHRASE`
+//! or is asked for once per run on the terminal.
+//!
+//! Secrets are also kept out of logs and status lines: `redact` and
+//! `redact_json` hide them in error text and response bodies.
+//!
+//! The idle lock is opened with that passphrase or with a PIN, kept in
+//! `lock_pin` as `argon2id:<base64>`: a random salt and the Argon2id hash.
+//!
+//! Files holding secrets are written with `write_private`.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// Marks a sealed value; the version leaves room for another scheme.
+const PREFIX: &str = "enc:v1:";
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Passphrase of this run, once given.
+static PASSPHRASE: OnceLock<String> = OnceLock::new();
+
+/// A key with the passphrase and salt it was derived from.
+struct DerivedKey {
+    passphrase: String,
+    salt: [u8; SALT_LEN],
+    key: [u8; 32],
+}
+
+/// Last derived key. Every value a save writes shares the salt, so the key
+/// is derived once, not per value.
+static KEY: Mutex<Option<DerivedKey>> = Mutex::new(None);
+
+/// Whether `value` was written sealed.
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+/// The passphrase from `LOBSTER_PASSPHRASE`, or asked for on the terminal
+/// the first time it is needed. Runs without a terminal must set the variable.
+pub fn passphrase() -> Result<&'static str> {
+    if let Some(passphrase) = PASSPHRASE.get() {
+        return Ok(passphrase);
+    }
+    let passphrase = match std::env::var("LOBSTER_PASSPHRASE") {
+        Ok(passphrase) => passphrase,
+        Err(_) if std::io::stdin().is_terminal() => {
+            rpassword::prompt_password("Passphrase for the secrets in config.toml: ").context("Cannot read the passphrase")?
+        }
+        Err(_) => bail!("config.toml has encrypted secrets; set LOBSTER_PASSPHRASE to run without a terminal"),
+    };
+    if passphrase.is_empty() {
+        bail!("The passphrase must not be empty");
+    }
+    Ok(PASSPHRASE.get_or_init(|| passphrase))
+}
+
+fn key(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<[u8; 32]> {
+    let mut cached = KEY.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(derived) = cached.as_ref().filter(|d| d.passphrase == passphrase && d.salt == salt) {
+        return Ok(derived.key);
+    }
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| anyhow!("Cannot derive the key: {}", e))?;
+    *cached = Some(DerivedKey { passphrase: passphrase.to_string(), salt, key });
+    Ok(key)
+}
+
+/// Salt of the cached key, or a fresh one.
+fn salt() -> Result<[u8; SALT_LEN]> {
+    if let Some(derived) = KEY.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Ok(derived.salt);
+    }
+    let mut salt = [0u8; SALT_LEN];
+    getrandom::getrandom(&mut salt).context("No randomness for the salt")?;
+    Ok(salt)
+}
+
+fn seal_with(passphrase: &str, plain: &str) -> Result<String> {
+    let salt = salt()?;
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut nonce).context("No randomness for the nonce")?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key(passphrase, salt)?));
+    let sealed = cipher
+        .encrypt(Nonce::from_slice(&nonce), plain.as_bytes())
+        .map_err(|_| anyhow!("Cannot encrypt"))?;
+    Ok(format!("{}{}", PREFIX, STANDARD.encode([&salt[..], &nonce, &sealed].concat())))
+}
+
+fn open_with(passphrase: &str, sealed: &str) -> Result<String> {
+    let bytes = sealed
+        .strip_prefix(PREFIX)
+        .and_then(|b64| STANDARD.decode(b64).ok())
+        .filter(|bytes| bytes.len() > SALT_LEN + NONCE_LEN)
+        .context("not an encrypted value")?;
+    let (salt, rest) = bytes.split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key(passphrase, salt.try_into()?)?));
+    let plain = cipher
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| anyhow!("cannot be decrypted; is the passphrase right?"))?;
+    String::from_utf8(plain).context("is not text once decrypted")
+}
+
+/// Encrypt `plain` with the run's passphrase.
+pub fn seal(plain: &str) -> Result<String> {
+    seal_with(passphrase()?, plain)
+}
+
+/// Decrypt a value written by `seal`.
+pub fn open(sealed: &str) -> Result<String> {
+    open_with(passphrase()?, sealed)
+}
+
+/// Marks a hashed lock PIN.
+const PIN_PREFIX: &str = "argon2id:";
+
+fn pin_hash(pin: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut hash = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(pin.as_bytes(), salt, &mut hash)
+        .map_err(|e| anyhow!("Cannot hash the PIN: {}", e))?;
+    Ok(hash)
+}
+
+/// `pin` hashed with a fresh salt, for `lock_pin`.
+pub fn hash_pin(pin: &str) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    getrandom::getrandom(&mut salt).context("No randomness for the salt")?;
+    Ok(format!("{}{}", PIN_PREFIX, STANDARD.encode([&salt[..], &pin_hash(pin, &salt)?].concat())))
+}
+
+/// Whether the idle lock can be opened: there is a PIN, or the secrets'
+/// passphrase was given this run.
+pub fn can_unlock(lock_pin: Option<&str>) -> bool {
+    lock_pin.is_some() || PASSPHRASE.get().is_some()
+}
+
+/// Whether `candidate` opens the idle lock: the PIN hashed in `lock_pin`,
+/// or the passphrase of this run.
+pub fn unlocks(candidate: &str, lock_pin: Option<&str>) -> bool {
+    if PASSPHRASE.get().is_some_and(|p| p == candidate) {
+        return true;
+    }
+    let Some(bytes) = lock_pin
+        .and_then(|stored| stored.strip_prefix(PIN_PREFIX))
+        .and_then(|b64| STANDARD.decode(b64).ok())
+        .filter(|bytes| bytes.len() == SALT_LEN + 32)
+    else {
+        return false;
+    };
+    let (salt, hash) = bytes.split_at(SALT_LEN);
+    pin_hash(candidate, salt).is_ok_and(|h| h[..] == *hash)
+}
+
+/// Replace `path` with `contents`, readable by the owner only. The data goes
+/// to a file created with mode 0600 next to it, which is then renamed over
+/// `path`: the secrets are never readable by others, not even for a moment,
+/// and a crash mid-write leaves the old file whole.
+pub fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    let name = path.file_name().and_then(|n| n.to_str()).ok_or_else(|| anyhow!("{} is not a file path", path.display()))?;
+    let staged = path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()));
+    // Left behind by a crash of an earlier process with the same id
+    let _ = std::fs::remove_file(&staged);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options
+        .open(&staged)
+        .and_then(|mut file| file.write_all(contents).and_then(|()| file.sync_all()))
+        .and_then(|()| std::fs::rename(&staged, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&staged);
+    }
+    written.with_context(|| format!("Cannot write {}", path.display()))
+}
+
+/// JSON fields that hold a secret, in bodies that might be logged or shown.
+const SECRET_FIELDS: &[&str] = &["api_key", "moltbook_api_key"];
+
+/// Shown in place of a secret.
+const REDACTED: &str = "[redacted]";
+
+/// `text` with every occurrence of `secret` hidden, for status lines and
+/// errors that might quote it.
+pub fn redact(text: &str, secret: &str) -> String {
+    if secret.is_empty() {
+        return text.to_string();
+    }
+    text.replace(secret, REDACTED)
+}
+
+/// A JSON response body with the values of secret fields hidden. Other
+/// bodies come back as they were.
+pub fn redact_json(body: &str) -> String {
+    /// Hide the secret fields under `value`; true when there were any.
+    fn walk(value: &mut serde_json::Value) -> bool {
+        let mut found = false;
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if SECRET_FIELDS.contains(&key.as_str()) && value.is_string() {
+                        *value = REDACTED.into();
+                        found = true;
+                    } else {
+                        found |= walk(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    found |= walk(item);
+                }
+            }
+            _ => {}
+        }
+        found
+    }
+    let Ok(mut json) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.to_string();
+    };
+    if walk(&mut json) { json.to_string() } else { body.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let sealed = seal_with("hunter2", "jwt.token.here").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("jwt"));
+        assert_ne!(sealed, seal_with("hunter2", "jwt.token.here").unwrap());
+        assert_eq!(open_with("hunter2", &sealed).unwrap(), "jwt.token.here");
+        assert!(open_with("hunter3", &sealed).unwrap_err().to_string().contains("passphrase"));
+        assert!(open_with("hunter2", "enc:v1:AAAA").is_err());
+    }
+
+    #[test]
+    fn test_write_private() {
+        let dir = std::env::temp_dir().join(format!("lobster-private-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "old").unwrap();
+        write_private(&path, b"auth_token = \"jwt\"\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "auth_token = \"jwt\"\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        // Nothing staged is left next to it
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_lock_pin() {
+        let stored = hash_pin("2468").unwrap();
+        assert!(!stored.contains("2468"));
+        assert!(unlocks("2468", Some(&stored)));
+        assert!(!unlocks("1357", Some(&stored)));
+        assert!(!unlocks("2468", None));
+        assert!(!unlocks("2468", Some("argon2id:AAAA")));
+    }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(redact("Invalid key moltbook_abc", "moltbook_abc"), "Invalid key [redacted]");
+        assert_eq!(redact("no key here", ""), "no key here");
+
+        let body = r#"{"agent":{"name":"pinchy","api_key":"moltbook_abc"},"hint":"x"}"#;
+        let redacted = redact_json(body);
+        assert!(!redacted.contains("moltbook_abc") && redacted.contains("pinchy"));
+        assert_eq!(redact_json(r#"{"error":"nope"}"#), r#"{"error":"nope"}"#);
+        assert_eq!(redact_json("Bad gateway"), "Bad gateway");
+    }
+}
@@ -0,0 +1,73 @@
+//! Spending limits on what the wallet sends from the TUI: deploy deposits
+//! and agent top-ups. `lobster daemon` adds its top-ups to the same total. A transaction over `spending_limits.per_transaction`,
+//! or one taking the day past `per_day`, is not refused; its amount has to
+//! be typed out to confirm it, which a slipped digit won't survive. What was
+//! sent each UTC day is kept by network in `spending.json` in the data
+//! directory, so the daily total holds across restarts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// `spending_limits` in config.toml, as token amounts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpendingLimits {
+    /// Most one transaction sends without typing the amount (unset is no limit)
+    pub per_transaction: Option<String>,
+    /// Most sent per UTC day without typing the amount (unset is no limit)
+    pub per_day: Option<String>,
+}
+
+/// What the wallet sent on one day, by network name, in planck.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Ledger {
+    /// Days since the epoch
+    day: u64,
+    spent: BTreeMap<String, u128>,
+}
+
+fn path() -> PathBuf {
+    crate::config::data_dir().join("spending.json")
+}
+
+fn today() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+        / 86_400
+}
+
+/// The ledger of `day`; empty when the file is from an earlier day.
+fn load(day: u64) -> Ledger {
+    let ledger: Ledger = std::fs::read_to_string(path())
+        .ok()
+        .and_then(|contents| {
+            serde_json::from_str(&contents)
+                .inspect_err(|e| tracing::warn!("Ignoring unreadable spending.json: {}", e))
+                .ok()
+        })
+        .unwrap_or_default();
+    if ledger.day == day { ledger } else { Ledger { day, spent: BTreeMap::new() } }
+}
+
+/// Planck the wallet sent today on `network`.
+pub fn spent_today(network: &str) -> u128 {
+    load(today()).spent.get(network).copied().unwrap_or(0)
+}
+
+/// Add `amount` planck sent on `network` to today's total. Failures only go
+/// to the log; the transaction went through either way.
+pub fn record(network: &str, amount: u128) {
+    let mut ledger = load(today());
+    let spent = ledger.spent.entry(network.to_string()).or_default();
+    *spent = spent.saturating_add(amount);
+    let path = path();
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, serde_json::to_string_pretty(&ledger).unwrap_or_default()));
+    if let Err(e) = written {
+        tracing::warn!("Cannot record today's spending in {}: {}", path.display(), e);
+    }
+}
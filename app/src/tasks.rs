@@ -0,0 +1,104 @@
+//! Background task spawning with an in-flight count for the status bar.
+//!
+//! Tasks that quitting would cut short (deploys, runs, transfers) are spawned
+//! as operations, which are also listed by name for the confirm-quit guard.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tokio::task::AbortHandle;
+
+/// Tasks spawned through `spawn` that have not finished yet.
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Abort handles of running tasks, by spawn id, for `shutdown`.
+static HANDLES: Mutex<Vec<(u64, AbortHandle)>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Descriptions of running operations, e.g. "a deployment", in start order.
+static OPERATIONS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// Decrements the count when the task ends, including when it panics or is aborted.
+struct InFlightGuard(u64);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        // Tokio keeps the panic in the JoinHandle, which nobody awaits
+        if std::thread::panicking() {
+            tracing::error!("background task panicked");
+        }
+        handles().retain(|(id, _)| *id != self.0);
+        IN_FLIGHT.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn handles() -> std::sync::MutexGuard<'static, Vec<(u64, AbortHandle)>> {
+    HANDLES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Removes the operation from the list when its task ends.
+struct OperationGuard(&'static str);
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        tracing::debug!(operation = self.0, "finished");
+        let mut operations = OPERATIONS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(i) = operations.iter().position(|op| *op == self.0) {
+            operations.remove(i);
+        }
+    }
+}
+
+/// `tokio::spawn` that is counted by `in_flight` while it runs.
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    IN_FLIGHT.fetch_add(1, Ordering::Relaxed);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let guard = InFlightGuard(id);
+    // Held across the spawn so the guard can't remove the handle before it is added
+    let mut handles = handles();
+    let handle = tokio::spawn(async move {
+        let _guard = guard;
+        future.await
+    });
+    handles.push((id, handle.abort_handle()));
+    handle
+}
+
+/// `spawn` for work that should not be abandoned silently; `what` names it
+/// in the quit confirmation ("a deployment", "an agent run").
+pub fn spawn_operation<F>(what: &'static str, future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    OPERATIONS.lock().unwrap_or_else(|e| e.into_inner()).push(what);
+    tracing::debug!(operation = what, "started");
+    let guard = OperationGuard(what);
+    spawn(async move {
+        let _guard = guard;
+        future.await
+    })
+}
+
+/// Number of background tasks currently running.
+pub fn in_flight() -> usize {
+    IN_FLIGHT.load(Ordering::Relaxed)
+}
+
+/// Operations currently running, oldest first.
+pub fn operations() -> Vec<&'static str> {
+    OPERATIONS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Abort every task still running, returning how many there were.
+pub fn abort_all() -> usize {
+    let running: Vec<AbortHandle> = handles().drain(..).map(|(_, handle)| handle).collect();
+    for handle in &running {
+        handle.abort();
+    }
+    running.len()
+}
@@ -0,0 +1,457 @@
+//! Harness for render tests: draw the app into ratatui's `TestBackend` and
+//! compare the frame, as plain text, with a snapshot under `app/snapshots/`.
+//! The tests themselves live next to the screen they draw.
+//!
+//! A missing snapshot fails the test; to add one, or after a deliberate UI
+//! change, rerun with `UPDATE_SNAPSHOTS=1` and review the diff. Colors and
+//! styles are not compared, only the characters on screen.
+
+use crate::{
+    app::App,
+    client::{
+        ActivityResponse, AgentInfo, AgentListItem, AgentState, Api, ApiClient, AuthMeResponse, BalanceResponse,
+        BuildExtrinsicResponse, ChainHeadResponse, CompileResponse, FaucetResponse, FeeEstimateResponse, FundResponse,
+        LeaderboardKind, LeaderboardResponse, MoltbookStatusResponse, PostStats, PostsResponse, RunsResponse,
+        StoreAgentResponse, SubmitResponse,
+    },
+    config::{AppConfig, GlyphMode},
+    mock::MockApi,
+    moltbook::{
+        AgentMeResponse, AgentProfile, Comment, CreatedPost, FeedPage, InboxItem, MoltbookError, MoltbookResult,
+        RegisterResponse, Submolt,
+    },
+    wallet::WalletConfig,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use ratatui::{backend::TestBackend, buffer::Buffer, text::Span, Terminal};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Frame size snapshots are taken at unless a test needs another.
+pub const WIDTH: u16 = 100;
+pub const HEIGHT: u16 = 32;
+
+/// Wallet address used by signed-in fixtures.
+pub const WALLET_ADDRESS: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+pub const AGENT_ADDRESS: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+/// Config of a returning user who is signed out. Glyphs are pinned to
+/// Unicode so the terminal running the tests doesn't matter.
+pub fn config() -> AppConfig {
+    AppConfig { onboarded: true, glyphs: GlyphMode::Unicode, ..AppConfig::default() }
+}
+
+/// The in-process gateway, replaying run events without the `--mock` pauses.
+pub fn mock_client() -> ApiClient {
+    ApiClient::from_api(Arc::new(MockApi::new(Duration::from_millis(10))))
+}
+
+/// App for `config`, talking to the in-process gateway.
+pub fn app(config: AppConfig) -> App {
+    App::with_config(config, mock_client(), None, "agent".to_string())
+}
+
+/// Signed in, with a funded wallet and a deployed agent.
+pub fn signed_in_app() -> App {
+    let config = AppConfig {
+        auth_token: Some("token".to_string()),
+        agent_address: Some(AGENT_ADDRESS.to_string()),
+        agent_name: Some("pinchy".to_string()),
+        ..config()
+    };
+    let wallet = WalletConfig { mnemonic: String::new(), public_key: WALLET_ADDRESS.to_string() };
+    let mut app = App::with_config(config, mock_client(), Some(wallet), "agent".to_string());
+    app.wallet_balance = Some(BalanceResponse {
+        balance: "1000000000000000".to_string(),
+        balance_formatted: "1000".to_string(),
+    });
+    app
+}
+
+/// Backend for screen tests: the in-process gateway, with every Moltbook call
+/// recorded by name and failing while `fail_moltbook` is set.
+#[derive(Clone)]
+pub struct FakeApi {
+    inner: Arc<MockApi>,
+    calls: Arc<Mutex<Vec<&'static str>>>,
+    fail_moltbook: Arc<Mutex<Option<String>>>,
+}
+
+impl Default for FakeApi {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(MockApi::new(Duration::from_millis(10))),
+            calls: Arc::default(),
+            fail_moltbook: Arc::default(),
+        }
+    }
+}
+
+impl FakeApi {
+    /// Client over this fake; the fake keeps seeing its calls.
+    pub fn client(&self) -> ApiClient {
+        ApiClient::from_api(Arc::new(self.clone()))
+    }
+
+    /// Fail Moltbook calls with `error` from now on, or stop failing them.
+    pub fn fail_moltbook(&self, error: Option<&str>) {
+        *self.fail_moltbook.lock().unwrap() = error.map(str::to_string);
+    }
+
+    /// Moltbook calls made so far, oldest first.
+    pub fn calls(&self) -> Vec<&'static str> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn called(&self, name: &'static str) -> MoltbookResult<()> {
+        self.calls.lock().unwrap().push(name);
+        match self.fail_moltbook.lock().unwrap().clone() {
+            Some(error) => Err(MoltbookError::Api(error)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl Api for FakeApi {
+    fn base_url(&self) -> &str {
+        self.inner.base_url()
+    }
+
+    fn with_auth_token(&self, _token: Option<String>) -> Arc<dyn Api> {
+        Arc::new(self.clone())
+    }
+
+    fn moltbook_url(&self) -> &str {
+        self.inner.moltbook_url()
+    }
+
+    fn with_moltbook_url(&self, _api_url: &str) -> Arc<dyn Api> {
+        Arc::new(self.clone())
+    }
+
+    async fn get_me(&self) -> Result<AuthMeResponse> {
+        self.inner.get_me().await
+    }
+
+    async fn get_balance(&self, address: &str) -> Result<BalanceResponse> {
+        self.inner.get_balance(address).await
+    }
+
+    async fn balance_events(&self, address: &str) -> Result<Option<reqwest::Response>> {
+        self.inner.balance_events(address).await
+    }
+
+    async fn get_chain_head(&self) -> Result<ChainHeadResponse> {
+        self.inner.get_chain_head().await
+    }
+
+    async fn fund_wallet(&self, public_key: &str) -> Result<FundResponse> {
+        self.inner.fund_wallet(public_key).await
+    }
+
+    async fn request_test_tokens(&self, address: &str) -> Result<FaucetResponse> {
+        self.inner.request_test_tokens(address).await
+    }
+
+    async fn store_agent(&self, name: &str, moltbook_api_key: &str) -> Result<StoreAgentResponse> {
+        self.inner.store_agent(name, moltbook_api_key).await
+    }
+
+    async fn update_agent_address(&self, agent_id: &str, chain_address: &str) -> Result<()> {
+        self.inner.update_agent_address(agent_id, chain_address).await
+    }
+
+    async fn delete_agent(&self, agent_id: &str) -> Result<()> {
+        self.inner.delete_agent(agent_id).await
+    }
+
+    async fn get_moltbook_status(&self, api_key: &str) -> Result<MoltbookStatusResponse> {
+        self.inner.get_moltbook_status(api_key).await
+    }
+
+    async fn compile(
+        &self,
+        agent_id: &str,
+        ship_file: &str,
+        soul_md: &str,
+        skill_md: &str,
+        heartbeat_md: &str,
+        schedule_blocks: Option<u32>,
+    ) -> Result<CompileResponse> {
+        self.inner.compile(agent_id, ship_file, soul_md, skill_md, heartbeat_md, schedule_blocks).await
+    }
+
+    async fn simulate(
+        &self,
+        ship_file: &str,
+        soul_md: &str,
+        skill_md: &str,
+        heartbeat_md: &str,
+        prompt: &str,
+    ) -> Result<reqwest::Response> {
+        self.inner.simulate(ship_file, soul_md, skill_md, heartbeat_md, prompt).await
+    }
+
+    async fn submit_extrinsic(&self, extrinsic_hex: &str) -> Result<SubmitResponse> {
+        self.inner.submit_extrinsic(extrinsic_hex).await
+    }
+
+    async fn estimate_fee(&self, extrinsic_hex: &str) -> Result<FeeEstimateResponse> {
+        self.inner.estimate_fee(extrinsic_hex).await
+    }
+
+    async fn run_events(&self, run_id: u64) -> Result<reqwest::Response> {
+        self.inner.run_events(run_id).await
+    }
+
+    async fn get_agent(&self, address: &str) -> Result<AgentInfo> {
+        self.inner.get_agent(address).await
+    }
+
+    async fn get_posts(&self, address: &str, offset: u32, limit: u32) -> Result<PostsResponse> {
+        self.inner.get_posts(address, offset, limit).await
+    }
+
+    async fn get_activity(&self, address: &str, limit: u32) -> Result<ActivityResponse> {
+        self.inner.get_activity(address, limit).await
+    }
+
+    async fn get_agent_state(&self, address: &str) -> Result<AgentState> {
+        self.inner.get_agent_state(address).await
+    }
+
+    async fn get_post_stats(&self, address: &str, days: u32) -> Result<PostStats> {
+        self.inner.get_post_stats(address, days).await
+    }
+
+    async fn get_runs(&self, address: &str, limit: u32) -> Result<RunsResponse> {
+        self.inner.get_runs(address, limit).await
+    }
+
+    async fn get_leaderboard(
+        &self,
+        kind: LeaderboardKind,
+        limit: u32,
+        agent: Option<&str>,
+    ) -> Result<LeaderboardResponse> {
+        self.inner.get_leaderboard(kind, limit, agent).await
+    }
+
+    async fn list_agents(&self) -> Result<Vec<AgentListItem>> {
+        self.inner.list_agents().await
+    }
+
+    async fn build_deploy(
+        &self,
+        compiled_hex: &str,
+        salt_hex: &str,
+        signer_address: &str,
+        value: u128,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.inner.build_deploy(compiled_hex, salt_hex, signer_address, value).await
+    }
+
+    async fn build_transfer(&self, dest: &str, value: u128, signer_address: &str) -> Result<BuildExtrinsicResponse> {
+        self.inner.build_transfer(dest, value, signer_address).await
+    }
+
+    async fn build_set_active(
+        &self,
+        agent_address: &str,
+        active: bool,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.inner.build_set_active(agent_address, active, signer_address).await
+    }
+
+    async fn build_withdraw(&self, agent_address: &str, signer_address: &str) -> Result<BuildExtrinsicResponse> {
+        self.inner.build_withdraw(agent_address, signer_address).await
+    }
+
+    async fn build_transfer_ownership(
+        &self,
+        agent_address: &str,
+        new_owner: &str,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.inner.build_transfer_ownership(agent_address, new_owner, signer_address).await
+    }
+
+    async fn build_call(
+        &self,
+        agent_address: &str,
+        input: &str,
+        signer_address: &str,
+    ) -> Result<BuildExtrinsicResponse> {
+        self.inner.build_call(agent_address, input, signer_address).await
+    }
+
+    async fn register_agent(&self, name: &str, description: &str) -> MoltbookResult<RegisterResponse> {
+        self.called("register_agent")?;
+        self.inner.register_agent(name, description).await
+    }
+
+    async fn get_moltbook_agent(&self, api_key: &str) -> MoltbookResult<AgentMeResponse> {
+        self.called("get_moltbook_agent")?;
+        self.inner.get_moltbook_agent(api_key).await
+    }
+
+    async fn get_feed(&self, api_key: &str, sort: &str, offset: u32, limit: u32) -> MoltbookResult<FeedPage> {
+        self.called("get_feed")?;
+        self.inner.get_feed(api_key, sort, offset, limit).await
+    }
+
+    async fn list_submolts(&self, api_key: &str) -> MoltbookResult<Vec<Submolt>> {
+        self.called("list_submolts")?;
+        self.inner.list_submolts(api_key).await
+    }
+
+    async fn subscribe_submolt(&self, api_key: &str, name: &str) -> MoltbookResult<()> {
+        self.called("subscribe_submolt")?;
+        self.inner.subscribe_submolt(api_key, name).await
+    }
+
+    async fn unsubscribe_submolt(&self, api_key: &str, name: &str) -> MoltbookResult<()> {
+        self.called("unsubscribe_submolt")?;
+        self.inner.unsubscribe_submolt(api_key, name).await
+    }
+
+    async fn create_post(
+        &self,
+        api_key: &str,
+        submolt: &str,
+        title: &str,
+        content: &str,
+    ) -> MoltbookResult<CreatedPost> {
+        self.called("create_post")?;
+        self.inner.create_post(api_key, submolt, title, content).await
+    }
+
+    async fn upvote_post(&self, api_key: &str, post_id: &str) -> MoltbookResult<()> {
+        self.called("upvote_post")?;
+        self.inner.upvote_post(api_key, post_id).await
+    }
+
+    async fn downvote_post(&self, api_key: &str, post_id: &str) -> MoltbookResult<()> {
+        self.called("downvote_post")?;
+        self.inner.downvote_post(api_key, post_id).await
+    }
+
+    async fn create_comment(&self, api_key: &str, post_id: &str, content: &str) -> MoltbookResult<()> {
+        self.called("create_comment")?;
+        self.inner.create_comment(api_key, post_id, content).await
+    }
+
+    async fn get_comments(&self, api_key: Option<&str>, post_id: &str) -> MoltbookResult<Vec<Comment>> {
+        self.called("get_comments")?;
+        self.inner.get_comments(api_key, post_id).await
+    }
+
+    async fn get_inbox(&self, api_key: &str, limit: u32) -> MoltbookResult<Vec<InboxItem>> {
+        self.called("get_inbox")?;
+        self.inner.get_inbox(api_key, limit).await
+    }
+
+    async fn mark_inbox_read(&self, api_key: &str, ids: &[String]) -> MoltbookResult<()> {
+        self.called("mark_inbox_read")?;
+        self.inner.mark_inbox_read(api_key, ids).await
+    }
+
+    async fn get_profile(&self, api_key: &str) -> MoltbookResult<AgentProfile> {
+        self.called("get_profile")?;
+        self.inner.get_profile(api_key).await
+    }
+
+    async fn update_profile(&self, api_key: &str, profile: &AgentProfile) -> MoltbookResult<AgentProfile> {
+        self.called("update_profile")?;
+        self.inner.update_profile(api_key, profile).await
+    }
+}
+
+/// Draw one frame of `app`.
+pub fn render(app: &mut App, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("test backend never fails");
+    terminal.draw(|frame| app.render(frame)).expect("test backend never fails");
+    terminal.backend().buffer().clone()
+}
+
+/// Rows of `buffer` as text, trailing spaces trimmed. Cells hidden behind a
+/// wide character are skipped, as a terminal would show them.
+pub fn buffer_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    let mut text = String::new();
+    for row in buffer.content.chunks(width) {
+        let mut line = String::new();
+        let mut skip = 0;
+        for cell in row {
+            if skip == 0 {
+                line.push_str(cell.symbol());
+            }
+            skip = skip.max(Span::raw(cell.symbol()).width()).saturating_sub(1);
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Screen contents of `app` at the default size, without the status bar,
+/// which shows process-wide counters (tasks, nonces) that tests running in
+/// parallel change.
+pub fn screen_text(app: &mut App) -> String {
+    let text = buffer_text(&render(app, WIDTH, HEIGHT));
+    let rows: Vec<&str> = text.lines().collect();
+    rows[..rows.len() - 1].join("\n") + "\n"
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots").join(format!("{}.txt", name))
+}
+
+/// Compare `actual` with the stored snapshot `name`. With `UPDATE_SNAPSHOTS=1`
+/// the snapshot is written instead, which is also the only way to add one.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    let update = std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1");
+    match std::fs::read_to_string(&path) {
+        Err(e) if !update => {
+            panic!("snapshot {} cannot be read ({}); UPDATE_SNAPSHOTS=1 to write it:\n{}", name, e, actual)
+        }
+        Ok(expected) if !update => {
+            if expected != actual {
+                let diff: Vec<String> = expected
+                    .lines()
+                    .zip(actual.lines())
+                    .enumerate()
+                    .filter(|(_, (e, a))| e != a)
+                    .map(|(i, (e, a))| format!("row {}:\n  - {}\n  + {}", i, e, a))
+                    .collect();
+                panic!(
+                    "snapshot {} differs (UPDATE_SNAPSHOTS=1 to accept):\n{}\n\nactual:\n{}",
+                    name,
+                    diff.join("\n"),
+                    actual
+                );
+            }
+        }
+        _ => {
+            std::fs::create_dir_all(path.parent().expect("snapshot path has a parent")).unwrap();
+            std::fs::write(&path, actual).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_text_skips_hidden_cells() {
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 4, 1));
+        buffer.set_string(0, 0, "🦞x", ratatui::style::Style::default());
+        assert_eq!(buffer_text(&buffer), "🦞x\n");
+    }
+}
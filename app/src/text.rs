@@ -0,0 +1,93 @@
+//! Fitting text into terminal columns.
+//!
+//! Widths here are display columns, not bytes or chars: text is cut between
+//! graphemes, so an emoji with a skin tone or a flag is kept or dropped
+//! whole, and wide characters such as 🦞 or CJK count the two cells they take.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns `text` takes on screen.
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(UnicodeWidthStr::width).sum()
+}
+
+/// Truncate text to at most `max_width` columns, ending with "..." when cut.
+pub fn truncate_width(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+    let mut out = head_width(text, max_width.saturating_sub(3)).to_string();
+    out.push_str("...");
+    out
+}
+
+/// Return the leading part of `text` that fits in `max_width` columns.
+pub fn head_width(text: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    for (i, grapheme) in text.grapheme_indices(true) {
+        width += grapheme.width();
+        if width > max_width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+/// Return the trailing part of `text` that fits in `max_width` columns.
+///
+/// Used by single-line inputs so the end being typed stays visible.
+pub fn tail_width(text: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    let mut start = text.len();
+    for (i, grapheme) in text.grapheme_indices(true).rev() {
+        width += grapheme.width();
+        if width > max_width {
+            break;
+        }
+        start = i;
+    }
+    &text[start..]
+}
+
+/// "5GrwvaEF...GKutQY" - the first `head_cols` and last `tail_cols` columns
+/// of a long address or hash, or all of it when that is no longer.
+pub fn shorten_middle(text: &str, head_cols: usize, tail_cols: usize) -> String {
+    if display_width(text) <= head_cols + 3 + tail_cols {
+        return text.to_string();
+    }
+    format!("{}...{}", head_width(text, head_cols), tail_width(text, tail_cols))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_width() {
+        assert_eq!(truncate_width("lobster", 10), "lobster");
+        assert_eq!(truncate_width("lobster claws", 10), "lobster...");
+        // Each lobster takes two columns
+        assert_eq!(display_width("🦞🦞🦞🦞🦞🦞"), 12);
+        assert_eq!(truncate_width("🦞🦞🦞🦞🦞🦞", 8), "🦞🦞...");
+        assert_eq!(truncate_width("🦞🦞🦞🦞🦞🦞", 6), "🦞...");
+        // A thumbs up with a skin tone is one grapheme, never split
+        assert_eq!(truncate_width("ok 👍🏽👍🏽", 6), "ok ...");
+        assert_eq!(truncate_width("éééé", 3), "...");
+    }
+
+    #[test]
+    fn test_tail_width() {
+        assert_eq!(tail_width("abcdef", 3), "def");
+        assert_eq!(tail_width("ééé", 5), "ééé");
+        assert_eq!(tail_width("ab🦞", 3), "b🦞");
+        assert_eq!(tail_width("ab🦞", 1), "");
+    }
+
+    #[test]
+    fn test_shorten_middle() {
+        assert_eq!(shorten_middle("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", 8, 6), "5GrwvaEF...GKutQY");
+        assert_eq!(shorten_middle("5Grwva", 8, 6), "5Grwva");
+        assert_eq!(shorten_middle("🦞🦞🦞🦞🦞🦞🦞🦞🦞🦞", 4, 4), "🦞🦞...🦞🦞");
+    }
+}
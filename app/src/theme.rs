@@ -0,0 +1,231 @@
+//! TUI color palettes.
+//!
+//! Screens never name colors directly; they pick a role from the active
+//! `Theme`, which is resolved from config at startup and after settings change.
+//!
+//! The color-blind, high-contrast and no-color presets also turn on status
+//! labels: success, warning and error marks carry a word ("OK", "WARN",
+//! "ERR") so that no status is told by color alone.
+
+use crate::config::{AppConfig, ThemeName};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Colors by role.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Primary text and headings
+    pub text: Color,
+    /// Secondary text
+    pub secondary: Color,
+    /// Borders, hints and disabled items
+    pub muted: Color,
+    /// Brand color: selection, focus, menu keys
+    pub accent: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    /// Addresses, links and other identifiers
+    pub info: Color,
+    /// Agent output
+    pub highlight: Color,
+    /// Spell status out next to its mark (see `ok_mark` and friends)
+    pub labels: bool,
+}
+
+/// Per-role overrides in config.toml, e.g. `colors = { accent = "#ff8800", muted = "gray" }`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeColors {
+    pub text: Option<String>,
+    pub secondary: Option<String>,
+    pub muted: Option<String>,
+    pub accent: Option<String>,
+    pub success: Option<String>,
+    pub warning: Option<String>,
+    pub error: Option<String>,
+    pub info: Option<String>,
+    pub highlight: Option<String>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// The original palette, for dark terminal backgrounds.
+    pub fn dark() -> Self {
+        Self {
+            text: Color::White,
+            secondary: Color::Gray,
+            muted: Color::DarkGray,
+            accent: Color::LightRed,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            info: Color::Cyan,
+            highlight: Color::Magenta,
+            labels: false,
+        }
+    }
+
+    /// Darker tones that stay readable on light backgrounds.
+    pub fn light() -> Self {
+        Self {
+            text: Color::Black,
+            secondary: Color::DarkGray,
+            muted: Color::Rgb(110, 110, 110),
+            accent: Color::Red,
+            success: Color::Rgb(0, 120, 0),
+            warning: Color::Rgb(160, 100, 0),
+            error: Color::Rgb(190, 0, 0),
+            info: Color::Blue,
+            highlight: Color::Magenta,
+            labels: false,
+        }
+    }
+
+    /// Terminal default colors everywhere; emphasis comes from modifiers only.
+    pub fn no_color() -> Self {
+        Self {
+            text: Color::Reset,
+            secondary: Color::Reset,
+            muted: Color::Reset,
+            accent: Color::Reset,
+            success: Color::Reset,
+            warning: Color::Reset,
+            error: Color::Reset,
+            info: Color::Reset,
+            highlight: Color::Reset,
+            labels: true,
+        }
+    }
+
+    /// Status in blue, yellow and orange from the Okabe-Ito set, which
+    /// green-weak eyes keep apart; the accent moves off red too.
+    pub fn deuteranopia() -> Self {
+        Self {
+            accent: Color::Rgb(204, 121, 167),
+            success: Color::Rgb(86, 180, 233),
+            warning: Color::Rgb(240, 228, 66),
+            error: Color::Rgb(213, 94, 0),
+            labels: true,
+            ..Self::dark()
+        }
+    }
+
+    /// Like `deuteranopia`, with an error orange bright enough for red-weak
+    /// eyes, to which vermilion looks dark.
+    pub fn protanopia() -> Self {
+        Self {
+            error: Color::Rgb(230, 159, 0),
+            warning: Color::Rgb(240, 240, 160),
+            ..Self::deuteranopia()
+        }
+    }
+
+    /// Bright variants only, and secondary text as bright as the rest.
+    pub fn high_contrast() -> Self {
+        Self {
+            text: Color::White,
+            secondary: Color::White,
+            muted: Color::Gray,
+            accent: Color::LightMagenta,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            info: Color::LightCyan,
+            highlight: Color::White,
+            labels: true,
+        }
+    }
+
+    /// Resolve the configured preset and overrides. `NO_COLOR` wins over both.
+    pub fn from_config(config: &AppConfig) -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return Self::no_color();
+        }
+        let mut theme = match config.theme {
+            ThemeName::Dark => Self::dark(),
+            ThemeName::Light => Self::light(),
+            ThemeName::NoColor => return Self::no_color(),
+            ThemeName::Deuteranopia => Self::deuteranopia(),
+            ThemeName::Protanopia => Self::protanopia(),
+            ThemeName::HighContrast => Self::high_contrast(),
+        };
+        theme.apply(&config.colors);
+        theme
+    }
+
+    /// Replace roles with the overrides that parse; unknown color names are ignored.
+    fn apply(&mut self, colors: &ThemeColors) {
+        let roles = [
+            (&mut self.text, &colors.text),
+            (&mut self.secondary, &colors.secondary),
+            (&mut self.muted, &colors.muted),
+            (&mut self.accent, &colors.accent),
+            (&mut self.success, &colors.success),
+            (&mut self.warning, &colors.warning),
+            (&mut self.error, &colors.error),
+            (&mut self.info, &colors.info),
+            (&mut self.highlight, &colors.highlight),
+        ];
+        for (role, value) in roles {
+            if let Some(color) = value.as_deref().and_then(|v| Color::from_str(v).ok()) {
+                *role = color;
+            }
+        }
+    }
+
+    /// Mark for something that worked.
+    pub fn ok_mark(&self) -> &'static str {
+        if self.labels { "✓ OK" } else { "✓" }
+    }
+
+    /// Mark for something to look at.
+    pub fn warn_mark(&self) -> &'static str {
+        if self.labels { "⚠ WARN" } else { "⚠" }
+    }
+
+    /// Mark for something that failed.
+    pub fn err_mark(&self) -> &'static str {
+        if self.labels { "✗ ERR" } else { "✗" }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_apply_to_preset() {
+        let config = AppConfig {
+            theme: ThemeName::Light,
+            colors: ThemeColors {
+                accent: Some("#ff8800".to_string()),
+                info: Some("not-a-color".to_string()),
+                ..ThemeColors::default()
+            },
+            ..AppConfig::default()
+        };
+        let theme = Theme::from_config(&config);
+        assert_eq!(theme.accent, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.info, Theme::light().info);
+        assert_eq!(theme.text, Color::Black);
+        assert!(!theme.labels);
+    }
+
+    #[test]
+    fn test_accessible_presets_label_status() {
+        for name in [ThemeName::Deuteranopia, ThemeName::Protanopia, ThemeName::HighContrast] {
+            let theme = Theme::from_config(&AppConfig { theme: name, ..AppConfig::default() });
+            assert!(theme.labels);
+            assert_eq!(theme.err_mark(), "✗ ERR");
+            assert!([theme.success, theme.warning].iter().all(|&c| c != theme.error));
+        }
+        assert_eq!(Theme::dark().ok_mark(), "✓");
+    }
+}
@@ -0,0 +1,238 @@
+//! Update check against GitHub releases, and `lobster self-update`.
+//!
+//! Release binaries are signed with Ed25519 by the release workflow; each
+//! asset has a `.sig` next to it holding the raw 64-byte signature of its
+//! manifest (see `manifest`), which names the version and asset along with
+//! the binary's SHA-256, so an older signed release can't be passed off as
+//! the latest. The public key is baked in at build time from
+//! `LOBSTER_UPDATE_PUBKEY` (hex), so a binary built without it can check for
+//! updates but not install them.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use sp_core::{ed25519, Pair};
+use std::path::Path;
+
+/// Latest release of the GitHub repository the binaries are published from.
+const RELEASES_URL: &str = "https://api.github.com/repos/theseus-network/proof-of-lobster/releases/latest";
+
+/// Release signing public key, hex encoded, set by the release workflow.
+const PUBLIC_KEY_HEX: Option<&str> = option_env!("LOBSTER_UPDATE_PUBKEY");
+
+/// Version of this binary.
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// Tag without the leading `v`.
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|a| a.name == name)
+    }
+}
+
+/// Release asset built for this platform, as named by the release workflow.
+fn platform_asset() -> Option<&'static str> {
+    if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Some("lobster-aarch64-apple-darwin")
+    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Some("lobster-x86_64-unknown-linux-gnu")
+    } else if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
+        Some("lobster-x86_64-pc-windows-msvc.exe")
+    } else {
+        None
+    }
+}
+
+/// Releases API URL; in debug builds `LOBSTER_RELEASES_URL` points it at a
+/// mirror or mock server. Release builds only ever ask GitHub.
+fn releases_url() -> String {
+    match std::env::var("LOBSTER_RELEASES_URL") {
+        Ok(url) if cfg!(debug_assertions) => url,
+        _ => RELEASES_URL.to_string(),
+    }
+}
+
+fn http() -> Result<reqwest::Client> {
+    // GitHub rejects API requests without a User-Agent
+    Ok(reqwest::Client::builder()
+        .user_agent(format!("lobster/{}", CURRENT_VERSION))
+        .build()?)
+}
+
+/// Fetch the latest published release.
+pub async fn latest_release() -> Result<Release> {
+    let resp = http()?.get(releases_url()).send().await?;
+    if !resp.status().is_success() {
+        bail!("GitHub releases API returned {}", resp.status());
+    }
+    Ok(resp.json().await?)
+}
+
+/// Version of the latest release, if it is newer than this binary.
+pub async fn newer_version() -> Result<Option<String>> {
+    let release = latest_release().await?;
+    Ok(is_newer(release.version(), CURRENT_VERSION).then(|| release.version().to_string()))
+}
+
+/// Compare dotted numeric versions; pre-release suffixes (`-rc1`) are ignored.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    }
+    let (candidate, current) = (parts(candidate), parts(current));
+    let len = candidate.len().max(current.len());
+    let at = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    (0..len)
+        .map(|i| at(&candidate, i).cmp(&at(&current, i)))
+        .find(|o| o.is_ne())
+        .is_some_and(|o| o.is_gt())
+}
+
+/// What the release workflow signs for each asset: the version it was
+/// released as, the asset name and the SHA-256 of its bytes.
+fn manifest(version: &str, asset_name: &str, data: &[u8]) -> Vec<u8> {
+    let digest = hex::encode(sp_core::hashing::sha2_256(data));
+    format!("lobster-release\nversion {}\nasset {}\nsha256 {}\n", version, asset_name, digest).into_bytes()
+}
+
+/// Check a raw Ed25519 signature of `data` against the hex public key.
+fn verify(data: &[u8], signature: &[u8], public_key_hex: &str) -> Result<()> {
+    let key: [u8; 32] = hex::decode(public_key_hex.trim())
+        .ok()
+        .and_then(|k| k.try_into().ok())
+        .context("Release signing key is not 32 hex-encoded bytes")?;
+    let signature: [u8; 64] = signature.try_into().map_err(|_| anyhow::anyhow!("Signature is not 64 bytes"))?;
+    let valid = ed25519::Pair::verify(&ed25519::Signature::from_raw(signature), data, &ed25519::Public::from_raw(key));
+    if !valid {
+        bail!("Signature does not match; the download was not installed");
+    }
+    Ok(())
+}
+
+async fn download(client: &reqwest::Client, asset: &ReleaseAsset) -> Result<Vec<u8>> {
+    let resp = client.get(&asset.browser_download_url).send().await?;
+    if !resp.status().is_success() {
+        bail!("Downloading {} failed: {}", asset.name, resp.status());
+    }
+    Ok(resp.bytes().await?.to_vec())
+}
+
+/// Replace the running binary with the latest release after verifying its
+/// signature. Prints progress; returns without changes when already current.
+pub async fn self_update() -> Result<()> {
+    let Some(public_key) = PUBLIC_KEY_HEX else {
+        bail!("This build has no release signing key; download the latest release from GitHub instead");
+    };
+    let Some(asset_name) = platform_asset() else {
+        bail!("No release binary is published for this platform");
+    };
+
+    let release = latest_release().await?;
+    if !is_newer(release.version(), CURRENT_VERSION) {
+        println!("lobster {} is the latest version", CURRENT_VERSION);
+        return Ok(());
+    }
+    let binary = release.asset(asset_name).context(format!("Release {} has no {}", release.tag_name, asset_name))?;
+    let signature_name = format!("{}.sig", asset_name);
+    let signature = release
+        .asset(&signature_name)
+        .context(format!("Release {} has no {}, refusing to install it unsigned", release.tag_name, signature_name))?;
+
+    println!("Downloading lobster {}...", release.version());
+    let client = http()?;
+    let data = download(&client, binary).await?;
+    let signature = download(&client, signature).await?;
+    // The signed manifest pins the version checked as newer above
+    verify(&manifest(release.version(), asset_name, &data), &signature, public_key)?;
+
+    let exe = std::env::current_exe().context("Could not locate the running binary")?;
+    replace_binary(&exe, &data)?;
+    println!("Updated lobster {} → {} ({})", CURRENT_VERSION, release.version(), exe.display());
+    Ok(())
+}
+
+/// Swap `exe` for `data` via a file in the same directory, so a failure
+/// halfway leaves the old binary in place.
+fn replace_binary(exe: &Path, data: &[u8]) -> Result<()> {
+    let staged = exe.with_extension("new");
+    std::fs::write(&staged, data).with_context(|| format!("Failed to write {}", staged.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+    // Windows can't overwrite a running executable but can rename it
+    #[cfg(windows)]
+    {
+        let old = exe.with_extension("old");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(exe, &old).with_context(|| format!("Failed to move {} aside", exe.display()))?;
+    }
+    std::fs::rename(&staged, exe).with_context(|| format!("Failed to replace {}", exe.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.2.0", "0.1.0"));
+        assert!(is_newer("1.0", "0.9.9"));
+        assert!(is_newer("0.1.1-rc1", "0.1.0"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.1.0", "0.1.1"));
+        assert!(!is_newer("0.1", "0.1.0"));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let pair = ed25519::Pair::from_seed(&[7; 32]);
+        let key = hex::encode(pair.public().0);
+        let data = b"lobster binary";
+        let signature = pair.sign(data).0;
+
+        assert!(verify(data, &signature, &key).is_ok());
+        assert!(verify(b"tampered binary", &signature, &key).is_err());
+        assert!(verify(data, &signature[..10], &key).is_err());
+        assert!(verify(data, &signature, "abcd").is_err());
+    }
+
+    #[test]
+    fn test_manifest_pins_version_and_asset() {
+        let pair = ed25519::Pair::from_seed(&[7; 32]);
+        let key = hex::encode(pair.public().0);
+        let binary = b"lobster binary";
+        let asset = "lobster-x86_64-unknown-linux-gnu";
+        let signature = pair.sign(&manifest("0.1.0", asset, binary)).0;
+
+        assert!(verify(&manifest("0.1.0", asset, binary), &signature, &key).is_ok());
+        // An old release served as a newer one, or another platform's binary
+        assert!(verify(&manifest("0.2.0", asset, binary), &signature, &key).is_err());
+        assert!(verify(&manifest("0.1.0", "lobster-aarch64-apple-darwin", binary), &signature, &key).is_err());
+        assert!(verify(&manifest("0.1.0", asset, b"tampered binary"), &signature, &key).is_err());
+    }
+}
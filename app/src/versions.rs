@@ -0,0 +1,134 @@
+//! Code deployed for each agent, recorded on this machine as it lands: the
+//! hash of the compiled program, the block it went in with, the chain's
+//! version counter at that point and a copy of the source it was built from.
+//!
+//! Records live in `versions.json` in the data directory and source
+//! snapshots under `versions/<code hash>/`. The chain only keeps the counter,
+//! so versions deployed from elsewhere show up as a gap in the timeline.
+
+use crate::agent_assets::AgentSource;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Agent files copied into a snapshot, as the compile step reads them.
+pub const SOURCE_FILES: [&str; 4] = ["moltbook_agent.ship", "SOUL.md", "SKILL.md", "HEARTBEAT.md"];
+
+/// One deploy of an agent's code.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeployedVersion {
+    pub agent_address: String,
+    /// Chain version counter right after the deploy, when it could be read
+    #[serde(default)]
+    pub version: Option<u32>,
+    /// blake2-256 of the compiled program, 0x-prefixed
+    pub code_hash: String,
+    pub block_number: u32,
+    pub block_hash: String,
+    /// Unix seconds
+    pub deployed_at: u64,
+    /// "embedded", or the custom agent directory
+    pub source: String,
+    /// Copy of the source files, when it could be written
+    #[serde(default)]
+    pub snapshot: Option<PathBuf>,
+}
+
+fn path() -> PathBuf {
+    crate::config::data_dir().join("versions.json")
+}
+
+/// Where source snapshots go, one directory per code hash.
+pub fn snapshots_dir() -> PathBuf {
+    crate::config::data_dir().join("versions")
+}
+
+fn load() -> Vec<DeployedVersion> {
+    let Ok(contents) = std::fs::read_to_string(path()) else { return Vec::new() };
+    serde_json::from_str(&contents)
+        .inspect_err(|e| tracing::warn!("Ignoring unreadable version history: {}", e))
+        .unwrap_or_default()
+}
+
+/// Recorded deploys of the agent at `agent_address`, oldest first.
+pub fn for_agent(agent_address: &str) -> Vec<DeployedVersion> {
+    load().into_iter().filter(|v| v.agent_address == agent_address).collect()
+}
+
+/// Add a deploy to the history.
+pub fn record(version: DeployedVersion) -> Result<()> {
+    let mut versions = load();
+    versions.push(version);
+    let path = path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&versions)?).with_context(|| format!("Cannot write {}", path.display()))
+}
+
+/// blake2-256 of a compiled program given as hex, the way the chain hashes code.
+pub fn code_hash(compiled_hex: &str) -> String {
+    let code = hex::decode(compiled_hex.trim_start_matches("0x")).unwrap_or_else(|_| compiled_hex.as_bytes().to_vec());
+    format!("0x{}", hex::encode(sp_core::hashing::blake2_256(&code)))
+}
+
+/// "embedded", or the custom agent directory.
+pub fn source_label(source: &AgentSource) -> String {
+    match source {
+        AgentSource::Embedded => "embedded".to_string(),
+        AgentSource::Custom(dir) => dir.clone(),
+    }
+}
+
+/// Copy the agent files from `source` under the code hash. The same code
+/// deployed twice shares its snapshot.
+pub fn snapshot(source: &AgentSource, code_hash: &str) -> Result<PathBuf> {
+    let dir = snapshots_dir().join(code_hash.trim_start_matches("0x"));
+    std::fs::create_dir_all(&dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+    for name in SOURCE_FILES {
+        if let Some(contents) = source.read_file(name) {
+            let file = dir.join(name);
+            std::fs::write(&file, contents).with_context(|| format!("Cannot write {}", file.display()))?;
+        }
+    }
+    Ok(dir)
+}
+
+/// Versions past the newest recorded one that the chain counter says exist,
+/// as an inclusive range: deployed from another machine or before recording.
+pub fn unrecorded(versions: &[DeployedVersion], chain_version: u32) -> Option<(u32, u32)> {
+    let newest = versions.iter().filter_map(|v| v.version).max().unwrap_or(0);
+    (chain_version > newest).then_some((newest + 1, chain_version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployed(version: Option<u32>) -> DeployedVersion {
+        DeployedVersion {
+            agent_address: "5Agent".to_string(),
+            version,
+            code_hash: code_hash("0x00"),
+            block_number: 10,
+            block_hash: "0x01".to_string(),
+            deployed_at: 0,
+            source: "embedded".to_string(),
+            snapshot: None,
+        }
+    }
+
+    #[test]
+    fn test_unrecorded_versions() {
+        assert_eq!(unrecorded(&[], 2), Some((1, 2)));
+        assert_eq!(unrecorded(&[deployed(Some(1))], 1), None);
+        assert_eq!(unrecorded(&[deployed(Some(1)), deployed(None)], 3), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_code_hash() {
+        assert_eq!(code_hash("0xdead"), code_hash("dead"));
+        assert_ne!(code_hash("0xdead"), code_hash("0xbeef"));
+        assert_eq!(code_hash("0x00").len(), 66);
+    }
+}
@@ -1,5 +1,6 @@
 //! Local wallet management for Proof of Lobster.
 
+use crate::config::AppConfig;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use sp_core::crypto::Ss58Codec;
@@ -16,12 +17,9 @@ pub struct WalletConfig {
 }
 
 impl WalletConfig {
-    /// Get the wallet file path.
-    pub fn path() -> PathBuf {
-        dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("proof-of-lobster")
-            .join("wallet.json")
+    /// Wallet file of the active network profile.
+    pub fn path(config: &AppConfig) -> PathBuf {
+        crate::config::config_dir().join(config.wallet_file.as_deref().unwrap_or("wallet.json"))
     }
 
     /// Generate a new wallet.
@@ -47,9 +45,9 @@ impl WalletConfig {
         })
     }
 
-    /// Load wallet from disk.
-    pub fn load() -> Result<Option<Self>> {
-        let path = Self::path();
+    /// Load the active profile's wallet from disk.
+    pub fn load(config: &AppConfig) -> Result<Option<Self>> {
+        let path = Self::path(config);
         if path.exists() {
             let contents = std::fs::read_to_string(&path)?;
             Ok(Some(serde_json::from_str(&contents)?))
@@ -58,9 +56,9 @@ impl WalletConfig {
         }
     }
 
-    /// Save wallet to disk.
-    pub fn save(&self) -> Result<()> {
-        let path = Self::path();
+    /// Save wallet to disk as the active profile's.
+    pub fn save(&self, config: &AppConfig) -> Result<()> {
+        let path = Self::path(config);
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
@@ -68,13 +66,13 @@ impl WalletConfig {
         Ok(())
     }
 
-    /// Load or generate a wallet.
-    pub fn load_or_generate() -> Result<Self> {
-        if let Some(wallet) = Self::load()? {
+    /// Load or generate the active profile's wallet.
+    pub fn load_or_generate(config: &AppConfig) -> Result<Self> {
+        if let Some(wallet) = Self::load(config)? {
             Ok(wallet)
         } else {
             let wallet = Self::generate()?;
-            wallet.save()?;
+            wallet.save(config)?;
             Ok(wallet)
         }
     }
@@ -88,11 +86,6 @@ impl WalletConfig {
 
     /// Get short display version of public key.
     pub fn short_address(&self) -> String {
-        let pk = &self.public_key;
-        if pk.len() > 16 {
-            format!("{}...{}", &pk[..8], &pk[pk.len() - 6..])
-        } else {
-            pk.clone()
-        }
+        crate::text::shorten_middle(&self.public_key, 8, 6)
     }
 }
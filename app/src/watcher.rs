@@ -0,0 +1,52 @@
+//! Watches a custom agent directory so files edited in another editor show
+//! up in the create wizard without leaving it.
+
+use crate::{app::AppMessage, screens::create::CreateMessage};
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use tokio::sync::mpsc;
+
+/// Live watch on one directory; dropping it stops watching.
+pub struct AgentDirWatcher {
+    pub dir: String,
+    _watcher: RecommendedWatcher,
+}
+
+impl AgentDirWatcher {
+    /// Send `AgentFilesChanged` whenever an agent file in `dir` is created, changed or removed.
+    pub fn start(dir: &str, tx: mpsc::Sender<AppMessage>) -> Result<Self> {
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            let changed = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_));
+            if changed && event.paths.iter().any(|p| is_agent_file(p)) {
+                // Runs on the watcher's own thread, outside the runtime
+                let _ = tx.blocking_send(CreateMessage::AgentFilesChanged.into());
+            }
+        })?;
+        // The directory rather than each file, so editors that save by
+        // writing a temp file and renaming it over the original are seen
+        watcher.watch(Path::new(dir), RecursiveMode::NonRecursive)?;
+        Ok(Self { dir: dir.to_string(), _watcher: watcher })
+    }
+}
+
+/// Files the wizard reads: the .ship program and the markdown it embeds.
+fn is_agent_file(path: &Path) -> bool {
+    matches!(path.extension().and_then(|e| e.to_str()), Some("ship" | "md"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_agent_files_count() {
+        assert!(is_agent_file(Path::new("/agents/lobster/SOUL.md")));
+        assert!(is_agent_file(Path::new("/agents/lobster/moltbook_agent.ship")));
+        assert!(!is_agent_file(Path::new("/agents/lobster/.SOUL.md.swp")));
+        assert!(!is_agent_file(Path::new("/agents/lobster/4913")));
+    }
+}
@@ -0,0 +1,144 @@
+//! Webhooks POSTed on deploys, finished or failed runs and a low agent
+//! balance, so a team can get alerts in Slack or Discord without watching
+//! the terminal. Best effort: a failed delivery is logged and dropped.
+
+use crate::config::AppConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Payload sent when a webhook has no template of its own. `text` is what
+/// Slack incoming webhooks display; the other fields are for tooling.
+pub const DEFAULT_TEMPLATE: &str =
+    r#"{"event": "{event}", "agent": "{agent}", "address": "{address}", "message": "{message}", "text": "{agent}: {message}"}"#;
+
+/// How long a receiver gets to answer before the delivery is dropped.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Something worth telling a webhook about.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    Deployed,
+    RunCompleted,
+    RunFailed,
+    LowBalance,
+}
+
+impl WebhookEvent {
+    pub fn name(self) -> &'static str {
+        match self {
+            WebhookEvent::Deployed => "deployed",
+            WebhookEvent::RunCompleted => "run_completed",
+            WebhookEvent::RunFailed => "run_failed",
+            WebhookEvent::LowBalance => "low_balance",
+        }
+    }
+}
+
+/// A URL to POST to, the events it wants and the JSON it expects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub url: String,
+    /// Events to send; empty sends all of them
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    /// JSON body with `{event}`, `{agent}`, `{address}` and `{message}`
+    /// placeholders (unset uses `DEFAULT_TEMPLATE`)
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+impl Webhook {
+    fn wants(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Fill in `template`, escaping each value for a JSON string, and check the
+/// result is JSON.
+pub fn render(template: &str, event: WebhookEvent, agent: &str, address: &str, message: &str) -> Result<serde_json::Value, String> {
+    let escape = |s: &str| {
+        let quoted = serde_json::Value::String(s.to_string()).to_string();
+        quoted[1..quoted.len() - 1].to_string()
+    };
+    let values = [
+        ("{event}", event.name().to_string()),
+        ("{agent}", escape(agent)),
+        ("{address}", escape(address)),
+        ("{message}", escape(message)),
+    ];
+    // One pass, so a value that happens to contain a placeholder stays as is
+    let mut body = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        body.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values.iter().find(|(placeholder, _)| rest.starts_with(placeholder)) {
+            Some((placeholder, value)) => {
+                body.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                body.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    body.push_str(rest);
+    serde_json::from_str(&body).map_err(|e| format!("template is not valid JSON: {}", e))
+}
+
+/// POST `event` to every configured webhook that wants it, in the background.
+pub fn send(config: &AppConfig, event: WebhookEvent, message: &str) {
+    let agent = config.agent_name.as_deref().unwrap_or("");
+    let address = config.agent_address.as_deref().unwrap_or("");
+    for hook in config.webhooks.iter().filter(|h| h.wants(event)) {
+        let template = hook.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+        let payload = match render(template, event, agent, address, message) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!(url = %hook.url, "Webhook skipped: {}", e);
+                continue;
+            }
+        };
+        let url = hook.url.clone();
+        crate::tasks::spawn(async move {
+            let result = reqwest::Client::new().post(&url).timeout(TIMEOUT).json(&payload).send().await;
+            match result {
+                Ok(resp) if resp.status().is_success() => tracing::debug!(%url, event = event.name(), "Webhook delivered"),
+                Ok(resp) => {
+                    let error = crate::client::error_body(resp).await;
+                    tracing::warn!(%url, event = event.name(), "Webhook rejected: {}", error);
+                }
+                Err(e) => tracing::warn!(%url, event = event.name(), "Webhook failed: {}", e),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_values() {
+        let payload = render(DEFAULT_TEMPLATE, WebhookEvent::RunFailed, "crab", "5Abc", "said \"no\"\nthen quit").unwrap();
+        assert_eq!(payload["event"], "run_failed");
+        assert_eq!(payload["address"], "5Abc");
+        assert_eq!(payload["text"], "crab: said \"no\"\nthen quit");
+
+        let discord = render(r#"{"content": "**{agent}** {event}"}"#, WebhookEvent::Deployed, "crab", "", "").unwrap();
+        assert_eq!(discord, serde_json::json!({ "content": "**crab** deployed" }));
+        assert_eq!(render(r#"{"m": "{agent}"}"#, WebhookEvent::Deployed, "{message}", "", "x").unwrap()["m"], "{message}");
+        assert!(render("{message}", WebhookEvent::Deployed, "", "", "x").is_err());
+    }
+
+    #[test]
+    fn test_events_filter() {
+        let hook: Webhook = serde_json::from_str(r#"{ "url": "http://x", "events": ["run_failed", "low_balance"] }"#).unwrap();
+        assert!(hook.wants(WebhookEvent::RunFailed));
+        assert!(!hook.wants(WebhookEvent::Deployed));
+        let all: Webhook = serde_json::from_str(r#"{ "url": "http://x" }"#).unwrap();
+        assert!(all.wants(WebhookEvent::Deployed));
+    }
+}
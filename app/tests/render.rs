@@ -0,0 +1,18 @@
+//! Render tests driven through keys, from outside the crate, with the
+//! harness in `proof_of_lobster::testing`.
+
+use crossterm::event::KeyCode;
+use proof_of_lobster::{app::AppScreen, testing::*};
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn test_email_login_typed() {
+    let mut app = app(config());
+    let (tx, _rx) = mpsc::channel(8);
+    app.handle_key(KeyCode::Char('1').into(), tx.clone()).await.unwrap();
+    assert_eq!(app.screen, AppScreen::EmailInput);
+    for c in "lobster@example.com".chars() {
+        app.handle_key(KeyCode::Char(c).into(), tx.clone()).await.unwrap();
+    }
+    assert_snapshot("email_input", &screen_text(&mut app));
+}